@@ -78,6 +78,7 @@ struct codec_load_info
 /** --- Main state control --- **/
 
 static int codec_type = AFMT_UNKNOWN; /* Codec type (C,A-) */
+static unsigned long codec_end_time = 0; /* Elapsed ending the track (0=none) */
 
 /* Private interfaces to main playback control */
 extern void audio_codec_update_elapsed(unsigned long elapsed);
@@ -364,6 +365,13 @@ static long codec_get_command_callback(intptr_t *param)
 {
     yield();
 
+    /* The track ends early, like at the start of its trailing silence */
+    if (UNLIKELY(codec_end_time) && ci.id3->elapsed >= codec_end_time)
+    {
+        codec_end_time = 0;
+        return CODEC_ACTION_HALT;
+    }
+
     if (LIKELY(queue_empty(&codec_queue)))
         return CODEC_ACTION_NULL; /* As you were */
 
@@ -752,3 +760,10 @@ int codec_loaded(void)
 {
     return codec_type;
 }
+
+/* Set the elapsed time at which the next track run ends, 0 plays it to the
+   end - on audio thread only, before codec_go() */
+void codec_set_end_time(unsigned long end_time)
+{
+    codec_end_time = end_time;
+}
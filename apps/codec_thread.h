@@ -51,6 +51,7 @@ size_t codec_finish_stream(void);
 #endif
 void codec_unload(void);
 int codec_loaded(void);
+void codec_set_end_time(unsigned long end_time);
 
 /* */
 
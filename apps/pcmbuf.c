@@ -86,6 +86,14 @@
 #define LOW_DATA            pcmbuf_watermark
 #endif
 
+#ifdef HAVE_CROSSFADE
+/* Provided by the library of the Rust server: whether the track change is
+ * crossfaded, given the decision of the crossfade setting. Its smart
+ * transitions keep consecutive tracks of an album gapless. */
+extern bool rb_transition_crossfade(bool crossfade, bool auto_skip)
+    __attribute__((weak));
+#endif
+
 /* Describes each audio packet - keep it small since there are many of them */
 struct chunkdesc
 {
@@ -744,6 +752,9 @@ void pcmbuf_start_track_change(enum pcm_track_change_type type)
                 crossfade = true;
                 break;
             }
+
+            if (rb_transition_crossfade)
+                crossfade = rb_transition_crossfade(crossfade, auto_skip);
         }
     }
 
@@ -119,6 +119,14 @@ extern struct event_queue audio_queue; /* from audio_thread.c */
 extern bool audio_is_initialized;      /* from audio_thread.c */
 extern struct codec_api ci;            /* from codecs.c */
 
+/* Provided by the library of the Rust server: where the codec starts and
+ * ends the track for its smart transitions, in ms, 0 keeping the bounds of
+ * the track. */
+extern void rb_transition_bounds(const char *path, bool auto_skip,
+                                 unsigned long *start_time,
+                                 unsigned long *end_time)
+    __attribute__((weak));
+
 /** Possible arrangements of the main buffer **/
 static enum audio_buffer_state
 {
@@ -1774,6 +1782,17 @@ static bool audio_start_codec(bool auto_skip)
         cur_id3->skip_resume_adjustments = true;
     }
 
+    /* Skip the leading silence of tracks started automatically and stop
+       before the trailing one */
+    unsigned long start_time = 0, end_time = 0;
+    if (rb_transition_bounds)
+        rb_transition_bounds(cur_id3->path, auto_skip, &start_time, &end_time);
+
+    if (start_time && !cur_id3->elapsed && !cur_id3->offset)
+        cur_id3->elapsed = start_time;
+
+    codec_set_end_time(end_time);
+
     /* Update the codec API with the metadata and track info */
     id3_write(CODEC_ID3, cur_id3);
 
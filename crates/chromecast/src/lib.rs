@@ -218,12 +218,24 @@ impl<'a> Player for Chromecast<'a> {
         self.cmd_tx
             .as_ref()
             .unwrap()
-            .send(CastPlayerCommand::Seek(seconds))?;
+            .send(CastPlayerCommand::Seek(seconds as f32))?;
         Ok(())
     }
 
-    async fn volume(&self, _level: f32) -> Result<(), Error> {
-        todo!()
+    async fn seek_ms(&self, position_ms: u32) -> Result<(), Error> {
+        self.cmd_tx
+            .as_ref()
+            .unwrap()
+            .send(CastPlayerCommand::Seek(position_ms as f32 / 1000.0))?;
+        Ok(())
+    }
+
+    async fn volume(&self, level: f32) -> Result<(), Error> {
+        self.cmd_tx
+            .as_ref()
+            .unwrap()
+            .send(CastPlayerCommand::Volume(level))?;
+        Ok(())
     }
 
     async fn load_tracks(&self, tracks: Vec<Track>, start_index: Option<i32>) -> Result<(), Error> {
@@ -351,7 +363,8 @@ pub enum CastPlayerCommand {
     Stop,
     Next,
     Previous,
-    Seek(i32),
+    Seek(f32),
+    Volume(f32),
     PlayNext(Track),
     Disconnect,
 }
@@ -624,8 +637,20 @@ impl<'a> CastPlayerInternal<'a> {
         Ok(())
     }
 
-    fn handle_seek(&self, _seconds: i32) -> Result<(), Error> {
-        todo!();
+    fn handle_seek(&self, seconds: f32) -> Result<(), Error> {
+        let (transport_id, media_session_id, _) = self.current_app_session()?;
+        self.cast_device.media.seek(
+            transport_id.as_str(),
+            media_session_id,
+            Some(seconds),
+            None,
+        )?;
+        Ok(())
+    }
+
+    fn handle_volume(&self, level: f32) -> Result<(), Error> {
+        self.cast_device.receiver.set_volume(level)?;
+        Ok(())
     }
 
     fn handle_play_next(&self, track: Track) -> Result<(), Error> {
@@ -706,6 +731,7 @@ impl<'a> CastPlayerInternal<'a> {
             CastPlayerCommand::Next => self.handle_next(),
             CastPlayerCommand::Previous => self.handle_previous(),
             CastPlayerCommand::Seek(seconds) => self.handle_seek(seconds),
+            CastPlayerCommand::Volume(level) => self.handle_volume(level),
             CastPlayerCommand::PlayNext(track) => self.handle_play_next(track),
             CastPlayerCommand::Disconnect => self.handle_disconnect(),
         }
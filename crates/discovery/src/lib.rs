@@ -1,7 +1,7 @@
 use async_stream::stream;
 use futures_util::Stream;
 use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
-use std::{env, thread};
+use std::{env, fs, path::Path, thread};
 
 pub const ROCKBOX_SERVICE_NAME: &'static str = "_rockbox._tcp.local.";
 pub const MUSIC_PLAYER_SERVICE_NAME: &'static str = "_music-player._tcp.local.";
@@ -22,36 +22,73 @@ impl MdnsResponder {
         }
     }
 
-    pub fn register_service(&mut self, name: &str, port: u16) {
-        let device_name = "rockbox";
+    pub fn register_service(&mut self, name: &str, port: u16, txt: &[&str]) {
+        let device_name = env::var("ROCKBOX_DEVICE_NAME").unwrap_or("rockbox".to_string());
         let device_name = format!("device_name={}", device_name);
+        let mut properties = vec!["path=/", device_name.as_str()];
+        properties.extend_from_slice(txt);
 
         self.svc.push(self.responder.register(
             "_rockbox._tcp".to_owned(),
             name.to_owned(),
             port,
-            &["path=/", device_name.as_str()],
+            &properties,
         ));
     }
 }
 
+/// Identifier of this install, generated on first run and kept in
+/// `~/.config/rockbox.org/device_id` so that it survives restarts.
+pub fn device_id() -> String {
+    let home = env::var("HOME").unwrap_or_default();
+    let path = format!("{}/.config/rockbox.org/device_id", home);
+
+    if let Ok(id) = fs::read_to_string(&path) {
+        let id = id.trim().to_string();
+        if !id.is_empty() {
+            return id;
+        }
+    }
+
+    let id = cuid::cuid1().unwrap_or_else(|_| format!("{:x}", md5::compute(home.as_bytes())));
+    if let Some(parent) = Path::new(&path).parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Err(e) = fs::write(&path, &id) {
+        eprintln!("Failed to save device id: {}", e);
+    }
+    id
+}
+
 pub fn register_services() {
-    let device_id = "123";
+    let device_id = device_id();
     let http_service = format!("http-{}", device_id);
     let graphql_service = format!("graphql-{}", device_id);
     let grpc_service = format!("grpc-{}", device_id);
     let mpd_service = format!("mpd-{}", device_id);
 
     thread::spawn(move || {
-        let http_port = env::var("ROCKBOX_HTTP_PORT").unwrap_or("6061".to_string());
+        let http_port = env::var("ROCKBOX_TCP_PORT").unwrap_or("6063".to_string());
         let graphql_port = env::var("ROCKBOX_GRAPHQL_PORT").unwrap_or("6062".to_string());
-        let grpc_port = env::var("ROCKBOX_PORT").unwrap_or("6063".to_string());
+        let grpc_port = env::var("ROCKBOX_PORT").unwrap_or("6061".to_string());
         let mpd_port = env::var("ROCKBOX_MPD_PORT").unwrap_or("6600".to_string());
+
+        // every service carries the ports of the others, so a peer only needs
+        // to resolve one of them to reach the whole instance.
+        let txt = [
+            format!("device_id={}", device_id),
+            format!("http_port={}", http_port),
+            format!("graphql_port={}", graphql_port),
+            format!("grpc_port={}", grpc_port),
+            format!("mpd_port={}", mpd_port),
+        ];
+        let txt: Vec<&str> = txt.iter().map(|s| s.as_str()).collect();
+
         let mut responder = MdnsResponder::new();
-        responder.register_service(&http_service, http_port.parse::<u16>().unwrap());
-        responder.register_service(&graphql_service, graphql_port.parse::<u16>().unwrap());
-        responder.register_service(&grpc_service, grpc_port.parse::<u16>().unwrap());
-        responder.register_service(&mpd_service, mpd_port.parse::<u16>().unwrap());
+        responder.register_service(&http_service, http_port.parse::<u16>().unwrap(), &txt);
+        responder.register_service(&graphql_service, graphql_port.parse::<u16>().unwrap(), &txt);
+        responder.register_service(&grpc_service, grpc_port.parse::<u16>().unwrap(), &txt);
+        responder.register_service(&mpd_service, mpd_port.parse::<u16>().unwrap(), &txt);
         loop {
             ::std::thread::sleep(::std::time::Duration::from_secs(10));
         }
@@ -0,0 +1,81 @@
+use async_graphql::*;
+use rockbox_service::RockboxService;
+
+use crate::schema::objects::bookmark::{Bookmark, BookmarkInput, Chapter, UpdateBookmarkInput};
+
+#[derive(Default)]
+pub struct BookmarkQuery;
+
+#[Object]
+impl BookmarkQuery {
+    /// The named bookmarks and the resume positions, the latest first.
+    async fn bookmarks(&self, ctx: &Context<'_>) -> Result<Vec<Bookmark>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        let bookmarks = service.bookmarks().await?;
+        Ok(bookmarks.into_iter().map(Into::into).collect())
+    }
+
+    async fn bookmark(&self, ctx: &Context<'_>, id: String) -> Result<Option<Bookmark>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.bookmark(&id).await?.map(Into::into))
+    }
+
+    async fn track_chapters(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<Option<Vec<Chapter>>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        let chapters = service.track_chapters(&id).await?;
+        Ok(chapters.map(|chapters| chapters.into_iter().map(Into::into).collect()))
+    }
+
+    /// The chapters of the track playing.
+    async fn current_chapters(&self, ctx: &Context<'_>) -> Result<Vec<Chapter>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        let chapters = service.current_chapters().await?;
+        Ok(chapters.into_iter().map(Into::into).collect())
+    }
+}
+
+#[derive(Default)]
+pub struct BookmarkMutation;
+
+#[Object]
+impl BookmarkMutation {
+    async fn create_bookmark(
+        &self,
+        ctx: &Context<'_>,
+        bookmark: BookmarkInput,
+    ) -> Result<Bookmark, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.create_bookmark(bookmark.into()).await?.into())
+    }
+
+    async fn update_bookmark(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        bookmark: UpdateBookmarkInput,
+    ) -> Result<Option<Bookmark>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        let bookmark = service.update_bookmark(&id, bookmark.into()).await?;
+        Ok(bookmark.map(Into::into))
+    }
+
+    async fn delete_bookmark(&self, ctx: &Context<'_>, id: String) -> Result<bool, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.delete_bookmark(&id).await?)
+    }
+
+    async fn play_bookmark(&self, ctx: &Context<'_>, id: String) -> Result<bool, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.play_bookmark(&id).await?)
+    }
+
+    /// Seeks to a chapter of the track playing.
+    async fn seek_chapter(&self, ctx: &Context<'_>, index: usize) -> Result<bool, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.seek_chapter(index).await?)
+    }
+}
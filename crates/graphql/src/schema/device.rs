@@ -1,6 +1,5 @@
 use async_graphql::*;
-
-use crate::rockbox_url;
+use rockbox_service::RockboxService;
 
 use super::objects::device::Device;
 
@@ -9,25 +8,14 @@ pub struct DeviceQuery;
 
 #[Object]
 impl DeviceQuery {
-    async fn devices(&self, _ctx: &Context<'_>) -> Result<Vec<Device>, Error> {
-        let client = reqwest::Client::new();
-        let url = format!("{}/devices", rockbox_url());
-        let response = client.get(&url).send().await?;
-        let response = response.json::<Vec<Device>>().await?;
-        Ok(response)
+    async fn devices(&self, ctx: &Context<'_>) -> Result<Vec<Device>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.devices().into_iter().map(Into::into).collect())
     }
 
-    async fn device(&self, _ctx: &Context<'_>, id: String) -> Result<Option<Device>, Error> {
-        let client = reqwest::Client::new();
-        let url = format!("{}/devices/{}", rockbox_url(), id);
-        let response = client.get(&url).send().await?;
-
-        if response.status() == 404 {
-            return Ok(None);
-        }
-
-        let response = response.json::<Option<Device>>().await?;
-        Ok(response)
+    async fn device(&self, ctx: &Context<'_>, id: String) -> Result<Option<Device>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.device(&id).map(Into::into))
     }
 }
 
@@ -36,17 +24,14 @@ pub struct DeviceMutation;
 
 #[Object]
 impl DeviceMutation {
-    async fn connect(&self, _ctx: &Context<'_>, id: String) -> Result<bool, Error> {
-        let client = reqwest::Client::new();
-        let url = format!("{}/devices/{}/connect", rockbox_url(), id);
-        client.put(&url).send().await?;
-        Ok(true)
+    async fn connect(&self, ctx: &Context<'_>, id: String) -> Result<bool, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.connect(&id).await?)
     }
 
-    async fn disconnect(&self, _ctx: &Context<'_>, id: String) -> Result<bool, Error> {
-        let client = reqwest::Client::new();
-        let url = format!("{}/devices/{}/disconnect", rockbox_url(), id);
-        client.put(&url).send().await?;
+    async fn disconnect(&self, ctx: &Context<'_>, _id: String) -> Result<bool, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        service.disconnect().await?;
         Ok(true)
     }
 }
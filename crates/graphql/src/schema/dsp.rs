@@ -0,0 +1,143 @@
+use async_graphql::*;
+use rockbox_service::RockboxService;
+use rockbox_types::dsp::{UpdateEqBand, UpdateEqualizer};
+
+use crate::schema::objects::{
+    eq_band_setting::{EqBandSetting, EqBandSettingInput},
+    equalizer::{EqComparison, EqDevicePreset, EqPreset, EqSlot, Equalizer, EqualizerInput},
+};
+
+#[derive(Default)]
+pub struct DspQuery;
+
+#[Object]
+impl DspQuery {
+    async fn equalizer(&self, ctx: &Context<'_>) -> Result<Equalizer, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.equalizer().into())
+    }
+
+    async fn eq_presets(&self, ctx: &Context<'_>) -> Result<Vec<EqPreset>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        let presets = service.eq_presets()?;
+        Ok(presets.into_iter().map(Into::into).collect())
+    }
+
+    async fn eq_preset(&self, ctx: &Context<'_>, name: String) -> Result<Option<EqPreset>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.eq_preset(&name)?.map(Into::into))
+    }
+
+    async fn eq_device_presets(&self, ctx: &Context<'_>) -> Result<Vec<EqDevicePreset>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        let mut devices: Vec<EqDevicePreset> = service
+            .eq_device_presets()?
+            .into_iter()
+            .map(|(device_id, preset)| EqDevicePreset { device_id, preset })
+            .collect();
+        devices.sort_by(|a, b| a.device_id.cmp(&b.device_id));
+        Ok(devices)
+    }
+
+    async fn eq_comparison(&self, ctx: &Context<'_>) -> Result<EqComparison, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.eq_comparison().into())
+    }
+}
+
+#[derive(Default)]
+pub struct DspMutation;
+
+#[Object]
+impl DspMutation {
+    async fn update_equalizer(
+        &self,
+        ctx: &Context<'_>,
+        enabled: Option<bool>,
+        precut: Option<i32>,
+        bands: Option<Vec<EqBandSettingInput>>,
+    ) -> Result<Equalizer, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        let equalizer = service.update_equalizer(UpdateEqualizer {
+            enabled,
+            precut,
+            bands: bands.map(|bands| bands.into_iter().map(Into::into).collect()),
+        })?;
+        Ok(equalizer.into())
+    }
+
+    async fn set_eq_band(
+        &self,
+        ctx: &Context<'_>,
+        band: usize,
+        cutoff: Option<i32>,
+        q: Option<i32>,
+        gain: Option<i32>,
+    ) -> Result<EqBandSetting, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        let setting = service.set_eq_band(band, UpdateEqBand { cutoff, q, gain })?;
+        Ok(setting.into())
+    }
+
+    async fn save_eq_preset(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+        equalizer: Option<EqualizerInput>,
+    ) -> Result<EqPreset, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        let preset = service.save_eq_preset(&name, equalizer.map(Into::into))?;
+        Ok(preset.into())
+    }
+
+    async fn import_eq_preset(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+        profile: String,
+    ) -> Result<EqPreset, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.import_eq_preset(&name, &profile)?.into())
+    }
+
+    async fn delete_eq_preset(&self, ctx: &Context<'_>, name: String) -> Result<bool, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.delete_eq_preset(&name)?)
+    }
+
+    async fn load_eq_preset(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+    ) -> Result<Option<Equalizer>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.load_eq_preset(&name)?.map(Into::into))
+    }
+
+    async fn bind_eq_preset(
+        &self,
+        ctx: &Context<'_>,
+        device_id: String,
+        preset: Option<String>,
+    ) -> Result<bool, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        service.bind_eq_preset(&device_id, preset.as_deref())?;
+        Ok(true)
+    }
+
+    async fn set_eq_slot(
+        &self,
+        ctx: &Context<'_>,
+        slot: EqSlot,
+        equalizer: EqualizerInput,
+    ) -> Result<EqComparison, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        let comparison = service.set_eq_slot(slot.into(), equalizer.into())?;
+        Ok(comparison.into())
+    }
+
+    async fn toggle_eq_slot(&self, ctx: &Context<'_>) -> Result<EqComparison, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.toggle_eq_slot()?.into())
+    }
+}
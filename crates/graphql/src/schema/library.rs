@@ -1,42 +1,115 @@
 use async_graphql::*;
-use rockbox_library::{entity::favourites::Favourites, repo};
-use rockbox_search::{search_entities, Indexes};
+use rockbox_library::{entity::favourites::Favourites, list, replaygain::AnalysisOptions, repo};
+use rockbox_search::{
+    query::{SearchOptions, SortBy, DEFAULT_LIMIT},
+    search_library,
+    suggestion::autocomplete,
+    Indexes,
+};
+use rockbox_service::{remote::RemoteQuery, RockboxService};
 use sqlx::{Pool, Sqlite};
 
-use crate::{rockbox_url, schema::objects::track::Track};
+use crate::schema::objects::track::Track;
 
-use super::objects::{album::Album, artist::Artist, search::SearchResults};
+use super::objects::{
+    album::Album,
+    artist::Artist,
+    genre::Genre,
+    list_options::ListOptions,
+    replaygain::{ReplayGain, ReplayGainProgress},
+    search::SearchResults,
+    suggestion::Suggestion,
+};
 
 #[derive(Default)]
 pub struct LibraryQuery;
 
 #[Object]
 impl LibraryQuery {
-    async fn albums(&self, ctx: &Context<'_>) -> Result<Vec<Album>, Error> {
+    async fn albums(
+        &self,
+        ctx: &Context<'_>,
+        options: Option<ListOptions>,
+    ) -> Result<Vec<Album>, Error> {
         let pool = ctx.data::<Pool<Sqlite>>()?;
-        let results = repo::album::all(pool.clone()).await?;
+        let options: list::ListOptions = options.unwrap_or_default().into();
+        let mut results = repo::album::list(pool.clone(), &options).await?;
+        // remote libraries can't be paginated nor filtered, only merge them in full listings
+        if !options.is_paginated() && !options.is_filtered() {
+            let service = ctx.data::<RockboxService>()?;
+            let remote = service.query_remote_sources(RemoteQuery::Albums).await;
+            results.extend(remote.albums);
+        }
         Ok(results.into_iter().map(Into::into).collect())
     }
 
-    async fn artists(&self, ctx: &Context<'_>) -> Result<Vec<Artist>, Error> {
+    async fn album_count(
+        &self,
+        ctx: &Context<'_>,
+        options: Option<ListOptions>,
+    ) -> Result<i64, Error> {
         let pool = ctx.data::<Pool<Sqlite>>()?;
-        let results = repo::artist::all(pool.clone()).await?;
+        let options = options.unwrap_or_default().into();
+        Ok(repo::album::count(pool.clone(), &options).await?)
+    }
+
+    async fn artists(
+        &self,
+        ctx: &Context<'_>,
+        options: Option<ListOptions>,
+    ) -> Result<Vec<Artist>, Error> {
+        let pool = ctx.data::<Pool<Sqlite>>()?;
+        let options = options.unwrap_or_default().into();
+        let results = repo::artist::list(pool.clone(), &options).await?;
         Ok(results.into_iter().map(Into::into).collect())
     }
 
-    async fn tracks(&self, ctx: &Context<'_>) -> Result<Vec<Track>, Error> {
+    async fn artist_count(
+        &self,
+        ctx: &Context<'_>,
+        options: Option<ListOptions>,
+    ) -> Result<i64, Error> {
         let pool = ctx.data::<Pool<Sqlite>>()?;
-        let results = repo::track::all(pool.clone()).await?;
+        let options = options.unwrap_or_default().into();
+        Ok(repo::artist::count(pool.clone(), &options).await?)
+    }
+
+    async fn tracks(
+        &self,
+        ctx: &Context<'_>,
+        options: Option<ListOptions>,
+    ) -> Result<Vec<Track>, Error> {
+        let pool = ctx.data::<Pool<Sqlite>>()?;
+        let options: list::ListOptions = options.unwrap_or_default().into();
+        let mut results = repo::track::list(pool.clone(), &options).await?;
+        // remote libraries can't be paginated nor filtered, only merge them in full listings
+        if !options.is_paginated() && !options.is_filtered() {
+            let service = ctx.data::<RockboxService>()?;
+            let remote = service.query_remote_sources(RemoteQuery::Tracks).await;
+            results.extend(remote.tracks);
+        }
         Ok(results.into_iter().map(Into::into).collect())
     }
 
+    async fn track_count(
+        &self,
+        ctx: &Context<'_>,
+        options: Option<ListOptions>,
+    ) -> Result<i64, Error> {
+        let pool = ctx.data::<Pool<Sqlite>>()?;
+        let options = options.unwrap_or_default().into();
+        Ok(repo::track::count(pool.clone(), &options).await?)
+    }
+
     async fn album(&self, ctx: &Context<'_>, id: String) -> Result<Option<Album>, Error> {
         let pool = ctx.data::<Pool<Sqlite>>()?;
         let results = repo::album::find(pool.clone(), &id).await?;
         let tracks = repo::album_tracks::find_by_album(pool.clone(), &id).await?;
+        let editions = repo::album::find_editions(pool.clone(), &id).await?;
         let mut album: Option<Album> = results.map(Into::into);
         if let Some(album) = album.as_mut() {
             album.tracks = tracks.into_iter().map(Into::into).collect();
+            album.editions = editions.into_iter().map(Into::into).collect();
         }
         Ok(album)
     }
@@ -47,15 +120,36 @@ impl LibraryQuery {
         let mut artist: Option<Artist> = results.map(Into::into);
         let albums = repo::album::find_by_artist(pool.clone(), &id).await?;
         let tracks = repo::artist_tracks::find_by_artist(pool.clone(), &id).await?;
+        let appears_on = repo::album::find_appears_on(pool.clone(), &id).await?;
 
         if let Some(artist) = artist.as_mut() {
             artist.albums = albums.into_iter().map(Into::into).collect();
             artist.tracks = tracks.into_iter().map(Into::into).collect();
+            artist.appears_on = appears_on.into_iter().map(Into::into).collect();
         }
 
         Ok(artist)
     }
 
+    async fn genres(&self, ctx: &Context<'_>) -> Result<Vec<Genre>, Error> {
+        let pool = ctx.data::<Pool<Sqlite>>()?;
+        let results = repo::genre::all(pool.clone()).await?;
+        Ok(results.into_iter().map(Into::into).collect())
+    }
+
+    async fn genre(&self, ctx: &Context<'_>, id: String) -> Result<Option<Genre>, Error> {
+        let pool = ctx.data::<Pool<Sqlite>>()?;
+        let results = repo::genre::find(pool.clone(), &id).await?;
+        let mut genre: Option<Genre> = results.map(Into::into);
+        let tracks = repo::genre_tracks::find_by_genre(pool.clone(), &id).await?;
+
+        if let Some(genre) = genre.as_mut() {
+            genre.tracks = tracks.into_iter().map(Into::into).collect();
+        }
+
+        Ok(genre)
+    }
+
     async fn track(&self, ctx: &Context<'_>, id: String) -> Result<Option<Track>, Error> {
         let pool = ctx.data::<Pool<Sqlite>>()?;
         let results = repo::track::find(pool.clone(), &id).await?;
@@ -74,41 +168,64 @@ impl LibraryQuery {
         Ok(results.into_iter().map(Into::into).collect())
     }
 
-    async fn search(&self, ctx: &Context<'_>, term: String) -> Result<SearchResults, Error> {
+    async fn search(
+        &self,
+        ctx: &Context<'_>,
+        term: String,
+        offset: Option<usize>,
+        limit: Option<usize>,
+        sort: Option<String>,
+        descending: Option<bool>,
+    ) -> Result<SearchResults, Error> {
+        let indexes = ctx.data::<Indexes>()?;
+        let options = SearchOptions {
+            offset: offset.unwrap_or(0),
+            limit: limit.unwrap_or(DEFAULT_LIMIT),
+            sort: sort.as_deref().and_then(SortBy::parse).unwrap_or_default(),
+            descending: descending.unwrap_or(false),
+        };
+        let mut results: rockbox_types::SearchResults =
+            search_library(indexes, &term, &options)?.into();
+
+        // remote libraries aren't paginated, only merge them in the first page
+        if options.offset == 0 {
+            let service = ctx.data::<RockboxService>()?;
+            let remote = service
+                .query_remote_sources(RemoteQuery::Search(term))
+                .await;
+            results
+                .albums
+                .extend(remote.albums.into_iter().map(|x| x.into()));
+            results
+                .tracks
+                .extend(remote.tracks.into_iter().map(|x| x.into()));
+        }
+        Ok(results.into())
+    }
+
+    async fn autocomplete(
+        &self,
+        ctx: &Context<'_>,
+        text: String,
+        limit: Option<usize>,
+    ) -> Result<Vec<Suggestion>, Error> {
         let indexes = ctx.data::<Indexes>()?;
-        let albums = search_entities(
-            &indexes.albums,
-            &term,
-            &rockbox_search::album::Album::default(),
-        )?;
-        let artists = search_entities(
-            &indexes.artists,
-            &term,
-            &rockbox_search::artist::Artist::default(),
-        )?;
-        let tracks = search_entities(
-            &indexes.tracks,
-            &term,
-            &rockbox_search::track::Track::default(),
-        )?;
-        let liked_tracks = search_entities(
-            &indexes.liked_tracks,
-            &term,
-            &rockbox_search::liked_track::LikedTrack::default(),
-        )?;
-        let liked_albums = search_entities(
-            &indexes.liked_albums,
-            &term,
-            &rockbox_search::liked_album::LikedAlbum::default(),
-        )?;
-
-        Ok(SearchResults {
-            albums: albums.into_iter().map(|(_, x)| x.into()).collect(),
-            artists: artists.into_iter().map(|(_, x)| x.into()).collect(),
-            tracks: tracks.into_iter().map(|(_, x)| x.into()).collect(),
-            liked_tracks: liked_tracks.into_iter().map(|(_, x)| x.into()).collect(),
-            liked_albums: liked_albums.into_iter().map(|(_, x)| x.into()).collect(),
-        })
+        let results = autocomplete(&indexes.suggestions, &text, limit.unwrap_or(DEFAULT_LIMIT))?;
+        Ok(results.into_iter().map(Into::into).collect())
+    }
+
+    async fn track_replaygain(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<Option<ReplayGain>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.track_replaygain(&id).await?.map(Into::into))
+    }
+
+    async fn replaygain_progress(&self, ctx: &Context<'_>) -> Result<ReplayGainProgress, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.replaygain_progress().into())
     }
 }
 
@@ -185,9 +302,22 @@ impl LibraryMutation {
     }
 
     async fn scan_library(&self, ctx: &Context<'_>) -> Result<i32, Error> {
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let url = format!("{}/scan-library", rockbox_url());
-        client.put(&url).send().await?;
+        let service = ctx.data::<RockboxService>()?;
+        service.scan_library(None).await?;
         Ok(0)
     }
+
+    async fn analyze_replaygain(
+        &self,
+        ctx: &Context<'_>,
+        force: Option<bool>,
+        write_tags: Option<bool>,
+    ) -> Result<ReplayGainProgress, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        service.analyze_replaygain(AnalysisOptions {
+            force: force.unwrap_or_default(),
+            write_tags: write_tags.unwrap_or_default(),
+        })?;
+        Ok(service.replaygain_progress().into())
+    }
 }
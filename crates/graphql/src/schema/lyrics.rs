@@ -0,0 +1,88 @@
+use async_graphql::*;
+use futures_util::{Stream, StreamExt};
+use rockbox_library::lrc;
+use sqlx::{Pool, Sqlite};
+
+use crate::{
+    schema::objects::{
+        lyrics::{CurrentLyricLine, Lyrics},
+        track::TrackPosition,
+    },
+    simplebroker::SimpleBroker,
+};
+
+#[derive(Default)]
+pub struct LyricsQuery;
+
+#[Object]
+impl LyricsQuery {
+    async fn lyrics(&self, ctx: &Context<'_>, track_id: String) -> Result<Option<Lyrics>, Error> {
+        let pool = ctx.data::<Pool<Sqlite>>()?;
+        let lyrics = lrc::load(pool.clone(), &track_id).await?;
+        Ok(lyrics.map(Into::into))
+    }
+}
+
+#[derive(Default)]
+pub struct LyricsSubscription;
+
+#[Subscription]
+impl LyricsSubscription {
+    async fn current_lyric_line(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<impl Stream<Item = CurrentLyricLine>, Error> {
+        let pool = ctx.data::<Pool<Sqlite>>()?;
+        Ok(current_lyric_lines(pool.clone()))
+    }
+}
+
+/// Follows the elapsed time of the current track and emits its lyric line
+/// each time it changes, tracks without synchronized lyrics emit nothing.
+pub fn current_lyric_lines(pool: Pool<Sqlite>) -> impl Stream<Item = CurrentLyricLine> {
+    let mut positions = SimpleBroker::<TrackPosition>::subscribe();
+    async_stream::stream! {
+        let mut lines: Vec<lrc::LyricLine> = vec![];
+        let mut loaded: Option<String> = None;
+        let mut current: Option<usize> = None;
+
+        while let Some(position) = positions.next().await {
+            let Some(track_id) = position.track_id else {
+                continue;
+            };
+
+            if loaded.as_ref() != Some(&track_id) {
+                lines = match lrc::load(pool.clone(), &track_id).await {
+                    Ok(lyrics) => lrc::parse(
+                        lyrics
+                            .and_then(|lyrics| lyrics.synced)
+                            .as_deref()
+                            .unwrap_or_default(),
+                    ),
+                    Err(e) => {
+                        eprintln!("Error loading lyrics: {:?}", e);
+                        vec![]
+                    }
+                };
+                loaded = Some(track_id.clone());
+                current = None;
+            }
+
+            let index = lrc::current_line(&lines, position.elapsed);
+            if index == current {
+                continue;
+            }
+            current = index;
+
+            if let Some(index) = index {
+                yield CurrentLyricLine {
+                    track_id,
+                    index: index as u32,
+                    time: lines[index].time,
+                    text: lines[index].text.clone(),
+                    next_time: lines.get(index + 1).map(|line| line.time),
+                };
+            }
+        }
+    }
+}
@@ -1,31 +1,49 @@
 use async_graphql::{MergedObject, MergedSubscription};
+use bookmark::{BookmarkMutation, BookmarkQuery};
 use browse::BrowseQuery;
 use device::{DeviceMutation, DeviceQuery};
+use dsp::{DspMutation, DspQuery};
 use library::{LibraryMutation, LibraryQuery};
+use lyrics::{LyricsQuery, LyricsSubscription};
 use playback::{PlaybackMutation, PlaybackQuery, PlaybackSubscription};
 use playlist::{PlaylistMutation, PlaylistQuery, PlaylistSubscription};
+use podcast::{PodcastMutation, PodcastQuery};
+use radio::{RadioMutation, RadioQuery};
+use scheduler::{SchedulerMutation, SchedulerQuery};
 use settings::{SettingsMutation, SettingsQuery};
 use sound::{SoundMutation, SoundQuery};
 use system::SystemQuery;
 
+pub mod bookmark;
 pub mod browse;
 pub mod device;
+pub mod dsp;
 pub mod library;
+pub mod lyrics;
 pub mod metadata;
 pub mod objects;
 pub mod playback;
 pub mod playlist;
+pub mod podcast;
+pub mod radio;
+pub mod scheduler;
 pub mod settings;
 pub mod sound;
 pub mod system;
 
 #[derive(MergedObject, Default)]
 pub struct Query(
+    BookmarkQuery,
     BrowseQuery,
     DeviceQuery,
+    DspQuery,
     LibraryQuery,
+    LyricsQuery,
     PlaybackQuery,
     PlaylistQuery,
+    PodcastQuery,
+    RadioQuery,
+    SchedulerQuery,
     SoundQuery,
     SettingsQuery,
     SystemQuery,
@@ -33,40 +51,22 @@ pub struct Query(
 
 #[derive(MergedObject, Default)]
 pub struct Mutation(
+    BookmarkMutation,
     DeviceMutation,
+    DspMutation,
     PlaybackMutation,
     PlaylistMutation,
+    PodcastMutation,
+    RadioMutation,
+    SchedulerMutation,
     SoundMutation,
     LibraryMutation,
     SettingsMutation,
 );
 
 #[derive(MergedSubscription, Default)]
-pub struct Subscription(PlaybackSubscription, PlaylistSubscription);
-
-#[macro_export]
-macro_rules! check_and_load_player {
-    ($client:expr, $tracks:expr, $shuffle:expr) => {
-        let response = $client
-            .get(&format!("{}/player", rockbox_url()))
-            .send()
-            .await?;
-        let player = response.json::<Device>().await?;
-
-        // connected to a player
-        if !player.host.is_empty() && player.port != 0 {
-            let client = reqwest::Client::new();
-            let body = serde_json::json!({
-                "tracks": $tracks,
-                "shuffle": $shuffle,
-            });
-
-            client
-                .put(&format!("{}/player/load", rockbox_url()))
-                .json(&body)
-                .send()
-                .await?;
-            return Ok(0);
-        }
-    };
-}
+pub struct Subscription(
+    PlaybackSubscription,
+    PlaylistSubscription,
+    LyricsSubscription,
+);
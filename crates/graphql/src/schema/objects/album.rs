@@ -19,6 +19,10 @@ pub struct Album {
     pub md5: String,
     pub artist_id: String,
     pub tracks: Vec<Track>,
+    pub musicbrainz_id: Option<String>,
+    pub musicbrainz_release_group_id: Option<String>,
+    /// The other editions of the album, filled by the `album` query.
+    pub editions: Vec<Album>,
 }
 
 #[Object]
@@ -58,6 +62,18 @@ impl Album {
     async fn tracks(&self) -> Vec<Track> {
         self.tracks.clone()
     }
+
+    async fn musicbrainz_id(&self) -> Option<&str> {
+        self.musicbrainz_id.as_deref()
+    }
+
+    async fn musicbrainz_release_group_id(&self) -> Option<&str> {
+        self.musicbrainz_release_group_id.as_deref()
+    }
+
+    async fn editions(&self) -> Vec<Album> {
+        self.editions.clone()
+    }
 }
 
 impl From<rockbox_library::entity::album::Album> for Album {
@@ -72,6 +88,9 @@ impl From<rockbox_library::entity::album::Album> for Album {
             md5: album.md5,
             artist_id: album.artist_id,
             tracks: vec![],
+            musicbrainz_id: album.musicbrainz_id,
+            musicbrainz_release_group_id: album.musicbrainz_release_group_id,
+            editions: vec![],
         }
     }
 }
@@ -88,6 +107,9 @@ impl From<rockbox_search::album::Album> for Album {
             md5: album.md5,
             artist_id: album.artist_id,
             tracks: vec![],
+            musicbrainz_id: None,
+            musicbrainz_release_group_id: None,
+            editions: vec![],
         }
     }
 }
@@ -104,6 +126,9 @@ impl From<rockbox_search::liked_album::LikedAlbum> for Album {
             md5: album.md5,
             artist_id: album.artist_id,
             tracks: vec![],
+            musicbrainz_id: None,
+            musicbrainz_release_group_id: None,
+            editions: vec![],
         }
     }
 }
@@ -15,6 +15,8 @@ pub struct Artist {
     pub image: Option<String>,
     pub tracks: Vec<Track>,
     pub albums: Vec<Album>,
+    pub appears_on: Vec<Album>,
+    pub musicbrainz_id: Option<String>,
 }
 
 #[Object]
@@ -42,6 +44,14 @@ impl Artist {
     async fn albums(&self) -> Vec<Album> {
         self.albums.clone()
     }
+
+    async fn appears_on(&self) -> Vec<Album> {
+        self.appears_on.clone()
+    }
+
+    async fn musicbrainz_id(&self) -> Option<&str> {
+        self.musicbrainz_id.as_deref()
+    }
 }
 
 impl From<rockbox_library::entity::artist::Artist> for Artist {
@@ -53,6 +63,8 @@ impl From<rockbox_library::entity::artist::Artist> for Artist {
             image: artist.image,
             tracks: vec![],
             albums: vec![],
+            appears_on: vec![],
+            musicbrainz_id: artist.musicbrainz_id,
         }
     }
 }
@@ -66,6 +78,8 @@ impl From<rockbox_search::artist::Artist> for Artist {
             image: artist.image,
             tracks: vec![],
             albums: vec![],
+            appears_on: vec![],
+            musicbrainz_id: None,
         }
     }
 }
@@ -0,0 +1,119 @@
+use async_graphql::*;
+use rockbox_library::chapters;
+use rockbox_types::bookmark;
+use serde::{Deserialize, Serialize};
+
+#[derive(Enum, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BookmarkSource {
+    #[default]
+    Track,
+    Album,
+    Directory,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct Bookmark {
+    pub id: String,
+    /// None for the resume positions.
+    pub name: Option<String>,
+    pub source: BookmarkSource,
+    pub source_id: String,
+    pub path: String,
+    pub track_id: Option<String>,
+    /// Milliseconds.
+    pub elapsed: u64,
+    /// Unix timestamps in seconds.
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, InputObject)]
+pub struct BookmarkInput {
+    pub name: String,
+    /// The track playing when not given.
+    pub path: Option<String>,
+    pub elapsed: Option<u64>,
+    pub source: Option<BookmarkSource>,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, InputObject)]
+pub struct UpdateBookmarkInput {
+    pub name: Option<String>,
+    pub elapsed: Option<u64>,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct Chapter {
+    pub index: usize,
+    pub title: String,
+    /// Milliseconds.
+    pub start: u64,
+    pub end: u64,
+}
+
+impl From<bookmark::BookmarkSource> for BookmarkSource {
+    fn from(source: bookmark::BookmarkSource) -> Self {
+        match source {
+            bookmark::BookmarkSource::Track => BookmarkSource::Track,
+            bookmark::BookmarkSource::Album => BookmarkSource::Album,
+            bookmark::BookmarkSource::Directory => BookmarkSource::Directory,
+        }
+    }
+}
+
+impl From<BookmarkSource> for bookmark::BookmarkSource {
+    fn from(source: BookmarkSource) -> Self {
+        match source {
+            BookmarkSource::Track => bookmark::BookmarkSource::Track,
+            BookmarkSource::Album => bookmark::BookmarkSource::Album,
+            BookmarkSource::Directory => bookmark::BookmarkSource::Directory,
+        }
+    }
+}
+
+impl From<bookmark::Bookmark> for Bookmark {
+    fn from(bookmark: bookmark::Bookmark) -> Self {
+        Self {
+            id: bookmark.id,
+            name: bookmark.name,
+            source: bookmark.source.into(),
+            source_id: bookmark.source_id,
+            path: bookmark.path,
+            track_id: bookmark.track_id,
+            elapsed: bookmark.elapsed,
+            created_at: bookmark.created_at,
+            updated_at: bookmark.updated_at,
+        }
+    }
+}
+
+impl From<BookmarkInput> for bookmark::NewBookmark {
+    fn from(bookmark: BookmarkInput) -> Self {
+        Self {
+            name: bookmark.name,
+            path: bookmark.path,
+            elapsed: bookmark.elapsed,
+            source: bookmark.source.map(Into::into),
+        }
+    }
+}
+
+impl From<UpdateBookmarkInput> for bookmark::UpdateBookmark {
+    fn from(update: UpdateBookmarkInput) -> Self {
+        Self {
+            name: update.name,
+            elapsed: update.elapsed,
+        }
+    }
+}
+
+impl From<chapters::Chapter> for Chapter {
+    fn from(chapter: chapters::Chapter) -> Self {
+        Self {
+            index: chapter.index,
+            title: chapter.title,
+            start: chapter.start,
+            end: chapter.end,
+        }
+    }
+}
@@ -67,3 +67,22 @@ impl Device {
         self.is_current_device
     }
 }
+
+impl From<rockbox_types::device::Device> for Device {
+    fn from(device: rockbox_types::device::Device) -> Self {
+        Self {
+            id: device.id,
+            name: device.name,
+            host: device.host,
+            ip: device.ip,
+            port: device.port,
+            service: device.service,
+            app: device.app,
+            is_connected: device.is_connected,
+            base_url: device.base_url,
+            is_cast_device: device.is_cast_device,
+            is_source_device: device.is_source_device,
+            is_current_device: device.is_current_device,
+        }
+    }
+}
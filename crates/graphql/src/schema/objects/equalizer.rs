@@ -0,0 +1,124 @@
+use async_graphql::*;
+use rockbox_types::dsp;
+use serde::{Deserialize, Serialize};
+
+use super::eq_band_setting::{EqBandSetting, EqBandSettingInput};
+
+#[derive(Default, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct Equalizer {
+    pub enabled: bool,
+    /// Tenths of dB the volume is lowered by before the equalizer.
+    pub precut: i32,
+    pub bands: Vec<EqBandSetting>,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, InputObject)]
+pub struct EqualizerInput {
+    pub enabled: bool,
+    pub precut: i32,
+    pub bands: Vec<EqBandSettingInput>,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct EqPreset {
+    pub name: String,
+    pub equalizer: Equalizer,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct EqDevicePreset {
+    pub device_id: String,
+    pub preset: String,
+}
+
+#[derive(Enum, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EqSlot {
+    #[default]
+    A,
+    B,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct EqComparison {
+    /// The side being heard.
+    pub slot: EqSlot,
+    pub a: Equalizer,
+    pub b: Equalizer,
+}
+
+impl From<rockbox_types::EqBandSetting> for EqBandSetting {
+    fn from(setting: rockbox_types::EqBandSetting) -> Self {
+        Self {
+            cutoff: setting.cutoff,
+            q: setting.q,
+            gain: setting.gain,
+        }
+    }
+}
+
+impl From<EqBandSettingInput> for rockbox_types::EqBandSetting {
+    fn from(setting: EqBandSettingInput) -> Self {
+        Self {
+            cutoff: setting.cutoff,
+            q: setting.q,
+            gain: setting.gain,
+        }
+    }
+}
+
+impl From<dsp::Equalizer> for Equalizer {
+    fn from(equalizer: dsp::Equalizer) -> Self {
+        Self {
+            enabled: equalizer.enabled,
+            precut: equalizer.precut,
+            bands: equalizer.bands.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<EqualizerInput> for dsp::Equalizer {
+    fn from(equalizer: EqualizerInput) -> Self {
+        Self {
+            enabled: equalizer.enabled,
+            precut: equalizer.precut,
+            bands: equalizer.bands.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<dsp::EqPreset> for EqPreset {
+    fn from(preset: dsp::EqPreset) -> Self {
+        Self {
+            name: preset.name,
+            equalizer: preset.equalizer.into(),
+        }
+    }
+}
+
+impl From<dsp::EqSlot> for EqSlot {
+    fn from(slot: dsp::EqSlot) -> Self {
+        match slot {
+            dsp::EqSlot::A => EqSlot::A,
+            dsp::EqSlot::B => EqSlot::B,
+        }
+    }
+}
+
+impl From<EqSlot> for dsp::EqSlot {
+    fn from(slot: EqSlot) -> Self {
+        match slot {
+            EqSlot::A => dsp::EqSlot::A,
+            EqSlot::B => dsp::EqSlot::B,
+        }
+    }
+}
+
+impl From<dsp::EqComparison> for EqComparison {
+    fn from(comparison: dsp::EqComparison) -> Self {
+        Self {
+            slot: comparison.slot.into(),
+            a: comparison.a.into(),
+            b: comparison.b.into(),
+        }
+    }
+}
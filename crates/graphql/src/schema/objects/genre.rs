@@ -0,0 +1,59 @@
+use super::track::Track;
+use async_graphql::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Clone, Serialize, Deserialize)]
+pub struct Genre {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub tracks: Vec<Track>,
+}
+
+#[Object]
+impl Genre {
+    async fn id(&self) -> &str {
+        &self.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    async fn image(&self) -> Option<&str> {
+        self.image.as_deref()
+    }
+
+    async fn tracks(&self) -> Vec<Track> {
+        self.tracks.clone()
+    }
+}
+
+impl From<rockbox_library::entity::genre::Genre> for Genre {
+    fn from(genre: rockbox_library::entity::genre::Genre) -> Self {
+        Self {
+            id: genre.id,
+            name: genre.name,
+            description: genre.description,
+            image: genre.image,
+            tracks: vec![],
+        }
+    }
+}
+
+impl From<rockbox_search::genre::Genre> for Genre {
+    fn from(genre: rockbox_search::genre::Genre) -> Self {
+        Self {
+            id: genre.id,
+            name: genre.name,
+            description: genre.description,
+            image: genre.image,
+            tracks: vec![],
+        }
+    }
+}
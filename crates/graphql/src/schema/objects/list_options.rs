@@ -0,0 +1,36 @@
+use async_graphql::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Clone, Serialize, Deserialize, InputObject)]
+pub struct ListOptions {
+    pub offset: Option<u32>,
+    pub limit: Option<u32>,
+    /// One of "title", "artist", "album", "year", "added", "length" or
+    /// "plays".
+    pub sort: Option<String>,
+    pub descending: Option<bool>,
+    pub artist_id: Option<String>,
+    pub album_id: Option<String>,
+    pub genre_id: Option<String>,
+    pub year_from: Option<u32>,
+    pub year_to: Option<u32>,
+    /// Lists a single album per MusicBrainz release group.
+    pub group_editions: Option<bool>,
+}
+
+impl From<ListOptions> for rockbox_library::list::ListOptions {
+    fn from(options: ListOptions) -> Self {
+        Self {
+            offset: options.offset,
+            limit: options.limit,
+            sort: options.sort,
+            descending: options.descending.unwrap_or(false),
+            artist_id: options.artist_id,
+            album_id: options.album_id,
+            genre_id: options.genre_id,
+            year_from: options.year_from,
+            year_to: options.year_to,
+            group_editions: options.group_editions.unwrap_or(false),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use async_graphql::*;
+use rockbox_library::lrc;
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct LyricLine {
+    /// Milliseconds since the start of the track.
+    pub time: u64,
+    pub text: String,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct Lyrics {
+    pub track_id: String,
+    pub text: String,
+    pub synced: bool,
+    /// Empty when the lyrics aren't synchronized.
+    pub lines: Vec<LyricLine>,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct CurrentLyricLine {
+    pub track_id: String,
+    pub index: u32,
+    pub time: u64,
+    pub text: String,
+    /// When the next line starts, none on the last line.
+    pub next_time: Option<u64>,
+}
+
+impl From<lrc::LyricLine> for LyricLine {
+    fn from(line: lrc::LyricLine) -> Self {
+        Self {
+            time: line.time,
+            text: line.text,
+        }
+    }
+}
+
+impl From<rockbox_library::entity::lyrics::Lyrics> for Lyrics {
+    fn from(lyrics: rockbox_library::entity::lyrics::Lyrics) -> Self {
+        let lines = lrc::parse(lyrics.synced.as_deref().unwrap_or_default());
+        Self {
+            track_id: lyrics.track_id,
+            text: lyrics.lyrics,
+            synced: !lines.is_empty(),
+            lines: lines.into_iter().map(Into::into).collect(),
+        }
+    }
+}
@@ -1,15 +1,28 @@
 pub mod album;
 pub mod artist;
 pub mod audio_status;
+pub mod bookmark;
 pub mod compressor_settings;
 pub mod device;
 pub mod entry;
 pub mod eq_band_setting;
+pub mod equalizer;
+pub mod genre;
+pub mod list_options;
+pub mod lyrics;
 pub mod new_global_settings;
+pub mod playback_settings;
 pub mod playlist;
+pub mod podcast;
+pub mod radio;
+pub mod replaygain;
 pub mod replaygain_settings;
+pub mod resume_settings;
+pub mod scheduler;
 pub mod search;
 pub mod settings_list;
+pub mod suggestion;
 pub mod system_status;
 pub mod track;
+pub mod transition_settings;
 pub mod user_settings;
@@ -0,0 +1,41 @@
+use async_graphql::*;
+use serde::{Deserialize, Serialize};
+
+/// Subset of the settings that players mirror (volume, shuffle, repeat and speed),
+/// published by the broker so frontends don't have to poll the whole settings.
+#[derive(Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlaybackSettings {
+    pub volume: i32,
+    pub playlist_shuffle: bool,
+    pub repeat_mode: i32,
+    pub pitch: i32,
+    pub timestretch: i32,
+    pub timestretch_available: bool,
+}
+
+#[Object]
+impl PlaybackSettings {
+    async fn volume(&self) -> i32 {
+        self.volume
+    }
+
+    async fn playlist_shuffle(&self) -> bool {
+        self.playlist_shuffle
+    }
+
+    async fn repeat_mode(&self) -> i32 {
+        self.repeat_mode
+    }
+
+    async fn pitch(&self) -> i32 {
+        self.pitch
+    }
+
+    async fn timestretch(&self) -> i32 {
+        self.timestretch
+    }
+
+    async fn timestretch_available(&self) -> bool {
+        self.timestretch_available
+    }
+}
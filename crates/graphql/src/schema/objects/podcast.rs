@@ -0,0 +1,107 @@
+use async_graphql::*;
+use rockbox_types::podcast;
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct Podcast {
+    pub id: String,
+    pub url: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub author: Option<String>,
+    pub image: Option<String>,
+    pub link: Option<String>,
+    /// The newest episodes downloaded when the feed is polled.
+    pub auto_download: u32,
+    pub delete_played: bool,
+    /// Unix timestamps in seconds.
+    pub last_polled_at: Option<i64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct PodcastEpisode {
+    pub id: String,
+    pub podcast_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub url: String,
+    pub mime_type: Option<String>,
+    /// Seconds.
+    pub duration: Option<u32>,
+    pub published_at: Option<i64>,
+    /// The downloaded file.
+    pub path: Option<String>,
+    pub played: bool,
+    pub played_at: Option<i64>,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, InputObject)]
+pub struct PodcastInput {
+    pub url: String,
+    pub auto_download: Option<u32>,
+    pub delete_played: Option<bool>,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, InputObject)]
+pub struct UpdatePodcastInput {
+    pub auto_download: Option<u32>,
+    pub delete_played: Option<bool>,
+}
+
+impl From<podcast::Podcast> for Podcast {
+    fn from(podcast: podcast::Podcast) -> Self {
+        Self {
+            id: podcast.id,
+            url: podcast.url,
+            title: podcast.title,
+            description: podcast.description,
+            author: podcast.author,
+            image: podcast.image,
+            link: podcast.link,
+            auto_download: podcast.auto_download,
+            delete_played: podcast.delete_played,
+            last_polled_at: podcast.last_polled_at,
+            created_at: podcast.created_at,
+            updated_at: podcast.updated_at,
+        }
+    }
+}
+
+impl From<podcast::Episode> for PodcastEpisode {
+    fn from(episode: podcast::Episode) -> Self {
+        Self {
+            id: episode.id,
+            podcast_id: episode.podcast_id,
+            title: episode.title,
+            description: episode.description,
+            url: episode.url,
+            mime_type: episode.mime_type,
+            duration: episode.duration,
+            published_at: episode.published_at,
+            path: episode.path,
+            played: episode.played,
+            played_at: episode.played_at,
+        }
+    }
+}
+
+impl From<PodcastInput> for podcast::NewPodcast {
+    fn from(podcast: PodcastInput) -> Self {
+        Self {
+            url: podcast.url,
+            auto_download: podcast.auto_download,
+            delete_played: podcast.delete_played,
+        }
+    }
+}
+
+impl From<UpdatePodcastInput> for podcast::UpdatePodcast {
+    fn from(update: UpdatePodcastInput) -> Self {
+        Self {
+            auto_download: update.auto_download,
+            delete_played: update.delete_played,
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use async_graphql::*;
+use rockbox_types::radio;
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct RadioStation {
+    pub id: String,
+    pub name: String,
+    /// The stream, or its PLS or M3U playlist.
+    pub url: String,
+    pub genre: Option<String>,
+    pub homepage: Option<String>,
+    pub image: Option<String>,
+    pub favourite: bool,
+    /// Unix timestamps in seconds.
+    pub last_played_at: Option<i64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct RadioNowPlaying {
+    pub station: RadioStation,
+    /// From the ICY metadata of the stream.
+    pub title: Option<String>,
+    pub artist: Option<String>,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, InputObject)]
+pub struct RadioStationInput {
+    pub url: String,
+    /// The `icy-name` of the stream when not given.
+    pub name: Option<String>,
+    pub genre: Option<String>,
+    pub homepage: Option<String>,
+    pub image: Option<String>,
+    pub favourite: Option<bool>,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, InputObject)]
+pub struct UpdateRadioStationInput {
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub genre: Option<String>,
+    pub homepage: Option<String>,
+    pub image: Option<String>,
+    pub favourite: Option<bool>,
+}
+
+impl From<radio::Station> for RadioStation {
+    fn from(station: radio::Station) -> Self {
+        Self {
+            id: station.id,
+            name: station.name,
+            url: station.url,
+            genre: station.genre,
+            homepage: station.homepage,
+            image: station.image,
+            favourite: station.favourite,
+            last_played_at: station.last_played_at,
+            created_at: station.created_at,
+            updated_at: station.updated_at,
+        }
+    }
+}
+
+impl From<radio::NowPlaying> for RadioNowPlaying {
+    fn from(now_playing: radio::NowPlaying) -> Self {
+        Self {
+            station: now_playing.station.into(),
+            title: now_playing.title,
+            artist: now_playing.artist,
+        }
+    }
+}
+
+impl From<RadioStationInput> for radio::NewStation {
+    fn from(station: RadioStationInput) -> Self {
+        Self {
+            url: station.url,
+            name: station.name,
+            genre: station.genre,
+            homepage: station.homepage,
+            image: station.image,
+            favourite: station.favourite,
+        }
+    }
+}
+
+impl From<UpdateRadioStationInput> for radio::UpdateStation {
+    fn from(update: UpdateRadioStationInput) -> Self {
+        Self {
+            name: update.name,
+            url: update.url,
+            genre: update.genre,
+            homepage: update.homepage,
+            image: update.image,
+            favourite: update.favourite,
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use async_graphql::*;
+use rockbox_library::replaygain;
+use serde::{Deserialize, Serialize};
+
+/// The loudness measured for a track, gains are in dB and peaks linear.
+#[derive(Default, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct ReplayGain {
+    pub track_id: String,
+    /// Integrated loudness in LUFS.
+    pub track_loudness: f64,
+    pub track_gain: f64,
+    pub track_peak: f64,
+    pub album_loudness: Option<f64>,
+    pub album_gain: Option<f64>,
+    pub album_peak: Option<f64>,
+    pub analyzed_at: String,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct ReplayGainProgress {
+    pub analyzed: usize,
+    pub failed: usize,
+    pub total: usize,
+    pub running: bool,
+    pub current: Option<String>,
+    pub error: Option<String>,
+}
+
+impl From<rockbox_library::entity::replaygain::ReplayGain> for ReplayGain {
+    fn from(replaygain: rockbox_library::entity::replaygain::ReplayGain) -> Self {
+        Self {
+            track_id: replaygain.track_id,
+            track_loudness: replaygain.track_loudness,
+            track_gain: replaygain.track_gain,
+            track_peak: replaygain.track_peak,
+            album_loudness: replaygain.album_loudness,
+            album_gain: replaygain.album_gain,
+            album_peak: replaygain.album_peak,
+            analyzed_at: replaygain.analyzed_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<replaygain::AnalysisProgress> for ReplayGainProgress {
+    fn from(progress: replaygain::AnalysisProgress) -> Self {
+        Self {
+            analyzed: progress.analyzed,
+            failed: progress.failed,
+            total: progress.total,
+            running: progress.running,
+            current: progress.current,
+            error: progress.error,
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use async_graphql::*;
+use rockbox_sys::types::user_settings;
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct ResumeSettings {
+    /// Tracks at least this long, in seconds, remember where they were
+    /// stopped, 0 to disable.
+    pub min_length: u32,
+    /// Audiobook and podcast directories.
+    pub directories: Vec<String>,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, InputObject)]
+pub struct ResumeSettingsInput {
+    pub min_length: u32,
+    pub directories: Vec<String>,
+}
+
+impl From<user_settings::ResumeSettings> for ResumeSettings {
+    fn from(settings: user_settings::ResumeSettings) -> Self {
+        Self {
+            min_length: settings.min_length,
+            directories: settings.directories,
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use async_graphql::*;
+use rockbox_types::scheduler;
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct SleepTimer {
+    pub active: bool,
+    /// Seconds.
+    pub duration: u32,
+    pub remaining: u32,
+    pub fade_out: u32,
+}
+
+#[derive(Enum, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlaybackSource {
+    #[default]
+    Playlist,
+    Album,
+    Artist,
+    Directory,
+    LikedTracks,
+    AllTracks,
+    Mix,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct Schedule {
+    pub id: String,
+    pub name: String,
+    pub cron: String,
+    pub source: PlaybackSource,
+    pub source_id: Option<String>,
+    pub shuffle: bool,
+    /// dB.
+    pub volume: Option<i32>,
+    /// Seconds.
+    pub fade_in: u32,
+    pub once: bool,
+    pub enabled: bool,
+    /// Unix timestamps in seconds.
+    pub last_run_at: Option<i64>,
+    pub next_run_at: Option<i64>,
+    pub created_at: i64,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, InputObject)]
+pub struct ScheduleInput {
+    pub name: String,
+    pub cron: String,
+    pub source: PlaybackSource,
+    pub source_id: Option<String>,
+    pub shuffle: Option<bool>,
+    pub volume: Option<i32>,
+    pub fade_in: Option<u32>,
+    pub once: Option<bool>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, InputObject)]
+pub struct UpdateScheduleInput {
+    pub name: Option<String>,
+    pub cron: Option<String>,
+    pub source: Option<PlaybackSource>,
+    pub source_id: Option<String>,
+    pub shuffle: Option<bool>,
+    pub volume: Option<i32>,
+    pub fade_in: Option<u32>,
+    pub once: Option<bool>,
+    pub enabled: Option<bool>,
+}
+
+impl From<scheduler::SleepTimer> for SleepTimer {
+    fn from(timer: scheduler::SleepTimer) -> Self {
+        Self {
+            active: timer.active,
+            duration: timer.duration,
+            remaining: timer.remaining,
+            fade_out: timer.fade_out,
+        }
+    }
+}
+
+impl From<scheduler::PlaybackSource> for PlaybackSource {
+    fn from(source: scheduler::PlaybackSource) -> Self {
+        match source {
+            scheduler::PlaybackSource::Playlist => PlaybackSource::Playlist,
+            scheduler::PlaybackSource::Album => PlaybackSource::Album,
+            scheduler::PlaybackSource::Artist => PlaybackSource::Artist,
+            scheduler::PlaybackSource::Directory => PlaybackSource::Directory,
+            scheduler::PlaybackSource::LikedTracks => PlaybackSource::LikedTracks,
+            scheduler::PlaybackSource::AllTracks => PlaybackSource::AllTracks,
+            scheduler::PlaybackSource::Mix => PlaybackSource::Mix,
+        }
+    }
+}
+
+impl From<PlaybackSource> for scheduler::PlaybackSource {
+    fn from(source: PlaybackSource) -> Self {
+        match source {
+            PlaybackSource::Playlist => scheduler::PlaybackSource::Playlist,
+            PlaybackSource::Album => scheduler::PlaybackSource::Album,
+            PlaybackSource::Artist => scheduler::PlaybackSource::Artist,
+            PlaybackSource::Directory => scheduler::PlaybackSource::Directory,
+            PlaybackSource::LikedTracks => scheduler::PlaybackSource::LikedTracks,
+            PlaybackSource::AllTracks => scheduler::PlaybackSource::AllTracks,
+            PlaybackSource::Mix => scheduler::PlaybackSource::Mix,
+        }
+    }
+}
+
+impl From<scheduler::Schedule> for Schedule {
+    fn from(schedule: scheduler::Schedule) -> Self {
+        Self {
+            id: schedule.id,
+            name: schedule.name,
+            cron: schedule.cron,
+            source: schedule.source.into(),
+            source_id: schedule.source_id,
+            shuffle: schedule.shuffle,
+            volume: schedule.volume,
+            fade_in: schedule.fade_in,
+            once: schedule.once,
+            enabled: schedule.enabled,
+            last_run_at: schedule.last_run_at,
+            next_run_at: schedule.next_run_at,
+            created_at: schedule.created_at,
+        }
+    }
+}
+
+impl From<ScheduleInput> for scheduler::NewSchedule {
+    fn from(schedule: ScheduleInput) -> Self {
+        Self {
+            name: schedule.name,
+            cron: schedule.cron,
+            source: schedule.source.into(),
+            source_id: schedule.source_id,
+            shuffle: schedule.shuffle.unwrap_or_default(),
+            volume: schedule.volume,
+            fade_in: schedule.fade_in.unwrap_or_default(),
+            once: schedule.once.unwrap_or_default(),
+            enabled: schedule.enabled,
+        }
+    }
+}
+
+impl From<UpdateScheduleInput> for scheduler::UpdateSchedule {
+    fn from(update: UpdateScheduleInput) -> Self {
+        Self {
+            name: update.name,
+            cron: update.cron,
+            source: update.source.map(Into::into),
+            source_id: update.source_id,
+            shuffle: update.shuffle,
+            volume: update.volume,
+            fade_in: update.fade_in,
+            once: update.once,
+            enabled: update.enabled,
+        }
+    }
+}
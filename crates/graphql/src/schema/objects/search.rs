@@ -1,7 +1,7 @@
 use async_graphql::*;
 use serde::{Deserialize, Serialize};
 
-use super::{album::Album, artist::Artist, track::Track};
+use super::{album::Album, artist::Artist, genre::Genre, track::Track};
 
 #[derive(Default, Clone, Serialize, Deserialize)]
 pub struct SearchResults {
@@ -10,6 +10,32 @@ pub struct SearchResults {
     pub tracks: Vec<Track>,
     pub liked_tracks: Vec<Track>,
     pub liked_albums: Vec<Album>,
+    pub genres: Vec<Genre>,
+    pub totals: SearchTotals,
+    pub facets: SearchFacets,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct SearchTotals {
+    pub artists: usize,
+    pub albums: usize,
+    pub tracks: usize,
+    pub liked_tracks: usize,
+    pub liked_albums: usize,
+    pub genres: usize,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: usize,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct SearchFacets {
+    pub genres: Vec<FacetCount>,
+    pub decades: Vec<FacetCount>,
+    pub formats: Vec<FacetCount>,
 }
 
 #[Object]
@@ -33,6 +59,18 @@ impl SearchResults {
     async fn liked_albums(&self) -> Vec<Album> {
         self.liked_albums.clone()
     }
+
+    async fn genres(&self) -> Vec<Genre> {
+        self.genres.clone()
+    }
+
+    async fn totals(&self) -> SearchTotals {
+        self.totals.clone()
+    }
+
+    async fn facets(&self) -> SearchFacets {
+        self.facets.clone()
+    }
 }
 
 impl From<rockbox_types::SearchResults> for SearchResults {
@@ -43,6 +81,35 @@ impl From<rockbox_types::SearchResults> for SearchResults {
             tracks: results.tracks.into_iter().map(Into::into).collect(),
             liked_tracks: results.liked_tracks.into_iter().map(Into::into).collect(),
             liked_albums: results.liked_albums.into_iter().map(Into::into).collect(),
+            genres: results.genres.into_iter().map(Into::into).collect(),
+            totals: SearchTotals {
+                artists: results.totals.artists,
+                albums: results.totals.albums,
+                tracks: results.totals.tracks,
+                liked_tracks: results.totals.liked_tracks,
+                liked_albums: results.totals.liked_albums,
+                genres: results.totals.genres,
+            },
+            facets: results.facets.into(),
+        }
+    }
+}
+
+impl From<rockbox_search::query::Facets> for SearchFacets {
+    fn from(facets: rockbox_search::query::Facets) -> Self {
+        let counts = |counts: Vec<rockbox_search::query::FacetCount>| {
+            counts
+                .into_iter()
+                .map(|facet| FacetCount {
+                    value: facet.value,
+                    count: facet.count,
+                })
+                .collect()
+        };
+        SearchFacets {
+            genres: counts(facets.genres),
+            decades: counts(facets.decades),
+            formats: counts(facets.formats),
         }
     }
 }
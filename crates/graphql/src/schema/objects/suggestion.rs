@@ -0,0 +1,25 @@
+use async_graphql::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct Suggestion {
+    pub id: String,
+    pub kind: String,
+    pub label: String,
+    pub artist: Option<String>,
+    pub cover: Option<String>,
+    pub popularity: u64,
+}
+
+impl From<rockbox_search::suggestion::Suggestion> for Suggestion {
+    fn from(suggestion: rockbox_search::suggestion::Suggestion) -> Self {
+        Self {
+            id: suggestion.id,
+            kind: suggestion.kind,
+            label: suggestion.label,
+            artist: suggestion.artist,
+            cover: suggestion.cover,
+            popularity: suggestion.popularity,
+        }
+    }
+}
@@ -57,3 +57,20 @@ impl SystemStatus {
         self.last_volume_change
     }
 }
+
+impl From<rockbox_sys::types::system_status::SystemStatus> for SystemStatus {
+    fn from(status: rockbox_sys::types::system_status::SystemStatus) -> Self {
+        Self {
+            resume_index: status.resume_index,
+            resume_crc32: status.resume_crc32,
+            resume_elapsed: status.resume_elapsed,
+            resume_offset: status.resume_offset,
+            runtime: status.runtime,
+            topruntime: status.topruntime,
+            dircache_size: status.dircache_size,
+            last_screen: status.last_screen,
+            viewer_icon_count: status.viewer_icon_count,
+            last_volume_change: status.last_volume_change,
+        }
+    }
+}
@@ -35,6 +35,30 @@ pub struct Track {
     pub artist_id: Option<String>,
     pub genre_id: Option<String>,
     pub album_art: Option<String>,
+    pub musicbrainz_id: Option<String>,
+    pub replaygain_track_gain: Option<f32>,
+    pub replaygain_track_peak: Option<f32>,
+    pub replaygain_album_gain: Option<f32>,
+    pub replaygain_album_peak: Option<f32>,
+    pub bpm: Option<f32>,
+    pub rating: Option<u32>,
+    pub label: Option<String>,
+    pub isrc: Option<String>,
+    pub original_date: Option<String>,
+    pub title_sort: Option<String>,
+    pub artist_sort: Option<String>,
+    pub album_sort: Option<String>,
+    pub album_artist_sort: Option<String>,
+    pub composer_sort: Option<String>,
+    pub lyrics: Option<String>,
+}
+
+/// The elapsed time of the current track, published on every broker tick
+/// while `Track` is only republished once per second.
+#[derive(Default, Debug, Clone)]
+pub struct TrackPosition {
+    pub track_id: Option<String>,
+    pub elapsed: u64,
 }
 
 #[Object]
@@ -142,6 +166,70 @@ impl Track {
     async fn album_art(&self) -> Option<&str> {
         self.album_art.as_deref()
     }
+
+    async fn musicbrainz_id(&self) -> Option<&str> {
+        self.musicbrainz_id.as_deref()
+    }
+
+    async fn replaygain_track_gain(&self) -> Option<f32> {
+        self.replaygain_track_gain
+    }
+
+    async fn replaygain_track_peak(&self) -> Option<f32> {
+        self.replaygain_track_peak
+    }
+
+    async fn replaygain_album_gain(&self) -> Option<f32> {
+        self.replaygain_album_gain
+    }
+
+    async fn replaygain_album_peak(&self) -> Option<f32> {
+        self.replaygain_album_peak
+    }
+
+    async fn bpm(&self) -> Option<f32> {
+        self.bpm
+    }
+
+    async fn rating(&self) -> Option<u32> {
+        self.rating
+    }
+
+    async fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    async fn isrc(&self) -> Option<&str> {
+        self.isrc.as_deref()
+    }
+
+    async fn original_date(&self) -> Option<&str> {
+        self.original_date.as_deref()
+    }
+
+    async fn title_sort(&self) -> Option<&str> {
+        self.title_sort.as_deref()
+    }
+
+    async fn artist_sort(&self) -> Option<&str> {
+        self.artist_sort.as_deref()
+    }
+
+    async fn album_sort(&self) -> Option<&str> {
+        self.album_sort.as_deref()
+    }
+
+    async fn album_artist_sort(&self) -> Option<&str> {
+        self.album_artist_sort.as_deref()
+    }
+
+    async fn composer_sort(&self) -> Option<&str> {
+        self.composer_sort.as_deref()
+    }
+
+    async fn lyrics(&self) -> Option<&str> {
+        self.lyrics.as_deref()
+    }
 }
 
 impl From<Mp3Entry> for Track {
@@ -226,6 +314,23 @@ impl From<rockbox_library::entity::track::Track> for Track {
             genre_id: Some(track.genre_id),
             path: track.path,
             album_art: track.album_art,
+            musicbrainz_id: track.musicbrainz_id,
+            replaygain_track_gain: track.replaygain_track_gain,
+            replaygain_track_peak: track.replaygain_track_peak,
+            replaygain_album_gain: track.replaygain_album_gain,
+            replaygain_album_peak: track.replaygain_album_peak,
+            bpm: track.bpm,
+            rating: track.rating,
+            comment: track.comment.unwrap_or_default(),
+            label: track.label,
+            isrc: track.isrc,
+            original_date: track.original_date,
+            title_sort: track.title_sort,
+            artist_sort: track.artist_sort,
+            album_sort: track.album_sort,
+            album_artist_sort: track.album_artist_sort,
+            composer_sort: track.composer_sort,
+            lyrics: track.lyrics,
             ..Default::default()
         }
     }
@@ -0,0 +1,45 @@
+use async_graphql::*;
+use rockbox_sys::types::user_settings;
+use serde::{Deserialize, Serialize};
+
+#[derive(Enum, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransitionMode {
+    #[default]
+    Crossfade,
+    Smart,
+    BeatMatch,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct TransitionSettings {
+    /// Queues playing in order.
+    pub album: TransitionMode,
+    /// Shuffled queues.
+    pub mix: TransitionMode,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, InputObject)]
+pub struct TransitionSettingsInput {
+    pub album: TransitionMode,
+    pub mix: TransitionMode,
+}
+
+impl From<user_settings::TransitionMode> for TransitionMode {
+    fn from(mode: user_settings::TransitionMode) -> Self {
+        match mode {
+            user_settings::TransitionMode::Crossfade => TransitionMode::Crossfade,
+            user_settings::TransitionMode::Smart => TransitionMode::Smart,
+            user_settings::TransitionMode::BeatMatch => TransitionMode::BeatMatch,
+        }
+    }
+}
+
+impl From<user_settings::TransitionSettings> for TransitionSettings {
+    fn from(settings: user_settings::TransitionSettings) -> Self {
+        Self {
+            album: settings.album.into(),
+            mix: settings.mix.into(),
+        }
+    }
+}
@@ -4,7 +4,8 @@ use serde::{Deserialize, Serialize};
 
 use super::{
     compressor_settings::CompressorSettings, eq_band_setting::EqBandSetting,
-    replaygain_settings::ReplaygainSettings,
+    replaygain_settings::ReplaygainSettings, resume_settings::ResumeSettings,
+    transition_settings::TransitionSettings,
 };
 
 #[derive(Default, Clone, Serialize, Deserialize)]
@@ -260,6 +261,9 @@ pub struct UserSettings {
 
     pub governor: i32,
     pub stereosw_mode: i32,
+
+    pub transitions: TransitionSettings,
+    pub resume: ResumeSettings,
 }
 
 #[Object]
@@ -1055,6 +1059,14 @@ impl UserSettings {
     async fn stereosw_mode(&self) -> i32 {
         self.stereosw_mode
     }
+
+    async fn transitions(&self) -> TransitionSettings {
+        self.transitions.clone()
+    }
+
+    async fn resume(&self) -> ResumeSettings {
+        self.resume.clone()
+    }
 }
 
 impl From<rb::types::user_settings::UserSettings> for UserSettings {
@@ -1262,6 +1274,8 @@ impl From<rb::types::user_settings::UserSettings> for UserSettings {
             afr_enabled: settings.afr_enabled,
             governor: settings.governor,
             stereosw_mode: settings.stereosw_mode,
+            transitions: settings.transitions.into(),
+            resume: settings.resume.into(),
         }
     }
 }
@@ -1,80 +1,35 @@
-use std::{
-    fs,
-    sync::{mpsc::Sender, Arc, Mutex},
-};
-
-use crate::{check_and_load_player, read_files, schema::objects, AUDIO_EXTENSIONS};
+use crate::schema::objects;
 use async_graphql::*;
 use futures_util::Stream;
-use rockbox_library::repo;
-use rockbox_sys::{
-    events::RockboxCommand,
-    types::{audio_status::AudioStatus, file_position::FilePosition, mp3_entry::Mp3Entry},
-};
-use rockbox_types::device::Device;
-use sqlx::{Pool, Sqlite};
+use rockbox_service::RockboxService;
 
-use crate::{rockbox_url, schema::objects::track::Track, simplebroker::SimpleBroker};
+use crate::{schema::objects::track::Track, simplebroker::SimpleBroker};
 
 #[derive(Default)]
 pub struct PlaybackQuery;
 
 #[Object]
 impl PlaybackQuery {
-    async fn status(&self) -> Result<i32, Error> {
-        let client = reqwest::Client::new();
-        let url = format!("{}/player/status", rockbox_url());
-        let response = client.get(&url).send().await?;
-        let response = response.json::<AudioStatus>().await?;
-        Ok(response.status)
+    async fn status(&self, ctx: &Context<'_>) -> Result<i32, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.status().await?.status)
     }
 
     async fn current_track(&self, ctx: &Context<'_>) -> Result<Option<Track>, Error> {
-        let pool = ctx.data::<Pool<Sqlite>>()?;
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let url = format!("{}/player/current-track", rockbox_url());
-        let response = client.get(&url).send().await?;
-        let track = response.json::<Option<Mp3Entry>>().await?;
-        let mut track: Option<Track> = track.map(|t| t.into());
-        let path: Option<String> = track.as_ref().map(|t| t.path.clone());
-        if let Some(path) = path {
-            let hash = format!("{:x}", md5::compute(path.as_bytes()));
-            if let Some(metadata) = repo::track::find_by_md5(pool.clone(), &hash).await? {
-                track.as_mut().unwrap().id = Some(metadata.id);
-                track.as_mut().unwrap().album_art = metadata.album_art;
-                track.as_mut().unwrap().album_id = Some(metadata.album_id);
-                track.as_mut().unwrap().artist_id = Some(metadata.artist_id);
-            }
-        }
-        Ok(track)
+        let service = ctx.data::<RockboxService>()?;
+        let track = service.current_track().await?;
+        Ok(track.map(|t| t.into()))
     }
 
     async fn next_track(&self, ctx: &Context<'_>) -> Result<Option<Track>, Error> {
-        let pool = ctx.data::<Pool<Sqlite>>()?;
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let url = format!("{}/player/next-track", rockbox_url());
-        let response = client.get(&url).send().await?;
-        let track = response.json::<Option<Mp3Entry>>().await?;
-        let mut track: Option<Track> = track.map(|t| t.into());
-        let path: Option<String> = track.as_ref().map(|t| t.path.clone());
-        if let Some(path) = path {
-            let hash = format!("{:x}", md5::compute(path.as_bytes()));
-            if let Some(metadata) = repo::track::find_by_md5(pool.clone(), &hash).await? {
-                track.as_mut().unwrap().id = Some(metadata.id);
-                track.as_mut().unwrap().album_art = metadata.album_art;
-                track.as_mut().unwrap().album_id = Some(metadata.album_id);
-                track.as_mut().unwrap().artist_id = Some(metadata.artist_id);
-            }
-        }
-        Ok(track)
+        let service = ctx.data::<RockboxService>()?;
+        let track = service.next_track().await?;
+        Ok(track.map(|t| t.into()))
     }
 
     async fn get_file_position(&self, ctx: &Context<'_>) -> Result<i32, Error> {
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let url = format!("{}/player/file-position", rockbox_url());
-        let response = client.get(&url).send().await?;
-        let response = response.json::<FilePosition>().await?;
-        Ok(response.position)
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.file_position().await?.position)
     }
 }
 
@@ -84,65 +39,50 @@ pub struct PlaybackMutation;
 #[Object]
 impl PlaybackMutation {
     async fn play(&self, ctx: &Context<'_>, elapsed: i64, offset: i64) -> Result<i32, Error> {
-        let cmd = ctx.data::<Arc<Mutex<Sender<RockboxCommand>>>>().unwrap();
-        cmd.lock()
-            .unwrap()
-            .send(RockboxCommand::Play(elapsed, offset))?;
+        let service = ctx.data::<RockboxService>()?;
+        service.play(elapsed, offset).await?;
         Ok(0)
     }
 
     async fn pause(&self, ctx: &Context<'_>) -> Result<i32, Error> {
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let url = format!("{}/player/pause", rockbox_url());
-        client.put(&url).send().await?;
+        let service = ctx.data::<RockboxService>()?;
+        service.pause().await?;
         Ok(0)
     }
 
     async fn resume(&self, ctx: &Context<'_>) -> Result<i32, Error> {
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let url = format!("{}/player/resume", rockbox_url());
-        client.put(&url).send().await?;
+        let service = ctx.data::<RockboxService>()?;
+        service.resume().await?;
         Ok(0)
     }
 
     async fn next(&self, ctx: &Context<'_>) -> Result<i32, Error> {
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let url = format!("{}/player/next", rockbox_url());
-        client.put(&url).send().await?;
+        let service = ctx.data::<RockboxService>()?;
+        service.next().await?;
         Ok(0)
     }
 
     async fn previous(&self, ctx: &Context<'_>) -> Result<i32, Error> {
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let url = format!("{}/player/previous", rockbox_url());
-        client.put(&url).send().await?;
+        let service = ctx.data::<RockboxService>()?;
+        service.previous().await?;
         Ok(0)
     }
 
     async fn fast_forward_rewind(&self, ctx: &Context<'_>, new_time: i32) -> Result<i32, Error> {
-        ctx.data::<Arc<Mutex<Sender<RockboxCommand>>>>()
-            .unwrap()
-            .lock()
-            .unwrap()
-            .send(RockboxCommand::FfRewind(new_time))?;
+        let service = ctx.data::<RockboxService>()?;
+        service.ff_rewind(new_time).await?;
         Ok(0)
     }
 
     async fn flush_and_reload_tracks(&self, ctx: &Context<'_>) -> Result<i32, Error> {
-        ctx.data::<Arc<Mutex<Sender<RockboxCommand>>>>()
-            .unwrap()
-            .lock()
-            .unwrap()
-            .send(RockboxCommand::FlushAndReloadTracks)?;
+        let service = ctx.data::<RockboxService>()?;
+        service.flush_and_reload_tracks().await?;
         Ok(0)
     }
 
     async fn hard_stop(&self, ctx: &Context<'_>) -> Result<i32, Error> {
-        ctx.data::<Arc<Mutex<Sender<RockboxCommand>>>>()
-            .unwrap()
-            .lock()
-            .unwrap()
-            .send(RockboxCommand::Stop)?;
+        let service = ctx.data::<RockboxService>()?;
+        service.hard_stop().await?;
         Ok(0)
     }
 
@@ -153,31 +93,10 @@ impl PlaybackMutation {
         shuffle: Option<bool>,
         position: Option<i32>,
     ) -> Result<i32, Error> {
-        let pool = ctx.data::<Pool<Sqlite>>()?;
-        let tracks = repo::album_tracks::find_by_album(pool.clone(), &album_id).await?;
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let tracks = tracks.into_iter().map(|t| t.path).collect::<Vec<String>>();
-        let body = serde_json::json!({
-            "tracks": tracks,
-        });
-
-        check_and_load_player!(client, tracks, shuffle.unwrap_or_default());
-
-        let url = format!("{}/playlists", rockbox_url());
-        client.post(&url).json(&body).send().await?;
-
-        if let Some(true) = shuffle {
-            let url = format!("{}/playlists/shuffle", rockbox_url());
-            client.put(&url).send().await?;
-        }
-
-        let url = match position {
-            Some(p) => format!("{}/playlists/start?start_index={}", rockbox_url(), p),
-            None => format!("{}/playlists/start", rockbox_url()),
-        };
-
-        client.put(&url).send().await?;
-
+        let service = ctx.data::<RockboxService>()?;
+        service
+            .play_album(&album_id, shuffle.unwrap_or_default(), position)
+            .await?;
         Ok(0)
     }
 
@@ -188,42 +107,25 @@ impl PlaybackMutation {
         shuffle: Option<bool>,
         position: Option<i32>,
     ) -> Result<i32, Error> {
-        let pool = ctx.data::<Pool<Sqlite>>()?;
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let tracks = repo::artist_tracks::find_by_artist(pool.clone(), &artist_id).await?;
-        let tracks = tracks.into_iter().map(|t| t.path).collect::<Vec<String>>();
-        let body = serde_json::json!({
-            "tracks": tracks,
-        });
-
-        check_and_load_player!(client, tracks, shuffle.unwrap_or_default());
-
-        let url = format!("{}/playlists", rockbox_url());
-        client.post(&url).json(&body).send().await?;
-
-        if let Some(true) = shuffle {
-            let url = format!("{}/playlists/shuffle", rockbox_url());
-            client.put(&url).send().await?;
-        }
-
-        let url = match position {
-            Some(p) => format!("{}/playlists/start?start_index={}", rockbox_url(), p),
-            None => format!("{}/playlists/start", rockbox_url()),
-        };
-
-        client.put(&url).send().await?;
-
+        let service = ctx.data::<RockboxService>()?;
+        service
+            .play_artist_tracks(&artist_id, shuffle.unwrap_or_default(), position)
+            .await?;
         Ok(0)
     }
 
     async fn play_playlist(
         &self,
-        _ctx: &Context<'_>,
-        _playlist_id: String,
-        _shuffle: Option<bool>,
-        _position: Option<i32>,
+        ctx: &Context<'_>,
+        playlist_id: String,
+        shuffle: Option<bool>,
+        position: Option<i32>,
     ) -> Result<i32, Error> {
-        todo!()
+        let service = ctx.data::<RockboxService>()?;
+        service
+            .play_playlist(&playlist_id, shuffle.unwrap_or_default(), position)
+            .await?;
+        Ok(0)
     }
 
     async fn play_directory(
@@ -234,82 +136,21 @@ impl PlaybackMutation {
         shuffle: Option<bool>,
         position: Option<i32>,
     ) -> Result<i32, Error> {
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let mut tracks: Vec<String> = vec![];
-
-        let recurse = match position {
-            Some(_) => Some(false),
-            None => recurse,
-        };
-
-        if !std::path::Path::new(&path).is_dir() {
-            return Err(Error::new("Invalid path"));
-        }
-
-        match recurse {
-            Some(true) => tracks = read_files(path).await?,
-            _ => {
-                for file in fs::read_dir(&path)? {
-                    let file = file?;
-
-                    if file.metadata()?.is_file() {
-                        if !AUDIO_EXTENSIONS.iter().any(|ext| {
-                            file.path()
-                                .to_string_lossy()
-                                .ends_with(&format!(".{}", ext))
-                        }) {
-                            continue;
-                        }
-
-                        tracks.push(file.path().to_string_lossy().to_string());
-                    }
-                }
-            }
-        }
-
-        let body = serde_json::json!({
-            "tracks": tracks,
-        });
-
-        check_and_load_player!(client, tracks, shuffle.unwrap_or_default());
-
-        let url = format!("{}/playlists", rockbox_url());
-        client.post(&url).json(&body).send().await?;
-
-        if let Some(true) = shuffle {
-            let url = format!("{}/playlists/shuffle", rockbox_url());
-            client.put(&url).send().await?;
-        }
-
-        let url = match position {
-            Some(p) => format!("{}/playlists/start?start_index={}", rockbox_url(), p),
-            None => format!("{}/playlists/start", rockbox_url()),
-        };
-
-        client.put(&url).send().await?;
-
+        let service = ctx.data::<RockboxService>()?;
+        service
+            .play_directory(
+                &path,
+                recurse.unwrap_or_default(),
+                shuffle.unwrap_or_default(),
+                position,
+            )
+            .await?;
         Ok(0)
     }
 
     async fn play_track(&self, ctx: &Context<'_>, path: String) -> Result<i32, Error> {
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let path = path.replace("file://", "");
-
-        let body = serde_json::json!({
-            "tracks": vec![path.clone()],
-        });
-
-        check_and_load_player!(client, vec![path], false);
-
-        let client = reqwest::Client::new();
-
-        let url = format!("{}/playlists", rockbox_url());
-        client.post(&url).json(&body).send().await?;
-
-        let client = reqwest::Client::new();
-        let url = format!("{}/playlists/start", rockbox_url());
-        client.put(&url).send().await?;
-
+        let service = ctx.data::<RockboxService>()?;
+        service.play_track(&path).await?;
         Ok(0)
     }
 
@@ -319,35 +160,10 @@ impl PlaybackMutation {
         shuffle: Option<bool>,
         position: Option<i32>,
     ) -> Result<i32, Error> {
-        let pool = ctx.data::<Pool<Sqlite>>()?;
-        let tracks = repo::favourites::all_tracks(pool.clone())
-            .await?
-            .into_iter()
-            .map(|t| t.path)
-            .collect::<Vec<String>>();
-
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let body = serde_json::json!({
-            "tracks": tracks,
-        });
-
-        check_and_load_player!(client, tracks, shuffle.unwrap_or_default());
-
-        let url = format!("{}/playlists", rockbox_url());
-        client.post(&url).json(&body).send().await?;
-
-        if let Some(true) = shuffle {
-            let url = format!("{}/playlists/shuffle", rockbox_url());
-            client.put(&url).send().await?;
-        }
-
-        let url = match position {
-            Some(p) => format!("{}/playlists/start?start_index={}", rockbox_url(), p),
-            None => format!("{}/playlists/start", rockbox_url()),
-        };
-
-        client.put(&url).send().await?;
-
+        let service = ctx.data::<RockboxService>()?;
+        service
+            .play_liked_tracks(shuffle.unwrap_or_default(), position)
+            .await?;
         Ok(0)
     }
 
@@ -357,35 +173,10 @@ impl PlaybackMutation {
         shuffle: Option<bool>,
         position: Option<i32>,
     ) -> Result<i32, Error> {
-        let pool = ctx.data::<Pool<Sqlite>>()?;
-        let tracks = repo::track::all(pool.clone())
-            .await?
-            .into_iter()
-            .map(|t| t.path)
-            .collect::<Vec<String>>();
-
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let body = serde_json::json!({
-            "tracks": tracks,
-        });
-
-        check_and_load_player!(client, tracks, shuffle.unwrap_or_default());
-
-        let url = format!("{}/playlists", rockbox_url());
-        client.post(&url).json(&body).send().await?;
-
-        if let Some(true) = shuffle {
-            let url = format!("{}/playlists/shuffle", rockbox_url());
-            client.put(&url).send().await?;
-        }
-
-        let url = match position {
-            Some(p) => format!("{}/playlists/start?start_index={}", rockbox_url(), p),
-            None => format!("{}/playlists/start", rockbox_url()),
-        };
-
-        client.put(&url).send().await?;
-
+        let service = ctx.data::<RockboxService>()?;
+        service
+            .play_all_tracks(shuffle.unwrap_or_default(), position)
+            .await?;
         Ok(0)
     }
 }
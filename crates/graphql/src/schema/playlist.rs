@@ -1,27 +1,18 @@
-use std::sync::{mpsc::Sender, Arc, Mutex};
-
 use async_graphql::*;
 use futures_util::Stream;
 use rockbox_library::repo;
-use rockbox_sys::{
-    events::RockboxCommand,
-    types::{playlist_amount::PlaylistAmount, playlist_info::PlaylistInfo},
-};
+use rockbox_service::RockboxService;
 
-use crate::{
-    rockbox_url, schema::objects::playlist::Playlist, simplebroker::SimpleBroker, types::StatusCode,
-};
+use crate::{schema::objects::playlist::Playlist, simplebroker::SimpleBroker};
 
 #[derive(Default)]
 pub struct PlaylistQuery;
 
 #[Object]
 impl PlaylistQuery {
-    async fn playlist_get_current(&self, _ctx: &Context<'_>) -> Result<Playlist, Error> {
-        let client = reqwest::Client::new();
-        let url = format!("{}/playlists/current", rockbox_url());
-        let response = client.get(&url).send().await?;
-        let response = response.json::<PlaylistInfo>().await?;
+    async fn playlist_get_current(&self, ctx: &Context<'_>) -> Result<Playlist, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        let response = service.current_playlist().await?;
         Ok(Playlist {
             amount: response.amount,
             index: response.index,
@@ -50,12 +41,9 @@ impl PlaylistQuery {
         "get display index".to_string()
     }
 
-    async fn playlist_amount(&self, _ctx: &Context<'_>) -> Result<i32, Error> {
-        let client = reqwest::Client::new();
-        let url = format!("{}/playlists/amount", rockbox_url());
-        let response = client.get(&url).send().await?;
-        let response = response.json::<PlaylistAmount>().await?;
-        Ok(response.amount)
+    async fn playlist_amount(&self, ctx: &Context<'_>) -> Result<i32, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.playlist_amount().await?)
     }
 }
 
@@ -64,20 +52,14 @@ pub struct PlaylistMutation;
 
 #[Object]
 impl PlaylistMutation {
-    async fn playlist_resume(&self, _ctx: &Context<'_>) -> Result<i32, Error> {
-        let client = reqwest::Client::new();
-        let url = format!("{}/playlists/resume", rockbox_url());
-        let response = client.put(&url).send().await?;
-        let response = response.json::<StatusCode>().await?;
-        Ok(response.code)
+    async fn playlist_resume(&self, ctx: &Context<'_>) -> Result<i32, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.resume_playlist().await?)
     }
 
     async fn resume_track(&self, ctx: &Context<'_>) -> Result<String, Error> {
-        ctx.data::<Arc<Mutex<Sender<RockboxCommand>>>>()
-            .unwrap()
-            .lock()
-            .unwrap()
-            .send(RockboxCommand::PlaylistResumeTrack)?;
+        let service = ctx.data::<RockboxService>()?;
+        service.resume_track().await?;
         Ok("".to_string())
     }
 
@@ -92,38 +74,20 @@ impl PlaylistMutation {
         elapsed: Option<i32>,
         offset: Option<i32>,
     ) -> Result<i32, Error> {
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let mut url = format!("{}/playlists/start", rockbox_url());
-
-        if let Some(start_index) = start_index {
-            url = format!("{}?start_index={}", url, start_index);
-        }
-
-        if let Some(elapsed) = elapsed {
-            url = match url.contains("?") {
-                true => format!("{}&elapsed={}", url, elapsed),
-                false => format!("{}?elapsed={}", url, elapsed),
-            };
-        }
-
-        if let Some(offset) = offset {
-            url = match url.contains("?") {
-                true => format!("{}&offset={}", url, offset),
-                false => format!("{}?offset={}", url, offset),
-            };
-        }
-
-        client.put(&url).send().await?;
+        let service = ctx.data::<RockboxService>()?;
+        service
+            .start_playlist(
+                start_index.unwrap_or_default(),
+                elapsed.unwrap_or_default() as u64,
+                offset.unwrap_or_default() as u64,
+            )
+            .await?;
         Ok(0)
     }
 
     async fn playlist_remove_track(&self, ctx: &Context<'_>, index: i32) -> Result<i32, Error> {
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let url = format!("{}/playlists/current/tracks", rockbox_url());
-        let body = serde_json::json!({
-            "positions": vec![index],
-        });
-        client.delete(&url).json(&body).send().await?;
+        let service = ctx.data::<RockboxService>()?;
+        service.remove_tracks(&[index]).await?;
         Ok(0)
     }
 
@@ -132,32 +96,18 @@ impl PlaylistMutation {
     }
 
     async fn playlist_remove_all_tracks(&self, ctx: &Context<'_>) -> Result<i32, Error> {
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let body = serde_json::json!({
-            "positions": [],
-        });
-        let url = format!("{}/playlists/current/tracks", rockbox_url());
-        let response = client.delete(&url).json(&body).send().await?;
-        let start_index = response.text().await?.parse()?;
-        Ok(start_index)
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.remove_tracks(&[]).await?)
     }
 
     async fn playlist_create(
         &self,
         ctx: &Context<'_>,
-        name: String,
+        _name: String,
         tracks: Vec<String>,
     ) -> Result<i32, Error> {
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let body = serde_json::json!({
-            "name": name,
-            "tracks": tracks,
-        });
-
-        let url = format!("{}/playlists", rockbox_url());
-        let response = client.post(&url).json(&body).send().await?;
-        let start_index = response.text().await?.parse()?;
-        Ok(start_index)
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.create_playlist(tracks).await?)
     }
 
     async fn insert_tracks(
@@ -167,15 +117,8 @@ impl PlaylistMutation {
         position: i32,
         tracks: Vec<String>,
     ) -> Result<i32, Error> {
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let body = serde_json::json!({
-            "position": position,
-            "tracks": tracks,
-        });
-        let url = format!("{}/playlists/current/tracks", rockbox_url());
-        let response = client.post(&url).json(&body).send().await?;
-        let start_index = response.text().await?.parse()?;
-        Ok(start_index)
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.insert_tracks(tracks, position, None).await?)
     }
 
     async fn insert_directory(
@@ -185,16 +128,10 @@ impl PlaylistMutation {
         position: i32,
         directory: String,
     ) -> Result<i32, Error> {
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let body = serde_json::json!({
-            "position": position,
-            "tracks": [],
-            "directory": directory,
-        });
-        let url = format!("{}/playlists/current/tracks", rockbox_url());
-        let response = client.post(&url).json(&body).send().await?;
-        let start_index = response.text().await?.parse()?;
-        Ok(start_index)
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service
+            .insert_tracks(vec![], position, Some(directory))
+            .await?)
     }
 
     async fn insert_playlist(
@@ -214,26 +151,15 @@ impl PlaylistMutation {
         album_id: String,
         position: i32,
     ) -> Result<i32, Error> {
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let pool = ctx.data::<sqlx::Pool<sqlx::Sqlite>>()?;
-        let tracks = repo::album_tracks::find_by_album(pool.clone(), &album_id).await?;
-        let tracks: Vec<String> = tracks.into_iter().map(|t| t.path).collect();
-        let body = serde_json::json!({
-            "position": position,
-            "tracks": tracks,
-        });
-        let url = format!("{}/playlists/current/tracks", rockbox_url());
-        let response = client.post(&url).json(&body).send().await?;
-        let start_index = response.text().await?.parse()?;
-        Ok(start_index)
+        let service = ctx.data::<RockboxService>()?;
+        let tracks = repo::album_tracks::find_by_album(service.pool.clone(), &album_id).await?;
+        let tracks = tracks.into_iter().map(|t| t.path).collect();
+        Ok(service.insert_tracks(tracks, position, None).await?)
     }
 
     async fn shuffle_playlist(&self, ctx: &Context<'_>) -> Result<i32, Error> {
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let url = format!("{}/playlists/shuffle", rockbox_url());
-        let response = client.put(&url).send().await?;
-        let ret = response.text().await?.parse()?;
-        Ok(ret)
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.shuffle_playlist(0).await?)
     }
 }
 
@@ -0,0 +1,102 @@
+use async_graphql::*;
+use rockbox_service::RockboxService;
+
+use crate::schema::objects::podcast::{Podcast, PodcastEpisode, PodcastInput, UpdatePodcastInput};
+
+#[derive(Default)]
+pub struct PodcastQuery;
+
+#[Object]
+impl PodcastQuery {
+    async fn podcasts(&self, ctx: &Context<'_>) -> Result<Vec<Podcast>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        let podcasts = service.podcasts().await?;
+        Ok(podcasts.into_iter().map(Into::into).collect())
+    }
+
+    async fn podcast(&self, ctx: &Context<'_>, id: String) -> Result<Option<Podcast>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.podcast(&id).await?.map(Into::into))
+    }
+
+    /// The episodes of a podcast, the newest first.
+    async fn podcast_episodes(
+        &self,
+        ctx: &Context<'_>,
+        podcast_id: String,
+    ) -> Result<Option<Vec<PodcastEpisode>>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        let episodes = service.podcast_episodes(&podcast_id).await?;
+        Ok(episodes.map(|episodes| episodes.into_iter().map(Into::into).collect()))
+    }
+
+    async fn podcast_episode(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<Option<PodcastEpisode>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.podcast_episode(&id).await?.map(Into::into))
+    }
+}
+
+#[derive(Default)]
+pub struct PodcastMutation;
+
+#[Object]
+impl PodcastMutation {
+    async fn subscribe_podcast(
+        &self,
+        ctx: &Context<'_>,
+        podcast: PodcastInput,
+    ) -> Result<Podcast, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.subscribe(podcast.into()).await?.into())
+    }
+
+    async fn update_podcast(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        podcast: UpdatePodcastInput,
+    ) -> Result<Option<Podcast>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        let podcast = service.update_podcast(&id, podcast.into()).await?;
+        Ok(podcast.map(Into::into))
+    }
+
+    async fn unsubscribe_podcast(&self, ctx: &Context<'_>, id: String) -> Result<bool, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.unsubscribe(&id).await?)
+    }
+
+    /// Polls the feed of a podcast now.
+    async fn refresh_podcast(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<Option<Podcast>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.refresh_podcast(&id).await?.map(Into::into))
+    }
+
+    async fn download_podcast_episode(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<Option<PodcastEpisode>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.download_episode(&id).await?.map(Into::into))
+    }
+
+    async fn set_podcast_episode_played(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        played: bool,
+    ) -> Result<Option<PodcastEpisode>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        let episode = service.set_episode_played(&id, played).await?;
+        Ok(episode.map(Into::into))
+    }
+}
@@ -0,0 +1,87 @@
+use async_graphql::*;
+use rockbox_service::{radio, RockboxService};
+
+use crate::schema::objects::radio::{
+    RadioNowPlaying, RadioStation, RadioStationInput, UpdateRadioStationInput,
+};
+
+#[derive(Default)]
+pub struct RadioQuery;
+
+#[Object]
+impl RadioQuery {
+    /// The stations, the favourites first.
+    async fn radio_stations(
+        &self,
+        ctx: &Context<'_>,
+        favourite: Option<bool>,
+    ) -> Result<Vec<RadioStation>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        let stations = service.stations(favourite.unwrap_or_default()).await?;
+        Ok(stations.into_iter().map(Into::into).collect())
+    }
+
+    async fn radio_station(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<Option<RadioStation>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.station(&id).await?.map(Into::into))
+    }
+
+    /// The station playing locally and the title of its stream.
+    async fn now_playing_station(&self) -> Option<RadioNowPlaying> {
+        radio::now_playing().map(Into::into)
+    }
+}
+
+#[derive(Default)]
+pub struct RadioMutation;
+
+#[Object]
+impl RadioMutation {
+    async fn add_radio_station(
+        &self,
+        ctx: &Context<'_>,
+        station: RadioStationInput,
+    ) -> Result<RadioStation, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.create_station(station.into()).await?.into())
+    }
+
+    async fn update_radio_station(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        station: UpdateRadioStationInput,
+    ) -> Result<Option<RadioStation>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        let station = service.update_station(&id, station.into()).await?;
+        Ok(station.map(Into::into))
+    }
+
+    async fn delete_radio_station(&self, ctx: &Context<'_>, id: String) -> Result<bool, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.delete_station(&id).await?)
+    }
+
+    async fn play_radio_station(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<Option<RadioStation>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.play_station(&id).await?.map(Into::into))
+    }
+
+    /// Plays a stream URL, as its station when one was added with it.
+    async fn play_radio_stream(
+        &self,
+        ctx: &Context<'_>,
+        url: String,
+    ) -> Result<RadioStation, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.play_stream(&url).await?.into())
+    }
+}
@@ -0,0 +1,79 @@
+use async_graphql::*;
+use rockbox_service::RockboxService;
+
+use crate::schema::objects::scheduler::{Schedule, ScheduleInput, SleepTimer, UpdateScheduleInput};
+
+#[derive(Default)]
+pub struct SchedulerQuery;
+
+#[Object]
+impl SchedulerQuery {
+    async fn sleep_timer(&self, ctx: &Context<'_>) -> Result<SleepTimer, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.sleep_timer().into())
+    }
+
+    async fn schedules(&self, ctx: &Context<'_>) -> Result<Vec<Schedule>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        let schedules = service.schedules().await?;
+        Ok(schedules.into_iter().map(Into::into).collect())
+    }
+
+    async fn schedule(&self, ctx: &Context<'_>, id: String) -> Result<Option<Schedule>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.schedule(&id).await?.map(Into::into))
+    }
+}
+
+#[derive(Default)]
+pub struct SchedulerMutation;
+
+#[Object]
+impl SchedulerMutation {
+    /// `duration` and `fade_out` are in seconds, a duration of 0 cancels the
+    /// timer.
+    async fn set_sleep_timer(
+        &self,
+        ctx: &Context<'_>,
+        duration: u32,
+        fade_out: Option<u32>,
+    ) -> Result<SleepTimer, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.set_sleep_timer(duration, fade_out).into())
+    }
+
+    async fn cancel_sleep_timer(&self, ctx: &Context<'_>) -> Result<SleepTimer, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.cancel_sleep_timer().into())
+    }
+
+    async fn create_schedule(
+        &self,
+        ctx: &Context<'_>,
+        schedule: ScheduleInput,
+    ) -> Result<Schedule, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.create_schedule(schedule.into()).await?.into())
+    }
+
+    async fn update_schedule(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        schedule: UpdateScheduleInput,
+    ) -> Result<Option<Schedule>, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        let schedule = service.update_schedule(&id, schedule.into()).await?;
+        Ok(schedule.map(Into::into))
+    }
+
+    async fn delete_schedule(&self, ctx: &Context<'_>, id: String) -> Result<bool, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.delete_schedule(&id).await?)
+    }
+
+    async fn run_schedule(&self, ctx: &Context<'_>, id: String) -> Result<bool, Error> {
+        let service = ctx.data::<RockboxService>()?;
+        Ok(service.run_schedule(&id).await?)
+    }
+}
@@ -1,7 +1,7 @@
 use async_graphql::*;
+use rockbox_service::RockboxService;
 
-use crate::{rockbox_url, schema::objects::user_settings::UserSettings};
-use rockbox_sys as rb;
+use crate::schema::objects::user_settings::UserSettings;
 
 use super::objects::new_global_settings::NewGlobalSettings;
 
@@ -11,14 +11,8 @@ pub struct SettingsQuery;
 #[Object]
 impl SettingsQuery {
     async fn global_settings(&self, ctx: &Context<'_>) -> Result<UserSettings, Error> {
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let url = format!("{}/settings", rockbox_url());
-        let settings = client
-            .get(url)
-            .send()
-            .await?
-            .json::<rb::types::user_settings::UserSettings>()
-            .await?;
+        let service = ctx.data::<RockboxService>()?;
+        let settings = service.global_settings().await?;
         Ok(settings.into())
     }
 }
@@ -33,9 +27,10 @@ impl SettingsMutation {
         ctx: &Context<'_>,
         settings: NewGlobalSettings,
     ) -> Result<bool, Error> {
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let url = format!("{}/settings", rockbox_url());
-        let response = client.put(url).json(&settings).send().await?;
-        Ok(response.status().is_success())
+        let service = ctx.data::<RockboxService>()?;
+        // both share the json representation of the settings file
+        let settings = serde_json::from_value(serde_json::to_value(&settings)?)?;
+        service.save_settings(settings).await?;
+        Ok(true)
     }
 }
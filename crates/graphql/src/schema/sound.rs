@@ -1,6 +1,6 @@
 use async_graphql::*;
 
-use crate::rockbox_url;
+use rockbox_service::RockboxService;
 
 #[derive(Default)]
 pub struct SoundQuery;
@@ -30,13 +30,8 @@ pub struct SoundMutation;
 #[Object]
 impl SoundMutation {
     async fn adjust_volume(&self, ctx: &Context<'_>, steps: i32) -> Result<i32, Error> {
-        let client = ctx.data::<reqwest::Client>().unwrap();
-        let body = serde_json::json!({
-            "steps": steps,
-        });
-        let url = format!("{}/player/volume", rockbox_url());
-        client.put(&url).json(&body).send().await?;
-
+        let service = ctx.data::<RockboxService>()?;
+        service.adjust_volume(steps).await?;
         Ok(0)
     }
 
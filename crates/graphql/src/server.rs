@@ -1,12 +1,9 @@
-use std::{
-    path::PathBuf,
-    sync::{mpsc::Sender, Arc, Mutex},
-};
+use std::path::PathBuf;
 
 use actix_cors::Cors;
-use actix_files::{self as fs, NamedFile};
+use actix_files::NamedFile;
 use actix_web::{
-    error::ErrorNotFound,
+    error::{ErrorInternalServerError, ErrorNotFound},
     guard,
     http::header::{ContentDisposition, DispositionType, HOST},
     web::{self, Data},
@@ -15,14 +12,16 @@ use actix_web::{
 use anyhow::Error;
 use async_graphql::{http::GraphiQLSource, Schema};
 use async_graphql_actix_web::{GraphQLRequest, GraphQLResponse, GraphQLSubscription};
-use rockbox_library::{create_connection_pool, repo};
-use rockbox_search::create_indexes;
-use rockbox_sys::events::RockboxCommand;
+use futures_util::StreamExt;
+use rockbox_library::{album_art, repo};
+use rockbox_stream::{Format, Listener, METAINT};
 use rockbox_webui::{dist, index, index_spa};
+use serde::Deserialize;
 use sqlx::{Pool, Sqlite};
 
 use crate::{
-    schema::{Mutation, Query, Subscription},
+    schema::{objects::track::Track, Mutation, Query, Subscription},
+    simplebroker::SimpleBroker,
     RockboxSchema,
 };
 
@@ -87,21 +86,79 @@ async fn index_file(req: HttpRequest) -> Result<NamedFile, actix_web::Error> {
     }
 }
 
-pub async fn start(cmd_tx: Arc<Mutex<Sender<RockboxCommand>>>) -> Result<(), Error> {
-    let client = reqwest::Client::new();
-    let pool = create_connection_pool().await?;
-    let indexes = create_indexes()?;
+#[derive(Deserialize)]
+struct CoverQuery {
+    size: Option<u32>,
+}
+
+/// Serves a cover by its filename, its album id or its artist id, `size`
+/// picks the smallest thumbnail at least that wide.
+async fn index_cover(
+    req: HttpRequest,
+    query: web::Query<CoverQuery>,
+) -> Result<NamedFile, actix_web::Error> {
+    let id = req.match_info().get("id").unwrap();
+    let pool = req.app_data::<Pool<Sqlite>>().unwrap();
+    match album_art::find_cover(pool.clone(), id, query.size).await {
+        Ok(Some(path)) => Ok(NamedFile::open(path)?),
+        Ok(None) => Err(ErrorNotFound("Cover not found")),
+        Err(e) => Err(ErrorInternalServerError(e)),
+    }
+}
+
+async fn index_stream(req: HttpRequest) -> Result<HttpResponse> {
+    let format = match req.match_info().get("format") {
+        Some(extension) => Format::from_extension(extension)
+            .ok_or_else(|| ErrorNotFound("Unsupported stream format"))?,
+        None => Format::Mp3,
+    };
+    let icy_metadata = req
+        .headers()
+        .get("icy-metadata")
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim() == "1")
+        .unwrap_or(false);
+
+    let listener = Listener::new(format, icy_metadata);
+    let body = futures_util::stream::unfold(listener, |mut listener| async move {
+        match listener.next_chunk().await {
+            Some(Ok(data)) => Some((Ok(web::Bytes::from(data)), listener)),
+            Some(Err(e)) => Some((Err(actix_web::error::ErrorInternalServerError(e)), listener)),
+            None => None,
+        }
+    });
+
+    let mut response = HttpResponse::Ok();
+    response
+        .content_type(format.content_type())
+        .insert_header(("Cache-Control", "no-cache, no-store"))
+        .insert_header(("icy-name", "Rockbox"));
+    if icy_metadata {
+        response.insert_header(("icy-metaint", METAINT.to_string()));
+    }
+    Ok(response.streaming(body))
+}
+
+pub async fn start() -> Result<(), Error> {
+    let service = rockbox_service::instance().await?;
+    let pool = service.pool.clone();
     let schema = Schema::build(
         Query::default(),
         Mutation::default(),
         Subscription::default(),
     )
-    .data(cmd_tx)
-    .data(client)
     .data(pool.clone())
-    .data(indexes)
+    .data(service.indexes.clone())
+    .data(service)
     .finish();
 
+    tokio::spawn(async {
+        let mut subscription = SimpleBroker::<Track>::subscribe();
+        while let Some(track) = subscription.next().await {
+            rockbox_stream::set_stream_title(&track.artist, &track.title);
+        }
+    });
+
     let graphql_port = std::env::var("ROCKBOX_GRAPHQL_PORT").unwrap_or("6062".to_string());
     let addr = format!("{}:{}", "0.0.0.0", graphql_port);
 
@@ -124,7 +181,6 @@ pub async fn start(cmd_tx: Arc<Mutex<Sender<RockboxCommand>>>) -> Result<(), Err
                     .guard(guard::Header("upgrade", "websocket"))
                     .to(index_ws),
             )
-            .service(fs::Files::new("/covers", covers_path).show_files_listing())
             .service(index)
             .route("/tracks", web::get().to(index_spa))
             .route("/artists", web::get().to(index_spa))
@@ -136,8 +192,11 @@ pub async fn start(cmd_tx: Arc<Mutex<Sender<RockboxCommand>>>) -> Result<(), Err
             .route("/albums/{_:.*}", web::get().to(index_spa))
             .route("/playlists/{_:.*}", web::get().to(index_spa))
             .route("/files/{_:.*}", web::get().to(index_spa))
+            .route("/covers/{id}", web::get().to(index_cover))
             .route("/tracks/{id}", web::get().to(index_file))
             .route("/tracks/{id}", web::head().to(index_file))
+            .route("/stream", web::get().to(index_stream))
+            .route("/stream.{format}", web::get().to(index_stream))
             .service(dist)
     })
     .bind(addr)?
@@ -0,0 +1,401 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Weak,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::Error;
+use async_trait::async_trait;
+use rockbox_traits::types::playback::Playback;
+use rockbox_traits::types::track::Track;
+use rockbox_traits::Player;
+use tokio::sync::Mutex;
+
+/// Time given to every member to buffer before the synchronized start.
+const START_LEAD_MS: u64 = 500;
+/// Members drifting further than this from the leader are re-seeked.
+const MAX_DRIFT_MS: i64 = 150;
+const DRIFT_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+pub struct GroupMember {
+    pub device_id: String,
+    pub player: Box<dyn Player + Send>,
+    pub volume_offset: f32,
+    pub latency_ms: u32,
+}
+
+impl GroupMember {
+    pub fn new(device_id: &str, player: Box<dyn Player + Send>) -> Self {
+        GroupMember {
+            device_id: device_id.to_string(),
+            player,
+            volume_offset: 0.0,
+            latency_ms: 0,
+        }
+    }
+
+    pub fn with_volume_offset(mut self, volume_offset: f32) -> Self {
+        self.volume_offset = volume_offset;
+        self
+    }
+
+    pub fn with_latency(mut self, latency_ms: u32) -> Self {
+        self.latency_ms = latency_ms;
+        self
+    }
+}
+
+/// A set of players driven as one, the first member is the leader: the
+/// current playback and tracklist are read from it and the other members
+/// are kept in sync with its position. Clones drive the same group, it stops
+/// once they are all dropped.
+#[derive(Clone)]
+pub struct PlayerGroup {
+    inner: Arc<GroupInner>,
+}
+
+struct GroupInner {
+    members: Arc<Mutex<Vec<GroupMember>>>,
+    running: Arc<AtomicBool>,
+}
+
+/// A reference to a group that doesn't keep it running, to update the
+/// settings of its members while it plays.
+#[derive(Clone)]
+pub struct WeakPlayerGroup {
+    inner: Weak<GroupInner>,
+}
+
+impl WeakPlayerGroup {
+    pub fn upgrade(&self) -> Option<PlayerGroup> {
+        self.inner.upgrade().map(|inner| PlayerGroup { inner })
+    }
+}
+
+impl PlayerGroup {
+    pub fn new(members: Vec<GroupMember>) -> Self {
+        let group = PlayerGroup {
+            inner: Arc::new(GroupInner {
+                members: Arc::new(Mutex::new(members)),
+                running: Arc::new(AtomicBool::new(true)),
+            }),
+        };
+        group.start_drift_correction();
+        group
+    }
+
+    pub fn downgrade(&self) -> WeakPlayerGroup {
+        WeakPlayerGroup {
+            inner: Arc::downgrade(&self.inner),
+        }
+    }
+
+    fn members(&self) -> &Mutex<Vec<GroupMember>> {
+        &self.inner.members
+    }
+
+    pub async fn set_member_volume_offset(&self, device_id: &str, volume_offset: f32) {
+        let mut members = self.members().lock().await;
+        if let Some(member) = members.iter_mut().find(|m| m.device_id == device_id) {
+            member.volume_offset = volume_offset;
+        }
+    }
+
+    pub async fn set_member_latency(&self, device_id: &str, latency_ms: u32) {
+        let mut members = self.members().lock().await;
+        if let Some(member) = members.iter_mut().find(|m| m.device_id == device_id) {
+            member.latency_ms = latency_ms;
+        }
+    }
+
+    fn start_drift_correction(&self) {
+        let members = self.inner.members.clone();
+        let running = self.inner.running.clone();
+        thread::spawn(move || {
+            let rt = tokio::runtime::Runtime::new().unwrap();
+            while running.load(Ordering::Relaxed) {
+                thread::sleep(DRIFT_CHECK_INTERVAL);
+                if !running.load(Ordering::Relaxed) {
+                    break;
+                }
+                if let Err(e) = rt.block_on(correct_drift(members.clone())) {
+                    eprintln!("group drift correction failed: {}", e);
+                }
+            }
+        });
+    }
+}
+
+impl Drop for GroupInner {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Starts every member at the same wall clock instant, members with a
+/// higher output latency are fired earlier so that audio comes out together.
+async fn start_aligned(members: &mut [GroupMember]) -> Result<(), Error> {
+    let now = Instant::now();
+    let latencies: Vec<u32> = members.iter().map(|member| member.latency_ms).collect();
+    for (index, delay) in start_schedule(&latencies) {
+        tokio::time::sleep_until((now + delay).into()).await;
+        members[index].player.resume().await?;
+    }
+    Ok(())
+}
+
+/// When each member is resumed after now, in firing order: the audio of
+/// every one comes out [`START_LEAD_MS`] from now, the ones with a latency
+/// longer than that are fired right away.
+fn start_schedule(latencies: &[u32]) -> Vec<(usize, Duration)> {
+    let mut schedule: Vec<(usize, Duration)> = latencies
+        .iter()
+        .enumerate()
+        .map(|(index, latency)| {
+            let delay = START_LEAD_MS.saturating_sub(*latency as u64);
+            (index, Duration::from_millis(delay))
+        })
+        .collect();
+    schedule.sort_by_key(|(_, delay)| *delay);
+    schedule
+}
+
+/// The position a follower should be at to sound together with the leader,
+/// `elapsed_ms` after the leader reported `leader_position_ms`. A player is
+/// heard its latency after it reports a position.
+fn expected_position(
+    leader_position_ms: u32,
+    elapsed_ms: u64,
+    leader_latency_ms: u32,
+    follower_latency_ms: u32,
+) -> i64 {
+    leader_position_ms as i64 + elapsed_ms as i64 + follower_latency_ms as i64
+        - leader_latency_ms as i64
+}
+
+/// Whether a follower at `position_ms` drifted too far from `expected`.
+fn drifted(position_ms: u32, expected: i64) -> bool {
+    (position_ms as i64 - expected).abs() > MAX_DRIFT_MS
+}
+
+async fn correct_drift(members: Arc<Mutex<Vec<GroupMember>>>) -> Result<(), Error> {
+    let mut members = members.lock().await;
+    let (leader, followers) = match members.split_first_mut() {
+        Some(split) => split,
+        None => return Ok(()),
+    };
+
+    let reference = leader.player.get_current_playback().await?;
+    let measured_at = Instant::now();
+    if !reference.is_playing {
+        return Ok(());
+    }
+
+    for follower in followers.iter_mut() {
+        let playback = follower.player.get_current_playback().await?;
+        if !playback.is_playing || playback.index != reference.index {
+            continue;
+        }
+        let expected = expected_position(
+            reference.position_ms,
+            measured_at.elapsed().as_millis() as u64,
+            leader.latency_ms,
+            follower.latency_ms,
+        );
+        if drifted(playback.position_ms, expected) {
+            follower.player.seek_ms(expected.max(0) as u32).await?;
+        }
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl Player for PlayerGroup {
+    async fn play(&self) -> Result<(), Error> {
+        let mut members = self.members().lock().await;
+        start_aligned(&mut members).await
+    }
+
+    async fn next(&self) -> Result<(), Error> {
+        let mut members = self.members().lock().await;
+        for member in members.iter_mut() {
+            member.player.next().await?;
+        }
+        Ok(())
+    }
+
+    async fn previous(&self) -> Result<(), Error> {
+        let mut members = self.members().lock().await;
+        for member in members.iter_mut() {
+            member.player.previous().await?;
+        }
+        Ok(())
+    }
+
+    async fn stop(&self) -> Result<(), Error> {
+        let mut members = self.members().lock().await;
+        for member in members.iter_mut() {
+            member.player.stop().await?;
+        }
+        Ok(())
+    }
+
+    async fn pause(&self) -> Result<(), Error> {
+        let mut members = self.members().lock().await;
+        for member in members.iter_mut() {
+            member.player.pause().await?;
+        }
+        Ok(())
+    }
+
+    async fn resume(&self) -> Result<(), Error> {
+        let mut members = self.members().lock().await;
+        start_aligned(&mut members).await
+    }
+
+    async fn seek(&self, seconds: i32) -> Result<(), Error> {
+        self.seek_ms((seconds.max(0) as u32) * 1000).await
+    }
+
+    async fn seek_ms(&self, position_ms: u32) -> Result<(), Error> {
+        let mut members = self.members().lock().await;
+        for member in members.iter_mut() {
+            member.player.pause().await?;
+            member.player.seek_ms(position_ms).await?;
+        }
+        start_aligned(&mut members).await
+    }
+
+    async fn volume(&self, level: f32) -> Result<(), Error> {
+        let mut members = self.members().lock().await;
+        for member in members.iter_mut() {
+            let level = (level + member.volume_offset).clamp(0.0, 1.0);
+            member.player.volume(level).await?;
+        }
+        Ok(())
+    }
+
+    async fn load_tracks(&self, tracks: Vec<Track>, start_index: Option<i32>) -> Result<(), Error> {
+        let mut members = self.members().lock().await;
+        for member in members.iter_mut() {
+            member
+                .player
+                .load_tracks(tracks.clone(), start_index)
+                .await?;
+            member.player.pause().await?;
+        }
+        start_aligned(&mut members).await
+    }
+
+    async fn play_next(&self, track: Track) -> Result<(), Error> {
+        let mut members = self.members().lock().await;
+        for member in members.iter_mut() {
+            member.player.play_next(track.clone()).await?;
+        }
+        Ok(())
+    }
+
+    async fn load(&mut self, track: Track) -> Result<(), Error> {
+        let mut members = self.members().lock().await;
+        for member in members.iter_mut() {
+            member.player.load(track.clone()).await?;
+            member.player.pause().await?;
+        }
+        start_aligned(&mut members).await
+    }
+
+    async fn get_current_playback(&mut self) -> Result<Playback, Error> {
+        let mut members = self.members().lock().await;
+        match members.first_mut() {
+            Some(leader) => leader.player.get_current_playback().await,
+            None => Ok(Playback::default()),
+        }
+    }
+
+    async fn get_current_tracklist(&self) -> Result<(Vec<Track>, Vec<Track>), Error> {
+        let mut members = self.members().lock().await;
+        match members.first_mut() {
+            Some(leader) => leader.player.get_current_tracklist().await,
+            None => Ok((vec![], vec![])),
+        }
+    }
+
+    async fn play_track_at(&self, position: u32) -> Result<(), Error> {
+        let mut members = self.members().lock().await;
+        for member in members.iter_mut() {
+            member.player.play_track_at(position).await?;
+            member.player.pause().await?;
+        }
+        start_aligned(&mut members).await
+    }
+
+    async fn remove_track_at(&self, position: u32) -> Result<(), Error> {
+        let mut members = self.members().lock().await;
+        for member in members.iter_mut() {
+            member.player.remove_track_at(position).await?;
+        }
+        Ok(())
+    }
+
+    async fn disconnect(&self) -> Result<(), Error> {
+        self.inner.running.store(false, Ordering::Relaxed);
+        let mut members = self.members().lock().await;
+        for member in members.iter_mut() {
+            member.player.disconnect().await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn start_schedule_fires_the_highest_latency_first() {
+        let schedule = start_schedule(&[0, 300, 100]);
+        assert_eq!(
+            schedule,
+            vec![
+                (1, Duration::from_millis(200)),
+                (2, Duration::from_millis(400)),
+                (0, Duration::from_millis(500)),
+            ]
+        );
+    }
+
+    #[test]
+    fn start_schedule_fires_latencies_beyond_the_lead_right_away() {
+        let schedule = start_schedule(&[800, 0]);
+        assert_eq!(
+            schedule,
+            vec![(0, Duration::ZERO), (1, Duration::from_millis(500))]
+        );
+    }
+
+    #[test]
+    fn expected_position_follows_the_leader() {
+        assert_eq!(expected_position(10_000, 250, 0, 0), 10_250);
+    }
+
+    #[test]
+    fn expected_position_counts_both_latencies() {
+        // the follower is heard 200ms later, the leader 50ms later
+        assert_eq!(expected_position(10_000, 0, 50, 200), 10_150);
+        // a leader slower than the follower is caught up by starting later
+        assert_eq!(expected_position(10_000, 0, 300, 100), 9_800);
+        assert_eq!(expected_position(100, 0, 300, 0), -200);
+    }
+
+    #[test]
+    fn drift_within_the_tolerance_is_kept() {
+        assert!(!drifted(10_150, 10_000));
+        assert!(!drifted(9_850, 10_000));
+        assert!(drifted(10_151, 10_000));
+        assert!(drifted(9_000, 10_000));
+        assert!(drifted(0, -200));
+    }
+}
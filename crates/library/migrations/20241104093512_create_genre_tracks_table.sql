@@ -0,0 +1,9 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS genre_tracks (
+    id VARCHAR(255) PRIMARY KEY,
+    genre_id VARCHAR(255) NOT NULL,
+    track_id VARCHAR(255) NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS genre_tracks_genre_id ON genre_tracks (genre_id);
+CREATE INDEX IF NOT EXISTS genre_tracks_track_id ON genre_tracks (track_id);
@@ -0,0 +1,5 @@
+-- Add migration script here
+ALTER TABLE track ADD COLUMN musicbrainz_id VARCHAR(255) DEFAULT NULL;
+ALTER TABLE album ADD COLUMN musicbrainz_id VARCHAR(255) DEFAULT NULL;
+ALTER TABLE album ADD COLUMN musicbrainz_release_group_id VARCHAR(255) DEFAULT NULL;
+ALTER TABLE artist ADD COLUMN musicbrainz_id VARCHAR(255) DEFAULT NULL;
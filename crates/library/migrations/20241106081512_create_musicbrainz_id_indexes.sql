@@ -0,0 +1,5 @@
+-- Add migration script here
+CREATE INDEX IF NOT EXISTS track_musicbrainz_id ON track (musicbrainz_id);
+CREATE INDEX IF NOT EXISTS album_musicbrainz_id ON album (musicbrainz_id);
+CREATE INDEX IF NOT EXISTS album_musicbrainz_release_group_id ON album (musicbrainz_release_group_id);
+CREATE INDEX IF NOT EXISTS artist_musicbrainz_id ON artist (musicbrainz_id);
@@ -0,0 +1,16 @@
+-- Add migration script here
+ALTER TABLE track ADD COLUMN replaygain_track_gain REAL DEFAULT NULL;
+ALTER TABLE track ADD COLUMN replaygain_track_peak REAL DEFAULT NULL;
+ALTER TABLE track ADD COLUMN replaygain_album_gain REAL DEFAULT NULL;
+ALTER TABLE track ADD COLUMN replaygain_album_peak REAL DEFAULT NULL;
+ALTER TABLE track ADD COLUMN bpm REAL DEFAULT NULL;
+ALTER TABLE track ADD COLUMN rating INT DEFAULT NULL;
+ALTER TABLE track ADD COLUMN comment TEXT DEFAULT NULL;
+ALTER TABLE track ADD COLUMN label VARCHAR(255) DEFAULT NULL;
+ALTER TABLE track ADD COLUMN isrc VARCHAR(255) DEFAULT NULL;
+ALTER TABLE track ADD COLUMN original_date VARCHAR(255) DEFAULT NULL;
+ALTER TABLE track ADD COLUMN title_sort VARCHAR(255) DEFAULT NULL;
+ALTER TABLE track ADD COLUMN artist_sort VARCHAR(255) DEFAULT NULL;
+ALTER TABLE track ADD COLUMN album_sort VARCHAR(255) DEFAULT NULL;
+ALTER TABLE track ADD COLUMN album_artist_sort VARCHAR(255) DEFAULT NULL;
+ALTER TABLE track ADD COLUMN composer_sort VARCHAR(255) DEFAULT NULL;
@@ -0,0 +1,6 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS lyrics (
+    id VARCHAR(255) PRIMARY KEY,
+    track_id VARCHAR(255) NOT NULL UNIQUE,
+    lyrics TEXT NOT NULL
+);
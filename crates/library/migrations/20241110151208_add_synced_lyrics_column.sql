@@ -0,0 +1,2 @@
+-- Add migration script here
+ALTER TABLE lyrics ADD COLUMN synced TEXT DEFAULT NULL;
@@ -0,0 +1,12 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS replaygain (
+    id VARCHAR(255) PRIMARY KEY,
+    track_id VARCHAR(255) NOT NULL UNIQUE,
+    track_loudness REAL NOT NULL,
+    track_gain REAL NOT NULL,
+    track_peak REAL NOT NULL,
+    album_loudness REAL,
+    album_gain REAL,
+    album_peak REAL,
+    analyzed_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,15 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS bookmark (
+    id VARCHAR(255) PRIMARY KEY,
+    name VARCHAR(255),
+    source VARCHAR(255) NOT NULL,
+    source_id VARCHAR(1024) NOT NULL,
+    path VARCHAR(1024) NOT NULL,
+    track_id VARCHAR(255),
+    elapsed INT NOT NULL DEFAULT 0,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- the resume positions saved automatically, one per track, album and directory
+CREATE UNIQUE INDEX IF NOT EXISTS bookmark_resume_position ON bookmark (source, source_id) WHERE name IS NULL;
@@ -0,0 +1,34 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS podcast (
+    id VARCHAR(255) PRIMARY KEY,
+    url VARCHAR(1024) NOT NULL UNIQUE,
+    title VARCHAR(255) NOT NULL,
+    description TEXT,
+    author VARCHAR(255),
+    image VARCHAR(1024),
+    link VARCHAR(1024),
+    auto_download INT NOT NULL DEFAULT 3,
+    delete_played BOOLEAN NOT NULL DEFAULT TRUE,
+    last_polled_at DATETIME,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS podcast_episode (
+    id VARCHAR(255) PRIMARY KEY,
+    podcast_id VARCHAR(255) NOT NULL,
+    guid VARCHAR(1024) NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    description TEXT,
+    url VARCHAR(1024) NOT NULL,
+    mime_type VARCHAR(255),
+    duration INT,
+    published_at DATETIME,
+    path VARCHAR(1024),
+    played BOOLEAN NOT NULL DEFAULT FALSE,
+    played_at DATETIME,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS podcast_episode_guid ON podcast_episode (podcast_id, guid);
+CREATE INDEX IF NOT EXISTS podcast_episode_path ON podcast_episode (path);
@@ -0,0 +1,13 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS radio_station (
+    id VARCHAR(255) PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    url VARCHAR(1024) NOT NULL UNIQUE,
+    genre VARCHAR(255),
+    homepage VARCHAR(1024),
+    image VARCHAR(1024),
+    favourite BOOLEAN NOT NULL DEFAULT FALSE,
+    last_played_at DATETIME,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,2 @@
+-- Add migration script here
+ALTER TABLE track ADD COLUMN play_count INTEGER NOT NULL DEFAULT 0;
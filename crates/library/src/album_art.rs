@@ -1,80 +1,236 @@
-use std::io::Write;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
 
 use anyhow::Error;
-use lofty::{file::TaggedFileExt, probe::Probe, tag::Accessor};
+use image::{imageops::FilterType, ImageFormat};
+use lofty::{
+    file::TaggedFileExt,
+    picture::{MimeType, PictureType},
+    probe::Probe,
+};
+use sqlx::{Pool, Sqlite};
 
+use crate::repo;
+
+/// The sizes of the thumbnails generated next to every cover, in pixels.
+pub const THUMBNAIL_SIZES: [u32; 5] = [64, 128, 256, 512, 1024];
+
+/// Covers whose thumbnails failed are tried again after that delay, on the
+/// next scan saving them.
+const THUMBNAIL_RETRY: Duration = Duration::from_secs(60 * 60);
+
+/// The covers whose thumbnails were generated, `None`, or failed and when.
+static THUMBNAILS: Mutex<Option<HashMap<String, Option<Instant>>>> = Mutex::new(None);
+
+const COVER_NAMES: [&str; 5] = ["cover", "folder", "front", "album", "albumart"];
+const ARTIST_NAMES: [&str; 1] = ["artist"];
+const IMAGE_EXTENSIONS: [&str; 5] = ["jpg", "jpeg", "png", "webp", "gif"];
+
+pub fn covers_dir() -> Result<PathBuf, Error> {
+    let home = std::env::var("HOME")?;
+    let covers_path = format!("{}/.config/rockbox.org/covers", home);
+    Ok(PathBuf::from(covers_path))
+}
+
+/// Saves the cover of a track, its embedded front cover first and otherwise
+/// an image of its folder (cover.jpg, folder.png, front.jpg...). Returns the
+/// filename of the cover in the covers directory.
+///
+/// Covers are named after the md5 of their content, the albums sharing the
+/// same art share the same file and albums with the same title no longer
+/// overwrite each other's cover.
 pub fn extract_and_save_album_cover(track_path: &str) -> Result<Option<String>, Error> {
-    let tagged_file = match Probe::open(track_path)
-        .expect("ERROR: Bad path provided!")
-        .read()
-    {
+    let picture = match embedded_picture(track_path) {
+        Some(picture) => Some(picture),
+        None => Path::new(track_path)
+            .parent()
+            .and_then(|dir| folder_image(dir, &COVER_NAMES)),
+    };
+    match picture {
+        Some((data, extension)) => save_image(&data, extension).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Saves the image of the artist of a track, an artist.jpg in the album
+/// folder or in the artist folder above it.
+pub fn extract_and_save_artist_image(track_path: &str) -> Result<Option<String>, Error> {
+    let image = Path::new(track_path)
+        .ancestors()
+        .skip(1)
+        .take(2)
+        .find_map(|dir| folder_image(dir, &ARTIST_NAMES));
+    match image {
+        Some((data, extension)) => save_image(&data, extension).map(Some),
+        None => Ok(None),
+    }
+}
+
+fn embedded_picture(track_path: &str) -> Option<(Vec<u8>, &'static str)> {
+    let tagged_file = match Probe::open(track_path).and_then(|probe| probe.read()) {
         Ok(tagged_file) => tagged_file,
         Err(e) => {
             println!("Error opening file: {}", e);
-            return Ok(None);
+            return None;
         }
     };
 
-    let primary_tag = tagged_file.primary_tag();
-    let tag = match primary_tag {
-        Some(tag) => tag,
-        None => {
-            println!("No tag found in file: {}", track_path);
-            return Ok(None);
-        }
+    let pictures = tagged_file
+        .tags()
+        .iter()
+        .flat_map(|tag| tag.pictures())
+        .collect::<Vec<_>>();
+    let picture = pictures
+        .iter()
+        .find(|picture| picture.pic_type() == PictureType::CoverFront)
+        .or(pictures.first())?;
+
+    let extension = match picture.mime_type() {
+        Some(MimeType::Jpeg) => "jpg",
+        Some(MimeType::Png) => "png",
+        Some(MimeType::Bmp) => "bmp",
+        Some(MimeType::Gif) => "gif",
+        Some(MimeType::Tiff) => "tiff",
+        _ => match image::guess_format(picture.data()) {
+            Ok(format) => *format.extensions_str().first()?,
+            Err(_) => {
+                eprintln!("Unsupported picture format: {}", track_path);
+                return None;
+            }
+        },
     };
+    Some((picture.data().to_vec(), extension))
+}
+
+/// Finds an image named after one of `names`, whatever its case.
+fn folder_image(dir: &Path, names: &[&str]) -> Option<(Vec<u8>, &'static str)> {
+    let mut images = std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter_map(|path| {
+            let stem = path.file_stem()?.to_str()?.to_lowercase();
+            let extension = path.extension()?.to_str()?.to_lowercase();
+            let name = names.iter().position(|name| *name == stem)?;
+            let extension = IMAGE_EXTENSIONS.into_iter().find(|e| *e == extension)?;
+            Some((name, path, extension))
+        })
+        .collect::<Vec<_>>();
+    images.sort();
 
-    let pictures = tag.pictures();
-    if pictures.len() > 0 {
-        let home = std::env::var("HOME")?;
-        let covers_path = format!("{}/.config/rockbox.org/covers", home);
-        std::fs::create_dir_all(&covers_path)?;
-        let picture = &pictures[0];
+    images
+        .into_iter()
+        .find_map(|(_, path, extension)| Some((std::fs::read(path).ok()?, extension)))
+}
 
-        if tag.album().is_none() {
-            println!("No album found in file: {}", track_path);
-            return Ok(None);
+fn save_image(data: &[u8], extension: &str) -> Result<String, Error> {
+    let covers_path = covers_dir()?;
+    std::fs::create_dir_all(&covers_path)?;
+
+    let hash = format!("{:x}", md5::compute(data));
+    let filename = format!("{}.{}", hash, extension);
+    let path = covers_path.join(&filename);
+    if !path.exists() {
+        std::fs::write(&path, data)?;
+    }
+    ensure_thumbnails(&covers_path, &hash, data);
+    Ok(filename)
+}
+
+/// Generates the thumbnails of a cover missing them, the ones that failed
+/// are tried again once [`THUMBNAIL_RETRY`] has passed.
+fn ensure_thumbnails(covers_path: &Path, hash: &str, data: &[u8]) {
+    let mut thumbnails = THUMBNAILS.lock().unwrap();
+    let thumbnails = thumbnails.get_or_insert_with(HashMap::new);
+    match thumbnails.get(hash) {
+        Some(None) => return,
+        Some(Some(failed)) if failed.elapsed() < THUMBNAIL_RETRY => return,
+        _ => {}
+    }
+    // the smallest thumbnail is saved last, the others are there with it
+    let smallest = covers_path
+        .join("thumbnails")
+        .join(THUMBNAIL_SIZES[0].to_string())
+        .join(format!("{}.jpg", hash));
+    let saved = match smallest.is_file() {
+        true => Ok(()),
+        false => save_thumbnails(covers_path, hash, data),
+    };
+    match saved {
+        Ok(()) => thumbnails.insert(hash.to_string(), None),
+        Err(e) => {
+            eprintln!("Error generating thumbnails for {}: {}", hash, e);
+            thumbnails.insert(hash.to_string(), Some(Instant::now()))
         }
+    };
+}
 
-        let album = md5::compute(tag.album().unwrap().as_bytes());
-        let filename = format!("{}/{:x}", covers_path, album);
-        match picture.mime_type() {
-            Some(lofty::picture::MimeType::Jpeg) => {
-                let filename = format!("{}.jpg", filename);
-                let mut file = std::fs::File::create(filename)?;
-                file.write_all(picture.data())?;
-                Ok(Some(format!("{:x}.jpg", album)))
-            }
-            Some(lofty::picture::MimeType::Png) => {
-                let filename = format!("{}.png", filename);
-                let mut file = std::fs::File::create(filename)?;
-                file.write_all(picture.data())?;
-                Ok(Some(format!("{:x}.png", album)))
-            }
-            Some(lofty::picture::MimeType::Bmp) => {
-                let filename = format!("{}.bmp", filename);
-                let mut file = std::fs::File::create(filename)?;
-                file.write_all(picture.data())?;
-                Ok(Some(format!("{:x}.bmp", album)))
-            }
-            Some(lofty::picture::MimeType::Gif) => {
-                let filename = format!("{}.gif", filename);
-                let mut file = std::fs::File::create(filename)?;
-                file.write_all(picture.data())?;
-                Ok(Some(format!("{:x}.gif", album)))
-            }
-            Some(lofty::picture::MimeType::Tiff) => {
-                let filename = format!("{}.tiff", filename);
-                let mut file = std::fs::File::create(filename)?;
-                file.write_all(picture.data())?;
-                Ok(Some(format!("{:x}.tiff", album)))
-            }
-            _ => {
-                println!("Unsupported picture format");
-                Ok(None)
-            }
+/// Thumbnails are saved as jpg in `covers/thumbnails/<size>`, only for the
+/// sizes smaller than the cover, the largest first.
+fn save_thumbnails(covers_path: &Path, hash: &str, data: &[u8]) -> Result<(), Error> {
+    let image = image::load_from_memory(data)?;
+    for size in THUMBNAIL_SIZES.into_iter().rev() {
+        if image.width() <= size && image.height() <= size {
+            continue;
         }
-    } else {
-        Ok(None)
+        let dir = covers_path.join("thumbnails").join(size.to_string());
+        std::fs::create_dir_all(&dir)?;
+        image
+            .resize(size, size, FilterType::Lanczos3)
+            .to_rgb8()
+            .save_with_format(dir.join(format!("{}.jpg", hash)), ImageFormat::Jpeg)?;
+    }
+    Ok(())
+}
+
+/// The path of a cover, or of its smallest thumbnail at least `size` pixels
+/// wide. Covers smaller than `size` are served as is.
+pub fn cover_path(filename: &str, size: Option<u32>) -> Option<PathBuf> {
+    // only files of the covers directory are served
+    if Path::new(filename).file_name()?.to_str()? != filename {
+        return None;
     }
+    let covers_path = covers_dir().ok()?;
+    let path = covers_path.join(filename);
+    if !path.is_file() {
+        return None;
+    }
+
+    let hash = path.file_stem()?.to_str()?;
+    let thumbnail = size.and_then(|size| {
+        THUMBNAIL_SIZES
+            .into_iter()
+            .filter(|s| *s >= size)
+            .map(|s| {
+                covers_path
+                    .join("thumbnails")
+                    .join(s.to_string())
+                    .join(format!("{}.jpg", hash))
+            })
+            .find(|thumbnail| thumbnail.is_file())
+    });
+    Some(thumbnail.unwrap_or(path))
+}
+
+/// Resolves `/covers/{id}`: `id` is the filename of a cover, an album id or
+/// an artist id.
+pub async fn find_cover(
+    pool: Pool<Sqlite>,
+    id: &str,
+    size: Option<u32>,
+) -> Result<Option<PathBuf>, Error> {
+    let filename = match id.contains('.') {
+        true => Some(id.to_string()),
+        false => match repo::album::find(pool.clone(), id).await? {
+            Some(album) => album.album_art,
+            None => repo::artist::find(pool, id)
+                .await?
+                .and_then(|artist| artist.image),
+        },
+    };
+    Ok(filename.and_then(|filename| cover_path(&filename, size)))
 }
@@ -1,8 +1,13 @@
-use crate::album_art::extract_and_save_album_cover;
+use crate::album_art::{extract_and_save_album_cover, extract_and_save_artist_image};
 use crate::entity::album::Album;
 use crate::entity::album_tracks::AlbumTracks;
 use crate::entity::artist::Artist;
 use crate::entity::artist_tracks::ArtistTracks;
+use crate::entity::genre::Genre;
+use crate::entity::genre_tracks::GenreTracks;
+use crate::entity::lyrics::Lyrics;
+use crate::lrc;
+use crate::tags::{read_tags, Tags, VARIOUS_ARTISTS};
 use crate::{entity::track::Track, repo};
 use anyhow::Error;
 use chrono::Utc;
@@ -10,7 +15,9 @@ use futures::future::BoxFuture;
 use futures::stream::{FuturesUnordered, StreamExt};
 use owo_colors::OwoColorize;
 use rockbox_sys as rb;
+use rockbox_sys::types::mp3_entry::Mp3Entry;
 use sqlx::{Pool, Sqlite};
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use tokio::fs;
 
@@ -19,12 +26,28 @@ const AUDIO_EXTENSIONS: [&str; 18] = [
     "opus", "spx", "sid", "ape", "wma",
 ];
 
+const GENRE_SEPARATORS: [char; 3] = [';', '/', '\0'];
+
+/// Splits a multi-valued genre tag ("Rock; Pop", "Jazz/Fusion") into unique
+/// genre names, duplicates are compared case-insensitively.
+pub fn split_genres(genre: &str) -> Vec<String> {
+    let mut genres: Vec<String> = vec![];
+    for name in genre.split(GENRE_SEPARATORS).map(str::trim) {
+        if name.is_empty() || genres.iter().any(|g| g.eq_ignore_ascii_case(name)) {
+            continue;
+        }
+        genres.push(name.to_string());
+    }
+    genres
+}
+
 pub fn scan_audio_files(
     pool: Pool<Sqlite>,
     audio_dir: PathBuf,
 ) -> BoxFuture<'static, Result<Vec<PathBuf>, Error>> {
     Box::pin(async move {
         let mut result = Vec::new();
+        let mut files = Vec::new();
         let mut dir = fs::read_dir(audio_dir).await?;
         let mut futures = FuturesUnordered::new();
         while let Some(entry) = dir.next_entry().await? {
@@ -44,13 +67,15 @@ pub fn scan_audio_files(
                 {
                     continue;
                 }
-                save_audio_metadata(pool.clone(), path).await?;
-
-                let path = path.into();
-                result.push(path);
+                files.push(path.to_string());
+                result.push(path.into());
             }
         }
 
+        // the files of a folder are saved together so that compilations
+        // without an album artist can be told apart from regular albums
+        save_audio_files(pool.clone(), &files).await?;
+
         while let Some(Ok(sub_result)) = futures.next().await {
             match sub_result {
                 Ok(paths) => result.extend(paths),
@@ -63,95 +88,239 @@ pub fn scan_audio_files(
 }
 
 pub async fn save_audio_metadata(pool: Pool<Sqlite>, path: &str) -> Result<(), Error> {
-    if !AUDIO_EXTENSIONS
-        .into_iter()
-        .any(|ext| path.ends_with(&format!(".{}", ext)))
-    {
-        return Ok(());
+    save_audio_files(pool, &[path.to_string()]).await
+}
+
+/// Saves the metadata of files from the same folder, an album without album
+/// artist whose tracks have different main artists is a compilation.
+pub async fn save_audio_files(pool: Pool<Sqlite>, paths: &[String]) -> Result<(), Error> {
+    let mut entries = vec![];
+    for path in paths {
+        if !AUDIO_EXTENSIONS
+            .into_iter()
+            .any(|ext| path.ends_with(&format!(".{}", ext)))
+        {
+            continue;
+        }
+
+        let filename = path.split('/').next_back().unwrap();
+        let dir = path.replace(filename, "");
+        println!(
+            "{} {}{}",
+            "Found".bright_green(),
+            dir,
+            filename.bright_yellow()
+        );
+        let entry = rb::metadata::get_metadata(-1, path);
+        let tags = read_tags(path, &entry.artist);
+        entries.push((entry, tags));
     }
 
-    let filename = path.split('/').last().unwrap();
-    let dir = path.replace(filename, "");
-    println!(
-        "{} {}{}",
-        "Found".bright_green(),
-        dir,
-        filename.bright_yellow()
-    );
-    let entry = rb::metadata::get_metadata(-1, path);
+    let mut album_artists: HashMap<String, HashSet<String>> = HashMap::new();
+    for (entry, tags) in entries.iter() {
+        if entry.albumartist.is_empty() && !entry.album.is_empty() {
+            album_artists
+                .entry(entry.album.clone())
+                .or_default()
+                .insert(
+                    tags.credits
+                        .main_artist()
+                        .unwrap_or_default()
+                        .to_lowercase(),
+                );
+        }
+    }
+
+    for (entry, mut tags) in entries {
+        if let Some(artists) = album_artists.get(&entry.album) {
+            tags.credits.compilation = tags.credits.compilation || artists.len() > 1;
+        }
+        save_entry(pool.clone(), entry, tags).await?;
+    }
+
+    Ok(())
+}
+
+async fn save_entry(pool: Pool<Sqlite>, entry: Mp3Entry, tags: Tags) -> Result<(), Error> {
+    let Tags {
+        credits,
+        musicbrainz,
+        extended,
+    } = tags;
+    let compilation = credits.compilation && entry.albumartist.is_empty();
+    let album_artist = match (entry.albumartist.is_empty(), compilation) {
+        (false, _) => entry.albumartist.clone(),
+        (true, true) => VARIOUS_ARTISTS.to_string(),
+        (true, false) => credits
+            .main_artist()
+            .unwrap_or(entry.artist.as_str())
+            .to_string(),
+    };
+    let album_artist_musicbrainz_id = match (entry.albumartist.is_empty(), compilation) {
+        (false, _) => musicbrainz.album_artist_id.clone(),
+        (true, true) => None,
+        (true, false) => musicbrainz.artist_id(&credits, 0),
+    };
 
     let track_hash = format!("{:x}", md5::compute(entry.path.as_bytes()));
     let artist_id = cuid::cuid1()?;
     let album_id = cuid::cuid1()?;
     let album_md5 = format!(
         "{:x}",
-        md5::compute(format!("{}{}{}", entry.albumartist, entry.album, entry.year).as_bytes())
+        md5::compute(
+            format!(
+                "{}{}{}",
+                match compilation {
+                    true => VARIOUS_ARTISTS,
+                    false => entry.albumartist.as_str(),
+                },
+                entry.album,
+                entry.year
+            )
+            .as_bytes()
+        )
     );
     let artist_id = repo::artist::save(
         pool.clone(),
         Artist {
             id: artist_id.clone(),
-            name: match entry.albumartist.is_empty() {
-                true => entry.artist.clone(),
-                false => entry.albumartist.clone(),
-            },
+            name: album_artist.clone(),
             bio: None,
             image: None,
+            musicbrainz_id: album_artist_musicbrainz_id,
         },
     )
     .await?;
 
+    if !compilation {
+        save_artist_image(pool.clone(), &artist_id, &entry.path).await?;
+    }
+
+    let mut credited_ids = vec![];
+    for (index, name) in credits.artists.iter().enumerate() {
+        let credited_id = repo::artist::save(
+            pool.clone(),
+            Artist {
+                id: cuid::cuid1()?,
+                name: name.clone(),
+                bio: None,
+                image: None,
+                musicbrainz_id: musicbrainz.artist_id(&credits, index),
+            },
+        )
+        .await?;
+        credited_ids.push(credited_id);
+    }
+
     let album_art = extract_and_save_album_cover(&entry.path)?;
     let album_id = repo::album::save(
         pool.clone(),
         Album {
             id: album_id,
             title: entry.album.clone(),
-            artist: match entry.albumartist.is_empty() {
-                true => entry.artist.clone(),
-                false => entry.albumartist.clone(),
-            },
+            artist: album_artist.clone(),
             year: entry.year as u32,
             year_string: entry.year_string.clone(),
             album_art: album_art.clone(),
             md5: album_md5,
             artist_id: artist_id.clone(),
+            musicbrainz_id: musicbrainz.album_id.clone(),
+            musicbrainz_release_group_id: musicbrainz.release_group_id.clone(),
         },
     )
     .await?;
 
-    let track_id = repo::track::save(
-        pool.clone(),
-        Track {
-            id: cuid::cuid1()?,
-            path: entry.path.clone(),
-            title: entry.title,
-            artist: entry.artist.clone(),
-            album: entry.album,
-            genre: match entry.genre_string.as_str() {
-                "" => None,
-                _ => Some(entry.genre_string),
-            },
-            year: Some(entry.year as u32),
-            track_number: Some(entry.tracknum as u32),
-            disc_number: entry.discnum as u32,
-            year_string: Some(entry.year_string),
-            composer: entry.composer,
-            album_artist: entry.albumartist.clone(),
-            bitrate: entry.bitrate,
-            frequency: entry.frequency as u32,
-            filesize: entry.filesize as u32,
-            length: entry.length as u32,
-            md5: track_hash,
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
-            artist_id: artist_id.clone(),
-            album_id: album_id.clone(),
-            album_art,
-            ..Default::default()
+    // albums scanned before keep their art until it is found again
+    if let Some(album_art) = &album_art {
+        repo::album::update_album_art(pool.clone(), &album_id, album_art).await?;
+    }
+
+    let track = Track {
+        id: cuid::cuid1()?,
+        path: entry.path.clone(),
+        title: entry.title,
+        artist: entry.artist.clone(),
+        album: entry.album,
+        genre: match entry.genre_string.as_str() {
+            "" => None,
+            _ => Some(entry.genre_string.clone()),
         },
-    )
-    .await?;
+        year: Some(entry.year as u32),
+        track_number: Some(entry.tracknum as u32),
+        disc_number: entry.discnum as u32,
+        year_string: Some(entry.year_string),
+        composer: entry.composer,
+        album_artist: match compilation {
+            true => VARIOUS_ARTISTS.to_string(),
+            false => entry.albumartist.clone(),
+        },
+        bitrate: entry.bitrate,
+        frequency: entry.frequency as u32,
+        filesize: entry.filesize as u32,
+        length: entry.length as u32,
+        md5: track_hash,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        // the tracks of a compilation belong to their own artist
+        artist_id: match compilation {
+            true => credited_ids.first().unwrap_or(&artist_id).clone(),
+            false => artist_id.clone(),
+        },
+        album_id: album_id.clone(),
+        album_art,
+        // rockbox reads the recording id from the UFID frame of mp3 files
+        musicbrainz_id: musicbrainz
+            .track_id
+            .clone()
+            .or(match entry.mb_track_id.as_str() {
+                "" => None,
+                id => Some(id.to_string()),
+            }),
+        replaygain_track_gain: extended.replaygain_track_gain,
+        replaygain_track_peak: extended.replaygain_track_peak,
+        replaygain_album_gain: extended.replaygain_album_gain,
+        replaygain_album_peak: extended.replaygain_album_peak,
+        bpm: extended.bpm,
+        rating: extended.rating,
+        comment: extended.comment,
+        label: extended.label,
+        isrc: extended.isrc,
+        original_date: extended.original_date,
+        title_sort: extended.title_sort,
+        artist_sort: extended.artist_sort,
+        album_sort: extended.album_sort,
+        album_artist_sort: extended.album_artist_sort,
+        composer_sort: extended.composer_sort,
+        ..Default::default()
+    };
+    let track_id = repo::track::save(pool.clone(), track.clone()).await?;
+    repo::track::update_tags(pool.clone(), &track_id, &track).await?;
+    if let Some(album_art) = &track.album_art {
+        repo::track::update_album_art(pool.clone(), &track.album_id, album_art).await?;
+    }
+
+    // lyrics written in LRC are kept as plain text next to their timestamps
+    let synced = lrc::read_synced_lyrics(&entry.path, extended.lyrics.as_deref());
+    let lyrics = match (&synced, extended.lyrics) {
+        (Some(lines), None) => Some(lrc::to_text(lines)),
+        (Some(lines), Some(text)) if lrc::is_synced(&text) => Some(lrc::to_text(lines)),
+        (_, text) => text,
+    };
+    match lyrics {
+        Some(lyrics) => {
+            repo::lyrics::save(
+                pool.clone(),
+                Lyrics {
+                    id: cuid::cuid1()?,
+                    track_id: track_id.clone(),
+                    lyrics,
+                    synced: synced.map(|lines| lrc::to_lrc(&lines)),
+                },
+            )
+            .await?
+        }
+        None => repo::lyrics::delete_by_track(pool.clone(), &track_id).await?,
+    }
 
     repo::album_tracks::save(
         pool.clone(),
@@ -163,13 +332,76 @@ pub async fn save_audio_metadata(pool: Pool<Sqlite>, path: &str) -> Result<(), E
     )
     .await?;
 
-    repo::artist_tracks::save(
-        pool.clone(),
-        ArtistTracks {
-            id: cuid::cuid1()?,
-            artist_id,
-            track_id,
-        },
+    // every credited artist gets the track, so featured artists list it too
+    for artist_id in std::iter::once(artist_id).chain(credited_ids) {
+        repo::artist_tracks::save(
+            pool.clone(),
+            ArtistTracks {
+                id: cuid::cuid1()?,
+                artist_id,
+                track_id: track_id.clone(),
+            },
+        )
+        .await?;
+    }
+
+    save_genres(pool, &track_id, &entry.genre_string).await?;
+
+    Ok(())
+}
+
+/// Artists without an image get the artist.jpg found next to their files.
+async fn save_artist_image(pool: Pool<Sqlite>, artist_id: &str, path: &str) -> Result<(), Error> {
+    match repo::artist::find(pool.clone(), artist_id).await? {
+        Some(artist) if artist.image.is_none() => {}
+        _ => return Ok(()),
+    }
+    if let Some(image) = extract_and_save_artist_image(path)? {
+        repo::artist::update_image(pool, artist_id, &image).await?;
+    }
+    Ok(())
+}
+
+/// Links the track to its genres, replacing the links of a previous scan so
+/// retagged files don't keep their old genres. The first genre is the one
+/// stored in `track.genre_id`.
+async fn save_genres(pool: Pool<Sqlite>, track_id: &str, genre: &str) -> Result<(), Error> {
+    repo::genre_tracks::delete_by_track(pool.clone(), track_id).await?;
+
+    let mut genre_ids = vec![];
+    for name in split_genres(genre) {
+        let genre_id = repo::genre::save(
+            pool.clone(),
+            Genre {
+                id: cuid::cuid1()?,
+                name,
+                description: None,
+                image: None,
+            },
+        )
+        .await?;
+
+        repo::genre_tracks::save(
+            pool.clone(),
+            GenreTracks {
+                id: cuid::cuid1()?,
+                genre_id: genre_id.clone(),
+                track_id: track_id.to_string(),
+            },
+        )
+        .await?;
+        genre_ids.push(genre_id);
+    }
+
+    let genre = match genre {
+        "" => None,
+        _ => Some(genre),
+    };
+    repo::track::update_genre(
+        pool,
+        track_id,
+        genre,
+        genre_ids.first().map(|id| id.as_str()),
     )
     .await?;
 
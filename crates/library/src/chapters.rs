@@ -0,0 +1,524 @@
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use anyhow::{anyhow, Error};
+use serde::{Deserialize, Serialize};
+
+/// Files with chapters, audiobooks are usually `.m4b`.
+const EXTENSIONS: [&str; 3] = ["m4b", "m4a", "mp4"];
+
+/// Larger movie boxes are not read, they hold no chapters worth it.
+const MAX_MOOV_SIZE: u64 = 64 * 1024 * 1024;
+
+/// The chapter track has a sample per chapter, the counts of a damaged file
+/// are not followed further.
+const MAX_CHAPTERS: usize = 10_000;
+
+/// A text sample is its length on two bytes then at most that many bytes.
+const MAX_TEXT_SAMPLE: u32 = 2 + u16::MAX as u32;
+
+/// A chapter of an audiobook, times in milliseconds.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Chapter {
+    pub index: usize,
+    pub title: String,
+    pub start: u64,
+    pub end: u64,
+}
+
+/// Reads the chapter markers of an MP4 file, from its QuickTime chapter
+/// track or else from its Nero `chpl` box. Other files have none. The last
+/// chapter ends at `length`, in milliseconds, when the file has no duration.
+pub fn read(path: &str, length: u64) -> Result<Vec<Chapter>, Error> {
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase());
+    if !EXTENSIONS.iter().any(|e| Some(*e) == extension.as_deref()) {
+        return Ok(vec![]);
+    }
+
+    let mut file = File::open(path)?;
+    let moov = match read_moov(&mut file)? {
+        Some(moov) => moov,
+        None => return Ok(vec![]),
+    };
+    let duration = child(&moov, b"mvhd")
+        .and_then(media_duration)
+        .filter(|duration| *duration > 0)
+        .unwrap_or(length);
+
+    let mut markers = match quicktime_chapters(&mut file, &moov)? {
+        markers if !markers.is_empty() => markers,
+        _ => child(&moov, b"udta")
+            .and_then(|udta| child(udta, b"chpl"))
+            .map(nero_chapters)
+            .unwrap_or_default(),
+    };
+    markers.sort_by_key(|(start, _)| *start);
+
+    let ends: Vec<u64> = markers
+        .iter()
+        .skip(1)
+        .map(|(start, _)| *start)
+        .chain([duration])
+        .collect();
+    Ok(markers
+        .into_iter()
+        .zip(ends)
+        .enumerate()
+        .map(|(index, ((start, title), end))| Chapter {
+            index,
+            title: match title.is_empty() {
+                true => format!("Chapter {}", index + 1),
+                false => title,
+            },
+            start,
+            end,
+        })
+        .collect())
+}
+
+fn read_moov(file: &mut File) -> Result<Option<Vec<u8>>, Error> {
+    let length = file.metadata()?.len();
+    let mut position: u64 = 0;
+    while length.saturating_sub(position) >= 8 {
+        file.seek(SeekFrom::Start(position))?;
+        let mut header = [0u8; 16];
+        file.read_exact(&mut header[..8])?;
+        let mut size = u32::from_be_bytes(header[..4].try_into()?) as u64;
+        let mut header_size = 8;
+        if size == 1 {
+            file.read_exact(&mut header[8..])?;
+            size = u64::from_be_bytes(header[8..].try_into()?);
+            header_size = 16;
+        } else if size == 0 {
+            size = length - position;
+        }
+        if size < header_size {
+            return Err(anyhow!("Invalid MP4 box size"));
+        }
+        if &header[4..8] == b"moov" {
+            if size > MAX_MOOV_SIZE {
+                return Ok(None);
+            }
+            let mut moov = vec![0u8; (size - header_size) as usize];
+            file.read_exact(&mut moov)?;
+            return Ok(Some(moov));
+        }
+        match position.checked_add(size) {
+            Some(next) => position = next,
+            None => break,
+        }
+    }
+    Ok(None)
+}
+
+/// The boxes in the payload of a container box, with their payload.
+fn children(data: &[u8]) -> Vec<([u8; 4], &[u8])> {
+    let mut boxes = vec![];
+    let mut position = 0;
+    while position + 8 <= data.len() {
+        let mut size = be_u32(data, position).unwrap_or(0) as usize;
+        let kind: [u8; 4] = data[position + 4..position + 8].try_into().unwrap();
+        let mut header_size = 8;
+        if size == 1 {
+            size = be_u64(data, position + 8).unwrap_or(0) as usize;
+            header_size = 16;
+        } else if size == 0 {
+            size = data.len() - position;
+        }
+        let end = match position.checked_add(size) {
+            Some(end) if size >= header_size && end <= data.len() => end,
+            _ => break,
+        };
+        boxes.push((kind, &data[position + header_size..end]));
+        position = end;
+    }
+    boxes
+}
+
+fn child<'a>(data: &'a [u8], kind: &[u8; 4]) -> Option<&'a [u8]> {
+    children(data)
+        .into_iter()
+        .find(|(k, _)| k == kind)
+        .map(|(_, payload)| payload)
+}
+
+/// The duration in milliseconds of a `mvhd` or `mdhd` box.
+fn media_duration(data: &[u8]) -> Option<u64> {
+    let (timescale, duration) = timescale_duration(data)?;
+    Some(duration * 1000 / timescale)
+}
+
+fn timescale_duration(data: &[u8]) -> Option<(u64, u64)> {
+    let (timescale, duration) = match data.first()? {
+        1 => (be_u32(data, 20)?, be_u64(data, 24)?),
+        _ => (be_u32(data, 12)?, be_u32(data, 16)? as u64),
+    };
+    match timescale {
+        0 => None,
+        timescale => Some((timescale as u64, duration)),
+    }
+}
+
+/// Chapters stored as the text samples of a track referenced by a `chap`
+/// track reference, like iTunes and ffmpeg write them.
+fn quicktime_chapters(file: &mut File, moov: &[u8]) -> Result<Vec<(u64, String)>, Error> {
+    let tracks: Vec<&[u8]> = children(moov)
+        .into_iter()
+        .filter(|(kind, _)| kind == b"trak")
+        .map(|(_, payload)| payload)
+        .collect();
+    let chapter_track_ids: Vec<u32> = tracks
+        .iter()
+        .filter_map(|trak| child(trak, b"tref"))
+        .filter_map(|tref| child(tref, b"chap"))
+        .flat_map(|chap| {
+            chap.chunks_exact(4)
+                .map(|id| u32::from_be_bytes(id.try_into().unwrap()))
+        })
+        .collect();
+    let Some(trak) = tracks.into_iter().find(|trak| {
+        child(trak, b"tkhd")
+            .and_then(track_id)
+            .map(|id| chapter_track_ids.contains(&id))
+            .unwrap_or(false)
+    }) else {
+        return Ok(vec![]);
+    };
+
+    let Some(mdia) = child(trak, b"mdia") else {
+        return Ok(vec![]);
+    };
+    let Some((timescale, _)) = child(mdia, b"mdhd").and_then(timescale_duration) else {
+        return Ok(vec![]);
+    };
+    let Some(stbl) = child(mdia, b"minf").and_then(|minf| child(minf, b"stbl")) else {
+        return Ok(vec![]);
+    };
+
+    let sizes = sample_sizes(child(stbl, b"stsz").unwrap_or_default());
+    let times = sample_times(child(stbl, b"stts").unwrap_or_default(), sizes.len());
+    let offsets = sample_offsets(stbl, &sizes);
+
+    let mut chapters = vec![];
+    for ((time, size), offset) in times.into_iter().zip(sizes).zip(offsets) {
+        let mut sample = vec![0u8; size.min(MAX_TEXT_SAMPLE) as usize];
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(&mut sample)?;
+        chapters.push((time * 1000 / timescale, text_sample(&sample)));
+    }
+    Ok(chapters)
+}
+
+fn track_id(tkhd: &[u8]) -> Option<u32> {
+    match tkhd.first()? {
+        1 => be_u32(tkhd, 20),
+        _ => be_u32(tkhd, 12),
+    }
+}
+
+/// The number of entries of a table box, at most the ones its size holds.
+fn entries(data: &[u8], header: usize, entry: usize) -> usize {
+    let count = be_u32(data, header - 4).unwrap_or(0) as usize;
+    count.min(data.len().saturating_sub(header) / entry)
+}
+
+/// The start of the first `samples` samples, in the timescale of the track.
+fn sample_times(stts: &[u8], samples: usize) -> Vec<u64> {
+    let mut times = vec![];
+    let mut time = 0;
+    for entry in 0..entries(stts, 8, 8) {
+        let (Some(count), Some(delta)) =
+            (be_u32(stts, 8 + entry * 8), be_u32(stts, 12 + entry * 8))
+        else {
+            break;
+        };
+        for _ in 0..(count as usize).min(samples - times.len()) {
+            times.push(time);
+            time += delta as u64;
+        }
+    }
+    times
+}
+
+fn sample_sizes(stsz: &[u8]) -> Vec<u32> {
+    let (Some(size), Some(count)) = (be_u32(stsz, 4), be_u32(stsz, 8)) else {
+        return vec![];
+    };
+    match size {
+        0 => (0..entries(stsz, 12, 4).min(MAX_CHAPTERS))
+            .map_while(|sample| be_u32(stsz, 12 + sample * 4))
+            .collect(),
+        size => vec![size; (count as usize).min(MAX_CHAPTERS)],
+    }
+}
+
+/// The offset in the file of each sample, from the chunk offsets and the
+/// samples per chunk.
+fn sample_offsets(stbl: &[u8], sizes: &[u32]) -> Vec<u64> {
+    let chunks: Vec<u64> = match (child(stbl, b"stco"), child(stbl, b"co64")) {
+        (Some(stco), _) => (0..entries(stco, 8, 4))
+            .map_while(|chunk| be_u32(stco, 8 + chunk * 4).map(|offset| offset as u64))
+            .collect(),
+        (None, Some(co64)) => (0..entries(co64, 8, 8))
+            .map_while(|chunk| be_u64(co64, 8 + chunk * 8))
+            .collect(),
+        (None, None) => vec![],
+    };
+    // (first chunk, samples per chunk), chunks are numbered from 1
+    let stsc = child(stbl, b"stsc").unwrap_or_default();
+    let runs: Vec<(u32, u32)> = (0..entries(stsc, 8, 12))
+        .map_while(|run| Some((be_u32(stsc, 8 + run * 12)?, be_u32(stsc, 12 + run * 12)?)))
+        .collect();
+
+    let mut offsets = vec![];
+    let mut sample = 0;
+    for (chunk, offset) in chunks.into_iter().enumerate() {
+        let samples = runs
+            .iter()
+            .take_while(|(first, _)| *first as usize <= chunk + 1)
+            .last()
+            .map(|(_, samples)| *samples)
+            .unwrap_or(1);
+        let mut offset = offset;
+        for _ in 0..samples {
+            let Some(size) = sizes.get(sample) else {
+                return offsets;
+            };
+            offsets.push(offset);
+            offset += *size as u64;
+            sample += 1;
+        }
+    }
+    offsets
+}
+
+/// A text sample is its length on two bytes then the text, in UTF-8 or in
+/// UTF-16 with a byte order mark.
+fn text_sample(sample: &[u8]) -> String {
+    let length = sample
+        .get(..2)
+        .map(|length| u16::from_be_bytes([length[0], length[1]]) as usize)
+        .unwrap_or(0);
+    let text = &sample[2.min(sample.len())..(2 + length).min(sample.len())];
+    match text {
+        [0xfe, 0xff, utf16 @ ..] => String::from_utf16_lossy(
+            &utf16
+                .chunks_exact(2)
+                .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                .collect::<Vec<_>>(),
+        ),
+        [0xff, 0xfe, utf16 @ ..] => String::from_utf16_lossy(
+            &utf16
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .collect::<Vec<_>>(),
+        ),
+        text => String::from_utf8_lossy(text).into_owned(),
+    }
+    .trim()
+    .to_string()
+}
+
+/// Nero chapters, starts in 100 nanoseconds units followed by their titles.
+fn nero_chapters(chpl: &[u8]) -> Vec<(u64, String)> {
+    // version and flags, then 4 reserved bytes from version 1
+    let mut position = match chpl.first() {
+        Some(1) => 8,
+        _ => 4,
+    };
+    let Some(count) = chpl.get(position) else {
+        return vec![];
+    };
+    position += 1;
+
+    let mut chapters = vec![];
+    for _ in 0..*count {
+        let (Some(start), Some(length)) = (be_u64(chpl, position), chpl.get(position + 8)) else {
+            break;
+        };
+        let title = position + 9;
+        let Some(title) = chpl.get(title..title + *length as usize) else {
+            break;
+        };
+        chapters.push((
+            start / 10_000,
+            String::from_utf8_lossy(title).trim().to_string(),
+        ));
+        position += 9 + *length as usize;
+    }
+    chapters
+}
+
+fn be_u32(data: &[u8], position: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(
+        data.get(position..position + 4)?.try_into().ok()?,
+    ))
+}
+
+fn be_u64(data: &[u8], position: usize) -> Option<u64> {
+    Some(u64::from_be_bytes(
+        data.get(position..position + 8)?.try_into().ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mp4_box(kind: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+        let mut data = ((8 + payload.len()) as u32).to_be_bytes().to_vec();
+        data.extend(kind);
+        data.extend(payload);
+        data
+    }
+
+    /// The payload of a full box, after its version and flags.
+    fn full_box(kind: &[u8; 4], fields: &[u32]) -> Vec<u8> {
+        let payload: Vec<u8> = [0]
+            .iter()
+            .chain(fields)
+            .flat_map(|f| f.to_be_bytes())
+            .collect();
+        mp4_box(kind, &payload)
+    }
+
+    fn text(title: &str) -> Vec<u8> {
+        let mut sample = (title.len() as u16).to_be_bytes().to_vec();
+        sample.extend(title.as_bytes());
+        sample
+    }
+
+    #[test]
+    fn stops_at_box_sizes_out_of_the_data() {
+        let mut data = mp4_box(b"free", &[0; 4]);
+        data.extend([0, 0, 0, 1]);
+        data.extend(b"huge");
+        data.extend(u64::MAX.to_be_bytes());
+        assert_eq!(children(&data), [(*b"free", &[0u8; 4][..])]);
+
+        let mut data = mp4_box(b"free", &[]);
+        data.extend(1000u32.to_be_bytes());
+        data.extend(b"long");
+        assert_eq!(children(&data).len(), 1);
+    }
+
+    #[test]
+    fn reads_the_sample_tables() {
+        let stts = full_box(b"stts", &[2, 2, 500, 1, 1000]);
+        assert_eq!(sample_times(&stts[8..], 3), [0, 500, 1000]);
+        // the samples beyond the sizes are left out
+        assert_eq!(sample_times(&stts[8..], 2), [0, 500]);
+
+        let stsz = full_box(b"stsz", &[0, 3, 7, 2, 9]);
+        assert_eq!(sample_sizes(&stsz[8..]), [7, 2, 9]);
+        let stsz = full_box(b"stsz", &[4, 2]);
+        assert_eq!(sample_sizes(&stsz[8..]), [4, 4]);
+        // a count larger than the table
+        let stsz = full_box(b"stsz", &[0, 100, 7]);
+        assert_eq!(sample_sizes(&stsz[8..]), [7]);
+
+        // two samples in the first chunk, then one per chunk
+        let stbl = [
+            full_box(b"stsc", &[2, 1, 2, 1, 2, 1, 1]),
+            full_box(b"stco", &[2, 100, 500]),
+        ]
+        .concat();
+        assert_eq!(sample_offsets(&stbl, &[7, 2, 9]), [100, 107, 500]);
+    }
+
+    #[test]
+    fn reads_text_samples() {
+        assert_eq!(text_sample(&text(" Intro ")), "Intro");
+        assert_eq!(text_sample(&[0, 4, 0xfe, 0xff, 0, b'A']), "A");
+        assert_eq!(text_sample(&[0, 4, 0xff, 0xfe, b'B', 0]), "B");
+        // a length beyond the sample
+        assert_eq!(text_sample(&[0, 10, b'C']), "C");
+        assert_eq!(text_sample(&[0]), "");
+    }
+
+    #[test]
+    fn reads_nero_chapters() {
+        let mut chpl = vec![1, 0, 0, 0, 0, 0, 0, 0, 3];
+        for (start, title) in [(0u64, "One"), (600_000_000, "Two")] {
+            chpl.extend(start.to_be_bytes());
+            chpl.push(title.len() as u8);
+            chpl.extend(title.as_bytes());
+        }
+        // the third chapter is cut
+        chpl.extend([0, 0]);
+
+        assert_eq!(
+            nero_chapters(&chpl),
+            [(0, "One".to_string()), (60_000, "Two".to_string())]
+        );
+    }
+
+    #[test]
+    fn reads_the_chapter_track() {
+        let samples = [text("Intro"), text("")].concat();
+        let mdat = mp4_box(b"mdat", &samples);
+        let stbl = [
+            full_box(b"stts", &[1, 2, 60]),
+            full_box(b"stsz", &[0, 2, 7, 2]),
+            full_box(b"stsc", &[1, 1, 2, 1]),
+            full_box(b"stco", &[1, 8]),
+        ]
+        .concat();
+        let chapter_track = [
+            full_box(b"tkhd", &[0, 0, 2]),
+            mp4_box(
+                b"mdia",
+                &[
+                    full_box(b"mdhd", &[0, 0, 1, 0]),
+                    mp4_box(b"minf", &mp4_box(b"stbl", &stbl)),
+                ]
+                .concat(),
+            ),
+        ]
+        .concat();
+        let audio_track = [
+            full_box(b"tkhd", &[0, 0, 1]),
+            mp4_box(b"tref", &mp4_box(b"chap", &2u32.to_be_bytes())),
+        ]
+        .concat();
+        let moov = mp4_box(
+            b"moov",
+            &[
+                full_box(b"mvhd", &[0, 0, 1000, 150_000]),
+                mp4_box(b"trak", &audio_track),
+                mp4_box(b"trak", &chapter_track),
+            ]
+            .concat(),
+        );
+
+        let path =
+            std::env::temp_dir().join(format!("rockbox-chapters-{}.m4b", std::process::id()));
+        std::fs::write(&path, [mdat, moov].concat()).unwrap();
+        let chapters = read(path.to_str().unwrap(), 0).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            chapters,
+            [
+                Chapter {
+                    index: 0,
+                    title: "Intro".to_string(),
+                    start: 0,
+                    end: 60_000,
+                },
+                Chapter {
+                    index: 1,
+                    title: "Chapter 2".to_string(),
+                    start: 60_000,
+                    end: 150_000,
+                },
+            ]
+        );
+    }
+}
@@ -11,4 +11,10 @@ pub struct Album {
     pub album_art: Option<String>,
     pub md5: String,
     pub artist_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub musicbrainz_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub musicbrainz_release_group_id: Option<String>,
 }
@@ -8,4 +8,7 @@ pub struct Artist {
     pub bio: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub musicbrainz_id: Option<String>,
 }
@@ -0,0 +1,18 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A position in a track, playing it plays `source`, one of `track`,
+/// `album` or `directory`, from there. Bookmarks without a name are the
+/// resume positions saved while playing.
+#[derive(sqlx::FromRow, Default, Debug, Clone, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub id: String,
+    pub name: Option<String>,
+    pub source: String,
+    pub source_id: String,
+    pub path: String,
+    pub track_id: Option<String>,
+    pub elapsed: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
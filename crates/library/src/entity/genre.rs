@@ -1,7 +1,11 @@
-#[derive(sqlx::FromRow, Default)]
+use serde::{Deserialize, Serialize};
+
+#[derive(sqlx::FromRow, Default, Serialize, Deserialize, Clone)]
 pub struct Genre {
     pub id: String,
     pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub image: Option<String>,
 }
@@ -0,0 +1,6 @@
+#[derive(sqlx::FromRow, Default)]
+pub struct GenreTracks {
+    pub id: String,
+    pub genre_id: String,
+    pub track_id: String,
+}
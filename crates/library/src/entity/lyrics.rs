@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(sqlx::FromRow, Default, Serialize, Deserialize, Clone)]
+pub struct Lyrics {
+    pub id: String,
+    pub track_id: String,
+    pub lyrics: String,
+    /// The synchronized lyrics in LRC.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub synced: Option<String>,
+}
@@ -0,0 +1,21 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// An episode of a podcast, `url` is its enclosure and `path` the
+/// downloaded file. `duration` is in seconds.
+#[derive(sqlx::FromRow, Default, Debug, Clone, Serialize, Deserialize)]
+pub struct PodcastEpisode {
+    pub id: String,
+    pub podcast_id: String,
+    pub guid: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub url: String,
+    pub mime_type: Option<String>,
+    pub duration: Option<i32>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub path: Option<String>,
+    pub played: bool,
+    pub played_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
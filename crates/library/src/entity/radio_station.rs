@@ -0,0 +1,18 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// An internet radio station, `url` being its stream or a PLS or M3U
+/// playlist of it.
+#[derive(sqlx::FromRow, Default, Debug, Clone, Serialize, Deserialize)]
+pub struct RadioStation {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    pub genre: Option<String>,
+    pub homepage: Option<String>,
+    pub image: Option<String>,
+    pub favourite: bool,
+    pub last_played_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
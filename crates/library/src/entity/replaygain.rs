@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// The loudness of a track measured by the analysis, for files without
+/// ReplayGain tags. Loudnesses are in LUFS, gains in dB and peaks are the
+/// linear true peaks, 1.0 being full scale.
+#[derive(sqlx::FromRow, Default, Serialize, Deserialize, Clone, Debug)]
+pub struct ReplayGain {
+    pub id: String,
+    pub track_id: String,
+    pub track_loudness: f64,
+    pub track_gain: f64,
+    pub track_peak: f64,
+    pub album_loudness: Option<f64>,
+    pub album_gain: Option<f64>,
+    pub album_peak: Option<f64>,
+    pub analyzed_at: DateTime<Utc>,
+}
+
+/// The values applied when playing a file, by path.
+#[derive(sqlx::FromRow, Default, Serialize, Deserialize, Clone, Debug)]
+pub struct StoredReplayGain {
+    pub path: String,
+    pub track_gain: f64,
+    pub track_peak: f64,
+    pub album_gain: Option<f64>,
+    pub album_peak: Option<f64>,
+}
@@ -0,0 +1,21 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A playback started when `cron` matches, `source` is one of `playlist`,
+/// `album`, `artist`, `directory`, `liked_tracks`, `all_tracks` or `mix`.
+#[derive(sqlx::FromRow, Default, Debug, Clone, Serialize, Deserialize)]
+pub struct Schedule {
+    pub id: String,
+    pub name: String,
+    pub cron: String,
+    pub source: String,
+    pub source_id: Option<String>,
+    pub shuffle: bool,
+    pub volume: Option<i32>,
+    pub fade_in: i32,
+    pub once: bool,
+    pub enabled: bool,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
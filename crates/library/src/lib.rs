@@ -4,8 +4,14 @@ use sqlx::{sqlite::SqliteConnectOptions, Error, Executor, Pool, Sqlite, SqlitePo
 
 pub mod album_art;
 pub mod audio_scan;
+pub mod chapters;
 pub mod entity;
+pub mod list;
+pub mod lrc;
+pub mod replaygain;
 pub mod repo;
+pub mod silence;
+pub mod tags;
 
 pub async fn create_connection_pool() -> Result<Pool<Sqlite>, Error> {
     let home = env::var("HOME").unwrap();
@@ -19,6 +25,17 @@ pub async fn create_connection_pool() -> Result<Pool<Sqlite>, Error> {
         .filename(db_url)
         .create_if_missing(true);
     let pool = SqlitePool::connect_with(options).await?;
+    migrate(&pool).await?;
+
+    sqlx::query("PRAGMA journal_mode=WAL")
+        .execute(&pool)
+        .await?;
+    Ok(pool)
+}
+
+/// Creates the tables of the library, migrations that add columns fail once
+/// applied and their errors are ignored.
+pub async fn migrate(pool: &Pool<Sqlite>) -> Result<(), Error> {
     pool.execute(include_str!(
         "../migrations/20240923093823_create_tables.sql"
     ))
@@ -43,8 +60,68 @@ pub async fn create_connection_pool() -> Result<Pool<Sqlite>, Error> {
         Err(_) => println!("album_id column already exists"),
     }
 
-    sqlx::query("PRAGMA journal_mode=WAL")
-        .execute(&pool)
-        .await?;
-    Ok(pool)
+    pool.execute(include_str!(
+        "../migrations/20241104093512_create_genre_tracks_table.sql"
+    ))
+    .await?;
+
+    pool.execute(include_str!(
+        "../migrations/20241106081245_add_musicbrainz_id_columns.sql"
+    ))
+    .await
+    .ok();
+
+    pool.execute(include_str!(
+        "../migrations/20241106081512_create_musicbrainz_id_indexes.sql"
+    ))
+    .await?;
+
+    pool.execute(include_str!(
+        "../migrations/20241108104021_add_extended_tag_columns.sql"
+    ))
+    .await
+    .ok();
+
+    pool.execute(include_str!(
+        "../migrations/20241108104533_create_lyrics_table.sql"
+    ))
+    .await?;
+
+    pool.execute(include_str!(
+        "../migrations/20241110151208_add_synced_lyrics_column.sql"
+    ))
+    .await
+    .ok();
+
+    pool.execute(include_str!(
+        "../migrations/20241112084512_create_replaygain_table.sql"
+    ))
+    .await?;
+
+    pool.execute(include_str!(
+        "../migrations/20241114071036_create_schedule_table.sql"
+    ))
+    .await?;
+
+    pool.execute(include_str!(
+        "../migrations/20241116093042_create_bookmark_table.sql"
+    ))
+    .await?;
+
+    pool.execute(include_str!(
+        "../migrations/20241118074215_create_podcast_tables.sql"
+    ))
+    .await?;
+
+    pool.execute(include_str!(
+        "../migrations/20241120081530_create_radio_station_table.sql"
+    ))
+    .await?;
+
+    pool.execute(include_str!(
+        "../migrations/20241122084107_add_play_count_column.sql"
+    ))
+    .await
+    .ok();
+    Ok(())
 }
@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{QueryBuilder, Sqlite};
+
+/// Pagination, sorting and filters shared by the library list queries, every
+/// field is optional and the default lists the whole table.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ListOptions {
+    pub offset: Option<u32>,
+    pub limit: Option<u32>,
+    /// One of "title", "artist", "album", "year", "added", "length" or
+    /// "plays", the keys that don't apply to the listed entity fall back to
+    /// its default.
+    pub sort: Option<String>,
+    #[serde(default)]
+    pub descending: bool,
+    pub artist_id: Option<String>,
+    pub album_id: Option<String>,
+    pub genre_id: Option<String>,
+    pub year_from: Option<u32>,
+    pub year_to: Option<u32>,
+    /// Lists a single album per MusicBrainz release group, only applies to
+    /// albums.
+    #[serde(default)]
+    pub group_editions: bool,
+}
+
+impl ListOptions {
+    pub fn is_paginated(&self) -> bool {
+        self.offset.is_some() || self.limit.is_some()
+    }
+
+    pub fn is_filtered(&self) -> bool {
+        self.artist_id.is_some()
+            || self.album_id.is_some()
+            || self.genre_id.is_some()
+            || self.year_from.is_some()
+            || self.year_to.is_some()
+            || self.group_editions
+    }
+
+    /// `columns` maps the sort keys supported by a table to their `ORDER BY`
+    /// expression, the first entry is the default order.
+    pub(crate) fn push_order_by(
+        &self,
+        query: &mut QueryBuilder<'_, Sqlite>,
+        columns: &[(&str, &str)],
+    ) {
+        let (_, column) = self
+            .sort
+            .as_deref()
+            .and_then(|sort| {
+                columns
+                    .iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case(sort))
+            })
+            .unwrap_or(&columns[0]);
+        let direction = match self.descending {
+            true => "DESC",
+            false => "ASC",
+        };
+        query.push(format!(" ORDER BY {} {}, id ASC", column, direction));
+    }
+
+    pub(crate) fn push_pagination(&self, query: &mut QueryBuilder<'_, Sqlite>) {
+        if !self.is_paginated() {
+            return;
+        }
+        // sqlite only accepts an OFFSET after a LIMIT, -1 means no limit
+        query.push(" LIMIT ");
+        query.push_bind(self.limit.map(i64::from).unwrap_or(-1));
+        query.push(" OFFSET ");
+        query.push_bind(i64::from(self.offset.unwrap_or(0)));
+    }
+}
+
+/// Appends `WHERE` before the first condition and `AND` before the others.
+pub(crate) struct Conditions {
+    empty: bool,
+}
+
+impl Conditions {
+    pub(crate) fn new() -> Self {
+        Self { empty: true }
+    }
+
+    pub(crate) fn next(&mut self, query: &mut QueryBuilder<'_, Sqlite>) {
+        query.push(match self.empty {
+            true => " WHERE ",
+            false => " AND ",
+        });
+        self.empty = false;
+    }
+}
@@ -0,0 +1,267 @@
+use std::path::Path;
+
+use lofty::{
+    config::ParseOptions,
+    file::AudioFile,
+    id3::v2::{Frame, Id3v2Tag},
+    mpeg::MpegFile,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Sqlite};
+
+use crate::{entity::lyrics::Lyrics, repo};
+
+/// A line of synchronized lyrics, `time` is in milliseconds.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LyricLine {
+    pub time: u64,
+    pub text: String,
+}
+
+/// Parses LRC lyrics: `[mm:ss.xx]` timestamps, lines with several
+/// timestamps, the `[offset:]` tag and the word timestamps of enhanced LRC.
+/// Returns no lines when the text has no timestamp.
+pub fn parse(text: &str) -> Vec<LyricLine> {
+    let mut offset: i64 = 0;
+    let mut lines = vec![];
+
+    for line in text.lines() {
+        let mut rest = line.trim();
+        let mut times = vec![];
+        while let Some(tag) = rest.strip_prefix('[') {
+            let Some(end) = tag.find(']') else {
+                break;
+            };
+            let (tag, after) = (&tag[..end], &tag[end + 1..]);
+            if let Some(time) = parse_timestamp(tag) {
+                times.push(time);
+            } else if let Some(value) = tag.strip_prefix("offset:") {
+                offset = value.trim().parse().unwrap_or(0);
+            }
+            rest = after;
+        }
+
+        let text = strip_word_timestamps(rest);
+        for time in times {
+            // a positive offset shows the lyrics sooner
+            lines.push(LyricLine {
+                time: (time as i64 - offset).max(0) as u64,
+                text: text.clone(),
+            });
+        }
+    }
+
+    lines.sort_by_key(|line| line.time);
+    lines
+}
+
+pub fn is_synced(text: &str) -> bool {
+    !parse(text).is_empty()
+}
+
+/// Reads `mm:ss`, `mm:ss.xx`, `mm:ss.xxx` and `mm:ss:xx`.
+fn parse_timestamp(tag: &str) -> Option<u64> {
+    let (minutes, seconds) = tag.split_once(':')?;
+    let minutes: u64 = minutes.trim().parse().ok()?;
+    let seconds = seconds.trim().replacen(':', ".", 1);
+    let seconds: f64 = seconds.parse().ok()?;
+    if !(0.0..60.0).contains(&seconds) {
+        return None;
+    }
+    Some(minutes * 60_000 + (seconds * 1000.0).round() as u64)
+}
+
+fn strip_word_timestamps(text: &str) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        match rest[start..].find('>') {
+            Some(end) if parse_timestamp(&rest[start + 1..start + end]).is_some() => {
+                result.push_str(&rest[..start]);
+                rest = &rest[start + end + 1..];
+            }
+            _ => {
+                result.push_str(&rest[..=start]);
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result.trim().to_string()
+}
+
+/// Formats the lines back to LRC, the format stored in the library.
+pub fn to_lrc(lines: &[LyricLine]) -> String {
+    lines
+        .iter()
+        .map(|line| {
+            format!(
+                "[{:02}:{:02}.{:02}]{}",
+                line.time / 60_000,
+                line.time / 1000 % 60,
+                line.time % 1000 / 10,
+                line.text
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn to_text(lines: &[LyricLine]) -> String {
+    lines
+        .iter()
+        .map(|line| line.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The index of the line sung at `elapsed` milliseconds, none before the
+/// first line.
+pub fn current_line(lines: &[LyricLine], elapsed: u64) -> Option<usize> {
+    lines
+        .partition_point(|line| line.time <= elapsed)
+        .checked_sub(1)
+}
+
+/// The `.lrc` file next to the track, "01 - Song.lrc" for "01 - Song.flac".
+pub fn read_sidecar(path: &str) -> Option<String> {
+    let sidecar = Path::new(path).with_extension("lrc");
+    std::fs::read_to_string(sidecar).ok()
+}
+
+/// Finds the synchronized lyrics of a file, from its `.lrc` file first, then
+/// from an embedded SYLT frame and finally from its unsynchronized lyrics
+/// when they are written in LRC.
+pub fn read_synced_lyrics(path: &str, unsynced: Option<&str>) -> Option<Vec<LyricLine>> {
+    read_sidecar(path)
+        .map(|text| parse(&text))
+        .filter(|lines| !lines.is_empty())
+        .or_else(|| read_sylt(path))
+        .or_else(|| unsynced.map(parse).filter(|lines| !lines.is_empty()))
+}
+
+fn read_sylt(path: &str) -> Option<Vec<LyricLine>> {
+    if !path.to_lowercase().ends_with(".mp3") {
+        return None;
+    }
+    let mut file = std::fs::File::open(path).ok()?;
+    let mpeg = MpegFile::read_from(&mut file, ParseOptions::new()).ok()?;
+    mpeg.id3v2().and_then(sylt_lines)
+}
+
+/// lofty keeps the SYLT frames as binary frames.
+fn sylt_lines(tag: &Id3v2Tag) -> Option<Vec<LyricLine>> {
+    tag.into_iter().find_map(|frame| match frame {
+        Frame::Binary(binary) if frame.id_str() == "SYLT" => parse_sylt(&binary.data),
+        _ => None,
+    })
+}
+
+/// SYLT frames start with the text encoding, the language, the timestamp
+/// format, the content type and a description, followed by the texts, each
+/// one followed by its timestamp. Only millisecond timestamps are supported.
+fn parse_sylt(data: &[u8]) -> Option<Vec<LyricLine>> {
+    const MILLISECONDS: u8 = 2;
+
+    let encoding = *data.first()?;
+    if *data.get(4)? != MILLISECONDS {
+        return None;
+    }
+    let (_, mut position) = read_terminated(data, 6, encoding)?;
+
+    let mut lines = vec![];
+    while position < data.len() {
+        let (text, next) = read_terminated(data, position, encoding)?;
+        let time = data.get(next..next + 4)?;
+        position = next + 4;
+        let text = text.trim_matches(|c| c == '\n' || c == '\r').to_string();
+        if text.is_empty() {
+            continue;
+        }
+        lines.push(LyricLine {
+            time: u32::from_be_bytes([time[0], time[1], time[2], time[3]]) as u64,
+            text,
+        });
+    }
+
+    lines.sort_by_key(|line| line.time);
+    match lines.is_empty() {
+        true => None,
+        false => Some(lines),
+    }
+}
+
+/// Reads a null terminated string, returns it with the position following
+/// its terminator.
+fn read_terminated(data: &[u8], start: usize, encoding: u8) -> Option<(String, usize)> {
+    let data_left = data.get(start..)?;
+    match encoding {
+        // UTF-16 with a BOM, or big endian without
+        1 | 2 => {
+            let end = data_left
+                .chunks_exact(2)
+                .position(|c| c[0] == 0 && c[1] == 0)
+                .map(|i| i * 2)
+                .unwrap_or(data_left.len() - data_left.len() % 2);
+            let bytes = &data_left[..end];
+            let (bytes, little_endian) = match bytes {
+                [0xFF, 0xFE, rest @ ..] => (rest, true),
+                [0xFE, 0xFF, rest @ ..] => (rest, false),
+                _ => (bytes, false),
+            };
+            let units = bytes.chunks_exact(2).map(|c| match little_endian {
+                true => u16::from_le_bytes([c[0], c[1]]),
+                false => u16::from_be_bytes([c[0], c[1]]),
+            });
+            let text = char::decode_utf16(units)
+                .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+                .collect();
+            Some((text, (start + end + 2).min(data.len())))
+        }
+        _ => {
+            let end = data_left
+                .iter()
+                .position(|b| *b == 0)
+                .unwrap_or(data_left.len());
+            let bytes = &data_left[..end];
+            let text = match encoding {
+                // ISO-8859-1 maps to the first 256 code points
+                0 => bytes.iter().map(|b| *b as char).collect(),
+                _ => String::from_utf8_lossy(bytes).to_string(),
+            };
+            Some((text, (start + end + 1).min(data.len())))
+        }
+    }
+}
+
+/// The lyrics of a track, with the ones of an `.lrc` file added next to it
+/// since the last scan.
+pub async fn load(pool: Pool<Sqlite>, track_id: &str) -> Result<Option<Lyrics>, sqlx::Error> {
+    let lyrics = repo::lyrics::find_by_track(pool.clone(), track_id).await?;
+    if lyrics
+        .as_ref()
+        .is_some_and(|lyrics| lyrics.synced.is_some())
+    {
+        return Ok(lyrics);
+    }
+
+    let sidecar = repo::track::find(pool, track_id)
+        .await?
+        .and_then(|track| read_sidecar(&track.path))
+        .map(|text| parse(&text))
+        .filter(|lines| !lines.is_empty());
+
+    Ok(match (lyrics, sidecar) {
+        (Some(lyrics), Some(lines)) => Some(Lyrics {
+            synced: Some(to_lrc(&lines)),
+            ..lyrics
+        }),
+        (None, Some(lines)) => Some(Lyrics {
+            id: String::new(),
+            track_id: track_id.to_string(),
+            lyrics: to_text(&lines),
+            synced: Some(to_lrc(&lines)),
+        }),
+        (lyrics, None) => lyrics,
+    })
+}
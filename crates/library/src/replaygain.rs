@@ -0,0 +1,312 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::ErrorKind,
+    path::Path,
+    sync::{Mutex, RwLock},
+};
+
+use anyhow::{anyhow, Error};
+use chrono::Utc;
+use ebur128::{EbuR128, Mode};
+use lofty::{
+    config::WriteOptions,
+    file::TaggedFileExt,
+    tag::{ItemKey, Tag, TagExt},
+};
+use owo_colors::OwoColorize;
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Sqlite};
+use symphonia::core::{
+    audio::SampleBuffer,
+    codecs::{DecoderOptions, CODEC_TYPE_NULL},
+    errors::Error as DecodeError,
+    formats::FormatOptions,
+    io::MediaSourceStream,
+    meta::MetadataOptions,
+    probe::Hint,
+};
+
+use crate::{
+    entity::{
+        replaygain::{ReplayGain, StoredReplayGain},
+        track::Track,
+    },
+    repo,
+};
+
+/// The loudness ReplayGain 2.0 brings tracks to, in LUFS.
+pub const REFERENCE_LOUDNESS: f64 = -18.0;
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct AnalysisOptions {
+    /// Analyzes every track again, tagged or not.
+    #[serde(default)]
+    pub force: bool,
+    /// Writes the values to the files as REPLAYGAIN_* tags.
+    #[serde(default)]
+    pub write_tags: bool,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct AnalysisProgress {
+    pub analyzed: usize,
+    pub failed: usize,
+    pub total: usize,
+    pub running: bool,
+    /// The file being decoded.
+    pub current: Option<String>,
+    pub error: Option<String>,
+}
+
+static PROGRESS: Mutex<AnalysisProgress> = Mutex::new(AnalysisProgress {
+    analyzed: 0,
+    failed: 0,
+    total: 0,
+    running: false,
+    current: None,
+    error: None,
+});
+
+/// The measured values of the files without tags by path, read by the
+/// metadata parser of the firmware each time it loads a track.
+static STORED: RwLock<Option<HashMap<String, StoredReplayGain>>> = RwLock::new(None);
+
+pub fn progress() -> AnalysisProgress {
+    PROGRESS.lock().unwrap().clone()
+}
+
+fn update_progress(update: impl FnOnce(&mut AnalysisProgress)) {
+    update(&mut PROGRESS.lock().unwrap());
+}
+
+/// The values to apply to a file without ReplayGain tags.
+pub fn stored(path: &str) -> Option<StoredReplayGain> {
+    STORED.read().unwrap().as_ref()?.get(path).cloned()
+}
+
+/// Reads the measured values of the untagged files from the database.
+pub async fn load_stored(pool: Pool<Sqlite>) -> Result<(), Error> {
+    let stored = repo::replaygain::all_untagged(pool)
+        .await?
+        .into_iter()
+        .map(|replaygain| (replaygain.path.clone(), replaygain))
+        .collect();
+    *STORED.write().unwrap() = Some(stored);
+    Ok(())
+}
+
+/// Measures the loudness of the tracks lacking ReplayGain tags, or of every
+/// track with `force`, album by album. Only one analysis runs at a time,
+/// follow it with [`progress`].
+pub async fn analyze_library(pool: Pool<Sqlite>, options: AnalysisOptions) -> Result<(), Error> {
+    {
+        let mut progress = PROGRESS.lock().unwrap();
+        if progress.running {
+            return Err(anyhow!("ReplayGain analysis already running"));
+        }
+        *progress = AnalysisProgress {
+            running: true,
+            ..Default::default()
+        };
+    }
+
+    let result = analyze_tracks(pool.clone(), options).await;
+    let result = match result {
+        Ok(_) => load_stored(pool).await,
+        Err(e) => Err(e),
+    };
+    update_progress(|progress| {
+        progress.running = false;
+        progress.current = None;
+        progress.error = result.as_ref().err().map(|e| e.to_string());
+    });
+    result
+}
+
+async fn analyze_tracks(pool: Pool<Sqlite>, options: AnalysisOptions) -> Result<(), Error> {
+    let tracks = match options.force {
+        true => repo::track::all(pool.clone()).await?,
+        false => repo::replaygain::find_pending_tracks(pool.clone()).await?,
+    };
+    update_progress(|progress| progress.total = tracks.len());
+
+    let mut albums: Vec<(String, Vec<Track>)> = vec![];
+    for track in tracks {
+        match albums.iter_mut().find(|(id, _)| *id == track.album_id) {
+            Some((_, tracks)) => tracks.push(track),
+            None => albums.push((track.album_id.clone(), vec![track])),
+        }
+    }
+
+    for (_, tracks) in albums {
+        let analyzed = tokio::task::spawn_blocking(move || analyze_album(tracks)).await?;
+        for (track, replaygain) in analyzed {
+            repo::replaygain::save(pool.clone(), replaygain.clone()).await?;
+            if options.write_tags {
+                if let Err(e) = write_tags(&track.path, &replaygain) {
+                    eprintln!("Failed to write ReplayGain tags to {}: {}", track.path, e);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Measures each track, the album values are computed from the tracks that
+/// could be decoded.
+fn analyze_album(tracks: Vec<Track>) -> Vec<(Track, ReplayGain)> {
+    let mut measured = vec![];
+    for track in tracks {
+        update_progress(|progress| progress.current = Some(track.path.clone()));
+        println!("{} {}", "Analyzing".bright_green(), track.path);
+        match measure(&track.path) {
+            Ok(loudness) => {
+                update_progress(|progress| progress.analyzed += 1);
+                measured.push((track, loudness));
+            }
+            Err(e) => {
+                eprintln!("Failed to analyze {}: {}", track.path, e);
+                update_progress(|progress| {
+                    progress.analyzed += 1;
+                    progress.failed += 1;
+                });
+            }
+        }
+    }
+
+    let album_loudness =
+        EbuR128::loudness_global_multiple(measured.iter().map(|(_, loudness)| loudness))
+            .ok()
+            .filter(|loudness| loudness.is_finite());
+    let album_peak = measured
+        .iter()
+        .filter_map(|(_, loudness)| true_peak(loudness).ok())
+        .reduce(f64::max);
+
+    measured
+        .into_iter()
+        .filter_map(|(track, loudness)| {
+            let track_loudness = loudness.loudness_global().ok()?;
+            if !track_loudness.is_finite() {
+                // digital silence has no loudness
+                return None;
+            }
+            let replaygain = ReplayGain {
+                id: cuid::cuid1().ok()?,
+                track_id: track.id.clone(),
+                track_loudness,
+                track_gain: REFERENCE_LOUDNESS - track_loudness,
+                track_peak: true_peak(&loudness).ok()?,
+                album_loudness,
+                album_gain: album_loudness.map(|loudness| REFERENCE_LOUDNESS - loudness),
+                album_peak,
+                analyzed_at: Utc::now(),
+            };
+            Some((track, replaygain))
+        })
+        .collect()
+}
+
+/// Decodes the file and measures its integrated loudness and true peak
+/// following EBU R128.
+pub fn measure(path: &str) -> Result<EbuR128, Error> {
+    let source = MediaSourceStream::new(Box::new(File::open(path)?), Default::default());
+    let mut hint = Hint::new();
+    if let Some(extension) = Path::new(path).extension().and_then(|e| e.to_str()) {
+        hint.with_extension(extension);
+    }
+    let probed = symphonia::default::get_probe().format(
+        &hint,
+        source,
+        &FormatOptions::default(),
+        &MetadataOptions::default(),
+    )?;
+    let mut format = probed.format;
+    let track = format
+        .tracks()
+        .iter()
+        .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| anyhow!("No audio track"))?;
+    let track_id = track.id;
+    let mut decoder =
+        symphonia::default::get_codecs().make(&track.codec_params, &DecoderOptions::default())?;
+
+    let mut loudness: Option<EbuR128> = None;
+    let mut samples: Option<SampleBuffer<f32>> = None;
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(DecodeError::IoError(e)) if e.kind() == ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(e.into()),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // a corrupted frame is skipped like players do
+            Err(DecodeError::DecodeError(_)) => continue,
+            Err(e) => return Err(e.into()),
+        };
+
+        let spec = *decoded.spec();
+        if loudness.is_none() {
+            loudness = Some(EbuR128::new(
+                spec.channels.count() as u32,
+                spec.rate,
+                Mode::I | Mode::TRUE_PEAK | Mode::HISTOGRAM,
+            )?);
+        }
+        if samples
+            .as_ref()
+            .map(|samples| samples.capacity() < decoded.capacity() * spec.channels.count())
+            .unwrap_or(true)
+        {
+            samples = Some(SampleBuffer::new(decoded.capacity() as u64, spec));
+        }
+        let samples = samples.as_mut().unwrap();
+        samples.copy_interleaved_ref(decoded);
+        loudness
+            .as_mut()
+            .unwrap()
+            .add_frames_f32(samples.samples())?;
+    }
+
+    loudness.ok_or_else(|| anyhow!("No audio decoded"))
+}
+
+fn true_peak(loudness: &EbuR128) -> Result<f64, Error> {
+    let mut peak: f64 = 0.0;
+    for channel in 0..loudness.channels() {
+        peak = peak.max(loudness.true_peak(channel)?);
+    }
+    Ok(peak)
+}
+
+/// Writes the REPLAYGAIN_* tags, to the primary tag of the file.
+pub fn write_tags(path: &str, replaygain: &ReplayGain) -> Result<(), Error> {
+    let mut tagged_file = lofty::read_from_path(path)?;
+    if tagged_file.primary_tag().is_none() {
+        let tag_type = tagged_file.primary_tag_type();
+        tagged_file.insert_tag(Tag::new(tag_type));
+    }
+    let tag = tagged_file.primary_tag_mut().unwrap();
+
+    tag.insert_text(
+        ItemKey::ReplayGainTrackGain,
+        format!("{:.2} dB", replaygain.track_gain),
+    );
+    tag.insert_text(
+        ItemKey::ReplayGainTrackPeak,
+        format!("{:.6}", replaygain.track_peak),
+    );
+    if let (Some(gain), Some(peak)) = (replaygain.album_gain, replaygain.album_peak) {
+        tag.insert_text(ItemKey::ReplayGainAlbumGain, format!("{:.2} dB", gain));
+        tag.insert_text(ItemKey::ReplayGainAlbumPeak, format!("{:.6}", peak));
+    }
+
+    tag.save_to_path(path, WriteOptions::default())?;
+    Ok(())
+}
@@ -1,7 +1,19 @@
 use crate::entity::album::Album;
-use sqlx::{Pool, Sqlite};
+use crate::list::{Conditions, ListOptions};
+use sqlx::{Pool, QueryBuilder, Sqlite};
 
+/// Saves the album unless it is already in the library, in which case the id
+/// of the existing album is returned. Albums tagged with a MusicBrainz
+/// release id are identified by it, so retagging their year or title updates
+/// the existing album instead of creating a new one.
 pub async fn save(pool: Pool<Sqlite>, album: Album) -> Result<String, sqlx::Error> {
+    if let Some(musicbrainz_id) = &album.musicbrainz_id {
+        if let Some(existing) = find_by_musicbrainz_id(pool.clone(), musicbrainz_id).await? {
+            update(pool.clone(), &existing.id, &album).await?;
+            return Ok(existing.id);
+        }
+    }
+
     match sqlx::query(
         r#"
         INSERT INTO album (
@@ -12,9 +24,11 @@ pub async fn save(pool: Pool<Sqlite>, album: Album) -> Result<String, sqlx::Erro
           year_string,
           album_art,
           md5,
-          artist_id
+          artist_id,
+          musicbrainz_id,
+          musicbrainz_release_group_id
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
         "#,
     )
     .bind(&album.id)
@@ -25,14 +39,74 @@ pub async fn save(pool: Pool<Sqlite>, album: Album) -> Result<String, sqlx::Erro
     .bind(&album.album_art)
     .bind(&album.md5)
     .bind(&album.artist_id)
+    .bind(&album.musicbrainz_id)
+    .bind(&album.musicbrainz_release_group_id)
     .execute(&pool)
     .await
     {
         Ok(_) => Ok(album.id.clone()),
         Err(_e) => {
             // eprintln!("Error saving album: {:?}", e);
-            let album = find_by_md5(pool.clone(), &album.md5).await?;
-            Ok(album.unwrap().id)
+            let existing = find_by_md5(pool.clone(), &album.md5).await?.unwrap();
+            // the album was scanned before its files were tagged
+            if existing.musicbrainz_id.is_none() && album.musicbrainz_id.is_some() {
+                update(pool.clone(), &existing.id, &album).await?;
+            }
+            Ok(existing.id)
+        }
+    }
+}
+
+/// Refreshes the tags of an existing album, the md5 is only replaced when no
+/// other album has it.
+pub async fn update(pool: Pool<Sqlite>, id: &str, album: &Album) -> Result<(), sqlx::Error> {
+    match sqlx::query(
+        r#"
+        UPDATE album SET
+          title = $2,
+          year = $3,
+          year_string = $4,
+          album_art = COALESCE($5, album_art),
+          md5 = CASE WHEN EXISTS (SELECT 1 FROM album WHERE md5 = $6 AND id != $1) THEN md5 ELSE $6 END,
+          musicbrainz_id = COALESCE($7, musicbrainz_id),
+          musicbrainz_release_group_id = COALESCE($8, musicbrainz_release_group_id)
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(&album.title)
+    .bind(album.year)
+    .bind(&album.year_string)
+    .bind(&album.album_art)
+    .bind(&album.md5)
+    .bind(&album.musicbrainz_id)
+    .bind(&album.musicbrainz_release_group_id)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error updating album: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn update_album_art(
+    pool: Pool<Sqlite>,
+    id: &str,
+    album_art: &str,
+) -> Result<(), sqlx::Error> {
+    match sqlx::query("UPDATE album SET album_art = $1 WHERE id = $2")
+        .bind(album_art)
+        .bind(id)
+        .execute(&pool)
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error updating album: {:?}", e);
+            Err(e)
         }
     }
 }
@@ -70,6 +144,51 @@ pub async fn find_by_md5(pool: Pool<Sqlite>, md5: &str) -> Result<Option<Album>,
     }
 }
 
+pub async fn find_by_musicbrainz_id(
+    pool: Pool<Sqlite>,
+    musicbrainz_id: &str,
+) -> Result<Option<Album>, sqlx::Error> {
+    match sqlx::query_as::<_, Album>(
+        r#"
+        SELECT * FROM album WHERE musicbrainz_id = $1
+        "#,
+    )
+    .bind(musicbrainz_id)
+    .fetch_optional(&pool)
+    .await
+    {
+        Ok(album) => Ok(album),
+        Err(e) => {
+            eprintln!("Error finding album: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// The other editions of an album, the albums tagged with the same
+/// MusicBrainz release group, oldest first.
+pub async fn find_editions(pool: Pool<Sqlite>, id: &str) -> Result<Vec<Album>, sqlx::Error> {
+    match sqlx::query_as::<_, Album>(
+        r#"
+        SELECT edition.* FROM album
+        INNER JOIN album AS edition
+          ON edition.musicbrainz_release_group_id = album.musicbrainz_release_group_id
+        WHERE album.id = $1 AND edition.id != $1
+        ORDER BY edition.year, edition.title ASC
+        "#,
+    )
+    .bind(id)
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(albums) => Ok(albums),
+        Err(e) => {
+            eprintln!("Error finding albums: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
 pub async fn find_by_artist(
     pool: Pool<Sqlite>,
     artist_id: &str,
@@ -91,6 +210,33 @@ pub async fn find_by_artist(
     }
 }
 
+/// Albums of other artists with tracks credited to the artist, like the
+/// compilations or the featurings of the artist.
+pub async fn find_appears_on(
+    pool: Pool<Sqlite>,
+    artist_id: &str,
+) -> Result<Vec<Album>, sqlx::Error> {
+    match sqlx::query_as::<_, Album>(
+        r#"
+        SELECT DISTINCT album.* FROM artist_tracks
+        INNER JOIN track ON artist_tracks.track_id = track.id
+        INNER JOIN album ON track.album_id = album.id
+        WHERE artist_tracks.artist_id = $1 AND album.artist_id != $1
+        ORDER BY album.year, album.title ASC
+        "#,
+    )
+    .bind(artist_id)
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(albums) => Ok(albums),
+        Err(e) => {
+            eprintln!("Error finding albums: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
 pub async fn find(pool: Pool<Sqlite>, id: &str) -> Result<Option<Album>, sqlx::Error> {
     match sqlx::query_as::<_, Album>(
         r#"
@@ -125,3 +271,65 @@ pub async fn all(pool: Pool<Sqlite>) -> Result<Vec<Album>, sqlx::Error> {
         }
     }
 }
+
+const ALBUM_SORT_COLUMNS: [(&str, &str); 5] = [
+    ("title", "title"),
+    ("artist", "artist"),
+    ("year", "year"),
+    (
+        "added",
+        "(SELECT MAX(track.created_at) FROM track WHERE track.album_id = album.id)",
+    ),
+    (
+        "plays",
+        "(SELECT SUM(track.play_count) FROM track WHERE track.album_id = album.id)",
+    ),
+];
+
+fn push_filters(query: &mut QueryBuilder<'_, Sqlite>, options: &ListOptions) {
+    let mut conditions = Conditions::new();
+    if let Some(artist_id) = &options.artist_id {
+        conditions.next(query);
+        query.push("artist_id = ").push_bind(artist_id.clone());
+    }
+    if let Some(album_id) = &options.album_id {
+        conditions.next(query);
+        query.push("id = ").push_bind(album_id.clone());
+    }
+    if let Some(genre_id) = &options.genre_id {
+        conditions.next(query);
+        query
+            .push("id IN (SELECT track.album_id FROM track INNER JOIN genre_tracks ON genre_tracks.track_id = track.id WHERE genre_tracks.genre_id = ")
+            .push_bind(genre_id.clone())
+            .push(")");
+    }
+    if let Some(year_from) = options.year_from {
+        conditions.next(query);
+        query.push("year >= ").push_bind(year_from);
+    }
+    if let Some(year_to) = options.year_to {
+        conditions.next(query);
+        query.push("year <= ").push_bind(year_to);
+    }
+    if options.group_editions {
+        // the oldest edition stands for its release group
+        conditions.next(query);
+        query.push(
+            "(musicbrainz_release_group_id IS NULL OR id = (SELECT edition.id FROM album AS edition WHERE edition.musicbrainz_release_group_id = album.musicbrainz_release_group_id ORDER BY edition.year ASC, edition.id ASC LIMIT 1))",
+        );
+    }
+}
+
+pub async fn list(pool: Pool<Sqlite>, options: &ListOptions) -> Result<Vec<Album>, sqlx::Error> {
+    let mut query = QueryBuilder::new("SELECT * FROM album");
+    push_filters(&mut query, options);
+    options.push_order_by(&mut query, &ALBUM_SORT_COLUMNS);
+    options.push_pagination(&mut query);
+    query.build_query_as().fetch_all(&pool).await
+}
+
+pub async fn count(pool: Pool<Sqlite>, options: &ListOptions) -> Result<i64, sqlx::Error> {
+    let mut query = QueryBuilder::new("SELECT COUNT(*) FROM album");
+    push_filters(&mut query, options);
+    query.build_query_scalar().fetch_one(&pool).await
+}
@@ -1,22 +1,33 @@
 use crate::entity::artist::Artist;
-use sqlx::{Error, Pool, Sqlite};
+use crate::list::{Conditions, ListOptions};
+use sqlx::{Error, Pool, QueryBuilder, Sqlite};
 
+/// Saves the artist unless it is already in the library, artists tagged with
+/// a MusicBrainz artist id are identified by it before their name.
 pub async fn save(pool: Pool<Sqlite>, artist: Artist) -> Result<String, Error> {
+    if let Some(musicbrainz_id) = &artist.musicbrainz_id {
+        if let Some(existing) = find_by_musicbrainz_id(pool.clone(), musicbrainz_id).await? {
+            return Ok(existing.id);
+        }
+    }
+
     match sqlx::query(
         r#"
         INSERT INTO artist (
           id, 
           name,
           bio,
-          image
+          image,
+          musicbrainz_id
         )
-        VALUES ($1, $2, $3, $4)
+        VALUES ($1, $2, $3, $4, $5)
         "#,
     )
     .bind(&artist.id)
     .bind(&artist.name)
     .bind(&artist.bio)
     .bind(&artist.image)
+    .bind(&artist.musicbrainz_id)
     .execute(&pool)
     .await
     {
@@ -24,8 +35,67 @@ pub async fn save(pool: Pool<Sqlite>, artist: Artist) -> Result<String, Error> {
         Err(_e) => {
             // eprintln!("Error saving artist: {:?}", e);
             // get the artist by name and return the id
-            let artist = find_by_name(pool.clone(), &artist.name).await?;
-            Ok(artist.unwrap().id)
+            let existing = find_by_name(pool.clone(), &artist.name).await?.unwrap();
+            if let (None, Some(musicbrainz_id)) = (&existing.musicbrainz_id, &artist.musicbrainz_id)
+            {
+                update_musicbrainz_id(pool.clone(), &existing.id, musicbrainz_id).await?;
+            }
+            Ok(existing.id)
+        }
+    }
+}
+
+pub async fn update_musicbrainz_id(
+    pool: Pool<Sqlite>,
+    id: &str,
+    musicbrainz_id: &str,
+) -> Result<(), Error> {
+    match sqlx::query("UPDATE artist SET musicbrainz_id = $1 WHERE id = $2")
+        .bind(musicbrainz_id)
+        .bind(id)
+        .execute(&pool)
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error updating artist: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn update_image(pool: Pool<Sqlite>, id: &str, image: &str) -> Result<(), Error> {
+    match sqlx::query("UPDATE artist SET image = $1 WHERE id = $2")
+        .bind(image)
+        .bind(id)
+        .execute(&pool)
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error updating artist: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn find_by_musicbrainz_id(
+    pool: Pool<Sqlite>,
+    musicbrainz_id: &str,
+) -> Result<Option<Artist>, Error> {
+    match sqlx::query_as::<_, Artist>(
+        r#"
+        SELECT * FROM artist WHERE musicbrainz_id = $1
+        "#,
+    )
+    .bind(musicbrainz_id)
+    .fetch_optional(&pool)
+    .await
+    {
+        Ok(artist) => Ok(artist),
+        Err(e) => {
+            eprintln!("Error finding artist: {:?}", e);
+            Err(e)
         }
     }
 }
@@ -97,3 +167,61 @@ pub async fn all(pool: Pool<Sqlite>) -> Result<Vec<Artist>, Error> {
         }
     }
 }
+
+const ARTIST_SORT_COLUMNS: [(&str, &str); 3] = [
+    ("name", "name"),
+    (
+        "added",
+        "(SELECT MAX(track.created_at) FROM track WHERE track.artist_id = artist.id)",
+    ),
+    (
+        "plays",
+        "(SELECT SUM(track.play_count) FROM track WHERE track.artist_id = artist.id)",
+    ),
+];
+
+fn push_filters(query: &mut QueryBuilder<'_, Sqlite>, options: &ListOptions) {
+    let mut conditions = Conditions::new();
+    if let Some(artist_id) = &options.artist_id {
+        conditions.next(query);
+        query.push("id = ").push_bind(artist_id.clone());
+    }
+    if let Some(album_id) = &options.album_id {
+        conditions.next(query);
+        query
+            .push("id IN (SELECT artist_id FROM album WHERE id = ")
+            .push_bind(album_id.clone())
+            .push(")");
+    }
+    if let Some(genre_id) = &options.genre_id {
+        conditions.next(query);
+        query
+            .push("id IN (SELECT track.artist_id FROM track INNER JOIN genre_tracks ON genre_tracks.track_id = track.id WHERE genre_tracks.genre_id = ")
+            .push_bind(genre_id.clone())
+            .push(")");
+    }
+    if options.year_from.is_some() || options.year_to.is_some() {
+        conditions.next(query);
+        query
+            .push("id IN (SELECT artist_id FROM track WHERE year BETWEEN ")
+            .push_bind(options.year_from.unwrap_or(0))
+            .push(" AND ")
+            .push_bind(options.year_to.unwrap_or(u32::MAX))
+            .push(")");
+    }
+}
+
+/// Artists are sorted by name for the "title" and "artist" keys as well.
+pub async fn list(pool: Pool<Sqlite>, options: &ListOptions) -> Result<Vec<Artist>, Error> {
+    let mut query = QueryBuilder::new("SELECT * FROM artist");
+    push_filters(&mut query, options);
+    options.push_order_by(&mut query, &ARTIST_SORT_COLUMNS);
+    options.push_pagination(&mut query);
+    query.build_query_as().fetch_all(&pool).await
+}
+
+pub async fn count(pool: Pool<Sqlite>, options: &ListOptions) -> Result<i64, Error> {
+    let mut query = QueryBuilder::new("SELECT COUNT(*) FROM artist");
+    push_filters(&mut query, options);
+    query.build_query_scalar().fetch_one(&pool).await
+}
@@ -0,0 +1,204 @@
+use crate::entity::bookmark::Bookmark;
+use sqlx::{Pool, Sqlite};
+
+pub async fn save(pool: Pool<Sqlite>, bookmark: Bookmark) -> Result<(), sqlx::Error> {
+    match sqlx::query(
+        r#"
+        INSERT INTO bookmark (
+          id,
+          name,
+          source,
+          source_id,
+          path,
+          track_id,
+          elapsed,
+          created_at,
+          updated_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        "#,
+    )
+    .bind(&bookmark.id)
+    .bind(&bookmark.name)
+    .bind(&bookmark.source)
+    .bind(&bookmark.source_id)
+    .bind(&bookmark.path)
+    .bind(&bookmark.track_id)
+    .bind(bookmark.elapsed)
+    .bind(bookmark.created_at)
+    .bind(bookmark.updated_at)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error saving bookmark: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Saves the resume position of a source, replacing the previous one.
+pub async fn save_resume_position(
+    pool: Pool<Sqlite>,
+    bookmark: Bookmark,
+) -> Result<(), sqlx::Error> {
+    match sqlx::query(
+        r#"
+        INSERT INTO bookmark (
+          id,
+          name,
+          source,
+          source_id,
+          path,
+          track_id,
+          elapsed,
+          created_at,
+          updated_at
+        )
+        VALUES ($1, NULL, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (source, source_id) WHERE name IS NULL DO UPDATE SET
+          path = excluded.path,
+          track_id = excluded.track_id,
+          elapsed = excluded.elapsed,
+          updated_at = excluded.updated_at
+        "#,
+    )
+    .bind(&bookmark.id)
+    .bind(&bookmark.source)
+    .bind(&bookmark.source_id)
+    .bind(&bookmark.path)
+    .bind(&bookmark.track_id)
+    .bind(bookmark.elapsed)
+    .bind(bookmark.created_at)
+    .bind(bookmark.updated_at)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error saving resume position: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn update(pool: Pool<Sqlite>, bookmark: &Bookmark) -> Result<(), sqlx::Error> {
+    match sqlx::query(
+        r#"
+        UPDATE bookmark SET
+          name = $2,
+          elapsed = $3,
+          updated_at = $4
+        WHERE id = $1
+        "#,
+    )
+    .bind(&bookmark.id)
+    .bind(&bookmark.name)
+    .bind(bookmark.elapsed)
+    .bind(bookmark.updated_at)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error updating bookmark: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn find(pool: Pool<Sqlite>, id: &str) -> Result<Option<Bookmark>, sqlx::Error> {
+    match sqlx::query_as::<_, Bookmark>(
+        r#"
+        SELECT * FROM bookmark WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(&pool)
+    .await
+    {
+        Ok(bookmark) => Ok(bookmark),
+        Err(e) => {
+            eprintln!("Error finding bookmark: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn find_resume_position(
+    pool: Pool<Sqlite>,
+    source: &str,
+    source_id: &str,
+) -> Result<Option<Bookmark>, sqlx::Error> {
+    match sqlx::query_as::<_, Bookmark>(
+        r#"
+        SELECT * FROM bookmark WHERE source = $1 AND source_id = $2 AND name IS NULL
+        "#,
+    )
+    .bind(source)
+    .bind(source_id)
+    .fetch_optional(&pool)
+    .await
+    {
+        Ok(bookmark) => Ok(bookmark),
+        Err(e) => {
+            eprintln!("Error finding resume position: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn all(pool: Pool<Sqlite>) -> Result<Vec<Bookmark>, sqlx::Error> {
+    match sqlx::query_as::<_, Bookmark>(
+        r#"
+        SELECT * FROM bookmark ORDER BY updated_at DESC
+        "#,
+    )
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(bookmarks) => Ok(bookmarks),
+        Err(e) => {
+            eprintln!("Error fetching bookmarks: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn delete(pool: Pool<Sqlite>, id: &str) -> Result<bool, sqlx::Error> {
+    match sqlx::query(
+        r#"
+        DELETE FROM bookmark WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .execute(&pool)
+    .await
+    {
+        Ok(result) => Ok(result.rows_affected() > 0),
+        Err(e) => {
+            eprintln!("Error deleting bookmark: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Deletes the resume positions in a track, once it is finished.
+pub async fn delete_resume_positions(pool: Pool<Sqlite>, path: &str) -> Result<(), sqlx::Error> {
+    match sqlx::query(
+        r#"
+        DELETE FROM bookmark WHERE path = $1 AND name IS NULL
+        "#,
+    )
+    .bind(path)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error deleting resume positions: {:?}", e);
+            Err(e)
+        }
+    }
+}
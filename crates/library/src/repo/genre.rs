@@ -0,0 +1,113 @@
+use crate::entity::genre::Genre;
+use sqlx::{Error, Pool, Sqlite};
+
+/// Genres are matched case-insensitively, the first spelling found during a
+/// scan is the one that is kept.
+pub async fn save(pool: Pool<Sqlite>, genre: Genre) -> Result<String, Error> {
+    if let Some(existing) = find_by_name(pool.clone(), &genre.name).await? {
+        return Ok(existing.id);
+    }
+
+    match sqlx::query(
+        r#"
+        INSERT INTO genre (
+          id,
+          name,
+          description,
+          image
+        )
+        VALUES ($1, $2, $3, $4)
+        "#,
+    )
+    .bind(&genre.id)
+    .bind(&genre.name)
+    .bind(&genre.description)
+    .bind(&genre.image)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Ok(genre.id.clone()),
+        Err(e) => {
+            eprintln!("Error saving genre: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn find_by_name(pool: Pool<Sqlite>, name: &str) -> Result<Option<Genre>, Error> {
+    match sqlx::query_as::<_, Genre>(
+        r#"
+        SELECT * FROM genre WHERE name = $1 COLLATE NOCASE
+        "#,
+    )
+    .bind(name)
+    .fetch_optional(&pool)
+    .await
+    {
+        Ok(genre) => Ok(genre),
+        Err(e) => {
+            eprintln!("Error finding genre: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn find(pool: Pool<Sqlite>, id: &str) -> Result<Option<Genre>, Error> {
+    match sqlx::query_as::<_, Genre>(
+        r#"
+        SELECT * FROM genre WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(&pool)
+    .await
+    {
+        Ok(genre) => Ok(genre),
+        Err(e) => {
+            eprintln!("Error finding genre: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn find_by_track(pool: Pool<Sqlite>, track_id: &str) -> Result<Vec<Genre>, Error> {
+    match sqlx::query_as::<_, Genre>(
+        r#"
+        SELECT genre.* FROM genre_tracks
+        INNER JOIN genre ON genre_tracks.genre_id = genre.id
+        WHERE genre_tracks.track_id = $1
+        ORDER BY genre.name ASC
+        "#,
+    )
+    .bind(track_id)
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(genres) => Ok(genres),
+        Err(e) => {
+            eprintln!("Error finding genres: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Only the genres that still have tracks, a genre whose tracks have all been
+/// retagged or removed is not listed.
+pub async fn all(pool: Pool<Sqlite>) -> Result<Vec<Genre>, Error> {
+    match sqlx::query_as::<_, Genre>(
+        r#"
+        SELECT * FROM genre
+        WHERE EXISTS (SELECT 1 FROM genre_tracks WHERE genre_tracks.genre_id = genre.id)
+        ORDER BY name ASC
+        "#,
+    )
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(genres) => Ok(genres),
+        Err(e) => {
+            eprintln!("Error finding genres: {:?}", e);
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::entity::{genre_tracks::GenreTracks, track::Track};
+use sqlx::{Pool, Sqlite};
+
+pub async fn save(pool: Pool<Sqlite>, genre_track: GenreTracks) -> Result<(), sqlx::Error> {
+    let results = sqlx::query(
+        r#"
+        SELECT * FROM genre_tracks WHERE genre_id = $1 AND track_id = $2
+        "#,
+    )
+    .bind(&genre_track.genre_id)
+    .bind(&genre_track.track_id)
+    .fetch_optional(&pool)
+    .await?;
+
+    if results.is_some() {
+        return Ok(());
+    }
+
+    match sqlx::query(
+        r#"
+        INSERT INTO genre_tracks (
+          id,
+          genre_id,
+          track_id
+        )
+        VALUES ($1, $2, $3)
+        "#,
+    )
+    .bind(&genre_track.id)
+    .bind(&genre_track.genre_id)
+    .bind(&genre_track.track_id)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error saving genre track: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn delete_by_track(pool: Pool<Sqlite>, track_id: &str) -> Result<(), sqlx::Error> {
+    match sqlx::query(
+        r#"
+        DELETE FROM genre_tracks WHERE track_id = $1
+        "#,
+    )
+    .bind(track_id)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error deleting genre tracks: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn find_by_genre(pool: Pool<Sqlite>, genre_id: &str) -> Result<Vec<Track>, sqlx::Error> {
+    match sqlx::query_as::<_, Track>(
+        r#"
+        SELECT track.* FROM genre_tracks
+        INNER JOIN track ON genre_tracks.track_id = track.id
+        WHERE genre_tracks.genre_id = $1
+        ORDER BY artist, album, disc_number, track_number ASC
+        "#,
+    )
+    .bind(genre_id)
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(genre_tracks) => Ok(genre_tracks),
+        Err(e) => {
+            eprintln!("Error finding genre tracks: {:?}", e);
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use crate::entity::lyrics::Lyrics;
+use sqlx::{Pool, Sqlite};
+
+/// Saves the lyrics of a track, replacing the ones of a previous scan.
+pub async fn save(pool: Pool<Sqlite>, lyrics: Lyrics) -> Result<(), sqlx::Error> {
+    match sqlx::query(
+        r#"
+        INSERT INTO lyrics (
+          id,
+          track_id,
+          lyrics,
+          synced
+        )
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (track_id) DO UPDATE SET lyrics = excluded.lyrics, synced = excluded.synced
+        "#,
+    )
+    .bind(&lyrics.id)
+    .bind(&lyrics.track_id)
+    .bind(&lyrics.lyrics)
+    .bind(&lyrics.synced)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error saving lyrics: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn find_by_track(
+    pool: Pool<Sqlite>,
+    track_id: &str,
+) -> Result<Option<Lyrics>, sqlx::Error> {
+    match sqlx::query_as::<_, Lyrics>(
+        r#"
+        SELECT * FROM lyrics WHERE track_id = $1
+        "#,
+    )
+    .bind(track_id)
+    .fetch_optional(&pool)
+    .await
+    {
+        Ok(lyrics) => Ok(lyrics),
+        Err(e) => {
+            eprintln!("Error finding lyrics: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn delete_by_track(pool: Pool<Sqlite>, track_id: &str) -> Result<(), sqlx::Error> {
+    match sqlx::query(
+        r#"
+        DELETE FROM lyrics WHERE track_id = $1
+        "#,
+    )
+    .bind(track_id)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error deleting lyrics: {:?}", e);
+            Err(e)
+        }
+    }
+}
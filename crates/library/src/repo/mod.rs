@@ -2,9 +2,17 @@ pub mod album;
 pub mod album_tracks;
 pub mod artist;
 pub mod artist_tracks;
+pub mod bookmark;
 pub mod favourites;
 pub mod folder;
 pub mod genre;
+pub mod genre_tracks;
+pub mod lyrics;
 pub mod playlist;
 pub mod playlist_tracks;
+pub mod podcast;
+pub mod podcast_episode;
+pub mod radio_station;
+pub mod replaygain;
+pub mod schedule;
 pub mod track;
@@ -3,6 +3,37 @@ use sqlx::{Pool, Sqlite};
 
 pub async fn save(pool: Pool<Sqlite>, playlist: Playlist) {}
 
-pub async fn find(pool: Pool<Sqlite>) {}
+pub async fn find(pool: Pool<Sqlite>, id: &str) -> Result<Option<Playlist>, sqlx::Error> {
+    match sqlx::query_as::<_, Playlist>(
+        r#"
+        SELECT * FROM playlist WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(&pool)
+    .await
+    {
+        Ok(playlist) => Ok(playlist),
+        Err(e) => {
+            eprintln!("Error finding playlist: {:?}", e);
+            Err(e)
+        }
+    }
+}
 
-pub async fn all(pool: Pool<Sqlite>) {}
+pub async fn all(pool: Pool<Sqlite>) -> Result<Vec<Playlist>, sqlx::Error> {
+    match sqlx::query_as::<_, Playlist>(
+        r#"
+        SELECT * FROM playlist ORDER BY name ASC
+        "#,
+    )
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(playlists) => Ok(playlists),
+        Err(e) => {
+            eprintln!("Error finding playlists: {:?}", e);
+            Err(e)
+        }
+    }
+}
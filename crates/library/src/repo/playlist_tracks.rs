@@ -1,6 +1,28 @@
-use crate::entity::playlist_tracks::PlaylistTracks;
+use crate::entity::{playlist_tracks::PlaylistTracks, track::Track};
 use sqlx::{Pool, Sqlite};
 
 pub async fn save(pool: Pool<Sqlite>, playlist_track: PlaylistTracks) {}
 
-pub async fn find(pool: Pool<Sqlite>) {}
+pub async fn find_by_playlist(
+    pool: Pool<Sqlite>,
+    playlist_id: &str,
+) -> Result<Vec<Track>, sqlx::Error> {
+    match sqlx::query_as::<_, Track>(
+        r#"
+        SELECT track.* FROM playlist_tracks
+        LEFT JOIN track ON playlist_tracks.track_id = track.id
+        WHERE playlist_tracks.playlist_id = $1
+        ORDER BY playlist_tracks.created_at ASC
+        "#,
+    )
+    .bind(playlist_id)
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(tracks) => Ok(tracks),
+        Err(e) => {
+            eprintln!("Error finding playlist tracks: {:?}", e);
+            Err(e)
+        }
+    }
+}
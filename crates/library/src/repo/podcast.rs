@@ -0,0 +1,169 @@
+use crate::entity::podcast::Podcast;
+use sqlx::{Pool, Sqlite};
+
+pub async fn save(pool: Pool<Sqlite>, podcast: Podcast) -> Result<(), sqlx::Error> {
+    match sqlx::query(
+        r#"
+        INSERT INTO podcast (
+          id,
+          url,
+          title,
+          description,
+          author,
+          image,
+          link,
+          auto_download,
+          delete_played,
+          last_polled_at,
+          created_at,
+          updated_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        "#,
+    )
+    .bind(&podcast.id)
+    .bind(&podcast.url)
+    .bind(&podcast.title)
+    .bind(&podcast.description)
+    .bind(&podcast.author)
+    .bind(&podcast.image)
+    .bind(&podcast.link)
+    .bind(podcast.auto_download)
+    .bind(podcast.delete_played)
+    .bind(podcast.last_polled_at)
+    .bind(podcast.created_at)
+    .bind(podcast.updated_at)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error saving podcast: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn update(pool: Pool<Sqlite>, podcast: &Podcast) -> Result<(), sqlx::Error> {
+    match sqlx::query(
+        r#"
+        UPDATE podcast SET
+          title = $2,
+          description = $3,
+          author = $4,
+          image = $5,
+          link = $6,
+          auto_download = $7,
+          delete_played = $8,
+          last_polled_at = $9,
+          updated_at = $10
+        WHERE id = $1
+        "#,
+    )
+    .bind(&podcast.id)
+    .bind(&podcast.title)
+    .bind(&podcast.description)
+    .bind(&podcast.author)
+    .bind(&podcast.image)
+    .bind(&podcast.link)
+    .bind(podcast.auto_download)
+    .bind(podcast.delete_played)
+    .bind(podcast.last_polled_at)
+    .bind(podcast.updated_at)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error updating podcast: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn find(pool: Pool<Sqlite>, id: &str) -> Result<Option<Podcast>, sqlx::Error> {
+    match sqlx::query_as::<_, Podcast>(
+        r#"
+        SELECT * FROM podcast WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(&pool)
+    .await
+    {
+        Ok(podcast) => Ok(podcast),
+        Err(e) => {
+            eprintln!("Error finding podcast: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn find_by_url(pool: Pool<Sqlite>, url: &str) -> Result<Option<Podcast>, sqlx::Error> {
+    match sqlx::query_as::<_, Podcast>(
+        r#"
+        SELECT * FROM podcast WHERE url = $1
+        "#,
+    )
+    .bind(url)
+    .fetch_optional(&pool)
+    .await
+    {
+        Ok(podcast) => Ok(podcast),
+        Err(e) => {
+            eprintln!("Error finding podcast: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn all(pool: Pool<Sqlite>) -> Result<Vec<Podcast>, sqlx::Error> {
+    match sqlx::query_as::<_, Podcast>(
+        r#"
+        SELECT * FROM podcast ORDER BY title ASC
+        "#,
+    )
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(podcasts) => Ok(podcasts),
+        Err(e) => {
+            eprintln!("Error fetching podcasts: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Deletes the podcast and its episodes.
+pub async fn delete(pool: Pool<Sqlite>, id: &str) -> Result<bool, sqlx::Error> {
+    let result = match sqlx::query(
+        r#"
+        DELETE FROM podcast WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .execute(&pool)
+    .await
+    {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("Error deleting podcast: {:?}", e);
+            return Err(e);
+        }
+    };
+    match sqlx::query(
+        r#"
+        DELETE FROM podcast_episode WHERE podcast_id = $1
+        "#,
+    )
+    .bind(id)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Ok(result.rows_affected() > 0),
+        Err(e) => {
+            eprintln!("Error deleting podcast episodes: {:?}", e);
+            Err(e)
+        }
+    }
+}
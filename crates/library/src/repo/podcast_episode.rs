@@ -0,0 +1,162 @@
+use crate::entity::podcast_episode::PodcastEpisode;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Sqlite};
+
+/// Saves a new episode, returns false when the podcast already has an
+/// episode with the same guid.
+pub async fn save(pool: Pool<Sqlite>, episode: PodcastEpisode) -> Result<bool, sqlx::Error> {
+    match sqlx::query(
+        r#"
+        INSERT INTO podcast_episode (
+          id,
+          podcast_id,
+          guid,
+          title,
+          description,
+          url,
+          mime_type,
+          duration,
+          published_at,
+          path,
+          played,
+          played_at,
+          created_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+        ON CONFLICT (podcast_id, guid) DO NOTHING
+        "#,
+    )
+    .bind(&episode.id)
+    .bind(&episode.podcast_id)
+    .bind(&episode.guid)
+    .bind(&episode.title)
+    .bind(&episode.description)
+    .bind(&episode.url)
+    .bind(&episode.mime_type)
+    .bind(episode.duration)
+    .bind(episode.published_at)
+    .bind(&episode.path)
+    .bind(episode.played)
+    .bind(episode.played_at)
+    .bind(episode.created_at)
+    .execute(&pool)
+    .await
+    {
+        Ok(result) => Ok(result.rows_affected() > 0),
+        Err(e) => {
+            eprintln!("Error saving podcast episode: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn find(pool: Pool<Sqlite>, id: &str) -> Result<Option<PodcastEpisode>, sqlx::Error> {
+    match sqlx::query_as::<_, PodcastEpisode>(
+        r#"
+        SELECT * FROM podcast_episode WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(&pool)
+    .await
+    {
+        Ok(episode) => Ok(episode),
+        Err(e) => {
+            eprintln!("Error finding podcast episode: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn find_by_path(
+    pool: Pool<Sqlite>,
+    path: &str,
+) -> Result<Option<PodcastEpisode>, sqlx::Error> {
+    match sqlx::query_as::<_, PodcastEpisode>(
+        r#"
+        SELECT * FROM podcast_episode WHERE path = $1
+        "#,
+    )
+    .bind(path)
+    .fetch_optional(&pool)
+    .await
+    {
+        Ok(episode) => Ok(episode),
+        Err(e) => {
+            eprintln!("Error finding podcast episode: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// The episodes of a podcast, the newest first.
+pub async fn find_by_podcast(
+    pool: Pool<Sqlite>,
+    podcast_id: &str,
+) -> Result<Vec<PodcastEpisode>, sqlx::Error> {
+    match sqlx::query_as::<_, PodcastEpisode>(
+        r#"
+        SELECT * FROM podcast_episode
+        WHERE podcast_id = $1
+        ORDER BY published_at DESC, created_at DESC
+        "#,
+    )
+    .bind(podcast_id)
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(episodes) => Ok(episodes),
+        Err(e) => {
+            eprintln!("Error fetching podcast episodes: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn update_path(
+    pool: Pool<Sqlite>,
+    id: &str,
+    path: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    match sqlx::query(
+        r#"
+        UPDATE podcast_episode SET path = $2 WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(path)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error updating podcast episode path: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn update_played(
+    pool: Pool<Sqlite>,
+    id: &str,
+    played: bool,
+    played_at: Option<DateTime<Utc>>,
+) -> Result<(), sqlx::Error> {
+    match sqlx::query(
+        r#"
+        UPDATE podcast_episode SET played = $2, played_at = $3 WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(played)
+    .bind(played_at)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error updating podcast episode: {:?}", e);
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use crate::entity::radio_station::RadioStation;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Sqlite};
+
+pub async fn save(pool: Pool<Sqlite>, station: RadioStation) -> Result<(), sqlx::Error> {
+    match sqlx::query(
+        r#"
+        INSERT INTO radio_station (
+          id,
+          name,
+          url,
+          genre,
+          homepage,
+          image,
+          favourite,
+          last_played_at,
+          created_at,
+          updated_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        "#,
+    )
+    .bind(&station.id)
+    .bind(&station.name)
+    .bind(&station.url)
+    .bind(&station.genre)
+    .bind(&station.homepage)
+    .bind(&station.image)
+    .bind(station.favourite)
+    .bind(station.last_played_at)
+    .bind(station.created_at)
+    .bind(station.updated_at)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error saving radio station: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn update(pool: Pool<Sqlite>, station: &RadioStation) -> Result<(), sqlx::Error> {
+    match sqlx::query(
+        r#"
+        UPDATE radio_station SET
+          name = $2,
+          url = $3,
+          genre = $4,
+          homepage = $5,
+          image = $6,
+          favourite = $7,
+          updated_at = $8
+        WHERE id = $1
+        "#,
+    )
+    .bind(&station.id)
+    .bind(&station.name)
+    .bind(&station.url)
+    .bind(&station.genre)
+    .bind(&station.homepage)
+    .bind(&station.image)
+    .bind(station.favourite)
+    .bind(station.updated_at)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error updating radio station: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn update_last_played(
+    pool: Pool<Sqlite>,
+    id: &str,
+    last_played_at: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    match sqlx::query(
+        r#"
+        UPDATE radio_station SET last_played_at = $2 WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(last_played_at)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error updating radio station: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn find(pool: Pool<Sqlite>, id: &str) -> Result<Option<RadioStation>, sqlx::Error> {
+    match sqlx::query_as::<_, RadioStation>(
+        r#"
+        SELECT * FROM radio_station WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(&pool)
+    .await
+    {
+        Ok(station) => Ok(station),
+        Err(e) => {
+            eprintln!("Error finding radio station: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn find_by_url(
+    pool: Pool<Sqlite>,
+    url: &str,
+) -> Result<Option<RadioStation>, sqlx::Error> {
+    match sqlx::query_as::<_, RadioStation>(
+        r#"
+        SELECT * FROM radio_station WHERE url = $1
+        "#,
+    )
+    .bind(url)
+    .fetch_optional(&pool)
+    .await
+    {
+        Ok(station) => Ok(station),
+        Err(e) => {
+            eprintln!("Error finding radio station: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// The favourite stations first, then by name.
+pub async fn all(pool: Pool<Sqlite>) -> Result<Vec<RadioStation>, sqlx::Error> {
+    match sqlx::query_as::<_, RadioStation>(
+        r#"
+        SELECT * FROM radio_station ORDER BY favourite DESC, name ASC
+        "#,
+    )
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(stations) => Ok(stations),
+        Err(e) => {
+            eprintln!("Error fetching radio stations: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn all_favourites(pool: Pool<Sqlite>) -> Result<Vec<RadioStation>, sqlx::Error> {
+    match sqlx::query_as::<_, RadioStation>(
+        r#"
+        SELECT * FROM radio_station WHERE favourite = TRUE ORDER BY name ASC
+        "#,
+    )
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(stations) => Ok(stations),
+        Err(e) => {
+            eprintln!("Error fetching favourite radio stations: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn delete(pool: Pool<Sqlite>, id: &str) -> Result<bool, sqlx::Error> {
+    match sqlx::query(
+        r#"
+        DELETE FROM radio_station WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .execute(&pool)
+    .await
+    {
+        Ok(result) => Ok(result.rows_affected() > 0),
+        Err(e) => {
+            eprintln!("Error deleting radio station: {:?}", e);
+            Err(e)
+        }
+    }
+}
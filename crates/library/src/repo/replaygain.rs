@@ -0,0 +1,119 @@
+use crate::entity::{
+    replaygain::{ReplayGain, StoredReplayGain},
+    track::Track,
+};
+use sqlx::{Pool, Sqlite};
+
+/// Saves the measured values of a track, replacing the ones of a previous
+/// analysis.
+pub async fn save(pool: Pool<Sqlite>, replaygain: ReplayGain) -> Result<(), sqlx::Error> {
+    match sqlx::query(
+        r#"
+        INSERT INTO replaygain (
+          id,
+          track_id,
+          track_loudness,
+          track_gain,
+          track_peak,
+          album_loudness,
+          album_gain,
+          album_peak,
+          analyzed_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        ON CONFLICT (track_id) DO UPDATE SET
+          track_loudness = excluded.track_loudness,
+          track_gain = excluded.track_gain,
+          track_peak = excluded.track_peak,
+          album_loudness = excluded.album_loudness,
+          album_gain = excluded.album_gain,
+          album_peak = excluded.album_peak,
+          analyzed_at = excluded.analyzed_at
+        "#,
+    )
+    .bind(&replaygain.id)
+    .bind(&replaygain.track_id)
+    .bind(replaygain.track_loudness)
+    .bind(replaygain.track_gain)
+    .bind(replaygain.track_peak)
+    .bind(replaygain.album_loudness)
+    .bind(replaygain.album_gain)
+    .bind(replaygain.album_peak)
+    .bind(replaygain.analyzed_at)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error saving replaygain: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn find_by_track(
+    pool: Pool<Sqlite>,
+    track_id: &str,
+) -> Result<Option<ReplayGain>, sqlx::Error> {
+    match sqlx::query_as::<_, ReplayGain>(
+        r#"
+        SELECT * FROM replaygain WHERE track_id = $1
+        "#,
+    )
+    .bind(track_id)
+    .fetch_optional(&pool)
+    .await
+    {
+        Ok(replaygain) => Ok(replaygain),
+        Err(e) => {
+            eprintln!("Error finding replaygain: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// The measured values of the tracks without ReplayGain tags.
+pub async fn all_untagged(pool: Pool<Sqlite>) -> Result<Vec<StoredReplayGain>, sqlx::Error> {
+    match sqlx::query_as::<_, StoredReplayGain>(
+        r#"
+        SELECT track.path, replaygain.track_gain, replaygain.track_peak,
+               replaygain.album_gain, replaygain.album_peak
+        FROM replaygain
+        INNER JOIN track ON track.id = replaygain.track_id
+        WHERE track.replaygain_track_gain IS NULL
+        "#,
+    )
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(replaygain) => Ok(replaygain),
+        Err(e) => {
+            eprintln!("Error finding replaygain: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// The tracks of the albums with a track that has neither ReplayGain tags
+/// nor measured values, all the tracks of an album are needed for its gain.
+pub async fn find_pending_tracks(pool: Pool<Sqlite>) -> Result<Vec<Track>, sqlx::Error> {
+    match sqlx::query_as::<_, Track>(
+        r#"
+        SELECT * FROM track WHERE album_id IN (
+          SELECT track.album_id FROM track
+          LEFT JOIN replaygain ON replaygain.track_id = track.id
+          WHERE track.replaygain_track_gain IS NULL AND replaygain.id IS NULL
+        )
+        ORDER BY album_id, disc_number, track_number
+        "#,
+    )
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(tracks) => Ok(tracks),
+        Err(e) => {
+            eprintln!("Error finding tracks: {:?}", e);
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use crate::entity::schedule::Schedule;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Sqlite};
+
+pub async fn save(pool: Pool<Sqlite>, schedule: Schedule) -> Result<(), sqlx::Error> {
+    match sqlx::query(
+        r#"
+        INSERT INTO schedule (
+          id,
+          name,
+          cron,
+          source,
+          source_id,
+          shuffle,
+          volume,
+          fade_in,
+          once,
+          enabled,
+          last_run_at,
+          created_at,
+          updated_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+        "#,
+    )
+    .bind(&schedule.id)
+    .bind(&schedule.name)
+    .bind(&schedule.cron)
+    .bind(&schedule.source)
+    .bind(&schedule.source_id)
+    .bind(schedule.shuffle)
+    .bind(schedule.volume)
+    .bind(schedule.fade_in)
+    .bind(schedule.once)
+    .bind(schedule.enabled)
+    .bind(schedule.last_run_at)
+    .bind(schedule.created_at)
+    .bind(schedule.updated_at)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error saving schedule: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn update(pool: Pool<Sqlite>, schedule: &Schedule) -> Result<(), sqlx::Error> {
+    match sqlx::query(
+        r#"
+        UPDATE schedule SET
+          name = $2,
+          cron = $3,
+          source = $4,
+          source_id = $5,
+          shuffle = $6,
+          volume = $7,
+          fade_in = $8,
+          once = $9,
+          enabled = $10,
+          updated_at = $11
+        WHERE id = $1
+        "#,
+    )
+    .bind(&schedule.id)
+    .bind(&schedule.name)
+    .bind(&schedule.cron)
+    .bind(&schedule.source)
+    .bind(&schedule.source_id)
+    .bind(schedule.shuffle)
+    .bind(schedule.volume)
+    .bind(schedule.fade_in)
+    .bind(schedule.once)
+    .bind(schedule.enabled)
+    .bind(schedule.updated_at)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error updating schedule: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Records a run, and disables the schedules running once.
+pub async fn update_last_run(
+    pool: Pool<Sqlite>,
+    id: &str,
+    last_run_at: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    match sqlx::query(
+        r#"
+        UPDATE schedule SET last_run_at = $2, enabled = enabled AND NOT once WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(last_run_at)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error updating schedule: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn find(pool: Pool<Sqlite>, id: &str) -> Result<Option<Schedule>, sqlx::Error> {
+    match sqlx::query_as::<_, Schedule>(
+        r#"
+        SELECT * FROM schedule WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(&pool)
+    .await
+    {
+        Ok(schedule) => Ok(schedule),
+        Err(e) => {
+            eprintln!("Error finding schedule: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn all(pool: Pool<Sqlite>) -> Result<Vec<Schedule>, sqlx::Error> {
+    match sqlx::query_as::<_, Schedule>(
+        r#"
+        SELECT * FROM schedule ORDER BY created_at ASC
+        "#,
+    )
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(schedules) => Ok(schedules),
+        Err(e) => {
+            eprintln!("Error fetching schedules: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn all_enabled(pool: Pool<Sqlite>) -> Result<Vec<Schedule>, sqlx::Error> {
+    match sqlx::query_as::<_, Schedule>(
+        r#"
+        SELECT * FROM schedule WHERE enabled = TRUE
+        "#,
+    )
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(schedules) => Ok(schedules),
+        Err(e) => {
+            eprintln!("Error fetching schedules: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn delete(pool: Pool<Sqlite>, id: &str) -> Result<bool, sqlx::Error> {
+    match sqlx::query(
+        r#"
+        DELETE FROM schedule WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .execute(&pool)
+    .await
+    {
+        Ok(result) => Ok(result.rows_affected() > 0),
+        Err(e) => {
+            eprintln!("Error deleting schedule: {:?}", e);
+            Err(e)
+        }
+    }
+}
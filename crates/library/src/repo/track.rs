@@ -1,7 +1,31 @@
+use std::path::Path;
+
 use crate::entity::track::Track;
-use sqlx::{Error, Pool, Sqlite};
+use crate::list::{Conditions, ListOptions};
+use sqlx::{Error, Pool, QueryBuilder, Sqlite};
 
+/// Saves the track unless its file is already in the library. A track tagged
+/// with a MusicBrainz recording id that is already in the same album, under
+/// a path that no longer exists, is the same file moved or renamed: its path
+/// is updated and its id kept, so its playlists and favourites still point to
+/// it. Other copies of a recording are tracks of their own.
 pub async fn save(pool: Pool<Sqlite>, track: Track) -> Result<String, Error> {
+    if let Some(musicbrainz_id) = &track.musicbrainz_id {
+        let existing =
+            find_by_musicbrainz_id(pool.clone(), musicbrainz_id, &track.album_id).await?;
+        if let Some(existing) = existing.iter().find(|t| t.path == track.path) {
+            return Ok(existing.id.clone());
+        }
+        if let Some(moved) = existing.iter().find(|t| !Path::new(&t.path).exists()) {
+            if update_path(pool.clone(), &moved.id, &track.path, &track.md5)
+                .await
+                .is_ok()
+            {
+                return Ok(moved.id.clone());
+            }
+        }
+    }
+
     match sqlx::query(
         r#"
         INSERT INTO track (
@@ -26,9 +50,10 @@ pub async fn save(pool: Pool<Sqlite>, track: Track) -> Result<String, Error> {
           updated_at,
           artist_id,
           album_id,
-          album_art
+          album_art,
+          musicbrainz_id
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23)
         "#,
     )
     .bind(&track.id)
@@ -53,22 +78,159 @@ pub async fn save(pool: Pool<Sqlite>, track: Track) -> Result<String, Error> {
     .bind(&track.artist_id)
     .bind(&track.album_id)
     .bind(&track.album_art)
+    .bind(&track.musicbrainz_id)
     .execute(&pool)
     .await {
         Ok(_) => Ok(track.id.clone()),
         Err(_e) => {
             // eprintln!("Error saving track: {:?}", e);
-            let track = find_by_md5(pool.clone(), &track.md5).await?;
-            Ok(track.unwrap().id)
+            let existing = find_by_md5(pool.clone(), &track.md5).await?.unwrap();
+            if let (None, Some(musicbrainz_id)) =
+                (&existing.musicbrainz_id, &track.musicbrainz_id)
+            {
+                update_musicbrainz_id(pool.clone(), &existing.id, musicbrainz_id).await?;
+            }
+            Ok(existing.id)
         }
     }
 }
 
-pub async fn find(pool: Pool<Sqlite>, id: &str) -> Result<Option<Track>, Error> {
-    let result: Option<Track> = sqlx::query_as("SELECT * FROM track WHERE id = $1")
+/// Fails when another track already has the path.
+pub async fn update_path(pool: Pool<Sqlite>, id: &str, path: &str, md5: &str) -> Result<(), Error> {
+    sqlx::query("UPDATE track SET path = $1, md5 = $2, updated_at = $3 WHERE id = $4")
+        .bind(path)
+        .bind(md5)
+        .bind(chrono::Utc::now())
         .bind(id)
-        .fetch_optional(&pool)
+        .execute(&pool)
+        .await?;
+    Ok(())
+}
+
+/// Counts a play of the track, starting from the playcount of rockbox's
+/// database when it counted more plays.
+pub async fn record_play(pool: Pool<Sqlite>, id: &str, playcount: i64) -> Result<(), Error> {
+    sqlx::query("UPDATE track SET play_count = MAX(play_count, $1) + 1 WHERE id = $2")
+        .bind(playcount)
+        .bind(id)
+        .execute(&pool)
         .await?;
+    Ok(())
+}
+
+/// Stores the tags read with lofty, they are refreshed on every scan since
+/// `save` keeps the existing row of a track.
+pub async fn update_tags(pool: Pool<Sqlite>, id: &str, track: &Track) -> Result<(), Error> {
+    match sqlx::query(
+        r#"
+        UPDATE track SET
+          replaygain_track_gain = $1,
+          replaygain_track_peak = $2,
+          replaygain_album_gain = $3,
+          replaygain_album_peak = $4,
+          bpm = $5,
+          rating = $6,
+          comment = $7,
+          label = $8,
+          isrc = $9,
+          original_date = $10,
+          title_sort = $11,
+          artist_sort = $12,
+          album_sort = $13,
+          album_artist_sort = $14,
+          composer_sort = $15
+        WHERE id = $16
+        "#,
+    )
+    .bind(track.replaygain_track_gain)
+    .bind(track.replaygain_track_peak)
+    .bind(track.replaygain_album_gain)
+    .bind(track.replaygain_album_peak)
+    .bind(track.bpm)
+    .bind(track.rating)
+    .bind(&track.comment)
+    .bind(&track.label)
+    .bind(&track.isrc)
+    .bind(&track.original_date)
+    .bind(&track.title_sort)
+    .bind(&track.artist_sort)
+    .bind(&track.album_sort)
+    .bind(&track.album_artist_sort)
+    .bind(&track.composer_sort)
+    .bind(id)
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error updating track: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Points the tracks of an album to its cover.
+pub async fn update_album_art(
+    pool: Pool<Sqlite>,
+    album_id: &str,
+    album_art: &str,
+) -> Result<(), Error> {
+    match sqlx::query("UPDATE track SET album_art = $1 WHERE album_id = $2")
+        .bind(album_art)
+        .bind(album_id)
+        .execute(&pool)
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error updating track: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+pub async fn update_musicbrainz_id(
+    pool: Pool<Sqlite>,
+    id: &str,
+    musicbrainz_id: &str,
+) -> Result<(), Error> {
+    match sqlx::query("UPDATE track SET musicbrainz_id = $1 WHERE id = $2")
+        .bind(musicbrainz_id)
+        .bind(id)
+        .execute(&pool)
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Error updating track: {:?}", e);
+            Err(e)
+        }
+    }
+}
+
+/// The copies of a recording in an album.
+pub async fn find_by_musicbrainz_id(
+    pool: Pool<Sqlite>,
+    musicbrainz_id: &str,
+    album_id: &str,
+) -> Result<Vec<Track>, Error> {
+    let result: Vec<Track> =
+        sqlx::query_as("SELECT * FROM track WHERE musicbrainz_id = $1 AND album_id = $2")
+            .bind(musicbrainz_id)
+            .bind(album_id)
+            .fetch_all(&pool)
+            .await?;
+    Ok(result)
+}
+
+/// Unlike the other queries, the track comes with its lyrics.
+pub async fn find(pool: Pool<Sqlite>, id: &str) -> Result<Option<Track>, Error> {
+    let result: Option<Track> = sqlx::query_as(
+        "SELECT track.*, lyrics.lyrics FROM track LEFT JOIN lyrics ON lyrics.track_id = track.id WHERE track.id = $1",
+    )
+    .bind(id)
+    .fetch_optional(&pool)
+    .await?;
     Ok(result)
 }
 
@@ -103,6 +265,22 @@ pub async fn find_by_path(pool: Pool<Sqlite>, path: &str) -> Result<Option<Track
     Ok(result)
 }
 
+/// Removes a track whose file was deleted, with its album, artist and genre
+/// links, its likes and its playlist entries.
+pub async fn delete(pool: Pool<Sqlite>, id: &str) -> Result<(), Error> {
+    for query in [
+        "DELETE FROM album_tracks WHERE track_id = $1",
+        "DELETE FROM artist_tracks WHERE track_id = $1",
+        "DELETE FROM genre_tracks WHERE track_id = $1",
+        "DELETE FROM favourites WHERE track_id = $1",
+        "DELETE FROM playlist_tracks WHERE track_id = $1",
+        "DELETE FROM track WHERE id = $1",
+    ] {
+        sqlx::query(query).bind(id).execute(&pool).await?;
+    }
+    Ok(())
+}
+
 pub async fn all(pool: Pool<Sqlite>) -> Result<Vec<Track>, Error> {
     let result: Vec<Track> = sqlx::query_as("SELECT * FROM track ORDER BY title ASC")
         .fetch_all(&pool)
@@ -162,3 +340,218 @@ pub async fn find_by_artist_album_date(
     .await?;
     Ok(result)
 }
+
+pub async fn find_by_genre(pool: Pool<Sqlite>, genre: &str) -> Result<Vec<Track>, Error> {
+    let result: Vec<Track> = sqlx::query_as(
+        "SELECT track.* FROM track INNER JOIN genre_tracks ON genre_tracks.track_id = track.id INNER JOIN genre ON genre_tracks.genre_id = genre.id WHERE genre.name = $1 COLLATE NOCASE ORDER BY title ASC",
+    )
+    .bind(genre)
+    .fetch_all(&pool)
+    .await?;
+    Ok(result)
+}
+
+pub async fn update_genre(
+    pool: Pool<Sqlite>,
+    id: &str,
+    genre: Option<&str>,
+    genre_id: Option<&str>,
+) -> Result<(), Error> {
+    sqlx::query("UPDATE track SET genre = $1, genre_id = $2 WHERE id = $3")
+        .bind(genre)
+        .bind(genre_id)
+        .bind(id)
+        .execute(&pool)
+        .await?;
+    Ok(())
+}
+
+const TRACK_SORT_COLUMNS: [(&str, &str); 7] = [
+    ("title", "title"),
+    ("artist", "artist"),
+    ("album", "album"),
+    ("year", "year"),
+    ("added", "created_at"),
+    ("length", "length"),
+    ("plays", "play_count"),
+];
+
+fn push_filters(query: &mut QueryBuilder<'_, Sqlite>, options: &ListOptions) {
+    let mut conditions = Conditions::new();
+    if let Some(artist_id) = &options.artist_id {
+        conditions.next(query);
+        query.push("artist_id = ").push_bind(artist_id.clone());
+    }
+    if let Some(album_id) = &options.album_id {
+        conditions.next(query);
+        query.push("album_id = ").push_bind(album_id.clone());
+    }
+    if let Some(genre_id) = &options.genre_id {
+        conditions.next(query);
+        query
+            .push("id IN (SELECT track_id FROM genre_tracks WHERE genre_id = ")
+            .push_bind(genre_id.clone())
+            .push(")");
+    }
+    if let Some(year_from) = options.year_from {
+        conditions.next(query);
+        query.push("year >= ").push_bind(year_from);
+    }
+    if let Some(year_to) = options.year_to {
+        conditions.next(query);
+        query.push("year <= ").push_bind(year_to);
+    }
+}
+
+pub async fn list(pool: Pool<Sqlite>, options: &ListOptions) -> Result<Vec<Track>, Error> {
+    let mut query = QueryBuilder::new("SELECT * FROM track");
+    push_filters(&mut query, options);
+    options.push_order_by(&mut query, &TRACK_SORT_COLUMNS);
+    options.push_pagination(&mut query);
+    let result: Vec<Track> = query.build_query_as().fetch_all(&pool).await?;
+    Ok(result)
+}
+
+pub async fn count(pool: Pool<Sqlite>, options: &ListOptions) -> Result<i64, Error> {
+    let mut query = QueryBuilder::new("SELECT COUNT(*) FROM track");
+    push_filters(&mut query, options);
+    let result: i64 = query.build_query_scalar().fetch_one(&pool).await?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use super::*;
+
+    async fn library() -> Pool<Sqlite> {
+        // Every connection to an in-memory database opens its own database
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::migrate(&pool).await.unwrap();
+        pool
+    }
+
+    fn recording(id: &str, path: &str) -> Track {
+        Track {
+            id: id.to_string(),
+            path: path.to_string(),
+            md5: format!("{:x}", md5::compute(path.as_bytes())),
+            album_id: "album".to_string(),
+            artist_id: "artist".to_string(),
+            musicbrainz_id: Some("recording".to_string()),
+            ..Default::default()
+        }
+    }
+
+    async fn count(pool: &Pool<Sqlite>) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM track")
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn copies_of_a_recording_are_saved_apart() {
+        let pool = library().await;
+        let directory = std::env::temp_dir().join(format!("rockbox-copies-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let (album, compilation) = (directory.join("album.flac"), directory.join("best-of.flac"));
+        std::fs::write(&album, b"").unwrap();
+        std::fs::write(&compilation, b"").unwrap();
+
+        let first = save(pool.clone(), recording("1", album.to_str().unwrap()))
+            .await
+            .unwrap();
+        let second = save(pool.clone(), recording("2", compilation.to_str().unwrap()))
+            .await
+            .unwrap();
+        let rescanned = save(pool.clone(), recording("3", compilation.to_str().unwrap()))
+            .await
+            .unwrap();
+
+        assert_eq!((first.as_str(), second.as_str()), ("1", "2"));
+        assert_eq!(rescanned, "2");
+        assert_eq!(count(&pool).await, 2);
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn a_moved_recording_keeps_its_id() {
+        let pool = library().await;
+        let directory = std::env::temp_dir().join(format!("rockbox-moved-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let moved = directory.join("renamed.flac");
+        std::fs::write(&moved, b"").unwrap();
+
+        save(pool.clone(), recording("1", "/music/gone.flac"))
+            .await
+            .unwrap();
+        let id = save(pool.clone(), recording("2", moved.to_str().unwrap()))
+            .await
+            .unwrap();
+
+        assert_eq!(id, "1");
+        assert_eq!(count(&pool).await, 1);
+        let track = find(pool.clone(), "1").await.unwrap().unwrap();
+        assert_eq!(track.path, moved.to_str().unwrap());
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn a_deleted_track_leaves_no_likes_or_playlist_entries() {
+        let pool = library().await;
+        let id = save(pool.clone(), recording("1", "/missing/episode.mp3"))
+            .await
+            .unwrap();
+        for query in [
+            "INSERT INTO favourites (id, track_id) VALUES ('like', $1)",
+            "INSERT INTO playlist_tracks (id, playlist_id, track_id) VALUES ('entry', 'playlist', $1)",
+        ] {
+            sqlx::query(query).bind(&id).execute(&pool).await.unwrap();
+        }
+
+        delete(pool.clone(), &id).await.unwrap();
+
+        assert_eq!(count(&pool).await, 0);
+        for table in ["favourites", "playlist_tracks"] {
+            let rows: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", table))
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(rows, 0, "{} still references the track", table);
+        }
+    }
+
+    #[tokio::test]
+    async fn plays_are_counted_and_sorted() {
+        let pool = library().await;
+        let mut first = recording("1", "/music/first.flac");
+        first.musicbrainz_id = None;
+        let mut second = recording("2", "/music/second.flac");
+        second.musicbrainz_id = None;
+        save(pool.clone(), first).await.unwrap();
+        save(pool.clone(), second).await.unwrap();
+
+        record_play(pool.clone(), "1", 0).await.unwrap();
+        // the database of rockbox counted more plays
+        record_play(pool.clone(), "2", 4).await.unwrap();
+        record_play(pool.clone(), "2", 0).await.unwrap();
+
+        let options = ListOptions {
+            sort: Some("plays".to_string()),
+            descending: true,
+            ..Default::default()
+        };
+        let tracks = list(pool.clone(), &options).await.unwrap();
+        let plays: Vec<(&str, u32)> = tracks
+            .iter()
+            .map(|track| (track.id.as_str(), track.play_count))
+            .collect();
+        assert_eq!(plays, [("2", 6), ("1", 1)]);
+    }
+}
@@ -0,0 +1,173 @@
+use std::{fs::File, io::ErrorKind, path::Path};
+
+use anyhow::{anyhow, Error};
+use serde::{Deserialize, Serialize};
+use symphonia::core::{
+    audio::SampleBuffer,
+    codecs::{DecoderOptions, CODEC_TYPE_NULL},
+    errors::Error as DecodeError,
+    formats::{FormatOptions, SeekMode, SeekTo},
+    io::MediaSourceStream,
+    meta::MetadataOptions,
+    probe::Hint,
+    units::Time,
+};
+
+/// Samples below -60 dBFS are silent.
+pub const THRESHOLD: f32 = 0.001;
+
+/// Seconds decoded at each end of a track, longer silences are only
+/// trimmed this far.
+const WINDOW_SECONDS: u64 = 15;
+
+/// Kept before and after the sound, so fades are not cut.
+const MARGIN_MS: u64 = 100;
+
+/// Where the sound of a track starts and ends, in milliseconds.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Silence {
+    /// The end of the leading silence.
+    pub start: u64,
+    /// The start of the trailing silence, the length of the track when
+    /// there is none.
+    pub end: u64,
+    pub length: u64,
+}
+
+/// Decodes the beginning and the end of a file to find its leading and
+/// trailing silence.
+pub fn detect(path: &str) -> Result<Silence, Error> {
+    let source = MediaSourceStream::new(Box::new(File::open(path)?), Default::default());
+    let mut hint = Hint::new();
+    if let Some(extension) = Path::new(path).extension().and_then(|e| e.to_str()) {
+        hint.with_extension(extension);
+    }
+    let probed = symphonia::default::get_probe().format(
+        &hint,
+        source,
+        &FormatOptions::default(),
+        &MetadataOptions::default(),
+    )?;
+    let mut format = probed.format;
+    let track = format
+        .tracks()
+        .iter()
+        .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| anyhow!("No audio track"))?;
+    let track_id = track.id;
+    let rate = track
+        .codec_params
+        .sample_rate
+        .ok_or_else(|| anyhow!("Unknown sample rate"))? as u64;
+    let frames = track.codec_params.n_frames;
+    let time_base = track.codec_params.time_base;
+    let size = std::fs::metadata(path)?.len();
+    let window = WINDOW_SECONDS * rate;
+    let mut decoder =
+        symphonia::default::get_codecs().make(&track.codec_params, &DecoderOptions::default())?;
+
+    let mut position = 0;
+    let mut bytes = 0;
+    let mut first: Option<u64> = None;
+    let mut last: Option<u64> = None;
+    let mut tail_start: Option<u64> = None;
+    // the end of the track could not be reached without decoding it all
+    let mut truncated = false;
+    let mut samples: Option<SampleBuffer<f32>> = None;
+    loop {
+        if tail_start.is_none() && position >= window {
+            // the end is decoded from there when the track is long enough to
+            // seek, without a frame count its length is estimated from the
+            // bitrate of the beginning and decoded from further ahead
+            let tail = match frames {
+                Some(frames) => (frames > 3 * window).then(|| frames - window),
+                None => {
+                    let estimate = size * position / bytes.max(1);
+                    (estimate > 3 * window).then(|| estimate - 2 * window)
+                }
+            };
+            if let Some(tail) = tail {
+                let time = Time::new(tail / rate, (tail % rate) as f64 / rate as f64);
+                let seeked = format.seek(
+                    SeekMode::Coarse,
+                    SeekTo::Time {
+                        time,
+                        track_id: Some(track_id),
+                    },
+                );
+                match (seeked, time_base) {
+                    (Ok(seeked), Some(time_base)) => {
+                        let time = time_base.calc_time(seeked.actual_ts);
+                        position = time.seconds * rate + (time.frac * rate as f64) as u64;
+                        decoder.reset();
+                        // the sound found so far is not the end of the track
+                        last = None;
+                        tail_start = Some(position);
+                    }
+                    _ => {
+                        truncated = true;
+                        break;
+                    }
+                }
+            } else if frames.is_none() && position >= 4 * window {
+                // the estimate was too short, the track is not decoded further
+                truncated = true;
+                break;
+            }
+        }
+
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(DecodeError::IoError(e)) if e.kind() == ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(e.into()),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        bytes += packet.buf().len() as u64;
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            Err(DecodeError::DecodeError(_)) => continue,
+            Err(e) => return Err(e.into()),
+        };
+
+        let spec = *decoded.spec();
+        let channels = spec.channels.count();
+        if samples
+            .as_ref()
+            .map(|samples| samples.capacity() < decoded.capacity() * channels)
+            .unwrap_or(true)
+        {
+            samples = Some(SampleBuffer::new(decoded.capacity() as u64, spec));
+        }
+        let samples = samples.as_mut().unwrap();
+        samples.copy_interleaved_ref(decoded);
+
+        let mut decoded_frames = 0;
+        for (frame, values) in samples.samples().chunks(channels).enumerate() {
+            decoded_frames += 1;
+            if values.iter().any(|value| value.abs() > THRESHOLD) {
+                let frame = position + frame as u64;
+                first.get_or_insert(frame);
+                last = Some(frame + 1);
+            }
+        }
+        position += decoded_frames;
+    }
+
+    let to_ms = |frames: u64| frames * 1000 / rate;
+    let length = to_ms(position);
+    let margin = MARGIN_MS * rate / 1000;
+    Ok(Silence {
+        // a longer leading silence is trimmed up to the window
+        start: to_ms(first.unwrap_or(position).min(window).saturating_sub(margin)),
+        // the trailing silence is kept when the end was not decoded
+        end: match (last, tail_start) {
+            _ if truncated => length,
+            (Some(last), _) => to_ms(last + margin).min(length),
+            (None, Some(tail_start)) => to_ms(tail_start),
+            (None, None) => length,
+        },
+        length,
+    })
+}
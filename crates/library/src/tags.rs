@@ -0,0 +1,344 @@
+use lofty::{
+    config::ParseOptions,
+    file::{AudioFile, TaggedFileExt},
+    id3::v2::{Frame, Id3v2Tag},
+    mpeg::MpegFile,
+    tag::{ItemKey, Tag},
+};
+
+pub const VARIOUS_ARTISTS: &str = "Various Artists";
+
+const FEATURING: [&str; 7] = [
+    "(featuring ",
+    "(feat. ",
+    "(ft. ",
+    " featuring ",
+    " feat. ",
+    " feat ",
+    " ft. ",
+];
+const ARTIST_SEPARATORS: [char; 2] = [';', '\0'];
+// ID3v2.3 has no multi-valued frames, Picard joins the ids with a slash
+const ID_SEPARATORS: [char; 3] = [';', '/', '\0'];
+
+/// The artists credited on a track and whether it belongs to a compilation,
+/// read from the tags rockbox's metadata parser doesn't expose.
+#[derive(Debug, Default, Clone)]
+pub struct Credits {
+    pub compilation: bool,
+    /// The main artist first, then the featured ones.
+    pub artists: Vec<String>,
+}
+
+impl Credits {
+    pub fn main_artist(&self) -> Option<&str> {
+        self.artists.first().map(|artist| artist.as_str())
+    }
+}
+
+/// The MusicBrainz identifiers written by taggers like Picard, they identify
+/// a track, an album or an artist regardless of the file's path or of how its
+/// other tags are spelled.
+#[derive(Debug, Default, Clone)]
+pub struct MusicBrainzIds {
+    /// MUSICBRAINZ_TRACKID, the recording.
+    pub track_id: Option<String>,
+    /// MUSICBRAINZ_ALBUMID, the release, each edition of an album has its own.
+    pub album_id: Option<String>,
+    /// MUSICBRAINZ_RELEASEGROUPID, shared by every edition of an album.
+    pub release_group_id: Option<String>,
+    /// MUSICBRAINZ_ARTISTID, one per credited artist, in the credits order.
+    pub artist_ids: Vec<String>,
+    /// MUSICBRAINZ_ALBUMARTISTID
+    pub album_artist_id: Option<String>,
+}
+
+impl MusicBrainzIds {
+    /// The identifier of a credited artist, only known when the file has as
+    /// many artist ids as credited artists.
+    pub fn artist_id(&self, credits: &Credits, index: usize) -> Option<String> {
+        match self.artist_ids.len() == credits.artists.len() {
+            true => self.artist_ids.get(index).cloned(),
+            false => None,
+        }
+    }
+}
+
+/// Tags kept on the track as is, none of them is parsed by rockbox.
+#[derive(Debug, Default, Clone)]
+pub struct ExtendedTags {
+    /// USLT/LYRICS, the unsynchronized lyrics.
+    pub lyrics: Option<String>,
+    /// ReplayGain values, gains are in dB.
+    pub replaygain_track_gain: Option<f32>,
+    pub replaygain_track_peak: Option<f32>,
+    pub replaygain_album_gain: Option<f32>,
+    pub replaygain_album_peak: Option<f32>,
+    pub bpm: Option<f32>,
+    /// From 1 to 5 stars, read from POPM, RATING or FMPS_RATING.
+    pub rating: Option<u32>,
+    pub comment: Option<String>,
+    pub label: Option<String>,
+    pub isrc: Option<String>,
+    pub original_date: Option<String>,
+    pub title_sort: Option<String>,
+    pub artist_sort: Option<String>,
+    pub album_sort: Option<String>,
+    pub album_artist_sort: Option<String>,
+    pub composer_sort: Option<String>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Tags {
+    pub credits: Credits,
+    pub musicbrainz: MusicBrainzIds,
+    pub extended: ExtendedTags,
+}
+
+/// Reads the tags rockbox doesn't expose, `artist` is the artist reported by
+/// rockbox, used when the file has no artist tag lofty can read.
+pub fn read_tags(path: &str, artist: &str) -> Tags {
+    let mut tags = Tags::default();
+    let mut artists: Vec<String> = vec![];
+
+    if let Ok(tagged_file) = lofty::read_from_path(path) {
+        if let Some(tag) = tagged_file.primary_tag().or(tagged_file.first_tag()) {
+            tags.credits.compilation = tag
+                .get_string(&ItemKey::FlagCompilation)
+                .map(|value| matches!(value.trim(), "1" | "true" | "TRUE" | "True"))
+                .unwrap_or(false);
+            artists.extend(tag.get_strings(&ItemKey::TrackArtist).map(String::from));
+            // the multi-valued ARTISTS field of Vorbis comments and APE tags
+            let multiple = ItemKey::Unknown("ARTISTS".to_string());
+            artists.extend(tag.get_strings(&multiple).map(String::from));
+
+            let id = |key: &ItemKey| tag.get_string(key).and_then(musicbrainz_id);
+            tags.musicbrainz = MusicBrainzIds {
+                track_id: id(&ItemKey::MusicBrainzRecordingId),
+                album_id: id(&ItemKey::MusicBrainzReleaseId),
+                release_group_id: id(&ItemKey::MusicBrainzReleaseGroupId),
+                artist_ids: tag
+                    .get_strings(&ItemKey::MusicBrainzArtistId)
+                    .flat_map(|value| value.split(ID_SEPARATORS))
+                    .filter_map(musicbrainz_id)
+                    .collect(),
+                album_artist_id: id(&ItemKey::MusicBrainzReleaseArtistId),
+            };
+
+            let text = |key: &ItemKey| {
+                tag.get_string(key)
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(String::from)
+            };
+            let number = |key: &ItemKey| tag.get_string(key).and_then(parse_number);
+            tags.extended = ExtendedTags {
+                lyrics: text(&ItemKey::Lyrics),
+                replaygain_track_gain: number(&ItemKey::ReplayGainTrackGain),
+                replaygain_track_peak: number(&ItemKey::ReplayGainTrackPeak),
+                replaygain_album_gain: number(&ItemKey::ReplayGainAlbumGain),
+                replaygain_album_peak: number(&ItemKey::ReplayGainAlbumPeak),
+                bpm: number(&ItemKey::Bpm).or_else(|| number(&ItemKey::IntegerBpm)),
+                rating: read_popm(path).or_else(|| read_rating(tag)),
+                comment: text(&ItemKey::Comment),
+                label: text(&ItemKey::Label),
+                isrc: text(&ItemKey::Isrc),
+                original_date: text(&ItemKey::OriginalReleaseDate),
+                title_sort: text(&ItemKey::TrackTitleSortOrder),
+                artist_sort: text(&ItemKey::TrackArtistSortOrder),
+                album_sort: text(&ItemKey::AlbumTitleSortOrder),
+                album_artist_sort: text(&ItemKey::AlbumArtistSortOrder),
+                composer_sort: text(&ItemKey::ComposerSortOrder),
+            };
+        }
+    }
+
+    tags.credits = credits(tags.credits.compilation, artists, artist);
+    tags
+}
+
+fn credits(compilation: bool, mut artists: Vec<String>, artist: &str) -> Credits {
+    let mut credits = Credits {
+        compilation,
+        artists: vec![],
+    };
+
+    if artists.is_empty() {
+        artists.push(artist.to_string());
+    }
+
+    for artist in artists {
+        for name in split_artists(&artist) {
+            if !credits
+                .artists
+                .iter()
+                .any(|a| a.eq_ignore_ascii_case(&name))
+            {
+                credits.artists.push(name);
+            }
+        }
+    }
+
+    credits
+}
+
+/// Reads "-6.54 dB", "0.988" or "128" as a number.
+fn parse_number(value: &str) -> Option<f32> {
+    let value = value.trim();
+    let value = value
+        .strip_suffix("dB")
+        .or_else(|| value.strip_suffix("db"))
+        .unwrap_or(value);
+    value.trim().parse::<f32>().ok().filter(|n| n.is_finite())
+}
+
+/// The rating of the POPM frames of an MP3, lofty drops them when merging
+/// the ID3v2 tag into a generic one.
+fn read_popm(path: &str) -> Option<u32> {
+    if !path.to_lowercase().ends_with(".mp3") {
+        return None;
+    }
+    let mut file = std::fs::File::open(path).ok()?;
+    let mpeg = MpegFile::read_from(&mut file, ParseOptions::new()).ok()?;
+    mpeg.id3v2().and_then(popm_rating)
+}
+
+/// POPM frames store a rating from 1 to 255 per player, 0 when unknown.
+fn popm_rating(tag: &Id3v2Tag) -> Option<u32> {
+    tag.into_iter().find_map(|frame| match frame {
+        Frame::Popularimeter(popularimeter) => popm_stars(popularimeter.rating),
+        _ => None,
+    })
+}
+
+/// The other formats use a free-form RATING from 1 to 5, 1 to 100 or an
+/// FMPS_RATING between 0 and 1.
+fn read_rating(tag: &Tag) -> Option<u32> {
+    if let Some(rating) = tag
+        .get_string(&ItemKey::Popularimeter)
+        .and_then(parse_number)
+    {
+        return stars(rating);
+    }
+
+    if let Some(rating) = tag
+        .get_string(&ItemKey::Unknown("FMPS_RATING".to_string()))
+        .and_then(parse_number)
+    {
+        return stars((rating * 5.0).max(0.0));
+    }
+
+    tag.get_string(&ItemKey::Unknown("RATING".to_string()))
+        .and_then(parse_number)
+        .and_then(stars)
+}
+
+fn popm_stars(rating: u8) -> Option<u32> {
+    match rating {
+        0 => None,
+        1..=31 => Some(1),
+        32..=95 => Some(2),
+        96..=159 => Some(3),
+        160..=223 => Some(4),
+        _ => Some(5),
+    }
+}
+
+fn stars(rating: f32) -> Option<u32> {
+    match rating {
+        r if r <= 0.0 => None,
+        r if r <= 5.0 => Some(r.round().max(1.0) as u32),
+        r if r <= 100.0 => Some((r / 20.0).round().max(1.0) as u32),
+        r if r <= 255.0 => popm_stars(r as u8),
+        _ => None,
+    }
+}
+
+/// MusicBrainz ids are UUIDs, anything else is ignored.
+fn musicbrainz_id(value: &str) -> Option<String> {
+    let value = value.trim().to_lowercase();
+    match value.len() == 36 && value.chars().all(|c| c == '-' || c.is_ascii_hexdigit()) {
+        true => Some(value),
+        false => None,
+    }
+}
+
+/// Splits "A feat. B; C" into "A", "B" and "C".
+pub fn split_artists(artist: &str) -> Vec<String> {
+    let mut names = vec![];
+    for value in artist.split(ARTIST_SEPARATORS) {
+        let mut rest = value;
+        // to_ascii_lowercase keeps the byte offsets of the original string
+        while let Some((start, separator)) = FEATURING
+            .iter()
+            .filter_map(|separator| {
+                rest.to_ascii_lowercase()
+                    .find(separator)
+                    .map(|start| (start, *separator))
+            })
+            .min_by_key(|(start, _)| *start)
+        {
+            names.push(rest[..start].to_string());
+            rest = &rest[start + separator.len()..];
+        }
+        names.push(rest.to_string());
+    }
+
+    names
+        .iter()
+        .map(|name| name.trim_matches(|c: char| c.is_whitespace() || c == '(' || c == ')'))
+        .filter(|name| !name.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use lofty::id3::v2::PopularimeterFrame;
+
+    use super::*;
+
+    #[test]
+    fn parses_numbers() {
+        assert_eq!(parse_number("-6.54 dB"), Some(-6.54));
+        assert_eq!(parse_number(" 0.988 "), Some(0.988));
+        assert_eq!(parse_number("128"), Some(128.0));
+        assert_eq!(parse_number("+1.20 db"), Some(1.2));
+        assert_eq!(parse_number("NaN"), None);
+        assert_eq!(parse_number("loud"), None);
+    }
+
+    #[test]
+    fn maps_ratings_to_stars() {
+        assert_eq!(stars(0.0), None);
+        assert_eq!(stars(0.2), Some(1));
+        assert_eq!(stars(3.0), Some(3));
+        assert_eq!(stars(5.0), Some(5));
+        assert_eq!(stars(60.0), Some(3));
+        assert_eq!(stars(100.0), Some(5));
+        assert_eq!(stars(196.0), Some(4));
+        assert_eq!(stars(256.0), None);
+    }
+
+    #[test]
+    fn maps_popm_ratings_to_stars() {
+        assert_eq!(popm_stars(0), None);
+        assert_eq!(popm_stars(1), Some(1));
+        assert_eq!(popm_stars(64), Some(2));
+        assert_eq!(popm_stars(128), Some(3));
+        assert_eq!(popm_stars(196), Some(4));
+        assert_eq!(popm_stars(255), Some(5));
+    }
+
+    #[test]
+    fn reads_the_popm_frame() {
+        let mut tag = Id3v2Tag::new();
+        assert_eq!(popm_rating(&tag), None);
+        tag.insert(Frame::Popularimeter(PopularimeterFrame::new(
+            "rockbox@example.com".to_string(),
+            196,
+            3,
+        )));
+        assert_eq!(popm_rating(&tag), Some(4));
+    }
+}
@@ -1,10 +1,77 @@
-use std::{fs, thread};
+use std::{fs, thread, time::SystemTime};
 
 use anyhow::Error;
+use rockbox_service::podcasts::podcasts_dir;
 use rockbox_settings::get_music_dir;
 
 use crate::{consts::AUDIO_EXTENSIONS, Context};
 
+/// The virtual directory of the music directory listing the downloaded
+/// podcast episodes.
+pub const PODCASTS_DIR: &str = "Podcasts";
+
+/// The virtual directory listing the radio stations, the favourites in
+/// [`RADIO_FAVOURITES_DIR`].
+pub const RADIO_DIR: &str = "Radio";
+pub const RADIO_FAVOURITES_DIR: &str = "Radio/Favourites";
+
+/// Whether a URI is a stream played as a radio station.
+pub fn is_stream(uri: &str) -> bool {
+    uri.starts_with("http://") || uri.starts_with("https://")
+}
+
+/// The stations of the radio directories, as streams named after them.
+pub async fn list_stations(ctx: &Context, path: &str) -> Result<String, Error> {
+    let favourites_only = path == RADIO_FAVOURITES_DIR;
+    let mut stations = String::new();
+    if !favourites_only {
+        stations.push_str(&format!("directory: {}\n", RADIO_FAVOURITES_DIR));
+    }
+    for station in ctx.service.stations(favourites_only).await? {
+        stations.push_str(&format!(
+            "file: {}\nTitle: {}\nName: {}\n",
+            station.url, station.name, station.name
+        ));
+        if let Some(genre) = station.genre {
+            stations.push_str(&format!("Genre: {}\n", genre));
+        }
+    }
+    Ok(stations)
+}
+
+/// The path on disk of a URI, relative to the music directory or to the
+/// podcasts directory when it starts with [`PODCASTS_DIR`]. Absolute paths
+/// are kept.
+pub fn resolve_path(uri: &str, music_dir: &str) -> String {
+    if uri.starts_with('/') {
+        return uri.to_string();
+    }
+    match uri.strip_prefix(PODCASTS_DIR) {
+        Some("") => podcasts_dir(),
+        Some(rest) if rest.starts_with('/') => format!("{}{}", podcasts_dir(), rest),
+        _ => format!("{}/{}", music_dir.trim_end_matches('/'), uri),
+    }
+}
+
+/// The URI of a path on disk, the inverse of [`resolve_path`].
+pub fn uri(path: &str, music_dir: &str) -> String {
+    let podcasts_dir = podcasts_dir();
+    let uri = match path.strip_prefix(&podcasts_dir) {
+        Some(rest) => format!("{}{}", PODCASTS_DIR, rest),
+        None => path.replace(music_dir, ""),
+    };
+    uri.trim_start_matches('/').to_string()
+}
+
+fn format_last_modified(modified: SystemTime) -> Result<String, Error> {
+    let last_modified = chrono::DateTime::from_timestamp(
+        modified.duration_since(std::time::UNIX_EPOCH)?.as_secs() as i64,
+        0,
+    )
+    .unwrap();
+    Ok(last_modified.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+}
+
 pub fn read_dir(
     ctx: Context,
     path: String,
@@ -41,22 +108,8 @@ pub fn read_dir(
             }
 
             if AUDIO_EXTENSIONS.iter().any(|ext| path_str.ends_with(ext)) || path.is_dir() {
-                let last_modified = entry.metadata()?.modified()?;
-                let last_modified = chrono::DateTime::from_timestamp(
-                    last_modified
-                        .duration_since(std::time::UNIX_EPOCH)?
-                        .as_secs() as i64,
-                    0,
-                )
-                .unwrap();
-
-                let last_modified = last_modified.format("%Y-%m-%dT%H:%M:%SZ").to_string();
-                let entry_str = path_str.replace(music_dir, "");
-                let entry_str = if entry_str.starts_with('/') {
-                    &entry_str[1..]
-                } else {
-                    &entry_str
-                };
+                let last_modified = format_last_modified(entry.metadata()?.modified()?)?;
+                let entry_str = uri(&path_str, music_dir);
 
                 let mut metadata = format!(
                     "{}: {}\nLast-Modified: {}\n",
@@ -104,6 +157,26 @@ pub fn read_dir(
         Ok(())
     }
 
+    let root = path.trim_end_matches('/') == music_dir.trim_end_matches('/');
     read_dir_recursive(&ctx, path, &music_dir, recursive, &mut files, with_metadata)?;
+
+    let podcasts_dir = podcasts_dir();
+    if root && fs::metadata(&podcasts_dir).is_ok() {
+        files.push_str(&format!(
+            "directory: {}\nLast-Modified: {}\n",
+            PODCASTS_DIR,
+            format_last_modified(fs::metadata(&podcasts_dir)?.modified()?)?
+        ));
+        if recursive {
+            read_dir_recursive(
+                &ctx,
+                podcasts_dir,
+                &music_dir,
+                recursive,
+                &mut files,
+                with_metadata,
+            )?;
+        }
+    }
     Ok(files)
 }
@@ -7,8 +7,8 @@ use super::{
     browse::{handle_listall, handle_listallinfo, handle_listfiles, handle_lsinfo},
     library::{
         handle_config, handle_find_album, handle_find_artist, handle_find_title, handle_list_album,
-        handle_list_artist, handle_list_title, handle_rescan, handle_search, handle_stats,
-        handle_tagtypes, handle_tagtypes_clear, handle_tagtypes_enable,
+        handle_list_artist, handle_list_genre, handle_list_title, handle_rescan, handle_search,
+        handle_stats, handle_tagtypes, handle_tagtypes_clear, handle_tagtypes_enable,
     },
     playback::{
         handle_currentsong, handle_getvol, handle_next, handle_outputs, handle_pause, handle_play,
@@ -101,6 +101,7 @@ pub async fn match_command(
         "move" => handle_move(ctx, request, tx.clone()).await,
         "list album" => handle_list_album(ctx, request, tx.clone()).await,
         "list artist" => handle_list_artist(ctx, request, tx.clone()).await,
+        "list genre" => handle_list_genre(ctx, request, tx.clone()).await,
         "list title" => handle_list_title(ctx, request, tx.clone()).await,
         "update" => handle_rescan(ctx, request, tx.clone()).await,
         "search" => handle_search(ctx, request, tx.clone()).await,
@@ -1,6 +1,9 @@
 use std::fs;
 
-use crate::{dir::read_dir, Context};
+use crate::{
+    dir::{list_stations, read_dir, resolve_path, uri, RADIO_DIR, RADIO_FAVOURITES_DIR},
+    Context,
+};
 use anyhow::Error;
 use regex::Regex;
 use rockbox_library::repo;
@@ -22,10 +25,17 @@ pub async fn handle_lsinfo(
         None => &music_dir,
     };
 
-    let path = match path.starts_with(&music_dir) {
-        true => path,
-        false => &format!("{}/{}", music_dir, path),
-    };
+    if path == RADIO_DIR || path == RADIO_FAVOURITES_DIR {
+        let mut response = list_stations(ctx, path).await?;
+        response.push_str("OK\n");
+        if !ctx.batch {
+            tx.send(response.clone()).await?;
+        }
+        return Ok(response);
+    }
+
+    let root = path == music_dir;
+    let path = &resolve_path(path, &music_dir);
 
     // verify if path is a file or directory or doesn't exist
     if fs::metadata(path).is_err() {
@@ -45,6 +55,9 @@ pub async fn handle_lsinfo(
     if fs::metadata(path)?.is_dir() {
         let files = read_dir(ctx.clone(), path.to_string(), false, true)?;
         response.push_str(&files);
+        if root && !ctx.service.stations(false).await?.is_empty() {
+            response.push_str(&format!("directory: {}\n", RADIO_DIR));
+        }
         response.push_str("OK\n");
     }
 
@@ -120,10 +133,7 @@ pub async fn handle_listfiles(
         None => &music_dir,
     };
 
-    let path = match path.starts_with(&music_dir) {
-        true => path,
-        false => &format!("{}/{}", music_dir, path),
-    };
+    let path = &resolve_path(path, &music_dir);
 
     // verify if path is a file or directory or doesn't exist
     if fs::metadata(&path).is_err() {
@@ -162,7 +172,7 @@ async fn build_file_metadata(
     let kv = ctx.kv.lock().await;
     let track = kv.get(path);
     let music_dir = get_music_dir()?;
-    let file = path.replace(&music_dir, "");
+    let file = uri(path, &music_dir);
     let last_modified = fs::metadata(path)?.modified().unwrap();
     let last_modified = chrono::DateTime::from_timestamp(
         last_modified
@@ -4,11 +4,10 @@ use anyhow::Error;
 use mpd_filters::{Expression, Parser, SqlOptions, ToSql};
 use regex::Regex;
 use rockbox_library::{entity::track::Track, repo};
-use rockbox_rpc::api::rockbox::v1alpha1::{
-    GetAlbumsRequest, GetArtistsRequest, GetGlobalSettingsRequest, GetTracksRequest,
-    ScanLibraryRequest, SearchRequest,
-};
+use rockbox_search::{query::SearchOptions, search_library};
+use rockbox_service::remote::RemoteQuery;
 use rockbox_settings::get_music_dir;
+use rockbox_types::SearchResults;
 use tokio::sync::mpsc::Sender;
 
 use crate::Context;
@@ -54,10 +53,8 @@ pub async fn handle_list_artist(
     _request: &str,
     tx: Sender<String>,
 ) -> Result<String, Error> {
-    let response = ctx.library.get_artists(GetArtistsRequest {}).await?;
-    let response = response.into_inner();
-    let response = response
-        .artists
+    let artists = repo::artist::all(ctx.pool.clone()).await?;
+    let response = artists
         .iter()
         .map(|x| format!("Artist: {}\n", x.name))
         .collect::<String>();
@@ -68,15 +65,30 @@ pub async fn handle_list_artist(
     Ok(response)
 }
 
+pub async fn handle_list_genre(
+    ctx: &mut Context,
+    _request: &str,
+    tx: Sender<String>,
+) -> Result<String, Error> {
+    let genres = repo::genre::all(ctx.pool.clone()).await?;
+    let response = genres
+        .iter()
+        .map(|x| format!("Genre: {}\n", x.name))
+        .collect::<String>();
+    let response = format!("{}OK\n", response);
+    if !ctx.batch {
+        tx.send(response.clone()).await?;
+    }
+    Ok(response)
+}
+
 pub async fn handle_list_title(
     ctx: &mut Context,
     _request: &str,
     tx: Sender<String>,
 ) -> Result<String, Error> {
-    let response = ctx.library.get_tracks(GetTracksRequest {}).await?;
-    let response = response.into_inner();
-    let response = response
-        .tracks
+    let tracks = repo::track::all(ctx.pool.clone()).await?;
+    let response = tracks
         .iter()
         .map(|x| format!("Title: {}\n", x.title))
         .collect::<String>();
@@ -135,10 +147,18 @@ pub async fn handle_search(
         return Ok(response);
     }
 
-    let response = ctx.library.search(SearchRequest { term }).await?;
-    let response = response.into_inner();
+    let mut results: SearchResults =
+        search_library(&ctx.service.indexes, &term, &SearchOptions::default())?.into();
+    // tracks of remote libraries are streamed from their instance
+    let remote = ctx
+        .service
+        .query_remote_sources(RemoteQuery::Search(term))
+        .await;
+    results
+        .tracks
+        .extend(remote.tracks.into_iter().map(|x| x.into()));
 
-    let response = response
+    let response = results
         .tracks
         .iter()
         .map(|x| {
@@ -166,11 +186,7 @@ pub async fn handle_rescan(
     request: &str,
     tx: Sender<String>,
 ) -> Result<String, Error> {
-    let response = ctx
-        .settings
-        .get_global_settings(GetGlobalSettingsRequest {})
-        .await?;
-    let response = response.into_inner();
+    let music_dir = ctx.service.global_settings().await?.music_dir;
     let path = request
         .replace("update ", "")
         .replace("rescan ", "")
@@ -178,11 +194,9 @@ pub async fn handle_rescan(
         .to_string();
     let path = Some(match path.starts_with("/") {
         true => path,
-        false => format!("{}/{}", response.music_dir, path),
+        false => format!("{}/{}", music_dir, path),
     });
-    ctx.library
-        .scan_library(ScanLibraryRequest { path })
-        .await?;
+    ctx.service.scan_library(path).await?;
 
     if !ctx.batch {
         tx.send("OK\n".to_string()).await?;
@@ -252,15 +266,9 @@ pub async fn handle_stats(
     _request: &str,
     tx: Sender<String>,
 ) -> Result<String, Error> {
-    let response = ctx.library.get_albums(GetAlbumsRequest {}).await?;
-    let response = response.into_inner();
-    let albums = response.albums.len();
-    let response = ctx.library.get_artists(GetArtistsRequest {}).await?;
-    let response = response.into_inner();
-    let artists = response.artists.len();
-    let response = ctx.library.get_tracks(GetTracksRequest {}).await?;
-    let response = response.into_inner();
-    let tracks = response.tracks.len();
+    let albums = repo::album::all(ctx.pool.clone()).await?.len();
+    let artists = repo::artist::all(ctx.pool.clone()).await?.len();
+    let tracks = repo::track::all(ctx.pool.clone()).await?.len();
     let response = format!(
         "artists: {}\nalbums: {}\nsongs: {}\nOK\n",
         artists, albums, tracks
@@ -1,8 +1,5 @@
 use anyhow::Error;
-use rockbox_rpc::api::rockbox::v1alpha1::{
-    AdjustVolumeRequest, NextRequest, PauseRequest, PlayRequest, PreviousRequest, ResumeRequest,
-    SaveSettingsRequest, StartRequest,
-};
+use rockbox_sys::types::user_settings::NewGlobalSettings;
 use tokio::sync::mpsc::Sender;
 
 use crate::Context;
@@ -14,7 +11,7 @@ pub async fn handle_play(
     _request: &str,
     tx: Sender<String>,
 ) -> Result<String, Error> {
-    ctx.playback.resume(ResumeRequest {}).await?;
+    ctx.service.resume().await?;
     match ctx.event_sender.send(Subsystem::Player) {
         Ok(_) => {}
         Err(_) => {}
@@ -37,10 +34,10 @@ pub async fn handle_pause(
 
     match status {
         Some(1) => {
-            ctx.playback.pause(PauseRequest {}).await?;
+            ctx.service.pause().await?;
         }
         Some(3) => {
-            ctx.playback.resume(ResumeRequest {}).await?;
+            ctx.service.resume().await?;
         }
         _ => {
             tx.send("ACK [2@0] {pause} no song is playing\n".to_string())
@@ -66,10 +63,10 @@ pub async fn handle_toggle(
 
     match playback_status {
         Some(1) => {
-            ctx.playback.pause(PauseRequest {}).await?;
+            ctx.service.pause().await?;
         }
         Some(3) => {
-            ctx.playback.resume(ResumeRequest {}).await?;
+            ctx.service.resume().await?;
         }
         _ => {
             tx.send("ACK [2@0] {toggle} no song is playing\n".to_string())
@@ -177,7 +174,7 @@ pub async fn handle_next(
     _request: &str,
     tx: Sender<String>,
 ) -> Result<String, Error> {
-    ctx.playback.next(NextRequest {}).await?;
+    ctx.service.next().await?;
     match ctx.event_sender.send(Subsystem::Player) {
         Ok(_) => {}
         Err(_) => {}
@@ -193,7 +190,7 @@ pub async fn handle_previous(
     _request: &str,
     tx: Sender<String>,
 ) -> Result<String, Error> {
-    ctx.playback.previous(PreviousRequest {}).await?;
+    ctx.service.previous().await?;
     match ctx.event_sender.send(Subsystem::Player) {
         Ok(_) => {}
         Err(_) => {}
@@ -232,12 +229,7 @@ pub async fn handle_playid(
 
     let arg = arg.unwrap();
 
-    ctx.playlist
-        .start(StartRequest {
-            start_index: Some(arg - 1),
-            ..Default::default()
-        })
-        .await?;
+    ctx.service.start_playlist(arg - 1, 0, 0).await?;
 
     if !ctx.batch {
         tx.send("OK\n".to_string()).await?;
@@ -288,15 +280,11 @@ pub async fn handle_seekcur(
         return Ok("ACK [2@0] {seekcur} incorrect arguments\n".to_string());
     }
 
-    ctx.playback
-        .play(PlayRequest {
-            elapsed: arg
-                .map(|x| x.trim_matches('"'))
-                .map(|x| x.parse::<i64>().unwrap() * 1000)
-                .unwrap_or_default(),
-            offset: 0,
-        })
-        .await?;
+    let elapsed = arg
+        .map(|x| x.trim_matches('"'))
+        .map(|x| x.parse::<i64>().unwrap() * 1000)
+        .unwrap_or_default();
+    ctx.service.play(elapsed, 0).await?;
 
     match ctx.event_sender.send(Subsystem::Player) {
         Ok(_) => {}
@@ -323,8 +311,8 @@ pub async fn handle_random(
         return Ok("ACK [2@0] {random} incorrect arguments\n".to_string());
     }
 
-    ctx.settings
-        .save_settings(SaveSettingsRequest {
+    ctx.service
+        .save_settings(NewGlobalSettings {
             playlist_shuffle: Some(arg.unwrap() == r#""1""#),
             ..Default::default()
         })
@@ -365,8 +353,8 @@ pub async fn handle_repeat(
             return Ok("ACK [2@0] {repeat} incorrect arguments\n".to_string());
         }
     };
-    ctx.settings
-        .save_settings(SaveSettingsRequest {
+    ctx.service
+        .save_settings(NewGlobalSettings {
             repeat_mode,
             ..Default::default()
         })
@@ -421,9 +409,7 @@ pub async fn handle_setvol(
     // 100 is 0 db
     let new_volume = ((new_volume * 80 / 100) - 80) as i32;
     let steps = new_volume - volume;
-    ctx.sound
-        .adjust_volume(AdjustVolumeRequest { steps })
-        .await?;
+    ctx.service.adjust_volume(steps).await?;
     if !ctx.batch {
         tx.send("OK\n".to_string()).await?;
     }
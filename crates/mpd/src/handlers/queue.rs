@@ -1,12 +1,13 @@
 use std::fs;
 
-use crate::{consts::PLAYLIST_INSERT_LAST, handlers::Subsystem, Context};
+use crate::{
+    consts::PLAYLIST_INSERT_LAST,
+    dir::{is_stream, resolve_path},
+    handlers::Subsystem,
+    Context,
+};
 use anyhow::Error;
 use regex::Regex;
-use rockbox_rpc::api::rockbox::v1alpha1::{
-    GetGlobalSettingsRequest, InsertDirectoryRequest, InsertTracksRequest, RemoveAllTracksRequest,
-    RemoveTracksRequest, ShufflePlaylistRequest, StartRequest,
-};
 use tokio::sync::mpsc::Sender;
 
 pub async fn handle_shuffle(
@@ -14,9 +15,7 @@ pub async fn handle_shuffle(
     _request: &str,
     tx: Sender<String>,
 ) -> Result<String, Error> {
-    ctx.playlist
-        .shuffle_playlist(ShufflePlaylistRequest { start_index: 0 })
-        .await?;
+    ctx.service.shuffle_playlist(0).await?;
     if !ctx.batch {
         tx.send("OK\n".to_string()).await?;
     }
@@ -34,12 +33,7 @@ pub async fn handle_add(
     request: &str,
     tx: Sender<String>,
 ) -> Result<String, Error> {
-    let response = ctx
-        .settings
-        .get_global_settings(GetGlobalSettingsRequest {})
-        .await?;
-    let response = response.into_inner();
-    let music_dir = response.music_dir;
+    let music_dir = ctx.service.global_settings().await?.music_dir;
 
     let request = request.trim();
     let re = Regex::new(r#"^(\w+)\s+"([^"]+)"(?:\s+"?(-?\d+)"?)?$"#).unwrap();
@@ -67,10 +61,12 @@ pub async fn handle_add(
         return Ok("ACK [2@0] {add} missing argument\n".to_string());
     }
 
-    let path = match path.starts_with('/') {
-        true => path,
-        false => format!("{}/{}", music_dir, path),
-    };
+    // a station replaces the queue, its stream is relayed as it plays
+    if is_stream(&path) {
+        return add_stream(ctx, "add", &path, tx).await;
+    }
+
+    let path = resolve_path(&path, &music_dir);
 
     // verify if path is a file or directory or doesn't exist
     if fs::metadata(&path).is_err() {
@@ -82,29 +78,21 @@ pub async fn handle_add(
     }
 
     if fs::metadata(&path)?.is_file() {
-        ctx.playlist
-            .insert_tracks(InsertTracksRequest {
-                tracks: vec![path.clone()],
-                position,
-                ..Default::default()
-            })
+        ctx.service
+            .insert_tracks(vec![path.clone()], position, None)
             .await?;
     }
 
     if fs::metadata(&path)?.is_dir() {
-        ctx.playlist
-            .insert_directory(InsertDirectoryRequest {
-                directory: path,
-                position,
-                ..Default::default()
-            })
+        ctx.service
+            .insert_tracks(vec![], position, Some(path))
             .await?;
     }
 
     let current_track = ctx.current_track.lock().await;
 
     if current_track.is_none() {
-        ctx.playlist.start(StartRequest::default()).await?;
+        ctx.service.start_playlist(0, 0, 0).await?;
     }
 
     if !ctx.batch {
@@ -124,12 +112,7 @@ pub async fn handle_addid(
     request: &str,
     tx: Sender<String>,
 ) -> Result<String, Error> {
-    let response = ctx
-        .settings
-        .get_global_settings(GetGlobalSettingsRequest {})
-        .await?;
-    let response = response.into_inner();
-    let music_dir = response.music_dir;
+    let music_dir = ctx.service.global_settings().await?.music_dir;
 
     let request = request.trim();
     let re = Regex::new(r#"^(\w+)\s+"([^"]+)"(?:\s+"?(-?\d+)"?)?$"#).unwrap();
@@ -160,10 +143,12 @@ pub async fn handle_addid(
         return Ok("ACK [2@0] {add} missing argument\n".to_string());
     }
 
-    let path = match path.starts_with('/') {
-        true => path,
-        false => format!("{}/{}", music_dir, path),
-    };
+    // a station replaces the queue, its stream is relayed as it plays
+    if is_stream(&path) {
+        return add_stream(ctx, "addid", &path, tx).await;
+    }
+
+    let path = resolve_path(&path, &music_dir);
 
     // verify if path is a file or directory or doesn't exist
     if fs::metadata(&path).is_err() {
@@ -175,12 +160,8 @@ pub async fn handle_addid(
     }
 
     if fs::metadata(&path)?.is_file() {
-        ctx.playlist
-            .insert_tracks(InsertTracksRequest {
-                tracks: vec![path.clone()],
-                position,
-                ..Default::default()
-            })
+        ctx.service
+            .insert_tracks(vec![path.clone()], position, None)
             .await?;
     }
 
@@ -195,7 +176,7 @@ pub async fn handle_addid(
 
     let current_track = ctx.current_track.lock().await;
     if current_track.is_none() {
-        ctx.playlist.start(StartRequest::default()).await?;
+        ctx.service.start_playlist(0, 0, 0).await?;
     }
 
     if !ctx.batch {
@@ -210,6 +191,25 @@ pub async fn handle_addid(
     Ok("OK\n".to_string())
 }
 
+async fn add_stream(
+    ctx: &mut Context,
+    command: &str,
+    url: &str,
+    tx: Sender<String>,
+) -> Result<String, Error> {
+    let response = match ctx.service.play_stream(url).await {
+        Ok(_) => "OK\n".to_string(),
+        Err(e) => format!("ACK [50@0] {{{}}} {}\n", command, e),
+    };
+    if !ctx.batch {
+        tx.send(response.clone()).await?;
+    }
+
+    let _ = ctx.event_sender.send(Subsystem::Playlist);
+
+    Ok(response)
+}
+
 pub async fn handle_playlistinfo(
     ctx: &mut Context,
     _request: &str,
@@ -283,9 +283,7 @@ pub async fn handle_deleteid(
             return Ok("ACK [2@0] {deleteid} invalid argument\n".to_string());
         }
     };
-    ctx.playlist
-        .remove_tracks(RemoveTracksRequest { positions })
-        .await?;
+    ctx.service.remove_tracks(&positions).await?;
     if !ctx.batch {
         tx.send("OK\n".to_string()).await?;
     }
@@ -317,9 +315,7 @@ pub async fn handle_delete(
         // get the range
         let range: Vec<i32> = arg.split(':').map(|x| x.parse::<i32>().unwrap()).collect();
         let positions: Vec<i32> = (range[0]..=range[1]).collect();
-        ctx.playlist
-            .remove_tracks(RemoveTracksRequest { positions })
-            .await?;
+        ctx.service.remove_tracks(&positions).await?;
         if !ctx.batch {
             tx.send("OK\n".to_string()).await?;
         }
@@ -335,9 +331,7 @@ pub async fn handle_delete(
             return Ok("ACK [2@0] {delete} invalid argument\n".to_string());
         }
     };
-    ctx.playlist
-        .remove_tracks(RemoveTracksRequest { positions })
-        .await?;
+    ctx.service.remove_tracks(&positions).await?;
     if !ctx.batch {
         tx.send("OK\n".to_string()).await?;
     }
@@ -355,9 +349,7 @@ pub async fn handle_clear(
     _request: &str,
     tx: Sender<String>,
 ) -> Result<String, Error> {
-    ctx.playlist
-        .remove_all_tracks(RemoveAllTracksRequest {})
-        .await?;
+    ctx.service.remove_tracks(&[]).await?;
     if !ctx.batch {
         tx.send("OK\n".to_string()).await?;
     }
@@ -4,8 +4,8 @@ use handlers::{
     browse::{handle_listall, handle_listallinfo, handle_listfiles, handle_lsinfo},
     library::{
         handle_config, handle_find, handle_find_album, handle_find_artist, handle_find_title,
-        handle_list_album, handle_list_artist, handle_list_title, handle_rescan, handle_search,
-        handle_stats, handle_tagtypes, handle_tagtypes_enable,
+        handle_list_album, handle_list_artist, handle_list_genre, handle_list_title, handle_rescan,
+        handle_search, handle_stats, handle_tagtypes, handle_tagtypes_enable,
     },
     playback::{
         handle_currentsong, handle_getvol, handle_next, handle_outputs, handle_pause, handle_play,
@@ -24,13 +24,8 @@ use rockbox_graphql::{
     schema::objects::{audio_status::AudioStatus, playlist::Playlist, track::Track},
     simplebroker::SimpleBroker,
 };
-use rockbox_library::{create_connection_pool, entity, repo};
-use rockbox_rpc::api::rockbox::v1alpha1::{
-    library_service_client::LibraryServiceClient, playback_service_client::PlaybackServiceClient,
-    playlist_service_client::PlaylistServiceClient, settings_service_client::SettingsServiceClient,
-    sound_service_client::SoundServiceClient, system_service_client::SystemServiceClient,
-    GetCurrentRequest, GetGlobalStatusRequest, PlaylistResumeRequest,
-};
+use rockbox_library::{entity, repo};
+use rockbox_service::RockboxService;
 use rockbox_sys::{playback::current_track, types::user_settings::UserSettings};
 use sqlx::{Pool, Sqlite};
 use std::{env, sync::Arc, thread, time::Duration};
@@ -40,7 +35,6 @@ use tokio::{
     sync::{broadcast, Mutex},
 };
 use tokio_stream::StreamExt;
-use tonic::transport::Channel;
 
 pub mod consts;
 pub mod dir;
@@ -49,12 +43,7 @@ pub mod kv;
 
 #[derive(Clone)]
 pub struct Context {
-    pub library: LibraryServiceClient<Channel>,
-    pub playback: PlaybackServiceClient<Channel>,
-    pub settings: SettingsServiceClient<Channel>,
-    pub sound: SoundServiceClient<Channel>,
-    pub playlist: PlaylistServiceClient<Channel>,
-    pub system: SystemServiceClient<Channel>,
+    pub service: RockboxService,
     pub single: Arc<Mutex<String>>,
     pub batch: bool,
     pub event_sender: broadcast::Sender<Subsystem>,
@@ -151,6 +140,7 @@ pub async fn handle_client(mut ctx: Context, stream: TcpStream) -> Result<(), Er
             "list album" => handle_list_album(&mut ctx, &request, tx.clone()).await?,
             "list albumartist" => handle_list_artist(&mut ctx, &request, tx.clone()).await?,
             "list artist" => handle_list_artist(&mut ctx, &request, tx.clone()).await?,
+            "list genre" => handle_list_genre(&mut ctx, &request, tx.clone()).await?,
             "list title" => handle_list_title(&mut ctx, &request, tx.clone()).await?,
             "update" => handle_rescan(&mut ctx, &request, tx.clone()).await?,
             "search" => handle_search(&mut ctx, &request, tx.clone()).await?,
@@ -221,29 +211,14 @@ fn parse_command(request: &str) -> Result<String, Error> {
 }
 
 pub async fn setup_context(batch: bool, ctx: Option<Context>) -> Result<Context, Error> {
-    let port = env::var("ROCKBOX_PORT").unwrap_or_else(|_| "6061".to_string());
-    let host = env::var("ROCKBOX_HOST").unwrap_or_else(|_| "localhost".to_string());
-    let url = format!("tcp://{}:{}", host, port);
-
-    let pool = create_connection_pool().await?;
+    let service = rockbox_service::instance().await?;
+    let pool = service.pool.clone();
     let kv = Arc::new(Mutex::new(build_tracks_kv(pool.clone()).await?));
 
-    let library = LibraryServiceClient::connect(url.clone()).await?;
-    let playback = PlaybackServiceClient::connect(url.clone()).await?;
-    let settings = SettingsServiceClient::connect(url.clone()).await?;
-    let sound = SoundServiceClient::connect(url.clone()).await?;
-    let playlist = PlaylistServiceClient::connect(url.clone()).await?;
-    let system = SystemServiceClient::connect(url.clone()).await?;
-
     let (event_sender, event_receiver) = broadcast::channel(16);
 
     Ok(Context {
-        library,
-        playback,
-        settings,
-        sound,
-        playlist,
-        system,
+        service,
         single: Arc::new(Mutex::new("\"0\"".to_string())),
         batch,
         event_sender: match ctx {
@@ -348,54 +323,35 @@ pub fn listen_events(ctx: Context) {
 pub fn restore_playlist(ctx: Context) -> Result<(), Error> {
     let ctx_clone = ctx.clone();
     thread::spawn(move || {
-        let mut ctx = ctx_clone.clone();
+        let ctx = ctx_clone.clone();
         let rt = tokio::runtime::Runtime::new().unwrap();
         rt.block_on(async {
-            let response = ctx
-                .system
-                .get_global_status(GetGlobalStatusRequest {})
-                .await?;
-            let response = response.into_inner();
+            let status = ctx.service.global_status().await?;
 
             let playback_status = ctx.playback_status.lock().await;
-            let mut status = 0;
+            let mut playback = 0;
 
             if playback_status.is_some() {
-                status = playback_status.as_ref().unwrap().status;
+                playback = playback_status.as_ref().unwrap().status;
             }
 
-            if response.resume_index > -1 && status != 1 {
-                ctx.playlist
-                    .playlist_resume(PlaylistResumeRequest {})
-                    .await?;
-                let resume_index = response.resume_index;
-                let resume_elapsed = response.resume_elapsed;
+            if status.resume_index > -1 && playback != 1 {
+                ctx.service.resume_playlist().await?;
+                let resume_index = status.resume_index;
+                let resume_elapsed = status.resume_elapsed;
                 thread::sleep(std::time::Duration::from_millis(500));
-                let response = ctx.playlist.get_current(GetCurrentRequest {}).await?;
-                let response = response.into_inner();
+                let playlist = ctx.service.current_playlist().await?;
                 let mut current_track = ctx.current_track.lock().await;
-                let path = response.tracks[resume_index as usize].path.clone();
-                
-                let mut track: Track = Track {
-                    path: path.clone(),
-                    artist: response.tracks[resume_index as usize].artist.clone(),
-                    album: response.tracks[resume_index as usize].album.clone(),
-                    title: response.tracks[resume_index as usize].title.clone(),
-                    album_artist: response.tracks[resume_index as usize].album_artist.clone(),
-                    elapsed: resume_elapsed as u64,
-                    length: response.tracks[resume_index as usize].length,
-                    tracknum: response.tracks[resume_index as usize].tracknum,
-                    year: response.tracks[resume_index as usize].year,
-                    year_string: response.tracks[resume_index as usize].year_string.clone(),
-                    ..Default::default()
-                };
+                let entry = playlist.entries[resume_index as usize].clone();
+                let path = entry.path.clone();
+
+                let mut track: Track = entry.into();
+                track.elapsed = resume_elapsed as u64;
 
                 *current_track = Some(track.clone());
                 let hash = format!("{:x}", md5::compute(path.as_bytes()));
-                let pool = rockbox_library::create_connection_pool().await?;
-
                 if let Ok(Some(metadata)) =
-                    repo::track::find_by_md5(pool.clone(), &hash).await
+                    repo::track::find_by_md5(ctx.pool.clone(), &hash).await
                 {
                     track.id = Some(metadata.id);
                     track.album_art = metadata.album_art;
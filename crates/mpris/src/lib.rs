@@ -1,27 +1,39 @@
-use std::{env, future, sync::Arc};
+use std::{
+    env, future,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
 
 use anyhow::Error;
 use async_std::stream::StreamExt;
-use mpris_server::{LoopStatus, Metadata, PlaybackStatus, Player, Time, TrackId};
+use mpris_server::{Property, Server, Signal, Time, TrackListSignal};
+use player::{
+    loop_status, playback_status, rate, rate_range, track_metadata, volume, RockboxPlayer, State,
+    TracklistChange,
+};
 use rockbox_graphql::{
-    schema::objects::{audio_status::AudioStatus, track::Track},
+    schema::objects::{
+        audio_status::AudioStatus, playback_settings::PlaybackSettings, playlist::Playlist,
+        track::Track,
+    },
     simplebroker::SimpleBroker,
 };
+use rockbox_library::create_connection_pool;
 use rockbox_rpc::api::rockbox::v1alpha1::{
-    playback_service_client::PlaybackServiceClient, settings_service_client::SettingsServiceClient,
-    sound_service_client::SoundServiceClient, AdjustVolumeRequest, GetGlobalSettingsRequest,
-    HardStopRequest, NextRequest, PauseRequest, PlayRequest, PlayTrackRequest, PreviousRequest,
-    ResumeRequest, SaveSettingsRequest,
-    PlayOrPauseRequest,
+    playback_service_client::PlaybackServiceClient, playlist_service_client::PlaylistServiceClient,
+    settings_service_client::SettingsServiceClient, sound_service_client::SoundServiceClient,
 };
-use tokio::sync::Mutex;
 
-pub mod macros;
+pub mod player;
 
 const PLAYER_NAME: &str = "rockbox";
 
+/// The broker republishes the current track about every second, only a gap
+/// larger than this between the reported and the expected position is a seek.
+const SEEK_THRESHOLD_MS: u64 = 1500;
+
 pub struct MprisServer {
-    player: Arc<Player>,
+    server: Arc<Server<RockboxPlayer>>,
 }
 
 impl MprisServer {
@@ -31,132 +43,442 @@ impl MprisServer {
         let url = format!("tcp://{}:{}", host, port);
 
         let rt = tokio::runtime::Runtime::new()?;
-        let client = Arc::new(Mutex::new(
-            rt.block_on(PlaybackServiceClient::connect(url.clone()))?,
-        ));
-        let settings_service_client = Arc::new(Mutex::new(
-            rt.block_on(SettingsServiceClient::connect(url.clone()))?,
-        ));
-        let sound_service_client = Arc::new(Mutex::new(
-            rt.block_on(SoundServiceClient::connect(url.clone()))?,
-        ));
-
-        let player = Player::builder(PLAYER_NAME)
-            .can_play(true)
-            .can_pause(true)
-            .can_seek(true)
-            .can_go_next(true)
-            .can_go_previous(true)
-            .can_control(true)
-            .build()
-            .await?;
-
-       player.set_identity("Rockbox").await?; 
+        let playback = rt.block_on(PlaybackServiceClient::connect(url.clone()))?;
+        let playlist = rt.block_on(PlaylistServiceClient::connect(url.clone()))?;
+        let settings = rt.block_on(SettingsServiceClient::connect(url.clone()))?;
+        let sound = rt.block_on(SoundServiceClient::connect(url.clone()))?;
+        let pool = rt.block_on(create_connection_pool())?;
 
-        connect_player_action!(
-            player,
-            client,
-            connect_previous,
-            previous,
-            PreviousRequest {}
+        let player = RockboxPlayer::new(
+            playback,
+            playlist,
+            settings,
+            sound,
+            pool,
+            rt.handle().clone(),
         );
-        connect_player_action!(player, client, connect_next, next, NextRequest {});
-        connect_player_action!(player, client, connect_play, resume, ResumeRequest {});
-        connect_player_action!(player, client, connect_pause, pause, PauseRequest {});
-        connect_player_action!(player, client, connect_play_pause, play_or_pause, PlayOrPauseRequest {});
-        connect_player_seek_action!(player, client);
-        connect_player_set_position_action!(player, client);
-        connect_player_action!(player, client, connect_stop, hard_stop, HardStopRequest {});
-        connect_player_volume_action!(player, sound_service_client, settings_service_client);
-        connect_player_shuffle_action!(player, settings_service_client);
-        connect_player_loop_status_action!(player, settings_service_client);
-        connect_player_open_uri_action!(player, client);
+        let state = player.state.clone();
 
         let server = MprisServer {
-            player: Arc::new(player),
+            server: Arc::new(Server::new_with_all(PLAYER_NAME, player).await?),
         };
 
-        async_std::task::spawn_local(server.player.run());
-
-        let player_mutex = Arc::new(std::sync::Mutex::new(server.player.clone()));
-        let player_mutex_clone = Arc::clone(&player_mutex);
-
+        let mpris = server.server.clone();
+        let status_state = state.clone();
         async_std::task::spawn_local(async move {
             let mut subscription = SimpleBroker::<AudioStatus>::subscribe();
             while let Some(response) = subscription.next().await {
-                let player = player_mutex_clone.lock().unwrap();
-                match response.status {
-                    1 => match player.set_playback_status(PlaybackStatus::Playing).await {
-                        Ok(_) => {}
-                        Err(e) => {
-                            eprintln!("Error: {}", e);
-                        }
-                    },
-                    3 => match player.set_playback_status(PlaybackStatus::Paused).await {
-                        Ok(_) => {}
-                        Err(e) => {
-                            eprintln!("Error: {}", e);
-                        }
-                    },
-                    _ => match player.set_playback_status(PlaybackStatus::Stopped).await {
-                        Ok(_) => {}
-                        Err(e) => {
-                            eprintln!("Error: {}", e);
-                        }
-                    },
+                {
+                    let mut state = status_state.lock().unwrap();
+                    if state.status == response.status {
+                        continue;
+                    }
+                    // re-anchor the position so it stops (or starts) moving with the status
+                    state.position.elapsed_ms = state.position.current(state.status);
+                    state.position.updated_at = Some(Instant::now());
+                    state.status = response.status;
+                }
+                if let Err(e) = mpris
+                    .properties_changed([Property::PlaybackStatus(playback_status(
+                        response.status,
+                    ))])
+                    .await
+                {
+                    eprintln!("Error: {}", e);
                 }
             }
         });
 
+        let mpris = server.server.clone();
+        let track_state = state.clone();
         async_std::task::spawn_local(async move {
-            let port = std::env::var("ROCKBOX_GRAPHQL_PORT").unwrap_or("6062".to_string());
             let mut subscription = SimpleBroker::<Track>::subscribe();
             while let Some(track) = subscription.next().await {
-                let player = player_mutex.lock().unwrap();
-                let mut metadata = Metadata::builder()
-                    .title(track.title)
-                    .artist([track.artist])
-                    .album(track.album)
-                    .album_artist([track.album_artist])
-                    .track_number(track.tracknum)
-                    .disc_number(track.discnum)
-                    .length(Time::from_millis(track.length as i64));
-
-                if let Some(album_art) = track.album_art {
-                    metadata = match album_art.starts_with("http") {
-                        true => metadata.art_url(album_art),
-                        false => metadata
-                            .art_url(format!("http://localhost:{}/covers/{}", port, album_art)),
-                    }
-                }
+                let (metadata, seeked) = {
+                    let mut state = track_state.lock().unwrap();
+                    let track_id = state
+                        .tracklist
+                        .track_id(state.playlist.index.max(0) as usize);
+                    let metadata = track_metadata(&track, track_id);
+                    let changed = state.metadata != metadata;
+                    let expected = match changed {
+                        true => 0,
+                        false => state.position.current(state.status),
+                    };
+                    let seeked = track.elapsed.abs_diff(expected) > SEEK_THRESHOLD_MS;
 
-                if let Some(trackid) = track.id {
-                    metadata = metadata.trackid(
-                        TrackId::try_from(format!("/rockbox/tracks/{}", trackid)).unwrap(),
-                    );
-                }
+                    state.position.elapsed_ms = track.elapsed;
+                    state.position.updated_at = Some(Instant::now());
+                    state.position.rate = rate(&state.settings);
 
-                let metadata = metadata.build();
+                    match changed {
+                        true => {
+                            state.metadata = metadata.clone();
+                            (Some(metadata), seeked)
+                        }
+                        false => (None, seeked),
+                    }
+                };
 
-                match player.set_metadata(metadata).await {
-                    Ok(_) => {}
-                    Err(e) => {
+                if let Some(metadata) = metadata {
+                    if let Err(e) = mpris
+                        .properties_changed([Property::Metadata(metadata)])
+                        .await
+                    {
                         eprintln!("Error: {}", e);
                     }
                 }
 
-                player.set_position(Time::from_millis(track.elapsed as i64));
-                match player.seeked(Time::from_millis(track.elapsed as i64)).await {
-                    Ok(_) => {}
-                    Err(e) => {
+                if seeked {
+                    if let Err(e) = mpris
+                        .emit(Signal::Seeked {
+                            position: Time::from_millis(track.elapsed as i64),
+                        })
+                        .await
+                    {
                         eprintln!("Error: {}", e);
                     }
                 }
             }
         });
 
+        let mpris = server.server.clone();
+        let settings_state = state.clone();
+        async_std::task::spawn_local(async move {
+            let mut subscription = SimpleBroker::<PlaybackSettings>::subscribe();
+            while let Some(settings) = subscription.next().await {
+                let properties = {
+                    let mut state = settings_state.lock().unwrap();
+                    let previous = std::mem::replace(&mut state.settings, settings.clone());
+                    if previous == settings {
+                        continue;
+                    }
+
+                    let mut properties = vec![];
+                    if volume(&previous) != volume(&settings) {
+                        properties.push(Property::Volume(volume(&settings)));
+                    }
+                    if previous.playlist_shuffle != settings.playlist_shuffle {
+                        properties.push(Property::Shuffle(settings.playlist_shuffle));
+                    }
+                    if loop_status(&previous) != loop_status(&settings) {
+                        properties.push(Property::LoopStatus(loop_status(&settings)));
+                    }
+                    if rate(&previous) != rate(&settings) {
+                        state.position.elapsed_ms = state.position.current(state.status);
+                        state.position.updated_at = Some(Instant::now());
+                        state.position.rate = rate(&settings);
+                        properties.push(Property::Rate(rate(&settings)));
+                    }
+                    if rate_range(&previous) != rate_range(&settings) {
+                        let (minimum, maximum) = rate_range(&settings);
+                        properties.push(Property::MinimumRate(minimum));
+                        properties.push(Property::MaximumRate(maximum));
+                    }
+                    properties
+                };
+
+                if properties.is_empty() {
+                    continue;
+                }
+
+                if let Err(e) = mpris.properties_changed(properties).await {
+                    eprintln!("Error: {}", e);
+                }
+            }
+        });
+
+        let mpris = server.server.clone();
+        async_std::task::spawn_local(async move {
+            let mut subscription = SimpleBroker::<Playlist>::subscribe();
+            while let Some(playlist) = subscription.next().await {
+                if let Err(e) = update_tracklist(&mpris, &state, playlist).await {
+                    eprintln!("Error: {}", e);
+                }
+            }
+        });
+
         future::pending::<()>().await;
 
         Ok(server)
     }
 }
+
+/// Stores the new playlist and tells the clients about the entries added and
+/// removed, or that the whole tracklist was replaced.
+pub async fn update_tracklist(
+    mpris: &Server<RockboxPlayer>,
+    state: &Mutex<State>,
+    playlist: Playlist,
+) -> Result<(), Error> {
+    let signals = {
+        let mut state = state.lock().unwrap();
+        let state = &mut *state;
+        let previous = std::mem::replace(&mut state.playlist, playlist);
+        let change = state
+            .tracklist
+            .update(&previous.tracks, &state.playlist.tracks);
+        match change {
+            TracklistChange::Unchanged => vec![],
+            TracklistChange::Replaced => vec![TrackListSignal::TrackListReplaced {
+                tracks: state.tracklist.track_ids(),
+                current_track: state
+                    .tracklist
+                    .track_id(state.playlist.index.max(0) as usize),
+            }],
+            TracklistChange::Edited { removed, added } => {
+                let mut signals: Vec<TrackListSignal> = removed
+                    .into_iter()
+                    .map(|track_id| TrackListSignal::TrackRemoved { track_id })
+                    .collect();
+                for position in added {
+                    signals.push(TrackListSignal::TrackAdded {
+                        metadata: track_metadata(
+                            &state.playlist.tracks[position],
+                            state.tracklist.track_id(position),
+                        ),
+                        after_track: state.tracklist.track_before(position),
+                    });
+                }
+                signals
+            }
+        }
+    };
+
+    for signal in signals {
+        mpris.track_list_emit(signal).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        io::{BufRead, BufReader},
+        process::{Child, Command, Stdio},
+    };
+
+    use mpris_server::zbus::{
+        fdo::PropertiesProxy,
+        names::InterfaceName,
+        zvariant::{OwnedObjectPath, OwnedValue},
+        Connection, MatchRule, Message, MessageStream,
+    };
+    use tonic::transport::Endpoint;
+
+    use super::*;
+
+    const OBJECT_PATH: &str = "/org/mpris/MediaPlayer2";
+    const PLAYER_INTERFACE: &str = "org.mpris.MediaPlayer2.Player";
+    const TRACKLIST_INTERFACE: &str = "org.mpris.MediaPlayer2.TrackList";
+
+    /// The session bus address is read from the environment, the tests take
+    /// turns on their own bus.
+    static BUS: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+    struct Bus(Child);
+
+    impl Bus {
+        fn start() -> Option<Self> {
+            let mut daemon = Command::new("dbus-daemon")
+                .args(["--session", "--nofork", "--print-address"])
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn()
+                .ok()?;
+            let mut address = String::new();
+            BufReader::new(daemon.stdout.as_mut()?)
+                .read_line(&mut address)
+                .ok()?;
+            env::set_var("DBUS_SESSION_BUS_ADDRESS", address.trim());
+            Some(Bus(daemon))
+        }
+    }
+
+    impl Drop for Bus {
+        fn drop(&mut self) {
+            self.0.kill().ok();
+            self.0.wait().ok();
+        }
+    }
+
+    /// A player whose clients are never used by the tested properties.
+    fn player() -> RockboxPlayer {
+        let channel = Endpoint::from_static("http://127.0.0.1:1").connect_lazy();
+        RockboxPlayer::new(
+            PlaybackServiceClient::new(channel.clone()),
+            PlaylistServiceClient::new(channel.clone()),
+            SettingsServiceClient::new(channel.clone()),
+            SoundServiceClient::new(channel),
+            sqlx::SqlitePool::connect_lazy("sqlite::memory:").unwrap(),
+            tokio::runtime::Handle::current(),
+        )
+    }
+
+    fn playlist(paths: &[&str]) -> Playlist {
+        Playlist {
+            tracks: paths
+                .iter()
+                .map(|path| Track {
+                    path: path.to_string(),
+                    title: path.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    async fn property(
+        connection: &Connection,
+        name: &str,
+        interface: &str,
+        property: &str,
+    ) -> OwnedValue {
+        let proxy = PropertiesProxy::builder(connection)
+            .destination(name.to_string())
+            .unwrap()
+            .path(OBJECT_PATH)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+        proxy
+            .get(InterfaceName::try_from(interface).unwrap(), property)
+            .await
+            .unwrap()
+    }
+
+    async fn tracks(connection: &Connection, name: &str) -> Vec<OwnedObjectPath> {
+        property(connection, name, TRACKLIST_INTERFACE, "Tracks")
+            .await
+            .try_into()
+            .unwrap()
+    }
+
+    async fn next_signal(stream: &mut MessageStream) -> Message {
+        let message = tokio::time::timeout(std::time::Duration::from_secs(5), stream.next())
+            .await
+            .expect("no signal was emitted");
+        message.unwrap().unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn tracklist_edits_are_signalled_with_stable_ids() {
+        let _turn = BUS.lock().await;
+        let Some(_bus) = Bus::start() else {
+            eprintln!("dbus-daemon is not available, skipping");
+            return;
+        };
+
+        let player = player();
+        let state = player.state.clone();
+        let name = "org.mpris.MediaPlayer2.rockbox_tracklist_test";
+        let mpris = Server::new_with_all("rockbox_tracklist_test", player)
+            .await
+            .unwrap();
+
+        let connection = Connection::session().await.unwrap();
+        let rule = MatchRule::builder()
+            .msg_type(mpris_server::zbus::message::Type::Signal)
+            .interface(TRACKLIST_INTERFACE)
+            .unwrap()
+            .build();
+        let mut signals = MessageStream::for_match_rule(rule, &connection, None)
+            .await
+            .unwrap();
+
+        update_tracklist(&mpris, &state, playlist(&["a", "b", "c"]))
+            .await
+            .unwrap();
+        let replaced = next_signal(&mut signals).await;
+        assert_eq!(
+            replaced.header().member().unwrap().as_str(),
+            "TrackListReplaced"
+        );
+        let (ids, current): (Vec<OwnedObjectPath>, OwnedObjectPath) =
+            replaced.body().deserialize().unwrap();
+        assert_eq!(ids.len(), 3);
+        assert_eq!(current, ids[0]);
+        assert_eq!(tracks(&connection, name).await, ids);
+
+        update_tracklist(&mpris, &state, playlist(&["a", "x", "b", "c"]))
+            .await
+            .unwrap();
+        let added = next_signal(&mut signals).await;
+        assert_eq!(added.header().member().unwrap().as_str(), "TrackAdded");
+        let (metadata, after): (HashMap<String, OwnedValue>, OwnedObjectPath) =
+            added.body().deserialize().unwrap();
+        assert_eq!(after, ids[0]);
+        let x: OwnedObjectPath = metadata["mpris:trackid"]
+            .try_clone()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert!(!ids.contains(&x));
+        assert_eq!(
+            tracks(&connection, name).await,
+            vec![ids[0].clone(), x.clone(), ids[1].clone(), ids[2].clone()]
+        );
+
+        update_tracklist(&mpris, &state, playlist(&["a", "b", "c"]))
+            .await
+            .unwrap();
+        let removed = next_signal(&mut signals).await;
+        assert_eq!(removed.header().member().unwrap().as_str(), "TrackRemoved");
+        let (track_id,): (OwnedObjectPath,) = removed.body().deserialize().unwrap();
+        assert_eq!(track_id, x);
+        assert_eq!(tracks(&connection, name).await, ids);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn rate_properties_follow_the_sound_settings() {
+        let _turn = BUS.lock().await;
+        let Some(_bus) = Bus::start() else {
+            eprintln!("dbus-daemon is not available, skipping");
+            return;
+        };
+
+        let player = player();
+        let state = player.state.clone();
+        let name = "org.mpris.MediaPlayer2.rockbox_rate_test";
+        let _mpris = Server::new_with_all("rockbox_rate_test", player)
+            .await
+            .unwrap();
+        let connection = Connection::session().await.unwrap();
+
+        let cases = [
+            // pitch, timestretch, available, rate, minimum, maximum
+            (12000, 0, false, 1.2, 0.5, 2.0),
+            (0, 0, true, 1.0, 0.35, 2.5),
+            (10000, 15000, true, 1.5, 0.35, 2.5),
+        ];
+        for (pitch, timestretch, available, rate, minimum, maximum) in cases {
+            {
+                let mut state = state.lock().unwrap();
+                state.settings.pitch = pitch;
+                state.settings.timestretch = timestretch;
+                state.settings.timestretch_available = available;
+            }
+            for (property_name, expected) in [
+                ("Rate", rate),
+                ("MinimumRate", minimum),
+                ("MaximumRate", maximum),
+            ] {
+                let value: f64 = property(&connection, name, PLAYER_INTERFACE, property_name)
+                    .await
+                    .try_into()
+                    .unwrap();
+                assert!(
+                    (value - expected).abs() < 1e-9,
+                    "{} is {} instead of {} for pitch {}",
+                    property_name,
+                    value,
+                    expected,
+                    pitch
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,851 @@
+use std::{
+    env,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use mpris_server::{
+    zbus::{fdo, Result},
+    LoopStatus, Metadata, PlaybackRate, PlaybackStatus, PlayerInterface, Playlist, PlaylistId,
+    PlaylistOrdering, PlaylistsInterface, RootInterface, Time, TrackId, TrackListInterface, Uri,
+    Volume,
+};
+use rockbox_graphql::schema::objects::{
+    playback_settings::PlaybackSettings, playlist::Playlist as CurrentPlaylist, track::Track,
+};
+use rockbox_library::repo;
+use rockbox_rpc::api::rockbox::v1alpha1::{
+    playback_service_client::PlaybackServiceClient, playlist_service_client::PlaylistServiceClient,
+    settings_service_client::SettingsServiceClient, sound_service_client::SoundServiceClient,
+    AdjustVolumeRequest, HardStopRequest, InsertTracksRequest, NextRequest, PauseRequest,
+    PlayLikedTracksRequest, PlayOrPauseRequest, PlayPlaylistRequest, PlayRequest, PlayTrackRequest,
+    PreviousRequest, RemoveTracksRequest, ResumeRequest, SaveSettingsRequest,
+    SetPlaybackSpeedRequest, StartRequest,
+};
+use rockbox_service::speed;
+use sqlx::{Pool, Sqlite};
+use tokio::runtime::Handle;
+use tonic::transport::Channel;
+
+const NO_TRACK: &str = "/org/mpris/MediaPlayer2/TrackList/NoTrack";
+const TRACKLIST_PREFIX: &str = "/org/rockbox/tracklist/";
+const PLAYLIST_PREFIX: &str = "/org/rockbox/playlists/";
+const LIKED_TRACKS_PLAYLIST: &str = "liked";
+
+pub const PLAYING: i32 = 1;
+pub const PAUSED: i32 = 3;
+
+/// Elapsed time reported for a track, and when it was reported, so the
+/// position can be extrapolated the same way MPRIS clients do.
+#[derive(Default)]
+pub struct Position {
+    pub elapsed_ms: u64,
+    pub rate: f64,
+    pub updated_at: Option<Instant>,
+}
+
+impl Position {
+    pub fn current(&self, status: i32) -> u64 {
+        match (status, self.updated_at) {
+            (PLAYING, Some(updated_at)) => {
+                self.elapsed_ms + (updated_at.elapsed().as_millis() as f64 * self.rate) as u64
+            }
+            _ => self.elapsed_ms,
+        }
+    }
+}
+
+/// Last values pushed by the broker, read by the D-Bus property getters.
+#[derive(Default)]
+pub struct State {
+    pub status: i32,
+    pub metadata: Metadata,
+    pub position: Position,
+    pub settings: PlaybackSettings,
+    pub playlist: CurrentPlaylist,
+    pub tracklist: Tracklist,
+    pub active_playlist: Option<Playlist>,
+}
+
+pub struct RockboxPlayer {
+    pub state: Arc<Mutex<State>>,
+    playback: PlaybackServiceClient<Channel>,
+    playlist: PlaylistServiceClient<Channel>,
+    settings: SettingsServiceClient<Channel>,
+    sound: SoundServiceClient<Channel>,
+    pool: Pool<Sqlite>,
+    runtime: Handle,
+}
+
+impl RockboxPlayer {
+    /// `runtime` is the runtime the clients and the pool were created on.
+    pub fn new(
+        playback: PlaybackServiceClient<Channel>,
+        playlist: PlaylistServiceClient<Channel>,
+        settings: SettingsServiceClient<Channel>,
+        sound: SoundServiceClient<Channel>,
+        pool: Pool<Sqlite>,
+        runtime: Handle,
+    ) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State::default())),
+            playback,
+            playlist,
+            settings,
+            sound,
+            pool,
+            runtime,
+        }
+    }
+
+    /// Runs a request on the runtime of the clients. The futures of the D-Bus
+    /// interfaces must be `Sync`, the ones of tonic and sqlx are not, so each
+    /// request is spawned right away with its own clone of the client and
+    /// only its handle is awaited.
+    fn run<T, F>(&self, request: F) -> impl Future<Output = fdo::Result<T>>
+    where
+        T: Send + 'static,
+        F: Future<Output = T> + Send + 'static,
+    {
+        let handle = self.runtime.spawn(request);
+        async move { handle.await.map_err(to_fdo) }
+    }
+
+    async fn saved_playlists(&self) -> fdo::Result<Vec<(Playlist, i64, i64)>> {
+        let mut playlists = vec![(
+            Playlist {
+                id: playlist_id(LIKED_TRACKS_PLAYLIST)?,
+                name: "Liked Tracks".to_string(),
+                icon: "".to_string(),
+            },
+            0,
+            0,
+        )];
+        let saved = self
+            .run(repo::playlist::all(self.pool.clone()))
+            .await?
+            .map_err(to_fdo)?;
+        for playlist in saved {
+            playlists.push((
+                Playlist {
+                    id: playlist_id(&playlist.id)?,
+                    name: playlist.name,
+                    icon: playlist.image.unwrap_or_default(),
+                },
+                playlist.created_at.timestamp(),
+                playlist.updated_at.timestamp(),
+            ));
+        }
+        Ok(playlists)
+    }
+}
+
+pub fn to_fdo<E: std::fmt::Display>(e: E) -> fdo::Error {
+    fdo::Error::Failed(e.to_string())
+}
+
+/// The ids of the entries of the current playlist. MPRIS clients expect an
+/// entry to keep its id while it stays in the tracklist, so ids are numbered
+/// in the order entries appear rather than by position.
+#[derive(Default)]
+pub struct Tracklist {
+    ids: Vec<u64>,
+    next_id: u64,
+}
+
+/// How the tracklist changed with a new playlist.
+#[derive(Debug, PartialEq)]
+pub enum TracklistChange {
+    Unchanged,
+    Replaced,
+    /// The ids removed, then the positions added in the new playlist.
+    Edited {
+        removed: Vec<TrackId>,
+        added: Vec<usize>,
+    },
+}
+
+impl Tracklist {
+    pub fn track_id(&self, position: usize) -> TrackId {
+        match self.ids.get(position) {
+            Some(id) => TrackId::try_from(format!("{}{}", TRACKLIST_PREFIX, id)).unwrap(),
+            None => TrackId::try_from(NO_TRACK).unwrap(),
+        }
+    }
+
+    /// The entry an entry at `position` comes after, `NoTrack` for the first.
+    pub fn track_before(&self, position: usize) -> TrackId {
+        match position.checked_sub(1) {
+            Some(position) => self.track_id(position),
+            None => TrackId::try_from(NO_TRACK).unwrap(),
+        }
+    }
+
+    pub fn track_ids(&self) -> Vec<TrackId> {
+        (0..self.ids.len()).map(|i| self.track_id(i)).collect()
+    }
+
+    pub fn position(&self, track_id: &TrackId) -> Option<usize> {
+        let id: u64 = track_id
+            .as_str()
+            .strip_prefix(TRACKLIST_PREFIX)?
+            .parse()
+            .ok()?;
+        self.ids.iter().position(|i| *i == id)
+    }
+
+    /// Keeps the ids of the entries before and after the part of `previous`
+    /// that changed in `current`, the entries in between get new ids.
+    pub fn update(&mut self, previous: &[Track], current: &[Track]) -> TracklistChange {
+        let prefix = previous
+            .iter()
+            .zip(current)
+            .take_while(|(a, b)| a.path == b.path)
+            .count();
+        if prefix == previous.len() && prefix == current.len() {
+            return TracklistChange::Unchanged;
+        }
+        let suffix = previous[prefix..]
+            .iter()
+            .rev()
+            .zip(current[prefix..].iter().rev())
+            .take_while(|(a, b)| a.path == b.path)
+            .count();
+
+        let added = prefix..current.len() - suffix;
+        let ids: Vec<u64> = added.clone().map(|i| self.next_id + i as u64).collect();
+        self.next_id += current.len() as u64;
+        let removed: Vec<TrackId> = (prefix..previous.len() - suffix)
+            .map(|i| self.track_id(i))
+            .collect();
+        self.ids.splice(prefix..previous.len() - suffix, ids);
+
+        match prefix + suffix {
+            0 => TracklistChange::Replaced,
+            _ => TracklistChange::Edited {
+                removed,
+                added: added.collect(),
+            },
+        }
+    }
+}
+
+fn playlist_id(id: &str) -> fdo::Result<PlaylistId> {
+    PlaylistId::try_from(format!("{}{}", PLAYLIST_PREFIX, id)).map_err(to_fdo)
+}
+
+pub fn track_metadata(track: &Track, track_id: TrackId) -> Metadata {
+    let port = env::var("ROCKBOX_GRAPHQL_PORT").unwrap_or("6062".to_string());
+    let mut metadata = Metadata::builder()
+        .title(track.title.clone())
+        .artist([track.artist.clone()])
+        .album(track.album.clone())
+        .album_artist([track.album_artist.clone()])
+        .track_number(track.tracknum)
+        .disc_number(track.discnum)
+        .length(Time::from_millis(track.length as i64))
+        .trackid(track_id);
+
+    if let Some(album_art) = track.album_art.clone() {
+        metadata = match album_art.starts_with("http") {
+            true => metadata.art_url(album_art),
+            false => metadata.art_url(format!(
+                "http://localhost:{}/covers/{}?size=512",
+                port, album_art
+            )),
+        }
+    }
+
+    metadata.build()
+}
+
+pub fn playback_status(status: i32) -> PlaybackStatus {
+    match status {
+        PLAYING => PlaybackStatus::Playing,
+        PAUSED => PlaybackStatus::Paused,
+        _ => PlaybackStatus::Stopped,
+    }
+}
+
+pub fn volume(settings: &PlaybackSettings) -> Volume {
+    ((settings.volume as f64 + 80.0) / 80.0).clamp(0.0, 1.0)
+}
+
+pub fn loop_status(settings: &PlaybackSettings) -> LoopStatus {
+    match settings.repeat_mode {
+        1 => LoopStatus::Playlist,
+        2 => LoopStatus::Track,
+        _ => LoopStatus::None,
+    }
+}
+
+pub fn rate(settings: &PlaybackSettings) -> PlaybackRate {
+    speed::rate(
+        settings.pitch,
+        settings.timestretch,
+        settings.timestretch_available,
+    )
+}
+
+pub fn rate_range(settings: &PlaybackSettings) -> (PlaybackRate, PlaybackRate) {
+    speed::rate_range(settings.pitch, settings.timestretch_available)
+}
+
+fn decode_uri(uri: &str) -> String {
+    match uri.starts_with("file://") {
+        true => urlencoding::decode(&uri.replace("file://", ""))
+            .unwrap()
+            .to_string(),
+        false => uri.to_string(),
+    }
+}
+
+impl RootInterface for RockboxPlayer {
+    async fn raise(&self) -> fdo::Result<()> {
+        Ok(())
+    }
+
+    async fn quit(&self) -> fdo::Result<()> {
+        Ok(())
+    }
+
+    async fn can_quit(&self) -> fdo::Result<bool> {
+        Ok(false)
+    }
+
+    async fn fullscreen(&self) -> fdo::Result<bool> {
+        Ok(false)
+    }
+
+    async fn set_fullscreen(&self, _fullscreen: bool) -> Result<()> {
+        Ok(())
+    }
+
+    async fn can_set_fullscreen(&self) -> fdo::Result<bool> {
+        Ok(false)
+    }
+
+    async fn can_raise(&self) -> fdo::Result<bool> {
+        Ok(false)
+    }
+
+    async fn has_track_list(&self) -> fdo::Result<bool> {
+        Ok(true)
+    }
+
+    async fn identity(&self) -> fdo::Result<String> {
+        Ok("Rockbox".to_string())
+    }
+
+    async fn desktop_entry(&self) -> fdo::Result<String> {
+        Ok("rockbox".to_string())
+    }
+
+    async fn supported_uri_schemes(&self) -> fdo::Result<Vec<String>> {
+        Ok(vec![
+            "file".to_string(),
+            "http".to_string(),
+            "https".to_string(),
+        ])
+    }
+
+    async fn supported_mime_types(&self) -> fdo::Result<Vec<String>> {
+        Ok(vec![
+            "audio/mpeg".to_string(),
+            "audio/flac".to_string(),
+            "audio/ogg".to_string(),
+            "audio/x-m4a".to_string(),
+            "audio/wav".to_string(),
+        ])
+    }
+}
+
+impl PlayerInterface for RockboxPlayer {
+    async fn next(&self) -> fdo::Result<()> {
+        let mut client = self.playback.clone();
+        self.run(async move { client.next(NextRequest {}).await })
+            .await?
+            .map_err(to_fdo)?;
+        Ok(())
+    }
+
+    async fn previous(&self) -> fdo::Result<()> {
+        let mut client = self.playback.clone();
+        self.run(async move { client.previous(PreviousRequest {}).await })
+            .await?
+            .map_err(to_fdo)?;
+        Ok(())
+    }
+
+    async fn pause(&self) -> fdo::Result<()> {
+        let mut client = self.playback.clone();
+        self.run(async move { client.pause(PauseRequest {}).await })
+            .await?
+            .map_err(to_fdo)?;
+        Ok(())
+    }
+
+    async fn play_pause(&self) -> fdo::Result<()> {
+        let mut client = self.playback.clone();
+        self.run(async move { client.play_or_pause(PlayOrPauseRequest {}).await })
+            .await?
+            .map_err(to_fdo)?;
+        Ok(())
+    }
+
+    async fn stop(&self) -> fdo::Result<()> {
+        let mut client = self.playback.clone();
+        self.run(async move { client.hard_stop(HardStopRequest {}).await })
+            .await?
+            .map_err(to_fdo)?;
+        Ok(())
+    }
+
+    async fn play(&self) -> fdo::Result<()> {
+        let mut client = self.playback.clone();
+        self.run(async move { client.resume(ResumeRequest {}).await })
+            .await?
+            .map_err(to_fdo)?;
+        Ok(())
+    }
+
+    async fn seek(&self, offset: Time) -> fdo::Result<()> {
+        let position = {
+            let state = self.state.lock().unwrap();
+            state.position.current(state.status) as i64
+        };
+        let elapsed = (position + offset.as_millis()).max(0);
+        let mut client = self.playback.clone();
+        self.run(async move { client.play(PlayRequest { elapsed, offset: 0 }).await })
+            .await?
+            .map_err(to_fdo)?;
+        Ok(())
+    }
+
+    async fn set_position(&self, _track_id: TrackId, position: Time) -> fdo::Result<()> {
+        let elapsed = position.as_millis();
+        let mut client = self.playback.clone();
+        self.run(async move { client.play(PlayRequest { elapsed, offset: 0 }).await })
+            .await?
+            .map_err(to_fdo)?;
+        Ok(())
+    }
+
+    async fn open_uri(&self, uri: String) -> fdo::Result<()> {
+        let path = decode_uri(&uri);
+        let mut client = self.playback.clone();
+        self.run(async move { client.play_track(PlayTrackRequest { path }).await })
+            .await?
+            .map_err(to_fdo)?;
+        Ok(())
+    }
+
+    async fn playback_status(&self) -> fdo::Result<PlaybackStatus> {
+        let status = self.state.lock().unwrap().status;
+        Ok(playback_status(status))
+    }
+
+    async fn loop_status(&self) -> fdo::Result<LoopStatus> {
+        Ok(loop_status(&self.state.lock().unwrap().settings))
+    }
+
+    async fn set_loop_status(&self, loop_status: LoopStatus) -> Result<()> {
+        let repeat_mode = match loop_status {
+            LoopStatus::None => Some(0),
+            LoopStatus::Playlist => Some(1),
+            LoopStatus::Track => Some(2),
+        };
+        let mut client = self.settings.clone();
+        let request = SaveSettingsRequest {
+            repeat_mode,
+            ..Default::default()
+        };
+        if let Err(e) = self
+            .run(async move { client.save_settings(request).await })
+            .await?
+        {
+            eprintln!("Error: {}", e);
+        }
+        Ok(())
+    }
+
+    async fn rate(&self) -> fdo::Result<PlaybackRate> {
+        Ok(rate(&self.state.lock().unwrap().settings))
+    }
+
+    async fn set_rate(&self, rate: PlaybackRate) -> Result<()> {
+        // A rate of 0.0 is a request to pause, see the MPRIS specification
+        if rate <= 0.0 {
+            let mut client = self.playback.clone();
+            if let Err(e) = self
+                .run(async move { client.pause(PauseRequest {}).await })
+                .await?
+            {
+                eprintln!("Error: {}", e);
+            }
+            return Ok(());
+        }
+
+        let (minimum, maximum) = rate_range(&self.state.lock().unwrap().settings);
+        let speed = speed::speed(rate.clamp(minimum, maximum));
+        let mut client = self.sound.clone();
+        if let Err(e) = self
+            .run(async move {
+                client
+                    .set_playback_speed(SetPlaybackSpeedRequest { speed })
+                    .await
+            })
+            .await?
+        {
+            eprintln!("Error: {}", e);
+        }
+        Ok(())
+    }
+
+    async fn shuffle(&self) -> fdo::Result<bool> {
+        Ok(self.state.lock().unwrap().settings.playlist_shuffle)
+    }
+
+    async fn set_shuffle(&self, shuffle: bool) -> Result<()> {
+        let mut client = self.settings.clone();
+        let request = SaveSettingsRequest {
+            playlist_shuffle: Some(shuffle),
+            ..Default::default()
+        };
+        if let Err(e) = self
+            .run(async move { client.save_settings(request).await })
+            .await?
+        {
+            eprintln!("Error: {}", e);
+        }
+        Ok(())
+    }
+
+    async fn metadata(&self) -> fdo::Result<Metadata> {
+        Ok(self.state.lock().unwrap().metadata.clone())
+    }
+
+    async fn volume(&self) -> fdo::Result<Volume> {
+        Ok(volume(&self.state.lock().unwrap().settings))
+    }
+
+    async fn set_volume(&self, volume: Volume) -> Result<()> {
+        let current = self.state.lock().unwrap().settings.volume;
+        // volume is a float between 0.0 and 1.0, rockbox expects -80 db to 0 db
+        let new_volume = (-80.0 + 80.0 * volume.clamp(0.0, 1.0)) as i32;
+        let steps = new_volume - current;
+        let mut client = self.sound.clone();
+        if let Err(e) = self
+            .run(async move { client.adjust_volume(AdjustVolumeRequest { steps }).await })
+            .await?
+        {
+            eprintln!("Error: {}", e);
+        }
+        Ok(())
+    }
+
+    async fn position(&self) -> fdo::Result<Time> {
+        let state = self.state.lock().unwrap();
+        Ok(Time::from_millis(
+            state.position.current(state.status) as i64
+        ))
+    }
+
+    async fn minimum_rate(&self) -> fdo::Result<PlaybackRate> {
+        Ok(rate_range(&self.state.lock().unwrap().settings).0)
+    }
+
+    async fn maximum_rate(&self) -> fdo::Result<PlaybackRate> {
+        Ok(rate_range(&self.state.lock().unwrap().settings).1)
+    }
+
+    async fn can_go_next(&self) -> fdo::Result<bool> {
+        Ok(true)
+    }
+
+    async fn can_go_previous(&self) -> fdo::Result<bool> {
+        Ok(true)
+    }
+
+    async fn can_play(&self) -> fdo::Result<bool> {
+        Ok(true)
+    }
+
+    async fn can_pause(&self) -> fdo::Result<bool> {
+        Ok(true)
+    }
+
+    async fn can_seek(&self) -> fdo::Result<bool> {
+        Ok(true)
+    }
+
+    async fn can_control(&self) -> fdo::Result<bool> {
+        Ok(true)
+    }
+}
+
+impl TrackListInterface for RockboxPlayer {
+    async fn get_tracks_metadata(&self, track_ids: Vec<TrackId>) -> fdo::Result<Vec<Metadata>> {
+        let state = self.state.lock().unwrap();
+        Ok(track_ids
+            .into_iter()
+            .filter_map(|track_id| {
+                let position = state.tracklist.position(&track_id)?;
+                let track = state.playlist.tracks.get(position)?;
+                Some(track_metadata(track, track_id))
+            })
+            .collect())
+    }
+
+    async fn add_track(
+        &self,
+        uri: Uri,
+        after_track: TrackId,
+        set_as_current: bool,
+    ) -> fdo::Result<()> {
+        let position = match after_track.as_str() {
+            NO_TRACK => 0,
+            _ => self
+                .state
+                .lock()
+                .unwrap()
+                .tracklist
+                .position(&after_track)
+                .map(|position| position as i32 + 1)
+                .ok_or_else(|| fdo::Error::InvalidArgs("Unknown track".to_string()))?,
+        };
+
+        let tracks = vec![decode_uri(&uri)];
+        let mut client = self.playlist.clone();
+        self.run(async move {
+            client
+                .insert_tracks(InsertTracksRequest {
+                    playlist_id: None,
+                    position,
+                    tracks,
+                    shuffle: None,
+                })
+                .await?;
+            if set_as_current {
+                client
+                    .start(StartRequest {
+                        start_index: Some(position),
+                        elapsed: None,
+                        offset: None,
+                    })
+                    .await?;
+            }
+            Ok::<_, tonic::Status>(())
+        })
+        .await?
+        .map_err(to_fdo)?;
+        Ok(())
+    }
+
+    async fn remove_track(&self, track_id: TrackId) -> fdo::Result<()> {
+        let position = self
+            .state
+            .lock()
+            .unwrap()
+            .tracklist
+            .position(&track_id)
+            .ok_or_else(|| fdo::Error::InvalidArgs("Unknown track".to_string()))?;
+        let positions = vec![position as i32];
+        let mut client = self.playlist.clone();
+        self.run(async move {
+            client
+                .remove_tracks(RemoveTracksRequest { positions })
+                .await
+        })
+        .await?
+        .map_err(to_fdo)?;
+        Ok(())
+    }
+
+    async fn go_to(&self, track_id: TrackId) -> fdo::Result<()> {
+        let position = self
+            .state
+            .lock()
+            .unwrap()
+            .tracklist
+            .position(&track_id)
+            .ok_or_else(|| fdo::Error::InvalidArgs("Unknown track".to_string()))?;
+        let request = StartRequest {
+            start_index: Some(position as i32),
+            elapsed: None,
+            offset: None,
+        };
+        let mut client = self.playlist.clone();
+        self.run(async move { client.start(request).await })
+            .await?
+            .map_err(to_fdo)?;
+        Ok(())
+    }
+
+    async fn tracks(&self) -> fdo::Result<Vec<TrackId>> {
+        let state = self.state.lock().unwrap();
+        Ok(state.tracklist.track_ids())
+    }
+
+    async fn can_edit_tracks(&self) -> fdo::Result<bool> {
+        Ok(true)
+    }
+}
+
+impl PlaylistsInterface for RockboxPlayer {
+    async fn activate_playlist(&self, playlist_id: PlaylistId) -> fdo::Result<()> {
+        let id = playlist_id
+            .as_str()
+            .strip_prefix(PLAYLIST_PREFIX)
+            .ok_or_else(|| fdo::Error::InvalidArgs("Unknown playlist".to_string()))?
+            .to_string();
+
+        let mut client = self.playback.clone();
+        self.run(async move {
+            match id.as_str() {
+                LIKED_TRACKS_PLAYLIST => {
+                    client
+                        .play_liked_tracks(PlayLikedTracksRequest {
+                            shuffle: None,
+                            position: None,
+                        })
+                        .await?;
+                }
+                _ => {
+                    client
+                        .play_playlist(PlayPlaylistRequest {
+                            playlist_id: id,
+                            shuffle: None,
+                        })
+                        .await?;
+                }
+            }
+            Ok::<_, tonic::Status>(())
+        })
+        .await?
+        .map_err(to_fdo)?;
+
+        let active = self
+            .saved_playlists()
+            .await?
+            .into_iter()
+            .map(|(playlist, _, _)| playlist)
+            .find(|playlist| playlist.id == playlist_id);
+        self.state.lock().unwrap().active_playlist = active;
+        Ok(())
+    }
+
+    async fn get_playlists(
+        &self,
+        index: u32,
+        max_count: u32,
+        order: PlaylistOrdering,
+        reverse_order: bool,
+    ) -> fdo::Result<Vec<Playlist>> {
+        let mut playlists = self.saved_playlists().await?;
+        match order {
+            PlaylistOrdering::Alphabetical => playlists.sort_by_key(|p| p.0.name.to_lowercase()),
+            PlaylistOrdering::CreationDate => playlists.sort_by_key(|p| p.1),
+            PlaylistOrdering::ModifiedDate => playlists.sort_by_key(|p| p.2),
+            _ => {}
+        }
+        if reverse_order {
+            playlists.reverse();
+        }
+        Ok(playlists
+            .into_iter()
+            .map(|(playlist, _, _)| playlist)
+            .skip(index as usize)
+            .take(max_count as usize)
+            .collect())
+    }
+
+    async fn playlist_count(&self) -> fdo::Result<u32> {
+        Ok(self.saved_playlists().await?.len() as u32)
+    }
+
+    async fn orderings(&self) -> fdo::Result<Vec<PlaylistOrdering>> {
+        Ok(vec![
+            PlaylistOrdering::Alphabetical,
+            PlaylistOrdering::CreationDate,
+            PlaylistOrdering::ModifiedDate,
+            PlaylistOrdering::UserDefined,
+        ])
+    }
+
+    async fn active_playlist(&self) -> fdo::Result<Option<Playlist>> {
+        Ok(self.state.lock().unwrap().active_playlist.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracks(paths: &[&str]) -> Vec<Track> {
+        paths
+            .iter()
+            .map(|path| Track {
+                path: path.to_string(),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn a_new_playlist_replaces_the_tracklist() {
+        let mut tracklist = Tracklist::default();
+        let change = tracklist.update(&[], &tracks(&["a", "b"]));
+        assert_eq!(change, TracklistChange::Replaced);
+        assert_eq!(tracklist.track_ids().len(), 2);
+
+        let change = tracklist.update(&tracks(&["a", "b"]), &tracks(&["c"]));
+        assert_eq!(change, TracklistChange::Replaced);
+        assert_eq!(tracklist.track_ids().len(), 1);
+    }
+
+    #[test]
+    fn entries_keep_their_ids_across_edits() {
+        let mut tracklist = Tracklist::default();
+        tracklist.update(&[], &tracks(&["a", "b", "c"]));
+        let ids = tracklist.track_ids();
+
+        let change = tracklist.update(&tracks(&["a", "b", "c"]), &tracks(&["a", "x", "b", "c"]));
+        assert_eq!(
+            change,
+            TracklistChange::Edited {
+                removed: vec![],
+                added: vec![1],
+            }
+        );
+        assert_eq!(tracklist.track_id(0), ids[0]);
+        assert_eq!(tracklist.track_id(2), ids[1]);
+        assert_eq!(tracklist.track_id(3), ids[2]);
+        assert!(!ids.contains(&tracklist.track_id(1)));
+        assert_eq!(tracklist.track_before(1), ids[0]);
+
+        let x = tracklist.track_id(1);
+        let change = tracklist.update(&tracks(&["a", "x", "b", "c"]), &tracks(&["a", "b", "c"]));
+        assert_eq!(
+            change,
+            TracklistChange::Edited {
+                removed: vec![x.clone()],
+                added: vec![],
+            }
+        );
+        assert_eq!(tracklist.track_ids(), ids);
+        assert_eq!(tracklist.position(&x), None);
+        assert_eq!(tracklist.position(&ids[2]), Some(2));
+    }
+
+    #[test]
+    fn an_unchanged_playlist_keeps_the_tracklist() {
+        let mut tracklist = Tracklist::default();
+        tracklist.update(&[], &tracks(&["a", "b"]));
+        let ids = tracklist.track_ids();
+        let change = tracklist.update(&tracks(&["a", "b"]), &tracks(&["a", "b"]));
+        assert_eq!(change, TracklistChange::Unchanged);
+        assert_eq!(tracklist.track_ids(), ids);
+    }
+}
@@ -0,0 +1,339 @@
+use std::borrow::Cow;
+
+use anyhow::{anyhow, Error};
+use chrono::{DateTime, Utc};
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader,
+};
+
+/// A podcast feed, RSS 2.0 with the iTunes extensions or Atom.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Feed {
+    pub title: String,
+    pub description: Option<String>,
+    pub author: Option<String>,
+    pub image: Option<String>,
+    pub link: Option<String>,
+    /// In the order of the feed, usually the newest first.
+    pub episodes: Vec<Episode>,
+}
+
+/// An item of a feed with an audio enclosure. `duration` is in seconds.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Episode {
+    pub guid: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub url: String,
+    pub mime_type: Option<String>,
+    pub duration: Option<u32>,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+pub async fn fetch(url: &str) -> Result<Feed, Error> {
+    let client = reqwest::Client::new();
+    let response = client.get(url).send().await?.error_for_status()?;
+    parse(&response.text().await?)
+}
+
+/// Parses an RSS or Atom feed, items without enclosure are skipped.
+pub fn parse(xml: &str) -> Result<Feed, Error> {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+
+    let mut feed = Feed::default();
+    let mut episode: Option<Episode> = None;
+    let mut path: Vec<String> = vec![];
+    let mut text = String::new();
+    let mut root = None;
+    loop {
+        match reader.read_event()? {
+            Event::Start(element) => {
+                let name = name(&element);
+                root.get_or_insert_with(|| name.clone());
+                if name == "item" || name == "entry" {
+                    episode = Some(Episode::default());
+                }
+                attributes(&element, &mut feed, episode.as_mut())?;
+                path.push(name);
+                text.clear();
+            }
+            Event::Empty(element) => attributes(&element, &mut feed, episode.as_mut())?,
+            Event::Text(value) => text.push_str(&unescaped(value.unescape(), &value)),
+            Event::CData(value) => text.push_str(&String::from_utf8_lossy(&value.into_inner())),
+            Event::End(_) => {
+                let name = path.pop().unwrap_or_default();
+                let value = text.trim().to_string();
+                text.clear();
+                let parent = path.last().map(String::as_str).unwrap_or_default();
+                if name == "item" || name == "entry" {
+                    if let Some(mut episode) = episode.take().filter(|e| !e.url.is_empty()) {
+                        if episode.guid.is_empty() {
+                            episode.guid = episode.url.clone();
+                        }
+                        feed.episodes.push(episode);
+                    }
+                    continue;
+                }
+                match episode.as_mut() {
+                    Some(episode) => episode_text(episode, &name, value),
+                    None => feed_text(&mut feed, &name, parent, value),
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    match root.as_deref() {
+        Some("rss") | Some("feed") | Some("rdf:RDF") => Ok(feed),
+        _ => Err(anyhow!("Not an RSS or Atom feed")),
+    }
+}
+
+fn name(element: &BytesStart) -> String {
+    String::from_utf8_lossy(element.name().as_ref()).to_string()
+}
+
+/// Feeds often carry HTML entities, or a bare `&`, in their text. The HTML5
+/// entities are decoded and text that still can't be unescaped is kept as is.
+fn unescaped(value: quick_xml::Result<Cow<str>>, raw: &[u8]) -> String {
+    match value {
+        Ok(value) => value.into_owned(),
+        Err(_) => String::from_utf8_lossy(raw).to_string(),
+    }
+}
+
+/// Reads the enclosures, links and images given as attributes.
+fn attributes(
+    element: &BytesStart,
+    feed: &mut Feed,
+    episode: Option<&mut Episode>,
+) -> Result<(), Error> {
+    let name = name(element);
+    let mut values = vec![];
+    for attribute in element.attributes() {
+        let attribute = attribute?;
+        values.push((
+            String::from_utf8_lossy(attribute.key.as_ref()).to_string(),
+            unescaped(attribute.unescape_value(), &attribute.value),
+        ));
+    }
+    let value = |key: &str| {
+        values
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.clone())
+    };
+
+    match (name.as_str(), episode) {
+        ("enclosure", Some(episode)) => {
+            episode.url = value("url").unwrap_or_default();
+            episode.mime_type = value("type");
+        }
+        ("link", Some(episode)) if value("rel").as_deref() == Some("enclosure") => {
+            episode.url = value("href").unwrap_or_default();
+            episode.mime_type = value("type");
+        }
+        ("media:content", Some(episode)) if episode.url.is_empty() => {
+            let mime_type = value("type");
+            if mime_type
+                .as_deref()
+                .map(|mime| mime.starts_with("audio/"))
+                .unwrap_or(false)
+            {
+                episode.url = value("url").unwrap_or_default();
+                episode.mime_type = mime_type;
+            }
+        }
+        ("link", None) if feed.link.is_none() => {
+            if matches!(value("rel").as_deref(), None | Some("alternate")) {
+                feed.link = value("href");
+            }
+        }
+        ("itunes:image", None) => {
+            if let Some(href) = value("href") {
+                feed.image = Some(href);
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn feed_text(feed: &mut Feed, name: &str, parent: &str, value: String) {
+    if value.is_empty() {
+        return;
+    }
+    let channel = matches!(parent, "channel" | "feed");
+    match name {
+        "title" if channel => feed.title = value,
+        "description" | "subtitle" | "itunes:summary" if channel && feed.description.is_none() => {
+            feed.description = Some(value)
+        }
+        "link" if channel && feed.link.is_none() => feed.link = Some(value),
+        "itunes:author" if channel => feed.author = Some(value),
+        "name" if parent == "author" && feed.author.is_none() => feed.author = Some(value),
+        "url" if parent == "image" && feed.image.is_none() => feed.image = Some(value),
+        "logo" | "icon" if channel && feed.image.is_none() => feed.image = Some(value),
+        _ => {}
+    }
+}
+
+fn episode_text(episode: &mut Episode, name: &str, value: String) {
+    if value.is_empty() {
+        return;
+    }
+    match name {
+        "title" => episode.title = value,
+        "guid" | "id" => episode.guid = value,
+        "description" | "summary" | "content" | "itunes:summary" | "content:encoded"
+            if episode.description.is_none() =>
+        {
+            episode.description = Some(value)
+        }
+        "itunes:duration" => episode.duration = parse_duration(&value),
+        "pubDate" | "published" | "dc:date" => episode.published_at = parse_date(&value),
+        "updated" if episode.published_at.is_none() => episode.published_at = parse_date(&value),
+        _ => {}
+    }
+}
+
+/// `HH:MM:SS`, `MM:SS` or seconds.
+fn parse_duration(value: &str) -> Option<u32> {
+    value.split(':').try_fold(0, |seconds, part| {
+        let part = part.trim().split('.').next()?.parse::<u32>().ok()?;
+        Some(seconds * 60 + part)
+    })
+}
+
+/// RFC 2822 dates of RSS and RFC 3339 ones of Atom.
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(value)
+        .or_else(|_| DateTime::parse_from_rfc3339(value))
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        path::PathBuf,
+        thread,
+    };
+
+    use super::*;
+
+    fn fixture(name: &str) -> String {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("fixtures");
+        path.push(name);
+        std::fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn test_parse_rss() {
+        let feed = parse(&fixture("rss.xml")).unwrap();
+
+        assert_eq!(feed.title, "Rockbox Radio");
+        assert_eq!(
+            feed.description.as_deref(),
+            Some("Talk about music players\u{a0}& their users")
+        );
+        assert_eq!(feed.author.as_deref(), Some("The Rockbox Crew"));
+        assert_eq!(feed.image.as_deref(), Some("https://example.com/radio.jpg"));
+        assert_eq!(feed.link.as_deref(), Some("https://example.com/radio"));
+        assert_eq!(feed.episodes.len(), 2);
+
+        let episode = &feed.episodes[0];
+        assert_eq!(episode.guid, "radio-2");
+        assert_eq!(episode.title, "Episode 2 \u{2013} It\u{2019}s the codecs");
+        assert_eq!(
+            episode.description.as_deref(),
+            Some("<p>All about <b>codecs</b></p>")
+        );
+        assert_eq!(episode.url, "https://example.com/radio/2.mp3?a=1&b=2");
+        assert_eq!(episode.mime_type.as_deref(), Some("audio/mpeg"));
+        assert_eq!(episode.duration, Some(3723));
+        assert_eq!(episode.published_at, parse_date("2024-11-12T08:00:00Z"));
+
+        let episode = &feed.episodes[1];
+        assert_eq!(episode.title, "Q&A");
+        assert_eq!(episode.guid, "https://example.com/radio/1.mp3");
+        assert_eq!(episode.duration, Some(754));
+    }
+
+    #[test]
+    fn test_parse_atom() {
+        let feed = parse(&fixture("atom.xml")).unwrap();
+
+        assert_eq!(feed.title, "Rockbox Dev Log");
+        assert_eq!(
+            feed.description.as_deref(),
+            Some("Notes from the developers")
+        );
+        assert_eq!(feed.author.as_deref(), Some("Rockbox Developers"));
+        assert_eq!(
+            feed.image.as_deref(),
+            Some("https://example.com/devlog.png")
+        );
+        assert_eq!(feed.link.as_deref(), Some("https://example.com/devlog"));
+        assert_eq!(feed.episodes.len(), 1);
+
+        let episode = &feed.episodes[0];
+        assert_eq!(episode.guid, "tag:example.com,2024:devlog-1");
+        assert_eq!(episode.title, "Gapless playback");
+        assert_eq!(
+            episode.description.as_deref(),
+            Some("How the buffering works")
+        );
+        assert_eq!(episode.url, "https://example.com/devlog/1.ogg");
+        assert_eq!(episode.mime_type.as_deref(), Some("audio/ogg"));
+        assert!(episode.published_at.is_some());
+    }
+
+    /// Answers a single request with `body`, returns the URL to request.
+    fn serve(body: String) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/feed.xml", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = vec![];
+            let mut buffer = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let read = stream.read(&mut buffer).unwrap();
+                if read == 0 {
+                    return;
+                }
+                request.extend_from_slice(&buffer[..read]);
+            }
+            let response = format!(
+                "HTTP/1.1 200 OK\r\n\
+                 Content-Type: application/rss+xml\r\n\
+                 Content-Length: {}\r\n\
+                 Connection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_fetch() {
+        let url = serve(fixture("rss.xml"));
+        let feed = fetch(&url).await.unwrap();
+
+        assert_eq!(feed, parse(&fixture("rss.xml")).unwrap());
+        assert_eq!(feed.episodes.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_rejects_other_documents() {
+        assert!(parse("<html><body>Not a feed</body></html>").is_err());
+    }
+}
@@ -1,8 +1,11 @@
-use std::{fs, io::copy};
+use std::{fs, io::Write};
 
 use anyhow::{anyhow, Error};
 use rockbox_library::{audio_scan::save_audio_metadata, create_connection_pool};
 
+pub mod feed;
+pub mod radio;
+
 pub async fn download(url: &str) -> Result<String, Error> {
     if !url.starts_with("http") {
         return Ok(url.to_string());
@@ -10,30 +13,50 @@ pub async fn download(url: &str) -> Result<String, Error> {
 
     let home_dir = dirs::home_dir().unwrap();
     let cache_dir = format!("{}/.cache/rockbox", home_dir.to_str().unwrap());
-    fs::create_dir_all(&cache_dir)?;
+    let hash = md5::compute(url.as_bytes());
+    download_to(url, &cache_dir, &format!("{:x}", hash)).await
+}
+
+/// Downloads a file into a directory, named `name` with the extension of its
+/// content type, and saves its metadata to the library. A file already
+/// downloaded is not downloaded again, live streams are refused as they
+/// never end.
+pub async fn download_to(url: &str, directory: &str, name: &str) -> Result<String, Error> {
+    fs::create_dir_all(directory)?;
 
     let client = reqwest::Client::new();
     let response = client.head(url).send().await?;
+    if radio::is_live(url, response.headers()) {
+        return Err(anyhow!(
+            "{} is a live stream, play it as a radio station",
+            url
+        ));
+    }
     let mime = response
         .headers()
         .get("content-type")
-        .unwrap()
-        .to_str()
-        .unwrap();
-    let extension = get_file_extension(mime)?;
-    let hash = md5::compute(url.as_bytes());
-    let file_path = format!("{}/{:x}.{}", cache_dir, hash, extension);
+        .and_then(|mime| mime.to_str().ok())
+        .and_then(|mime| mime.split(';').next())
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+    let extension = match get_file_extension(&mime) {
+        Ok(extension) => extension.to_string(),
+        Err(e) => url_extension(url).ok_or(e)?,
+    };
+    let file_path = format!("{}/{}.{}", directory, name, extension);
 
     if fs::metadata(&file_path).is_ok() {
         return Ok(file_path);
     }
 
-    let client = reqwest::Client::new();
-    let response = client.get(url).send().await?;
-    let mut file = fs::File::create(&file_path)?;
-    let content = response.bytes().await?;
-
-    copy(&mut content.as_ref(), &mut file)?;
+    let mut response = client.get(url).send().await?.error_for_status()?;
+    let part_path = format!("{}.part", file_path);
+    let mut file = fs::File::create(&part_path)?;
+    while let Some(chunk) = response.chunk().await? {
+        file.write_all(&chunk)?;
+    }
+    fs::rename(&part_path, &file_path)?;
 
     let pool = create_connection_pool().await?;
     save_audio_metadata(pool, &file_path).await?;
@@ -59,6 +82,8 @@ fn get_file_extension(mime: &str) -> Result<&str, Error> {
         "audio/ogg" => Ok("ogg"),
         "audio/flac" => Ok("flac"),
         "audio/x-m4a" => Ok("m4a"),
+        "audio/mp4" => Ok("m4a"),
+        "audio/x-m4b" => Ok("m4b"),
         "audio/aac" => Ok("aac"),
         "video/mp4" => Ok("mp4"),
         "audio/wav" => Ok("wav"),
@@ -78,3 +103,20 @@ fn get_file_extension(mime: &str) -> Result<&str, Error> {
         _ => Err(anyhow!("Unsupported mime type: {}", mime)),
     }
 }
+
+/// The extension of the path of a URL, for servers answering with a generic
+/// content type.
+fn url_extension(url: &str) -> Option<String> {
+    let path = url.split(['?', '#']).next()?;
+    let name = path.rsplit('/').next()?;
+    let (_, extension) = name.rsplit_once('.')?;
+    let extension = extension.to_lowercase();
+    match [
+        "mp3", "ogg", "oga", "flac", "m4a", "m4b", "aac", "mp4", "wav", "opus", "wma",
+    ]
+    .contains(&extension.as_str())
+    {
+        true => Some(extension),
+        false => None,
+    }
+}
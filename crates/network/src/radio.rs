@@ -0,0 +1,502 @@
+use std::{
+    fs,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use anyhow::{anyhow, Error};
+use reqwest::{header::HeaderMap, Response};
+
+/// Bytes of audio in a segment of a relayed stream, about 8 seconds at
+/// 128 kbps.
+pub const SEGMENT_SIZE: usize = 128 * 1024;
+
+/// Playlists listing other playlists are followed this deep.
+const MAX_NESTED_PLAYLISTS: usize = 3;
+
+/// Station playlists are small, a larger body is a stream.
+const MAX_PLAYLIST_SIZE: usize = 64 * 1024;
+
+const PLAYLIST_TYPES: [&str; 7] = [
+    "audio/x-scpls",
+    "audio/scpls",
+    "application/pls+xml",
+    "audio/x-mpegurl",
+    "audio/mpegurl",
+    "application/x-mpegurl",
+    "application/vnd.apple.mpegurl",
+];
+
+const PLAYLIST_EXTENSIONS: [&str; 3] = ["pls", "m3u", "m3u8"];
+
+/// A live stream, opened asking for its ICY metadata.
+pub struct Stream {
+    /// The stream the station URL leads to.
+    pub url: String,
+    /// The `icy-name` and `icy-genre` headers.
+    pub name: Option<String>,
+    pub genre: Option<String>,
+    pub content_type: Option<String>,
+    metaint: Option<usize>,
+    response: Response,
+}
+
+impl Stream {
+    /// The extension of the segments relayed, `None` for the formats the
+    /// firmware cannot play as raw frames.
+    pub fn extension(&self) -> Option<&'static str> {
+        match self.content_type.as_deref() {
+            Some("audio/mpeg") | Some("audio/mp3") | None => Some("mp3"),
+            Some("audio/aac") | Some("audio/aacp") | Some("audio/x-aac") => Some("aac"),
+            _ => None,
+        }
+    }
+}
+
+/// Opens the stream of a station, following its PLS or M3U playlist.
+pub async fn open(url: &str) -> Result<Stream, Error> {
+    let client = reqwest::Client::new();
+    let mut url = url.to_string();
+    for _ in 0..=MAX_NESTED_PLAYLISTS {
+        let mut response = client
+            .get(&url)
+            .header("Icy-MetaData", "1")
+            .send()
+            .await?
+            .error_for_status()?;
+        let headers = response.headers();
+        let content_type = content_type(headers);
+        if !is_playlist(&url, content_type.as_deref()) {
+            return Ok(Stream {
+                name: header(headers, "icy-name"),
+                genre: header(headers, "icy-genre"),
+                metaint: header(headers, "icy-metaint").and_then(|m| m.parse().ok()),
+                content_type,
+                url,
+                response,
+            });
+        }
+
+        let playlist = read_playlist(&mut response).await?;
+        if playlist.contains("#EXT-X-") {
+            return Err(anyhow!("HLS streams are not supported: {}", url));
+        }
+        url = parse_playlist(&playlist)
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("No stream in the playlist {}", url))?;
+    }
+    Err(anyhow!("Too many nested playlists"))
+}
+
+/// Whether the headers of a URL, usually from a `HEAD` request, are those
+/// of a live stream or of a station playlist rather than of a file.
+pub fn is_live(url: &str, headers: &HeaderMap) -> bool {
+    let content_type = content_type(headers);
+    headers.keys().any(|key| key.as_str().starts_with("icy-"))
+        || is_playlist(url, content_type.as_deref())
+        || (content_type
+            .as_deref()
+            .map(|t| t.starts_with("audio/"))
+            .unwrap_or(false)
+            && !headers.contains_key("content-length"))
+}
+
+/// The stream URLs of a PLS or M3U playlist, in order.
+pub fn parse_playlist(playlist: &str) -> Vec<String> {
+    let lines = playlist
+        .lines()
+        .map(|line| line.trim_start_matches('\u{feff}').trim())
+        .filter(|line| !line.is_empty());
+
+    if playlist
+        .trim_start_matches('\u{feff}')
+        .trim_start()
+        .to_lowercase()
+        .starts_with("[playlist]")
+    {
+        let mut entries: Vec<(u32, String)> = lines
+            .filter_map(|line| {
+                let (key, value) = line.split_once('=')?;
+                let number = key
+                    .trim()
+                    .to_lowercase()
+                    .strip_prefix("file")?
+                    .parse()
+                    .ok()?;
+                Some((number, value.trim().to_string()))
+            })
+            .collect();
+        entries.sort_by_key(|(number, _)| *number);
+        return entries.into_iter().map(|(_, url)| url).collect();
+    }
+
+    lines
+        .filter(|line| !line.starts_with('#') && line.contains("://"))
+        .map(|line| line.to_string())
+        .collect()
+}
+
+/// The title of an ICY metadata block, `StreamTitle='Artist - Title';`.
+pub fn parse_stream_title(metadata: &str) -> Option<String> {
+    let start = metadata.find("StreamTitle='")? + "StreamTitle='".len();
+    let title = &metadata[start..];
+    let end = title.find("';").unwrap_or(title.len());
+    Some(
+        title[..end]
+            .trim_end_matches(['\0', '\''])
+            .trim()
+            .to_string(),
+    )
+}
+
+/// Splits a stream title into its artist and title, most stations send
+/// `Artist - Title`.
+pub fn split_stream_title(stream_title: &str) -> (Option<String>, String) {
+    match stream_title.split_once(" - ") {
+        Some((artist, title)) if !artist.trim().is_empty() => {
+            (Some(artist.trim().to_string()), title.trim().to_string())
+        }
+        _ => (None, stream_title.trim().to_string()),
+    }
+}
+
+/// Relays a live stream into numbered segments of `directory`, each one
+/// handed to `on_segment` once written, until `stop` is set or
+/// `on_segment` returns false. `on_title` gets the stream titles.
+pub async fn relay(
+    mut stream: Stream,
+    directory: &str,
+    stop: &AtomicBool,
+    mut on_title: impl FnMut(String),
+    mut on_segment: impl FnMut(String) -> bool,
+) -> Result<(), Error> {
+    let extension = stream.extension().ok_or_else(|| {
+        anyhow!(
+            "Unsupported stream format: {}",
+            stream.content_type.as_deref().unwrap_or_default()
+        )
+    })?;
+    fs::create_dir_all(directory)?;
+
+    let mut icy = IcyReader::new(stream.metaint);
+    let mut buffer = Vec::with_capacity(2 * SEGMENT_SIZE);
+    let mut index = 0;
+    while !stop.load(Ordering::Relaxed) {
+        let chunk = match stream.response.chunk().await? {
+            Some(chunk) => chunk,
+            None => return Err(anyhow!("The stream ended: {}", stream.url)),
+        };
+        if let Some(title) = icy.read(&chunk, &mut buffer) {
+            on_title(title);
+        }
+        while let Some(end) = segment_end(&buffer) {
+            let path = format!("{}/{:06}.{}", directory, index, extension);
+            fs::write(&path, &buffer[..end])?;
+            buffer.drain(..end);
+            index += 1;
+            if !on_segment(path) {
+                return Ok(());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Separates the audio of a stream from its metadata blocks, sent every
+/// `icy-metaint` bytes after a byte giving their length in 16 bytes units.
+struct IcyReader {
+    metaint: Option<usize>,
+    /// Audio bytes until the next metadata block.
+    remaining: usize,
+    metadata: Vec<u8>,
+    metadata_length: usize,
+}
+
+impl IcyReader {
+    fn new(metaint: Option<usize>) -> Self {
+        Self {
+            metaint,
+            remaining: metaint.unwrap_or_default(),
+            metadata: vec![],
+            metadata_length: 0,
+        }
+    }
+
+    /// Appends the audio of `data` to `audio`, returns the last title read.
+    fn read(&mut self, mut data: &[u8], audio: &mut Vec<u8>) -> Option<String> {
+        let Some(metaint) = self.metaint.filter(|metaint| *metaint > 0) else {
+            audio.extend_from_slice(data);
+            return None;
+        };
+
+        let mut title = None;
+        while !data.is_empty() {
+            if self.metadata_length > 0 {
+                let length = (self.metadata_length - self.metadata.len()).min(data.len());
+                self.metadata.extend_from_slice(&data[..length]);
+                data = &data[length..];
+                if self.metadata.len() == self.metadata_length {
+                    if let Some(stream_title) =
+                        parse_stream_title(&String::from_utf8_lossy(&self.metadata))
+                    {
+                        title = Some(stream_title);
+                    }
+                    self.metadata.clear();
+                    self.metadata_length = 0;
+                    self.remaining = metaint;
+                }
+            } else if self.remaining == 0 {
+                self.metadata_length = data[0] as usize * 16;
+                data = &data[1..];
+                if self.metadata_length == 0 {
+                    self.remaining = metaint;
+                }
+            } else {
+                let length = self.remaining.min(data.len());
+                audio.extend_from_slice(&data[..length]);
+                data = &data[length..];
+                self.remaining -= length;
+            }
+        }
+        title
+    }
+}
+
+/// Where the segment buffered ends, on the first frame after
+/// `SEGMENT_SIZE` bytes so that each segment decodes on its own.
+fn segment_end(buffer: &[u8]) -> Option<usize> {
+    if buffer.len() < SEGMENT_SIZE {
+        return None;
+    }
+    match (SEGMENT_SIZE..buffer.len().saturating_sub(3))
+        .find(|i| is_frame_sync(&buffer[*i..*i + 4]))
+    {
+        Some(end) => Some(end),
+        None if buffer.len() >= 2 * SEGMENT_SIZE => Some(SEGMENT_SIZE),
+        None => None,
+    }
+}
+
+/// Whether an MPEG audio or an ADTS frame header starts there.
+fn is_frame_sync(header: &[u8]) -> bool {
+    if header[0] != 0xff || header[1] & 0xe0 != 0xe0 {
+        return false;
+    }
+    // ADTS, its layer is always 0
+    if header[1] & 0xf6 == 0xf0 {
+        return (header[2] >> 2) & 0x0f < 13;
+    }
+    let layer = (header[1] >> 1) & 0x03;
+    let bitrate = header[2] >> 4;
+    let sample_rate = (header[2] >> 2) & 0x03;
+    layer != 0 && bitrate != 0 && bitrate != 0x0f && sample_rate != 0x03
+}
+
+async fn read_playlist(response: &mut Response) -> Result<String, Error> {
+    let mut playlist = vec![];
+    while let Some(chunk) = response.chunk().await? {
+        playlist.extend_from_slice(&chunk);
+        if playlist.len() > MAX_PLAYLIST_SIZE {
+            return Err(anyhow!("Invalid station playlist"));
+        }
+    }
+    Ok(String::from_utf8_lossy(&playlist).to_string())
+}
+
+fn is_playlist(url: &str, content_type: Option<&str>) -> bool {
+    if let Some(content_type) = content_type {
+        if PLAYLIST_TYPES.contains(&content_type) {
+            return true;
+        }
+        if content_type.starts_with("audio/") {
+            return false;
+        }
+    }
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    path.rsplit_once('.')
+        .map(|(_, extension)| PLAYLIST_EXTENSIONS.contains(&extension.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+fn content_type(headers: &HeaderMap) -> Option<String> {
+    header(headers, "content-type").map(|content_type| {
+        content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase()
+    })
+}
+
+fn header(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Audio bytes, a metadata block of `metadata` padded to 16 bytes units
+    /// and more audio.
+    fn icy_stream(audio: &[&[u8]], metadata: &[&str]) -> Vec<u8> {
+        let mut stream = vec![];
+        for (index, chunk) in audio.iter().enumerate() {
+            stream.extend_from_slice(chunk);
+            if let Some(metadata) = metadata.get(index) {
+                let mut block = metadata.as_bytes().to_vec();
+                block.resize(block.len().div_ceil(16) * 16, 0);
+                stream.push((block.len() / 16) as u8);
+                stream.extend_from_slice(&block);
+            }
+        }
+        stream
+    }
+
+    #[test]
+    fn test_parse_pls_playlist() {
+        let playlist = "\u{feff}[playlist]\r\n\
+            NumberOfEntries=2\r\n\
+            File2=http://example.com/backup\r\n\
+            Title2=Backup\r\n\
+            File1 = http://example.com/stream \r\n\
+            Version=2\r\n";
+
+        assert_eq!(
+            parse_playlist(playlist),
+            vec!["http://example.com/stream", "http://example.com/backup"]
+        );
+    }
+
+    #[test]
+    fn test_parse_m3u_playlist() {
+        let playlist = "\u{feff}#EXTM3U\n\
+            \n\
+            #EXTINF:-1,Station\n\
+            http://example.com/stream.mp3\n\
+            not a url\n\
+            https://example.com/stream.aac\n";
+
+        assert_eq!(
+            parse_playlist(playlist),
+            vec![
+                "http://example.com/stream.mp3",
+                "https://example.com/stream.aac"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_stream_title() {
+        assert_eq!(
+            parse_stream_title("StreamTitle='Artist - Title';StreamUrl='';"),
+            Some("Artist - Title".to_string())
+        );
+        assert_eq!(
+            parse_stream_title("StreamTitle='It's live'\0\0\0"),
+            Some("It's live".to_string())
+        );
+        assert_eq!(parse_stream_title("StreamTitle='';"), Some("".to_string()));
+        assert_eq!(parse_stream_title("StreamUrl='http://example.com';"), None);
+    }
+
+    #[test]
+    fn test_split_stream_title() {
+        assert_eq!(
+            split_stream_title("Artist - Title - Live"),
+            (Some("Artist".to_string()), "Title - Live".to_string())
+        );
+        assert_eq!(
+            split_stream_title(" - Title"),
+            (None, "- Title".to_string())
+        );
+        assert_eq!(
+            split_stream_title("Station jingle"),
+            (None, "Station jingle".to_string())
+        );
+    }
+
+    #[test]
+    fn test_icy_reader_in_one_chunk() {
+        let stream = icy_stream(&[b"abcd", b"efgh", b"ij"], &["StreamTitle='A';", ""]);
+        let mut reader = IcyReader::new(Some(4));
+        let mut audio = vec![];
+
+        assert_eq!(reader.read(&stream, &mut audio), Some("A".to_string()));
+        assert_eq!(audio, b"abcdefghij");
+        assert_eq!(reader.remaining, 2);
+    }
+
+    #[test]
+    fn test_icy_reader_split_across_chunks() {
+        let stream = icy_stream(&[b"abcd", b"efgh"], &["StreamTitle='Artist - Title';"]);
+        let mut reader = IcyReader::new(Some(4));
+        let mut audio = vec![];
+
+        let titles: Vec<(usize, String)> = stream
+            .iter()
+            .enumerate()
+            .filter_map(|(index, byte)| Some((index, reader.read(&[*byte], &mut audio)?)))
+            .collect();
+
+        // the title is read with the last byte of its block
+        assert_eq!(titles, vec![(4 + 1 + 32 - 1, "Artist - Title".to_string())]);
+        assert_eq!(audio, b"abcdefgh");
+    }
+
+    #[test]
+    fn test_icy_reader_empty_metadata() {
+        let stream = icy_stream(&[b"abcd", b"efgh", b"ij"], &["", ""]);
+        let mut reader = IcyReader::new(Some(4));
+        let mut audio = vec![];
+
+        assert_eq!(reader.read(&stream[..5], &mut audio), None);
+        assert_eq!(reader.remaining, 4);
+        assert_eq!(reader.read(&stream[5..], &mut audio), None);
+        assert_eq!(audio, b"abcdefghij");
+    }
+
+    #[test]
+    fn test_icy_reader_without_metaint() {
+        let mut reader = IcyReader::new(None);
+        let mut audio = vec![];
+
+        assert_eq!(reader.read(b"\x01StreamTitle='A';", &mut audio), None);
+        assert_eq!(audio, b"\x01StreamTitle='A';");
+    }
+
+    #[test]
+    fn test_is_frame_sync() {
+        // MPEG 1 layer III, 128 kbps, 44.1 kHz
+        assert!(is_frame_sync(&[0xff, 0xfb, 0x90, 0x64]));
+        // ADTS, AAC LC, 44.1 kHz
+        assert!(is_frame_sync(&[0xff, 0xf1, 0x50, 0x80]));
+        // invalid bitrate and reserved sample rates
+        assert!(!is_frame_sync(&[0xff, 0xfb, 0xf0, 0x00]));
+        assert!(!is_frame_sync(&[0xff, 0xfb, 0x9c, 0x00]));
+        assert!(!is_frame_sync(&[0xff, 0xf1, 0x7c, 0x80]));
+        assert!(!is_frame_sync(&[0x00, 0x00, 0x00, 0x00]));
+    }
+
+    #[test]
+    fn test_segment_end() {
+        let mut buffer = vec![0; SEGMENT_SIZE + 100];
+        assert_eq!(segment_end(&buffer[..SEGMENT_SIZE - 1]), None);
+        assert_eq!(segment_end(&buffer), None);
+
+        buffer[SEGMENT_SIZE + 10..SEGMENT_SIZE + 14].copy_from_slice(&[0xff, 0xfb, 0x90, 0x64]);
+        assert_eq!(segment_end(&buffer), Some(SEGMENT_SIZE + 10));
+
+        // a frame header cut by the end of the buffer is not a sync yet
+        assert_eq!(segment_end(&buffer[..SEGMENT_SIZE + 12]), None);
+
+        // a stream without frames is cut at `SEGMENT_SIZE`
+        buffer.fill(0);
+        buffer.resize(2 * SEGMENT_SIZE, 0);
+        assert_eq!(segment_end(&buffer), Some(SEGMENT_SIZE));
+    }
+}
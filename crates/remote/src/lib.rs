@@ -0,0 +1,272 @@
+use anyhow::Error;
+use async_trait::async_trait;
+use rockbox_rpc::api::rockbox::v1alpha1::{
+    playback_service_client::PlaybackServiceClient, playlist_service_client::PlaylistServiceClient,
+    sound_service_client::SoundServiceClient, AdjustVolumeRequest, CreatePlaylistRequest,
+    CurrentTrackRequest, CurrentTrackResponse, FastForwardRewindRequest, GetCurrentRequest,
+    HardStopRequest, InsertTracksRequest, NextRequest, PauseRequest, PreviousRequest,
+    RemoveTracksRequest, ResumeRequest, SoundCurrentRequest, SoundMaxRequest, SoundMinRequest,
+    StartRequest, StatusRequest,
+};
+use rockbox_sys::{AUDIO_STATUS_PLAY, SOUND_VOLUME};
+use rockbox_traits::types::playback::Playback;
+use rockbox_traits::types::track::Track;
+use rockbox_traits::Player;
+use rockbox_types::device::Device;
+use tonic::transport::Channel;
+
+pub mod library;
+
+pub use library::RemoteLibrary;
+
+const PLAYLIST_INSERT_FIRST: i32 = -4;
+
+/// Drives another Rockbox instance through its gRPC API.
+///
+/// A new channel is opened for every call: the HTTP handlers run each request
+/// on a short lived runtime, which would take a long lived channel down with it.
+pub struct RemotePlayer {
+    url: String,
+}
+
+impl RemotePlayer {
+    pub fn new(device: &Device) -> Self {
+        RemotePlayer {
+            url: grpc_url(device),
+        }
+    }
+
+    pub async fn connect(device: Device) -> Result<Option<Box<dyn Player + Send>>, Error> {
+        let player = RemotePlayer::new(&device);
+        // make sure the instance is reachable before handing it out
+        player.playback().await?;
+        Ok(Some(Box::new(player)))
+    }
+
+    async fn playback(&self) -> Result<PlaybackServiceClient<Channel>, Error> {
+        Ok(PlaybackServiceClient::connect(self.url.clone()).await?)
+    }
+
+    async fn playlist(&self) -> Result<PlaylistServiceClient<Channel>, Error> {
+        Ok(PlaylistServiceClient::connect(self.url.clone()).await?)
+    }
+
+    async fn sound(&self) -> Result<SoundServiceClient<Channel>, Error> {
+        Ok(SoundServiceClient::connect(self.url.clone()).await?)
+    }
+}
+
+pub fn grpc_url(device: &Device) -> String {
+    format!("tcp://{}:{}", device.ip, device.port)
+}
+
+fn to_track(track: CurrentTrackResponse) -> Track {
+    Track {
+        id: track.id,
+        uri: track.path.clone(),
+        path: track.path,
+        title: track.title,
+        artist: track.artist,
+        album: track.album,
+        track_number: Some(track.tracknum as u32),
+        disc_number: track.discnum as u32,
+        duration: Some(track.length as f32 / 1000.0),
+        album_artist: Some(track.album_artist),
+        album_cover: track.album_art,
+        album_id: Some(track.album_id),
+        artist_id: Some(track.artist_id),
+    }
+}
+
+#[async_trait]
+impl Player for RemotePlayer {
+    async fn play(&self) -> Result<(), Error> {
+        self.playback().await?.resume(ResumeRequest {}).await?;
+        Ok(())
+    }
+
+    async fn next(&self) -> Result<(), Error> {
+        self.playback().await?.next(NextRequest {}).await?;
+        Ok(())
+    }
+
+    async fn previous(&self) -> Result<(), Error> {
+        self.playback().await?.previous(PreviousRequest {}).await?;
+        Ok(())
+    }
+
+    async fn stop(&self) -> Result<(), Error> {
+        self.playback().await?.hard_stop(HardStopRequest {}).await?;
+        Ok(())
+    }
+
+    async fn pause(&self) -> Result<(), Error> {
+        self.playback().await?.pause(PauseRequest {}).await?;
+        Ok(())
+    }
+
+    async fn resume(&self) -> Result<(), Error> {
+        self.playback().await?.resume(ResumeRequest {}).await?;
+        Ok(())
+    }
+
+    async fn seek(&self, seconds: i32) -> Result<(), Error> {
+        self.seek_ms((seconds.max(0) as u32) * 1000).await
+    }
+
+    async fn seek_ms(&self, position_ms: u32) -> Result<(), Error> {
+        self.playback()
+            .await?
+            .fast_forward_rewind(FastForwardRewindRequest {
+                new_time: position_ms as i32,
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn volume(&self, level: f32) -> Result<(), Error> {
+        let mut sound = self.sound().await?;
+        let min = sound
+            .sound_min(SoundMinRequest {
+                setting: SOUND_VOLUME,
+            })
+            .await?
+            .into_inner()
+            .value;
+        let max = sound
+            .sound_max(SoundMaxRequest {
+                setting: SOUND_VOLUME,
+            })
+            .await?
+            .into_inner()
+            .value;
+        let current = sound
+            .sound_current(SoundCurrentRequest {
+                setting: SOUND_VOLUME,
+            })
+            .await?
+            .into_inner()
+            .value;
+        let target = min + ((max - min) as f32 * level.clamp(0.0, 1.0)).round() as i32;
+        sound
+            .adjust_volume(AdjustVolumeRequest {
+                steps: target - current,
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn load_tracks(&self, tracks: Vec<Track>, start_index: Option<i32>) -> Result<(), Error> {
+        let mut playlist = self.playlist().await?;
+        // the remote instance can't read our files, it downloads them from our http endpoint
+        let tracks = tracks
+            .into_iter()
+            .map(|t| match t.uri.is_empty() {
+                true => t.path,
+                false => t.uri,
+            })
+            .collect();
+        playlist
+            .create_playlist(CreatePlaylistRequest {
+                name: "".to_string(),
+                tracks,
+                folder_id: None,
+            })
+            .await?;
+        playlist
+            .start(StartRequest {
+                start_index: Some(start_index.unwrap_or(0)),
+                elapsed: None,
+                offset: None,
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn play_next(&self, track: Track) -> Result<(), Error> {
+        let track = match track.uri.is_empty() {
+            true => track.path,
+            false => track.uri,
+        };
+        self.playlist()
+            .await?
+            .insert_tracks(InsertTracksRequest {
+                playlist_id: None,
+                position: PLAYLIST_INSERT_FIRST,
+                tracks: vec![track],
+                shuffle: None,
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn load(&mut self, track: Track) -> Result<(), Error> {
+        self.load_tracks(vec![track], None).await
+    }
+
+    async fn get_current_playback(&mut self) -> Result<Playback, Error> {
+        let mut playback = self.playback().await?;
+        let status = playback.status(StatusRequest {}).await?.into_inner();
+        let track = playback
+            .current_track(CurrentTrackRequest {})
+            .await?
+            .into_inner();
+        let current = self
+            .playlist()
+            .await?
+            .get_current(GetCurrentRequest {})
+            .await?
+            .into_inner();
+        let position_ms = track.elapsed as u32;
+        let current_track = match track.path.is_empty() {
+            true => None,
+            false => Some(to_track(track)),
+        };
+        Ok(Playback {
+            current_track,
+            index: current.index.max(0) as u32,
+            position_ms,
+            is_playing: status.status == AUDIO_STATUS_PLAY,
+            ..Default::default()
+        })
+    }
+
+    async fn get_current_tracklist(&self) -> Result<(Vec<Track>, Vec<Track>), Error> {
+        let current = self
+            .playlist()
+            .await?
+            .get_current(GetCurrentRequest {})
+            .await?
+            .into_inner();
+        let index = current.index.max(0) as usize;
+        let mut tracks: Vec<Track> = current.tracks.into_iter().map(to_track).collect();
+        let next_tracks = tracks.split_off(index.min(tracks.len()));
+        Ok((tracks, next_tracks))
+    }
+
+    async fn play_track_at(&self, position: u32) -> Result<(), Error> {
+        self.playlist()
+            .await?
+            .start(StartRequest {
+                start_index: Some(position as i32),
+                elapsed: None,
+                offset: None,
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn remove_track_at(&self, position: u32) -> Result<(), Error> {
+        self.playlist()
+            .await?
+            .remove_tracks(RemoveTracksRequest {
+                positions: vec![position as i32],
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn disconnect(&self) -> Result<(), Error> {
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+    time::Duration,
+};
+
+use anyhow::Error;
+use rockbox_library::entity::{album::Album, track::Track};
+use rockbox_rpc::{
+    api::rockbox::v1alpha1::{
+        library_service_client::LibraryServiceClient, GetAlbumsRequest, GetTracksRequest,
+        SearchRequest,
+    },
+    REMOTE_QUERY_HEADER,
+};
+use rockbox_service::remote::{RemoteQuery, RemoteResults};
+use rockbox_types::device::Device;
+use tokio::runtime::Runtime;
+use tonic::{metadata::MetadataValue, transport::Channel};
+
+use crate::grpc_url;
+
+/// Remote instances that don't answer within this delay are left out of the results.
+const REMOTE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// The HTTP handlers run each request on a short lived runtime, the channels
+/// to the remote libraries are opened and used on this one so they can be
+/// kept between requests.
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+static CHANNELS: OnceLock<Mutex<HashMap<String, Channel>>> = OnceLock::new();
+
+fn runtime() -> &'static Runtime {
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("remote-library")
+            .enable_all()
+            .build()
+            .unwrap()
+    })
+}
+
+/// The channel to an instance, connected on first use and reconnected when
+/// the instance comes back after a failure.
+fn channel(url: &str) -> Result<Channel, Error> {
+    let mut channels = CHANNELS.get_or_init(Default::default).lock().unwrap();
+    if let Some(channel) = channels.get(url) {
+        return Ok(channel.clone());
+    }
+    let _runtime = runtime().enter();
+    let channel = Channel::from_shared(url.to_string())?
+        .connect_timeout(REMOTE_TIMEOUT)
+        .timeout(REMOTE_TIMEOUT)
+        .connect_lazy();
+    channels.insert(url.to_string(), channel.clone());
+    Ok(channel)
+}
+
+/// Runs a query on the library of `device`, registered in the service with
+/// [`rockbox_service::remote::register_remote_library`].
+pub async fn query(device: Device, query: RemoteQuery) -> Result<RemoteResults, Error> {
+    let library = RemoteLibrary::new(&device);
+    runtime()
+        .spawn(async move {
+            match query {
+                RemoteQuery::Albums => Ok(RemoteResults {
+                    albums: library.albums().await?,
+                    tracks: vec![],
+                }),
+                RemoteQuery::Tracks => Ok(RemoteResults {
+                    albums: vec![],
+                    tracks: library.tracks().await?,
+                }),
+                RemoteQuery::Search(term) => {
+                    let (albums, tracks) = library.search(&term).await?;
+                    Ok(RemoteResults { albums, tracks })
+                }
+            }
+        })
+        .await?
+}
+
+/// Read access to the library of another Rockbox instance, tracks and covers
+/// are rewritten to the instance's http endpoints so they can be streamed.
+pub struct RemoteLibrary {
+    url: String,
+    base_url: Option<String>,
+}
+
+impl RemoteLibrary {
+    pub fn new(device: &Device) -> Self {
+        RemoteLibrary {
+            url: grpc_url(device),
+            base_url: device.base_url.clone(),
+        }
+    }
+
+    fn client(&self) -> Result<LibraryServiceClient<Channel>, Error> {
+        Ok(LibraryServiceClient::new(channel(&self.url)?))
+    }
+
+    fn request<T>(message: T) -> tonic::Request<T> {
+        let mut request = tonic::Request::new(message);
+        request
+            .metadata_mut()
+            .insert(REMOTE_QUERY_HEADER, MetadataValue::from_static("1"));
+        request
+    }
+
+    pub async fn albums(&self) -> Result<Vec<Album>, Error> {
+        let response = self
+            .client()?
+            .get_albums(Self::request(GetAlbumsRequest::default()))
+            .await?
+            .into_inner();
+        Ok(response
+            .albums
+            .into_iter()
+            .map(|album| self.rewrite_album(album.into()))
+            .collect())
+    }
+
+    pub async fn tracks(&self) -> Result<Vec<Track>, Error> {
+        let response = self
+            .client()?
+            .get_tracks(Self::request(GetTracksRequest::default()))
+            .await?
+            .into_inner();
+        Ok(response
+            .tracks
+            .into_iter()
+            .map(|track| self.rewrite_track(track.into()))
+            .collect())
+    }
+
+    pub async fn search(&self, term: &str) -> Result<(Vec<Album>, Vec<Track>), Error> {
+        let response = self
+            .client()?
+            .search(Self::request(SearchRequest {
+                term: term.to_string(),
+                ..Default::default()
+            }))
+            .await?
+            .into_inner();
+        let albums = response
+            .albums
+            .into_iter()
+            .map(|album| self.rewrite_album(album.into()))
+            .collect();
+        let tracks = response
+            .tracks
+            .into_iter()
+            .map(|track| self.rewrite_track(track.into()))
+            .collect();
+        Ok((albums, tracks))
+    }
+
+    fn rewrite_album(&self, mut album: Album) -> Album {
+        if let Some(base_url) = &self.base_url {
+            album.album_art = album
+                .album_art
+                .map(|cover| format!("{}/covers/{}", base_url, cover));
+        }
+        album
+    }
+
+    fn rewrite_track(&self, mut track: Track) -> Track {
+        if let Some(base_url) = &self.base_url {
+            track.path = format!("{}/tracks/{}", base_url, track.id);
+            track.album_art = track
+                .album_art
+                .map(|cover| format!("{}/covers/{}", base_url, cover));
+        }
+        track
+    }
+}
@@ -1,43 +1,152 @@
 // This file is @generated by prost-build.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Bookmark {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "2")]
+    pub name: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(enumeration = "BookmarkSource", tag = "3")]
+    pub source: i32,
+    #[prost(string, tag = "4")]
+    pub source_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub path: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "6")]
+    pub track_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint64, tag = "7")]
+    pub elapsed: u64,
+    #[prost(int64, tag = "8")]
+    pub created_at: i64,
+    #[prost(int64, tag = "9")]
+    pub updated_at: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Chapter {
+    #[prost(uint32, tag = "1")]
+    pub index: u32,
+    #[prost(string, tag = "2")]
+    pub title: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "3")]
+    pub start: u64,
+    #[prost(uint64, tag = "4")]
+    pub end: u64,
+}
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct RockboxBrowseRequest {}
-#[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct RockboxBrowseResponse {}
-#[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct TreeGetContextRequest {}
-#[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct TreeGetContextResponse {}
+pub struct GetBookmarksRequest {}
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct TreeGetEntriesRequest {
-    #[prost(string, optional, tag = "1")]
-    pub path: ::core::option::Option<::prost::alloc::string::String>,
+pub struct GetBookmarksResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub bookmarks: ::prost::alloc::vec::Vec<Bookmark>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct Entry {
+pub struct GetBookmarkRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBookmarkResponse {
+    #[prost(message, optional, tag = "1")]
+    pub bookmark: ::core::option::Option<Bookmark>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateBookmarkRequest {
     #[prost(string, tag = "1")]
     pub name: ::prost::alloc::string::String,
-    #[prost(int32, tag = "2")]
-    pub attr: i32,
-    #[prost(uint32, tag = "3")]
-    pub time_write: u32,
-    #[prost(int32, tag = "4")]
-    pub customaction: i32,
+    #[prost(string, optional, tag = "2")]
+    pub path: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint64, optional, tag = "3")]
+    pub elapsed: ::core::option::Option<u64>,
+    #[prost(enumeration = "BookmarkSource", optional, tag = "4")]
+    pub source: ::core::option::Option<i32>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct TreeGetEntriesResponse {
-    #[prost(message, repeated, tag = "1")]
-    pub entries: ::prost::alloc::vec::Vec<Entry>,
+pub struct CreateBookmarkResponse {
+    #[prost(message, optional, tag = "1")]
+    pub bookmark: ::core::option::Option<Bookmark>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateBookmarkRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "2")]
+    pub name: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint64, optional, tag = "3")]
+    pub elapsed: ::core::option::Option<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateBookmarkResponse {
+    #[prost(message, optional, tag = "1")]
+    pub bookmark: ::core::option::Option<Bookmark>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteBookmarkRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
 }
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct TreeGetEntryAtRequest {}
+pub struct DeleteBookmarkResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PlayBookmarkRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct TreeGetEntryAtResponse {}
+pub struct PlayBookmarkResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetTrackChaptersRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetTrackChaptersResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub chapters: ::prost::alloc::vec::Vec<Chapter>,
+}
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct BrowseId3Request {}
+pub struct GetCurrentChaptersRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetCurrentChaptersResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub chapters: ::prost::alloc::vec::Vec<Chapter>,
+}
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct BrowseId3Response {}
+pub struct SeekChapterRequest {
+    #[prost(uint32, tag = "1")]
+    pub index: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SeekChapterResponse {}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum BookmarkSource {
+    Track = 0,
+    Album = 1,
+    Directory = 2,
+}
+impl BookmarkSource {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Track => "BOOKMARK_SOURCE_TRACK",
+            Self::Album => "BOOKMARK_SOURCE_ALBUM",
+            Self::Directory => "BOOKMARK_SOURCE_DIRECTORY",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "BOOKMARK_SOURCE_TRACK" => Some(Self::Track),
+            "BOOKMARK_SOURCE_ALBUM" => Some(Self::Album),
+            "BOOKMARK_SOURCE_DIRECTORY" => Some(Self::Directory),
+            _ => None,
+        }
+    }
+}
 /// Generated client implementations.
-pub mod browse_service_client {
+pub mod bookmark_service_client {
     #![allow(
         unused_variables,
         dead_code,
@@ -48,10 +157,10 @@ pub mod browse_service_client {
     use tonic::codegen::*;
     use tonic::codegen::http::Uri;
     #[derive(Debug, Clone)]
-    pub struct BrowseServiceClient<T> {
+    pub struct BookmarkServiceClient<T> {
         inner: tonic::client::Grpc<T>,
     }
-    impl BrowseServiceClient<tonic::transport::Channel> {
+    impl BookmarkServiceClient<tonic::transport::Channel> {
         /// Attempt to create a new client by connecting to a given endpoint.
         pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
         where
@@ -62,7 +171,7 @@ pub mod browse_service_client {
             Ok(Self::new(conn))
         }
     }
-    impl<T> BrowseServiceClient<T>
+    impl<T> BookmarkServiceClient<T>
     where
         T: tonic::client::GrpcService<tonic::body::BoxBody>,
         T::Error: Into<StdError>,
@@ -80,7 +189,7 @@ pub mod browse_service_client {
         pub fn with_interceptor<F>(
             inner: T,
             interceptor: F,
-        ) -> BrowseServiceClient<InterceptedService<T, F>>
+        ) -> BookmarkServiceClient<InterceptedService<T, F>>
         where
             F: tonic::service::Interceptor,
             T::ResponseBody: Default,
@@ -94,7 +203,7 @@ pub mod browse_service_client {
                 http::Request<tonic::body::BoxBody>,
             >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
         {
-            BrowseServiceClient::new(InterceptedService::new(inner, interceptor))
+            BookmarkServiceClient::new(InterceptedService::new(inner, interceptor))
         }
         /// Compress requests with the given encoding.
         ///
@@ -127,11 +236,11 @@ pub mod browse_service_client {
             self.inner = self.inner.max_encoding_message_size(limit);
             self
         }
-        pub async fn tree_get_entries(
+        pub async fn get_bookmarks(
             &mut self,
-            request: impl tonic::IntoRequest<super::TreeGetEntriesRequest>,
+            request: impl tonic::IntoRequest<super::GetBookmarksRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::TreeGetEntriesResponse>,
+            tonic::Response<super::GetBookmarksResponse>,
             tonic::Status,
         > {
             self.inner
@@ -144,85 +253,355 @@ pub mod browse_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/rockbox.v1alpha1.BrowseService/TreeGetEntries",
+                "/rockbox.v1alpha1.BookmarkService/GetBookmarks",
             );
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(
-                    GrpcMethod::new("rockbox.v1alpha1.BrowseService", "TreeGetEntries"),
+                    GrpcMethod::new("rockbox.v1alpha1.BookmarkService", "GetBookmarks"),
                 );
             self.inner.unary(req, path, codec).await
         }
-    }
-}
-/// Generated server implementations.
-pub mod browse_service_server {
-    #![allow(
-        unused_variables,
-        dead_code,
-        missing_docs,
-        clippy::wildcard_imports,
-        clippy::let_unit_value,
-    )]
-    use tonic::codegen::*;
-    /// Generated trait containing gRPC methods that should be implemented for use with BrowseServiceServer.
-    #[async_trait]
-    pub trait BrowseService: std::marker::Send + std::marker::Sync + 'static {
-        async fn tree_get_entries(
-            &self,
-            request: tonic::Request<super::TreeGetEntriesRequest>,
+        pub async fn get_bookmark(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetBookmarkRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::TreeGetEntriesResponse>,
+            tonic::Response<super::GetBookmarkResponse>,
             tonic::Status,
-        >;
-    }
-    #[derive(Debug)]
-    pub struct BrowseServiceServer<T> {
-        inner: Arc<T>,
-        accept_compression_encodings: EnabledCompressionEncodings,
-        send_compression_encodings: EnabledCompressionEncodings,
-        max_decoding_message_size: Option<usize>,
-        max_encoding_message_size: Option<usize>,
-    }
-    impl<T> BrowseServiceServer<T> {
-        pub fn new(inner: T) -> Self {
-            Self::from_arc(Arc::new(inner))
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.BookmarkService/GetBookmark",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.BookmarkService", "GetBookmark"),
+                );
+            self.inner.unary(req, path, codec).await
         }
-        pub fn from_arc(inner: Arc<T>) -> Self {
-            Self {
-                inner,
-                accept_compression_encodings: Default::default(),
-                send_compression_encodings: Default::default(),
-                max_decoding_message_size: None,
-                max_encoding_message_size: None,
-            }
+        pub async fn create_bookmark(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CreateBookmarkRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CreateBookmarkResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.BookmarkService/CreateBookmark",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.BookmarkService", "CreateBookmark"),
+                );
+            self.inner.unary(req, path, codec).await
         }
-        pub fn with_interceptor<F>(
-            inner: T,
-            interceptor: F,
-        ) -> InterceptedService<Self, F>
-        where
-            F: tonic::service::Interceptor,
-        {
-            InterceptedService::new(Self::new(inner), interceptor)
+        pub async fn update_bookmark(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateBookmarkRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::UpdateBookmarkResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.BookmarkService/UpdateBookmark",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.BookmarkService", "UpdateBookmark"),
+                );
+            self.inner.unary(req, path, codec).await
         }
-        /// Enable decompressing requests with the given encoding.
-        #[must_use]
-        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.accept_compression_encodings.enable(encoding);
-            self
+        pub async fn delete_bookmark(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DeleteBookmarkRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DeleteBookmarkResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.BookmarkService/DeleteBookmark",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.BookmarkService", "DeleteBookmark"),
+                );
+            self.inner.unary(req, path, codec).await
         }
-        /// Compress responses with the given encoding, if the client supports it.
-        #[must_use]
-        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.send_compression_encodings.enable(encoding);
-            self
+        pub async fn play_bookmark(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PlayBookmarkRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::PlayBookmarkResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.BookmarkService/PlayBookmark",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.BookmarkService", "PlayBookmark"),
+                );
+            self.inner.unary(req, path, codec).await
         }
-        /// Limits the maximum size of a decoded message.
-        ///
-        /// Default: `4MB`
-        #[must_use]
-        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+        pub async fn get_track_chapters(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetTrackChaptersRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetTrackChaptersResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.BookmarkService/GetTrackChapters",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "rockbox.v1alpha1.BookmarkService",
+                        "GetTrackChapters",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_current_chapters(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetCurrentChaptersRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetCurrentChaptersResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.BookmarkService/GetCurrentChapters",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "rockbox.v1alpha1.BookmarkService",
+                        "GetCurrentChapters",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn seek_chapter(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SeekChapterRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SeekChapterResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.BookmarkService/SeekChapter",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.BookmarkService", "SeekChapter"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod bookmark_service_server {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with BookmarkServiceServer.
+    #[async_trait]
+    pub trait BookmarkService: std::marker::Send + std::marker::Sync + 'static {
+        async fn get_bookmarks(
+            &self,
+            request: tonic::Request<super::GetBookmarksRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBookmarksResponse>,
+            tonic::Status,
+        >;
+        async fn get_bookmark(
+            &self,
+            request: tonic::Request<super::GetBookmarkRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBookmarkResponse>,
+            tonic::Status,
+        >;
+        async fn create_bookmark(
+            &self,
+            request: tonic::Request<super::CreateBookmarkRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CreateBookmarkResponse>,
+            tonic::Status,
+        >;
+        async fn update_bookmark(
+            &self,
+            request: tonic::Request<super::UpdateBookmarkRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::UpdateBookmarkResponse>,
+            tonic::Status,
+        >;
+        async fn delete_bookmark(
+            &self,
+            request: tonic::Request<super::DeleteBookmarkRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DeleteBookmarkResponse>,
+            tonic::Status,
+        >;
+        async fn play_bookmark(
+            &self,
+            request: tonic::Request<super::PlayBookmarkRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::PlayBookmarkResponse>,
+            tonic::Status,
+        >;
+        async fn get_track_chapters(
+            &self,
+            request: tonic::Request<super::GetTrackChaptersRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetTrackChaptersResponse>,
+            tonic::Status,
+        >;
+        async fn get_current_chapters(
+            &self,
+            request: tonic::Request<super::GetCurrentChaptersRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetCurrentChaptersResponse>,
+            tonic::Status,
+        >;
+        async fn seek_chapter(
+            &self,
+            request: tonic::Request<super::SeekChapterRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SeekChapterResponse>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct BookmarkServiceServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> BookmarkServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
             self.max_decoding_message_size = Some(limit);
             self
         }
@@ -235,9 +614,9 @@ pub mod browse_service_server {
             self
         }
     }
-    impl<T, B> tonic::codegen::Service<http::Request<B>> for BrowseServiceServer<T>
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for BookmarkServiceServer<T>
     where
-        T: BrowseService,
+        T: BookmarkService,
         B: Body + std::marker::Send + 'static,
         B::Error: Into<StdError> + std::marker::Send + 'static,
     {
@@ -252,26 +631,25 @@ pub mod browse_service_server {
         }
         fn call(&mut self, req: http::Request<B>) -> Self::Future {
             match req.uri().path() {
-                "/rockbox.v1alpha1.BrowseService/TreeGetEntries" => {
+                "/rockbox.v1alpha1.BookmarkService/GetBookmarks" => {
                     #[allow(non_camel_case_types)]
-                    struct TreeGetEntriesSvc<T: BrowseService>(pub Arc<T>);
+                    struct GetBookmarksSvc<T: BookmarkService>(pub Arc<T>);
                     impl<
-                        T: BrowseService,
-                    > tonic::server::UnaryService<super::TreeGetEntriesRequest>
-                    for TreeGetEntriesSvc<T> {
-                        type Response = super::TreeGetEntriesResponse;
+                        T: BookmarkService,
+                    > tonic::server::UnaryService<super::GetBookmarksRequest>
+                    for GetBookmarksSvc<T> {
+                        type Response = super::GetBookmarksResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::TreeGetEntriesRequest>,
+                            request: tonic::Request<super::GetBookmarksRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as BrowseService>::tree_get_entries(&inner, request)
-                                    .await
+                                <T as BookmarkService>::get_bookmarks(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -282,7 +660,7 @@ pub mod browse_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = TreeGetEntriesSvc(inner);
+                        let method = GetBookmarksSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -298,57 +676,762 @@ pub mod browse_service_server {
                     };
                     Box::pin(fut)
                 }
-                _ => {
-                    Box::pin(async move {
-                        let mut response = http::Response::new(empty_body());
-                        let headers = response.headers_mut();
-                        headers
-                            .insert(
-                                tonic::Status::GRPC_STATUS,
-                                (tonic::Code::Unimplemented as i32).into(),
-                            );
-                        headers
-                            .insert(
-                                http::header::CONTENT_TYPE,
-                                tonic::metadata::GRPC_CONTENT_TYPE,
+                "/rockbox.v1alpha1.BookmarkService/GetBookmark" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetBookmarkSvc<T: BookmarkService>(pub Arc<T>);
+                    impl<
+                        T: BookmarkService,
+                    > tonic::server::UnaryService<super::GetBookmarkRequest>
+                    for GetBookmarkSvc<T> {
+                        type Response = super::GetBookmarkResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetBookmarkRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as BookmarkService>::get_bookmark(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetBookmarkSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
                             );
-                        Ok(response)
-                    })
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
                 }
-            }
-        }
-    }
-    impl<T> Clone for BrowseServiceServer<T> {
-        fn clone(&self) -> Self {
-            let inner = self.inner.clone();
-            Self {
-                inner,
-                accept_compression_encodings: self.accept_compression_encodings,
-                send_compression_encodings: self.send_compression_encodings,
-                max_decoding_message_size: self.max_decoding_message_size,
-                max_encoding_message_size: self.max_encoding_message_size,
-            }
-        }
-    }
-    /// Generated gRPC service name
-    pub const SERVICE_NAME: &str = "rockbox.v1alpha1.BrowseService";
-    impl<T> tonic::server::NamedService for BrowseServiceServer<T> {
-        const NAME: &'static str = SERVICE_NAME;
-    }
-}
-#[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct GetDevicesRequest {}
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetDevicesResponse {
-    #[prost(message, repeated, tag = "1")]
-    pub devices: ::prost::alloc::vec::Vec<Device>,
-}
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetDeviceRequest {
-    #[prost(string, tag = "1")]
-    pub id: ::prost::alloc::string::String,
-}
-#[derive(Clone, PartialEq, ::prost::Message)]
+                "/rockbox.v1alpha1.BookmarkService/CreateBookmark" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateBookmarkSvc<T: BookmarkService>(pub Arc<T>);
+                    impl<
+                        T: BookmarkService,
+                    > tonic::server::UnaryService<super::CreateBookmarkRequest>
+                    for CreateBookmarkSvc<T> {
+                        type Response = super::CreateBookmarkResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateBookmarkRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as BookmarkService>::create_bookmark(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CreateBookmarkSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.BookmarkService/UpdateBookmark" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateBookmarkSvc<T: BookmarkService>(pub Arc<T>);
+                    impl<
+                        T: BookmarkService,
+                    > tonic::server::UnaryService<super::UpdateBookmarkRequest>
+                    for UpdateBookmarkSvc<T> {
+                        type Response = super::UpdateBookmarkResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateBookmarkRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as BookmarkService>::update_bookmark(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = UpdateBookmarkSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.BookmarkService/DeleteBookmark" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteBookmarkSvc<T: BookmarkService>(pub Arc<T>);
+                    impl<
+                        T: BookmarkService,
+                    > tonic::server::UnaryService<super::DeleteBookmarkRequest>
+                    for DeleteBookmarkSvc<T> {
+                        type Response = super::DeleteBookmarkResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteBookmarkRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as BookmarkService>::delete_bookmark(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = DeleteBookmarkSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.BookmarkService/PlayBookmark" => {
+                    #[allow(non_camel_case_types)]
+                    struct PlayBookmarkSvc<T: BookmarkService>(pub Arc<T>);
+                    impl<
+                        T: BookmarkService,
+                    > tonic::server::UnaryService<super::PlayBookmarkRequest>
+                    for PlayBookmarkSvc<T> {
+                        type Response = super::PlayBookmarkResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PlayBookmarkRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as BookmarkService>::play_bookmark(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = PlayBookmarkSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.BookmarkService/GetTrackChapters" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetTrackChaptersSvc<T: BookmarkService>(pub Arc<T>);
+                    impl<
+                        T: BookmarkService,
+                    > tonic::server::UnaryService<super::GetTrackChaptersRequest>
+                    for GetTrackChaptersSvc<T> {
+                        type Response = super::GetTrackChaptersResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetTrackChaptersRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as BookmarkService>::get_track_chapters(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetTrackChaptersSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.BookmarkService/GetCurrentChapters" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetCurrentChaptersSvc<T: BookmarkService>(pub Arc<T>);
+                    impl<
+                        T: BookmarkService,
+                    > tonic::server::UnaryService<super::GetCurrentChaptersRequest>
+                    for GetCurrentChaptersSvc<T> {
+                        type Response = super::GetCurrentChaptersResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetCurrentChaptersRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as BookmarkService>::get_current_chapters(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetCurrentChaptersSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.BookmarkService/SeekChapter" => {
+                    #[allow(non_camel_case_types)]
+                    struct SeekChapterSvc<T: BookmarkService>(pub Arc<T>);
+                    impl<
+                        T: BookmarkService,
+                    > tonic::server::UnaryService<super::SeekChapterRequest>
+                    for SeekChapterSvc<T> {
+                        type Response = super::SeekChapterResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SeekChapterRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as BookmarkService>::seek_chapter(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SeekChapterSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        let mut response = http::Response::new(empty_body());
+                        let headers = response.headers_mut();
+                        headers
+                            .insert(
+                                tonic::Status::GRPC_STATUS,
+                                (tonic::Code::Unimplemented as i32).into(),
+                            );
+                        headers
+                            .insert(
+                                http::header::CONTENT_TYPE,
+                                tonic::metadata::GRPC_CONTENT_TYPE,
+                            );
+                        Ok(response)
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for BookmarkServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "rockbox.v1alpha1.BookmarkService";
+    impl<T> tonic::server::NamedService for BookmarkServiceServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct RockboxBrowseRequest {}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct RockboxBrowseResponse {}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct TreeGetContextRequest {}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct TreeGetContextResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TreeGetEntriesRequest {
+    #[prost(string, optional, tag = "1")]
+    pub path: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Entry {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(int32, tag = "2")]
+    pub attr: i32,
+    #[prost(uint32, tag = "3")]
+    pub time_write: u32,
+    #[prost(int32, tag = "4")]
+    pub customaction: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TreeGetEntriesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub entries: ::prost::alloc::vec::Vec<Entry>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct TreeGetEntryAtRequest {}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct TreeGetEntryAtResponse {}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct BrowseId3Request {}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct BrowseId3Response {}
+/// Generated client implementations.
+pub mod browse_service_client {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    #[derive(Debug, Clone)]
+    pub struct BrowseServiceClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl BrowseServiceClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> BrowseServiceClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> BrowseServiceClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+            >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
+        {
+            BrowseServiceClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        pub async fn tree_get_entries(
+            &mut self,
+            request: impl tonic::IntoRequest<super::TreeGetEntriesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::TreeGetEntriesResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.BrowseService/TreeGetEntries",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.BrowseService", "TreeGetEntries"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod browse_service_server {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with BrowseServiceServer.
+    #[async_trait]
+    pub trait BrowseService: std::marker::Send + std::marker::Sync + 'static {
+        async fn tree_get_entries(
+            &self,
+            request: tonic::Request<super::TreeGetEntriesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::TreeGetEntriesResponse>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct BrowseServiceServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> BrowseServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for BrowseServiceServer<T>
+    where
+        T: BrowseService,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/rockbox.v1alpha1.BrowseService/TreeGetEntries" => {
+                    #[allow(non_camel_case_types)]
+                    struct TreeGetEntriesSvc<T: BrowseService>(pub Arc<T>);
+                    impl<
+                        T: BrowseService,
+                    > tonic::server::UnaryService<super::TreeGetEntriesRequest>
+                    for TreeGetEntriesSvc<T> {
+                        type Response = super::TreeGetEntriesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::TreeGetEntriesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as BrowseService>::tree_get_entries(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = TreeGetEntriesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        let mut response = http::Response::new(empty_body());
+                        let headers = response.headers_mut();
+                        headers
+                            .insert(
+                                tonic::Status::GRPC_STATUS,
+                                (tonic::Code::Unimplemented as i32).into(),
+                            );
+                        headers
+                            .insert(
+                                http::header::CONTENT_TYPE,
+                                tonic::metadata::GRPC_CONTENT_TYPE,
+                            );
+                        Ok(response)
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for BrowseServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "rockbox.v1alpha1.BrowseService";
+    impl<T> tonic::server::NamedService for BrowseServiceServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct GetDevicesRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetDevicesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub devices: ::prost::alloc::vec::Vec<Device>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetDeviceRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetDeviceResponse {
     #[prost(message, optional, tag = "1")]
     pub device: ::core::option::Option<Device>,
@@ -978,6 +2061,42 @@ pub struct Track {
     pub created_at: ::prost::alloc::string::String,
     #[prost(string, tag = "23")]
     pub updated_at: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "24")]
+    pub musicbrainz_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(float, optional, tag = "25")]
+    pub replaygain_track_gain: ::core::option::Option<f32>,
+    #[prost(float, optional, tag = "26")]
+    pub replaygain_track_peak: ::core::option::Option<f32>,
+    #[prost(float, optional, tag = "27")]
+    pub replaygain_album_gain: ::core::option::Option<f32>,
+    #[prost(float, optional, tag = "28")]
+    pub replaygain_album_peak: ::core::option::Option<f32>,
+    #[prost(float, optional, tag = "29")]
+    pub bpm: ::core::option::Option<f32>,
+    #[prost(uint32, optional, tag = "30")]
+    pub rating: ::core::option::Option<u32>,
+    #[prost(string, optional, tag = "31")]
+    pub comment: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "32")]
+    pub label: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "33")]
+    pub isrc: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "34")]
+    pub original_date: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "35")]
+    pub title_sort: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "36")]
+    pub artist_sort: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "37")]
+    pub album_sort: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "38")]
+    pub album_artist_sort: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "39")]
+    pub composer_sort: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "40")]
+    pub lyrics: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint32, tag = "41")]
+    pub play_count: u32,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Artist {
@@ -993,6 +2112,10 @@ pub struct Artist {
     pub albums: ::prost::alloc::vec::Vec<Album>,
     #[prost(message, repeated, tag = "6")]
     pub tracks: ::prost::alloc::vec::Vec<Track>,
+    #[prost(message, repeated, tag = "7")]
+    pub appears_on: ::prost::alloc::vec::Vec<Album>,
+    #[prost(string, optional, tag = "8")]
+    pub musicbrainz_id: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Album {
@@ -1014,6 +2137,27 @@ pub struct Album {
     pub artist_id: ::prost::alloc::string::String,
     #[prost(message, repeated, tag = "9")]
     pub tracks: ::prost::alloc::vec::Vec<Track>,
+    #[prost(string, optional, tag = "10")]
+    pub musicbrainz_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "11")]
+    pub musicbrainz_release_group_id: ::core::option::Option<
+        ::prost::alloc::string::String,
+    >,
+    #[prost(message, repeated, tag = "12")]
+    pub editions: ::prost::alloc::vec::Vec<Album>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Genre {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "3")]
+    pub description: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "4")]
+    pub image: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, repeated, tag = "5")]
+    pub tracks: ::prost::alloc::vec::Vec<Track>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetAlbumRequest {
@@ -1045,26 +2189,81 @@ pub struct GetTrackResponse {
     #[prost(message, optional, tag = "1")]
     pub track: ::core::option::Option<Track>,
 }
-#[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct GetAlbumsRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListOptions {
+    #[prost(uint32, optional, tag = "1")]
+    pub offset: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag = "2")]
+    pub limit: ::core::option::Option<u32>,
+    #[prost(string, optional, tag = "3")]
+    pub sort: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bool, optional, tag = "4")]
+    pub descending: ::core::option::Option<bool>,
+    #[prost(string, optional, tag = "5")]
+    pub artist_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "6")]
+    pub album_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "7")]
+    pub genre_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint32, optional, tag = "8")]
+    pub year_from: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag = "9")]
+    pub year_to: ::core::option::Option<u32>,
+    #[prost(bool, optional, tag = "10")]
+    pub group_editions: ::core::option::Option<bool>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAlbumsRequest {
+    #[prost(message, optional, tag = "1")]
+    pub options: ::core::option::Option<ListOptions>,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetAlbumsResponse {
     #[prost(message, repeated, tag = "1")]
     pub albums: ::prost::alloc::vec::Vec<Album>,
+    #[prost(int64, tag = "2")]
+    pub total: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetArtistsRequest {
+    #[prost(message, optional, tag = "1")]
+    pub options: ::core::option::Option<ListOptions>,
 }
-#[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct GetArtistsRequest {}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetArtistsResponse {
     #[prost(message, repeated, tag = "1")]
     pub artists: ::prost::alloc::vec::Vec<Artist>,
+    #[prost(int64, tag = "2")]
+    pub total: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetGenreRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetGenreResponse {
+    #[prost(message, optional, tag = "1")]
+    pub genre: ::core::option::Option<Genre>,
 }
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct GetTracksRequest {}
+pub struct GetGenresRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetGenresResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub genres: ::prost::alloc::vec::Vec<Genre>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetTracksRequest {
+    #[prost(message, optional, tag = "1")]
+    pub options: ::core::option::Option<ListOptions>,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetTracksResponse {
     #[prost(message, repeated, tag = "1")]
     pub tracks: ::prost::alloc::vec::Vec<Track>,
+    #[prost(int64, tag = "2")]
+    pub total: i64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct LikeTrackRequest {
@@ -1119,6 +2318,30 @@ pub struct ScanLibraryResponse {}
 pub struct SearchRequest {
     #[prost(string, tag = "1")]
     pub term: ::prost::alloc::string::String,
+    #[prost(int32, optional, tag = "2")]
+    pub offset: ::core::option::Option<i32>,
+    #[prost(int32, optional, tag = "3")]
+    pub limit: ::core::option::Option<i32>,
+    #[prost(string, optional, tag = "4")]
+    pub sort: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bool, optional, tag = "5")]
+    pub descending: ::core::option::Option<bool>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetCount {
+    #[prost(string, tag = "1")]
+    pub value: ::prost::alloc::string::String,
+    #[prost(int32, tag = "2")]
+    pub count: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchFacets {
+    #[prost(message, repeated, tag = "1")]
+    pub genres: ::prost::alloc::vec::Vec<FacetCount>,
+    #[prost(message, repeated, tag = "2")]
+    pub decades: ::prost::alloc::vec::Vec<FacetCount>,
+    #[prost(message, repeated, tag = "3")]
+    pub formats: ::prost::alloc::vec::Vec<FacetCount>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SearchResponse {
@@ -1128,6 +2351,88 @@ pub struct SearchResponse {
     pub albums: ::prost::alloc::vec::Vec<Album>,
     #[prost(message, repeated, tag = "3")]
     pub artists: ::prost::alloc::vec::Vec<Artist>,
+    #[prost(int32, tag = "4")]
+    pub total_tracks: i32,
+    #[prost(int32, tag = "5")]
+    pub total_albums: i32,
+    #[prost(int32, tag = "6")]
+    pub total_artists: i32,
+    #[prost(message, optional, tag = "7")]
+    pub facets: ::core::option::Option<SearchFacets>,
+    #[prost(message, repeated, tag = "8")]
+    pub genres: ::prost::alloc::vec::Vec<Genre>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AutocompleteRequest {
+    #[prost(string, tag = "1")]
+    pub text: ::prost::alloc::string::String,
+    #[prost(int32, optional, tag = "2")]
+    pub limit: ::core::option::Option<i32>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Suggestion {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub kind: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub label: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "4")]
+    pub artist: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "5")]
+    pub cover: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(int64, tag = "6")]
+    pub popularity: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AutocompleteResponse {
+    #[prost(string, tag = "1")]
+    pub text: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    pub suggestions: ::prost::alloc::vec::Vec<Suggestion>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LyricLine {
+    #[prost(uint64, tag = "1")]
+    pub time: u64,
+    #[prost(string, tag = "2")]
+    pub text: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Lyrics {
+    #[prost(string, tag = "1")]
+    pub track_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub text: ::prost::alloc::string::String,
+    #[prost(bool, tag = "3")]
+    pub synced: bool,
+    #[prost(message, repeated, tag = "4")]
+    pub lines: ::prost::alloc::vec::Vec<LyricLine>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetLyricsRequest {
+    #[prost(string, tag = "1")]
+    pub track_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetLyricsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub lyrics: ::core::option::Option<Lyrics>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct StreamLyricsRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CurrentLyricLineResponse {
+    #[prost(string, tag = "1")]
+    pub track_id: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub index: u32,
+    #[prost(uint64, tag = "3")]
+    pub time: u64,
+    #[prost(string, tag = "4")]
+    pub text: ::prost::alloc::string::String,
+    #[prost(uint64, optional, tag = "5")]
+    pub next_time: ::core::option::Option<u64>,
 }
 /// Generated client implementations.
 pub mod library_service_client {
@@ -1366,6 +2671,54 @@ pub mod library_service_client {
                 .insert(GrpcMethod::new("rockbox.v1alpha1.LibraryService", "GetTrack"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn get_genres(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetGenresRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetGenresResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.LibraryService/GetGenres",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("rockbox.v1alpha1.LibraryService", "GetGenres"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_genre(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetGenreRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetGenreResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.LibraryService/GetGenre",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("rockbox.v1alpha1.LibraryService", "GetGenre"));
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn like_track(
             &mut self,
             request: impl tonic::IntoRequest<super::LikeTrackRequest>,
@@ -1492,11 +2845,86 @@ pub mod library_service_client {
                 );
             self.inner.unary(req, path, codec).await
         }
-        pub async fn get_liked_albums(
+        pub async fn get_liked_albums(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetLikedAlbumsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetLikedAlbumsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.LibraryService/GetLikedAlbums",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.LibraryService", "GetLikedAlbums"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn scan_library(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ScanLibraryRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ScanLibraryResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.LibraryService/ScanLibrary",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.LibraryService", "ScanLibrary"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn search(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SearchRequest>,
+        ) -> std::result::Result<tonic::Response<super::SearchResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.LibraryService/Search",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("rockbox.v1alpha1.LibraryService", "Search"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn autocomplete(
             &mut self,
-            request: impl tonic::IntoRequest<super::GetLikedAlbumsRequest>,
+            request: impl tonic::IntoStreamingRequest<
+                Message = super::AutocompleteRequest,
+            >,
         ) -> std::result::Result<
-            tonic::Response<super::GetLikedAlbumsResponse>,
+            tonic::Response<tonic::codec::Streaming<super::AutocompleteResponse>>,
             tonic::Status,
         > {
             self.inner
@@ -1509,20 +2937,20 @@ pub mod library_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/rockbox.v1alpha1.LibraryService/GetLikedAlbums",
+                "/rockbox.v1alpha1.LibraryService/Autocomplete",
             );
-            let mut req = request.into_request();
+            let mut req = request.into_streaming_request();
             req.extensions_mut()
                 .insert(
-                    GrpcMethod::new("rockbox.v1alpha1.LibraryService", "GetLikedAlbums"),
+                    GrpcMethod::new("rockbox.v1alpha1.LibraryService", "Autocomplete"),
                 );
-            self.inner.unary(req, path, codec).await
+            self.inner.streaming(req, path, codec).await
         }
-        pub async fn scan_library(
+        pub async fn get_lyrics(
             &mut self,
-            request: impl tonic::IntoRequest<super::ScanLibraryRequest>,
+            request: impl tonic::IntoRequest<super::GetLyricsRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::ScanLibraryResponse>,
+            tonic::Response<super::GetLyricsResponse>,
             tonic::Status,
         > {
             self.inner
@@ -1535,19 +2963,20 @@ pub mod library_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/rockbox.v1alpha1.LibraryService/ScanLibrary",
+                "/rockbox.v1alpha1.LibraryService/GetLyrics",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(
-                    GrpcMethod::new("rockbox.v1alpha1.LibraryService", "ScanLibrary"),
-                );
+                .insert(GrpcMethod::new("rockbox.v1alpha1.LibraryService", "GetLyrics"));
             self.inner.unary(req, path, codec).await
         }
-        pub async fn search(
+        pub async fn stream_lyrics(
             &mut self,
-            request: impl tonic::IntoRequest<super::SearchRequest>,
-        ) -> std::result::Result<tonic::Response<super::SearchResponse>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::StreamLyricsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::CurrentLyricLineResponse>>,
+            tonic::Status,
+        > {
             self.inner
                 .ready()
                 .await
@@ -1558,12 +2987,14 @@ pub mod library_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/rockbox.v1alpha1.LibraryService/Search",
+                "/rockbox.v1alpha1.LibraryService/StreamLyrics",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("rockbox.v1alpha1.LibraryService", "Search"));
-            self.inner.unary(req, path, codec).await
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.LibraryService", "StreamLyrics"),
+                );
+            self.inner.server_streaming(req, path, codec).await
         }
     }
 }
@@ -1622,6 +3053,20 @@ pub mod library_service_server {
             tonic::Response<super::GetTrackResponse>,
             tonic::Status,
         >;
+        async fn get_genres(
+            &self,
+            request: tonic::Request<super::GetGenresRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetGenresResponse>,
+            tonic::Status,
+        >;
+        async fn get_genre(
+            &self,
+            request: tonic::Request<super::GetGenreRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetGenreResponse>,
+            tonic::Status,
+        >;
         async fn like_track(
             &self,
             request: tonic::Request<super::LikeTrackRequest>,
@@ -1675,6 +3120,42 @@ pub mod library_service_server {
             &self,
             request: tonic::Request<super::SearchRequest>,
         ) -> std::result::Result<tonic::Response<super::SearchResponse>, tonic::Status>;
+        /// Server streaming response type for the Autocomplete method.
+        type AutocompleteStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::AutocompleteResponse, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn autocomplete(
+            &self,
+            request: tonic::Request<tonic::Streaming<super::AutocompleteRequest>>,
+        ) -> std::result::Result<
+            tonic::Response<Self::AutocompleteStream>,
+            tonic::Status,
+        >;
+        async fn get_lyrics(
+            &self,
+            request: tonic::Request<super::GetLyricsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetLyricsResponse>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the StreamLyrics method.
+        type StreamLyricsStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<
+                    super::CurrentLyricLineResponse,
+                    tonic::Status,
+                >,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn stream_lyrics(
+            &self,
+            request: tonic::Request<super::StreamLyricsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::StreamLyricsStream>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct LibraryServiceServer<T> {
@@ -2022,6 +3503,96 @@ pub mod library_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/rockbox.v1alpha1.LibraryService/GetGenres" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetGenresSvc<T: LibraryService>(pub Arc<T>);
+                    impl<
+                        T: LibraryService,
+                    > tonic::server::UnaryService<super::GetGenresRequest>
+                    for GetGenresSvc<T> {
+                        type Response = super::GetGenresResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetGenresRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as LibraryService>::get_genres(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetGenresSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.LibraryService/GetGenre" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetGenreSvc<T: LibraryService>(pub Arc<T>);
+                    impl<
+                        T: LibraryService,
+                    > tonic::server::UnaryService<super::GetGenreRequest>
+                    for GetGenreSvc<T> {
+                        type Response = super::GetGenreResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetGenreRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as LibraryService>::get_genre(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetGenreSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/rockbox.v1alpha1.LibraryService/LikeTrack" => {
                     #[allow(non_camel_case_types)]
                     struct LikeTrackSvc<T: LibraryService>(pub Arc<T>);
@@ -2384,6 +3955,145 @@ pub mod library_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/rockbox.v1alpha1.LibraryService/Autocomplete" => {
+                    #[allow(non_camel_case_types)]
+                    struct AutocompleteSvc<T: LibraryService>(pub Arc<T>);
+                    impl<
+                        T: LibraryService,
+                    > tonic::server::StreamingService<super::AutocompleteRequest>
+                    for AutocompleteSvc<T> {
+                        type Response = super::AutocompleteResponse;
+                        type ResponseStream = T::AutocompleteStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                tonic::Streaming<super::AutocompleteRequest>,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as LibraryService>::autocomplete(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = AutocompleteSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.LibraryService/GetLyrics" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetLyricsSvc<T: LibraryService>(pub Arc<T>);
+                    impl<
+                        T: LibraryService,
+                    > tonic::server::UnaryService<super::GetLyricsRequest>
+                    for GetLyricsSvc<T> {
+                        type Response = super::GetLyricsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetLyricsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as LibraryService>::get_lyrics(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetLyricsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.LibraryService/StreamLyrics" => {
+                    #[allow(non_camel_case_types)]
+                    struct StreamLyricsSvc<T: LibraryService>(pub Arc<T>);
+                    impl<
+                        T: LibraryService,
+                    > tonic::server::ServerStreamingService<super::StreamLyricsRequest>
+                    for StreamLyricsSvc<T> {
+                        type Response = super::CurrentLyricLineResponse;
+                        type ResponseStream = T::StreamLyricsStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::StreamLyricsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as LibraryService>::stream_lyrics(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = StreamLyricsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         let mut response = http::Response::new(empty_body());
@@ -5923,23 +7633,298 @@ pub mod playlist_service_server {
             match req.uri().path() {
                 "/rockbox.v1alpha1.PlaylistService/GetCurrent" => {
                     #[allow(non_camel_case_types)]
-                    struct GetCurrentSvc<T: PlaylistService>(pub Arc<T>);
+                    struct GetCurrentSvc<T: PlaylistService>(pub Arc<T>);
+                    impl<
+                        T: PlaylistService,
+                    > tonic::server::UnaryService<super::GetCurrentRequest>
+                    for GetCurrentSvc<T> {
+                        type Response = super::GetCurrentResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetCurrentRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PlaylistService>::get_current(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetCurrentSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.PlaylistService/GetResumeInfo" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetResumeInfoSvc<T: PlaylistService>(pub Arc<T>);
+                    impl<
+                        T: PlaylistService,
+                    > tonic::server::UnaryService<super::GetResumeInfoRequest>
+                    for GetResumeInfoSvc<T> {
+                        type Response = super::GetResumeInfoResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetResumeInfoRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PlaylistService>::get_resume_info(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetResumeInfoSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.PlaylistService/GetTrackInfo" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetTrackInfoSvc<T: PlaylistService>(pub Arc<T>);
+                    impl<
+                        T: PlaylistService,
+                    > tonic::server::UnaryService<super::GetTrackInfoRequest>
+                    for GetTrackInfoSvc<T> {
+                        type Response = super::GetTrackInfoResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetTrackInfoRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PlaylistService>::get_track_info(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetTrackInfoSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.PlaylistService/GetFirstIndex" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetFirstIndexSvc<T: PlaylistService>(pub Arc<T>);
+                    impl<
+                        T: PlaylistService,
+                    > tonic::server::UnaryService<super::GetFirstIndexRequest>
+                    for GetFirstIndexSvc<T> {
+                        type Response = super::GetFirstIndexResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetFirstIndexRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PlaylistService>::get_first_index(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetFirstIndexSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.PlaylistService/GetDisplayIndex" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetDisplayIndexSvc<T: PlaylistService>(pub Arc<T>);
+                    impl<
+                        T: PlaylistService,
+                    > tonic::server::UnaryService<super::GetDisplayIndexRequest>
+                    for GetDisplayIndexSvc<T> {
+                        type Response = super::GetDisplayIndexResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetDisplayIndexRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PlaylistService>::get_display_index(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetDisplayIndexSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.PlaylistService/Amount" => {
+                    #[allow(non_camel_case_types)]
+                    struct AmountSvc<T: PlaylistService>(pub Arc<T>);
+                    impl<
+                        T: PlaylistService,
+                    > tonic::server::UnaryService<super::AmountRequest>
+                    for AmountSvc<T> {
+                        type Response = super::AmountResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AmountRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PlaylistService>::amount(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = AmountSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.PlaylistService/PlaylistResume" => {
+                    #[allow(non_camel_case_types)]
+                    struct PlaylistResumeSvc<T: PlaylistService>(pub Arc<T>);
                     impl<
                         T: PlaylistService,
-                    > tonic::server::UnaryService<super::GetCurrentRequest>
-                    for GetCurrentSvc<T> {
-                        type Response = super::GetCurrentResponse;
+                    > tonic::server::UnaryService<super::PlaylistResumeRequest>
+                    for PlaylistResumeSvc<T> {
+                        type Response = super::PlaylistResumeResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetCurrentRequest>,
+                            request: tonic::Request<super::PlaylistResumeRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as PlaylistService>::get_current(&inner, request).await
+                                <T as PlaylistService>::playlist_resume(&inner, request)
+                                    .await
                             };
                             Box::pin(fut)
                         }
@@ -5950,7 +7935,7 @@ pub mod playlist_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = GetCurrentSvc(inner);
+                        let method = PlaylistResumeSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -5966,26 +7951,25 @@ pub mod playlist_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.PlaylistService/GetResumeInfo" => {
+                "/rockbox.v1alpha1.PlaylistService/ResumeTrack" => {
                     #[allow(non_camel_case_types)]
-                    struct GetResumeInfoSvc<T: PlaylistService>(pub Arc<T>);
+                    struct ResumeTrackSvc<T: PlaylistService>(pub Arc<T>);
                     impl<
                         T: PlaylistService,
-                    > tonic::server::UnaryService<super::GetResumeInfoRequest>
-                    for GetResumeInfoSvc<T> {
-                        type Response = super::GetResumeInfoResponse;
+                    > tonic::server::UnaryService<super::ResumeTrackRequest>
+                    for ResumeTrackSvc<T> {
+                        type Response = super::ResumeTrackResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetResumeInfoRequest>,
+                            request: tonic::Request<super::ResumeTrackRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as PlaylistService>::get_resume_info(&inner, request)
-                                    .await
+                                <T as PlaylistService>::resume_track(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -5996,7 +7980,7 @@ pub mod playlist_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = GetResumeInfoSvc(inner);
+                        let method = ResumeTrackSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6012,26 +7996,25 @@ pub mod playlist_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.PlaylistService/GetTrackInfo" => {
+                "/rockbox.v1alpha1.PlaylistService/SetModified" => {
                     #[allow(non_camel_case_types)]
-                    struct GetTrackInfoSvc<T: PlaylistService>(pub Arc<T>);
+                    struct SetModifiedSvc<T: PlaylistService>(pub Arc<T>);
                     impl<
                         T: PlaylistService,
-                    > tonic::server::UnaryService<super::GetTrackInfoRequest>
-                    for GetTrackInfoSvc<T> {
-                        type Response = super::GetTrackInfoResponse;
+                    > tonic::server::UnaryService<super::SetModifiedRequest>
+                    for SetModifiedSvc<T> {
+                        type Response = super::SetModifiedResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetTrackInfoRequest>,
+                            request: tonic::Request<super::SetModifiedRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as PlaylistService>::get_track_info(&inner, request)
-                                    .await
+                                <T as PlaylistService>::set_modified(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -6042,7 +8025,7 @@ pub mod playlist_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = GetTrackInfoSvc(inner);
+                        let method = SetModifiedSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6058,25 +8041,113 @@ pub mod playlist_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.PlaylistService/GetFirstIndex" => {
+                "/rockbox.v1alpha1.PlaylistService/Start" => {
                     #[allow(non_camel_case_types)]
-                    struct GetFirstIndexSvc<T: PlaylistService>(pub Arc<T>);
+                    struct StartSvc<T: PlaylistService>(pub Arc<T>);
                     impl<
                         T: PlaylistService,
-                    > tonic::server::UnaryService<super::GetFirstIndexRequest>
-                    for GetFirstIndexSvc<T> {
-                        type Response = super::GetFirstIndexResponse;
+                    > tonic::server::UnaryService<super::StartRequest> for StartSvc<T> {
+                        type Response = super::StartResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetFirstIndexRequest>,
+                            request: tonic::Request<super::StartRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as PlaylistService>::get_first_index(&inner, request)
+                                <T as PlaylistService>::start(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = StartSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.PlaylistService/Sync" => {
+                    #[allow(non_camel_case_types)]
+                    struct SyncSvc<T: PlaylistService>(pub Arc<T>);
+                    impl<
+                        T: PlaylistService,
+                    > tonic::server::UnaryService<super::SyncRequest> for SyncSvc<T> {
+                        type Response = super::SyncResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SyncRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PlaylistService>::sync(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SyncSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.PlaylistService/RemoveAllTracks" => {
+                    #[allow(non_camel_case_types)]
+                    struct RemoveAllTracksSvc<T: PlaylistService>(pub Arc<T>);
+                    impl<
+                        T: PlaylistService,
+                    > tonic::server::UnaryService<super::RemoveAllTracksRequest>
+                    for RemoveAllTracksSvc<T> {
+                        type Response = super::RemoveAllTracksResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RemoveAllTracksRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PlaylistService>::remove_all_tracks(&inner, request)
                                     .await
                             };
                             Box::pin(fut)
@@ -6088,7 +8159,7 @@ pub mod playlist_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = GetFirstIndexSvc(inner);
+                        let method = RemoveAllTracksSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6104,25 +8175,70 @@ pub mod playlist_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.PlaylistService/GetDisplayIndex" => {
+                "/rockbox.v1alpha1.PlaylistService/RemoveTracks" => {
                     #[allow(non_camel_case_types)]
-                    struct GetDisplayIndexSvc<T: PlaylistService>(pub Arc<T>);
+                    struct RemoveTracksSvc<T: PlaylistService>(pub Arc<T>);
                     impl<
                         T: PlaylistService,
-                    > tonic::server::UnaryService<super::GetDisplayIndexRequest>
-                    for GetDisplayIndexSvc<T> {
-                        type Response = super::GetDisplayIndexResponse;
+                    > tonic::server::UnaryService<super::RemoveTracksRequest>
+                    for RemoveTracksSvc<T> {
+                        type Response = super::RemoveTracksResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetDisplayIndexRequest>,
+                            request: tonic::Request<super::RemoveTracksRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as PlaylistService>::get_display_index(&inner, request)
+                                <T as PlaylistService>::remove_tracks(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = RemoveTracksSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.PlaylistService/CreatePlaylist" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreatePlaylistSvc<T: PlaylistService>(pub Arc<T>);
+                    impl<
+                        T: PlaylistService,
+                    > tonic::server::UnaryService<super::CreatePlaylistRequest>
+                    for CreatePlaylistSvc<T> {
+                        type Response = super::CreatePlaylistResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreatePlaylistRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PlaylistService>::create_playlist(&inner, request)
                                     .await
                             };
                             Box::pin(fut)
@@ -6134,7 +8250,52 @@ pub mod playlist_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = GetDisplayIndexSvc(inner);
+                        let method = CreatePlaylistSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.PlaylistService/InsertTracks" => {
+                    #[allow(non_camel_case_types)]
+                    struct InsertTracksSvc<T: PlaylistService>(pub Arc<T>);
+                    impl<
+                        T: PlaylistService,
+                    > tonic::server::UnaryService<super::InsertTracksRequest>
+                    for InsertTracksSvc<T> {
+                        type Response = super::InsertTracksResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::InsertTracksRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PlaylistService>::insert_tracks(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = InsertTracksSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6150,25 +8311,26 @@ pub mod playlist_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.PlaylistService/Amount" => {
+                "/rockbox.v1alpha1.PlaylistService/InsertDirectory" => {
                     #[allow(non_camel_case_types)]
-                    struct AmountSvc<T: PlaylistService>(pub Arc<T>);
+                    struct InsertDirectorySvc<T: PlaylistService>(pub Arc<T>);
                     impl<
                         T: PlaylistService,
-                    > tonic::server::UnaryService<super::AmountRequest>
-                    for AmountSvc<T> {
-                        type Response = super::AmountResponse;
+                    > tonic::server::UnaryService<super::InsertDirectoryRequest>
+                    for InsertDirectorySvc<T> {
+                        type Response = super::InsertDirectoryResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::AmountRequest>,
+                            request: tonic::Request<super::InsertDirectoryRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as PlaylistService>::amount(&inner, request).await
+                                <T as PlaylistService>::insert_directory(&inner, request)
+                                    .await
                             };
                             Box::pin(fut)
                         }
@@ -6179,7 +8341,7 @@ pub mod playlist_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = AmountSvc(inner);
+                        let method = InsertDirectorySvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6195,25 +8357,25 @@ pub mod playlist_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.PlaylistService/PlaylistResume" => {
+                "/rockbox.v1alpha1.PlaylistService/InsertPlaylist" => {
                     #[allow(non_camel_case_types)]
-                    struct PlaylistResumeSvc<T: PlaylistService>(pub Arc<T>);
+                    struct InsertPlaylistSvc<T: PlaylistService>(pub Arc<T>);
                     impl<
                         T: PlaylistService,
-                    > tonic::server::UnaryService<super::PlaylistResumeRequest>
-                    for PlaylistResumeSvc<T> {
-                        type Response = super::PlaylistResumeResponse;
+                    > tonic::server::UnaryService<super::InsertPlaylistRequest>
+                    for InsertPlaylistSvc<T> {
+                        type Response = super::InsertPlaylistResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::PlaylistResumeRequest>,
+                            request: tonic::Request<super::InsertPlaylistRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as PlaylistService>::playlist_resume(&inner, request)
+                                <T as PlaylistService>::insert_playlist(&inner, request)
                                     .await
                             };
                             Box::pin(fut)
@@ -6225,7 +8387,7 @@ pub mod playlist_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = PlaylistResumeSvc(inner);
+                        let method = InsertPlaylistSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6241,25 +8403,25 @@ pub mod playlist_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.PlaylistService/ResumeTrack" => {
+                "/rockbox.v1alpha1.PlaylistService/InsertAlbum" => {
                     #[allow(non_camel_case_types)]
-                    struct ResumeTrackSvc<T: PlaylistService>(pub Arc<T>);
+                    struct InsertAlbumSvc<T: PlaylistService>(pub Arc<T>);
                     impl<
                         T: PlaylistService,
-                    > tonic::server::UnaryService<super::ResumeTrackRequest>
-                    for ResumeTrackSvc<T> {
-                        type Response = super::ResumeTrackResponse;
+                    > tonic::server::UnaryService<super::InsertAlbumRequest>
+                    for InsertAlbumSvc<T> {
+                        type Response = super::InsertAlbumResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ResumeTrackRequest>,
+                            request: tonic::Request<super::InsertAlbumRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as PlaylistService>::resume_track(&inner, request).await
+                                <T as PlaylistService>::insert_album(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -6270,7 +8432,7 @@ pub mod playlist_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = ResumeTrackSvc(inner);
+                        let method = InsertAlbumSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6286,25 +8448,29 @@ pub mod playlist_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.PlaylistService/SetModified" => {
+                "/rockbox.v1alpha1.PlaylistService/InsertArtistTracks" => {
                     #[allow(non_camel_case_types)]
-                    struct SetModifiedSvc<T: PlaylistService>(pub Arc<T>);
+                    struct InsertArtistTracksSvc<T: PlaylistService>(pub Arc<T>);
                     impl<
                         T: PlaylistService,
-                    > tonic::server::UnaryService<super::SetModifiedRequest>
-                    for SetModifiedSvc<T> {
-                        type Response = super::SetModifiedResponse;
+                    > tonic::server::UnaryService<super::InsertArtistTracksRequest>
+                    for InsertArtistTracksSvc<T> {
+                        type Response = super::InsertArtistTracksResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SetModifiedRequest>,
+                            request: tonic::Request<super::InsertArtistTracksRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as PlaylistService>::set_modified(&inner, request).await
+                                <T as PlaylistService>::insert_artist_tracks(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
                             };
                             Box::pin(fut)
                         }
@@ -6315,7 +8481,7 @@ pub mod playlist_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = SetModifiedSvc(inner);
+                        let method = InsertArtistTracksSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6331,24 +8497,26 @@ pub mod playlist_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.PlaylistService/Start" => {
+                "/rockbox.v1alpha1.PlaylistService/ShufflePlaylist" => {
                     #[allow(non_camel_case_types)]
-                    struct StartSvc<T: PlaylistService>(pub Arc<T>);
+                    struct ShufflePlaylistSvc<T: PlaylistService>(pub Arc<T>);
                     impl<
                         T: PlaylistService,
-                    > tonic::server::UnaryService<super::StartRequest> for StartSvc<T> {
-                        type Response = super::StartResponse;
+                    > tonic::server::UnaryService<super::ShufflePlaylistRequest>
+                    for ShufflePlaylistSvc<T> {
+                        type Response = super::ShufflePlaylistResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::StartRequest>,
+                            request: tonic::Request<super::ShufflePlaylistRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as PlaylistService>::start(&inner, request).await
+                                <T as PlaylistService>::shuffle_playlist(&inner, request)
+                                    .await
                             };
                             Box::pin(fut)
                         }
@@ -6359,7 +8527,7 @@ pub mod playlist_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = StartSvc(inner);
+                        let method = ShufflePlaylistSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6375,24 +8543,748 @@ pub mod playlist_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.PlaylistService/Sync" => {
+                _ => {
+                    Box::pin(async move {
+                        let mut response = http::Response::new(empty_body());
+                        let headers = response.headers_mut();
+                        headers
+                            .insert(
+                                tonic::Status::GRPC_STATUS,
+                                (tonic::Code::Unimplemented as i32).into(),
+                            );
+                        headers
+                            .insert(
+                                http::header::CONTENT_TYPE,
+                                tonic::metadata::GRPC_CONTENT_TYPE,
+                            );
+                        Ok(response)
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for PlaylistServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "rockbox.v1alpha1.PlaylistService";
+    impl<T> tonic::server::NamedService for PlaylistServiceServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SleepTimer {
+    #[prost(bool, tag = "1")]
+    pub active: bool,
+    #[prost(uint32, tag = "2")]
+    pub duration: u32,
+    #[prost(uint32, tag = "3")]
+    pub remaining: u32,
+    #[prost(uint32, tag = "4")]
+    pub fade_out: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct GetSleepTimerRequest {}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct GetSleepTimerResponse {
+    #[prost(message, optional, tag = "1")]
+    pub timer: ::core::option::Option<SleepTimer>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetSleepTimerRequest {
+    #[prost(uint32, tag = "1")]
+    pub duration: u32,
+    #[prost(uint32, optional, tag = "2")]
+    pub fade_out: ::core::option::Option<u32>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetSleepTimerResponse {
+    #[prost(message, optional, tag = "1")]
+    pub timer: ::core::option::Option<SleepTimer>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct CancelSleepTimerRequest {}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct CancelSleepTimerResponse {
+    #[prost(message, optional, tag = "1")]
+    pub timer: ::core::option::Option<SleepTimer>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Schedule {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub cron: ::prost::alloc::string::String,
+    #[prost(enumeration = "PlaybackSource", tag = "4")]
+    pub source: i32,
+    #[prost(string, optional, tag = "5")]
+    pub source_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bool, tag = "6")]
+    pub shuffle: bool,
+    #[prost(int32, optional, tag = "7")]
+    pub volume: ::core::option::Option<i32>,
+    #[prost(uint32, tag = "8")]
+    pub fade_in: u32,
+    #[prost(bool, tag = "9")]
+    pub once: bool,
+    #[prost(bool, tag = "10")]
+    pub enabled: bool,
+    #[prost(int64, optional, tag = "11")]
+    pub last_run_at: ::core::option::Option<i64>,
+    #[prost(int64, optional, tag = "12")]
+    pub next_run_at: ::core::option::Option<i64>,
+    #[prost(int64, tag = "13")]
+    pub created_at: i64,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct GetSchedulesRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSchedulesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub schedules: ::prost::alloc::vec::Vec<Schedule>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetScheduleRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetScheduleResponse {
+    #[prost(message, optional, tag = "1")]
+    pub schedule: ::core::option::Option<Schedule>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateScheduleRequest {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub cron: ::prost::alloc::string::String,
+    #[prost(enumeration = "PlaybackSource", tag = "3")]
+    pub source: i32,
+    #[prost(string, optional, tag = "4")]
+    pub source_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bool, tag = "5")]
+    pub shuffle: bool,
+    #[prost(int32, optional, tag = "6")]
+    pub volume: ::core::option::Option<i32>,
+    #[prost(uint32, tag = "7")]
+    pub fade_in: u32,
+    #[prost(bool, tag = "8")]
+    pub once: bool,
+    #[prost(bool, optional, tag = "9")]
+    pub enabled: ::core::option::Option<bool>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateScheduleResponse {
+    #[prost(message, optional, tag = "1")]
+    pub schedule: ::core::option::Option<Schedule>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateScheduleRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "2")]
+    pub name: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "3")]
+    pub cron: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(enumeration = "PlaybackSource", optional, tag = "4")]
+    pub source: ::core::option::Option<i32>,
+    #[prost(string, optional, tag = "5")]
+    pub source_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bool, optional, tag = "6")]
+    pub shuffle: ::core::option::Option<bool>,
+    #[prost(int32, optional, tag = "7")]
+    pub volume: ::core::option::Option<i32>,
+    #[prost(uint32, optional, tag = "8")]
+    pub fade_in: ::core::option::Option<u32>,
+    #[prost(bool, optional, tag = "9")]
+    pub once: ::core::option::Option<bool>,
+    #[prost(bool, optional, tag = "10")]
+    pub enabled: ::core::option::Option<bool>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateScheduleResponse {
+    #[prost(message, optional, tag = "1")]
+    pub schedule: ::core::option::Option<Schedule>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteScheduleRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct DeleteScheduleResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RunScheduleRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct RunScheduleResponse {}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PlaybackSource {
+    Playlist = 0,
+    Album = 1,
+    Artist = 2,
+    Directory = 3,
+    LikedTracks = 4,
+    AllTracks = 5,
+    Mix = 6,
+}
+impl PlaybackSource {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Playlist => "PLAYBACK_SOURCE_PLAYLIST",
+            Self::Album => "PLAYBACK_SOURCE_ALBUM",
+            Self::Artist => "PLAYBACK_SOURCE_ARTIST",
+            Self::Directory => "PLAYBACK_SOURCE_DIRECTORY",
+            Self::LikedTracks => "PLAYBACK_SOURCE_LIKED_TRACKS",
+            Self::AllTracks => "PLAYBACK_SOURCE_ALL_TRACKS",
+            Self::Mix => "PLAYBACK_SOURCE_MIX",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "PLAYBACK_SOURCE_PLAYLIST" => Some(Self::Playlist),
+            "PLAYBACK_SOURCE_ALBUM" => Some(Self::Album),
+            "PLAYBACK_SOURCE_ARTIST" => Some(Self::Artist),
+            "PLAYBACK_SOURCE_DIRECTORY" => Some(Self::Directory),
+            "PLAYBACK_SOURCE_LIKED_TRACKS" => Some(Self::LikedTracks),
+            "PLAYBACK_SOURCE_ALL_TRACKS" => Some(Self::AllTracks),
+            "PLAYBACK_SOURCE_MIX" => Some(Self::Mix),
+            _ => None,
+        }
+    }
+}
+/// Generated client implementations.
+pub mod scheduler_service_client {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    #[derive(Debug, Clone)]
+    pub struct SchedulerServiceClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl SchedulerServiceClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> SchedulerServiceClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> SchedulerServiceClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+            >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
+        {
+            SchedulerServiceClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        pub async fn get_sleep_timer(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetSleepTimerRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetSleepTimerResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SchedulerService/GetSleepTimer",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.SchedulerService", "GetSleepTimer"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_sleep_timer(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetSleepTimerRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetSleepTimerResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SchedulerService/SetSleepTimer",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.SchedulerService", "SetSleepTimer"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn cancel_sleep_timer(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CancelSleepTimerRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CancelSleepTimerResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SchedulerService/CancelSleepTimer",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "rockbox.v1alpha1.SchedulerService",
+                        "CancelSleepTimer",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_schedules(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetSchedulesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetSchedulesResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SchedulerService/GetSchedules",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.SchedulerService", "GetSchedules"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_schedule(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetScheduleRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetScheduleResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SchedulerService/GetSchedule",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.SchedulerService", "GetSchedule"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn create_schedule(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CreateScheduleRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CreateScheduleResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SchedulerService/CreateSchedule",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "rockbox.v1alpha1.SchedulerService",
+                        "CreateSchedule",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn update_schedule(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateScheduleRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::UpdateScheduleResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SchedulerService/UpdateSchedule",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "rockbox.v1alpha1.SchedulerService",
+                        "UpdateSchedule",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn delete_schedule(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DeleteScheduleRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DeleteScheduleResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SchedulerService/DeleteSchedule",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "rockbox.v1alpha1.SchedulerService",
+                        "DeleteSchedule",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn run_schedule(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RunScheduleRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::RunScheduleResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SchedulerService/RunSchedule",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.SchedulerService", "RunSchedule"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod scheduler_service_server {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with SchedulerServiceServer.
+    #[async_trait]
+    pub trait SchedulerService: std::marker::Send + std::marker::Sync + 'static {
+        async fn get_sleep_timer(
+            &self,
+            request: tonic::Request<super::GetSleepTimerRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetSleepTimerResponse>,
+            tonic::Status,
+        >;
+        async fn set_sleep_timer(
+            &self,
+            request: tonic::Request<super::SetSleepTimerRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetSleepTimerResponse>,
+            tonic::Status,
+        >;
+        async fn cancel_sleep_timer(
+            &self,
+            request: tonic::Request<super::CancelSleepTimerRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CancelSleepTimerResponse>,
+            tonic::Status,
+        >;
+        async fn get_schedules(
+            &self,
+            request: tonic::Request<super::GetSchedulesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetSchedulesResponse>,
+            tonic::Status,
+        >;
+        async fn get_schedule(
+            &self,
+            request: tonic::Request<super::GetScheduleRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetScheduleResponse>,
+            tonic::Status,
+        >;
+        async fn create_schedule(
+            &self,
+            request: tonic::Request<super::CreateScheduleRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CreateScheduleResponse>,
+            tonic::Status,
+        >;
+        async fn update_schedule(
+            &self,
+            request: tonic::Request<super::UpdateScheduleRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::UpdateScheduleResponse>,
+            tonic::Status,
+        >;
+        async fn delete_schedule(
+            &self,
+            request: tonic::Request<super::DeleteScheduleRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DeleteScheduleResponse>,
+            tonic::Status,
+        >;
+        async fn run_schedule(
+            &self,
+            request: tonic::Request<super::RunScheduleRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::RunScheduleResponse>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct SchedulerServiceServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> SchedulerServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for SchedulerServiceServer<T>
+    where
+        T: SchedulerService,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/rockbox.v1alpha1.SchedulerService/GetSleepTimer" => {
                     #[allow(non_camel_case_types)]
-                    struct SyncSvc<T: PlaylistService>(pub Arc<T>);
+                    struct GetSleepTimerSvc<T: SchedulerService>(pub Arc<T>);
                     impl<
-                        T: PlaylistService,
-                    > tonic::server::UnaryService<super::SyncRequest> for SyncSvc<T> {
-                        type Response = super::SyncResponse;
+                        T: SchedulerService,
+                    > tonic::server::UnaryService<super::GetSleepTimerRequest>
+                    for GetSleepTimerSvc<T> {
+                        type Response = super::GetSleepTimerResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SyncRequest>,
+                            request: tonic::Request<super::GetSleepTimerRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as PlaylistService>::sync(&inner, request).await
+                                <T as SchedulerService>::get_sleep_timer(&inner, request)
+                                    .await
                             };
                             Box::pin(fut)
                         }
@@ -6403,7 +9295,7 @@ pub mod playlist_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = SyncSvc(inner);
+                        let method = GetSleepTimerSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6419,25 +9311,25 @@ pub mod playlist_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.PlaylistService/RemoveAllTracks" => {
+                "/rockbox.v1alpha1.SchedulerService/SetSleepTimer" => {
                     #[allow(non_camel_case_types)]
-                    struct RemoveAllTracksSvc<T: PlaylistService>(pub Arc<T>);
+                    struct SetSleepTimerSvc<T: SchedulerService>(pub Arc<T>);
                     impl<
-                        T: PlaylistService,
-                    > tonic::server::UnaryService<super::RemoveAllTracksRequest>
-                    for RemoveAllTracksSvc<T> {
-                        type Response = super::RemoveAllTracksResponse;
+                        T: SchedulerService,
+                    > tonic::server::UnaryService<super::SetSleepTimerRequest>
+                    for SetSleepTimerSvc<T> {
+                        type Response = super::SetSleepTimerResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::RemoveAllTracksRequest>,
+                            request: tonic::Request<super::SetSleepTimerRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as PlaylistService>::remove_all_tracks(&inner, request)
+                                <T as SchedulerService>::set_sleep_timer(&inner, request)
                                     .await
                             };
                             Box::pin(fut)
@@ -6449,7 +9341,7 @@ pub mod playlist_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = RemoveAllTracksSvc(inner);
+                        let method = SetSleepTimerSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6465,25 +9357,26 @@ pub mod playlist_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.PlaylistService/RemoveTracks" => {
+                "/rockbox.v1alpha1.SchedulerService/CancelSleepTimer" => {
                     #[allow(non_camel_case_types)]
-                    struct RemoveTracksSvc<T: PlaylistService>(pub Arc<T>);
+                    struct CancelSleepTimerSvc<T: SchedulerService>(pub Arc<T>);
                     impl<
-                        T: PlaylistService,
-                    > tonic::server::UnaryService<super::RemoveTracksRequest>
-                    for RemoveTracksSvc<T> {
-                        type Response = super::RemoveTracksResponse;
+                        T: SchedulerService,
+                    > tonic::server::UnaryService<super::CancelSleepTimerRequest>
+                    for CancelSleepTimerSvc<T> {
+                        type Response = super::CancelSleepTimerResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::RemoveTracksRequest>,
+                            request: tonic::Request<super::CancelSleepTimerRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as PlaylistService>::remove_tracks(&inner, request).await
+                                <T as SchedulerService>::cancel_sleep_timer(&inner, request)
+                                    .await
                             };
                             Box::pin(fut)
                         }
@@ -6494,7 +9387,7 @@ pub mod playlist_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = RemoveTracksSvc(inner);
+                        let method = CancelSleepTimerSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6510,25 +9403,25 @@ pub mod playlist_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.PlaylistService/CreatePlaylist" => {
+                "/rockbox.v1alpha1.SchedulerService/GetSchedules" => {
                     #[allow(non_camel_case_types)]
-                    struct CreatePlaylistSvc<T: PlaylistService>(pub Arc<T>);
+                    struct GetSchedulesSvc<T: SchedulerService>(pub Arc<T>);
                     impl<
-                        T: PlaylistService,
-                    > tonic::server::UnaryService<super::CreatePlaylistRequest>
-                    for CreatePlaylistSvc<T> {
-                        type Response = super::CreatePlaylistResponse;
+                        T: SchedulerService,
+                    > tonic::server::UnaryService<super::GetSchedulesRequest>
+                    for GetSchedulesSvc<T> {
+                        type Response = super::GetSchedulesResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::CreatePlaylistRequest>,
+                            request: tonic::Request<super::GetSchedulesRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as PlaylistService>::create_playlist(&inner, request)
+                                <T as SchedulerService>::get_schedules(&inner, request)
                                     .await
                             };
                             Box::pin(fut)
@@ -6540,7 +9433,7 @@ pub mod playlist_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = CreatePlaylistSvc(inner);
+                        let method = GetSchedulesSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6556,25 +9449,25 @@ pub mod playlist_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.PlaylistService/InsertTracks" => {
+                "/rockbox.v1alpha1.SchedulerService/GetSchedule" => {
                     #[allow(non_camel_case_types)]
-                    struct InsertTracksSvc<T: PlaylistService>(pub Arc<T>);
+                    struct GetScheduleSvc<T: SchedulerService>(pub Arc<T>);
                     impl<
-                        T: PlaylistService,
-                    > tonic::server::UnaryService<super::InsertTracksRequest>
-                    for InsertTracksSvc<T> {
-                        type Response = super::InsertTracksResponse;
+                        T: SchedulerService,
+                    > tonic::server::UnaryService<super::GetScheduleRequest>
+                    for GetScheduleSvc<T> {
+                        type Response = super::GetScheduleResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::InsertTracksRequest>,
+                            request: tonic::Request<super::GetScheduleRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as PlaylistService>::insert_tracks(&inner, request).await
+                                <T as SchedulerService>::get_schedule(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -6585,7 +9478,7 @@ pub mod playlist_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = InsertTracksSvc(inner);
+                        let method = GetScheduleSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6601,25 +9494,25 @@ pub mod playlist_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.PlaylistService/InsertDirectory" => {
+                "/rockbox.v1alpha1.SchedulerService/CreateSchedule" => {
                     #[allow(non_camel_case_types)]
-                    struct InsertDirectorySvc<T: PlaylistService>(pub Arc<T>);
+                    struct CreateScheduleSvc<T: SchedulerService>(pub Arc<T>);
                     impl<
-                        T: PlaylistService,
-                    > tonic::server::UnaryService<super::InsertDirectoryRequest>
-                    for InsertDirectorySvc<T> {
-                        type Response = super::InsertDirectoryResponse;
+                        T: SchedulerService,
+                    > tonic::server::UnaryService<super::CreateScheduleRequest>
+                    for CreateScheduleSvc<T> {
+                        type Response = super::CreateScheduleResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::InsertDirectoryRequest>,
+                            request: tonic::Request<super::CreateScheduleRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as PlaylistService>::insert_directory(&inner, request)
+                                <T as SchedulerService>::create_schedule(&inner, request)
                                     .await
                             };
                             Box::pin(fut)
@@ -6631,7 +9524,7 @@ pub mod playlist_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = InsertDirectorySvc(inner);
+                        let method = CreateScheduleSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6647,25 +9540,25 @@ pub mod playlist_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.PlaylistService/InsertPlaylist" => {
+                "/rockbox.v1alpha1.SchedulerService/UpdateSchedule" => {
                     #[allow(non_camel_case_types)]
-                    struct InsertPlaylistSvc<T: PlaylistService>(pub Arc<T>);
+                    struct UpdateScheduleSvc<T: SchedulerService>(pub Arc<T>);
                     impl<
-                        T: PlaylistService,
-                    > tonic::server::UnaryService<super::InsertPlaylistRequest>
-                    for InsertPlaylistSvc<T> {
-                        type Response = super::InsertPlaylistResponse;
+                        T: SchedulerService,
+                    > tonic::server::UnaryService<super::UpdateScheduleRequest>
+                    for UpdateScheduleSvc<T> {
+                        type Response = super::UpdateScheduleResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::InsertPlaylistRequest>,
+                            request: tonic::Request<super::UpdateScheduleRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as PlaylistService>::insert_playlist(&inner, request)
+                                <T as SchedulerService>::update_schedule(&inner, request)
                                     .await
                             };
                             Box::pin(fut)
@@ -6677,52 +9570,7 @@ pub mod playlist_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = InsertPlaylistSvc(inner);
-                        let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec)
-                            .apply_compression_config(
-                                accept_compression_encodings,
-                                send_compression_encodings,
-                            )
-                            .apply_max_message_size_config(
-                                max_decoding_message_size,
-                                max_encoding_message_size,
-                            );
-                        let res = grpc.unary(method, req).await;
-                        Ok(res)
-                    };
-                    Box::pin(fut)
-                }
-                "/rockbox.v1alpha1.PlaylistService/InsertAlbum" => {
-                    #[allow(non_camel_case_types)]
-                    struct InsertAlbumSvc<T: PlaylistService>(pub Arc<T>);
-                    impl<
-                        T: PlaylistService,
-                    > tonic::server::UnaryService<super::InsertAlbumRequest>
-                    for InsertAlbumSvc<T> {
-                        type Response = super::InsertAlbumResponse;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
-                        fn call(
-                            &mut self,
-                            request: tonic::Request<super::InsertAlbumRequest>,
-                        ) -> Self::Future {
-                            let inner = Arc::clone(&self.0);
-                            let fut = async move {
-                                <T as PlaylistService>::insert_album(&inner, request).await
-                            };
-                            Box::pin(fut)
-                        }
-                    }
-                    let accept_compression_encodings = self.accept_compression_encodings;
-                    let send_compression_encodings = self.send_compression_encodings;
-                    let max_decoding_message_size = self.max_decoding_message_size;
-                    let max_encoding_message_size = self.max_encoding_message_size;
-                    let inner = self.inner.clone();
-                    let fut = async move {
-                        let method = InsertAlbumSvc(inner);
+                        let method = UpdateScheduleSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6738,28 +9586,25 @@ pub mod playlist_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.PlaylistService/InsertArtistTracks" => {
+                "/rockbox.v1alpha1.SchedulerService/DeleteSchedule" => {
                     #[allow(non_camel_case_types)]
-                    struct InsertArtistTracksSvc<T: PlaylistService>(pub Arc<T>);
+                    struct DeleteScheduleSvc<T: SchedulerService>(pub Arc<T>);
                     impl<
-                        T: PlaylistService,
-                    > tonic::server::UnaryService<super::InsertArtistTracksRequest>
-                    for InsertArtistTracksSvc<T> {
-                        type Response = super::InsertArtistTracksResponse;
+                        T: SchedulerService,
+                    > tonic::server::UnaryService<super::DeleteScheduleRequest>
+                    for DeleteScheduleSvc<T> {
+                        type Response = super::DeleteScheduleResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::InsertArtistTracksRequest>,
+                            request: tonic::Request<super::DeleteScheduleRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as PlaylistService>::insert_artist_tracks(
-                                        &inner,
-                                        request,
-                                    )
+                                <T as SchedulerService>::delete_schedule(&inner, request)
                                     .await
                             };
                             Box::pin(fut)
@@ -6771,7 +9616,7 @@ pub mod playlist_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = InsertArtistTracksSvc(inner);
+                        let method = DeleteScheduleSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6787,26 +9632,25 @@ pub mod playlist_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.PlaylistService/ShufflePlaylist" => {
+                "/rockbox.v1alpha1.SchedulerService/RunSchedule" => {
                     #[allow(non_camel_case_types)]
-                    struct ShufflePlaylistSvc<T: PlaylistService>(pub Arc<T>);
+                    struct RunScheduleSvc<T: SchedulerService>(pub Arc<T>);
                     impl<
-                        T: PlaylistService,
-                    > tonic::server::UnaryService<super::ShufflePlaylistRequest>
-                    for ShufflePlaylistSvc<T> {
-                        type Response = super::ShufflePlaylistResponse;
+                        T: SchedulerService,
+                    > tonic::server::UnaryService<super::RunScheduleRequest>
+                    for RunScheduleSvc<T> {
+                        type Response = super::RunScheduleResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ShufflePlaylistRequest>,
+                            request: tonic::Request<super::RunScheduleRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as PlaylistService>::shuffle_playlist(&inner, request)
-                                    .await
+                                <T as SchedulerService>::run_schedule(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -6817,7 +9661,7 @@ pub mod playlist_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = ShufflePlaylistSvc(inner);
+                        let method = RunScheduleSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -6853,7 +9697,7 @@ pub mod playlist_service_server {
             }
         }
     }
-    impl<T> Clone for PlaylistServiceServer<T> {
+    impl<T> Clone for SchedulerServiceServer<T> {
         fn clone(&self) -> Self {
             let inner = self.inner.clone();
             Self {
@@ -6866,8 +9710,8 @@ pub mod playlist_service_server {
         }
     }
     /// Generated gRPC service name
-    pub const SERVICE_NAME: &str = "rockbox.v1alpha1.PlaylistService";
-    impl<T> tonic::server::NamedService for PlaylistServiceServer<T> {
+    pub const SERVICE_NAME: &str = "rockbox.v1alpha1.SchedulerService";
+    impl<T> tonic::server::NamedService for SchedulerServiceServer<T> {
         const NAME: &'static str = SERVICE_NAME;
     }
 }
@@ -6924,6 +9768,20 @@ pub struct CompressorSettings {
     #[prost(int32, tag = "6")]
     pub attack_time: i32,
 }
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct TransitionSettings {
+    #[prost(enumeration = "TransitionMode", tag = "1")]
+    pub album: i32,
+    #[prost(enumeration = "TransitionMode", tag = "2")]
+    pub mix: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResumeSettings {
+    #[prost(uint32, tag = "1")]
+    pub min_length: u32,
+    #[prost(string, repeated, tag = "2")]
+    pub directories: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetGlobalSettingsResponse {
     #[prost(int32, tag = "1")]
@@ -7290,6 +10148,10 @@ pub struct GetGlobalSettingsResponse {
     pub stereosw_mode: i32,
     #[prost(string, tag = "184")]
     pub music_dir: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "185")]
+    pub transitions: ::core::option::Option<TransitionSettings>,
+    #[prost(message, optional, tag = "186")]
+    pub resume: ::core::option::Option<ResumeSettings>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SaveSettingsRequest {
@@ -7347,9 +10209,42 @@ pub struct SaveSettingsRequest {
     pub eq_band_settings: ::prost::alloc::vec::Vec<EqBandSetting>,
     #[prost(message, optional, tag = "27")]
     pub replaygain_settings: ::core::option::Option<ReplaygainSettings>,
+    #[prost(message, optional, tag = "28")]
+    pub transitions: ::core::option::Option<TransitionSettings>,
+    #[prost(message, optional, tag = "29")]
+    pub resume: ::core::option::Option<ResumeSettings>,
 }
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
 pub struct SaveSettingsResponse {}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TransitionMode {
+    Crossfade = 0,
+    Smart = 1,
+    BeatMatch = 2,
+}
+impl TransitionMode {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Crossfade => "TRANSITION_MODE_CROSSFADE",
+            Self::Smart => "TRANSITION_MODE_SMART",
+            Self::BeatMatch => "TRANSITION_MODE_BEAT_MATCH",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "TRANSITION_MODE_CROSSFADE" => Some(Self::Crossfade),
+            "TRANSITION_MODE_SMART" => Some(Self::Smart),
+            "TRANSITION_MODE_BEAT_MATCH" => Some(Self::BeatMatch),
+            _ => None,
+        }
+    }
+}
 /// Generated client implementations.
 pub mod settings_service_client {
     #![allow(
@@ -7893,12 +10788,30 @@ pub struct GetPitchResponse {
     pub value: i32,
 }
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct SetPitchRequest {
+pub struct SetPitchRequest {
+    #[prost(int32, tag = "1")]
+    pub value: i32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetPitchResponse {}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct GetPlaybackSpeedRequest {}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct GetPlaybackSpeedResponse {
+    #[prost(int32, tag = "1")]
+    pub pitch: i32,
+    #[prost(int32, tag = "2")]
+    pub timestretch: i32,
+    #[prost(bool, tag = "3")]
+    pub timestretch_available: bool,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetPlaybackSpeedRequest {
     #[prost(int32, tag = "1")]
-    pub value: i32,
+    pub speed: i32,
 }
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct SetPitchResponse {}
+pub struct SetPlaybackSpeedResponse {}
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
 pub struct BeepPlayRequest {
     #[prost(uint32, tag = "1")]
@@ -7942,6 +10855,187 @@ pub struct KeyclickClickRequest {
 }
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
 pub struct KeyclickClickResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Equalizer {
+    #[prost(bool, tag = "1")]
+    pub enabled: bool,
+    #[prost(int32, tag = "2")]
+    pub precut: i32,
+    #[prost(message, repeated, tag = "3")]
+    pub bands: ::prost::alloc::vec::Vec<EqBandSetting>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct GetEqualizerRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetEqualizerResponse {
+    #[prost(message, optional, tag = "1")]
+    pub equalizer: ::core::option::Option<Equalizer>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateEqualizerRequest {
+    #[prost(bool, optional, tag = "1")]
+    pub enabled: ::core::option::Option<bool>,
+    #[prost(int32, optional, tag = "2")]
+    pub precut: ::core::option::Option<i32>,
+    #[prost(message, repeated, tag = "3")]
+    pub bands: ::prost::alloc::vec::Vec<EqBandSetting>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateEqualizerResponse {
+    #[prost(message, optional, tag = "1")]
+    pub equalizer: ::core::option::Option<Equalizer>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetEqBandRequest {
+    #[prost(uint32, tag = "1")]
+    pub band: u32,
+    #[prost(int32, optional, tag = "2")]
+    pub cutoff: ::core::option::Option<i32>,
+    #[prost(int32, optional, tag = "3")]
+    pub q: ::core::option::Option<i32>,
+    #[prost(int32, optional, tag = "4")]
+    pub gain: ::core::option::Option<i32>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetEqBandResponse {
+    #[prost(message, optional, tag = "1")]
+    pub band: ::core::option::Option<EqBandSetting>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EqPreset {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub equalizer: ::core::option::Option<Equalizer>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct GetEqPresetsRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetEqPresetsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub presets: ::prost::alloc::vec::Vec<EqPreset>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SaveEqPresetRequest {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub equalizer: ::core::option::Option<Equalizer>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SaveEqPresetResponse {
+    #[prost(message, optional, tag = "1")]
+    pub preset: ::core::option::Option<EqPreset>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ImportEqPresetRequest {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub profile: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ImportEqPresetResponse {
+    #[prost(message, optional, tag = "1")]
+    pub preset: ::core::option::Option<EqPreset>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteEqPresetRequest {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct DeleteEqPresetResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LoadEqPresetRequest {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LoadEqPresetResponse {
+    #[prost(message, optional, tag = "1")]
+    pub equalizer: ::core::option::Option<Equalizer>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct GetEqDevicePresetsRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetEqDevicePresetsResponse {
+    #[prost(map = "string, string", tag = "1")]
+    pub devices: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        ::prost::alloc::string::String,
+    >,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BindEqPresetRequest {
+    #[prost(string, tag = "1")]
+    pub device_id: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "2")]
+    pub preset: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct BindEqPresetResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EqComparison {
+    #[prost(enumeration = "EqSlot", tag = "1")]
+    pub slot: i32,
+    #[prost(message, optional, tag = "2")]
+    pub a: ::core::option::Option<Equalizer>,
+    #[prost(message, optional, tag = "3")]
+    pub b: ::core::option::Option<Equalizer>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct GetEqComparisonRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetEqComparisonResponse {
+    #[prost(message, optional, tag = "1")]
+    pub comparison: ::core::option::Option<EqComparison>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetEqSlotRequest {
+    #[prost(enumeration = "EqSlot", tag = "1")]
+    pub slot: i32,
+    #[prost(message, optional, tag = "2")]
+    pub equalizer: ::core::option::Option<Equalizer>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetEqSlotResponse {
+    #[prost(message, optional, tag = "1")]
+    pub comparison: ::core::option::Option<EqComparison>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ToggleEqSlotRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ToggleEqSlotResponse {
+    #[prost(message, optional, tag = "1")]
+    pub comparison: ::core::option::Option<EqComparison>,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum EqSlot {
+    A = 0,
+    B = 1,
+}
+impl EqSlot {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::A => "EQ_SLOT_A",
+            Self::B => "EQ_SLOT_B",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "EQ_SLOT_A" => Some(Self::A),
+            "EQ_SLOT_B" => Some(Self::B),
+            _ => None,
+        }
+    }
+}
 /// Generated client implementations.
 pub mod sound_service_client {
     #![allow(
@@ -7967,77 +11061,401 @@ pub mod sound_service_client {
             let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
             Ok(Self::new(conn))
         }
-    }
-    impl<T> SoundServiceClient<T>
-    where
-        T: tonic::client::GrpcService<tonic::body::BoxBody>,
-        T::Error: Into<StdError>,
-        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
-        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
-    {
-        pub fn new(inner: T) -> Self {
-            let inner = tonic::client::Grpc::new(inner);
-            Self { inner }
+    }
+    impl<T> SoundServiceClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> SoundServiceClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+            >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
+        {
+            SoundServiceClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        pub async fn adjust_volume(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AdjustVolumeRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::AdjustVolumeResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SoundService/AdjustVolume",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "AdjustVolume"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn sound_set(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SoundSetRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SoundSetResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SoundService/SoundSet",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("rockbox.v1alpha1.SoundService", "SoundSet"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn sound_current(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SoundCurrentRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SoundCurrentResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SoundService/SoundCurrent",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "SoundCurrent"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn sound_default(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SoundDefaultRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SoundDefaultResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SoundService/SoundDefault",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "SoundDefault"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn sound_min(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SoundMinRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SoundMinResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SoundService/SoundMin",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("rockbox.v1alpha1.SoundService", "SoundMin"));
+            self.inner.unary(req, path, codec).await
         }
-        pub fn with_origin(inner: T, origin: Uri) -> Self {
-            let inner = tonic::client::Grpc::with_origin(inner, origin);
-            Self { inner }
+        pub async fn sound_max(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SoundMaxRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SoundMaxResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SoundService/SoundMax",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("rockbox.v1alpha1.SoundService", "SoundMax"));
+            self.inner.unary(req, path, codec).await
         }
-        pub fn with_interceptor<F>(
-            inner: T,
-            interceptor: F,
-        ) -> SoundServiceClient<InterceptedService<T, F>>
-        where
-            F: tonic::service::Interceptor,
-            T::ResponseBody: Default,
-            T: tonic::codegen::Service<
-                http::Request<tonic::body::BoxBody>,
-                Response = http::Response<
-                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
-                >,
-            >,
-            <T as tonic::codegen::Service<
-                http::Request<tonic::body::BoxBody>,
-            >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
-        {
-            SoundServiceClient::new(InterceptedService::new(inner, interceptor))
+        pub async fn sound_unit(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SoundUnitRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SoundUnitResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SoundService/SoundUnit",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("rockbox.v1alpha1.SoundService", "SoundUnit"));
+            self.inner.unary(req, path, codec).await
         }
-        /// Compress requests with the given encoding.
-        ///
-        /// This requires the server to support it otherwise it might respond with an
-        /// error.
-        #[must_use]
-        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.inner = self.inner.send_compressed(encoding);
-            self
+        pub async fn sound_val2_phys(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SoundVal2PhysRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SoundVal2PhysResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SoundService/SoundVal2Phys",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "SoundVal2Phys"),
+                );
+            self.inner.unary(req, path, codec).await
         }
-        /// Enable decompressing responses.
-        #[must_use]
-        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.inner = self.inner.accept_compressed(encoding);
-            self
+        pub async fn get_pitch(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetPitchRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetPitchResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SoundService/GetPitch",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("rockbox.v1alpha1.SoundService", "GetPitch"));
+            self.inner.unary(req, path, codec).await
         }
-        /// Limits the maximum size of a decoded message.
-        ///
-        /// Default: `4MB`
-        #[must_use]
-        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
-            self.inner = self.inner.max_decoding_message_size(limit);
-            self
+        pub async fn set_pitch(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetPitchRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetPitchResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SoundService/SetPitch",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("rockbox.v1alpha1.SoundService", "SetPitch"));
+            self.inner.unary(req, path, codec).await
         }
-        /// Limits the maximum size of an encoded message.
-        ///
-        /// Default: `usize::MAX`
-        #[must_use]
-        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
-            self.inner = self.inner.max_encoding_message_size(limit);
-            self
+        pub async fn get_playback_speed(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetPlaybackSpeedRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetPlaybackSpeedResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SoundService/GetPlaybackSpeed",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "GetPlaybackSpeed"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_playback_speed(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetPlaybackSpeedRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetPlaybackSpeedResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SoundService/SetPlaybackSpeed",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "SetPlaybackSpeed"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn beep_play(
+            &mut self,
+            request: impl tonic::IntoRequest<super::BeepPlayRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::BeepPlayResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SoundService/BeepPlay",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("rockbox.v1alpha1.SoundService", "BeepPlay"));
+            self.inner.unary(req, path, codec).await
         }
-        pub async fn adjust_volume(
+        pub async fn pcmbuf_fade(
             &mut self,
-            request: impl tonic::IntoRequest<super::AdjustVolumeRequest>,
+            request: impl tonic::IntoRequest<super::PcmbufFadeRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::AdjustVolumeResponse>,
+            tonic::Response<super::PcmbufFadeResponse>,
             tonic::Status,
         > {
             self.inner
@@ -8050,20 +11468,18 @@ pub mod sound_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/rockbox.v1alpha1.SoundService/AdjustVolume",
+                "/rockbox.v1alpha1.SoundService/PcmbufFade",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(
-                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "AdjustVolume"),
-                );
+                .insert(GrpcMethod::new("rockbox.v1alpha1.SoundService", "PcmbufFade"));
             self.inner.unary(req, path, codec).await
         }
-        pub async fn sound_set(
+        pub async fn pcmbuf_set_low_latency(
             &mut self,
-            request: impl tonic::IntoRequest<super::SoundSetRequest>,
+            request: impl tonic::IntoRequest<super::PcmbufSetLowLatencyRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::SoundSetResponse>,
+            tonic::Response<super::PcmbufSetLowLatencyResponse>,
             tonic::Status,
         > {
             self.inner
@@ -8076,18 +11492,23 @@ pub mod sound_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/rockbox.v1alpha1.SoundService/SoundSet",
+                "/rockbox.v1alpha1.SoundService/PcmbufSetLowLatency",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("rockbox.v1alpha1.SoundService", "SoundSet"));
+                .insert(
+                    GrpcMethod::new(
+                        "rockbox.v1alpha1.SoundService",
+                        "PcmbufSetLowLatency",
+                    ),
+                );
             self.inner.unary(req, path, codec).await
         }
-        pub async fn sound_current(
+        pub async fn system_sound_play(
             &mut self,
-            request: impl tonic::IntoRequest<super::SoundCurrentRequest>,
+            request: impl tonic::IntoRequest<super::SystemSoundPlayRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::SoundCurrentResponse>,
+            tonic::Response<super::SystemSoundPlayResponse>,
             tonic::Status,
         > {
             self.inner
@@ -8100,20 +11521,20 @@ pub mod sound_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/rockbox.v1alpha1.SoundService/SoundCurrent",
+                "/rockbox.v1alpha1.SoundService/SystemSoundPlay",
             );
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(
-                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "SoundCurrent"),
+                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "SystemSoundPlay"),
                 );
             self.inner.unary(req, path, codec).await
         }
-        pub async fn sound_default(
+        pub async fn keyclick_click(
             &mut self,
-            request: impl tonic::IntoRequest<super::SoundDefaultRequest>,
+            request: impl tonic::IntoRequest<super::KeyclickClickRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::SoundDefaultResponse>,
+            tonic::Response<super::KeyclickClickResponse>,
             tonic::Status,
         > {
             self.inner
@@ -8126,20 +11547,20 @@ pub mod sound_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/rockbox.v1alpha1.SoundService/SoundDefault",
+                "/rockbox.v1alpha1.SoundService/KeyclickClick",
             );
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(
-                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "SoundDefault"),
+                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "KeyclickClick"),
                 );
             self.inner.unary(req, path, codec).await
         }
-        pub async fn sound_min(
+        pub async fn get_equalizer(
             &mut self,
-            request: impl tonic::IntoRequest<super::SoundMinRequest>,
+            request: impl tonic::IntoRequest<super::GetEqualizerRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::SoundMinResponse>,
+            tonic::Response<super::GetEqualizerResponse>,
             tonic::Status,
         > {
             self.inner
@@ -8152,18 +11573,20 @@ pub mod sound_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/rockbox.v1alpha1.SoundService/SoundMin",
+                "/rockbox.v1alpha1.SoundService/GetEqualizer",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("rockbox.v1alpha1.SoundService", "SoundMin"));
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "GetEqualizer"),
+                );
             self.inner.unary(req, path, codec).await
         }
-        pub async fn sound_max(
+        pub async fn update_equalizer(
             &mut self,
-            request: impl tonic::IntoRequest<super::SoundMaxRequest>,
+            request: impl tonic::IntoRequest<super::UpdateEqualizerRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::SoundMaxResponse>,
+            tonic::Response<super::UpdateEqualizerResponse>,
             tonic::Status,
         > {
             self.inner
@@ -8176,18 +11599,20 @@ pub mod sound_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/rockbox.v1alpha1.SoundService/SoundMax",
+                "/rockbox.v1alpha1.SoundService/UpdateEqualizer",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("rockbox.v1alpha1.SoundService", "SoundMax"));
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "UpdateEqualizer"),
+                );
             self.inner.unary(req, path, codec).await
         }
-        pub async fn sound_unit(
+        pub async fn set_eq_band(
             &mut self,
-            request: impl tonic::IntoRequest<super::SoundUnitRequest>,
+            request: impl tonic::IntoRequest<super::SetEqBandRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::SoundUnitResponse>,
+            tonic::Response<super::SetEqBandResponse>,
             tonic::Status,
         > {
             self.inner
@@ -8200,18 +11625,18 @@ pub mod sound_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/rockbox.v1alpha1.SoundService/SoundUnit",
+                "/rockbox.v1alpha1.SoundService/SetEqBand",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("rockbox.v1alpha1.SoundService", "SoundUnit"));
+                .insert(GrpcMethod::new("rockbox.v1alpha1.SoundService", "SetEqBand"));
             self.inner.unary(req, path, codec).await
         }
-        pub async fn sound_val2_phys(
+        pub async fn get_eq_presets(
             &mut self,
-            request: impl tonic::IntoRequest<super::SoundVal2PhysRequest>,
+            request: impl tonic::IntoRequest<super::GetEqPresetsRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::SoundVal2PhysResponse>,
+            tonic::Response<super::GetEqPresetsResponse>,
             tonic::Status,
         > {
             self.inner
@@ -8224,20 +11649,20 @@ pub mod sound_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/rockbox.v1alpha1.SoundService/SoundVal2Phys",
+                "/rockbox.v1alpha1.SoundService/GetEqPresets",
             );
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(
-                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "SoundVal2Phys"),
+                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "GetEqPresets"),
                 );
             self.inner.unary(req, path, codec).await
         }
-        pub async fn get_pitch(
+        pub async fn save_eq_preset(
             &mut self,
-            request: impl tonic::IntoRequest<super::GetPitchRequest>,
+            request: impl tonic::IntoRequest<super::SaveEqPresetRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::GetPitchResponse>,
+            tonic::Response<super::SaveEqPresetResponse>,
             tonic::Status,
         > {
             self.inner
@@ -8250,18 +11675,20 @@ pub mod sound_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/rockbox.v1alpha1.SoundService/GetPitch",
+                "/rockbox.v1alpha1.SoundService/SaveEqPreset",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("rockbox.v1alpha1.SoundService", "GetPitch"));
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "SaveEqPreset"),
+                );
             self.inner.unary(req, path, codec).await
         }
-        pub async fn set_pitch(
+        pub async fn import_eq_preset(
             &mut self,
-            request: impl tonic::IntoRequest<super::SetPitchRequest>,
+            request: impl tonic::IntoRequest<super::ImportEqPresetRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::SetPitchResponse>,
+            tonic::Response<super::ImportEqPresetResponse>,
             tonic::Status,
         > {
             self.inner
@@ -8274,18 +11701,20 @@ pub mod sound_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/rockbox.v1alpha1.SoundService/SetPitch",
+                "/rockbox.v1alpha1.SoundService/ImportEqPreset",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("rockbox.v1alpha1.SoundService", "SetPitch"));
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "ImportEqPreset"),
+                );
             self.inner.unary(req, path, codec).await
         }
-        pub async fn beep_play(
+        pub async fn delete_eq_preset(
             &mut self,
-            request: impl tonic::IntoRequest<super::BeepPlayRequest>,
+            request: impl tonic::IntoRequest<super::DeleteEqPresetRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::BeepPlayResponse>,
+            tonic::Response<super::DeleteEqPresetResponse>,
             tonic::Status,
         > {
             self.inner
@@ -8298,18 +11727,20 @@ pub mod sound_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/rockbox.v1alpha1.SoundService/BeepPlay",
+                "/rockbox.v1alpha1.SoundService/DeleteEqPreset",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("rockbox.v1alpha1.SoundService", "BeepPlay"));
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "DeleteEqPreset"),
+                );
             self.inner.unary(req, path, codec).await
         }
-        pub async fn pcmbuf_fade(
+        pub async fn load_eq_preset(
             &mut self,
-            request: impl tonic::IntoRequest<super::PcmbufFadeRequest>,
+            request: impl tonic::IntoRequest<super::LoadEqPresetRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::PcmbufFadeResponse>,
+            tonic::Response<super::LoadEqPresetResponse>,
             tonic::Status,
         > {
             self.inner
@@ -8322,18 +11753,20 @@ pub mod sound_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/rockbox.v1alpha1.SoundService/PcmbufFade",
+                "/rockbox.v1alpha1.SoundService/LoadEqPreset",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("rockbox.v1alpha1.SoundService", "PcmbufFade"));
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "LoadEqPreset"),
+                );
             self.inner.unary(req, path, codec).await
         }
-        pub async fn pcmbuf_set_low_latency(
+        pub async fn get_eq_device_presets(
             &mut self,
-            request: impl tonic::IntoRequest<super::PcmbufSetLowLatencyRequest>,
+            request: impl tonic::IntoRequest<super::GetEqDevicePresetsRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::PcmbufSetLowLatencyResponse>,
+            tonic::Response<super::GetEqDevicePresetsResponse>,
             tonic::Status,
         > {
             self.inner
@@ -8346,23 +11779,23 @@ pub mod sound_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/rockbox.v1alpha1.SoundService/PcmbufSetLowLatency",
+                "/rockbox.v1alpha1.SoundService/GetEqDevicePresets",
             );
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(
                     GrpcMethod::new(
                         "rockbox.v1alpha1.SoundService",
-                        "PcmbufSetLowLatency",
+                        "GetEqDevicePresets",
                     ),
                 );
             self.inner.unary(req, path, codec).await
         }
-        pub async fn system_sound_play(
+        pub async fn bind_eq_preset(
             &mut self,
-            request: impl tonic::IntoRequest<super::SystemSoundPlayRequest>,
+            request: impl tonic::IntoRequest<super::BindEqPresetRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::SystemSoundPlayResponse>,
+            tonic::Response<super::BindEqPresetResponse>,
             tonic::Status,
         > {
             self.inner
@@ -8375,20 +11808,20 @@ pub mod sound_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/rockbox.v1alpha1.SoundService/SystemSoundPlay",
+                "/rockbox.v1alpha1.SoundService/BindEqPreset",
             );
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(
-                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "SystemSoundPlay"),
+                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "BindEqPreset"),
                 );
             self.inner.unary(req, path, codec).await
         }
-        pub async fn keyclick_click(
+        pub async fn get_eq_comparison(
             &mut self,
-            request: impl tonic::IntoRequest<super::KeyclickClickRequest>,
+            request: impl tonic::IntoRequest<super::GetEqComparisonRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::KeyclickClickResponse>,
+            tonic::Response<super::GetEqComparisonResponse>,
             tonic::Status,
         > {
             self.inner
@@ -8401,12 +11834,62 @@ pub mod sound_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/rockbox.v1alpha1.SoundService/KeyclickClick",
+                "/rockbox.v1alpha1.SoundService/GetEqComparison",
             );
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(
-                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "KeyclickClick"),
+                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "GetEqComparison"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_eq_slot(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetEqSlotRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetEqSlotResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SoundService/SetEqSlot",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("rockbox.v1alpha1.SoundService", "SetEqSlot"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn toggle_eq_slot(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ToggleEqSlotRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ToggleEqSlotResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/rockbox.v1alpha1.SoundService/ToggleEqSlot",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("rockbox.v1alpha1.SoundService", "ToggleEqSlot"),
                 );
             self.inner.unary(req, path, codec).await
         }
@@ -8495,6 +11978,20 @@ pub mod sound_service_server {
             tonic::Response<super::SetPitchResponse>,
             tonic::Status,
         >;
+        async fn get_playback_speed(
+            &self,
+            request: tonic::Request<super::GetPlaybackSpeedRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetPlaybackSpeedResponse>,
+            tonic::Status,
+        >;
+        async fn set_playback_speed(
+            &self,
+            request: tonic::Request<super::SetPlaybackSpeedRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetPlaybackSpeedResponse>,
+            tonic::Status,
+        >;
         async fn beep_play(
             &self,
             request: tonic::Request<super::BeepPlayRequest>,
@@ -8530,6 +12027,97 @@ pub mod sound_service_server {
             tonic::Response<super::KeyclickClickResponse>,
             tonic::Status,
         >;
+        async fn get_equalizer(
+            &self,
+            request: tonic::Request<super::GetEqualizerRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetEqualizerResponse>,
+            tonic::Status,
+        >;
+        async fn update_equalizer(
+            &self,
+            request: tonic::Request<super::UpdateEqualizerRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::UpdateEqualizerResponse>,
+            tonic::Status,
+        >;
+        async fn set_eq_band(
+            &self,
+            request: tonic::Request<super::SetEqBandRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetEqBandResponse>,
+            tonic::Status,
+        >;
+        async fn get_eq_presets(
+            &self,
+            request: tonic::Request<super::GetEqPresetsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetEqPresetsResponse>,
+            tonic::Status,
+        >;
+        async fn save_eq_preset(
+            &self,
+            request: tonic::Request<super::SaveEqPresetRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SaveEqPresetResponse>,
+            tonic::Status,
+        >;
+        async fn import_eq_preset(
+            &self,
+            request: tonic::Request<super::ImportEqPresetRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ImportEqPresetResponse>,
+            tonic::Status,
+        >;
+        async fn delete_eq_preset(
+            &self,
+            request: tonic::Request<super::DeleteEqPresetRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DeleteEqPresetResponse>,
+            tonic::Status,
+        >;
+        async fn load_eq_preset(
+            &self,
+            request: tonic::Request<super::LoadEqPresetRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::LoadEqPresetResponse>,
+            tonic::Status,
+        >;
+        async fn get_eq_device_presets(
+            &self,
+            request: tonic::Request<super::GetEqDevicePresetsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetEqDevicePresetsResponse>,
+            tonic::Status,
+        >;
+        async fn bind_eq_preset(
+            &self,
+            request: tonic::Request<super::BindEqPresetRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::BindEqPresetResponse>,
+            tonic::Status,
+        >;
+        async fn get_eq_comparison(
+            &self,
+            request: tonic::Request<super::GetEqComparisonRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetEqComparisonResponse>,
+            tonic::Status,
+        >;
+        async fn set_eq_slot(
+            &self,
+            request: tonic::Request<super::SetEqSlotRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SetEqSlotResponse>,
+            tonic::Status,
+        >;
+        async fn toggle_eq_slot(
+            &self,
+            request: tonic::Request<super::ToggleEqSlotRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ToggleEqSlotResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct SoundServiceServer<T> {
@@ -8609,23 +12197,610 @@ pub mod sound_service_server {
             match req.uri().path() {
                 "/rockbox.v1alpha1.SoundService/AdjustVolume" => {
                     #[allow(non_camel_case_types)]
-                    struct AdjustVolumeSvc<T: SoundService>(pub Arc<T>);
+                    struct AdjustVolumeSvc<T: SoundService>(pub Arc<T>);
+                    impl<
+                        T: SoundService,
+                    > tonic::server::UnaryService<super::AdjustVolumeRequest>
+                    for AdjustVolumeSvc<T> {
+                        type Response = super::AdjustVolumeResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AdjustVolumeRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SoundService>::adjust_volume(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = AdjustVolumeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.SoundService/SoundSet" => {
+                    #[allow(non_camel_case_types)]
+                    struct SoundSetSvc<T: SoundService>(pub Arc<T>);
+                    impl<
+                        T: SoundService,
+                    > tonic::server::UnaryService<super::SoundSetRequest>
+                    for SoundSetSvc<T> {
+                        type Response = super::SoundSetResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SoundSetRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SoundService>::sound_set(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SoundSetSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.SoundService/SoundCurrent" => {
+                    #[allow(non_camel_case_types)]
+                    struct SoundCurrentSvc<T: SoundService>(pub Arc<T>);
+                    impl<
+                        T: SoundService,
+                    > tonic::server::UnaryService<super::SoundCurrentRequest>
+                    for SoundCurrentSvc<T> {
+                        type Response = super::SoundCurrentResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SoundCurrentRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SoundService>::sound_current(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SoundCurrentSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.SoundService/SoundDefault" => {
+                    #[allow(non_camel_case_types)]
+                    struct SoundDefaultSvc<T: SoundService>(pub Arc<T>);
+                    impl<
+                        T: SoundService,
+                    > tonic::server::UnaryService<super::SoundDefaultRequest>
+                    for SoundDefaultSvc<T> {
+                        type Response = super::SoundDefaultResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SoundDefaultRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SoundService>::sound_default(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SoundDefaultSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.SoundService/SoundMin" => {
+                    #[allow(non_camel_case_types)]
+                    struct SoundMinSvc<T: SoundService>(pub Arc<T>);
+                    impl<
+                        T: SoundService,
+                    > tonic::server::UnaryService<super::SoundMinRequest>
+                    for SoundMinSvc<T> {
+                        type Response = super::SoundMinResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SoundMinRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SoundService>::sound_min(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SoundMinSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.SoundService/SoundMax" => {
+                    #[allow(non_camel_case_types)]
+                    struct SoundMaxSvc<T: SoundService>(pub Arc<T>);
+                    impl<
+                        T: SoundService,
+                    > tonic::server::UnaryService<super::SoundMaxRequest>
+                    for SoundMaxSvc<T> {
+                        type Response = super::SoundMaxResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SoundMaxRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SoundService>::sound_max(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SoundMaxSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.SoundService/SoundUnit" => {
+                    #[allow(non_camel_case_types)]
+                    struct SoundUnitSvc<T: SoundService>(pub Arc<T>);
+                    impl<
+                        T: SoundService,
+                    > tonic::server::UnaryService<super::SoundUnitRequest>
+                    for SoundUnitSvc<T> {
+                        type Response = super::SoundUnitResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SoundUnitRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SoundService>::sound_unit(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SoundUnitSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.SoundService/SoundVal2Phys" => {
+                    #[allow(non_camel_case_types)]
+                    struct SoundVal2PhysSvc<T: SoundService>(pub Arc<T>);
+                    impl<
+                        T: SoundService,
+                    > tonic::server::UnaryService<super::SoundVal2PhysRequest>
+                    for SoundVal2PhysSvc<T> {
+                        type Response = super::SoundVal2PhysResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SoundVal2PhysRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SoundService>::sound_val2_phys(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SoundVal2PhysSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.SoundService/GetPitch" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetPitchSvc<T: SoundService>(pub Arc<T>);
+                    impl<
+                        T: SoundService,
+                    > tonic::server::UnaryService<super::GetPitchRequest>
+                    for GetPitchSvc<T> {
+                        type Response = super::GetPitchResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetPitchRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SoundService>::get_pitch(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetPitchSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.SoundService/SetPitch" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetPitchSvc<T: SoundService>(pub Arc<T>);
+                    impl<
+                        T: SoundService,
+                    > tonic::server::UnaryService<super::SetPitchRequest>
+                    for SetPitchSvc<T> {
+                        type Response = super::SetPitchResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetPitchRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SoundService>::set_pitch(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetPitchSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.SoundService/GetPlaybackSpeed" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetPlaybackSpeedSvc<T: SoundService>(pub Arc<T>);
+                    impl<
+                        T: SoundService,
+                    > tonic::server::UnaryService<super::GetPlaybackSpeedRequest>
+                    for GetPlaybackSpeedSvc<T> {
+                        type Response = super::GetPlaybackSpeedResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetPlaybackSpeedRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SoundService>::get_playback_speed(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetPlaybackSpeedSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.SoundService/SetPlaybackSpeed" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetPlaybackSpeedSvc<T: SoundService>(pub Arc<T>);
+                    impl<
+                        T: SoundService,
+                    > tonic::server::UnaryService<super::SetPlaybackSpeedRequest>
+                    for SetPlaybackSpeedSvc<T> {
+                        type Response = super::SetPlaybackSpeedResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetPlaybackSpeedRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SoundService>::set_playback_speed(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SetPlaybackSpeedSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.SoundService/BeepPlay" => {
+                    #[allow(non_camel_case_types)]
+                    struct BeepPlaySvc<T: SoundService>(pub Arc<T>);
+                    impl<
+                        T: SoundService,
+                    > tonic::server::UnaryService<super::BeepPlayRequest>
+                    for BeepPlaySvc<T> {
+                        type Response = super::BeepPlayResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::BeepPlayRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SoundService>::beep_play(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = BeepPlaySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.SoundService/PcmbufFade" => {
+                    #[allow(non_camel_case_types)]
+                    struct PcmbufFadeSvc<T: SoundService>(pub Arc<T>);
                     impl<
                         T: SoundService,
-                    > tonic::server::UnaryService<super::AdjustVolumeRequest>
-                    for AdjustVolumeSvc<T> {
-                        type Response = super::AdjustVolumeResponse;
+                    > tonic::server::UnaryService<super::PcmbufFadeRequest>
+                    for PcmbufFadeSvc<T> {
+                        type Response = super::PcmbufFadeResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::AdjustVolumeRequest>,
+                            request: tonic::Request<super::PcmbufFadeRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as SoundService>::adjust_volume(&inner, request).await
+                                <T as SoundService>::pcmbuf_fade(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -8636,7 +12811,7 @@ pub mod sound_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = AdjustVolumeSvc(inner);
+                        let method = PcmbufFadeSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -8652,25 +12827,26 @@ pub mod sound_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.SoundService/SoundSet" => {
+                "/rockbox.v1alpha1.SoundService/PcmbufSetLowLatency" => {
                     #[allow(non_camel_case_types)]
-                    struct SoundSetSvc<T: SoundService>(pub Arc<T>);
+                    struct PcmbufSetLowLatencySvc<T: SoundService>(pub Arc<T>);
                     impl<
                         T: SoundService,
-                    > tonic::server::UnaryService<super::SoundSetRequest>
-                    for SoundSetSvc<T> {
-                        type Response = super::SoundSetResponse;
+                    > tonic::server::UnaryService<super::PcmbufSetLowLatencyRequest>
+                    for PcmbufSetLowLatencySvc<T> {
+                        type Response = super::PcmbufSetLowLatencyResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SoundSetRequest>,
+                            request: tonic::Request<super::PcmbufSetLowLatencyRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as SoundService>::sound_set(&inner, request).await
+                                <T as SoundService>::pcmbuf_set_low_latency(&inner, request)
+                                    .await
                             };
                             Box::pin(fut)
                         }
@@ -8681,7 +12857,7 @@ pub mod sound_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = SoundSetSvc(inner);
+                        let method = PcmbufSetLowLatencySvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -8697,25 +12873,26 @@ pub mod sound_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.SoundService/SoundCurrent" => {
+                "/rockbox.v1alpha1.SoundService/SystemSoundPlay" => {
                     #[allow(non_camel_case_types)]
-                    struct SoundCurrentSvc<T: SoundService>(pub Arc<T>);
+                    struct SystemSoundPlaySvc<T: SoundService>(pub Arc<T>);
                     impl<
                         T: SoundService,
-                    > tonic::server::UnaryService<super::SoundCurrentRequest>
-                    for SoundCurrentSvc<T> {
-                        type Response = super::SoundCurrentResponse;
+                    > tonic::server::UnaryService<super::SystemSoundPlayRequest>
+                    for SystemSoundPlaySvc<T> {
+                        type Response = super::SystemSoundPlayResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SoundCurrentRequest>,
+                            request: tonic::Request<super::SystemSoundPlayRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as SoundService>::sound_current(&inner, request).await
+                                <T as SoundService>::system_sound_play(&inner, request)
+                                    .await
                             };
                             Box::pin(fut)
                         }
@@ -8726,7 +12903,7 @@ pub mod sound_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = SoundCurrentSvc(inner);
+                        let method = SystemSoundPlaySvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -8742,25 +12919,25 @@ pub mod sound_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.SoundService/SoundDefault" => {
+                "/rockbox.v1alpha1.SoundService/KeyclickClick" => {
                     #[allow(non_camel_case_types)]
-                    struct SoundDefaultSvc<T: SoundService>(pub Arc<T>);
+                    struct KeyclickClickSvc<T: SoundService>(pub Arc<T>);
                     impl<
                         T: SoundService,
-                    > tonic::server::UnaryService<super::SoundDefaultRequest>
-                    for SoundDefaultSvc<T> {
-                        type Response = super::SoundDefaultResponse;
+                    > tonic::server::UnaryService<super::KeyclickClickRequest>
+                    for KeyclickClickSvc<T> {
+                        type Response = super::KeyclickClickResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SoundDefaultRequest>,
+                            request: tonic::Request<super::KeyclickClickRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as SoundService>::sound_default(&inner, request).await
+                                <T as SoundService>::keyclick_click(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -8771,7 +12948,7 @@ pub mod sound_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = SoundDefaultSvc(inner);
+                        let method = KeyclickClickSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -8787,25 +12964,25 @@ pub mod sound_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.SoundService/SoundMin" => {
+                "/rockbox.v1alpha1.SoundService/GetEqualizer" => {
                     #[allow(non_camel_case_types)]
-                    struct SoundMinSvc<T: SoundService>(pub Arc<T>);
+                    struct GetEqualizerSvc<T: SoundService>(pub Arc<T>);
                     impl<
                         T: SoundService,
-                    > tonic::server::UnaryService<super::SoundMinRequest>
-                    for SoundMinSvc<T> {
-                        type Response = super::SoundMinResponse;
+                    > tonic::server::UnaryService<super::GetEqualizerRequest>
+                    for GetEqualizerSvc<T> {
+                        type Response = super::GetEqualizerResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SoundMinRequest>,
+                            request: tonic::Request<super::GetEqualizerRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as SoundService>::sound_min(&inner, request).await
+                                <T as SoundService>::get_equalizer(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -8816,7 +12993,7 @@ pub mod sound_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = SoundMinSvc(inner);
+                        let method = GetEqualizerSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -8832,25 +13009,25 @@ pub mod sound_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.SoundService/SoundMax" => {
+                "/rockbox.v1alpha1.SoundService/UpdateEqualizer" => {
                     #[allow(non_camel_case_types)]
-                    struct SoundMaxSvc<T: SoundService>(pub Arc<T>);
+                    struct UpdateEqualizerSvc<T: SoundService>(pub Arc<T>);
                     impl<
                         T: SoundService,
-                    > tonic::server::UnaryService<super::SoundMaxRequest>
-                    for SoundMaxSvc<T> {
-                        type Response = super::SoundMaxResponse;
+                    > tonic::server::UnaryService<super::UpdateEqualizerRequest>
+                    for UpdateEqualizerSvc<T> {
+                        type Response = super::UpdateEqualizerResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SoundMaxRequest>,
+                            request: tonic::Request<super::UpdateEqualizerRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as SoundService>::sound_max(&inner, request).await
+                                <T as SoundService>::update_equalizer(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -8861,7 +13038,7 @@ pub mod sound_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = SoundMaxSvc(inner);
+                        let method = UpdateEqualizerSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -8877,25 +13054,25 @@ pub mod sound_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.SoundService/SoundUnit" => {
+                "/rockbox.v1alpha1.SoundService/SetEqBand" => {
                     #[allow(non_camel_case_types)]
-                    struct SoundUnitSvc<T: SoundService>(pub Arc<T>);
+                    struct SetEqBandSvc<T: SoundService>(pub Arc<T>);
                     impl<
                         T: SoundService,
-                    > tonic::server::UnaryService<super::SoundUnitRequest>
-                    for SoundUnitSvc<T> {
-                        type Response = super::SoundUnitResponse;
+                    > tonic::server::UnaryService<super::SetEqBandRequest>
+                    for SetEqBandSvc<T> {
+                        type Response = super::SetEqBandResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SoundUnitRequest>,
+                            request: tonic::Request<super::SetEqBandRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as SoundService>::sound_unit(&inner, request).await
+                                <T as SoundService>::set_eq_band(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -8906,7 +13083,7 @@ pub mod sound_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = SoundUnitSvc(inner);
+                        let method = SetEqBandSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -8922,25 +13099,25 @@ pub mod sound_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.SoundService/SoundVal2Phys" => {
+                "/rockbox.v1alpha1.SoundService/GetEqPresets" => {
                     #[allow(non_camel_case_types)]
-                    struct SoundVal2PhysSvc<T: SoundService>(pub Arc<T>);
+                    struct GetEqPresetsSvc<T: SoundService>(pub Arc<T>);
                     impl<
                         T: SoundService,
-                    > tonic::server::UnaryService<super::SoundVal2PhysRequest>
-                    for SoundVal2PhysSvc<T> {
-                        type Response = super::SoundVal2PhysResponse;
+                    > tonic::server::UnaryService<super::GetEqPresetsRequest>
+                    for GetEqPresetsSvc<T> {
+                        type Response = super::GetEqPresetsResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SoundVal2PhysRequest>,
+                            request: tonic::Request<super::GetEqPresetsRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as SoundService>::sound_val2_phys(&inner, request).await
+                                <T as SoundService>::get_eq_presets(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -8951,7 +13128,7 @@ pub mod sound_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = SoundVal2PhysSvc(inner);
+                        let method = GetEqPresetsSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -8967,25 +13144,25 @@ pub mod sound_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.SoundService/GetPitch" => {
+                "/rockbox.v1alpha1.SoundService/SaveEqPreset" => {
                     #[allow(non_camel_case_types)]
-                    struct GetPitchSvc<T: SoundService>(pub Arc<T>);
+                    struct SaveEqPresetSvc<T: SoundService>(pub Arc<T>);
                     impl<
                         T: SoundService,
-                    > tonic::server::UnaryService<super::GetPitchRequest>
-                    for GetPitchSvc<T> {
-                        type Response = super::GetPitchResponse;
+                    > tonic::server::UnaryService<super::SaveEqPresetRequest>
+                    for SaveEqPresetSvc<T> {
+                        type Response = super::SaveEqPresetResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetPitchRequest>,
+                            request: tonic::Request<super::SaveEqPresetRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as SoundService>::get_pitch(&inner, request).await
+                                <T as SoundService>::save_eq_preset(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -8996,7 +13173,7 @@ pub mod sound_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = GetPitchSvc(inner);
+                        let method = SaveEqPresetSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -9012,25 +13189,25 @@ pub mod sound_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.SoundService/SetPitch" => {
+                "/rockbox.v1alpha1.SoundService/ImportEqPreset" => {
                     #[allow(non_camel_case_types)]
-                    struct SetPitchSvc<T: SoundService>(pub Arc<T>);
+                    struct ImportEqPresetSvc<T: SoundService>(pub Arc<T>);
                     impl<
                         T: SoundService,
-                    > tonic::server::UnaryService<super::SetPitchRequest>
-                    for SetPitchSvc<T> {
-                        type Response = super::SetPitchResponse;
+                    > tonic::server::UnaryService<super::ImportEqPresetRequest>
+                    for ImportEqPresetSvc<T> {
+                        type Response = super::ImportEqPresetResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SetPitchRequest>,
+                            request: tonic::Request<super::ImportEqPresetRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as SoundService>::set_pitch(&inner, request).await
+                                <T as SoundService>::import_eq_preset(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -9041,7 +13218,7 @@ pub mod sound_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = SetPitchSvc(inner);
+                        let method = ImportEqPresetSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -9057,25 +13234,25 @@ pub mod sound_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.SoundService/BeepPlay" => {
+                "/rockbox.v1alpha1.SoundService/DeleteEqPreset" => {
                     #[allow(non_camel_case_types)]
-                    struct BeepPlaySvc<T: SoundService>(pub Arc<T>);
+                    struct DeleteEqPresetSvc<T: SoundService>(pub Arc<T>);
                     impl<
                         T: SoundService,
-                    > tonic::server::UnaryService<super::BeepPlayRequest>
-                    for BeepPlaySvc<T> {
-                        type Response = super::BeepPlayResponse;
+                    > tonic::server::UnaryService<super::DeleteEqPresetRequest>
+                    for DeleteEqPresetSvc<T> {
+                        type Response = super::DeleteEqPresetResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::BeepPlayRequest>,
+                            request: tonic::Request<super::DeleteEqPresetRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as SoundService>::beep_play(&inner, request).await
+                                <T as SoundService>::delete_eq_preset(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -9086,7 +13263,7 @@ pub mod sound_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = BeepPlaySvc(inner);
+                        let method = DeleteEqPresetSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -9102,25 +13279,25 @@ pub mod sound_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.SoundService/PcmbufFade" => {
+                "/rockbox.v1alpha1.SoundService/LoadEqPreset" => {
                     #[allow(non_camel_case_types)]
-                    struct PcmbufFadeSvc<T: SoundService>(pub Arc<T>);
+                    struct LoadEqPresetSvc<T: SoundService>(pub Arc<T>);
                     impl<
                         T: SoundService,
-                    > tonic::server::UnaryService<super::PcmbufFadeRequest>
-                    for PcmbufFadeSvc<T> {
-                        type Response = super::PcmbufFadeResponse;
+                    > tonic::server::UnaryService<super::LoadEqPresetRequest>
+                    for LoadEqPresetSvc<T> {
+                        type Response = super::LoadEqPresetResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::PcmbufFadeRequest>,
+                            request: tonic::Request<super::LoadEqPresetRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as SoundService>::pcmbuf_fade(&inner, request).await
+                                <T as SoundService>::load_eq_preset(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -9131,7 +13308,7 @@ pub mod sound_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = PcmbufFadeSvc(inner);
+                        let method = LoadEqPresetSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -9147,25 +13324,25 @@ pub mod sound_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.SoundService/PcmbufSetLowLatency" => {
+                "/rockbox.v1alpha1.SoundService/GetEqDevicePresets" => {
                     #[allow(non_camel_case_types)]
-                    struct PcmbufSetLowLatencySvc<T: SoundService>(pub Arc<T>);
+                    struct GetEqDevicePresetsSvc<T: SoundService>(pub Arc<T>);
                     impl<
                         T: SoundService,
-                    > tonic::server::UnaryService<super::PcmbufSetLowLatencyRequest>
-                    for PcmbufSetLowLatencySvc<T> {
-                        type Response = super::PcmbufSetLowLatencyResponse;
+                    > tonic::server::UnaryService<super::GetEqDevicePresetsRequest>
+                    for GetEqDevicePresetsSvc<T> {
+                        type Response = super::GetEqDevicePresetsResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::PcmbufSetLowLatencyRequest>,
+                            request: tonic::Request<super::GetEqDevicePresetsRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as SoundService>::pcmbuf_set_low_latency(&inner, request)
+                                <T as SoundService>::get_eq_device_presets(&inner, request)
                                     .await
                             };
                             Box::pin(fut)
@@ -9177,7 +13354,7 @@ pub mod sound_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = PcmbufSetLowLatencySvc(inner);
+                        let method = GetEqDevicePresetsSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -9193,25 +13370,70 @@ pub mod sound_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.SoundService/SystemSoundPlay" => {
+                "/rockbox.v1alpha1.SoundService/BindEqPreset" => {
                     #[allow(non_camel_case_types)]
-                    struct SystemSoundPlaySvc<T: SoundService>(pub Arc<T>);
+                    struct BindEqPresetSvc<T: SoundService>(pub Arc<T>);
                     impl<
                         T: SoundService,
-                    > tonic::server::UnaryService<super::SystemSoundPlayRequest>
-                    for SystemSoundPlaySvc<T> {
-                        type Response = super::SystemSoundPlayResponse;
+                    > tonic::server::UnaryService<super::BindEqPresetRequest>
+                    for BindEqPresetSvc<T> {
+                        type Response = super::BindEqPresetResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SystemSoundPlayRequest>,
+                            request: tonic::Request<super::BindEqPresetRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as SoundService>::system_sound_play(&inner, request)
+                                <T as SoundService>::bind_eq_preset(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = BindEqPresetSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.SoundService/GetEqComparison" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetEqComparisonSvc<T: SoundService>(pub Arc<T>);
+                    impl<
+                        T: SoundService,
+                    > tonic::server::UnaryService<super::GetEqComparisonRequest>
+                    for GetEqComparisonSvc<T> {
+                        type Response = super::GetEqComparisonResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetEqComparisonRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SoundService>::get_eq_comparison(&inner, request)
                                     .await
                             };
                             Box::pin(fut)
@@ -9223,7 +13445,7 @@ pub mod sound_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = SystemSoundPlaySvc(inner);
+                        let method = GetEqComparisonSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -9239,25 +13461,25 @@ pub mod sound_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/rockbox.v1alpha1.SoundService/KeyclickClick" => {
+                "/rockbox.v1alpha1.SoundService/SetEqSlot" => {
                     #[allow(non_camel_case_types)]
-                    struct KeyclickClickSvc<T: SoundService>(pub Arc<T>);
+                    struct SetEqSlotSvc<T: SoundService>(pub Arc<T>);
                     impl<
                         T: SoundService,
-                    > tonic::server::UnaryService<super::KeyclickClickRequest>
-                    for KeyclickClickSvc<T> {
-                        type Response = super::KeyclickClickResponse;
+                    > tonic::server::UnaryService<super::SetEqSlotRequest>
+                    for SetEqSlotSvc<T> {
+                        type Response = super::SetEqSlotResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::KeyclickClickRequest>,
+                            request: tonic::Request<super::SetEqSlotRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as SoundService>::keyclick_click(&inner, request).await
+                                <T as SoundService>::set_eq_slot(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -9268,7 +13490,52 @@ pub mod sound_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = KeyclickClickSvc(inner);
+                        let method = SetEqSlotSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/rockbox.v1alpha1.SoundService/ToggleEqSlot" => {
+                    #[allow(non_camel_case_types)]
+                    struct ToggleEqSlotSvc<T: SoundService>(pub Arc<T>);
+                    impl<
+                        T: SoundService,
+                    > tonic::server::UnaryService<super::ToggleEqSlotRequest>
+                    for ToggleEqSlotSvc<T> {
+                        type Response = super::ToggleEqSlotResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ToggleEqSlotRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SoundService>::toggle_eq_slot(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ToggleEqSlotSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
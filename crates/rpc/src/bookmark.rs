@@ -0,0 +1,173 @@
+use rockbox_service::RockboxService;
+use rockbox_types::bookmark::{NewBookmark, UpdateBookmark};
+
+use crate::api::rockbox::v1alpha1::{bookmark_service_server::BookmarkService, *};
+
+pub struct Bookmarks {
+    service: RockboxService,
+}
+
+impl Bookmarks {
+    pub fn new(service: RockboxService) -> Self {
+        Self { service }
+    }
+}
+
+fn invalid_source(e: prost::UnknownEnumValue) -> tonic::Status {
+    tonic::Status::invalid_argument(e.to_string())
+}
+
+#[tonic::async_trait]
+impl BookmarkService for Bookmarks {
+    async fn get_bookmarks(
+        &self,
+        _request: tonic::Request<GetBookmarksRequest>,
+    ) -> Result<tonic::Response<GetBookmarksResponse>, tonic::Status> {
+        let bookmarks = self
+            .service
+            .bookmarks()
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        Ok(tonic::Response::new(GetBookmarksResponse {
+            bookmarks: bookmarks.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn get_bookmark(
+        &self,
+        request: tonic::Request<GetBookmarkRequest>,
+    ) -> Result<tonic::Response<GetBookmarkResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let bookmark = self
+            .service
+            .bookmark(&request.id)
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        Ok(tonic::Response::new(GetBookmarkResponse {
+            bookmark: bookmark.map(Into::into),
+        }))
+    }
+
+    async fn create_bookmark(
+        &self,
+        request: tonic::Request<CreateBookmarkRequest>,
+    ) -> Result<tonic::Response<CreateBookmarkResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let source = match request.source {
+            Some(source) => Some(BookmarkSource::try_from(source).map_err(invalid_source)?.into()),
+            None => None,
+        };
+        let bookmark = NewBookmark {
+            name: request.name,
+            path: request.path,
+            elapsed: request.elapsed,
+            source,
+        };
+        let bookmark = self
+            .service
+            .create_bookmark(bookmark)
+            .await
+            .map_err(|e| tonic::Status::invalid_argument(e.to_string()))?;
+        Ok(tonic::Response::new(CreateBookmarkResponse {
+            bookmark: Some(bookmark.into()),
+        }))
+    }
+
+    async fn update_bookmark(
+        &self,
+        request: tonic::Request<UpdateBookmarkRequest>,
+    ) -> Result<tonic::Response<UpdateBookmarkResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let update = UpdateBookmark {
+            name: request.name,
+            elapsed: request.elapsed,
+        };
+        let bookmark = self
+            .service
+            .update_bookmark(&request.id, update)
+            .await
+            .map_err(|e| tonic::Status::invalid_argument(e.to_string()))?
+            .ok_or_else(|| tonic::Status::not_found("Bookmark not found"))?;
+        Ok(tonic::Response::new(UpdateBookmarkResponse {
+            bookmark: Some(bookmark.into()),
+        }))
+    }
+
+    async fn delete_bookmark(
+        &self,
+        request: tonic::Request<DeleteBookmarkRequest>,
+    ) -> Result<tonic::Response<DeleteBookmarkResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let deleted = self
+            .service
+            .delete_bookmark(&request.id)
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        if !deleted {
+            return Err(tonic::Status::not_found("Bookmark not found"));
+        }
+        Ok(tonic::Response::new(DeleteBookmarkResponse {}))
+    }
+
+    async fn play_bookmark(
+        &self,
+        request: tonic::Request<PlayBookmarkRequest>,
+    ) -> Result<tonic::Response<PlayBookmarkResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let found = self
+            .service
+            .play_bookmark(&request.id)
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        if !found {
+            return Err(tonic::Status::not_found("Bookmark not found"));
+        }
+        Ok(tonic::Response::new(PlayBookmarkResponse {}))
+    }
+
+    async fn get_track_chapters(
+        &self,
+        request: tonic::Request<GetTrackChaptersRequest>,
+    ) -> Result<tonic::Response<GetTrackChaptersResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let chapters = self
+            .service
+            .track_chapters(&request.id)
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?
+            .ok_or_else(|| tonic::Status::not_found("Track not found"))?;
+        Ok(tonic::Response::new(GetTrackChaptersResponse {
+            chapters: chapters.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn get_current_chapters(
+        &self,
+        _request: tonic::Request<GetCurrentChaptersRequest>,
+    ) -> Result<tonic::Response<GetCurrentChaptersResponse>, tonic::Status> {
+        let chapters = self
+            .service
+            .current_chapters()
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        Ok(tonic::Response::new(GetCurrentChaptersResponse {
+            chapters: chapters.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn seek_chapter(
+        &self,
+        request: tonic::Request<SeekChapterRequest>,
+    ) -> Result<tonic::Response<SeekChapterResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let found = self
+            .service
+            .seek_chapter(request.index as usize)
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        if !found {
+            return Err(tonic::Status::not_found("Chapter not found"));
+        }
+        Ok(tonic::Response::new(SeekChapterResponse {}))
+    }
+}
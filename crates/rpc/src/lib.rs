@@ -1,23 +1,22 @@
-use anyhow::Error;
-use futures::{future::BoxFuture, stream::FuturesUnordered, StreamExt};
-use tokio::fs;
-
+pub mod bookmark;
 pub mod browse;
 pub mod device;
 pub mod library;
 pub mod metadata;
 pub mod playback;
 pub mod playlist;
+pub mod scheduler;
 pub mod server;
 pub mod settings;
 pub mod sound;
 pub mod system;
 pub mod types;
 
-pub const AUDIO_EXTENSIONS: [&str; 17] = [
-    "mp3", "ogg", "flac", "m4a", "aac", "mp4", "alac", "wav", "wv", "mpc", "aiff", "ac3", "opus",
-    "spx", "sid", "ape", "wma",
-];
+pub use rockbox_service::{read_files, AUDIO_EXTENSIONS};
+
+/// Set on the library requests an instance sends to the others, they answer
+/// with their own library only so instances don't query each other in turn.
+pub const REMOTE_QUERY_HEADER: &str = "x-rockbox-remote-query";
 
 pub mod api {
     #[path = ""]
@@ -28,7 +27,7 @@ pub mod api {
             system_status::SystemStatus,
             user_settings::{
                 CompressorSettings, EqBandSetting, NewGlobalSettings, ReplaygainSettings,
-                UserSettings,
+                ResumeSettings, TransitionMode, TransitionSettings, UserSettings,
             },
         };
         use tantivy::schema::Schema;
@@ -36,9 +35,10 @@ pub mod api {
         use tantivy::schema::*;
         use tantivy::TantivyDocument;
         use v1alpha1::{
-            Album, Artist, CurrentTrackResponse, Device, Entry, GetGlobalSettingsResponse,
-            GetGlobalStatusResponse, NextTrackResponse, SaveSettingsRequest, SearchResponse,
-            StatusResponse, Track,
+            Album, Artist, CurrentLyricLineResponse, CurrentTrackResponse, Device, Entry,
+            FacetCount, Genre, GetGlobalSettingsResponse, GetGlobalStatusResponse, ListOptions,
+            LyricLine, Lyrics, NextTrackResponse, SaveSettingsRequest, SearchFacets,
+            SearchResponse, StatusResponse, Suggestion, Track,
         };
 
         #[path = "rockbox.v1alpha1.rs"]
@@ -522,9 +522,13 @@ pub mod api {
                 let governor = settings.governor;
                 let stereosw_mode = settings.stereosw_mode;
                 let music_dir = settings.music_dir;
+                let transitions = v1alpha1::TransitionSettings::from(settings.transitions);
+                let resume = v1alpha1::ResumeSettings::from(settings.resume);
 
                 GetGlobalSettingsResponse {
                     music_dir,
+                    transitions: Some(transitions),
+                    resume: Some(resume),
                     volume,
                     balance,
                     bass,
@@ -763,6 +767,37 @@ pub mod api {
                     image: artist.image,
                     albums: vec![],
                     tracks: vec![],
+                    appears_on: vec![],
+                    musicbrainz_id: artist.musicbrainz_id,
+                }
+            }
+        }
+
+        impl From<ListOptions> for rockbox_library::list::ListOptions {
+            fn from(options: ListOptions) -> Self {
+                Self {
+                    offset: options.offset,
+                    limit: options.limit,
+                    sort: options.sort,
+                    descending: options.descending.unwrap_or(false),
+                    artist_id: options.artist_id,
+                    album_id: options.album_id,
+                    genre_id: options.genre_id,
+                    year_from: options.year_from,
+                    year_to: options.year_to,
+                    group_editions: options.group_editions.unwrap_or(false),
+                }
+            }
+        }
+
+        impl From<rockbox_library::entity::genre::Genre> for Genre {
+            fn from(genre: rockbox_library::entity::genre::Genre) -> Self {
+                Self {
+                    id: genre.id,
+                    name: genre.name,
+                    description: genre.description,
+                    image: genre.image,
+                    tracks: vec![],
                 }
             }
         }
@@ -779,6 +814,9 @@ pub mod api {
                     md5: album.md5,
                     artist_id: album.artist_id,
                     tracks: vec![],
+                    musicbrainz_id: album.musicbrainz_id,
+                    musicbrainz_release_group_id: album.musicbrainz_release_group_id,
+                    editions: vec![],
                 }
             }
         }
@@ -809,6 +847,93 @@ pub mod api {
                     genre_id: Some(track.genre_id),
                     created_at: track.created_at.to_rfc3339(),
                     updated_at: track.updated_at.to_rfc3339(),
+                    musicbrainz_id: track.musicbrainz_id,
+                    replaygain_track_gain: track.replaygain_track_gain,
+                    replaygain_track_peak: track.replaygain_track_peak,
+                    replaygain_album_gain: track.replaygain_album_gain,
+                    replaygain_album_peak: track.replaygain_album_peak,
+                    bpm: track.bpm,
+                    rating: track.rating,
+                    comment: track.comment,
+                    label: track.label,
+                    isrc: track.isrc,
+                    original_date: track.original_date,
+                    title_sort: track.title_sort,
+                    artist_sort: track.artist_sort,
+                    album_sort: track.album_sort,
+                    album_artist_sort: track.album_artist_sort,
+                    composer_sort: track.composer_sort,
+                    lyrics: track.lyrics,
+                    play_count: track.play_count,
+                }
+            }
+        }
+
+        impl From<Album> for rockbox_library::entity::album::Album {
+            fn from(album: Album) -> Self {
+                Self {
+                    id: album.id,
+                    title: album.title,
+                    artist: album.artist,
+                    year: album.year,
+                    year_string: album.year_string,
+                    album_art: album.album_art,
+                    md5: album.md5,
+                    artist_id: album.artist_id,
+                    musicbrainz_id: album.musicbrainz_id,
+                    musicbrainz_release_group_id: album.musicbrainz_release_group_id,
+                }
+            }
+        }
+
+        impl From<Track> for rockbox_library::entity::track::Track {
+            fn from(track: Track) -> Self {
+                Self {
+                    id: track.id,
+                    path: track.path,
+                    title: track.title,
+                    artist: track.artist,
+                    album: track.album,
+                    album_artist: track.album_artist,
+                    bitrate: track.bitrate,
+                    composer: track.composer,
+                    disc_number: track.disc_number,
+                    filesize: track.filesize,
+                    frequency: track.frequency,
+                    length: track.length,
+                    track_number: Some(track.track_number),
+                    year: Some(track.year),
+                    year_string: Some(track.year_string),
+                    genre: Some(track.genre),
+                    md5: track.md5,
+                    album_art: track.album_art,
+                    artist_id: track.artist_id.unwrap_or_default(),
+                    album_id: track.album_id.unwrap_or_default(),
+                    genre_id: track.genre_id.unwrap_or_default(),
+                    created_at: chrono::DateTime::parse_from_rfc3339(&track.created_at)
+                        .map(|d| d.with_timezone(&chrono::Utc))
+                        .unwrap_or_default(),
+                    updated_at: chrono::DateTime::parse_from_rfc3339(&track.updated_at)
+                        .map(|d| d.with_timezone(&chrono::Utc))
+                        .unwrap_or_default(),
+                    musicbrainz_id: track.musicbrainz_id,
+                    replaygain_track_gain: track.replaygain_track_gain,
+                    replaygain_track_peak: track.replaygain_track_peak,
+                    replaygain_album_gain: track.replaygain_album_gain,
+                    replaygain_album_peak: track.replaygain_album_peak,
+                    bpm: track.bpm,
+                    rating: track.rating,
+                    comment: track.comment,
+                    label: track.label,
+                    isrc: track.isrc,
+                    original_date: track.original_date,
+                    title_sort: track.title_sort,
+                    artist_sort: track.artist_sort,
+                    album_sort: track.album_sort,
+                    album_artist_sort: track.album_artist_sort,
+                    composer_sort: track.composer_sort,
+                    lyrics: track.lyrics,
+                    play_count: track.play_count,
                 }
             }
         }
@@ -825,6 +950,9 @@ pub mod api {
                     md5: album.md5,
                     artist_id: album.artist_id,
                     tracks: vec![],
+                    musicbrainz_id: None,
+                    musicbrainz_release_group_id: None,
+                    editions: vec![],
                 }
             }
         }
@@ -838,6 +966,20 @@ pub mod api {
                     image: artist.image,
                     albums: vec![],
                     tracks: vec![],
+                    appears_on: vec![],
+                    musicbrainz_id: None,
+                }
+            }
+        }
+
+        impl From<rockbox_search::genre::Genre> for Genre {
+            fn from(genre: rockbox_search::genre::Genre) -> Self {
+                Self {
+                    id: genre.id,
+                    name: genre.name,
+                    description: genre.description,
+                    image: genre.image,
+                    tracks: vec![],
                 }
             }
         }
@@ -868,6 +1010,8 @@ pub mod api {
                     genre_id: track.genre_id,
                     created_at: track.created_at,
                     updated_at: track.updated_at,
+                    musicbrainz_id: None,
+                    ..Default::default()
                 }
             }
         }
@@ -889,11 +1033,87 @@ pub mod api {
                     .into_iter()
                     .map(|track| track.into())
                     .collect();
+                let genres = results
+                    .genres
+                    .into_iter()
+                    .map(|genre| genre.into())
+                    .collect();
 
                 Self {
                     artists,
                     albums,
                     tracks,
+                    genres,
+                    total_tracks: results.totals.tracks as i32,
+                    total_albums: results.totals.albums as i32,
+                    total_artists: results.totals.artists as i32,
+                    facets: Some(results.facets.into()),
+                }
+            }
+        }
+
+        impl From<rockbox_search::query::Facets> for SearchFacets {
+            fn from(facets: rockbox_search::query::Facets) -> Self {
+                let counts = |counts: Vec<rockbox_search::query::FacetCount>| {
+                    counts
+                        .into_iter()
+                        .map(|facet| FacetCount {
+                            value: facet.value,
+                            count: facet.count as i32,
+                        })
+                        .collect()
+                };
+                Self {
+                    genres: counts(facets.genres),
+                    decades: counts(facets.decades),
+                    formats: counts(facets.formats),
+                }
+            }
+        }
+
+        impl From<rockbox_search::suggestion::Suggestion> for Suggestion {
+            fn from(suggestion: rockbox_search::suggestion::Suggestion) -> Self {
+                Self {
+                    id: suggestion.id,
+                    kind: suggestion.kind,
+                    label: suggestion.label,
+                    artist: suggestion.artist,
+                    cover: suggestion.cover,
+                    popularity: suggestion.popularity as i64,
+                }
+            }
+        }
+
+        impl From<rockbox_library::lrc::LyricLine> for LyricLine {
+            fn from(line: rockbox_library::lrc::LyricLine) -> Self {
+                Self {
+                    time: line.time,
+                    text: line.text,
+                }
+            }
+        }
+
+        impl From<rockbox_library::entity::lyrics::Lyrics> for Lyrics {
+            fn from(lyrics: rockbox_library::entity::lyrics::Lyrics) -> Self {
+                let lines =
+                    rockbox_library::lrc::parse(lyrics.synced.as_deref().unwrap_or_default());
+                Self {
+                    track_id: lyrics.track_id,
+                    text: lyrics.lyrics,
+                    synced: !lines.is_empty(),
+                    lines: lines.into_iter().map(Into::into).collect(),
+                }
+            }
+        }
+
+        impl From<schema::objects::lyrics::CurrentLyricLine> for CurrentLyricLineResponse {
+            fn from(line: schema::objects::lyrics::CurrentLyricLine) -> Self {
+                Self {
+                    track_id: line.track_id,
+                    index: line.index,
+                    time: line.time,
+                    text: line.text,
+                    next_time: line.next_time,
                 }
             }
         }
@@ -1182,6 +1402,8 @@ pub mod api {
                     genre_id,
                     created_at,
                     updated_at,
+                    musicbrainz_id: None,
+                    ..Default::default()
                 }
             }
         }
@@ -1234,6 +1456,8 @@ pub mod api {
                             preamp: settings.preamp,
                         }
                     }),
+                    transitions: self.transitions.map(|settings| settings.into()),
+                    resume: self.resume.map(|settings| settings.into()),
                 }
             }
         }
@@ -1256,70 +1480,253 @@ pub mod api {
                 }
             }
         }
-    }
-}
 
-pub fn rockbox_url() -> String {
-    let port = std::env::var("ROCKBOX_TCP_PORT").unwrap_or_else(|_| "6063".to_string());
-    format!("http://127.0.0.1:{}", port)
-}
+        impl From<rockbox_types::EqBandSetting> for v1alpha1::EqBandSetting {
+            fn from(band: rockbox_types::EqBandSetting) -> Self {
+                Self {
+                    cutoff: band.cutoff,
+                    q: band.q,
+                    gain: band.gain,
+                }
+            }
+        }
 
-pub fn read_files(path: String) -> BoxFuture<'static, Result<Vec<String>, Error>> {
-    Box::pin(async move {
-        let mut result = Vec::new();
-        let mut dir = fs::read_dir(path).await?;
-        let mut futures = FuturesUnordered::new();
-        while let Some(entry) = dir.next_entry().await? {
-            let path = entry.path();
-            if path.is_dir() {
-                let dir_path = path.clone();
-                futures.push(tokio::spawn(async move {
-                    read_files(dir_path.to_str().unwrap().to_string()).await
-                }));
-            } else if path.is_file() {
-                if !AUDIO_EXTENSIONS
-                    .into_iter()
-                    .any(|ext| path.to_str().unwrap().ends_with(&format!(".{}", ext)))
-                {
-                    continue;
+        impl From<v1alpha1::EqBandSetting> for rockbox_types::EqBandSetting {
+            fn from(band: v1alpha1::EqBandSetting) -> Self {
+                Self {
+                    cutoff: band.cutoff,
+                    q: band.q,
+                    gain: band.gain,
+                }
+            }
+        }
+
+        impl From<rockbox_types::dsp::Equalizer> for v1alpha1::Equalizer {
+            fn from(equalizer: rockbox_types::dsp::Equalizer) -> Self {
+                Self {
+                    enabled: equalizer.enabled,
+                    precut: equalizer.precut,
+                    bands: equalizer.bands.into_iter().map(Into::into).collect(),
                 }
-                result.push(path.to_str().unwrap().to_string());
             }
         }
-        while let Some(Ok(future)) = futures.next().await {
-            result.extend(future?);
+
+        impl From<v1alpha1::Equalizer> for rockbox_types::dsp::Equalizer {
+            fn from(equalizer: v1alpha1::Equalizer) -> Self {
+                Self {
+                    enabled: equalizer.enabled,
+                    precut: equalizer.precut,
+                    bands: equalizer.bands.into_iter().map(Into::into).collect(),
+                }
+            }
+        }
+
+        impl From<rockbox_types::dsp::EqPreset> for v1alpha1::EqPreset {
+            fn from(preset: rockbox_types::dsp::EqPreset) -> Self {
+                Self {
+                    name: preset.name,
+                    equalizer: Some(preset.equalizer.into()),
+                }
+            }
+        }
+
+        impl From<rockbox_types::dsp::EqSlot> for v1alpha1::EqSlot {
+            fn from(slot: rockbox_types::dsp::EqSlot) -> Self {
+                match slot {
+                    rockbox_types::dsp::EqSlot::A => v1alpha1::EqSlot::A,
+                    rockbox_types::dsp::EqSlot::B => v1alpha1::EqSlot::B,
+                }
+            }
+        }
+
+        impl From<v1alpha1::EqSlot> for rockbox_types::dsp::EqSlot {
+            fn from(slot: v1alpha1::EqSlot) -> Self {
+                match slot {
+                    v1alpha1::EqSlot::A => rockbox_types::dsp::EqSlot::A,
+                    v1alpha1::EqSlot::B => rockbox_types::dsp::EqSlot::B,
+                }
+            }
+        }
+
+        impl From<rockbox_types::dsp::EqComparison> for v1alpha1::EqComparison {
+            fn from(comparison: rockbox_types::dsp::EqComparison) -> Self {
+                Self {
+                    slot: v1alpha1::EqSlot::from(comparison.slot) as i32,
+                    a: Some(comparison.a.into()),
+                    b: Some(comparison.b.into()),
+                }
+            }
+        }
+
+        impl From<rockbox_types::scheduler::SleepTimer> for v1alpha1::SleepTimer {
+            fn from(timer: rockbox_types::scheduler::SleepTimer) -> Self {
+                Self {
+                    active: timer.active,
+                    duration: timer.duration,
+                    remaining: timer.remaining,
+                    fade_out: timer.fade_out,
+                }
+            }
         }
-        Ok(result)
-    })
-}
 
-#[macro_export]
-macro_rules! check_and_load_player {
-    ($response:expr, $tracks:expr, $shuffle:expr) => {
-        let client = reqwest::Client::new();
-        let response = client
-            .get(format!("{}/player", rockbox_url()))
-            .send()
-            .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?;
-        let player = response
-            .json::<rockbox_types::device::Device>()
-            .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?;
-
-        if !player.host.is_empty() && player.port != 0 {
-            let client = reqwest::Client::new();
-            let body = serde_json::json!({
-                "tracks": $tracks,
-                "shuffle": $shuffle,
-            });
-            client
-                .put(&format!("{}/player/load", rockbox_url()))
-                .json(&body)
-                .send()
-                .await
-                .map_err(|e| tonic::Status::internal(e.to_string()))?;
-            return Ok(tonic::Response::new($response));
+        impl From<rockbox_types::scheduler::PlaybackSource> for v1alpha1::PlaybackSource {
+            fn from(source: rockbox_types::scheduler::PlaybackSource) -> Self {
+                use rockbox_types::scheduler::PlaybackSource;
+                match source {
+                    PlaybackSource::Playlist => v1alpha1::PlaybackSource::Playlist,
+                    PlaybackSource::Album => v1alpha1::PlaybackSource::Album,
+                    PlaybackSource::Artist => v1alpha1::PlaybackSource::Artist,
+                    PlaybackSource::Directory => v1alpha1::PlaybackSource::Directory,
+                    PlaybackSource::LikedTracks => v1alpha1::PlaybackSource::LikedTracks,
+                    PlaybackSource::AllTracks => v1alpha1::PlaybackSource::AllTracks,
+                    PlaybackSource::Mix => v1alpha1::PlaybackSource::Mix,
+                }
+            }
         }
-    };
+
+        impl From<v1alpha1::PlaybackSource> for rockbox_types::scheduler::PlaybackSource {
+            fn from(source: v1alpha1::PlaybackSource) -> Self {
+                use rockbox_types::scheduler::PlaybackSource;
+                match source {
+                    v1alpha1::PlaybackSource::Playlist => PlaybackSource::Playlist,
+                    v1alpha1::PlaybackSource::Album => PlaybackSource::Album,
+                    v1alpha1::PlaybackSource::Artist => PlaybackSource::Artist,
+                    v1alpha1::PlaybackSource::Directory => PlaybackSource::Directory,
+                    v1alpha1::PlaybackSource::LikedTracks => PlaybackSource::LikedTracks,
+                    v1alpha1::PlaybackSource::AllTracks => PlaybackSource::AllTracks,
+                    v1alpha1::PlaybackSource::Mix => PlaybackSource::Mix,
+                }
+            }
+        }
+
+        impl From<rockbox_types::scheduler::Schedule> for v1alpha1::Schedule {
+            fn from(schedule: rockbox_types::scheduler::Schedule) -> Self {
+                Self {
+                    id: schedule.id,
+                    name: schedule.name,
+                    cron: schedule.cron,
+                    source: v1alpha1::PlaybackSource::from(schedule.source) as i32,
+                    source_id: schedule.source_id,
+                    shuffle: schedule.shuffle,
+                    volume: schedule.volume,
+                    fade_in: schedule.fade_in,
+                    once: schedule.once,
+                    enabled: schedule.enabled,
+                    last_run_at: schedule.last_run_at,
+                    next_run_at: schedule.next_run_at,
+                    created_at: schedule.created_at,
+                }
+            }
+        }
+
+        impl From<TransitionMode> for v1alpha1::TransitionMode {
+            fn from(mode: TransitionMode) -> Self {
+                match mode {
+                    TransitionMode::Crossfade => v1alpha1::TransitionMode::Crossfade,
+                    TransitionMode::Smart => v1alpha1::TransitionMode::Smart,
+                    TransitionMode::BeatMatch => v1alpha1::TransitionMode::BeatMatch,
+                }
+            }
+        }
+
+        impl From<v1alpha1::TransitionMode> for TransitionMode {
+            fn from(mode: v1alpha1::TransitionMode) -> Self {
+                match mode {
+                    v1alpha1::TransitionMode::Crossfade => TransitionMode::Crossfade,
+                    v1alpha1::TransitionMode::Smart => TransitionMode::Smart,
+                    v1alpha1::TransitionMode::BeatMatch => TransitionMode::BeatMatch,
+                }
+            }
+        }
+
+        impl From<TransitionSettings> for v1alpha1::TransitionSettings {
+            fn from(settings: TransitionSettings) -> Self {
+                Self {
+                    album: v1alpha1::TransitionMode::from(settings.album) as i32,
+                    mix: v1alpha1::TransitionMode::from(settings.mix) as i32,
+                }
+            }
+        }
+
+        impl From<v1alpha1::TransitionSettings> for TransitionSettings {
+            fn from(settings: v1alpha1::TransitionSettings) -> Self {
+                let mode = |mode: i32| {
+                    v1alpha1::TransitionMode::try_from(mode)
+                        .map(TransitionMode::from)
+                        .unwrap_or_default()
+                };
+                Self {
+                    album: mode(settings.album),
+                    mix: mode(settings.mix),
+                }
+            }
+        }
+
+        impl From<ResumeSettings> for v1alpha1::ResumeSettings {
+            fn from(settings: ResumeSettings) -> Self {
+                Self {
+                    min_length: settings.min_length,
+                    directories: settings.directories,
+                }
+            }
+        }
+
+        impl From<v1alpha1::ResumeSettings> for ResumeSettings {
+            fn from(settings: v1alpha1::ResumeSettings) -> Self {
+                Self {
+                    min_length: settings.min_length,
+                    directories: settings.directories,
+                }
+            }
+        }
+
+        impl From<rockbox_types::bookmark::BookmarkSource> for v1alpha1::BookmarkSource {
+            fn from(source: rockbox_types::bookmark::BookmarkSource) -> Self {
+                use rockbox_types::bookmark::BookmarkSource;
+                match source {
+                    BookmarkSource::Track => v1alpha1::BookmarkSource::Track,
+                    BookmarkSource::Album => v1alpha1::BookmarkSource::Album,
+                    BookmarkSource::Directory => v1alpha1::BookmarkSource::Directory,
+                }
+            }
+        }
+
+        impl From<v1alpha1::BookmarkSource> for rockbox_types::bookmark::BookmarkSource {
+            fn from(source: v1alpha1::BookmarkSource) -> Self {
+                use rockbox_types::bookmark::BookmarkSource;
+                match source {
+                    v1alpha1::BookmarkSource::Track => BookmarkSource::Track,
+                    v1alpha1::BookmarkSource::Album => BookmarkSource::Album,
+                    v1alpha1::BookmarkSource::Directory => BookmarkSource::Directory,
+                }
+            }
+        }
+
+        impl From<rockbox_types::bookmark::Bookmark> for v1alpha1::Bookmark {
+            fn from(bookmark: rockbox_types::bookmark::Bookmark) -> Self {
+                Self {
+                    id: bookmark.id,
+                    name: bookmark.name,
+                    source: v1alpha1::BookmarkSource::from(bookmark.source) as i32,
+                    source_id: bookmark.source_id,
+                    path: bookmark.path,
+                    track_id: bookmark.track_id,
+                    elapsed: bookmark.elapsed,
+                    created_at: bookmark.created_at,
+                    updated_at: bookmark.updated_at,
+                }
+            }
+        }
+
+        impl From<rockbox_library::chapters::Chapter> for v1alpha1::Chapter {
+            fn from(chapter: rockbox_library::chapters::Chapter) -> Self {
+                Self {
+                    index: chapter.index as u32,
+                    title: chapter.title,
+                    start: chapter.start,
+                    end: chapter.end,
+                }
+            }
+        }
+    }
 }
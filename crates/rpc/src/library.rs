@@ -1,37 +1,44 @@
-use rockbox_library::{entity::favourites::Favourites, repo};
-use rockbox_search::search_entities;
-use sqlx::Sqlite;
+use std::pin::Pin;
+
+use rockbox_graphql::schema::lyrics::current_lyric_lines;
+use rockbox_library::{entity::favourites::Favourites, list, lrc, repo};
+use rockbox_search::{
+    query::{SearchOptions, SortBy, DEFAULT_LIMIT},
+    search_library,
+    suggestion::autocomplete,
+};
+use rockbox_service::{remote::RemoteQuery, RockboxService};
+use tokio_stream::{Stream, StreamExt};
 
 use crate::{
     api::rockbox::v1alpha1::{
-        library_service_server::LibraryService, Album, Artist, GetAlbumRequest, GetAlbumResponse,
+        library_service_server::LibraryService, Album, Artist, AutocompleteRequest,
+        AutocompleteResponse, CurrentLyricLineResponse, Genre, GetAlbumRequest, GetAlbumResponse,
         GetAlbumsRequest, GetAlbumsResponse, GetArtistRequest, GetArtistResponse,
-        GetArtistsRequest, GetArtistsResponse, GetLikedAlbumsRequest, GetLikedAlbumsResponse,
-        GetLikedTracksRequest, GetLikedTracksResponse, GetTrackRequest, GetTrackResponse,
-        GetTracksRequest, GetTracksResponse, LikeAlbumRequest, LikeAlbumResponse, LikeTrackRequest,
-        LikeTrackResponse, ScanLibraryRequest, ScanLibraryResponse, SearchRequest, SearchResponse,
-        UnlikeAlbumRequest, UnlikeAlbumResponse, UnlikeTrackRequest, UnlikeTrackResponse,
+        GetArtistsRequest, GetArtistsResponse, GetGenreRequest, GetGenreResponse, GetGenresRequest,
+        GetGenresResponse, GetLikedAlbumsRequest, GetLikedAlbumsResponse, GetLikedTracksRequest,
+        GetLikedTracksResponse, GetLyricsRequest, GetLyricsResponse, GetTrackRequest,
+        GetTrackResponse, GetTracksRequest, GetTracksResponse, LikeAlbumRequest, LikeAlbumResponse,
+        LikeTrackRequest, LikeTrackResponse, ScanLibraryRequest, ScanLibraryResponse,
+        SearchRequest, SearchResponse, StreamLyricsRequest, UnlikeAlbumRequest,
+        UnlikeAlbumResponse, UnlikeTrackRequest, UnlikeTrackResponse,
     },
-    rockbox_url,
+    REMOTE_QUERY_HEADER,
 };
 
 pub struct Library {
-    pool: sqlx::Pool<Sqlite>,
-    client: reqwest::Client,
-    indexes: rockbox_search::Indexes,
+    service: RockboxService,
 }
 
 impl Library {
-    pub fn new(
-        pool: sqlx::Pool<Sqlite>,
-        client: reqwest::Client,
-        indexes: rockbox_search::Indexes,
-    ) -> Self {
-        Self {
-            pool,
-            client,
-            indexes,
-        }
+    pub fn new(service: RockboxService) -> Self {
+        Self { service }
+    }
+
+    /// The libraries of the other instances are merged in the answers to
+    /// clients, not in those to the other instances.
+    fn merges_remote_sources<T>(request: &tonic::Request<T>) -> bool {
+        request.metadata().get(REMOTE_QUERY_HEADER).is_none()
     }
 }
 
@@ -39,37 +46,64 @@ impl Library {
 impl LibraryService for Library {
     async fn get_albums(
         &self,
-        _request: tonic::Request<GetAlbumsRequest>,
+        request: tonic::Request<GetAlbumsRequest>,
     ) -> Result<tonic::Response<GetAlbumsResponse>, tonic::Status> {
-        let albums = repo::album::all(self.pool.clone())
+        let remote = Self::merges_remote_sources(&request);
+        let options: list::ListOptions = request.into_inner().options.unwrap_or_default().into();
+        let mut albums = repo::album::list(self.service.pool.clone(), &options)
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        let total = repo::album::count(self.service.pool.clone(), &options)
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        // remote libraries can't be paginated nor filtered, only merge them in full listings
+        if remote && !options.is_paginated() && !options.is_filtered() {
+            let remote = self.service.query_remote_sources(RemoteQuery::Albums).await;
+            albums.extend(remote.albums);
+        }
         Ok(tonic::Response::new(GetAlbumsResponse {
             albums: albums.into_iter().map(|a| a.into()).collect(),
+            total,
         }))
     }
 
     async fn get_artists(
         &self,
-        _request: tonic::Request<GetArtistsRequest>,
+        request: tonic::Request<GetArtistsRequest>,
     ) -> Result<tonic::Response<GetArtistsResponse>, tonic::Status> {
-        let artists = repo::artist::all(self.pool.clone())
+        let options = request.into_inner().options.unwrap_or_default().into();
+        let artists = repo::artist::list(self.service.pool.clone(), &options)
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        let total = repo::artist::count(self.service.pool.clone(), &options)
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
         Ok(tonic::Response::new(GetArtistsResponse {
             artists: artists.into_iter().map(|a| a.into()).collect(),
+            total,
         }))
     }
 
     async fn get_tracks(
         &self,
-        _request: tonic::Request<GetTracksRequest>,
+        request: tonic::Request<GetTracksRequest>,
     ) -> Result<tonic::Response<GetTracksResponse>, tonic::Status> {
-        let tracks = repo::track::all(self.pool.clone())
+        let remote = Self::merges_remote_sources(&request);
+        let options: list::ListOptions = request.into_inner().options.unwrap_or_default().into();
+        let mut tracks = repo::track::list(self.service.pool.clone(), &options)
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        let total = repo::track::count(self.service.pool.clone(), &options)
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        // remote libraries can't be paginated nor filtered, only merge them in full listings
+        if remote && !options.is_paginated() && !options.is_filtered() {
+            let remote = self.service.query_remote_sources(RemoteQuery::Tracks).await;
+            tracks.extend(remote.tracks);
+        }
         Ok(tonic::Response::new(GetTracksResponse {
             tracks: tracks.into_iter().map(|t| t.into()).collect(),
+            total,
         }))
     }
 
@@ -78,16 +112,20 @@ impl LibraryService for Library {
         request: tonic::Request<GetAlbumRequest>,
     ) -> Result<tonic::Response<GetAlbumResponse>, tonic::Status> {
         let params = request.into_inner();
-        let album = repo::album::find(self.pool.clone(), &params.id)
+        let album = repo::album::find(self.service.pool.clone(), &params.id)
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
         let mut album: Option<Album> = album.map(|a| a.into());
-        let tracks = repo::album_tracks::find_by_album(self.pool.clone(), &params.id)
+        let tracks = repo::album_tracks::find_by_album(self.service.pool.clone(), &params.id)
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        let editions = repo::album::find_editions(self.service.pool.clone(), &params.id)
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
 
         if let Some(album) = album.as_mut() {
             album.tracks = tracks.into_iter().map(|t| t.into()).collect();
+            album.editions = editions.into_iter().map(|a| a.into()).collect();
         }
 
         let response = GetAlbumResponse { album };
@@ -99,31 +137,67 @@ impl LibraryService for Library {
         request: tonic::Request<GetArtistRequest>,
     ) -> Result<tonic::Response<GetArtistResponse>, tonic::Status> {
         let params = request.into_inner();
-        let artist = repo::artist::find(self.pool.clone(), &params.id)
+        let artist = repo::artist::find(self.service.pool.clone(), &params.id)
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
         let mut artist: Option<Artist> = artist.map(|a| a.into());
-        let albums = repo::album::find_by_artist(self.pool.clone(), &params.id)
+        let albums = repo::album::find_by_artist(self.service.pool.clone(), &params.id)
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
-        let tracks = repo::artist_tracks::find_by_artist(self.pool.clone(), &params.id)
+        let tracks = repo::artist_tracks::find_by_artist(self.service.pool.clone(), &params.id)
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        let appears_on = repo::album::find_appears_on(self.service.pool.clone(), &params.id)
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
 
         if let Some(artist) = artist.as_mut() {
             artist.albums = albums.into_iter().map(|a| a.into()).collect();
             artist.tracks = tracks.into_iter().map(|t| t.into()).collect();
+            artist.appears_on = appears_on.into_iter().map(|a| a.into()).collect();
         }
 
         Ok(tonic::Response::new(GetArtistResponse { artist }))
     }
 
+    async fn get_genres(
+        &self,
+        _request: tonic::Request<GetGenresRequest>,
+    ) -> Result<tonic::Response<GetGenresResponse>, tonic::Status> {
+        let genres = repo::genre::all(self.service.pool.clone())
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        Ok(tonic::Response::new(GetGenresResponse {
+            genres: genres.into_iter().map(|g| g.into()).collect(),
+        }))
+    }
+
+    async fn get_genre(
+        &self,
+        request: tonic::Request<GetGenreRequest>,
+    ) -> Result<tonic::Response<GetGenreResponse>, tonic::Status> {
+        let params = request.into_inner();
+        let genre = repo::genre::find(self.service.pool.clone(), &params.id)
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        let mut genre: Option<Genre> = genre.map(|g| g.into());
+        let tracks = repo::genre_tracks::find_by_genre(self.service.pool.clone(), &params.id)
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+
+        if let Some(genre) = genre.as_mut() {
+            genre.tracks = tracks.into_iter().map(|t| t.into()).collect();
+        }
+
+        Ok(tonic::Response::new(GetGenreResponse { genre }))
+    }
+
     async fn get_track(
         &self,
         request: tonic::Request<GetTrackRequest>,
     ) -> Result<tonic::Response<GetTrackResponse>, tonic::Status> {
         let params = request.into_inner();
-        let track = repo::track::find(self.pool.clone(), &params.id)
+        let track = repo::track::find(self.service.pool.clone(), &params.id)
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
         Ok(tonic::Response::new(GetTrackResponse {
@@ -137,7 +211,7 @@ impl LibraryService for Library {
     ) -> Result<tonic::Response<LikeTrackResponse>, tonic::Status> {
         let params = request.into_inner();
         repo::favourites::save(
-            self.pool.clone(),
+            self.service.pool.clone(),
             Favourites {
                 id: cuid::cuid1().map_err(|e| tonic::Status::internal(e.to_string()))?,
                 track_id: Some(params.id.clone()),
@@ -148,12 +222,12 @@ impl LibraryService for Library {
         .await
         .map_err(|e| tonic::Status::internal(e.to_string()))?;
 
-        let track = repo::track::find(self.pool.clone(), &params.id)
+        let track = repo::track::find(self.service.pool.clone(), &params.id)
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
 
         if let Some(track) = track {
-            let album = repo::album::find(self.pool.clone(), &track.album_id)
+            let album = repo::album::find(self.service.pool.clone(), &track.album_id)
                 .await
                 .map_err(|e| tonic::Status::internal(e.to_string()))?;
             if let Some(album) = album {
@@ -175,7 +249,7 @@ impl LibraryService for Library {
     ) -> Result<tonic::Response<LikeAlbumResponse>, tonic::Status> {
         let params = request.into_inner();
         repo::favourites::save(
-            self.pool.clone(),
+            self.service.pool.clone(),
             Favourites {
                 id: cuid::cuid1().map_err(|e| tonic::Status::internal(e.to_string()))?,
                 track_id: None,
@@ -193,11 +267,11 @@ impl LibraryService for Library {
         request: tonic::Request<UnlikeTrackRequest>,
     ) -> Result<tonic::Response<UnlikeTrackResponse>, tonic::Status> {
         let params = request.into_inner();
-        repo::favourites::delete(self.pool.clone(), &params.id)
+        repo::favourites::delete(self.service.pool.clone(), &params.id)
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
 
-        let track = repo::track::find(self.pool.clone(), &params.id)
+        let track = repo::track::find(self.service.pool.clone(), &params.id)
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
 
@@ -218,7 +292,7 @@ impl LibraryService for Library {
         request: tonic::Request<UnlikeAlbumRequest>,
     ) -> Result<tonic::Response<UnlikeAlbumResponse>, tonic::Status> {
         let params = request.into_inner();
-        repo::favourites::delete(self.pool.clone(), &params.id)
+        repo::favourites::delete(self.service.pool.clone(), &params.id)
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
         Ok(tonic::Response::new(UnlikeAlbumResponse {}))
@@ -228,7 +302,7 @@ impl LibraryService for Library {
         &self,
         _request: tonic::Request<GetLikedTracksRequest>,
     ) -> Result<tonic::Response<GetLikedTracksResponse>, tonic::Status> {
-        let tracks = repo::favourites::all_tracks(self.pool.clone())
+        let tracks = repo::favourites::all_tracks(self.service.pool.clone())
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
         Ok(tonic::Response::new(GetLikedTracksResponse {
@@ -240,7 +314,7 @@ impl LibraryService for Library {
         &self,
         _request: tonic::Request<GetLikedAlbumsRequest>,
     ) -> Result<tonic::Response<GetLikedAlbumsResponse>, tonic::Status> {
-        let albums = repo::favourites::all_albums(self.pool.clone())
+        let albums = repo::favourites::all_albums(self.service.pool.clone())
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
         Ok(tonic::Response::new(GetLikedAlbumsResponse {
@@ -253,14 +327,8 @@ impl LibraryService for Library {
         request: tonic::Request<ScanLibraryRequest>,
     ) -> Result<tonic::Response<ScanLibraryResponse>, tonic::Status> {
         let request = request.into_inner();
-        let params = match request.path {
-            Some(path) => format!("?path={}", path),
-            None => "".to_string(),
-        };
-        let url = format!("{}/scan-library{}", rockbox_url(), params);
-        self.client
-            .put(&url)
-            .send()
+        self.service
+            .scan_library(request.path)
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
         Ok(tonic::Response::new(ScanLibraryResponse {}))
@@ -270,32 +338,99 @@ impl LibraryService for Library {
         &self,
         request: tonic::Request<SearchRequest>,
     ) -> Result<tonic::Response<SearchResponse>, tonic::Status> {
+        let remote = Self::merges_remote_sources(&request);
         let request = request.into_inner();
-        let term = request.term;
+        let options = SearchOptions {
+            offset: request.offset.unwrap_or(0).max(0) as usize,
+            limit: request
+                .limit
+                .map(|limit| limit.max(0) as usize)
+                .unwrap_or(DEFAULT_LIMIT),
+            sort: request
+                .sort
+                .as_deref()
+                .and_then(SortBy::parse)
+                .unwrap_or_default(),
+            descending: request.descending.unwrap_or(false),
+        };
 
-        let albums = search_entities(
-            &self.indexes.albums,
-            &term,
-            &rockbox_search::album::Album::default(),
-        )
-        .map_err(|e| tonic::Status::internal(e.to_string()))?;
-        let artists = search_entities(
-            &self.indexes.artists,
-            &term,
-            &rockbox_search::artist::Artist::default(),
-        )
-        .map_err(|e| tonic::Status::internal(e.to_string()))?;
-        let tracks = search_entities(
-            &self.indexes.tracks,
-            &term,
-            &rockbox_search::track::Track::default(),
-        )
-        .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        let mut results: rockbox_types::SearchResults =
+            search_library(&self.service.indexes, &request.term, &options)
+                .map_err(|e| tonic::Status::internal(e.to_string()))?
+                .into();
+
+        // remote libraries aren't paginated, only merge them in the first page
+        if remote && options.offset == 0 {
+            let remote = self
+                .service
+                .query_remote_sources(RemoteQuery::Search(request.term))
+                .await;
+            results
+                .albums
+                .extend(remote.albums.into_iter().map(|x| x.into()));
+            results
+                .tracks
+                .extend(remote.tracks.into_iter().map(|x| x.into()));
+        }
+
+        Ok(tonic::Response::new(results.into()))
+    }
 
-        Ok(tonic::Response::new(SearchResponse {
-            albums: albums.into_iter().map(|(_, x)| x.into()).collect(),
-            artists: artists.into_iter().map(|(_, x)| x.into()).collect(),
-            tracks: tracks.into_iter().map(|(_, x)| x.into()).collect(),
+    type AutocompleteStream =
+        Pin<Box<dyn Stream<Item = Result<AutocompleteResponse, tonic::Status>> + Send + 'static>>;
+
+    async fn autocomplete(
+        &self,
+        request: tonic::Request<tonic::Streaming<AutocompleteRequest>>,
+    ) -> Result<tonic::Response<Self::AutocompleteStream>, tonic::Status> {
+        let mut requests = request.into_inner();
+        let index = self.service.indexes.suggestions.clone();
+        let output = async_stream::try_stream! {
+            while let Some(request) = requests.message().await? {
+                let limit = request
+                    .limit
+                    .map(|limit| limit.max(0) as usize)
+                    .unwrap_or(DEFAULT_LIMIT);
+                let suggestions = autocomplete(&index, &request.text, limit)
+                    .map_err(|e| tonic::Status::internal(e.to_string()))?;
+                yield AutocompleteResponse {
+                    text: request.text,
+                    suggestions: suggestions.into_iter().map(Into::into).collect(),
+                };
+            }
+        };
+
+        Ok(tonic::Response::new(
+            Box::pin(output) as Self::AutocompleteStream
+        ))
+    }
+
+    async fn get_lyrics(
+        &self,
+        request: tonic::Request<GetLyricsRequest>,
+    ) -> Result<tonic::Response<GetLyricsResponse>, tonic::Status> {
+        let params = request.into_inner();
+        let lyrics = lrc::load(self.service.pool.clone(), &params.track_id)
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        Ok(tonic::Response::new(GetLyricsResponse {
+            lyrics: lyrics.map(|l| l.into()),
         }))
     }
+
+    type StreamLyricsStream = Pin<
+        Box<dyn Stream<Item = Result<CurrentLyricLineResponse, tonic::Status>> + Send + 'static>,
+    >;
+
+    async fn stream_lyrics(
+        &self,
+        _request: tonic::Request<StreamLyricsRequest>,
+    ) -> Result<tonic::Response<Self::StreamLyricsStream>, tonic::Status> {
+        let output = current_lyric_lines(self.service.pool.clone())
+            .map(CurrentLyricLineResponse::from)
+            .map(Ok);
+        Ok(tonic::Response::new(
+            Box::pin(output) as Self::StreamLyricsStream
+        ))
+    }
 }
@@ -1,35 +1,15 @@
-use std::sync::{mpsc::Sender, Arc, Mutex};
-
 use rockbox_library::repo;
-use rockbox_sys::{
-    events::RockboxCommand,
-    types::{playlist_amount::PlaylistAmount, playlist_info::PlaylistInfo},
-};
-use sqlx::Sqlite;
+use rockbox_service::RockboxService;
 
-use crate::{
-    api::rockbox::v1alpha1::{playlist_service_server::PlaylistService, *},
-    rockbox_url,
-    types::StatusCode,
-};
+use crate::api::rockbox::v1alpha1::{playlist_service_server::PlaylistService, *};
 
 pub struct Playlist {
-    cmd_tx: Arc<Mutex<Sender<RockboxCommand>>>,
-    client: reqwest::Client,
-    pool: sqlx::Pool<Sqlite>,
+    service: RockboxService,
 }
 
 impl Playlist {
-    pub fn new(
-        cmd_tx: Arc<Mutex<Sender<RockboxCommand>>>,
-        client: reqwest::Client,
-        pool: sqlx::Pool<Sqlite>,
-    ) -> Self {
-        Self {
-            cmd_tx,
-            client,
-            pool,
-        }
+    pub fn new(service: RockboxService) -> Self {
+        Self { service }
     }
 }
 
@@ -39,15 +19,9 @@ impl PlaylistService for Playlist {
         &self,
         _request: tonic::Request<GetCurrentRequest>,
     ) -> Result<tonic::Response<GetCurrentResponse>, tonic::Status> {
-        let url = format!("{}/playlists/current", rockbox_url());
-        let response = self
-            .client
-            .get(url)
-            .send()
-            .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?;
-        let data = response
-            .json::<PlaylistInfo>()
+        let data = self
+            .service
+            .current_playlist()
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
         let tracks = data
@@ -99,51 +73,34 @@ impl PlaylistService for Playlist {
         &self,
         _request: tonic::Request<AmountRequest>,
     ) -> Result<tonic::Response<AmountResponse>, tonic::Status> {
-        let url = format!("{}/playlists/amount", rockbox_url());
-        let response = self
-            .client
-            .get(url)
-            .send()
-            .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?;
-        let data = response
-            .json::<PlaylistAmount>()
+        let amount = self
+            .service
+            .playlist_amount()
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
-        Ok(tonic::Response::new(AmountResponse {
-            amount: data.amount,
-        }))
+        Ok(tonic::Response::new(AmountResponse { amount }))
     }
 
     async fn playlist_resume(
         &self,
         _request: tonic::Request<PlaylistResumeRequest>,
     ) -> Result<tonic::Response<PlaylistResumeResponse>, tonic::Status> {
-        let url = format!("{}/playlists/resume", rockbox_url());
-        let response = self
-            .client
-            .put(&url)
-            .send()
-            .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?;
-        let response = response
-            .json::<StatusCode>()
+        let code = self
+            .service
+            .resume_playlist()
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
-        Ok(tonic::Response::new(PlaylistResumeResponse {
-            code: response.code,
-        }))
+        Ok(tonic::Response::new(PlaylistResumeResponse { code }))
     }
 
     async fn resume_track(
         &self,
         _request: tonic::Request<ResumeTrackRequest>,
     ) -> Result<tonic::Response<ResumeTrackResponse>, tonic::Status> {
-        self.cmd_tx
-            .lock()
-            .unwrap()
-            .send(RockboxCommand::PlaylistResumeTrack)
-            .map_err(|_| tonic::Status::internal("Failed to send command"))?;
+        self.service
+            .resume_track()
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
         Ok(tonic::Response::new(ResumeTrackResponse::default()))
     }
 
@@ -160,29 +117,12 @@ impl PlaylistService for Playlist {
     ) -> Result<tonic::Response<StartResponse>, tonic::Status> {
         let request = request.into_inner();
 
-        let mut url = format!("{}/playlists/start", rockbox_url());
-
-        if let Some(start_index) = request.start_index {
-            url = format!("{}?start_index={}", url, start_index);
-        }
-
-        if let Some(elapsed) = request.elapsed {
-            url = match url.contains("?") {
-                true => format!("{}&elapsed={}", url, elapsed),
-                false => format!("{}?elapsed={}", url, elapsed),
-            };
-        }
-
-        if let Some(offset) = request.offset {
-            url = match url.contains("?") {
-                true => format!("{}&offset={}", url, offset),
-                false => format!("{}?offset={}", url, offset),
-            };
-        }
-
-        self.client
-            .put(&url)
-            .send()
+        self.service
+            .start_playlist(
+                request.start_index.unwrap_or_default(),
+                request.elapsed.unwrap_or_default() as u64,
+                request.offset.unwrap_or_default() as u64,
+            )
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
         Ok(tonic::Response::new(StartResponse::default()))
@@ -199,14 +139,8 @@ impl PlaylistService for Playlist {
         &self,
         _request: tonic::Request<RemoveAllTracksRequest>,
     ) -> Result<tonic::Response<RemoveAllTracksResponse>, tonic::Status> {
-        let body = serde_json::json!({
-            "positions": [],
-        });
-        let url = format!("{}/playlists/current/tracks", rockbox_url());
-        self.client
-            .delete(&url)
-            .json(&body)
-            .send()
+        self.service
+            .remove_tracks(&[])
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
         Ok(tonic::Response::new(RemoveAllTracksResponse::default()))
@@ -217,14 +151,8 @@ impl PlaylistService for Playlist {
         request: tonic::Request<RemoveTracksRequest>,
     ) -> Result<tonic::Response<RemoveTracksResponse>, tonic::Status> {
         let request = request.into_inner();
-        let body = serde_json::json!({
-            "positions": request.positions,
-        });
-        let url = format!("{}/playlists/current/tracks", rockbox_url());
-        self.client
-            .delete(&url)
-            .json(&body)
-            .send()
+        self.service
+            .remove_tracks(&request.positions)
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
         Ok(tonic::Response::new(RemoveTracksResponse::default()))
@@ -235,26 +163,11 @@ impl PlaylistService for Playlist {
         request: tonic::Request<CreatePlaylistRequest>,
     ) -> Result<tonic::Response<CreatePlaylistResponse>, tonic::Status> {
         let request = request.into_inner();
-        let body = serde_json::json!({
-            "name": request.name,
-            "tracks": request.tracks,
-            "folder_id": request.folder_id,
-        });
-
-        let url = format!("{}/playlists", rockbox_url());
-        let response = self
-            .client
-            .post(&url)
-            .json(&body)
-            .send()
+        let start_index = self
+            .service
+            .create_playlist(request.tracks)
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
-        let start_index = response
-            .text()
-            .await
-            .map_err(|e| tonic::Status::internal(e.to_string()))?
-            .parse()
-            .unwrap_or(-1);
         Ok(tonic::Response::new(CreatePlaylistResponse { start_index }))
     }
 
@@ -263,15 +176,8 @@ impl PlaylistService for Playlist {
         request: tonic::Request<InsertTracksRequest>,
     ) -> Result<tonic::Response<InsertTracksResponse>, tonic::Status> {
         let request = request.into_inner();
-        let body = serde_json::json!({
-            "position": request.position,
-            "tracks": request.tracks,
-        });
-        let url = format!("{}/playlists/current/tracks", rockbox_url());
-        self.client
-            .post(&url)
-            .json(&body)
-            .send()
+        self.service
+            .insert_tracks(request.tracks, request.position, None)
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
         Ok(tonic::Response::new(InsertTracksResponse::default()))
@@ -282,16 +188,8 @@ impl PlaylistService for Playlist {
         request: tonic::Request<InsertDirectoryRequest>,
     ) -> Result<tonic::Response<InsertDirectoryResponse>, tonic::Status> {
         let request = request.into_inner();
-        let body = serde_json::json!({
-            "position": request.position,
-            "tracks": [],
-            "directory": request.directory,
-        });
-        let url = format!("{}/playlists/current/tracks", rockbox_url());
-        self.client
-            .post(&url)
-            .json(&body)
-            .send()
+        self.service
+            .insert_tracks(vec![], request.position, Some(request.directory))
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
         Ok(tonic::Response::new(InsertDirectoryResponse::default()))
@@ -309,14 +207,8 @@ impl PlaylistService for Playlist {
         request: tonic::Request<ShufflePlaylistRequest>,
     ) -> Result<tonic::Response<ShufflePlaylistResponse>, tonic::Status> {
         let request = request.into_inner();
-        let url = format!(
-            "{}/playlists/shuffle?start_index={}",
-            rockbox_url(),
-            request.start_index
-        );
-        self.client
-            .put(&url)
-            .send()
+        self.service
+            .shuffle_playlist(request.start_index)
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
         Ok(tonic::Response::new(ShufflePlaylistResponse::default()))
@@ -329,19 +221,12 @@ impl PlaylistService for Playlist {
         let request = request.into_inner();
         let album_id = request.album_id;
         let position = request.position;
-        let tracks = repo::album_tracks::find_by_album(self.pool.clone(), &album_id)
+        let tracks = repo::album_tracks::find_by_album(self.service.pool.clone(), &album_id)
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
         let tracks: Vec<String> = tracks.into_iter().map(|t| t.path).collect();
-        let body = serde_json::json!({
-            "position": position,
-            "tracks": tracks,
-        });
-        let url = format!("{}/playlists/current/tracks", rockbox_url());
-        self.client
-            .post(&url)
-            .json(&body)
-            .send()
+        self.service
+            .insert_tracks(tracks, position, None)
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
 
@@ -355,19 +240,12 @@ impl PlaylistService for Playlist {
         let request = request.into_inner();
         let artist_id = request.artist_id;
         let position = request.position;
-        let tracks = repo::artist_tracks::find_by_artist(self.pool.clone(), &artist_id)
+        let tracks = repo::artist_tracks::find_by_artist(self.service.pool.clone(), &artist_id)
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
         let tracks: Vec<String> = tracks.into_iter().map(|t| t.path).collect();
-        let body = serde_json::json!({
-            "position": position,
-            "tracks": tracks,
-        });
-        let url = format!("{}/playlists/current/tracks", rockbox_url());
-        self.client
-            .post(&url)
-            .json(&body)
-            .send()
+        self.service
+            .insert_tracks(tracks, position, None)
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
 
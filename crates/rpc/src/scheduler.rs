@@ -0,0 +1,170 @@
+use rockbox_service::RockboxService;
+use rockbox_types::scheduler::{NewSchedule, UpdateSchedule};
+
+use crate::api::rockbox::v1alpha1::{scheduler_service_server::SchedulerService, *};
+
+pub struct Scheduler {
+    service: RockboxService,
+}
+
+impl Scheduler {
+    pub fn new(service: RockboxService) -> Self {
+        Self { service }
+    }
+}
+
+fn invalid_source(e: prost::UnknownEnumValue) -> tonic::Status {
+    tonic::Status::invalid_argument(e.to_string())
+}
+
+#[tonic::async_trait]
+impl SchedulerService for Scheduler {
+    async fn get_sleep_timer(
+        &self,
+        _request: tonic::Request<GetSleepTimerRequest>,
+    ) -> Result<tonic::Response<GetSleepTimerResponse>, tonic::Status> {
+        Ok(tonic::Response::new(GetSleepTimerResponse {
+            timer: Some(self.service.sleep_timer().into()),
+        }))
+    }
+
+    async fn set_sleep_timer(
+        &self,
+        request: tonic::Request<SetSleepTimerRequest>,
+    ) -> Result<tonic::Response<SetSleepTimerResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let timer = self
+            .service
+            .set_sleep_timer(request.duration, request.fade_out);
+        Ok(tonic::Response::new(SetSleepTimerResponse {
+            timer: Some(timer.into()),
+        }))
+    }
+
+    async fn cancel_sleep_timer(
+        &self,
+        _request: tonic::Request<CancelSleepTimerRequest>,
+    ) -> Result<tonic::Response<CancelSleepTimerResponse>, tonic::Status> {
+        Ok(tonic::Response::new(CancelSleepTimerResponse {
+            timer: Some(self.service.cancel_sleep_timer().into()),
+        }))
+    }
+
+    async fn get_schedules(
+        &self,
+        _request: tonic::Request<GetSchedulesRequest>,
+    ) -> Result<tonic::Response<GetSchedulesResponse>, tonic::Status> {
+        let schedules = self
+            .service
+            .schedules()
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        Ok(tonic::Response::new(GetSchedulesResponse {
+            schedules: schedules.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn get_schedule(
+        &self,
+        request: tonic::Request<GetScheduleRequest>,
+    ) -> Result<tonic::Response<GetScheduleResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let schedule = self
+            .service
+            .schedule(&request.id)
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        Ok(tonic::Response::new(GetScheduleResponse {
+            schedule: schedule.map(Into::into),
+        }))
+    }
+
+    async fn create_schedule(
+        &self,
+        request: tonic::Request<CreateScheduleRequest>,
+    ) -> Result<tonic::Response<CreateScheduleResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let schedule = NewSchedule {
+            source: PlaybackSource::try_from(request.source).map_err(invalid_source)?.into(),
+            name: request.name,
+            cron: request.cron,
+            source_id: request.source_id,
+            shuffle: request.shuffle,
+            volume: request.volume,
+            fade_in: request.fade_in,
+            once: request.once,
+            enabled: request.enabled,
+        };
+        let schedule = self
+            .service
+            .create_schedule(schedule)
+            .await
+            .map_err(|e| tonic::Status::invalid_argument(e.to_string()))?;
+        Ok(tonic::Response::new(CreateScheduleResponse {
+            schedule: Some(schedule.into()),
+        }))
+    }
+
+    async fn update_schedule(
+        &self,
+        request: tonic::Request<UpdateScheduleRequest>,
+    ) -> Result<tonic::Response<UpdateScheduleResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let source = match request.source {
+            Some(source) => Some(PlaybackSource::try_from(source).map_err(invalid_source)?.into()),
+            None => None,
+        };
+        let update = UpdateSchedule {
+            name: request.name,
+            cron: request.cron,
+            source,
+            source_id: request.source_id,
+            shuffle: request.shuffle,
+            volume: request.volume,
+            fade_in: request.fade_in,
+            once: request.once,
+            enabled: request.enabled,
+        };
+        let schedule = self
+            .service
+            .update_schedule(&request.id, update)
+            .await
+            .map_err(|e| tonic::Status::invalid_argument(e.to_string()))?
+            .ok_or_else(|| tonic::Status::not_found("Schedule not found"))?;
+        Ok(tonic::Response::new(UpdateScheduleResponse {
+            schedule: Some(schedule.into()),
+        }))
+    }
+
+    async fn delete_schedule(
+        &self,
+        request: tonic::Request<DeleteScheduleRequest>,
+    ) -> Result<tonic::Response<DeleteScheduleResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let deleted = self
+            .service
+            .delete_schedule(&request.id)
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        if !deleted {
+            return Err(tonic::Status::not_found("Schedule not found"));
+        }
+        Ok(tonic::Response::new(DeleteScheduleResponse {}))
+    }
+
+    async fn run_schedule(
+        &self,
+        request: tonic::Request<RunScheduleRequest>,
+    ) -> Result<tonic::Response<RunScheduleResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let found = self
+            .service
+            .run_schedule(&request.id)
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        if !found {
+            return Err(tonic::Status::not_found("Schedule not found"));
+        }
+        Ok(tonic::Response::new(RunScheduleResponse {}))
+    }
+}
@@ -1,31 +1,28 @@
 use std::net::SocketAddr;
-use std::sync::mpsc::Sender;
-use std::sync::{Arc, Mutex};
 
+use crate::api::rockbox::v1alpha1::bookmark_service_server::BookmarkServiceServer;
 use crate::api::rockbox::v1alpha1::browse_service_server::BrowseServiceServer;
 use crate::api::rockbox::v1alpha1::device_service_server::DeviceServiceServer;
 use crate::api::rockbox::v1alpha1::library_service_server::LibraryServiceServer;
 use crate::api::rockbox::v1alpha1::playback_service_server::PlaybackServiceServer;
 use crate::api::rockbox::v1alpha1::playlist_service_server::PlaylistServiceServer;
+use crate::api::rockbox::v1alpha1::scheduler_service_server::SchedulerServiceServer;
 use crate::api::rockbox::v1alpha1::settings_service_server::SettingsServiceServer;
 use crate::api::rockbox::v1alpha1::sound_service_server::SoundServiceServer;
 use crate::api::rockbox::FILE_DESCRIPTOR_SET;
+use crate::bookmark::Bookmarks;
 use crate::browse::Browse;
 use crate::device::Device;
 use crate::library::Library;
 use crate::playback::Playback;
 use crate::playlist::Playlist;
+use crate::scheduler::Scheduler;
 use crate::settings::Settings;
 use crate::sound::Sound;
 use crate::system::System;
-use rockbox_library::create_connection_pool;
-use rockbox_search::create_indexes;
-use rockbox_sys::events::RockboxCommand;
 use tonic::transport::Server;
 
-pub async fn start(
-    cmd_tx: Arc<Mutex<Sender<RockboxCommand>>>,
-) -> Result<(), Box<dyn std::error::Error>> {
+pub async fn start() -> Result<(), Box<dyn std::error::Error>> {
     let rockbox_port: u16 = std::env::var("ROCKBOX_PORT")
         .unwrap_or_else(|_| "6061".to_string())
         .parse()
@@ -33,9 +30,7 @@ pub async fn start(
 
     let addr: SocketAddr = format!("0.0.0.0:{}", rockbox_port).parse()?;
 
-    let client = reqwest::Client::new();
-    let pool = create_connection_pool().await?;
-    let indexes = create_indexes()?;
+    let service = rockbox_service::instance().await?;
 
     Server::builder()
         .accept_http1(true)
@@ -45,31 +40,35 @@ pub async fn start(
                 .build_v1alpha()?,
         )
         .add_service(tonic_web::enable(DeviceServiceServer::new(Device::new(
-            client.clone(),
+            service.clone(),
         ))))
         .add_service(tonic_web::enable(LibraryServiceServer::new(Library::new(
-            pool.clone(),
-            client.clone(),
-            indexes,
+            service.clone(),
         ))))
         .add_service(tonic_web::enable(PlaylistServiceServer::new(
-            Playlist::new(cmd_tx.clone(), client.clone(), pool.clone()),
+            Playlist::new(service.clone()),
         )))
         .add_service(tonic_web::enable(PlaybackServiceServer::new(
-            Playback::new(cmd_tx.clone(), client.clone(), pool.clone()),
+            Playback::new(service.clone()),
         )))
         .add_service(tonic_web::enable(BrowseServiceServer::new(
             Browse::default(),
         )))
         .add_service(tonic_web::enable(SoundServiceServer::new(Sound::new(
-            client.clone(),
+            service.clone(),
         ))))
+        .add_service(tonic_web::enable(SchedulerServiceServer::new(
+            Scheduler::new(service.clone()),
+        )))
+        .add_service(tonic_web::enable(BookmarkServiceServer::new(
+            Bookmarks::new(service.clone()),
+        )))
         .add_service(tonic_web::enable(SettingsServiceServer::new(
-            Settings::new(client.clone()),
+            Settings::new(service.clone()),
         )))
         .add_service(tonic_web::enable(
             crate::api::rockbox::v1alpha1::system_service_server::SystemServiceServer::new(
-                System::new(client.clone()),
+                System::new(service),
             ),
         ))
         .serve(addr)
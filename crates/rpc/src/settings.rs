@@ -1,23 +1,19 @@
-use rockbox_sys::types::user_settings::{NewGlobalSettings, UserSettings};
+use rockbox_service::RockboxService;
+use rockbox_sys::types::user_settings::NewGlobalSettings;
 use tonic::{Request, Response, Status};
 
-use crate::{
-    api::rockbox::v1alpha1::{
-        settings_service_server::SettingsService, GetGlobalSettingsRequest,
-        GetGlobalSettingsResponse, GetSettingsListRequest, GetSettingsListResponse,
-        SaveSettingsRequest, SaveSettingsResponse,
-    },
-    rockbox_url,
+use crate::api::rockbox::v1alpha1::{
+    settings_service_server::SettingsService, GetGlobalSettingsRequest, GetGlobalSettingsResponse,
+    GetSettingsListRequest, GetSettingsListResponse, SaveSettingsRequest, SaveSettingsResponse,
 };
 
-#[derive(Default)]
 pub struct Settings {
-    client: reqwest::Client,
+    service: RockboxService,
 }
 
 impl Settings {
-    pub fn new(client: reqwest::Client) -> Self {
-        Self { client }
+    pub fn new(service: RockboxService) -> Self {
+        Self { service }
     }
 }
 
@@ -27,15 +23,9 @@ impl SettingsService for Settings {
         &self,
         _request: Request<GetGlobalSettingsRequest>,
     ) -> Result<Response<GetGlobalSettingsResponse>, Status> {
-        let url = format!("{}/settings", rockbox_url());
-        let response = self
-            .client
-            .get(url)
-            .send()
-            .await
-            .map_err(|e| Status::internal(e.to_string()))?;
-        let settings = response
-            .json::<UserSettings>()
+        let settings = self
+            .service
+            .global_settings()
             .await
             .map_err(|e| Status::internal(e.to_string()))?;
         Ok(Response::new(settings.into()))
@@ -45,15 +35,7 @@ impl SettingsService for Settings {
         &self,
         _request: Request<GetSettingsListRequest>,
     ) -> Result<Response<GetSettingsListResponse>, Status> {
-        let url = format!("{}/settingslist", rockbox_url());
-        let _response = self
-            .client
-            .get(url)
-            .send()
-            .await
-            .map_err(|e| Status::internal(e.to_string()))?;
-        //let settings = response.json::<UserSettings>().await?;
-        todo!()
+        Err(Status::unimplemented("settings list is not available"))
     }
 
     async fn save_settings(
@@ -62,12 +44,8 @@ impl SettingsService for Settings {
     ) -> Result<Response<SaveSettingsResponse>, Status> {
         let settings = request.into_inner();
         let settings: NewGlobalSettings = settings.into();
-
-        let url = format!("{}/settings", rockbox_url());
-        self.client
-            .put(url)
-            .json(&settings)
-            .send()
+        self.service
+            .save_settings(settings)
             .await
             .map_err(|e| Status::internal(e.to_string()))?;
         Ok(Response::new(SaveSettingsResponse::default()))
@@ -1,16 +1,22 @@
-use crate::{
-    api::rockbox::v1alpha1::{sound_service_server::SoundService, *},
-    rockbox_url,
-};
+use rockbox_service::RockboxService;
+use rockbox_types::dsp::{UpdateEqBand, UpdateEqualizer};
+
+use crate::api::rockbox::v1alpha1::{sound_service_server::SoundService, *};
 
-#[derive(Default)]
 pub struct Sound {
-    client: reqwest::Client,
+    service: RockboxService,
 }
 
 impl Sound {
-    pub fn new(client: reqwest::Client) -> Self {
-        Self { client }
+    pub fn new(service: RockboxService) -> Self {
+        Self { service }
+    }
+
+    async fn playback_speed(&self) -> Result<rockbox_types::PlaybackSpeed, tonic::Status> {
+        self.service
+            .playback_speed()
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))
     }
 }
 
@@ -21,14 +27,8 @@ impl SoundService for Sound {
         request: tonic::Request<AdjustVolumeRequest>,
     ) -> Result<tonic::Response<AdjustVolumeResponse>, tonic::Status> {
         let request = request.into_inner();
-        let body = serde_json::json!({
-            "steps": request.steps,
-        });
-        let url = format!("{}/player/volume", rockbox_url());
-        self.client
-            .put(&url)
-            .json(&body)
-            .send()
+        self.service
+            .adjust_volume(request.steps)
             .await
             .map_err(|e| tonic::Status::internal(e.to_string()))?;
 
@@ -88,7 +88,10 @@ impl SoundService for Sound {
         &self,
         _request: tonic::Request<GetPitchRequest>,
     ) -> Result<tonic::Response<GetPitchResponse>, tonic::Status> {
-        Ok(tonic::Response::new(GetPitchResponse::default()))
+        let speed = self.playback_speed().await?;
+        Ok(tonic::Response::new(GetPitchResponse {
+            value: speed.pitch,
+        }))
     }
 
     async fn set_pitch(
@@ -98,6 +101,31 @@ impl SoundService for Sound {
         Ok(tonic::Response::new(SetPitchResponse::default()))
     }
 
+    async fn get_playback_speed(
+        &self,
+        _request: tonic::Request<GetPlaybackSpeedRequest>,
+    ) -> Result<tonic::Response<GetPlaybackSpeedResponse>, tonic::Status> {
+        let speed = self.playback_speed().await?;
+        Ok(tonic::Response::new(GetPlaybackSpeedResponse {
+            pitch: speed.pitch,
+            timestretch: speed.timestretch,
+            timestretch_available: speed.timestretch_available,
+        }))
+    }
+
+    async fn set_playback_speed(
+        &self,
+        request: tonic::Request<SetPlaybackSpeedRequest>,
+    ) -> Result<tonic::Response<SetPlaybackSpeedResponse>, tonic::Status> {
+        let request = request.into_inner();
+        self.service
+            .set_playback_speed(request.speed)
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+
+        Ok(tonic::Response::new(SetPlaybackSpeedResponse::default()))
+    }
+
     async fn beep_play(
         &self,
         _request: tonic::Request<BeepPlayRequest>,
@@ -132,4 +160,190 @@ impl SoundService for Sound {
     ) -> Result<tonic::Response<KeyclickClickResponse>, tonic::Status> {
         Ok(tonic::Response::new(KeyclickClickResponse::default()))
     }
+
+    async fn get_equalizer(
+        &self,
+        _request: tonic::Request<GetEqualizerRequest>,
+    ) -> Result<tonic::Response<GetEqualizerResponse>, tonic::Status> {
+        Ok(tonic::Response::new(GetEqualizerResponse {
+            equalizer: Some(self.service.equalizer().into()),
+        }))
+    }
+
+    async fn update_equalizer(
+        &self,
+        request: tonic::Request<UpdateEqualizerRequest>,
+    ) -> Result<tonic::Response<UpdateEqualizerResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let bands = match request.bands.is_empty() {
+            true => None,
+            false => Some(request.bands.into_iter().map(Into::into).collect()),
+        };
+        let equalizer = self
+            .service
+            .update_equalizer(UpdateEqualizer {
+                enabled: request.enabled,
+                precut: request.precut,
+                bands,
+            })
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        Ok(tonic::Response::new(UpdateEqualizerResponse {
+            equalizer: Some(equalizer.into()),
+        }))
+    }
+
+    async fn set_eq_band(
+        &self,
+        request: tonic::Request<SetEqBandRequest>,
+    ) -> Result<tonic::Response<SetEqBandResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let band = self
+            .service
+            .set_eq_band(
+                request.band as usize,
+                UpdateEqBand {
+                    cutoff: request.cutoff,
+                    q: request.q,
+                    gain: request.gain,
+                },
+            )
+            .map_err(|e| tonic::Status::invalid_argument(e.to_string()))?;
+        Ok(tonic::Response::new(SetEqBandResponse {
+            band: Some(band.into()),
+        }))
+    }
+
+    async fn get_eq_presets(
+        &self,
+        _request: tonic::Request<GetEqPresetsRequest>,
+    ) -> Result<tonic::Response<GetEqPresetsResponse>, tonic::Status> {
+        let presets = self
+            .service
+            .eq_presets()
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        Ok(tonic::Response::new(GetEqPresetsResponse {
+            presets: presets.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn save_eq_preset(
+        &self,
+        request: tonic::Request<SaveEqPresetRequest>,
+    ) -> Result<tonic::Response<SaveEqPresetResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let preset = self
+            .service
+            .save_eq_preset(&request.name, request.equalizer.map(Into::into))
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        Ok(tonic::Response::new(SaveEqPresetResponse {
+            preset: Some(preset.into()),
+        }))
+    }
+
+    async fn import_eq_preset(
+        &self,
+        request: tonic::Request<ImportEqPresetRequest>,
+    ) -> Result<tonic::Response<ImportEqPresetResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let preset = self
+            .service
+            .import_eq_preset(&request.name, &request.profile)
+            .map_err(|e| tonic::Status::invalid_argument(e.to_string()))?;
+        Ok(tonic::Response::new(ImportEqPresetResponse {
+            preset: Some(preset.into()),
+        }))
+    }
+
+    async fn delete_eq_preset(
+        &self,
+        request: tonic::Request<DeleteEqPresetRequest>,
+    ) -> Result<tonic::Response<DeleteEqPresetResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let deleted = self
+            .service
+            .delete_eq_preset(&request.name)
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        if !deleted {
+            return Err(tonic::Status::not_found("Preset not found"));
+        }
+        Ok(tonic::Response::new(DeleteEqPresetResponse::default()))
+    }
+
+    async fn load_eq_preset(
+        &self,
+        request: tonic::Request<LoadEqPresetRequest>,
+    ) -> Result<tonic::Response<LoadEqPresetResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let equalizer = self
+            .service
+            .load_eq_preset(&request.name)
+            .map_err(|e| tonic::Status::internal(e.to_string()))?
+            .ok_or_else(|| tonic::Status::not_found("Preset not found"))?;
+        Ok(tonic::Response::new(LoadEqPresetResponse {
+            equalizer: Some(equalizer.into()),
+        }))
+    }
+
+    async fn get_eq_device_presets(
+        &self,
+        _request: tonic::Request<GetEqDevicePresetsRequest>,
+    ) -> Result<tonic::Response<GetEqDevicePresetsResponse>, tonic::Status> {
+        let devices = self
+            .service
+            .eq_device_presets()
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        Ok(tonic::Response::new(GetEqDevicePresetsResponse { devices }))
+    }
+
+    async fn bind_eq_preset(
+        &self,
+        request: tonic::Request<BindEqPresetRequest>,
+    ) -> Result<tonic::Response<BindEqPresetResponse>, tonic::Status> {
+        let request = request.into_inner();
+        self.service
+            .bind_eq_preset(&request.device_id, request.preset.as_deref())
+            .map_err(|e| tonic::Status::not_found(e.to_string()))?;
+        Ok(tonic::Response::new(BindEqPresetResponse::default()))
+    }
+
+    async fn get_eq_comparison(
+        &self,
+        _request: tonic::Request<GetEqComparisonRequest>,
+    ) -> Result<tonic::Response<GetEqComparisonResponse>, tonic::Status> {
+        Ok(tonic::Response::new(GetEqComparisonResponse {
+            comparison: Some(self.service.eq_comparison().into()),
+        }))
+    }
+
+    async fn set_eq_slot(
+        &self,
+        request: tonic::Request<SetEqSlotRequest>,
+    ) -> Result<tonic::Response<SetEqSlotResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let slot = EqSlot::try_from(request.slot)
+            .map_err(|e| tonic::Status::invalid_argument(e.to_string()))?;
+        let equalizer = request
+            .equalizer
+            .ok_or_else(|| tonic::Status::invalid_argument("Missing equalizer"))?;
+        let comparison = self
+            .service
+            .set_eq_slot(slot.into(), equalizer.into())
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        Ok(tonic::Response::new(SetEqSlotResponse {
+            comparison: Some(comparison.into()),
+        }))
+    }
+
+    async fn toggle_eq_slot(
+        &self,
+        _request: tonic::Request<ToggleEqSlotRequest>,
+    ) -> Result<tonic::Response<ToggleEqSlotResponse>, tonic::Status> {
+        let comparison = self
+            .service
+            .toggle_eq_slot()
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        Ok(tonic::Response::new(ToggleEqSlotResponse {
+            comparison: Some(comparison.into()),
+        }))
+    }
 }
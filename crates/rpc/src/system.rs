@@ -1,22 +1,18 @@
-use rockbox_sys::types::{system_status::SystemStatus, RockboxVersion};
+use rockbox_service::RockboxService;
 use tonic::{Request, Response, Status};
 
-use crate::{
-    api::rockbox::v1alpha1::{
-        system_service_server::SystemService, GetGlobalStatusRequest, GetGlobalStatusResponse,
-        GetRockboxVersionRequest, GetRockboxVersionResponse,
-    },
-    rockbox_url,
+use crate::api::rockbox::v1alpha1::{
+    system_service_server::SystemService, GetGlobalStatusRequest, GetGlobalStatusResponse,
+    GetRockboxVersionRequest, GetRockboxVersionResponse,
 };
 
-#[derive(Default)]
 pub struct System {
-    client: reqwest::Client,
+    service: RockboxService,
 }
 
 impl System {
-    pub fn new(client: reqwest::Client) -> Self {
-        Self { client }
+    pub fn new(service: RockboxService) -> Self {
+        Self { service }
     }
 }
 
@@ -26,15 +22,9 @@ impl SystemService for System {
         &self,
         _request: Request<GetGlobalStatusRequest>,
     ) -> Result<Response<GetGlobalStatusResponse>, Status> {
-        let url = format!("{}/status", rockbox_url());
-        let response = self
-            .client
-            .get(url)
-            .send()
-            .await
-            .map_err(|e| Status::internal(e.to_string()))?;
-        let status = response
-            .json::<SystemStatus>()
+        let status = self
+            .service
+            .global_status()
             .await
             .map_err(|e| Status::internal(e.to_string()))?;
         Ok(Response::new(status.into()))
@@ -44,15 +34,9 @@ impl SystemService for System {
         &self,
         _request: Request<GetRockboxVersionRequest>,
     ) -> Result<Response<GetRockboxVersionResponse>, Status> {
-        let url = format!("{}/version", rockbox_url());
-        let response = self
-            .client
-            .get(url)
-            .send()
-            .await
-            .map_err(|e| Status::internal(e.to_string()))?;
-        let version = response
-            .json::<RockboxVersion>()
+        let version = self
+            .service
+            .rockbox_version()
             .await
             .map_err(|e| Status::internal(e.to_string()))?
             .version;
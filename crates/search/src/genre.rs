@@ -0,0 +1,107 @@
+use crate::{Indexable, Searchable};
+use rockbox_library::entity;
+use serde::{Deserialize, Serialize};
+use tantivy::{doc, schema::*, TantivyDocument};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Genre {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub image: Option<String>,
+}
+
+impl Indexable for Genre {
+    fn to_document(&self) -> TantivyDocument {
+        let schema: Schema = self.build_schema();
+
+        let id = schema.get_field("id").unwrap();
+        let name = schema.get_field("name").unwrap();
+        let description = schema.get_field("description").unwrap();
+        let image = schema.get_field("image").unwrap();
+
+        let mut document = doc!(
+            id => self.id.to_owned(),
+            name => self.name.to_owned(),
+        );
+
+        if let Some(value) = &self.description {
+            document.add_text(description, value);
+        }
+
+        if let Some(value) = &self.image {
+            document.add_text(image, value);
+        }
+
+        document
+    }
+
+    fn build_schema(&self) -> Schema {
+        let mut schema_builder: SchemaBuilder = Schema::builder();
+
+        schema_builder.add_text_field("id", STRING | STORED);
+        schema_builder.add_text_field("name", TEXT | STORED);
+        schema_builder.add_text_field("description", TEXT | STORED);
+        schema_builder.add_text_field("image", STRING | STORED);
+
+        schema_builder.build()
+    }
+}
+
+impl Searchable for Genre {
+    fn schema(&self) -> Schema {
+        self.build_schema()
+    }
+
+    fn default_fields(&self) -> Vec<String> {
+        vec!["name".to_string(), "description".to_string()]
+    }
+}
+
+impl From<entity::genre::Genre> for Genre {
+    fn from(genre: entity::genre::Genre) -> Self {
+        Self {
+            id: genre.id,
+            name: genre.name,
+            description: genre.description,
+            image: genre.image,
+        }
+    }
+}
+
+impl From<TantivyDocument> for Genre {
+    fn from(document: TantivyDocument) -> Self {
+        let mut schema_builder: SchemaBuilder = Schema::builder();
+
+        let id_field = schema_builder.add_text_field("id", STRING | STORED);
+        let name_field = schema_builder.add_text_field("name", TEXT | STORED);
+        let description_field = schema_builder.add_text_field("description", TEXT | STORED);
+        let image_field = schema_builder.add_text_field("image", STRING | STORED);
+
+        let id = document
+            .get_first(id_field)
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .to_string();
+        let name = document
+            .get_first(name_field)
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .to_string();
+        let description = document
+            .get_first(description_field)
+            .map(|value| value.as_str().unwrap().to_string());
+        let image = document
+            .get_first(image_field)
+            .map(|value| value.as_str().unwrap().to_string());
+
+        Self {
+            id,
+            name,
+            description,
+            image,
+        }
+    }
+}
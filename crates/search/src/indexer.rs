@@ -0,0 +1,263 @@
+use std::{collections::HashSet, sync::Mutex, thread};
+
+use anyhow::Error;
+use rockbox_library::repo;
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Sqlite};
+use tantivy::{
+    collector::DocSetCollector,
+    query::AllQuery,
+    schema::{Field, Value},
+    Index, IndexWriter, TantivyDocument, Term,
+};
+
+use crate::{
+    album::Album, artist::Artist, genre::Genre, liked_album::LikedAlbum, liked_track::LikedTrack,
+    suggestion::library_suggestions, track::Track, Indexable, Indexes,
+};
+
+const WRITER_HEAP_SIZE: usize = 50_000_000;
+
+/// Documents are committed in batches so that progress is visible to
+/// searches while a large library is being indexed.
+const BATCH_SIZE: usize = 5_000;
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct IndexProgress {
+    pub index: String,
+    pub indexed: usize,
+    pub total: usize,
+    pub done: bool,
+    pub error: Option<String>,
+}
+
+static PROGRESS: Mutex<Vec<IndexProgress>> = Mutex::new(Vec::new());
+
+pub fn progress() -> Vec<IndexProgress> {
+    PROGRESS.lock().unwrap().clone()
+}
+
+fn update_progress(index: &str, update: impl FnOnce(&mut IndexProgress)) {
+    let mut progress = PROGRESS.lock().unwrap();
+    let position = match progress.iter().position(|p| p.index == index) {
+        Some(position) => position,
+        None => {
+            progress.push(IndexProgress {
+                index: index.to_string(),
+                ..Default::default()
+            });
+            progress.len() - 1
+        }
+    };
+    update(&mut progress[position]);
+}
+
+fn id_field(index: &Index) -> Result<Field, Error> {
+    Ok(index.schema().get_field("id")?)
+}
+
+fn document_id(document: &TantivyDocument, id: Field) -> Option<String> {
+    document
+        .get_first(id)
+        .and_then(|value| value.as_str())
+        .map(|value| value.to_string())
+}
+
+/// Tantivy allows a single writer per index and fails with `LockBusy` on a
+/// second one. The writers are opened once and kept, updates are serialized
+/// so that incremental ones wait for the batch of a background reindex
+/// instead of failing.
+static WRITERS: Mutex<Vec<IndexWriter>> = Mutex::new(Vec::new());
+
+/// Clones of an index share its search executor, which tells the writer of
+/// `index` apart. The writer keeps the executor alive, its address can't be
+/// reused by another index.
+fn writes_to(index_writer: &IndexWriter, index: &Index) -> bool {
+    std::ptr::eq(
+        index_writer.index().search_executor(),
+        index.search_executor(),
+    )
+}
+
+/// Runs `update` with the writer of `index` and commits its changes, they are
+/// rolled back when it fails.
+pub(crate) fn write<R>(
+    index: &Index,
+    update: impl FnOnce(&mut IndexWriter) -> Result<R, Error>,
+) -> Result<R, Error> {
+    let mut writers = WRITERS.lock().unwrap();
+    let position = match writers.iter().position(|writer| writes_to(writer, index)) {
+        Some(position) => position,
+        None => {
+            writers.push(index.writer(WRITER_HEAP_SIZE)?);
+            writers.len() - 1
+        }
+    };
+    let index_writer = &mut writers[position];
+    let result = match update(index_writer) {
+        Ok(result) => result,
+        Err(e) => {
+            index_writer.rollback()?;
+            return Err(e);
+        }
+    };
+    index_writer.commit()?;
+    Ok(result)
+}
+
+/// Adds or replaces the documents of the given entities, documents are matched
+/// by their `id` term. `on_progress` is called after each committed batch with
+/// the number of documents indexed so far.
+pub fn index_entities<T: Indexable>(
+    index: &Index,
+    entities: &[T],
+    mut on_progress: impl FnMut(usize),
+) -> Result<usize, Error> {
+    let id = id_field(index)?;
+
+    for (i, batch) in entities.chunks(BATCH_SIZE).enumerate() {
+        write(index, |index_writer| {
+            for entity in batch {
+                let document = entity.to_document();
+                if let Some(value) = document_id(&document, id) {
+                    index_writer.delete_term(Term::from_field_text(id, &value));
+                }
+                index_writer.add_document(document)?;
+            }
+            Ok(())
+        })?;
+        on_progress(i * BATCH_SIZE + batch.len());
+    }
+
+    Ok(entities.len())
+}
+
+pub fn remove_entity(index: &Index, id: &str) -> Result<(), Error> {
+    remove_entities(index, &[id.to_string()])
+}
+
+pub fn remove_entities(index: &Index, ids: &[String]) -> Result<(), Error> {
+    let id = id_field(index)?;
+    write(index, |index_writer| {
+        for value in ids {
+            index_writer.delete_term(Term::from_field_text(id, value));
+        }
+        Ok(())
+    })
+}
+
+/// Removes the documents whose `id` isn't part of `ids`, used after a scan to
+/// drop the entities that no longer exist in the library.
+pub fn retain_entities(index: &Index, ids: &HashSet<String>) -> Result<usize, Error> {
+    let id = id_field(index)?;
+    let searcher = index.reader()?.searcher();
+    let stale: Vec<String> = searcher
+        .search(&AllQuery, &DocSetCollector)?
+        .into_iter()
+        .filter_map(|address| searcher.doc::<TantivyDocument>(address).ok())
+        .filter_map(|document| document_id(&document, id))
+        .filter(|value| !ids.contains(value))
+        .collect();
+
+    if !stale.is_empty() {
+        remove_entities(index, &stale)?;
+    }
+    Ok(stale.len())
+}
+
+/// Upserts the entities and removes stale documents on a background thread,
+/// the state of the job is available through [`progress`].
+pub fn spawn_indexing<T: Indexable + Send + 'static>(
+    name: &str,
+    index: Index,
+    entities: Vec<T>,
+    ids: HashSet<String>,
+) -> thread::JoinHandle<()> {
+    let name = name.to_string();
+    update_progress(&name, |progress| {
+        *progress = IndexProgress {
+            index: name.clone(),
+            total: entities.len(),
+            ..Default::default()
+        }
+    });
+
+    thread::spawn(move || {
+        let result = index_entities(&index, &entities, |indexed| {
+            update_progress(&name, |progress| progress.indexed = indexed)
+        })
+        .and_then(|_| retain_entities(&index, &ids));
+
+        update_progress(&name, |progress| {
+            progress.done = true;
+            progress.error = result.err().map(|e| e.to_string());
+        });
+    })
+}
+
+/// Brings every index up to date with the library database, without wiping
+/// the indexes first so searches keep working while this runs.
+pub async fn reindex_library(pool: Pool<Sqlite>, indexes: Indexes) -> Result<(), Error> {
+    let tracks = repo::track::all(pool.clone()).await?;
+    let albums = repo::album::all(pool.clone()).await?;
+    let artists = repo::artist::all(pool.clone()).await?;
+    let genres = repo::genre::all(pool.clone()).await?;
+    let liked_albums = repo::favourites::all_albums(pool.clone()).await?;
+    let liked_tracks = repo::favourites::all_tracks(pool.clone()).await?;
+
+    let ids = |ids: Vec<&String>| ids.into_iter().cloned().collect::<HashSet<String>>();
+
+    spawn_indexing(
+        "tracks",
+        indexes.tracks,
+        tracks.iter().map(|t| Track::from(t.clone())).collect(),
+        ids(tracks.iter().map(|t| &t.id).collect()),
+    );
+    spawn_indexing(
+        "albums",
+        indexes.albums,
+        albums.iter().map(|a| Album::from(a.clone())).collect(),
+        ids(albums.iter().map(|a| &a.id).collect()),
+    );
+    spawn_indexing(
+        "artists",
+        indexes.artists,
+        artists.iter().map(|a| Artist::from(a.clone())).collect(),
+        ids(artists.iter().map(|a| &a.id).collect()),
+    );
+    spawn_indexing(
+        "genres",
+        indexes.genres,
+        genres.iter().map(|g| Genre::from(g.clone())).collect(),
+        ids(genres.iter().map(|g| &g.id).collect()),
+    );
+    spawn_indexing(
+        "liked_albums",
+        indexes.liked_albums,
+        liked_albums
+            .iter()
+            .map(|a| LikedAlbum::from(a.clone()))
+            .collect(),
+        ids(liked_albums.iter().map(|a| &a.id).collect()),
+    );
+    spawn_indexing(
+        "liked_tracks",
+        indexes.liked_tracks,
+        liked_tracks
+            .iter()
+            .map(|t| LikedTrack::from(t.clone()))
+            .collect(),
+        ids(liked_tracks.iter().map(|t| &t.id).collect()),
+    );
+
+    let suggestions = library_suggestions(&artists, &albums, &tracks, &liked_albums, &liked_tracks);
+    let suggestion_ids = ids(suggestions.iter().map(|s| &s.id).collect());
+    spawn_indexing(
+        "suggestions",
+        indexes.suggestions,
+        suggestions,
+        suggestion_ids,
+    );
+
+    Ok(())
+}
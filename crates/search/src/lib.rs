@@ -4,20 +4,26 @@ use album::Album;
 use anyhow::Error;
 use artist::Artist;
 use file::File;
+use genre::Genre;
 use liked_album::LikedAlbum;
 use liked_track::LikedTrack;
-use tantivy::collector::TopDocs;
+use query::{document_ids, search_index, SearchHits, SearchOptions, SearchQuery};
+use std::collections::HashSet;
+use suggestion::Suggestion;
 use tantivy::directory::MmapDirectory;
-use tantivy::query::{FuzzyTermQuery, QueryParser};
-use tantivy::{schema::Schema, Index, TantivyDocument};
-use tantivy::{ReloadPolicy, Term};
+use tantivy::{schema::Schema, Index, TantivyDocument, TantivyError};
 use track::Track;
 
 pub mod album;
 pub mod artist;
 pub mod file;
+pub mod genre;
+pub mod indexer;
 pub mod liked_album;
 pub mod liked_track;
+pub mod query;
+pub mod suggestion;
+pub mod tokenizer;
 pub mod track;
 
 #[derive(Clone)]
@@ -28,6 +34,8 @@ pub struct Indexes {
     pub liked_albums: Index,
     pub liked_tracks: Index,
     pub files: Index,
+    pub genres: Index,
+    pub suggestions: Index,
 }
 
 pub fn create_indexes() -> Result<Indexes, Error> {
@@ -50,6 +58,11 @@ pub fn create_indexes() -> Result<Indexes, Error> {
         &format!("{}/liked_tracks", index_dir),
     )?;
     let files = create_index(File::default().schema(), &format!("{}/files", index_dir))?;
+    let genres = create_index(Genre::default().schema(), &format!("{}/genres", index_dir))?;
+    let suggestions = create_index(
+        Suggestion::default().build_schema(),
+        &format!("{}/suggestions", index_dir),
+    )?;
 
     Ok(Indexes {
         albums,
@@ -58,21 +71,34 @@ pub fn create_indexes() -> Result<Indexes, Error> {
         liked_albums,
         liked_tracks,
         files,
+        genres,
+        suggestions,
     })
 }
 
 fn create_index(schema: Schema, index_path: &str) -> Result<Index, Error> {
     std::fs::create_dir_all(index_path)?;
     let dir = MmapDirectory::open(index_path)?;
-    let index: Index = Index::open_or_create(dir, schema.clone())?;
+    let index: Index = match Index::open_or_create(dir, schema.clone()) {
+        Ok(index) => index,
+        // an index written with an older schema is recreated, the next scan
+        // fills it again
+        Err(TantivyError::SchemaError(_)) => {
+            std::fs::remove_dir_all(index_path)?;
+            std::fs::create_dir_all(index_path)?;
+            Index::open_or_create(MmapDirectory::open(index_path)?, schema)?
+        }
+        Err(e) => return Err(e.into()),
+    };
+    tokenizer::register_tokenizers(&index);
     Ok(index)
 }
 
 pub fn delete_all_documents(index: &Index) -> Result<(), Error> {
-    let mut index_writer = index.writer::<TantivyDocument>(50_000_000)?;
-    index_writer.delete_all_documents()?;
-    index_writer.commit()?;
-    Ok(())
+    indexer::write(index, |index_writer| {
+        index_writer.delete_all_documents()?;
+        Ok(())
+    })
 }
 
 pub trait Indexable {
@@ -86,10 +112,7 @@ pub trait Searchable {
 }
 
 pub fn index_entity<T: Indexable>(index: &Index, entity: &T) -> Result<(), Error> {
-    let mut index_writer = index.writer(50_000_000)?;
-    let doc = entity.to_document();
-    index_writer.add_document(doc)?;
-    index_writer.commit()?;
+    indexer::index_entities(index, std::slice::from_ref(entity), |_| {})?;
     Ok(())
 }
 
@@ -97,49 +120,65 @@ pub fn search_entities<T: Searchable>(
     index: &Index,
     query_string: &str,
     entity: &T, // The entity type to search
-) -> tantivy::Result<Vec<(f32, TantivyDocument)>> {
-    let reader = index
-        .reader_builder()
-        .reload_policy(ReloadPolicy::OnCommitWithDelay)
-        .try_into()?;
-    let searcher = reader.searcher();
-
-    // Get the schema and fields for the entity
-    let schema = entity.schema();
-    let default_fields: Vec<String> = entity.default_fields();
-
-    // Convert field names to Field objects
-    let fields: Vec<tantivy::schema::Field> = default_fields
-        .iter()
-        .filter_map(|field_name| Some(schema.get_field(field_name).unwrap()))
-        .collect();
-
-    // Parse the query
-    let query_parser = QueryParser::for_index(index, fields.clone());
-    let query = query_parser.parse_query(query_string)?;
-
-    // Execute the search and collect the top 10 results
-    let top_docs = searcher.search(&query, &TopDocs::with_limit(10))?;
-
-    // Return the documents and their scores
-    let mut results: Vec<(f32, TantivyDocument)> = top_docs
-        .into_iter()
-        .map(|(score, doc_address)| (score, searcher.doc(doc_address).unwrap()))
-        .collect();
+) -> Result<Vec<(f32, TantivyDocument)>, Error> {
+    let query = SearchQuery::parse(query_string);
+    let hits = search_index(index, &query, &SearchOptions::default(), entity, None)?;
+    Ok(hits.documents)
+}
 
-    if results.is_empty() {
-        // loop through the fields and search for fuzzy matches
-        for field in fields {
-            let term = Term::from_field_text(field, query_string);
-            let query = FuzzyTermQuery::new(term, 1, true);
-            let fuzzy_results: Vec<(f32, TantivyDocument)> = searcher
-                .search(&query, &TopDocs::with_limit(10))?
-                .into_iter()
-                .map(|(score, doc_address)| (score, searcher.doc(doc_address).unwrap()))
-                .collect();
-            results.extend(fuzzy_results);
-        }
-    }
+#[derive(Debug, Default, Clone)]
+pub struct LibrarySearchResults {
+    pub artists: SearchHits,
+    pub albums: SearchHits,
+    pub tracks: SearchHits,
+    pub liked_tracks: SearchHits,
+    pub liked_albums: SearchHits,
+    pub files: SearchHits,
+    pub genres: SearchHits,
+}
 
-    Ok(results)
+/// Searches every index with the same term and options, `liked:` filters
+/// select between the library and the liked tracks and albums indexes.
+pub fn search_library(
+    indexes: &Indexes,
+    term: &str,
+    options: &SearchOptions,
+) -> Result<LibrarySearchResults, Error> {
+    let query = SearchQuery::parse(term);
+    let tracks = |index: &Index, excluded: Option<&HashSet<String>>| {
+        search_index(index, &query, options, &Track::default(), excluded)
+    };
+    let albums = |index: &Index, excluded: Option<&HashSet<String>>| {
+        search_index(index, &query, options, &Album::default(), excluded)
+    };
+    let artists = || search_index(&indexes.artists, &query, options, &Artist::default(), None);
+    let files = || search_index(&indexes.files, &query, options, &File::default(), None);
+    let genres = || search_index(&indexes.genres, &query, options, &Genre::default(), None);
+
+    Ok(match query.liked {
+        Some(true) => LibrarySearchResults {
+            tracks: tracks(&indexes.liked_tracks, None)?,
+            albums: albums(&indexes.liked_albums, None)?,
+            liked_tracks: tracks(&indexes.liked_tracks, None)?,
+            liked_albums: albums(&indexes.liked_albums, None)?,
+            ..Default::default()
+        },
+        Some(false) => LibrarySearchResults {
+            tracks: tracks(&indexes.tracks, Some(&document_ids(&indexes.liked_tracks)?))?,
+            albums: albums(&indexes.albums, Some(&document_ids(&indexes.liked_albums)?))?,
+            artists: artists()?,
+            files: files()?,
+            genres: genres()?,
+            ..Default::default()
+        },
+        None => LibrarySearchResults {
+            tracks: tracks(&indexes.tracks, None)?,
+            albums: albums(&indexes.albums, None)?,
+            liked_tracks: tracks(&indexes.liked_tracks, None)?,
+            liked_albums: albums(&indexes.liked_albums, None)?,
+            artists: artists()?,
+            files: files()?,
+            genres: genres()?,
+        },
+    })
 }
@@ -52,7 +52,7 @@ impl Indexable for LikedAlbum {
         schema_builder.add_text_field("id", STRING | STORED);
         schema_builder.add_text_field("title", TEXT | STORED);
         schema_builder.add_text_field("artist", TEXT | STORED);
-        schema_builder.add_i64_field("year", STORED);
+        schema_builder.add_i64_field("year", INDEXED | STORED);
         schema_builder.add_text_field("year_string", STRING | STORED);
         schema_builder.add_text_field("album_art", STRING | STORED);
         schema_builder.add_text_field("md5", STRING | STORED);
@@ -94,7 +94,7 @@ impl From<TantivyDocument> for LikedAlbum {
         let id_field = schema_builder.add_text_field("id", STRING | STORED);
         let title_field = schema_builder.add_text_field("title", TEXT | STORED);
         let artist_field = schema_builder.add_text_field("artist", TEXT | STORED);
-        let year_field = schema_builder.add_i64_field("year", STORED);
+        let year_field = schema_builder.add_i64_field("year", INDEXED | STORED);
         let year_string_field = schema_builder.add_text_field("year_string", STRING | STORED);
         let album_art_field = schema_builder.add_text_field("album_art", STRING | STORED);
         let md5_field = schema_builder.add_text_field("md5", STRING | STORED);
@@ -0,0 +1,460 @@
+use std::{cmp::Ordering, collections::HashMap, collections::HashSet, ops::Bound};
+
+use anyhow::Error;
+use rockbox_library::audio_scan::split_genres;
+use serde::{Deserialize, Serialize};
+use tantivy::{
+    collector::{Count, DocSetCollector, TopDocs},
+    query::{
+        AllQuery, BooleanQuery, BoostQuery, EmptyQuery, Occur, Query, QueryParser, RangeQuery,
+        TermQuery,
+    },
+    schema::{Field, IndexRecordOption, Schema, Value},
+    Index, ReloadPolicy, TantivyDocument, Term,
+};
+
+use crate::Searchable;
+
+pub const DEFAULT_LIMIT: usize = 10;
+
+/// Matches loaded for a search, the facets and the sorts on a field only
+/// cover the most relevant ones.
+const MAX_MATCHES: usize = 1_000;
+
+/// Score multiplier of the exact matches when fuzzy matches are included.
+const EXACT_BOOST: f32 = 2.0;
+
+/// Fields that can be filtered on with a `field:value` term, values with
+/// spaces can be quoted: `artist:"pink floyd"`.
+const TEXT_FILTERS: [&str; 5] = ["artist", "album", "title", "genre", "composer"];
+
+/// A search term split into free text and field filters, e.g.
+/// `live artist:nirvana year:1990..1999 liked:true`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SearchQuery {
+    pub text: String,
+    pub fields: Vec<(String, String)>,
+    pub year: Option<(i64, i64)>,
+    pub format: Option<String>,
+    pub liked: Option<bool>,
+}
+
+impl SearchQuery {
+    pub fn parse(term: &str) -> Self {
+        let mut query = SearchQuery::default();
+        let mut text = vec![];
+
+        for token in tokenize(term) {
+            let (key, value) = match token.split_once(':') {
+                Some((key, value)) if !value.is_empty() => (key.to_lowercase(), value),
+                _ => {
+                    text.push(token);
+                    continue;
+                }
+            };
+            let value = value.trim_matches('"').to_string();
+
+            match key.as_str() {
+                "year" => match parse_year_range(&value) {
+                    Some(range) => query.year = Some(range),
+                    None => text.push(token),
+                },
+                "format" => query.format = Some(value.to_lowercase()),
+                "liked" => match value.as_str() {
+                    "true" | "yes" | "1" => query.liked = Some(true),
+                    "false" | "no" | "0" => query.liked = Some(false),
+                    _ => text.push(token),
+                },
+                key if TEXT_FILTERS.contains(&key) => query.fields.push((key.to_string(), value)),
+                _ => text.push(token),
+            }
+        }
+
+        query.text = text.join(" ");
+        query
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+            && self.fields.is_empty()
+            && self.year.is_none()
+            && self.format.is_none()
+            && self.liked.is_none()
+    }
+}
+
+fn tokenize(term: &str) -> Vec<String> {
+    let mut tokens = vec![];
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in term.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                current.push(c);
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+/// Accepts `1994`, `1990..1999`, `1990..` and `..1999`.
+fn parse_year_range(value: &str) -> Option<(i64, i64)> {
+    match value.split_once("..") {
+        Some((from, to)) => {
+            let from = match from {
+                "" => i64::MIN,
+                from => from.parse().ok()?,
+            };
+            let to = match to {
+                "" => i64::MAX,
+                to => to.parse().ok()?,
+            };
+            Some((from, to))
+        }
+        None => value.parse().ok().map(|year| (year, year)),
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortBy {
+    #[default]
+    Relevance,
+    Title,
+    Artist,
+    Album,
+    Year,
+    Length,
+    Added,
+}
+
+impl SortBy {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "relevance" | "score" => Some(SortBy::Relevance),
+            "title" | "name" => Some(SortBy::Title),
+            "artist" => Some(SortBy::Artist),
+            "album" => Some(SortBy::Album),
+            "year" => Some(SortBy::Year),
+            "length" | "duration" => Some(SortBy::Length),
+            "added" | "created_at" => Some(SortBy::Added),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchOptions {
+    pub offset: usize,
+    pub limit: usize,
+    pub sort: SortBy,
+    pub descending: bool,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            limit: DEFAULT_LIMIT,
+            sort: SortBy::Relevance,
+            descending: false,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Facets {
+    pub genres: Vec<FacetCount>,
+    pub decades: Vec<FacetCount>,
+    pub formats: Vec<FacetCount>,
+}
+
+/// One page of matching documents, `total` counts every match.
+#[derive(Debug, Default, Clone)]
+pub struct SearchHits {
+    pub total: usize,
+    pub documents: Vec<(f32, TantivyDocument)>,
+    pub facets: Facets,
+}
+
+struct Fields {
+    year: Option<Field>,
+    path: Option<Field>,
+    genre: Option<Field>,
+    sort: Option<Field>,
+}
+
+impl Fields {
+    fn new(schema: &Schema, sort: SortBy) -> Self {
+        let field = |name: &str| schema.get_field(name).ok();
+        let sort = match sort {
+            SortBy::Relevance => None,
+            SortBy::Title => field("title").or_else(|| field("name")),
+            SortBy::Artist => field("artist").or_else(|| field("name")),
+            SortBy::Album => field("album").or_else(|| field("title")),
+            SortBy::Year => field("year"),
+            SortBy::Length => field("length"),
+            SortBy::Added => field("created_at"),
+        };
+        Self {
+            year: field("year"),
+            path: field("path"),
+            genre: field("genre"),
+            sort,
+        }
+    }
+}
+
+fn text_value(document: &TantivyDocument, field: Option<Field>) -> Option<&str> {
+    field.and_then(|field| document.get_first(field).and_then(|value| value.as_str()))
+}
+
+fn i64_value(document: &TantivyDocument, field: Option<Field>) -> Option<i64> {
+    field.and_then(|field| document.get_first(field).and_then(|value| value.as_i64()))
+}
+
+pub(crate) fn format_of(path: &str) -> Option<String> {
+    std::path::Path::new(path)
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+}
+
+fn decade_of(year: i64) -> Option<String> {
+    match year > 0 {
+        true => Some(format!("{}s", year - year % 10)),
+        false => None,
+    }
+}
+
+/// Ids of every document of an index, used to evaluate `liked:false`
+/// against the liked tracks and albums indexes.
+pub fn document_ids(index: &Index) -> Result<HashSet<String>, Error> {
+    let id = index.schema().get_field("id")?;
+    let searcher = index.reader()?.searcher();
+    Ok(searcher
+        .search(&AllQuery, &DocSetCollector)?
+        .into_iter()
+        .filter_map(|address| searcher.doc::<TantivyDocument>(address).ok())
+        .filter_map(|document| text_value(&document, Some(id)).map(|id| id.to_string()))
+        .collect())
+}
+
+fn text_query<T: Searchable>(index: &Index, text: &str, entity: &T, fuzzy: bool) -> Box<dyn Query> {
+    let schema = index.schema();
+    let fields: Vec<Field> = entity
+        .default_fields()
+        .iter()
+        .filter_map(|name| schema.get_field(name).ok())
+        .collect();
+    let mut parser = QueryParser::for_index(index, fields.clone());
+    if fuzzy {
+        for field in &fields {
+            parser.set_field_fuzzy(*field, true, 1, true);
+        }
+    }
+    parser.parse_query_lenient(text).0
+}
+
+fn build_query<T: Searchable>(
+    index: &Index,
+    query: &SearchQuery,
+    entity: &T,
+    excluded_ids: Option<&HashSet<String>>,
+    fuzzy: bool,
+) -> Result<Box<dyn Query>, Error> {
+    let schema = index.schema();
+    let mut clauses: Vec<(Occur, Box<dyn Query>)> = vec![];
+
+    if !query.text.is_empty() {
+        let exact = text_query(index, &query.text, entity, false);
+        clauses.push(match fuzzy {
+            // exact matches still rank above the fuzzy ones
+            true => (
+                Occur::Must,
+                Box::new(BooleanQuery::new(vec![
+                    (Occur::Should, Box::new(BoostQuery::new(exact, EXACT_BOOST))),
+                    (Occur::Should, text_query(index, &query.text, entity, true)),
+                ])),
+            ),
+            false => (Occur::Must, exact),
+        });
+    }
+
+    for (name, value) in &query.fields {
+        // a filter on a field this entity doesn't have can't match anything
+        let field = match schema.get_field(name) {
+            Ok(field) => field,
+            Err(_) => return Ok(Box::new(EmptyQuery)),
+        };
+        let mut parser = QueryParser::for_index(index, vec![field]);
+        parser.set_conjunction_by_default();
+        let (field_query, _) = parser.parse_query_lenient(value);
+        clauses.push((Occur::Must, field_query));
+    }
+
+    if let Some((from, to)) = query.year {
+        if schema.get_field("year").is_err() {
+            return Ok(Box::new(EmptyQuery));
+        }
+        clauses.push((
+            Occur::Must,
+            Box::new(RangeQuery::new_i64_bounds(
+                "year".to_string(),
+                Bound::Included(from),
+                Bound::Included(to),
+            )),
+        ));
+    }
+
+    if let Some(format) = &query.format {
+        let field = match schema.get_field("format") {
+            Ok(field) => field,
+            Err(_) => return Ok(Box::new(EmptyQuery)),
+        };
+        clauses.push((
+            Occur::Must,
+            Box::new(TermQuery::new(
+                Term::from_field_text(field, format),
+                IndexRecordOption::Basic,
+            )),
+        ));
+    }
+
+    if let (Some(excluded_ids), Ok(id)) = (excluded_ids, schema.get_field("id")) {
+        for excluded_id in excluded_ids {
+            clauses.push((
+                Occur::MustNot,
+                Box::new(TermQuery::new(
+                    Term::from_field_text(id, excluded_id),
+                    IndexRecordOption::Basic,
+                )),
+            ));
+        }
+    }
+
+    // excluding documents needs a set to exclude them from
+    if !clauses.iter().any(|(occur, _)| *occur == Occur::Must) {
+        clauses.push((Occur::Must, Box::new(AllQuery)));
+    }
+    Ok(Box::new(BooleanQuery::new(clauses)))
+}
+
+/// Runs a fielded search on an index, the filters are part of the tantivy
+/// query. The most relevant matches, `MAX_MATCHES` or up to the end of the
+/// page requested, are loaded to count the facets and to sort on a field.
+pub fn search_index<T: Searchable>(
+    index: &Index,
+    query: &SearchQuery,
+    options: &SearchOptions,
+    entity: &T,
+    excluded_ids: Option<&HashSet<String>>,
+) -> Result<SearchHits, Error> {
+    let reader = index
+        .reader_builder()
+        .reload_policy(ReloadPolicy::OnCommitWithDelay)
+        .try_into()?;
+    let searcher = reader.searcher();
+    let schema = index.schema();
+    let fields = Fields::new(&schema, options.sort);
+    let page_end = options.offset + options.limit;
+
+    let mut tantivy_query = build_query(index, query, entity, excluded_ids, false)?;
+    let mut total = searcher.search(&tantivy_query, &Count)?;
+    if !query.text.is_empty() && total < page_end {
+        tantivy_query = build_query(index, query, entity, excluded_ids, true)?;
+        total = searcher.search(&tantivy_query, &Count)?;
+    }
+
+    let limit = page_end.max(MAX_MATCHES).min(total).max(1);
+    let mut matches: Vec<(f32, TantivyDocument)> = searcher
+        .search(&tantivy_query, &TopDocs::with_limit(limit))?
+        .into_iter()
+        .filter_map(|(score, address)| searcher.doc(address).ok().map(|doc| (score, doc)))
+        .collect();
+
+    let facets = facets(&matches, &fields);
+
+    if fields.sort.is_some() {
+        matches.sort_by(|(_, a), (_, b)| {
+            match (i64_value(a, fields.sort), i64_value(b, fields.sort)) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                _ => {
+                    let a = text_value(a, fields.sort)
+                        .unwrap_or_default()
+                        .to_lowercase();
+                    let b = text_value(b, fields.sort)
+                        .unwrap_or_default()
+                        .to_lowercase();
+                    a.cmp(&b)
+                }
+            }
+        });
+    } else {
+        matches.sort_by(|(a, _), (b, _)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+    }
+
+    if options.descending {
+        matches.reverse();
+    }
+
+    Ok(SearchHits {
+        total,
+        documents: matches
+            .into_iter()
+            .skip(options.offset)
+            .take(options.limit)
+            .collect(),
+        facets,
+    })
+}
+
+fn facets(matches: &[(f32, TantivyDocument)], fields: &Fields) -> Facets {
+    let mut genres: HashMap<String, usize> = HashMap::new();
+    let mut decades: HashMap<String, usize> = HashMap::new();
+    let mut formats: HashMap<String, usize> = HashMap::new();
+
+    for (_, document) in matches {
+        if let Some(genre) = text_value(document, fields.genre) {
+            for genre in split_genres(genre) {
+                *genres.entry(genre).or_default() += 1;
+            }
+        }
+        if let Some(decade) = i64_value(document, fields.year).and_then(decade_of) {
+            *decades.entry(decade).or_default() += 1;
+        }
+        if let Some(format) = text_value(document, fields.path).and_then(format_of) {
+            *formats.entry(format).or_default() += 1;
+        }
+    }
+
+    let sorted = |counts: HashMap<String, usize>| {
+        let mut counts: Vec<FacetCount> = counts
+            .into_iter()
+            .map(|(value, count)| FacetCount { value, count })
+            .collect();
+        counts.sort_by(|a, b| b.count.cmp(&a.count).then(a.value.cmp(&b.value)));
+        counts
+    };
+
+    Facets {
+        genres: sorted(genres),
+        decades: sorted(decades),
+        formats: sorted(formats),
+    }
+}
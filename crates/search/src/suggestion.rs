@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+
+use crate::{tokenizer::fold_terms, tokenizer::AUTOCOMPLETE_TOKENIZER, Indexable};
+use anyhow::Error;
+use rockbox_library::entity;
+use serde::{Deserialize, Serialize};
+use tantivy::{
+    collector::TopDocs,
+    query::{BooleanQuery, BoostQuery, Occur, Query, TermQuery},
+    schema::*,
+    Index, TantivyDocument, Term,
+};
+
+/// Number of best matching documents reranked by popularity.
+const CANDIDATES: usize = 200;
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Suggestion {
+    pub id: String,
+    /// One of "artist", "album" or "track".
+    pub kind: String,
+    pub label: String,
+    pub artist: Option<String>,
+    pub cover: Option<String>,
+    pub popularity: u64,
+}
+
+impl Indexable for Suggestion {
+    fn to_document(&self) -> TantivyDocument {
+        let schema: Schema = self.build_schema();
+
+        let id = schema.get_field("id").unwrap();
+        let kind = schema.get_field("kind").unwrap();
+        let name = schema.get_field("name").unwrap();
+        let context = schema.get_field("context").unwrap();
+        let label = schema.get_field("label").unwrap();
+        let artist = schema.get_field("artist").unwrap();
+        let cover = schema.get_field("cover").unwrap();
+        let popularity = schema.get_field("popularity").unwrap();
+
+        let mut document = TantivyDocument::default();
+        document.add_text(id, &self.id);
+        document.add_text(kind, &self.kind);
+        document.add_text(name, &self.label);
+        document.add_text(label, &self.label);
+        document.add_u64(popularity, self.popularity);
+
+        if let Some(value) = &self.artist {
+            document.add_text(context, value);
+            document.add_text(artist, value);
+        }
+
+        if let Some(value) = &self.cover {
+            document.add_text(cover, value);
+        }
+
+        document
+    }
+
+    fn build_schema(&self) -> Schema {
+        let mut schema_builder: SchemaBuilder = Schema::builder();
+
+        let prefixes = TextOptions::default().set_indexing_options(
+            TextFieldIndexing::default()
+                .set_tokenizer(AUTOCOMPLETE_TOKENIZER)
+                .set_index_option(IndexRecordOption::WithFreqs),
+        );
+
+        schema_builder.add_text_field("id", STRING | STORED);
+        schema_builder.add_text_field("kind", STRING | STORED);
+        schema_builder.add_text_field("name", prefixes.clone());
+        schema_builder.add_text_field("context", prefixes);
+        schema_builder.add_text_field("label", STORED);
+        schema_builder.add_text_field("artist", STORED);
+        schema_builder.add_text_field("cover", STORED);
+        schema_builder.add_u64_field("popularity", STORED);
+
+        schema_builder.build()
+    }
+}
+
+impl From<TantivyDocument> for Suggestion {
+    fn from(document: TantivyDocument) -> Self {
+        let schema = Suggestion::default().build_schema();
+        let text = |name: &str| {
+            document
+                .get_first(schema.get_field(name).unwrap())
+                .and_then(|value| value.as_str())
+                .map(|value| value.to_string())
+        };
+
+        Self {
+            id: text("id").unwrap_or_default(),
+            kind: text("kind").unwrap_or_default(),
+            label: text("label").unwrap_or_default(),
+            artist: text("artist"),
+            cover: text("cover"),
+            popularity: document
+                .get_first(schema.get_field("popularity").unwrap())
+                .and_then(|value| value.as_u64())
+                .unwrap_or(0),
+        }
+    }
+}
+
+/// Builds the suggestions of the whole library, the popularity of an entry is
+/// the number of favourites pointing to it: a liked track counts for itself,
+/// its album and its artist, a liked album for itself and its artist.
+pub fn library_suggestions(
+    artists: &[entity::artist::Artist],
+    albums: &[entity::album::Album],
+    tracks: &[entity::track::Track],
+    liked_albums: &[entity::album::Album],
+    liked_tracks: &[entity::track::Track],
+) -> Vec<Suggestion> {
+    let mut popularity: HashMap<&str, u64> = HashMap::new();
+    for track in liked_tracks {
+        for id in [&track.id, &track.album_id, &track.artist_id] {
+            *popularity.entry(id).or_default() += 1;
+        }
+    }
+    for album in liked_albums {
+        for id in [&album.id, &album.artist_id] {
+            *popularity.entry(id).or_default() += 1;
+        }
+    }
+    let popularity = |id: &str| popularity.get(id).copied().unwrap_or(0);
+
+    let artists = artists.iter().map(|artist| Suggestion {
+        id: artist.id.clone(),
+        kind: "artist".to_string(),
+        label: artist.name.clone(),
+        artist: None,
+        cover: artist.image.clone(),
+        popularity: popularity(&artist.id),
+    });
+    let albums = albums.iter().map(|album| Suggestion {
+        id: album.id.clone(),
+        kind: "album".to_string(),
+        label: album.title.clone(),
+        artist: Some(album.artist.clone()),
+        cover: album.album_art.clone(),
+        popularity: popularity(&album.id),
+    });
+    let tracks = tracks.iter().map(|track| Suggestion {
+        id: track.id.clone(),
+        kind: "track".to_string(),
+        label: track.title.clone(),
+        artist: Some(track.artist.clone()),
+        cover: track.album_art.clone(),
+        popularity: popularity(&track.id),
+    });
+
+    artists.chain(albums).chain(tracks).collect()
+}
+
+/// Every typed word has to be a prefix of a word of the suggestion, either in
+/// its label or in its artist. Matches are ordered by popularity, then by
+/// relevance, labels weigh more than artists.
+pub fn autocomplete(index: &Index, text: &str, limit: usize) -> Result<Vec<Suggestion>, Error> {
+    let terms = fold_terms(text);
+    if terms.is_empty() || limit == 0 {
+        return Ok(vec![]);
+    }
+
+    let schema = index.schema();
+    let name = schema.get_field("name")?;
+    let context = schema.get_field("context")?;
+
+    let term_query = |field: Field, value: &str| -> Box<dyn Query> {
+        Box::new(TermQuery::new(
+            Term::from_field_text(field, value),
+            IndexRecordOption::WithFreqs,
+        ))
+    };
+    let query = BooleanQuery::new(
+        terms
+            .iter()
+            .map(|term| {
+                let either: Box<dyn Query> = Box::new(BooleanQuery::new(vec![
+                    (
+                        Occur::Should,
+                        Box::new(BoostQuery::new(term_query(name, term), 2.0)),
+                    ),
+                    (Occur::Should, term_query(context, term)),
+                ]));
+                (Occur::Must, either)
+            })
+            .collect(),
+    );
+
+    let searcher = index.reader()?.searcher();
+    let mut suggestions = searcher
+        .search(&query, &TopDocs::with_limit(CANDIDATES))?
+        .into_iter()
+        .map(|(score, address)| {
+            let document: TantivyDocument = searcher.doc(address)?;
+            Ok((score, Suggestion::from(document)))
+        })
+        .collect::<Result<Vec<(f32, Suggestion)>, Error>>()?;
+
+    suggestions.sort_by(|(a_score, a), (b_score, b)| {
+        b.popularity
+            .cmp(&a.popularity)
+            .then(b_score.total_cmp(a_score))
+            .then(a.label.len().cmp(&b.label.len()))
+    });
+
+    Ok(suggestions
+        .into_iter()
+        .map(|(_, suggestion)| suggestion)
+        .take(limit)
+        .collect())
+}
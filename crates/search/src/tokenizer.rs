@@ -0,0 +1,96 @@
+use tantivy::{
+    tokenizer::{
+        AsciiFoldingFilter, LowerCaser, SimpleTokenizer, TextAnalyzer, Token, TokenStream,
+        Tokenizer,
+    },
+    Index,
+};
+
+pub const AUTOCOMPLETE_TOKENIZER: &str = "autocomplete";
+
+const MIN_GRAM: usize = 1;
+const MAX_GRAM: usize = 15;
+
+/// Splits the text into words and emits every prefix of each word, so a
+/// single term lookup matches a word that is still being typed.
+#[derive(Clone, Default)]
+pub struct EdgeNgramTokenizer {
+    words: SimpleTokenizer,
+}
+
+pub struct EdgeNgramTokenStream {
+    tokens: Vec<Token>,
+    index: usize,
+}
+
+impl Tokenizer for EdgeNgramTokenizer {
+    type TokenStream<'a> = EdgeNgramTokenStream;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        let mut tokens = vec![];
+        let mut words = self.words.token_stream(text);
+        while words.advance() {
+            let word = words.token();
+            let prefixes = word.text.char_indices().take(MAX_GRAM).skip(MIN_GRAM - 1);
+            for (start, c) in prefixes {
+                let end = start + c.len_utf8();
+                tokens.push(Token {
+                    offset_from: word.offset_from,
+                    offset_to: word.offset_from + end,
+                    position: word.position,
+                    text: word.text[..end].to_string(),
+                    position_length: 1,
+                });
+            }
+        }
+        EdgeNgramTokenStream { tokens, index: 0 }
+    }
+}
+
+impl TokenStream for EdgeNgramTokenStream {
+    fn advance(&mut self) -> bool {
+        if self.index < self.tokens.len() {
+            self.index += 1;
+            return true;
+        }
+        false
+    }
+
+    fn token(&self) -> &Token {
+        &self.tokens[self.index - 1]
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.tokens[self.index - 1]
+    }
+}
+
+/// Prefixes are folded to lowercase ASCII, "Björk" is indexed as "b", "bj",
+/// "bjo", "bjor" and "bjork".
+pub fn autocomplete_analyzer() -> TextAnalyzer {
+    TextAnalyzer::builder(EdgeNgramTokenizer::default())
+        .filter(LowerCaser)
+        .filter(AsciiFoldingFilter)
+        .build()
+}
+
+pub fn register_tokenizers(index: &Index) {
+    index
+        .tokenizers()
+        .register(AUTOCOMPLETE_TOKENIZER, autocomplete_analyzer());
+}
+
+/// Folds the typed text the same way the prefixes are indexed, long words are
+/// cut to the longest indexed prefix.
+pub fn fold_terms(text: &str) -> Vec<String> {
+    let mut analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+        .filter(LowerCaser)
+        .filter(AsciiFoldingFilter)
+        .build();
+    let mut terms = vec![];
+    let mut stream = analyzer.token_stream(text);
+    while stream.advance() {
+        terms.push(stream.token().text.chars().take(MAX_GRAM).collect());
+    }
+    terms
+}
@@ -1,4 +1,4 @@
-use crate::{Indexable, Searchable};
+use crate::{query::format_of, Indexable, Searchable};
 use rockbox_library::entity;
 use serde::{Deserialize, Serialize};
 use tantivy::{doc, schema::*, TantivyDocument};
@@ -57,6 +57,7 @@ impl Indexable for Track {
         let genre_id = schema.get_field("genre_id").unwrap();
         let created_at = schema.get_field("created_at").unwrap();
         let updated_at = schema.get_field("updated_at").unwrap();
+        let format = schema.get_field("format").unwrap();
 
         let mut document = doc!(
             id => self.id.to_owned(),
@@ -96,6 +97,9 @@ impl Indexable for Track {
 
         document.add_text(created_at, &self.created_at);
         document.add_text(updated_at, &self.updated_at);
+        if let Some(value) = format_of(&self.path) {
+            document.add_text(format, value);
+        }
 
         document
     }
@@ -116,7 +120,7 @@ impl Indexable for Track {
         schema_builder.add_i64_field("frequency", STORED);
         schema_builder.add_i64_field("length", STORED);
         schema_builder.add_i64_field("track_number", STORED);
-        schema_builder.add_i64_field("year", STORED);
+        schema_builder.add_i64_field("year", INDEXED | STORED);
         schema_builder.add_text_field("year_string", STRING | STORED);
         schema_builder.add_text_field("genre", TEXT | STORED);
         schema_builder.add_text_field("md5", STRING | STORED);
@@ -126,6 +130,8 @@ impl Indexable for Track {
         schema_builder.add_text_field("genre_id", STRING | STORED);
         schema_builder.add_text_field("created_at", STRING | STORED);
         schema_builder.add_text_field("updated_at", STRING | STORED);
+        // the extension of the path, for `format:` filters
+        schema_builder.add_text_field("format", STRING);
 
         schema_builder.build()
     }
@@ -194,7 +200,7 @@ impl From<TantivyDocument> for Track {
         let frequency_field = schema_builder.add_i64_field("frequency", STORED);
         let length_field = schema_builder.add_i64_field("length", STORED);
         let track_number_field = schema_builder.add_i64_field("track_number", STORED);
-        let year_field = schema_builder.add_i64_field("year", STORED);
+        let year_field = schema_builder.add_i64_field("year", INDEXED | STORED);
         let year_string_field = schema_builder.add_text_field("year_string", STRING | STORED);
         let genre_field = schema_builder.add_text_field("genre", TEXT | STORED);
         let md5_field = schema_builder.add_text_field("md5", STRING | STORED);
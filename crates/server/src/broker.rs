@@ -0,0 +1,374 @@
+use std::{collections::HashMap, thread, time::Instant};
+
+use anyhow::Error;
+use rockbox_graphql::{
+    schema::objects::{
+        self,
+        audio_status::AudioStatus,
+        track::{Track, TrackPosition},
+    },
+    simplebroker::SimpleBroker,
+};
+use rockbox_library::repo;
+use rockbox_service::{
+    events::{self, EventKind, PlaybackSettings, QueueDiff},
+    radio, transitions, RockboxService,
+};
+use rockbox_sys::{self as rb, types::mp3_entry::Mp3Entry};
+use sqlx::{Pool, Sqlite};
+use tokio::runtime::Runtime;
+
+use crate::{GLOBAL_MUTEX, PLAYER_MUTEX};
+
+/// Ticks between two snapshots of the player.
+const INTERVAL: i32 = rb::HZ / 4;
+
+/// Difference between the elapsed time and the expected one above which
+/// the track is considered seeked.
+const SEEK_THRESHOLD_MS: u64 = 2000;
+
+/// Cheap to read values changing whenever the queue does.
+#[derive(PartialEq, Default)]
+struct QueueFingerprint {
+    generation: u64,
+    amount: i32,
+    first_index: i32,
+    last_insert_pos: i32,
+    seed: i32,
+    last_shuffled_start: i32,
+}
+
+impl QueueFingerprint {
+    fn read() -> Self {
+        Self {
+            generation: events::queue_generation(),
+            amount: rb::playlist::amount(),
+            first_index: rb::playlist::first_index(),
+            last_insert_pos: rb::playlist::last_insert_pos(),
+            seed: rb::playlist::seed(),
+            last_shuffled_start: rb::playlist::last_shuffled_start(),
+        }
+    }
+}
+
+/// The last published state of the player, a snapshot taken every
+/// `INTERVAL` is compared to it and only the differences are published.
+#[derive(Default)]
+struct Broker {
+    status: Option<i32>,
+    index: Option<i32>,
+    path: Option<String>,
+    track: Option<Track>,
+    elapsed: u64,
+    elapsed_at: Option<Instant>,
+    volume: Option<i32>,
+    settings: Option<PlaybackSettings>,
+    timestretch_available: bool,
+    queue: Option<QueueFingerprint>,
+    queue_index: Option<i32>,
+    paths: Vec<String>,
+    entries: HashMap<String, Mp3Entry>,
+}
+
+pub fn run() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let service = rt.block_on(rockbox_service::instance()).unwrap();
+    let pool = service.pool.clone();
+    let mut broker = Broker::default();
+
+    loop {
+        // playback runs on a remote device, its player publishes the changes
+        if *GLOBAL_MUTEX.lock().unwrap() == 1 {
+            rb::system::sleep(rb::HZ);
+            continue;
+        }
+
+        let player_mutex = PLAYER_MUTEX.lock().unwrap();
+        if let Err(e) = broker.update(&rt, &service, &pool) {
+            eprintln!("Error: {}", e);
+        }
+        drop(player_mutex);
+
+        rb::system::sleep(INTERVAL);
+    }
+}
+
+impl Broker {
+    fn update(
+        &mut self,
+        rt: &Runtime,
+        service: &RockboxService,
+        pool: &Pool<Sqlite>,
+    ) -> Result<(), Error> {
+        let status = rb::playback::status().status;
+        if self.status != Some(status) {
+            self.status = Some(status);
+            SimpleBroker::publish(AudioStatus { status });
+            events::publish(EventKind::StatusChanged { status });
+        }
+
+        self.update_settings();
+        self.update_track(rt, service, pool, status)?;
+        self.update_queue(rt, pool)?;
+        Ok(())
+    }
+
+    fn update_settings(&mut self) {
+        let global_settings = rb::settings::get_global_settings();
+        let volume = global_settings.volume;
+        let settings = PlaybackSettings {
+            playlist_shuffle: global_settings.playlist_shuffle,
+            repeat_mode: global_settings.repeat_mode,
+            pitch: rb::sound::get_pitch(),
+            timestretch: rb::sound::dsp::get_timestretch(),
+        };
+        let timestretch_available = rb::sound::dsp::timestretch_available();
+
+        let volume_changed = self.volume != Some(volume);
+        let settings_changed = self.settings.as_ref() != Some(&settings)
+            || self.timestretch_available != timestretch_available;
+        if !volume_changed && !settings_changed {
+            return;
+        }
+
+        SimpleBroker::publish(objects::playback_settings::PlaybackSettings {
+            volume,
+            playlist_shuffle: settings.playlist_shuffle,
+            repeat_mode: settings.repeat_mode,
+            pitch: settings.pitch,
+            timestretch: settings.timestretch,
+            timestretch_available,
+        });
+        if volume_changed {
+            events::publish(EventKind::VolumeChanged { volume });
+        }
+        if settings_changed {
+            events::publish(EventKind::SettingsChanged(settings.clone()));
+        }
+        self.volume = Some(volume);
+        self.settings = Some(settings);
+        self.timestretch_available = timestretch_available;
+    }
+
+    fn update_track(
+        &mut self,
+        rt: &Runtime,
+        service: &RockboxService,
+        pool: &Pool<Sqlite>,
+        status: i32,
+    ) -> Result<(), Error> {
+        let mut current_track = rb::playback::current_track();
+        // a radio stream plays as its station, whichever segment plays
+        let playing_radio = current_track
+            .as_mut()
+            .map(radio::now_playing_entry)
+            .unwrap_or(false);
+        let index = rb::playlist::index();
+        let path = current_track.as_ref().map(|t| t.path.clone());
+        let now = Instant::now();
+
+        let (elapsed, length) = current_track
+            .as_ref()
+            .map(|t| (t.elapsed, t.length))
+            .unwrap_or_default();
+        if let Err(e) = rt.block_on(service.track_progress(path.as_deref(), elapsed, length)) {
+            eprintln!("Error saving the resume positions: {}", e);
+        }
+
+        let changed = match playing_radio {
+            // the segments follow each other, the stream title tells the track
+            true => {
+                self.path != path
+                    || self.track.as_ref().map(|t| (&t.title, &t.artist))
+                        != current_track.as_ref().map(|t| (&t.title, &t.artist))
+            }
+            false => self.path != path || self.index != Some(index),
+        };
+        if changed {
+            let mut track: Option<Track> = current_track.clone().map(|t| t.into());
+            let mut entry = current_track;
+            if let (Some(track), Some(entry)) = (track.as_mut(), entry.as_mut()) {
+                let hash = format!("{:x}", md5::compute(entry.path.as_bytes()));
+                if let Some(metadata) =
+                    rt.block_on(repo::track::find_by_md5(pool.clone(), &hash))?
+                {
+                    // rockbox fills the playcount of its database when enabled
+                    if let Err(e) = rt.block_on(repo::track::record_play(
+                        pool.clone(),
+                        &metadata.id,
+                        entry.playcount,
+                    )) {
+                        eprintln!("Error counting the play: {}", e);
+                    }
+                    track.id = Some(metadata.id.clone());
+                    track.album_art = metadata.album_art.clone();
+                    track.album_id = Some(metadata.album_id.clone());
+                    track.artist_id = Some(metadata.artist_id.clone());
+                    entry.id = Some(metadata.id);
+                    entry.album_art = metadata.album_art;
+                    entry.album_id = Some(metadata.album_id);
+                    entry.artist_id = Some(metadata.artist_id);
+                    entry.genre_id = Some(metadata.genre_id);
+                }
+            }
+
+            if let Some(track) = track.clone() {
+                SimpleBroker::publish(track.clone());
+                SimpleBroker::publish(TrackPosition {
+                    track_id: track.id.clone(),
+                    elapsed: track.elapsed,
+                });
+                if track.id.is_some() {
+                    let pool = pool.clone();
+                    thread::spawn(move || {
+                        let rt = tokio::runtime::Builder::new_current_thread()
+                            .enable_all()
+                            .build()
+                            .unwrap();
+                        match rt.block_on(scrobble(track, pool)) {
+                            Ok(_) => {}
+                            Err(e) => eprintln!("{}", e),
+                        }
+                    });
+                }
+            }
+            events::publish(EventKind::TrackChanged {
+                index,
+                track: entry.map(Box::new),
+            });
+
+            self.elapsed = track.as_ref().map(|t| t.elapsed).unwrap_or_default();
+            self.elapsed_at = Some(now);
+            self.path = path;
+            self.index = Some(index);
+            self.track = track;
+            return Ok(());
+        }
+
+        let (Some(track), Some(current_track)) = (self.track.as_mut(), current_track) else {
+            return Ok(());
+        };
+
+        let expected = match (status, self.elapsed_at) {
+            (1, Some(elapsed_at)) => {
+                self.elapsed + now.duration_since(elapsed_at).as_millis() as u64
+            }
+            _ => self.elapsed,
+        };
+        if current_track.elapsed.abs_diff(expected) > SEEK_THRESHOLD_MS {
+            events::publish(EventKind::Seeked {
+                elapsed: current_track.elapsed,
+            });
+        }
+
+        // the lyrics follow the position on every tick, the track subscribers
+        // once per second
+        if current_track.elapsed != self.elapsed {
+            SimpleBroker::publish(TrackPosition {
+                track_id: track.id.clone(),
+                elapsed: current_track.elapsed,
+            });
+        }
+        if current_track.elapsed / 1000 != track.elapsed / 1000 {
+            track.elapsed = current_track.elapsed;
+            SimpleBroker::publish(track.clone());
+        }
+        self.elapsed = current_track.elapsed;
+        self.elapsed_at = Some(now);
+        Ok(())
+    }
+
+    fn update_queue(&mut self, rt: &Runtime, pool: &Pool<Sqlite>) -> Result<(), Error> {
+        let fingerprint = QueueFingerprint::read();
+        let index = rb::playlist::index();
+        let queue_changed = self.queue.as_ref() != Some(&fingerprint);
+        if !queue_changed && self.queue_index == Some(index) {
+            return Ok(());
+        }
+        self.queue_index = Some(index);
+
+        if queue_changed {
+            let paths: Vec<String> = (0..fingerprint.amount)
+                .map(|i| rb::playlist::get_track_info(i).filename)
+                .collect();
+
+            if let Some(diff) = QueueDiff::between(&self.paths, &paths) {
+                events::publish(EventKind::QueueChanged {
+                    amount: fingerprint.amount,
+                    diff,
+                });
+            }
+
+            // only the tracks entering the queue are read
+            let mut entries = HashMap::new();
+            for path in &paths {
+                if entries.contains_key(path) {
+                    continue;
+                }
+                let entry = match self.entries.remove(path) {
+                    Some(entry) => entry,
+                    None => load_entry(rt, pool, path)?,
+                };
+                entries.insert(path.clone(), entry);
+            }
+            transitions::set_queue(paths.clone());
+            self.entries = entries;
+            self.paths = paths;
+            self.queue = Some(fingerprint);
+        }
+        transitions::prepare(index);
+
+        SimpleBroker::publish(objects::playlist::Playlist {
+            amount: self.paths.len() as i32,
+            index,
+            max_playlist_size: rb::playlist::max_playlist_size(),
+            first_index: rb::playlist::first_index(),
+            last_insert_pos: rb::playlist::last_insert_pos(),
+            seed: rb::playlist::seed(),
+            last_shuffled_start: rb::playlist::last_shuffled_start(),
+            tracks: self
+                .paths
+                .iter()
+                .filter_map(|path| self.entries.get(path))
+                .map(|entry| entry.clone().into())
+                .collect(),
+        });
+        Ok(())
+    }
+}
+
+/// Reads the metadata of a track of the queue, with the ids and the cover
+/// of the library.
+fn load_entry(rt: &Runtime, pool: &Pool<Sqlite>, path: &str) -> Result<Mp3Entry, Error> {
+    let mut entry = rb::metadata::get_metadata(-1, path);
+    radio::segment_entry(&mut entry);
+    let hash = format!("{:x}", md5::compute(path.as_bytes()));
+    if let Some(track) = rt.block_on(repo::track::find_by_md5(pool.clone(), &hash))? {
+        entry.id = Some(track.id);
+        entry.album_art = track.album_art;
+        entry.album_id = Some(track.album_id);
+        entry.artist_id = Some(track.artist_id);
+        entry.genre_id = Some(track.genre_id);
+    }
+    Ok(entry)
+}
+
+async fn scrobble(track: Track, pool: Pool<Sqlite>) -> Result<(), Error> {
+    let album_id = track.album_id.unwrap();
+    let track = repo::track::find(pool.clone(), &track.id.unwrap()).await?;
+    let album = repo::album::find(pool, &album_id).await?;
+
+    if let Some(track) = track {
+        if let Some(album) = album {
+            match rockbox_rocksky::scrobble(track, album).await {
+                Ok(_) => {}
+                Err(e) => eprintln!("Failed to scrobble {}", e),
+            };
+        }
+    }
+
+    Ok(())
+}
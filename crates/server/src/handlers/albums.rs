@@ -1,11 +1,23 @@
 use anyhow::Error;
 use rockbox_library::repo;
+use rockbox_service::remote::RemoteQuery;
 
-use crate::http::{Context, Request, Response};
+use crate::{
+    handlers::{list_options, select_fields},
+    http::{Context, Request, Response},
+};
 
-pub async fn get_albums(ctx: &Context, _req: &Request, res: &mut Response) -> Result<(), Error> {
-    let albums = repo::album::all(ctx.pool.clone()).await?;
-    res.json(&albums);
+pub async fn get_albums(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    let options = list_options(req);
+    let mut albums = repo::album::list(ctx.pool.clone(), &options).await?;
+    let total = repo::album::count(ctx.pool.clone(), &options).await?;
+    res.add_header("X-Total-Count", &total.to_string());
+
+    // remote libraries can't be paginated nor filtered, only merge them in full listings
+    if !options.is_paginated() && !options.is_filtered() {
+        albums.extend(ctx.query_remote_sources(RemoteQuery::Albums).await.albums);
+    }
+    res.json(&select_fields(req, &albums)?);
     Ok(())
 }
 
@@ -24,3 +36,15 @@ pub async fn get_album_tracks(
     res.json(&tracks);
     Ok(())
 }
+
+/// The other editions of the album, the albums from the same MusicBrainz
+/// release group.
+pub async fn get_album_editions(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    let albums = repo::album::find_editions(ctx.pool.clone(), &req.params[0]).await?;
+    res.json(&albums);
+    Ok(())
+}
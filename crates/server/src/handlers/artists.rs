@@ -1,11 +1,17 @@
 use anyhow::Error;
 use rockbox_library::repo;
 
-use crate::http::{Context, Request, Response};
+use crate::{
+    handlers::{list_options, select_fields},
+    http::{Context, Request, Response},
+};
 
-pub async fn get_artists(ctx: &Context, _req: &Request, res: &mut Response) -> Result<(), Error> {
-    let artists = repo::artist::all(ctx.pool.clone()).await?;
-    res.json(&artists);
+pub async fn get_artists(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    let options = list_options(req);
+    let artists = repo::artist::list(ctx.pool.clone(), &options).await?;
+    let total = repo::artist::count(ctx.pool.clone(), &options).await?;
+    res.add_header("X-Total-Count", &total.to_string());
+    res.json(&select_fields(req, &artists)?);
     Ok(())
 }
 
@@ -34,3 +40,13 @@ pub async fn get_artist_tracks(
     res.json(&tracks);
     Ok(())
 }
+
+pub async fn get_artist_appears_on(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    let albums = repo::album::find_appears_on(ctx.pool.clone(), &req.params[0]).await?;
+    res.json(&albums);
+    Ok(())
+}
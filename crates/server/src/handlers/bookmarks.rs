@@ -0,0 +1,85 @@
+use crate::http::{Context, Request, Response};
+use anyhow::Error;
+use rockbox_types::bookmark::{NewBookmark, UpdateBookmark};
+
+/// The named bookmarks and the resume positions, which have no name.
+pub async fn get_bookmarks(ctx: &Context, _req: &Request, res: &mut Response) -> Result<(), Error> {
+    res.json(&ctx.bookmarks().await?);
+    Ok(())
+}
+
+pub async fn get_bookmark(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    match ctx.bookmark(&req.params[0]).await? {
+        Some(bookmark) => res.json(&bookmark),
+        None => res.set_status(404),
+    }
+    Ok(())
+}
+
+/// Bookmarks the track playing, or the `path` of the body, e.g.
+/// `{"name": "Chapter 12"}`.
+pub async fn create_bookmark(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    if req.body.is_none() {
+        res.set_status(400);
+        return Ok(());
+    }
+    let body = req.body.as_ref().unwrap();
+    let bookmark: NewBookmark = serde_json::from_str(body)?;
+    match ctx.create_bookmark(bookmark).await {
+        Ok(bookmark) => {
+            res.set_status(201);
+            res.json(&bookmark);
+        }
+        Err(e) => {
+            res.set_status(400);
+            res.text(&e.to_string());
+        }
+    }
+    Ok(())
+}
+
+pub async fn update_bookmark(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    if req.body.is_none() {
+        res.set_status(400);
+        return Ok(());
+    }
+    let body = req.body.as_ref().unwrap();
+    let update: UpdateBookmark = serde_json::from_str(body)?;
+    match ctx.update_bookmark(&req.params[0], update).await {
+        Ok(Some(bookmark)) => res.json(&bookmark),
+        Ok(None) => res.set_status(404),
+        Err(e) => {
+            res.set_status(400);
+            res.text(&e.to_string());
+        }
+    }
+    Ok(())
+}
+
+pub async fn delete_bookmark(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    match ctx.delete_bookmark(&req.params[0]).await? {
+        true => res.set_status(204),
+        false => res.set_status(404),
+    }
+    Ok(())
+}
+
+pub async fn play_bookmark(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    match ctx.play_bookmark(&req.params[0]).await? {
+        true => res.set_status(204),
+        false => res.set_status(404),
+    }
+    Ok(())
+}
@@ -1,72 +1,44 @@
 use anyhow::Error;
 use rockbox_chromecast::Chromecast;
+use rockbox_remote::RemotePlayer;
+use rockbox_traits::Player;
+use rockbox_types::device::Device;
 
-use crate::{
-    http::{Context, Request, Response},
-    GLOBAL_MUTEX,
-};
+use crate::http::{Context, Request, Response};
+
+pub async fn connect_player(device: Device) -> Result<Option<Box<dyn Player + Send>>, Error> {
+    match device.app.as_str() {
+        "rockbox" => RemotePlayer::connect(device).await,
+        _ => Chromecast::connect(device),
+    }
+}
 
 pub async fn connect(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
     let id = &req.params[0];
-    let mut player = ctx.player.lock().unwrap();
-    let mut current_device = ctx.current_device.lock().unwrap();
-    let devices = ctx.devices.lock().unwrap();
-    let device = devices.iter().find(|d| d.id == *id);
-    if let Some(device) = device {
-        let mut mutex = GLOBAL_MUTEX.lock().unwrap();
-        *mutex = 1;
-        *player = Chromecast::connect(device.clone())?;
-        *current_device = Some(device.clone());
-        res.set_status(200);
-        return Ok(());
+    match ctx.connect(id).await? {
+        true => res.set_status(200),
+        false => res.set_status(404),
     }
-    res.set_status(404);
     Ok(())
 }
 
 pub async fn disconnect(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
     let _id = &req.params[0];
-    let mut player = ctx.player.lock().unwrap();
-    let mut current_device = ctx.current_device.lock().unwrap();
-    if let Some(player) = player.as_mut() {
-        player.stop().await?;
-        player.disconnect().await?;
-    }
-    let mut mutex = GLOBAL_MUTEX.lock().unwrap();
-    *mutex = 0;
-    *player = None;
-    *current_device = None;
+    ctx.disconnect().await?;
     res.set_status(200);
     Ok(())
 }
 
 pub async fn get_devices(ctx: &Context, _req: &Request, res: &mut Response) -> Result<(), Error> {
-    let devices = ctx.devices.lock().unwrap();
-    res.json(&devices.clone());
+    res.json(&ctx.devices());
     Ok(())
 }
 
 pub async fn get_device(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
     let id = &req.params[0];
-    if id == "current" {
-        let current_device = ctx.current_device.lock().unwrap();
-        if let Some(device) = current_device.as_ref() {
-            res.json(&device.clone());
-            return Ok(());
-        }
-        res.set_status(404);
-        return Ok(());
+    match ctx.device(id) {
+        Some(device) => res.json(&device),
+        None => res.set_status(404),
     }
-
-    let devices = ctx.devices.lock().unwrap();
-    let device = devices.iter().find(|d| d.id == *id);
-
-    if let Some(device) = device {
-        res.json(&device.clone());
-        return Ok(());
-    }
-
-    res.json(&device);
-    res.set_status(404);
     Ok(())
 }
@@ -0,0 +1,174 @@
+use crate::http::{Context, Request, Response};
+use anyhow::Error;
+use rockbox_types::dsp::{
+    BindEqPreset, EqSlot, Equalizer, ImportEqPreset, NewEqPreset, UpdateEqBand, UpdateEqualizer,
+};
+
+pub async fn get_equalizer(ctx: &Context, _req: &Request, res: &mut Response) -> Result<(), Error> {
+    res.json(&ctx.equalizer());
+    Ok(())
+}
+
+pub async fn update_equalizer(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    let body = req.body.as_ref().unwrap();
+    let update: UpdateEqualizer = serde_json::from_str(body)?;
+    res.json(&ctx.update_equalizer(update)?);
+    Ok(())
+}
+
+pub async fn get_eq_band(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    let band = req.params[0].parse::<usize>()?;
+    match ctx.eq_band(band) {
+        Some(band) => res.json(&band),
+        None => res.set_status(404),
+    }
+    Ok(())
+}
+
+pub async fn update_eq_band(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    let band = req.params[0].parse::<usize>()?;
+    if ctx.eq_band(band).is_none() {
+        res.set_status(404);
+        return Ok(());
+    }
+    let body = req.body.as_ref().unwrap();
+    let update: UpdateEqBand = serde_json::from_str(body)?;
+    res.json(&ctx.set_eq_band(band, update)?);
+    Ok(())
+}
+
+pub async fn get_eq_presets(
+    ctx: &Context,
+    _req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    res.json(&ctx.eq_presets()?);
+    Ok(())
+}
+
+pub async fn get_eq_preset(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    let name = urlencoding::decode(&req.params[0])?;
+    match ctx.eq_preset(&name)? {
+        Some(preset) => res.json(&preset),
+        None => res.set_status(404),
+    }
+    Ok(())
+}
+
+pub async fn save_eq_preset(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    if req.body.is_none() {
+        res.set_status(400);
+        return Ok(());
+    }
+    let body = req.body.as_ref().unwrap();
+    let preset: NewEqPreset = serde_json::from_str(body)?;
+    res.json(&ctx.save_eq_preset(&preset.name, preset.equalizer)?);
+    Ok(())
+}
+
+pub async fn import_eq_preset(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    if req.body.is_none() {
+        res.set_status(400);
+        return Ok(());
+    }
+    let body = req.body.as_ref().unwrap();
+    let preset: ImportEqPreset = serde_json::from_str(body)?;
+    match ctx.import_eq_preset(&preset.name, &preset.profile) {
+        Ok(preset) => res.json(&preset),
+        Err(e) => {
+            res.set_status(400);
+            res.text(&e.to_string());
+        }
+    }
+    Ok(())
+}
+
+pub async fn delete_eq_preset(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    let name = urlencoding::decode(&req.params[0])?;
+    match ctx.delete_eq_preset(&name)? {
+        true => res.set_status(204),
+        false => res.set_status(404),
+    }
+    Ok(())
+}
+
+pub async fn load_eq_preset(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    let name = urlencoding::decode(&req.params[0])?;
+    match ctx.load_eq_preset(&name)? {
+        Some(equalizer) => res.json(&equalizer),
+        None => res.set_status(404),
+    }
+    Ok(())
+}
+
+pub async fn get_eq_device_presets(
+    ctx: &Context,
+    _req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    res.json(&ctx.eq_device_presets()?);
+    Ok(())
+}
+
+pub async fn bind_eq_preset(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    let device_id = &req.params[0];
+    let body = req.body.as_ref().unwrap();
+    let bind: BindEqPreset = serde_json::from_str(body)?;
+    match ctx.bind_eq_preset(device_id, bind.preset.as_deref()) {
+        Ok(_) => res.set_status(204),
+        Err(e) => {
+            res.set_status(404);
+            res.text(&e.to_string());
+        }
+    }
+    Ok(())
+}
+
+pub async fn get_eq_comparison(
+    ctx: &Context,
+    _req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    res.json(&ctx.eq_comparison());
+    Ok(())
+}
+
+pub async fn toggle_eq_comparison(
+    ctx: &Context,
+    _req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    res.json(&ctx.toggle_eq_slot()?);
+    Ok(())
+}
+
+pub async fn set_eq_comparison_slot(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    let slot = match req.params[0].to_lowercase().as_str() {
+        "a" => EqSlot::A,
+        "b" => EqSlot::B,
+        _ => {
+            res.set_status(404);
+            return Ok(());
+        }
+    };
+    let body = req.body.as_ref().unwrap();
+    let equalizer: Equalizer = serde_json::from_str(body)?;
+    res.json(&ctx.set_eq_slot(slot, equalizer)?);
+    Ok(())
+}
@@ -0,0 +1,209 @@
+use std::{net::TcpStream, time::Duration};
+
+use anyhow::{anyhow, Error};
+use futures_util::{SinkExt, StreamExt};
+use rockbox_service::events::{self, Event};
+use tokio::{
+    io::AsyncWriteExt,
+    sync::broadcast::{self, error::RecvError},
+};
+use tokio_tungstenite::{
+    tungstenite::{handshake::derive_accept_key, protocol::Role, Message},
+    WebSocketStream,
+};
+
+use crate::http::{Context, Request};
+
+/// Sent on idle Server-Sent Events connections so dead clients are noticed.
+const KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+/// Streams the player events as JSON, over a WebSocket when the client asks
+/// for an upgrade and as Server-Sent Events otherwise.
+///
+/// `?topics=playback,queue` keeps the events of these topics only. Clients
+/// resume with `?last_event_id=` or the `Last-Event-ID` header, a `Resync`
+/// message tells them some events were missed and the state must be fetched
+/// again.
+pub fn events(_ctx: &Context, req: &Request, stream: TcpStream) -> Result<(), Error> {
+    let topics: Vec<String> = req
+        .query_params
+        .get("topics")
+        .and_then(|topics| topics.as_str())
+        .map(|topics| topics.split(',').map(|t| t.trim().to_string()).collect())
+        .unwrap_or_default();
+    let last_event_id = req
+        .query_params
+        .get("last_event_id")
+        .and_then(|id| id.as_str())
+        .or(req.headers.get("last-event-id").map(|id| id.as_str()))
+        .and_then(|id| id.parse::<u64>().ok());
+    let websocket = req
+        .headers
+        .get("upgrade")
+        .map(|upgrade| upgrade.eq_ignore_ascii_case("websocket"))
+        .unwrap_or(false);
+    let key = req.headers.get("sec-websocket-key").cloned();
+
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    rt.block_on(async move {
+        stream.set_nonblocking(true)?;
+        let stream = tokio::net::TcpStream::from_std(stream)?;
+        let subscription = Subscription::new(topics, last_event_id);
+        match websocket {
+            true => {
+                let key = key.ok_or_else(|| anyhow!("Missing Sec-WebSocket-Key header"))?;
+                send_websocket(stream, &key, subscription).await
+            }
+            false => send_server_sent_events(stream, subscription).await,
+        }
+    })
+}
+
+struct Subscription {
+    topics: Vec<String>,
+    backlog: Option<Vec<Event>>,
+    receiver: broadcast::Receiver<Event>,
+}
+
+impl Subscription {
+    fn new(topics: Vec<String>, last_event_id: Option<u64>) -> Self {
+        let (backlog, receiver) = match last_event_id {
+            Some(last_event_id) => events::subscribe_since(last_event_id),
+            None => (Some(vec![]), events::subscribe()),
+        };
+        Self {
+            topics,
+            backlog,
+            receiver,
+        }
+    }
+
+    fn accepts(&self, event: &Event) -> bool {
+        self.topics.is_empty() || self.topics.iter().any(|t| t == event.kind.topic())
+    }
+
+    /// The next event of the topics, `None` when some were missed.
+    async fn next(&mut self) -> Result<Option<Event>, Error> {
+        loop {
+            let event = match self.receiver.recv().await {
+                Ok(event) => event,
+                Err(RecvError::Lagged(_)) => return Ok(None),
+                Err(RecvError::Closed) => return Err(anyhow!("Event bus closed")),
+            };
+            if self.accepts(&event) {
+                return Ok(Some(event));
+            }
+        }
+    }
+
+    /// The events published since the last one received by the client,
+    /// `None` when they are not all kept anymore.
+    fn backlog(&mut self) -> Option<Vec<Event>> {
+        let backlog = self.backlog.take()?;
+        Some(backlog.into_iter().filter(|e| self.accepts(e)).collect())
+    }
+}
+
+async fn send_server_sent_events(
+    mut stream: tokio::net::TcpStream,
+    mut subscription: Subscription,
+) -> Result<(), Error> {
+    stream
+        .write_all(
+            b"HTTP/1.1 200 OK\r\n\
+            Content-Type: text/event-stream\r\n\
+            Cache-Control: no-cache\r\n\
+            Connection: keep-alive\r\n\
+            Access-Control-Allow-Origin: *\r\n\r\n",
+        )
+        .await?;
+
+    match subscription.backlog() {
+        Some(backlog) => {
+            for event in backlog {
+                stream
+                    .write_all(server_sent_event(&event)?.as_bytes())
+                    .await?;
+            }
+        }
+        None => stream.write_all(b"event: Resync\ndata: {}\n\n").await?,
+    }
+    stream.flush().await?;
+
+    loop {
+        let message = match tokio::time::timeout(KEEP_ALIVE, subscription.next()).await {
+            Ok(Ok(Some(event))) => server_sent_event(&event)?,
+            Ok(Ok(None)) => "event: Resync\ndata: {}\n\n".to_string(),
+            Ok(Err(e)) => return Err(e),
+            Err(_) => ": keep-alive\n\n".to_string(),
+        };
+        // the client is gone when writing fails
+        if stream.write_all(message.as_bytes()).await.is_err() {
+            return Ok(());
+        }
+        stream.flush().await?;
+    }
+}
+
+fn server_sent_event(event: &Event) -> Result<String, Error> {
+    Ok(format!(
+        "id: {}\nevent: {}\ndata: {}\n\n",
+        event.seq,
+        event.kind.name(),
+        serde_json::to_string(event)?
+    ))
+}
+
+async fn send_websocket(
+    mut stream: tokio::net::TcpStream,
+    key: &str,
+    mut subscription: Subscription,
+) -> Result<(), Error> {
+    stream
+        .write_all(
+            format!(
+                "HTTP/1.1 101 Switching Protocols\r\n\
+                Upgrade: websocket\r\n\
+                Connection: Upgrade\r\n\
+                Sec-WebSocket-Accept: {}\r\n\r\n",
+                derive_accept_key(key.as_bytes())
+            )
+            .as_bytes(),
+        )
+        .await?;
+
+    let websocket = WebSocketStream::from_raw_socket(stream, Role::Server, None).await;
+    let (mut sink, mut source) = websocket.split();
+    let resync = || Message::Text(r#"{"type":"Resync"}"#.to_string());
+
+    match subscription.backlog() {
+        Some(backlog) => {
+            for event in backlog {
+                sink.send(Message::Text(serde_json::to_string(&event)?))
+                    .await?;
+            }
+        }
+        None => sink.send(resync()).await?,
+    }
+
+    loop {
+        tokio::select! {
+            event = subscription.next() => {
+                let message = match event? {
+                    Some(event) => Message::Text(serde_json::to_string(&event)?),
+                    None => resync(),
+                };
+                if sink.send(message).await.is_err() {
+                    return Ok(());
+                }
+            }
+            message = source.next() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return Ok(()),
+                // pings are answered by the websocket itself
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use anyhow::Error;
+use rockbox_library::repo;
+
+use crate::http::{Context, Request, Response};
+
+pub async fn get_genres(ctx: &Context, _req: &Request, res: &mut Response) -> Result<(), Error> {
+    let genres = repo::genre::all(ctx.pool.clone()).await?;
+    res.json(&genres);
+    Ok(())
+}
+
+pub async fn get_genre(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    let genre = repo::genre::find(ctx.pool.clone(), &req.params[0]).await?;
+    res.json(&genre);
+    Ok(())
+}
+
+pub async fn get_genre_tracks(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    let tracks = repo::genre_tracks::find_by_genre(ctx.pool.clone(), &req.params[0]).await?;
+    res.json(&tracks);
+    Ok(())
+}
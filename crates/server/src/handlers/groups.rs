@@ -0,0 +1,221 @@
+use std::{env, fs, sync::Mutex};
+
+use anyhow::Error;
+use rockbox_group::{GroupMember, PlayerGroup, WeakPlayerGroup};
+use rockbox_traits::Player;
+use rockbox_types::{
+    device::Device,
+    group::{Group, NewGroup, UpdateGroupMember, GROUP_DEVICE, LOCAL_DEVICE_ID},
+};
+
+use crate::{
+    handlers::devices::connect_player,
+    http::{Context, Request, Response},
+    local_player::LocalPlayer,
+    GLOBAL_MUTEX,
+};
+
+/// The group connected last, by id, so its members can be updated while it
+/// plays.
+static CONNECTED_GROUP: Mutex<Option<(String, WeakPlayerGroup)>> = Mutex::new(None);
+
+fn groups_path() -> Result<String, Error> {
+    let home = env::var("HOME")?;
+    Ok(format!("{}/.config/rockbox.org/groups.json", home))
+}
+
+fn load_groups() -> Result<Vec<Group>, Error> {
+    let path = groups_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => Ok(serde_json::from_str(&content)?),
+        Err(_) => Ok(vec![]),
+    }
+}
+
+fn save_groups(groups: &Vec<Group>) -> Result<(), Error> {
+    let path = groups_path()?;
+    fs::create_dir_all(std::path::Path::new(&path).parent().unwrap())?;
+    fs::write(&path, serde_json::to_string_pretty(groups)?)?;
+    Ok(())
+}
+
+pub async fn get_groups(_ctx: &Context, _req: &Request, res: &mut Response) -> Result<(), Error> {
+    let groups = load_groups()?;
+    res.json(&groups);
+    Ok(())
+}
+
+pub async fn get_group(_ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    let id = &req.params[0];
+    let groups = load_groups()?;
+    match groups.iter().find(|g| g.id == *id) {
+        Some(group) => res.json(group),
+        None => res.set_status(404),
+    }
+    Ok(())
+}
+
+pub async fn create_group(_ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    if req.body.is_none() {
+        res.set_status(400);
+        return Ok(());
+    }
+    let body = req.body.as_ref().unwrap();
+    let new_group: NewGroup = serde_json::from_str(body)?;
+    let group = Group {
+        id: cuid::cuid1()?,
+        name: new_group.name,
+        members: new_group.members,
+    };
+    let mut groups = load_groups()?;
+    groups.push(group.clone());
+    save_groups(&groups)?;
+    res.json(&group);
+    Ok(())
+}
+
+pub async fn delete_group(_ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    let id = &req.params[0];
+    let mut groups = load_groups()?;
+    let len = groups.len();
+    groups.retain(|g| g.id != *id);
+    if groups.len() == len {
+        res.set_status(404);
+        return Ok(());
+    }
+    save_groups(&groups)?;
+    Ok(())
+}
+
+pub async fn update_group_member(
+    _ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    let id = &req.params[0];
+    let device_id = &req.params[1];
+    if req.body.is_none() {
+        res.set_status(400);
+        return Ok(());
+    }
+    let body = req.body.as_ref().unwrap();
+    let params: UpdateGroupMember = serde_json::from_str(body)?;
+
+    let mut groups = load_groups()?;
+    let member = groups
+        .iter_mut()
+        .find(|g| g.id == *id)
+        .and_then(|g| g.members.iter_mut().find(|m| m.device_id == *device_id));
+
+    match member {
+        Some(member) => {
+            if let Some(volume_offset) = params.volume_offset {
+                member.volume_offset = volume_offset;
+            }
+            if let Some(latency_ms) = params.latency_ms {
+                member.latency_ms = latency_ms;
+            }
+            res.json(member);
+        }
+        None => {
+            res.set_status(404);
+            return Ok(());
+        }
+    }
+
+    save_groups(&groups)?;
+
+    let connected = CONNECTED_GROUP
+        .lock()
+        .unwrap()
+        .as_ref()
+        .filter(|(group_id, _)| group_id == id)
+        .and_then(|(_, group)| group.upgrade());
+    if let Some(group) = connected {
+        if let Some(volume_offset) = params.volume_offset {
+            group
+                .set_member_volume_offset(device_id, volume_offset)
+                .await;
+        }
+        if let Some(latency_ms) = params.latency_ms {
+            group.set_member_latency(device_id, latency_ms).await;
+        }
+    }
+    Ok(())
+}
+
+pub async fn connect_group(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    let id = &req.params[0];
+    let groups = load_groups()?;
+    let group = match groups.into_iter().find(|g| g.id == *id) {
+        Some(group) => group,
+        None => {
+            res.set_status(404);
+            return Ok(());
+        }
+    };
+
+    let mut player = ctx.player.lock().await;
+    // discovery keeps updating the devices while the members connect
+    let devices = ctx.devices.lock().unwrap().clone();
+
+    let mut members = vec![];
+    for member in &group.members {
+        let member_player: Box<dyn Player + Send> = match member.device_id.as_str() {
+            LOCAL_DEVICE_ID => Box::new(LocalPlayer::new()),
+            device_id => {
+                let device = devices.iter().find(|d| d.id == device_id);
+                match device {
+                    Some(device) if device.app == "chromecast" || device.app == "rockbox" => {
+                        match connect_player(device.clone()).await? {
+                            Some(player) => player,
+                            None => continue,
+                        }
+                    }
+                    _ => {
+                        eprintln!("Group member {} is not available, skipping", device_id);
+                        continue;
+                    }
+                }
+            }
+        };
+        members.push(
+            GroupMember::new(&member.device_id, member_player)
+                .with_volume_offset(member.volume_offset)
+                .with_latency(member.latency_ms),
+        );
+    }
+
+    if members.is_empty() {
+        res.set_status(404);
+        return Ok(());
+    }
+
+    if let Some(player) = player.as_mut() {
+        player.disconnect().await?;
+    }
+
+    let mut mutex = GLOBAL_MUTEX.lock().unwrap();
+    *mutex = 1;
+    let group_player = PlayerGroup::new(members);
+    *CONNECTED_GROUP.lock().unwrap() = Some((group.id.clone(), group_player.downgrade()));
+    *player = Some(Box::new(group_player));
+    // host and port are what the rpc layer looks at to route playback to `player`
+    let host = env::var("ROCKBOX_HOST").unwrap_or_else(|_| "localhost".to_string());
+    let port = env::var("ROCKBOX_TCP_PORT").unwrap_or_else(|_| "6063".to_string());
+    *ctx.current_device.lock().unwrap() = Some(Device {
+        id: group.id.clone(),
+        name: group.name.clone(),
+        host,
+        port: port.parse().unwrap_or(6063),
+        service: GROUP_DEVICE.to_string(),
+        app: "group".to_string(),
+        is_connected: true,
+        is_cast_device: true,
+        is_current_device: true,
+        ..Default::default()
+    });
+
+    res.set_status(200);
+    Ok(())
+}
@@ -1,10 +1,18 @@
 pub mod albums;
 pub mod artists;
+pub mod bookmarks;
 pub mod browse;
 pub mod devices;
 pub mod docs;
+pub mod dsp;
+pub mod events;
+pub mod genres;
+pub mod groups;
 pub mod player;
 pub mod playlists;
+pub mod podcasts;
+pub mod radio;
+pub mod scheduler;
 pub mod search;
 pub mod settings;
 pub mod system;
@@ -12,6 +20,61 @@ pub mod tracks;
 
 use crate::http::{Context, Request, Response};
 use anyhow::Error;
+use rockbox_library::list::ListOptions;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Reads the pagination, sort and filter query parameters of the library list
+/// endpoints, e.g. `?offset=50&limit=50&sort=year&order=desc&genre_id=...`,
+/// albums also accept `?group_editions=true`.
+pub fn list_options(req: &Request) -> ListOptions {
+    let param = |name: &str| {
+        req.query_params
+            .get(name)
+            .and_then(|value| value.as_str())
+            .map(|value| value.to_string())
+    };
+    let number = |name: &str| param(name).and_then(|value| value.parse().ok());
+
+    ListOptions {
+        offset: number("offset"),
+        limit: number("limit"),
+        sort: param("sort"),
+        descending: param("order").as_deref() == Some("desc"),
+        artist_id: param("artist_id"),
+        album_id: param("album_id"),
+        genre_id: param("genre_id"),
+        year_from: number("year_from"),
+        year_to: number("year_to"),
+        group_editions: param("group_editions").as_deref() == Some("true"),
+    }
+}
+
+/// Keeps only the keys listed in `?fields=id,title` of every item, or all of
+/// them without the parameter.
+pub fn select_fields<T: Serialize>(req: &Request, items: &[T]) -> Result<Value, Error> {
+    let items = serde_json::to_value(items)?;
+    let fields = match req.query_params.get("fields").and_then(|f| f.as_str()) {
+        Some(fields) => fields.split(',').map(str::trim).collect::<Vec<_>>(),
+        None => return Ok(items),
+    };
+
+    Ok(match items {
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(|item| match item {
+                    Value::Object(mut item) => {
+                        item.retain(|key, _| fields.contains(&key.as_str()));
+                        Value::Object(item)
+                    }
+                    item => item,
+                })
+                .collect(),
+        ),
+        items => items,
+    })
+}
 
 macro_rules! async_handler {
     ($module:ident, $handler:ident) => {
@@ -30,11 +93,37 @@ macro_rules! async_handler {
 async_handler!(albums, get_albums);
 async_handler!(albums, get_album);
 async_handler!(albums, get_album_tracks);
+async_handler!(albums, get_album_editions);
 async_handler!(artists, get_artists);
 async_handler!(artists, get_artist);
 async_handler!(artists, get_artist_albums);
 async_handler!(artists, get_artist_tracks);
+async_handler!(artists, get_artist_appears_on);
+async_handler!(dsp, get_equalizer);
+async_handler!(dsp, update_equalizer);
+async_handler!(dsp, get_eq_band);
+async_handler!(dsp, update_eq_band);
+async_handler!(dsp, get_eq_presets);
+async_handler!(dsp, get_eq_preset);
+async_handler!(dsp, save_eq_preset);
+async_handler!(dsp, import_eq_preset);
+async_handler!(dsp, delete_eq_preset);
+async_handler!(dsp, load_eq_preset);
+async_handler!(dsp, get_eq_device_presets);
+async_handler!(dsp, bind_eq_preset);
+async_handler!(dsp, get_eq_comparison);
+async_handler!(dsp, toggle_eq_comparison);
+async_handler!(dsp, set_eq_comparison_slot);
+async_handler!(genres, get_genres);
+async_handler!(genres, get_genre);
+async_handler!(genres, get_genre_tracks);
 async_handler!(browse, get_tree_entries);
+async_handler!(groups, get_groups);
+async_handler!(groups, get_group);
+async_handler!(groups, create_group);
+async_handler!(groups, delete_group);
+async_handler!(groups, update_group_member);
+async_handler!(groups, connect_group);
 async_handler!(player, load);
 async_handler!(player, play);
 async_handler!(player, pause);
@@ -49,7 +138,11 @@ async_handler!(player, previous);
 async_handler!(player, stop);
 async_handler!(player, get_file_position);
 async_handler!(player, adjust_volume);
+async_handler!(player, get_speed);
+async_handler!(player, set_speed);
 async_handler!(player, get_current_player);
+async_handler!(player, get_chapters);
+async_handler!(player, seek_chapter);
 async_handler!(playlists, create_playlist);
 async_handler!(playlists, start_playlist);
 async_handler!(playlists, shuffle_playlist);
@@ -62,9 +155,47 @@ async_handler!(playlists, remove_tracks);
 async_handler!(playlists, get_playlist);
 async_handler!(tracks, get_tracks);
 async_handler!(tracks, get_track);
+async_handler!(tracks, get_track_lyrics);
+async_handler!(tracks, get_track_replaygain);
+async_handler!(tracks, get_track_chapters);
 async_handler!(system, get_rockbox_version);
 async_handler!(system, get_status);
 async_handler!(system, scan_library);
+async_handler!(system, get_indexing_progress);
+async_handler!(system, analyze_replaygain);
+async_handler!(system, get_replaygain_progress);
+async_handler!(scheduler, get_sleep_timer);
+async_handler!(scheduler, set_sleep_timer);
+async_handler!(scheduler, cancel_sleep_timer);
+async_handler!(scheduler, get_schedules);
+async_handler!(scheduler, get_schedule);
+async_handler!(scheduler, create_schedule);
+async_handler!(scheduler, update_schedule);
+async_handler!(scheduler, delete_schedule);
+async_handler!(scheduler, run_schedule);
+async_handler!(bookmarks, get_bookmarks);
+async_handler!(bookmarks, get_bookmark);
+async_handler!(bookmarks, create_bookmark);
+async_handler!(bookmarks, update_bookmark);
+async_handler!(bookmarks, delete_bookmark);
+async_handler!(bookmarks, play_bookmark);
+async_handler!(podcasts, get_podcasts);
+async_handler!(podcasts, get_podcast);
+async_handler!(podcasts, subscribe_podcast);
+async_handler!(podcasts, update_podcast);
+async_handler!(podcasts, unsubscribe_podcast);
+async_handler!(podcasts, refresh_podcast);
+async_handler!(podcasts, get_podcast_episodes);
+async_handler!(podcasts, get_podcast_episode);
+async_handler!(podcasts, download_episode);
+async_handler!(podcasts, set_episode_played);
+async_handler!(radio, get_stations);
+async_handler!(radio, get_station);
+async_handler!(radio, create_station);
+async_handler!(radio, update_station);
+async_handler!(radio, delete_station);
+async_handler!(radio, play_station);
+async_handler!(radio, get_current_station);
 async_handler!(settings, get_global_settings);
 async_handler!(settings, update_global_settings);
 async_handler!(docs, get_openapi);
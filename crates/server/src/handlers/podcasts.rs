@@ -0,0 +1,147 @@
+use crate::http::{Context, Request, Response};
+use anyhow::Error;
+use rockbox_types::podcast::{EpisodePlayed, NewPodcast, UpdatePodcast};
+
+pub async fn get_podcasts(ctx: &Context, _req: &Request, res: &mut Response) -> Result<(), Error> {
+    res.json(&ctx.podcasts().await?);
+    Ok(())
+}
+
+pub async fn get_podcast(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    match ctx.podcast(&req.params[0]).await? {
+        Some(podcast) => res.json(&podcast),
+        None => res.set_status(404),
+    }
+    Ok(())
+}
+
+/// Subscribes to a feed, e.g. `{"url": "https://example.com/feed.xml",
+/// "auto_download": 5}`.
+pub async fn subscribe_podcast(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    if req.body.is_none() {
+        res.set_status(400);
+        return Ok(());
+    }
+    let body = req.body.as_ref().unwrap();
+    let podcast: NewPodcast = serde_json::from_str(body)?;
+    match ctx.subscribe(podcast).await {
+        Ok(podcast) => {
+            res.set_status(201);
+            res.json(&podcast);
+        }
+        Err(e) => {
+            res.set_status(400);
+            res.text(&e.to_string());
+        }
+    }
+    Ok(())
+}
+
+pub async fn update_podcast(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    if req.body.is_none() {
+        res.set_status(400);
+        return Ok(());
+    }
+    let body = req.body.as_ref().unwrap();
+    let update: UpdatePodcast = serde_json::from_str(body)?;
+    match ctx.update_podcast(&req.params[0], update).await? {
+        Some(podcast) => res.json(&podcast),
+        None => res.set_status(404),
+    }
+    Ok(())
+}
+
+pub async fn unsubscribe_podcast(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    match ctx.unsubscribe(&req.params[0]).await? {
+        true => res.set_status(204),
+        false => res.set_status(404),
+    }
+    Ok(())
+}
+
+pub async fn refresh_podcast(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    match ctx.refresh_podcast(&req.params[0]).await {
+        Ok(Some(podcast)) => res.json(&podcast),
+        Ok(None) => res.set_status(404),
+        Err(e) => {
+            res.set_status(502);
+            res.text(&e.to_string());
+        }
+    }
+    Ok(())
+}
+
+/// The episodes of a podcast, the newest first.
+pub async fn get_podcast_episodes(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    match ctx.podcast_episodes(&req.params[0]).await? {
+        Some(episodes) => res.json(&episodes),
+        None => res.set_status(404),
+    }
+    Ok(())
+}
+
+pub async fn get_podcast_episode(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    match ctx.podcast_episode(&req.params[0]).await? {
+        Some(episode) => res.json(&episode),
+        None => res.set_status(404),
+    }
+    Ok(())
+}
+
+pub async fn download_episode(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    match ctx.download_episode(&req.params[0]).await {
+        Ok(Some(episode)) => res.json(&episode),
+        Ok(None) => res.set_status(404),
+        Err(e) => {
+            res.set_status(502);
+            res.text(&e.to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Marks an episode played or unplayed, e.g. `{"played": true}`.
+pub async fn set_episode_played(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    if req.body.is_none() {
+        res.set_status(400);
+        return Ok(());
+    }
+    let body = req.body.as_ref().unwrap();
+    let played: EpisodePlayed = serde_json::from_str(body)?;
+    match ctx
+        .set_episode_played(&req.params[0], played.played)
+        .await?
+    {
+        Some(episode) => res.json(&episode),
+        None => res.set_status(404),
+    }
+    Ok(())
+}
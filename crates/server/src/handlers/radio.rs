@@ -0,0 +1,92 @@
+use crate::http::{Context, Request, Response};
+use anyhow::Error;
+use rockbox_service::radio;
+use rockbox_types::radio::{NewStation, UpdateStation};
+
+/// The stations, the favourites first, only them with `?favourite=true`.
+pub async fn get_stations(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    let favourites_only = match req.query_params.get("favourite") {
+        Some(favourite) => favourite.as_str().unwrap_or("false") == "true",
+        None => false,
+    };
+    res.json(&ctx.stations(favourites_only).await?);
+    Ok(())
+}
+
+pub async fn get_station(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    match ctx.station(&req.params[0]).await? {
+        Some(station) => res.json(&station),
+        None => res.set_status(404),
+    }
+    Ok(())
+}
+
+/// Adds a station, e.g. `{"url": "https://example.com/station.pls",
+/// "favourite": true}`.
+pub async fn create_station(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    if req.body.is_none() {
+        res.set_status(400);
+        return Ok(());
+    }
+    let body = req.body.as_ref().unwrap();
+    let station: NewStation = serde_json::from_str(body)?;
+    match ctx.create_station(station).await {
+        Ok(station) => {
+            res.set_status(201);
+            res.json(&station);
+        }
+        Err(e) => {
+            res.set_status(400);
+            res.text(&e.to_string());
+        }
+    }
+    Ok(())
+}
+
+pub async fn update_station(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    if req.body.is_none() {
+        res.set_status(400);
+        return Ok(());
+    }
+    let body = req.body.as_ref().unwrap();
+    let update: UpdateStation = serde_json::from_str(body)?;
+    match ctx.update_station(&req.params[0], update).await? {
+        Some(station) => res.json(&station),
+        None => res.set_status(404),
+    }
+    Ok(())
+}
+
+pub async fn delete_station(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    match ctx.delete_station(&req.params[0]).await? {
+        true => res.set_status(204),
+        false => res.set_status(404),
+    }
+    Ok(())
+}
+
+pub async fn play_station(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    match ctx.play_station(&req.params[0]).await {
+        Ok(Some(station)) => res.json(&station),
+        Ok(None) => res.set_status(404),
+        Err(e) => {
+            res.set_status(502);
+            res.text(&e.to_string());
+        }
+    }
+    Ok(())
+}
+
+/// The station playing locally and the title of its stream, 204 when no
+/// station plays.
+pub async fn get_current_station(
+    _ctx: &Context,
+    _req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    match radio::now_playing() {
+        Some(now_playing) => res.json(&now_playing),
+        None => res.set_status(204),
+    }
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use crate::http::{Context, Request, Response};
+use anyhow::Error;
+use rockbox_types::scheduler::{NewSchedule, NewSleepTimer, UpdateSchedule};
+
+pub async fn get_sleep_timer(
+    ctx: &Context,
+    _req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    res.json(&ctx.sleep_timer());
+    Ok(())
+}
+
+pub async fn set_sleep_timer(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    if req.body.is_none() {
+        res.set_status(400);
+        return Ok(());
+    }
+    let body = req.body.as_ref().unwrap();
+    let timer: NewSleepTimer = serde_json::from_str(body)?;
+    res.json(&ctx.set_sleep_timer(timer.duration, timer.fade_out));
+    Ok(())
+}
+
+pub async fn cancel_sleep_timer(
+    ctx: &Context,
+    _req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    res.json(&ctx.cancel_sleep_timer());
+    Ok(())
+}
+
+pub async fn get_schedules(ctx: &Context, _req: &Request, res: &mut Response) -> Result<(), Error> {
+    res.json(&ctx.schedules().await?);
+    Ok(())
+}
+
+pub async fn get_schedule(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    match ctx.schedule(&req.params[0]).await? {
+        Some(schedule) => res.json(&schedule),
+        None => res.set_status(404),
+    }
+    Ok(())
+}
+
+pub async fn create_schedule(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    if req.body.is_none() {
+        res.set_status(400);
+        return Ok(());
+    }
+    let body = req.body.as_ref().unwrap();
+    let schedule: NewSchedule = serde_json::from_str(body)?;
+    match ctx.create_schedule(schedule).await {
+        Ok(schedule) => {
+            res.set_status(201);
+            res.json(&schedule);
+        }
+        Err(e) => {
+            res.set_status(400);
+            res.text(&e.to_string());
+        }
+    }
+    Ok(())
+}
+
+pub async fn update_schedule(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    if req.body.is_none() {
+        res.set_status(400);
+        return Ok(());
+    }
+    let body = req.body.as_ref().unwrap();
+    let update: UpdateSchedule = serde_json::from_str(body)?;
+    match ctx.update_schedule(&req.params[0], update).await {
+        Ok(Some(schedule)) => res.json(&schedule),
+        Ok(None) => res.set_status(404),
+        Err(e) => {
+            res.set_status(400);
+            res.text(&e.to_string());
+        }
+    }
+    Ok(())
+}
+
+pub async fn delete_schedule(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    match ctx.delete_schedule(&req.params[0]).await? {
+        true => res.set_status(204),
+        false => res.set_status(404),
+    }
+    Ok(())
+}
+
+/// Plays the schedule now, e.g. to try an alarm.
+pub async fn run_schedule(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    match ctx.run_schedule(&req.params[0]).await? {
+        true => res.set_status(204),
+        false => res.set_status(404),
+    }
+    Ok(())
+}
@@ -1,6 +1,10 @@
 use crate::http::{Context, Request, Response};
 use anyhow::Error;
-use rockbox_search::search_entities;
+use rockbox_search::{
+    query::{SearchOptions, SortBy, DEFAULT_LIMIT},
+    search_library,
+};
+use rockbox_service::remote::RemoteQuery;
 use rockbox_types::SearchResults;
 
 pub async fn search(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
@@ -10,50 +14,48 @@ pub async fn search(ctx: &Context, req: &Request, res: &mut Response) -> Result<
         .map(|t| t.as_str())
         .unwrap_or_default();
 
+    let options = SearchOptions {
+        offset: match req.query_params.get("offset") {
+            Some(offset) => offset.as_str().unwrap_or("0").parse().unwrap_or(0),
+            None => 0,
+        },
+        limit: match req.query_params.get("limit") {
+            Some(limit) => limit
+                .as_str()
+                .unwrap_or("")
+                .parse()
+                .unwrap_or(DEFAULT_LIMIT),
+            None => DEFAULT_LIMIT,
+        },
+        sort: match req.query_params.get("sort") {
+            Some(sort) => SortBy::parse(sort.as_str().unwrap_or_default()).unwrap_or_default(),
+            None => SortBy::Relevance,
+        },
+        descending: match req.query_params.get("order") {
+            Some(order) => order.as_str().unwrap_or("asc") == "desc",
+            None => false,
+        },
+    };
+
     match term {
         None => {
             res.json(&SearchResults::default());
         }
         Some(term) => {
-            let albums = search_entities(
-                &ctx.indexes.albums,
-                term,
-                &rockbox_search::album::Album::default(),
-            )?;
-            let artists = search_entities(
-                &ctx.indexes.artists,
-                term,
-                &rockbox_search::artist::Artist::default(),
-            )?;
-            let tracks = search_entities(
-                &ctx.indexes.tracks,
-                term,
-                &rockbox_search::track::Track::default(),
-            )?;
-            let files = search_entities(
-                &ctx.indexes.files,
-                term,
-                &rockbox_search::file::File::default(),
-            )?;
-            let liked_tracks = search_entities(
-                &ctx.indexes.liked_tracks,
-                term,
-                &rockbox_search::liked_track::LikedTrack::default(),
-            )?;
-            let liked_albums = search_entities(
-                &ctx.indexes.liked_albums,
-                term,
-                &rockbox_search::liked_album::LikedAlbum::default(),
-            )?;
+            let mut results: SearchResults = search_library(&ctx.indexes, term, &options)?.into();
 
-            let results = SearchResults {
-                albums: albums.into_iter().map(|(_, x)| x.into()).collect(),
-                artists: artists.into_iter().map(|(_, x)| x.into()).collect(),
-                tracks: tracks.into_iter().map(|(_, x)| x.into()).collect(),
-                files: files.into_iter().map(|(_, x)| x.into()).collect(),
-                liked_tracks: liked_tracks.into_iter().map(|(_, x)| x.into()).collect(),
-                liked_albums: liked_albums.into_iter().map(|(_, x)| x.into()).collect(),
-            };
+            // remote libraries aren't paginated, only merge them in the first page
+            if options.offset == 0 {
+                let remote = ctx
+                    .query_remote_sources(RemoteQuery::Search(term.to_string()))
+                    .await;
+                results
+                    .albums
+                    .extend(remote.albums.into_iter().map(|x| x.into()));
+                results
+                    .tracks
+                    .extend(remote.tracks.into_iter().map(|x| x.into()));
+            }
 
             res.json(&results);
         }
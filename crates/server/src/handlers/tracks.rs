@@ -1,11 +1,23 @@
 use anyhow::Error;
-use rockbox_library::repo;
+use rockbox_library::{lrc, repo};
+use rockbox_service::remote::RemoteQuery;
 
-use crate::http::{Context, Request, Response};
+use crate::{
+    handlers::{list_options, select_fields},
+    http::{Context, Request, Response},
+};
 
-pub async fn get_tracks(ctx: &Context, _req: &Request, res: &mut Response) -> Result<(), Error> {
-    let tracks = repo::track::all(ctx.pool.clone()).await?;
-    res.json(&tracks);
+pub async fn get_tracks(ctx: &Context, req: &Request, res: &mut Response) -> Result<(), Error> {
+    let options = list_options(req);
+    let mut tracks = repo::track::list(ctx.pool.clone(), &options).await?;
+    let total = repo::track::count(ctx.pool.clone(), &options).await?;
+    res.add_header("X-Total-Count", &total.to_string());
+
+    // remote libraries can't be paginated nor filtered, only merge them in full listings
+    if !options.is_paginated() && !options.is_filtered() {
+        tracks.extend(ctx.query_remote_sources(RemoteQuery::Tracks).await.tracks);
+    }
+    res.json(&select_fields(req, &tracks)?);
     Ok(())
 }
 
@@ -14,3 +26,47 @@ pub async fn get_track(ctx: &Context, req: &Request, res: &mut Response) -> Resu
     res.json(&track);
     Ok(())
 }
+
+/// The loudness measured for the track, `null` when it was not analyzed.
+pub async fn get_track_replaygain(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    res.json(&ctx.track_replaygain(&req.params[0]).await?);
+    Ok(())
+}
+
+/// The chapters of an audiobook, in milliseconds.
+pub async fn get_track_chapters(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    match ctx.track_chapters(&req.params[0]).await? {
+        Some(chapters) => res.json(&chapters),
+        None => res.set_status(404),
+    }
+    Ok(())
+}
+
+/// The lyrics of the track, `lines` holds the timed lines of synchronized
+/// lyrics and is empty otherwise.
+pub async fn get_track_lyrics(
+    ctx: &Context,
+    req: &Request,
+    res: &mut Response,
+) -> Result<(), Error> {
+    let lyrics = lrc::load(ctx.pool.clone(), &req.params[0]).await?;
+    let lyrics = lyrics.map(|lyrics| {
+        let lines = lrc::parse(lyrics.synced.as_deref().unwrap_or_default());
+        serde_json::json!({
+            "track_id": lyrics.track_id,
+            "lyrics": lyrics.lyrics,
+            "synced": !lines.is_empty(),
+            "lines": lines,
+        })
+    });
+    res.json(&lyrics);
+    Ok(())
+}
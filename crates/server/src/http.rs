@@ -1,16 +1,9 @@
 use anyhow::Error;
 use owo_colors::OwoColorize;
-use rockbox_library::entity::track::Track;
-use rockbox_search::{create_indexes, Indexes};
-use rockbox_sys::{
-    self as rb,
-    types::{mp3_entry::Mp3Entry, tree::Entry},
-};
-use rockbox_traits::Player;
-use rockbox_types::device::Device;
+use rockbox_service::RockboxService;
+use rockbox_sys as rb;
 use serde::Serialize;
 use serde_json::Value;
-use sqlx::Sqlite;
 use std::{
     collections::HashMap,
     io::{BufRead, BufReader, Read, Write},
@@ -22,29 +15,25 @@ use std::{
 use threadpool::ThreadPool;
 
 use crate::{
-    kv::{build_tracks_kv, KV},
     player_events::listen_for_playback_changes,
-    scan::scan_chromecast_devices,
+    scan::{scan_chromecast_devices, scan_rockbox_devices},
 };
 
 type Handler = fn(&Context, &Request, &mut Response) -> Result<(), Error>;
 
-pub struct Context {
-    pub pool: sqlx::Pool<Sqlite>,
-    pub fs_cache: Arc<tokio::sync::Mutex<HashMap<String, Vec<Entry>>>>,
-    pub metadata_cache: Arc<tokio::sync::Mutex<HashMap<String, Mp3Entry>>>,
-    pub indexes: Indexes,
-    pub devices: Arc<Mutex<Vec<Device>>>,
-    pub current_device: Arc<Mutex<Option<Device>>>,
-    pub player: Arc<Mutex<Option<Box<dyn Player + Send>>>>,
-    pub kv: Arc<Mutex<KV<Track>>>,
-}
+/// Writes to the connection for as long as the client stays connected.
+type StreamHandler = fn(&Context, &Request, TcpStream) -> Result<(), Error>;
+
+/// Handlers share the service with the gRPC, GraphQL and MPD servers.
+pub type Context = RockboxService;
 
 #[derive(Debug)]
 pub struct Request {
     pub method: String,
     pub params: Vec<String>,
     pub query_params: Value,
+    /// Lowercase names.
+    pub headers: HashMap<String, String>,
     pub body: Option<String>,
 }
 
@@ -121,12 +110,14 @@ fn split_path_and_query(path: &str) -> (&str, Option<&str>) {
 #[derive(Clone)]
 struct Router {
     routes: HashMap<String, HashMap<String, Handler>>, // method -> path -> handler
+    streams: HashMap<String, StreamHandler>,           // GET path -> handler
 }
 
 impl Router {
     pub fn new() -> Self {
         Router {
             routes: HashMap::new(),
+            streams: HashMap::new(),
         }
     }
 
@@ -149,6 +140,10 @@ impl Router {
         self.add_route("DELETE", path, handler);
     }
 
+    fn stream(&mut self, path: &str, handler: StreamHandler) {
+        self.streams.insert(path.to_string(), handler);
+    }
+
     // Add route to the routing table
     pub fn add_route(&mut self, method: &str, path: &str, handler: Handler) {
         self.routes
@@ -171,6 +166,20 @@ impl Router {
         None
     }
 
+    pub fn route_stream(&self, method: &str, path: &str) -> Option<(&StreamHandler, Vec<String>)> {
+        if method != "GET" {
+            return None;
+        }
+        let (path_without_query, _) = split_path_and_query(path);
+        for (route_path, handler) in &self.streams {
+            let mut params = Vec::new();
+            if self.match_route(route_path, path_without_query, &mut params) {
+                return Some((handler, params));
+            }
+        }
+        None
+    }
+
     // Simple route matching to support dynamic parameters
     pub fn match_route(
         &self,
@@ -238,6 +247,12 @@ impl RockboxHttpServer {
         self.router.delete(path, handler);
     }
 
+    /// Routes GET requests to a handler running on a thread of its own, the
+    /// pool threads are kept for short requests.
+    pub fn stream(&mut self, path: &str, handler: StreamHandler) {
+        self.router.stream(path, handler);
+    }
+
     // Start listening and handling incoming requests
     pub fn listen(&mut self) -> Result<(), Error> {
         let port = std::env::var("ROCKBOX_TCP_PORT").unwrap_or_else(|_| "6063".to_string());
@@ -248,37 +263,23 @@ impl RockboxHttpServer {
         let pool = ThreadPool::new(4);
         let active_connections = Arc::new(Mutex::new(0));
         let rt = tokio::runtime::Runtime::new()?;
-        let db_pool = rt.block_on(rockbox_library::create_connection_pool())?;
-        let fs_cache = Arc::new(tokio::sync::Mutex::new(HashMap::new()));
-        let metadata_cache = Arc::new(tokio::sync::Mutex::new(HashMap::new()));
-        let devices = Arc::new(Mutex::new(Vec::new()));
-        let current_device = Arc::new(Mutex::new(None));
-        let player = Arc::new(Mutex::new(None));
-        let kv = Arc::new(Mutex::new(rt.block_on(build_tracks_kv(db_pool.clone()))?));
+        let service = rt.block_on(rockbox_service::instance())?;
 
         // Start scanning for devices
-        scan_chromecast_devices(devices.clone());
-        listen_for_playback_changes(player.clone(), db_pool.clone());
-
-        let indexes = create_indexes()?;
+        scan_chromecast_devices(service.devices.clone());
+        scan_rockbox_devices(service.devices.clone());
+        listen_for_playback_changes(service.player.clone(), service.pool.clone());
 
         loop {
             match listener.accept() {
                 Ok((stream, _)) => {
-                    let db_pool = db_pool.clone();
                     let active_connections = Arc::clone(&active_connections);
                     {
                         let mut active_connections = active_connections.lock().unwrap();
                         *active_connections += 1;
                     }
                     let mut cloned_self = self.clone();
-                    let cloned_fs_cache = fs_cache.clone();
-                    let cloned_metadata_cache = metadata_cache.clone();
-                    let cloned_indexes = indexes.clone();
-                    let cloned_devices = devices.clone();
-                    let cloned_current_device = current_device.clone();
-                    let cloned_player = player.clone();
-                    let cloned_kv = kv.clone();
+                    let cloned_service = service.clone();
                     pool.execute(move || {
                         let mut buf_reader = BufReader::new(&stream);
                         let mut request = String::new();
@@ -299,6 +300,7 @@ impl RockboxHttpServer {
                             };
 
                             let mut content_length = 0;
+                            let mut headers = HashMap::new();
 
                             loop {
                                 let mut line = Default::default();
@@ -314,6 +316,13 @@ impl RockboxHttpServer {
                                     if line.as_str() == "\r\n" || line == "\n" {
                                         break;
                                     }
+
+                                    if let Some((name, value)) = line.split_once(':') {
+                                        headers.insert(
+                                            name.trim().to_lowercase(),
+                                            value.trim().to_string(),
+                                        );
+                                    }
                                 } else {
                                     break;
                                 }
@@ -339,16 +348,10 @@ impl RockboxHttpServer {
                                 method,
                                 path,
                                 query_params,
+                                headers,
                                 stream,
-                                db_pool,
                                 req_body,
-                                cloned_fs_cache,
-                                cloned_metadata_cache,
-                                cloned_indexes,
-                                cloned_devices,
-                                cloned_current_device,
-                                cloned_player,
-                                cloned_kv,
+                                cloned_service,
                             );
                         }
 
@@ -387,35 +390,37 @@ impl RockboxHttpServer {
         method: &str,
         path: &str,
         query_params: Value,
+        headers: HashMap<String, String>,
         mut stream: TcpStream,
-        pool: sqlx::Pool<Sqlite>,
         body: Option<String>,
-        fs_cache: Arc<tokio::sync::Mutex<HashMap<String, Vec<Entry>>>>,
-        metadata_cache: Arc<tokio::sync::Mutex<HashMap<String, Mp3Entry>>>,
-        indexes: Indexes,
-        devices: Arc<Mutex<Vec<Device>>>,
-        current_device: Arc<Mutex<Option<Device>>>,
-        player: Arc<Mutex<Option<Box<dyn Player + Send>>>>,
-        kv: Arc<Mutex<KV<Track>>>,
+        context: Context,
     ) {
         println!("{} {}", method.bright_cyan(), path);
+        if let Some((handler, params)) = self.router.route_stream(method, path) {
+            let handler = *handler;
+            let request = Request {
+                method: method.to_string(),
+                params,
+                query_params,
+                headers,
+                body,
+            };
+            thread::spawn(move || {
+                if let Err(e) = handler(&context, &request, stream) {
+                    eprintln!("Error: {}", e);
+                }
+            });
+            return;
+        }
+
         match self.router.route(method, path) {
             Some((handler, params)) => {
                 let mut response = Response::new();
-                let context = Context {
-                    pool,
-                    fs_cache,
-                    metadata_cache,
-                    indexes,
-                    devices,
-                    current_device,
-                    player,
-                    kv,
-                };
                 let request = Request {
                     method: method.to_string(),
                     params,
                     query_params,
+                    headers,
                     body,
                 };
                 match handler(&context, &request, &mut response) {
@@ -1,42 +1,21 @@
-use anyhow::Error;
 use handlers::*;
 
+use handlers::devices::connect_player;
+use handlers::events::events;
 use http::RockboxHttpServer;
-use lazy_static::lazy_static;
-use rockbox_graphql::{
-    schema::objects::{self, audio_status::AudioStatus, track::Track},
-    simplebroker::SimpleBroker,
-};
-use rockbox_library::repo;
 use rockbox_mpd::MpdServer;
 use rockbox_mpris::MprisServer;
-use rockbox_sys::events::RockboxCommand;
-use rockbox_sys::{self as rb, types::mp3_entry::Mp3Entry};
-use sqlx::{Pool, Sqlite};
-use std::{
-    collections::HashMap,
-    ffi::c_char,
-    ffi::c_int,
-    sync::{Arc, Mutex},
-    thread,
-};
+use std::{ffi::c_char, ffi::c_int, ffi::c_long, ffi::c_ulong, thread};
 
+pub mod broker;
 pub mod cache;
 pub mod handlers;
 pub mod http;
-pub mod kv;
+pub mod local_player;
 pub mod player_events;
 pub mod scan;
 
-pub const AUDIO_EXTENSIONS: [&str; 17] = [
-    "mp3", "ogg", "flac", "m4a", "aac", "mp4", "alac", "wav", "wv", "mpc", "aiff", "ac3", "opus",
-    "spx", "sid", "ape", "wma",
-];
-
-lazy_static! {
-    pub static ref GLOBAL_MUTEX: Mutex<i32> = Mutex::new(0);
-    pub static ref PLAYER_MUTEX: Mutex<i32> = Mutex::new(0);
-}
+pub use rockbox_service::{AUDIO_EXTENSIONS, GLOBAL_MUTEX, PLAYER_MUTEX};
 
 #[no_mangle]
 pub extern "C" fn debugfn(args: *const c_char, value: c_int) {
@@ -54,16 +33,27 @@ pub extern "C" fn start_server() {
         }
     }
 
+    rockbox_service::devices::register_connector(|device| Box::pin(connect_player(device)));
+    rockbox_service::remote::register_remote_library(|device, query| {
+        Box::pin(rockbox_remote::library::query(device, query))
+    });
+
     let mut app = RockboxHttpServer::new();
 
     app.get("/albums", get_albums);
     app.get("/albums/:id", get_album);
     app.get("/albums/:id/tracks", get_album_tracks);
+    app.get("/albums/:id/editions", get_album_editions);
 
     app.get("/artists", get_artists);
     app.get("/artists/:id", get_artist);
     app.get("/artists/:id/albums", get_artist_albums);
     app.get("/artists/:id/tracks", get_artist_tracks);
+    app.get("/artists/:id/appears-on", get_artist_appears_on);
+
+    app.get("/genres", get_genres);
+    app.get("/genres/:id", get_genre);
+    app.get("/genres/:id/tracks", get_genre_tracks);
 
     app.get("/browse/tree-entries", get_tree_entries);
 
@@ -82,6 +72,10 @@ pub extern "C" fn start_server() {
     app.put("/player/stop", stop);
     app.get("/player/file-position", get_file_position);
     app.put("/player/volume", adjust_volume);
+    app.get("/player/speed", get_speed);
+    app.put("/player/speed", set_speed);
+    app.get("/player/chapters", get_chapters);
+    app.put("/player/chapters/:index", seek_chapter);
 
     app.post("/playlists", create_playlist);
     app.put("/playlists/start", start_playlist);
@@ -96,12 +90,18 @@ pub extern "C" fn start_server() {
 
     app.get("/tracks", get_tracks);
     app.get("/tracks/:id", get_track);
+    app.get("/tracks/:id/lyrics", get_track_lyrics);
+    app.get("/tracks/:id/replaygain", get_track_replaygain);
+    app.get("/tracks/:id/chapters", get_track_chapters);
 
     app.get("/version", get_rockbox_version);
     app.get("/status", get_status);
     app.get("/settings", get_global_settings);
     app.put("/settings", update_global_settings);
     app.put("/scan-library", scan_library);
+    app.get("/scan-library/progress", get_indexing_progress);
+    app.post("/replaygain/analyze", analyze_replaygain);
+    app.get("/replaygain/progress", get_replaygain_progress);
     app.get("/search", search);
 
     app.get("/devices", get_devices);
@@ -109,11 +109,72 @@ pub extern "C" fn start_server() {
     app.put("/devices/:id/connect", connect);
     app.put("/devices/:id/disconnect", disconnect);
 
+    app.get("/dsp/equalizer", get_equalizer);
+    app.put("/dsp/equalizer", update_equalizer);
+    app.get("/dsp/equalizer/bands/:band", get_eq_band);
+    app.put("/dsp/equalizer/bands/:band", update_eq_band);
+    app.get("/dsp/equalizer/comparison", get_eq_comparison);
+    app.post("/dsp/equalizer/comparison/toggle", toggle_eq_comparison);
+    app.put("/dsp/equalizer/comparison/:slot", set_eq_comparison_slot);
+    app.get("/dsp/presets", get_eq_presets);
+    app.post("/dsp/presets", save_eq_preset);
+    app.post("/dsp/presets/import", import_eq_preset);
+    app.get("/dsp/presets/:name", get_eq_preset);
+    app.delete("/dsp/presets/:name", delete_eq_preset);
+    app.put("/dsp/presets/:name/load", load_eq_preset);
+    app.get("/dsp/devices", get_eq_device_presets);
+    app.put("/dsp/devices/:id", bind_eq_preset);
+
+    app.get("/sleep-timer", get_sleep_timer);
+    app.put("/sleep-timer", set_sleep_timer);
+    app.delete("/sleep-timer", cancel_sleep_timer);
+    app.get("/schedules", get_schedules);
+    app.post("/schedules", create_schedule);
+    app.get("/schedules/:id", get_schedule);
+    app.put("/schedules/:id", update_schedule);
+    app.delete("/schedules/:id", delete_schedule);
+    app.post("/schedules/:id/run", run_schedule);
+
+    app.get("/bookmarks", get_bookmarks);
+    app.post("/bookmarks", create_bookmark);
+    app.get("/bookmarks/:id", get_bookmark);
+    app.put("/bookmarks/:id", update_bookmark);
+    app.delete("/bookmarks/:id", delete_bookmark);
+    app.post("/bookmarks/:id/play", play_bookmark);
+
+    app.get("/podcasts", get_podcasts);
+    app.post("/podcasts", subscribe_podcast);
+    app.get("/podcasts/:id", get_podcast);
+    app.put("/podcasts/:id", update_podcast);
+    app.delete("/podcasts/:id", unsubscribe_podcast);
+    app.post("/podcasts/:id/refresh", refresh_podcast);
+    app.get("/podcasts/:id/episodes", get_podcast_episodes);
+    app.get("/podcast-episodes/:id", get_podcast_episode);
+    app.post("/podcast-episodes/:id/download", download_episode);
+    app.put("/podcast-episodes/:id/played", set_episode_played);
+
+    app.get("/stations", get_stations);
+    app.post("/stations", create_station);
+    app.get("/stations/:id", get_station);
+    app.put("/stations/:id", update_station);
+    app.delete("/stations/:id", delete_station);
+    app.post("/stations/:id/play", play_station);
+    app.get("/player/station", get_current_station);
+
+    app.get("/groups", get_groups);
+    app.post("/groups", create_group);
+    app.get("/groups/:id", get_group);
+    app.delete("/groups/:id", delete_group);
+    app.put("/groups/:id/connect", connect_group);
+    app.put("/groups/:id/members/:device_id", update_group_member);
+
     app.get("/", index);
     app.get("/operations/:id", index);
     app.get("/schemas/:id", index);
     app.get("/openapi.json", get_openapi);
 
+    app.stream("/events", events);
+
     match app.listen() {
         Ok(_) => {}
         Err(e) => {
@@ -124,82 +185,17 @@ pub extern "C" fn start_server() {
 
 #[no_mangle]
 pub extern "C" fn start_servers() {
-    let (cmd_tx, cmd_rx) = std::sync::mpsc::channel::<RockboxCommand>();
-    let cmd_tx = Arc::new(Mutex::new(cmd_tx));
-
-    thread::spawn(move || {
-        let port = std::env::var("ROCKBOX_TCP_PORT").unwrap_or_else(|_| "6063".to_string());
-        let url = format!("http://127.0.0.1:{}", port);
-        let client = reqwest::blocking::Client::new();
-
-        while let Ok(event) = cmd_rx.recv() {
-            match event {
-                RockboxCommand::Play(elapsed, offset) => {
-                    client
-                        .put(&format!(
-                            "{}/player/play?elapsed={}&offset={}",
-                            url, elapsed, offset
-                        ))
-                        .send()
-                        .unwrap();
-                }
-                RockboxCommand::Pause => {
-                    client.put(&format!("{}/player/pause", url)).send().unwrap();
-                }
-                RockboxCommand::Resume => {
-                    client
-                        .put(&format!("{}/player/resume", url))
-                        .send()
-                        .unwrap();
-                }
-                RockboxCommand::Next => {
-                    client.put(&format!("{}/player/next", url)).send().unwrap();
-                }
-                RockboxCommand::Prev => {
-                    client
-                        .put(&format!("{}/player/previous", url))
-                        .send()
-                        .unwrap();
-                }
-                RockboxCommand::FfRewind(newtime) => {
-                    client
-                        .put(&format!("{}/player/ff-rewind?newtime={}", url, newtime))
-                        .send()
-                        .unwrap();
-                }
-                RockboxCommand::FlushAndReloadTracks => {
-                    client
-                        .put(&format!("{}/player/flush-and-reload-tracks", url))
-                        .send()
-                        .unwrap();
-                }
-                RockboxCommand::Stop => {
-                    client.put(&format!("{}/player/stop", url)).send().unwrap();
-                }
-                RockboxCommand::PlaylistResume => {
-                    client
-                        .put(&format!("{}/playlists/resume", url))
-                        .send()
-                        .unwrap();
-                }
-                RockboxCommand::PlaylistResumeTrack => {
-                    client
-                        .put(&format!("{}/playlists/resume-track", url))
-                        .send()
-                        .unwrap();
-                }
-            }
-        }
+    rockbox_service::devices::register_connector(|device| Box::pin(connect_player(device)));
+    rockbox_service::remote::register_remote_library(|device, query| {
+        Box::pin(rockbox_remote::library::query(device, query))
     });
 
-    let cloned_cmd_tx = cmd_tx.clone();
-
     thread::spawn(move || {
         let runtime = tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()
             .unwrap();
-        match runtime.block_on(rockbox_rpc::server::start(cmd_tx.clone())) {
+        match runtime.block_on(rockbox_rpc::server::start()) {
             Ok(_) => {}
             Err(e) => {
                 eprintln!("Error starting server: {}", e);
@@ -212,7 +208,7 @@ pub extern "C" fn start_servers() {
             .enable_all()
             .build()
             .unwrap();
-        match runtime.block_on(rockbox_graphql::server::start(cloned_cmd_tx.clone())) {
+        match runtime.block_on(rockbox_graphql::server::start()) {
             Ok(_) => {}
             Err(e) => {
                 eprintln!("Error starting server: {}", e);
@@ -244,146 +240,71 @@ pub extern "C" fn start_servers() {
             }
         }
     });
+
+    rockbox_stream::install();
+    rockbox_discovery::register_services();
 }
 
 #[no_mangle]
 pub extern "C" fn start_broker() {
-    let rt = tokio::runtime::Builder::new_current_thread()
-        .enable_all()
-        .build()
-        .unwrap();
-    let pool = rt
-        .block_on(rockbox_library::create_connection_pool())
-        .unwrap();
-
-    let mut metadata_cache: HashMap<String, Mp3Entry> = HashMap::new();
-    let mut previous_index = -10; // Arbitrary value to ensure the first track is scobbled
-
-    loop {
-        let mutex = GLOBAL_MUTEX.lock().unwrap();
-        if *mutex == 1 {
-            drop(mutex);
-            thread::sleep(std::time::Duration::from_millis(100));
-            rb::system::sleep(rb::HZ);
-            continue;
-        }
-
-        drop(mutex);
-
-        let player_mutex = PLAYER_MUTEX.lock().unwrap();
-
-        let playback_status: AudioStatus = rb::playback::status().into();
-        SimpleBroker::publish(playback_status);
-        match rb::playback::current_track() {
-            Some(current_track) => {
-                let hash = format!("{:x}", md5::compute(current_track.path.as_bytes()));
-                if let Ok(Some(metadata)) =
-                    rt.block_on(repo::track::find_by_md5(pool.clone(), &hash))
-                {
-                    let mut track: Track = current_track.into();
-                    track.id = Some(metadata.id);
-                    track.album_art = metadata.album_art;
-                    track.album_id = Some(metadata.album_id);
-                    track.artist_id = Some(metadata.artist_id);
-                    SimpleBroker::publish(track.clone());
-
-                    if previous_index != rb::playlist::index() {
-                        let cloned_pool = pool.clone();
-                        thread::spawn(move || {
-                            let rt = tokio::runtime::Builder::new_current_thread()
-                                .enable_all()
-                                .build()
-                                .unwrap();
-                            match rt.block_on(scrobble(track.clone(), cloned_pool.clone())) {
-                                Ok(_) => {}
-                                Err(e) => eprintln!("{}", e),
-                            }
-                        });
-                    }
-                    previous_index = rb::playlist::index();
-                }
-            }
-            None => {}
-        };
-
-        let mut entries: Vec<Mp3Entry> = vec![];
-
-        let mut current_playlist = rb::playlist::get_current();
-        let amount = rb::playlist::amount();
-
-        for i in 0..amount {
-            let info = rb::playlist::get_track_info(i);
-            let mut entry = rb::metadata::get_metadata(-1, &info.filename);
-
-            let hash = format!("{:x}", md5::compute(info.filename.as_bytes()));
-
-            if let Some(entry) = metadata_cache.get(&hash) {
-                entries.push(entry.clone());
-                continue;
-            }
-
-            let track = rt
-                .block_on(repo::track::find_by_md5(pool.clone(), &hash))
-                .unwrap();
-
-            if track.is_none() {
-                entries.push(entry);
-                continue;
-            }
-
-            entry.album_art = track.as_ref().map(|t| t.album_art.clone()).flatten();
-            entry.album_id = track.as_ref().map(|t| t.album_id.clone());
-            entry.artist_id = track.as_ref().map(|t| t.artist_id.clone());
-            entry.genre_id = track.as_ref().map(|t| t.genre_id.clone());
-
-            metadata_cache.insert(hash, entry.clone());
-            entries.push(entry);
-        }
+    broker::run();
+}
 
-        drop(player_mutex);
-
-        current_playlist.amount = amount;
-        current_playlist.max_playlist_size = rb::playlist::max_playlist_size();
-        current_playlist.index = rb::playlist::index();
-        current_playlist.first_index = rb::playlist::first_index();
-        current_playlist.last_insert_pos = rb::playlist::last_insert_pos();
-        current_playlist.seed = rb::playlist::seed();
-        current_playlist.last_shuffled_start = rb::playlist::last_shuffled_start();
-        current_playlist.entries = entries;
-
-        SimpleBroker::publish(objects::playlist::Playlist {
-            amount: current_playlist.amount,
-            index: current_playlist.index,
-            max_playlist_size: current_playlist.max_playlist_size,
-            first_index: current_playlist.first_index,
-            last_insert_pos: current_playlist.last_insert_pos,
-            seed: current_playlist.seed,
-            last_shuffled_start: current_playlist.last_shuffled_start,
-            tracks: current_playlist
-                .entries
-                .into_iter()
-                .map(|t| t.into())
-                .collect(),
-        });
-
-        thread::sleep(std::time::Duration::from_millis(100));
-        rb::system::sleep(rb::HZ);
+/// Called by the metadata parser of the firmware for files without
+/// ReplayGain tags, fills in the values measured by the analysis. Gains are
+/// in dB multiplied by 512 and peaks in Q7.24.
+#[no_mangle]
+pub extern "C" fn rb_stored_replaygain(
+    path: *const c_char,
+    track_gain: *mut c_long,
+    track_peak: *mut c_long,
+    album_gain: *mut c_long,
+    album_peak: *mut c_long,
+) -> bool {
+    let path = unsafe { std::ffi::CStr::from_ptr(path) };
+    let Some(stored) = path
+        .to_str()
+        .ok()
+        .and_then(rockbox_library::replaygain::stored)
+    else {
+        return false;
+    };
+
+    let gain = |gain: f64| (gain * 512.0).round() as c_long;
+    let peak = |peak: f64| (peak * (1 << 24) as f64).round() as c_long;
+    unsafe {
+        *track_gain = gain(stored.track_gain);
+        *track_peak = peak(stored.track_peak);
+        *album_gain = stored.album_gain.map(gain).unwrap_or(0);
+        *album_peak = stored.album_peak.map(peak).unwrap_or(0);
     }
+    true
 }
 
-async fn scrobble(track: Track, pool: Pool<Sqlite>) -> Result<(), Error> {
-    let album_id = track.album_id.unwrap();
-    let track = repo::track::find(pool.clone(), &track.id.unwrap()).await?;
-    let album = repo::album::find(pool, &album_id).await?;
-
-    if let Some(track) = track {
-        if let Some(album) = album {
-            match rockbox_rocksky::scrobble(track, album).await {
-                Ok(_) => {}
-                Err(e) => eprintln!("Failed to scrobble {}", e),
-            };
-        }
-    }
+/// Called by the PCM buffer on a track change it can crossfade, with the
+/// decision of the crossfade setting, see
+/// [`rockbox_service::transitions::crossfade`].
+#[no_mangle]
+pub extern "C" fn rb_transition_crossfade(crossfade: bool, auto_skip: bool) -> bool {
+    rockbox_service::transitions::crossfade(crossfade, auto_skip)
+}
 
-    Ok(())
+/// Called by playback when the codec starts a track, sets where it starts
+/// and ends in ms, 0 keeping the bounds of the track.
+#[no_mangle]
+pub extern "C" fn rb_transition_bounds(
+    path: *const c_char,
+    auto_skip: bool,
+    start_time: *mut c_ulong,
+    end_time: *mut c_ulong,
+) {
+    let path = unsafe { std::ffi::CStr::from_ptr(path) };
+    let Ok(path) = path.to_str() else {
+        return;
+    };
+    let (start, end) = rockbox_service::transitions::bounds(path, auto_skip);
+    unsafe {
+        *start_time = start as c_ulong;
+        *end_time = end as c_ulong;
+    }
 }
@@ -0,0 +1,176 @@
+use anyhow::Error;
+use async_trait::async_trait;
+use rockbox_service::radio;
+use rockbox_sys::{self as rb, types::mp3_entry::Mp3Entry};
+use rockbox_traits::types::{playback::Playback, track::Track};
+use rockbox_traits::Player;
+use rockbox_types::radio::Station;
+
+/// The built-in rockbox playback engine behind the `Player` trait, so that the
+/// local output can take part in a group next to remote devices.
+pub struct LocalPlayer {}
+
+impl LocalPlayer {
+    pub fn new() -> Self {
+        LocalPlayer {}
+    }
+}
+
+impl Default for LocalPlayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn to_track(entry: Mp3Entry) -> Track {
+    Track {
+        id: entry.id.unwrap_or_default(),
+        uri: entry.path.clone(),
+        path: entry.path,
+        title: entry.title,
+        artist: entry.artist,
+        album: entry.album,
+        track_number: Some(entry.tracknum as u32),
+        disc_number: entry.discnum as u32,
+        duration: Some(entry.length as f32 / 1000.0),
+        album_artist: Some(entry.albumartist),
+        album_id: entry.album_id,
+        artist_id: entry.artist_id,
+        ..Default::default()
+    }
+}
+
+#[async_trait]
+impl Player for LocalPlayer {
+    async fn play(&self) -> Result<(), Error> {
+        rb::playback::resume();
+        Ok(())
+    }
+
+    async fn next(&self) -> Result<(), Error> {
+        rb::playback::next();
+        Ok(())
+    }
+
+    async fn previous(&self) -> Result<(), Error> {
+        rb::playback::prev();
+        Ok(())
+    }
+
+    async fn stop(&self) -> Result<(), Error> {
+        rb::playback::hard_stop();
+        Ok(())
+    }
+
+    async fn pause(&self) -> Result<(), Error> {
+        rb::playback::pause();
+        Ok(())
+    }
+
+    async fn resume(&self) -> Result<(), Error> {
+        rb::playback::resume();
+        Ok(())
+    }
+
+    async fn seek(&self, seconds: i32) -> Result<(), Error> {
+        rb::playback::ff_rewind(seconds * 1000);
+        Ok(())
+    }
+
+    async fn seek_ms(&self, position_ms: u32) -> Result<(), Error> {
+        rb::playback::ff_rewind(position_ms as i32);
+        Ok(())
+    }
+
+    async fn volume(&self, level: f32) -> Result<(), Error> {
+        let min = rb::sound::min(rb::SOUND_VOLUME);
+        let max = rb::sound::max(rb::SOUND_VOLUME);
+        let target = min + ((max - min) as f32 * level.clamp(0.0, 1.0)).round() as i32;
+        let current = unsafe { rb::global_settings.volume };
+        rb::sound::adjust_volume(target - current);
+        Ok(())
+    }
+
+    async fn load_tracks(&self, tracks: Vec<Track>, start_index: Option<i32>) -> Result<(), Error> {
+        if tracks.is_empty() {
+            return Ok(());
+        }
+        // a radio station, its stream is relayed to files the firmware plays
+        if tracks[0].path.starts_with("http") {
+            let track = &tracks[0];
+            return radio::play_locally(Station {
+                id: track.id.clone(),
+                name: track.title.clone(),
+                url: track.path.clone(),
+                image: track.album_cover.clone(),
+                ..Default::default()
+            })
+            .await;
+        }
+        let paths: Vec<&str> = tracks.iter().map(|t| t.path.as_str()).collect();
+        let dir_parts: Vec<_> = paths[0].split('/').collect();
+        let dir = dir_parts[0..dir_parts.len() - 1].join("/");
+        if rb::playlist::create(&dir, None) == -1 {
+            return Err(Error::msg("Failed to create playlist"));
+        }
+        rb::playlist::build_playlist(paths, 0, tracks.len() as i32);
+        rb::playlist::start(start_index.unwrap_or(0), 0, 0);
+        Ok(())
+    }
+
+    async fn play_next(&self, track: Track) -> Result<(), Error> {
+        let position = rb::playlist::index() + 1;
+        rb::playlist::insert_tracks(vec![track.path.as_str()], position, 1);
+        Ok(())
+    }
+
+    async fn load(&mut self, track: Track) -> Result<(), Error> {
+        self.load_tracks(vec![track], None).await
+    }
+
+    async fn get_current_playback(&mut self) -> Result<Playback, Error> {
+        let current_track = rb::playback::current_track();
+        let position_ms = current_track.as_ref().map(|t| t.elapsed).unwrap_or(0) as u32;
+        Ok(Playback {
+            current_track: current_track.map(to_track),
+            index: rb::playlist::index().max(0) as u32,
+            position_ms,
+            is_playing: rb::playback::status().status == rb::AUDIO_STATUS_PLAY,
+            ..Default::default()
+        })
+    }
+
+    async fn get_current_tracklist(&self) -> Result<(Vec<Track>, Vec<Track>), Error> {
+        let current = rb::playlist::index().max(0);
+        let amount = rb::playlist::amount();
+        let mut previous_tracks = vec![];
+        let mut next_tracks = vec![];
+        for i in 0..amount {
+            let info = rb::playlist::get_track_info(i);
+            let track = Track {
+                uri: info.filename.clone(),
+                path: info.filename,
+                ..Default::default()
+            };
+            match i < current {
+                true => previous_tracks.push(track),
+                false => next_tracks.push(track),
+            }
+        }
+        Ok((previous_tracks, next_tracks))
+    }
+
+    async fn play_track_at(&self, position: u32) -> Result<(), Error> {
+        rb::playlist::start(position as i32, 0, 0);
+        Ok(())
+    }
+
+    async fn remove_track_at(&self, position: u32) -> Result<(), Error> {
+        rb::playlist::delete_track(position as i32);
+        Ok(())
+    }
+
+    async fn disconnect(&self) -> Result<(), Error> {
+        Ok(())
+    }
+}
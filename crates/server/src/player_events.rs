@@ -1,8 +1,4 @@
-use std::{
-    env,
-    sync::{Arc, Mutex},
-    thread,
-};
+use std::{env, sync::Arc, thread};
 
 use rockbox_graphql::{
     schema::objects::{audio_status::AudioStatus, playlist::Playlist, track::Track},
@@ -15,7 +11,7 @@ use sqlx::{Pool, Sqlite};
 use url::Url;
 
 pub fn listen_for_playback_changes(
-    player: Arc<Mutex<Option<Box<dyn Player + Send>>>>,
+    player: Arc<tokio::sync::Mutex<Option<Box<dyn Player + Send>>>>,
     pool: Pool<Sqlite>,
 ) {
     let cloned_player = player.clone();
@@ -23,7 +19,7 @@ pub fn listen_for_playback_changes(
         let rt = tokio::runtime::Runtime::new().unwrap();
         let client = reqwest::blocking::Client::new();
         loop {
-            let mut player = cloned_player.lock().unwrap();
+            let mut player = cloned_player.blocking_lock();
 
             if let Some(player) = player.as_deref_mut() {
                 if let Ok(current_playback) = rt.block_on(player.get_current_playback()) {
@@ -34,7 +30,10 @@ pub fn listen_for_playback_changes(
                             let album_art = match current_track.album_cover {
                                 Some(cover) => {
                                     let url = Url::parse(&cover).unwrap();
-                                    let path = url.path();
+                                    let path = match url.query() {
+                                        Some(query) => format!("{}?{}", url.path(), query),
+                                        None => url.path().to_string(),
+                                    };
                                     match client
                                         .get(&format!(
                                             "{}:{}{}",
@@ -1,6 +1,7 @@
 use futures_util::StreamExt;
-use rockbox_discovery::{discover, CHROMECAST_SERVICE_NAME};
+use rockbox_discovery::{device_id, discover, CHROMECAST_SERVICE_NAME, ROCKBOX_SERVICE_NAME};
 use rockbox_graphql::simplebroker::SimpleBroker;
+use rockbox_service::events::{self, EventKind};
 use rockbox_types::device::Device;
 use std::{
     sync::{Arc, Mutex},
@@ -10,6 +11,7 @@ use std::{
 pub fn scan_chromecast_devices(devices: Arc<Mutex<Vec<Device>>>) {
     thread::spawn(move || {
         tokio::runtime::Runtime::new().unwrap().block_on(async {
+            let current_device_id = device_id();
             let services = discover(CHROMECAST_SERVICE_NAME);
             tokio::pin!(services);
             while let Some(info) = services.next().await {
@@ -20,8 +22,34 @@ pub fn scan_chromecast_devices(devices: Arc<Mutex<Vec<Device>>>) {
                 {
                     continue;
                 }
-                devices.push(Device::from(info.clone()));
-                SimpleBroker::<Device>::publish(Device::from(info.clone()));
+                let device = Device::from_service(info, &current_device_id);
+                devices.push(device.clone());
+                SimpleBroker::<Device>::publish(device.clone());
+                events::publish(EventKind::DeviceFound(device));
+            }
+        });
+    });
+}
+
+pub fn scan_rockbox_devices(devices: Arc<Mutex<Vec<Device>>>) {
+    thread::spawn(move || {
+        tokio::runtime::Runtime::new().unwrap().block_on(async {
+            let current_device_id = device_id();
+            let services = discover(ROCKBOX_SERVICE_NAME);
+            tokio::pin!(services);
+            while let Some(info) = services.next().await {
+                let device = Device::from_service(info, &current_device_id);
+                // every instance advertises several services, keep the grpc one
+                if device.service != "grpc" || device.is_current_device {
+                    continue;
+                }
+                let mut devices = devices.lock().unwrap();
+                if devices.iter().any(|d| d.id == device.id) {
+                    continue;
+                }
+                devices.push(device.clone());
+                SimpleBroker::<Device>::publish(device.clone());
+                events::publish(EventKind::DeviceFound(device));
             }
         });
     });
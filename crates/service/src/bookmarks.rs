@@ -0,0 +1,389 @@
+use std::{path::Path, sync::Mutex};
+
+use anyhow::{anyhow, Error};
+use chrono::Utc;
+use rockbox_library::{
+    chapters::{self, Chapter},
+    entity, repo,
+};
+use rockbox_sys as rb;
+use rockbox_types::bookmark::{Bookmark, BookmarkSource, NewBookmark, UpdateBookmark};
+
+use crate::{list_directory, podcasts::podcasts_dir, read_files, RockboxService};
+
+/// Milliseconds of playback between two saves of the resume positions.
+const SAVE_INTERVAL: u64 = 10_000;
+
+/// A track stopped this close to its end, in milliseconds, is finished and
+/// plays from the beginning next time.
+const FINISHED_MARGIN: u64 = 30_000;
+
+/// Genres of long-form tracks, matched in lowercase.
+const LONG_FORM_GENRES: [&str; 3] = ["audiobook", "podcast", "spoken"];
+
+/// The long-form track playing.
+#[derive(Clone)]
+struct Progress {
+    path: String,
+    track_id: Option<String>,
+    album_id: Option<String>,
+    /// The directory the track resumes from.
+    directory: Option<String>,
+    length: u64,
+    elapsed: u64,
+    /// The elapsed time last saved.
+    saved: u64,
+}
+
+static PROGRESS: Mutex<Option<Progress>> = Mutex::new(None);
+
+/// The directory played last, its tracks resume from it rather than from
+/// their own folder when it was played recursively.
+static PLAYED_DIRECTORY: Mutex<Option<String>> = Mutex::new(None);
+
+pub(crate) fn set_played_directory(directory: Option<&str>) {
+    *PLAYED_DIRECTORY.lock().unwrap() = directory.map(String::from);
+}
+
+impl RockboxService {
+    /// Follows the track playing, called by the broker with each snapshot of
+    /// the player. The resume positions of long-form tracks are saved while
+    /// they play, and deleted once they are finished.
+    pub async fn track_progress(
+        &self,
+        path: Option<&str>,
+        elapsed: u64,
+        length: u64,
+    ) -> Result<(), Error> {
+        let (stopped, playing) = {
+            let mut progress = PROGRESS.lock().unwrap();
+            match progress.as_mut() {
+                Some(current) if Some(current.path.as_str()) == path => {
+                    current.elapsed = elapsed;
+                    if elapsed.abs_diff(current.saved) < SAVE_INTERVAL {
+                        return Ok(());
+                    }
+                    current.saved = elapsed;
+                    (None, Some(current.clone()))
+                }
+                _ => {
+                    let stopped = progress.take();
+                    *progress = path
+                        .filter(|path| self.is_long_form(path, length))
+                        .map(|path| self.progress(path, elapsed, length));
+                    (stopped, None)
+                }
+            }
+        };
+
+        if let Some(stopped) = stopped {
+            if stopped.length.saturating_sub(stopped.elapsed) <= FINISHED_MARGIN {
+                repo::bookmark::delete_resume_positions(self.pool.clone(), &stopped.path).await?;
+                self.episode_finished(&stopped.path).await?;
+            } else if stopped.elapsed != stopped.saved {
+                self.save_resume_positions(&stopped).await?;
+            }
+        }
+        if let Some(playing) = playing {
+            self.save_resume_positions(&playing).await?;
+        }
+        Ok(())
+    }
+
+    /// Whether a track remembers where it was stopped: `.m4b` audiobooks,
+    /// the downloaded podcast episodes, the tracks of the resume directories
+    /// or of the audiobook and podcast genres, and the tracks longer than the
+    /// minimum length.
+    pub fn is_long_form(&self, path: &str, length: u64) -> bool {
+        let settings = rb::settings::get_resume_settings();
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase());
+        if extension.as_deref() == Some("m4b")
+            || (settings.min_length > 0 && length >= settings.min_length as u64 * 1000)
+            || Path::new(path).starts_with(podcasts_dir())
+            || settings
+                .directories
+                .iter()
+                .any(|directory| Path::new(path).starts_with(directory))
+        {
+            return true;
+        }
+
+        let kv = self.kv.lock().unwrap();
+        kv.get(path)
+            .and_then(|track| track.genre.as_ref())
+            .map(|genre| {
+                let genre = genre.to_lowercase();
+                LONG_FORM_GENRES.iter().any(|g| genre.contains(g))
+            })
+            .unwrap_or(false)
+    }
+
+    fn progress(&self, path: &str, elapsed: u64, length: u64) -> Progress {
+        let kv = self.kv.lock().unwrap();
+        let track = kv.get(path);
+        Progress {
+            path: path.to_string(),
+            track_id: track.map(|track| track.id.clone()),
+            album_id: track.map(|track| track.album_id.clone()),
+            directory: resume_directory(path),
+            length,
+            elapsed,
+            saved: elapsed,
+        }
+    }
+
+    /// Saves where the track, its album and its directory resume.
+    async fn save_resume_positions(&self, progress: &Progress) -> Result<(), Error> {
+        let sources = [
+            Some((BookmarkSource::Track, progress.path.clone())),
+            progress
+                .album_id
+                .clone()
+                .map(|album_id| (BookmarkSource::Album, album_id)),
+            progress
+                .directory
+                .clone()
+                .map(|directory| (BookmarkSource::Directory, directory)),
+        ];
+        let now = Utc::now();
+        for (source, source_id) in sources.into_iter().flatten() {
+            let bookmark = entity::bookmark::Bookmark {
+                id: cuid::cuid1()?,
+                name: None,
+                source: source.to_string(),
+                source_id,
+                path: progress.path.clone(),
+                track_id: progress.track_id.clone(),
+                elapsed: progress.elapsed as i64,
+                created_at: now,
+                updated_at: now,
+            };
+            repo::bookmark::save_resume_position(self.pool.clone(), bookmark).await?;
+        }
+        Ok(())
+    }
+
+    /// Where `tracks` played from a source resume, the index of the track
+    /// and the elapsed time.
+    pub(crate) async fn resume_position(
+        &self,
+        source: BookmarkSource,
+        source_id: &str,
+        tracks: &[String],
+    ) -> Result<Option<(i32, u64)>, Error> {
+        let bookmark =
+            repo::bookmark::find_resume_position(self.pool.clone(), source.as_str(), source_id)
+                .await?;
+        Ok(bookmark.and_then(|bookmark| {
+            tracks
+                .iter()
+                .position(|path| *path == bookmark.path)
+                .map(|index| (index as i32, bookmark.elapsed.max(0) as u64))
+        }))
+    }
+
+    /// The named bookmarks and the resume positions, which have no name,
+    /// the latest first.
+    pub async fn bookmarks(&self) -> Result<Vec<Bookmark>, Error> {
+        let bookmarks = repo::bookmark::all(self.pool.clone()).await?;
+        Ok(bookmarks.into_iter().map(into_bookmark).collect())
+    }
+
+    pub async fn bookmark(&self, id: &str) -> Result<Option<Bookmark>, Error> {
+        let bookmark = repo::bookmark::find(self.pool.clone(), id).await?;
+        Ok(bookmark.map(into_bookmark))
+    }
+
+    pub async fn create_bookmark(&self, bookmark: NewBookmark) -> Result<Bookmark, Error> {
+        if bookmark.name.trim().is_empty() {
+            return Err(anyhow!("Missing bookmark name"));
+        }
+        let (path, elapsed) = match bookmark.path {
+            Some(path) => (
+                path.replace("file://", ""),
+                bookmark.elapsed.unwrap_or_default(),
+            ),
+            None => {
+                let track = self
+                    .current_track()
+                    .await?
+                    .ok_or_else(|| anyhow!("No track is playing"))?;
+                (track.path, bookmark.elapsed.unwrap_or(track.elapsed))
+            }
+        };
+        let (track_id, album_id) = {
+            let kv = self.kv.lock().unwrap();
+            let track = kv.get(&path);
+            (
+                track.map(|track| track.id.clone()),
+                track.map(|track| track.album_id.clone()),
+            )
+        };
+
+        let source = bookmark.source.unwrap_or(match album_id {
+            Some(_) => BookmarkSource::Album,
+            None => BookmarkSource::Directory,
+        });
+        let source_id = match source {
+            BookmarkSource::Track => path.clone(),
+            BookmarkSource::Album => {
+                album_id.ok_or_else(|| anyhow!("The track is not in the library"))?
+            }
+            BookmarkSource::Directory => {
+                parent_directory(&path).ok_or_else(|| anyhow!("The track has no directory"))?
+            }
+        };
+
+        let now = Utc::now();
+        let bookmark = entity::bookmark::Bookmark {
+            id: cuid::cuid1()?,
+            name: Some(bookmark.name.trim().to_string()),
+            source: source.to_string(),
+            source_id,
+            path,
+            track_id,
+            elapsed: elapsed as i64,
+            created_at: now,
+            updated_at: now,
+        };
+        repo::bookmark::save(self.pool.clone(), bookmark.clone()).await?;
+        Ok(into_bookmark(bookmark))
+    }
+
+    /// Returns `None` when there is no such bookmark. Naming a resume
+    /// position keeps it as a bookmark.
+    pub async fn update_bookmark(
+        &self,
+        id: &str,
+        update: UpdateBookmark,
+    ) -> Result<Option<Bookmark>, Error> {
+        let mut bookmark = match repo::bookmark::find(self.pool.clone(), id).await? {
+            Some(bookmark) => bookmark,
+            None => return Ok(None),
+        };
+        if let Some(name) = update.name {
+            if name.trim().is_empty() {
+                return Err(anyhow!("Missing bookmark name"));
+            }
+            bookmark.name = Some(name.trim().to_string());
+        }
+        if let Some(elapsed) = update.elapsed {
+            bookmark.elapsed = elapsed as i64;
+        }
+        bookmark.updated_at = Utc::now();
+        repo::bookmark::update(self.pool.clone(), &bookmark).await?;
+        Ok(Some(into_bookmark(bookmark)))
+    }
+
+    pub async fn delete_bookmark(&self, id: &str) -> Result<bool, Error> {
+        Ok(repo::bookmark::delete(self.pool.clone(), id).await?)
+    }
+
+    /// Plays the source of a bookmark from its position. Returns false when
+    /// there is no such bookmark.
+    pub async fn play_bookmark(&self, id: &str) -> Result<bool, Error> {
+        let bookmark = match repo::bookmark::find(self.pool.clone(), id).await? {
+            Some(bookmark) => bookmark,
+            None => return Ok(false),
+        };
+        let source = bookmark.source.parse().map_err(Error::msg)?;
+        let tracks = match source {
+            BookmarkSource::Track => vec![bookmark.path.clone()],
+            BookmarkSource::Album => {
+                repo::album_tracks::find_by_album(self.pool.clone(), &bookmark.source_id)
+                    .await?
+                    .into_iter()
+                    .map(|track| track.path)
+                    .collect()
+            }
+            BookmarkSource::Directory => match parent_directory(&bookmark.path) {
+                Some(parent) if parent == bookmark.source_id => list_directory(&parent)?,
+                // saved while the directory played with its subdirectories
+                _ => read_files(bookmark.source_id.clone()).await?,
+            },
+        };
+        let position = tracks
+            .iter()
+            .position(|path| *path == bookmark.path)
+            .ok_or_else(|| anyhow!("The track of the bookmark is missing"))?;
+
+        let directory = match source {
+            BookmarkSource::Directory => Some(bookmark.source_id.as_str()),
+            _ => None,
+        };
+        self.play_queue(
+            tracks,
+            false,
+            Some(position as i32),
+            bookmark.elapsed.max(0) as u64,
+            directory,
+        )
+        .await?;
+        Ok(true)
+    }
+
+    /// The chapters of an audiobook, none for the other files. `length` is
+    /// the length of the track in milliseconds.
+    pub fn chapters(&self, path: &str, length: u64) -> Result<Vec<Chapter>, Error> {
+        chapters::read(path, length)
+    }
+
+    /// Returns `None` when there is no such track.
+    pub async fn track_chapters(&self, id: &str) -> Result<Option<Vec<Chapter>>, Error> {
+        match repo::track::find(self.pool.clone(), id).await? {
+            Some(track) => Ok(Some(self.chapters(&track.path, track.length as u64)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// The chapters of the track playing.
+    pub async fn current_chapters(&self) -> Result<Vec<Chapter>, Error> {
+        match self.current_track().await? {
+            Some(track) => self.chapters(&track.path, track.length),
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Seeks to the start of a chapter of the track playing. Returns false
+    /// when it has no such chapter.
+    pub async fn seek_chapter(&self, index: usize) -> Result<bool, Error> {
+        match self.current_chapters().await?.get(index) {
+            Some(chapter) => {
+                self.ff_rewind(chapter.start as i32).await?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+/// The directory played when `path` is one of its tracks, else its folder.
+fn resume_directory(path: &str) -> Option<String> {
+    let played = PLAYED_DIRECTORY.lock().unwrap().clone();
+    played
+        .filter(|directory| Path::new(path).starts_with(directory))
+        .or_else(|| parent_directory(path))
+}
+
+fn parent_directory(path: &str) -> Option<String> {
+    Path::new(path)
+        .parent()
+        .map(|parent| parent.to_string_lossy().to_string())
+}
+
+fn into_bookmark(bookmark: entity::bookmark::Bookmark) -> Bookmark {
+    Bookmark {
+        id: bookmark.id,
+        name: bookmark.name,
+        source: bookmark.source.parse().unwrap_or_default(),
+        source_id: bookmark.source_id,
+        path: bookmark.path,
+        track_id: bookmark.track_id,
+        elapsed: bookmark.elapsed.max(0) as u64,
+        created_at: bookmark.created_at.timestamp(),
+        updated_at: bookmark.updated_at.timestamp(),
+    }
+}
@@ -0,0 +1,154 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Timelike};
+
+/// Days searched for the next run, a schedule on February 29 runs at least
+/// once in four years.
+const LOOKAHEAD_DAYS: i64 = 4 * 366;
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAYS: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// A cron expression of five fields, `minute hour day-of-month month
+/// day-of-week`, in local time. Fields take `*`, values, ranges, steps and
+/// lists like `*/15`, `1-5` or `mon,wed,fri`, `@hourly`, `@daily`,
+/// `@weekly`, `@monthly` and `@yearly` are accepted too.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cron {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Like in crontab, when both days are restricted either of them
+    /// matching is enough.
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl FromStr for Cron {
+    type Err = Error;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let expression = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            expression => expression,
+        };
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(anyhow!(
+                "Cron expression must have 5 fields: {}",
+                expression
+            ));
+        }
+        let weekdays = parse_field(fields[4], 0, 7, &WEEKDAYS)?;
+        Ok(Self {
+            minutes: parse_field(fields[0], 0, 59, &[])?,
+            hours: parse_field(fields[1], 0, 23, &[])?,
+            days: parse_field(fields[2], 1, 31, &[])?,
+            months: parse_field(fields[3], 1, 12, &MONTHS)?,
+            // 7 is sunday too
+            weekdays: (weekdays | weekdays >> 7) & 0x7f,
+            any_day: fields[2].starts_with('*'),
+            any_weekday: fields[4].starts_with('*'),
+        })
+    }
+}
+
+impl Cron {
+    pub fn matches(&self, time: &DateTime<Local>) -> bool {
+        has(self.minutes, time.minute())
+            && has(self.hours, time.hour())
+            && has(self.months, time.month())
+            && self.matches_day(time.date_naive())
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day = has(self.days, date.day());
+        let weekday = has(self.weekdays, date.weekday().num_days_from_sunday());
+        match self.any_day || self.any_weekday {
+            true => day && weekday,
+            false => day || weekday,
+        }
+    }
+
+    /// The first time matching after `time`, `None` when the expression
+    /// never matches, e.g. on February 30.
+    pub fn next_after(&self, time: &DateTime<Local>) -> Option<DateTime<Local>> {
+        let start = time.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        for offset in 0..LOOKAHEAD_DAYS {
+            let date = start.date_naive() + Duration::days(offset);
+            if !has(self.months, date.month()) || !self.matches_day(date) {
+                continue;
+            }
+            for hour in (0..24).filter(|hour| has(self.hours, *hour)) {
+                for minute in (0..60).filter(|minute| has(self.minutes, *minute)) {
+                    let naive = date.and_hms_opt(hour, minute, 0)?;
+                    // times skipped by daylight saving don't exist
+                    let next = match Local.from_local_datetime(&naive).earliest() {
+                        Some(next) => next,
+                        None => continue,
+                    };
+                    if next >= start {
+                        return Some(next);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+fn has(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, Error> {
+    let mut mask = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<usize>()?),
+            None => (part, 1),
+        };
+        if step == 0 {
+            return Err(anyhow!("Invalid step in cron field: {}", field));
+        }
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((start, end)) => (
+                parse_value(start, min, names)?,
+                parse_value(end, min, names)?,
+            ),
+            // `5/15` goes from 5 to the end
+            None if part.contains('/') => (parse_value(range, min, names)?, max),
+            None => {
+                let value = parse_value(range, min, names)?;
+                (value, value)
+            }
+        };
+        if start < min || end > max || start > end {
+            return Err(anyhow!("Value out of range in cron field: {}", field));
+        }
+        for value in (start..=end).step_by(step) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+fn parse_value(value: &str, min: u32, names: &[&str]) -> Result<u32, Error> {
+    let name = value.to_lowercase();
+    match names.iter().position(|n| *n == name) {
+        Some(index) => Ok(min + index as u32),
+        None => value
+            .parse()
+            .map_err(|_| anyhow!("Invalid value in cron field: {}", value)),
+    }
+}
@@ -0,0 +1,101 @@
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, Error};
+use futures::future::BoxFuture;
+use rockbox_traits::Player;
+use rockbox_types::device::Device;
+
+use crate::{
+    events::{self, EventKind},
+    RockboxService, GLOBAL_MUTEX,
+};
+
+/// Opens a connection to a device of the network. Cast and remote Rockbox
+/// players live in crates depending on this one, the server registers the
+/// connector at startup.
+pub type Connector =
+    fn(Device) -> BoxFuture<'static, Result<Option<Box<dyn Player + Send>>, Error>>;
+
+static CONNECTOR: OnceLock<Connector> = OnceLock::new();
+
+pub fn register_connector(connector: Connector) {
+    let _ = CONNECTOR.set(connector);
+}
+
+pub async fn connect_player(device: Device) -> Result<Option<Box<dyn Player + Send>>, Error> {
+    match CONNECTOR.get() {
+        Some(connect) => connect(device).await,
+        None => Err(anyhow!("No device connector registered")),
+    }
+}
+
+impl RockboxService {
+    pub fn devices(&self) -> Vec<Device> {
+        self.devices.lock().unwrap().clone()
+    }
+
+    /// Finds a device by id, `current` is the device playback is sent to.
+    pub fn device(&self, id: &str) -> Option<Device> {
+        match id {
+            "current" => self.current_device.lock().unwrap().clone(),
+            _ => self
+                .devices
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|d| d.id == id)
+                .cloned(),
+        }
+    }
+
+    /// Other Rockbox instances found on the network, their libraries are
+    /// merged into ours.
+    pub fn remote_sources(&self) -> Vec<Device> {
+        let devices = self.devices.lock().unwrap();
+        devices
+            .iter()
+            .filter(|d| d.app == "rockbox" && !d.is_current_device)
+            .cloned()
+            .collect()
+    }
+
+    /// Sends playback to a device, returns false when the device is unknown.
+    pub async fn connect(&self, id: &str) -> Result<bool, Error> {
+        let device = match self.device(id) {
+            Some(device) => device,
+            None => return Ok(false),
+        };
+        let mut player = self.player.lock().await;
+        *GLOBAL_MUTEX.lock().unwrap() = 1;
+        *player = connect_player(device.clone()).await?;
+        *self.current_device.lock().unwrap() = Some(device.clone());
+        if let Err(e) = self
+            .restore_device_eq_preset()
+            .and_then(|_| self.apply_device_eq_preset(&device.id))
+        {
+            eprintln!(
+                "Error loading the equalizer preset of {}: {}",
+                device.name, e
+            );
+        }
+        events::publish(EventKind::DeviceConnected(device));
+        Ok(true)
+    }
+
+    /// Stops the device playback is sent to and plays locally again.
+    pub async fn disconnect(&self) -> Result<(), Error> {
+        let mut player = self.player.lock().await;
+        if let Some(player) = player.as_mut() {
+            player.stop().await?;
+            player.disconnect().await?;
+        }
+        *GLOBAL_MUTEX.lock().unwrap() = 0;
+        *player = None;
+        *self.current_device.lock().unwrap() = None;
+        if let Err(e) = self.restore_device_eq_preset() {
+            eprintln!("Error restoring the equalizer: {}", e);
+        }
+        events::publish(EventKind::DeviceDisconnected);
+        Ok(())
+    }
+}
@@ -0,0 +1,482 @@
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use anyhow::{anyhow, Error};
+use lazy_static::lazy_static;
+use rockbox_sys::{self as rb, types::user_settings};
+use rockbox_types::{
+    dsp::{
+        EqComparison, EqPreset, EqPresets, EqSlot, Equalizer, UpdateEqBand, UpdateEqualizer,
+        EQ_CUTOFF_MAX, EQ_CUTOFF_MIN, EQ_GAIN_MAX, EQ_GAIN_MIN, EQ_NUM_BANDS, EQ_PRECUT_MAX,
+        EQ_Q_MAX, EQ_Q_MIN,
+    },
+    EqBandSetting,
+};
+
+use crate::{
+    events::{self, EventKind},
+    firmware, RockboxService,
+};
+
+/// `settings.toml` is written once the equalizer has not changed for this
+/// long, dragging a slider applies dozens of changes per second.
+const WRITE_DELAY: Duration = Duration::from_secs(2);
+
+static WRITE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+lazy_static! {
+    static ref COMPARISON: Mutex<Option<EqComparison>> = Mutex::new(None);
+    /// The equalizer to restore when disconnecting from a device with a
+    /// preset.
+    static ref BEFORE_DEVICE: Mutex<Option<Equalizer>> = Mutex::new(None);
+}
+
+impl RockboxService {
+    pub fn equalizer(&self) -> Equalizer {
+        firmware(read_equalizer)
+    }
+
+    /// Applies every band of the equalizer, missing bands are left untouched.
+    pub fn set_equalizer(&self, equalizer: Equalizer) -> Result<Equalizer, Error> {
+        let equalizer = firmware(|| {
+            apply_enabled(equalizer.enabled);
+            apply_precut(equalizer.precut);
+            for (band, setting) in equalizer.bands.into_iter().take(EQ_NUM_BANDS).enumerate() {
+                apply_band(band, setting);
+            }
+            read_equalizer()
+        });
+        changed(&equalizer);
+        Ok(equalizer)
+    }
+
+    pub fn update_equalizer(&self, update: UpdateEqualizer) -> Result<Equalizer, Error> {
+        let equalizer = firmware(|| {
+            if let Some(enabled) = update.enabled {
+                apply_enabled(enabled);
+            }
+            if let Some(precut) = update.precut {
+                apply_precut(precut);
+            }
+            for (band, setting) in update
+                .bands
+                .unwrap_or_default()
+                .into_iter()
+                .take(EQ_NUM_BANDS)
+                .enumerate()
+            {
+                apply_band(band, setting);
+            }
+            read_equalizer()
+        });
+        changed(&equalizer);
+        Ok(equalizer)
+    }
+
+    pub fn eq_band(&self, band: usize) -> Option<EqBandSetting> {
+        self.equalizer().bands.into_iter().nth(band)
+    }
+
+    /// Changes a band of the running equalizer, the new coefficients are
+    /// heard right away.
+    pub fn set_eq_band(&self, band: usize, update: UpdateEqBand) -> Result<EqBandSetting, Error> {
+        if band >= EQ_NUM_BANDS {
+            return Err(anyhow!("Band {} does not exist", band));
+        }
+        let equalizer = firmware(|| {
+            let current = read_equalizer().bands.swap_remove(band);
+            apply_band(
+                band,
+                EqBandSetting {
+                    cutoff: update.cutoff.unwrap_or(current.cutoff),
+                    q: update.q.unwrap_or(current.q),
+                    gain: update.gain.unwrap_or(current.gain),
+                },
+            );
+            read_equalizer()
+        });
+        let setting = equalizer.bands[band].clone();
+        changed(&equalizer);
+        Ok(setting)
+    }
+
+    pub fn eq_presets(&self) -> Result<Vec<EqPreset>, Error> {
+        Ok(load_presets()?.presets)
+    }
+
+    pub fn eq_preset(&self, name: &str) -> Result<Option<EqPreset>, Error> {
+        Ok(load_presets()?.presets.into_iter().find(|p| p.name == name))
+    }
+
+    /// Saves the equalizer as a preset, replacing the preset with the same
+    /// name. The running equalizer is saved when `equalizer` is `None`.
+    pub fn save_eq_preset(
+        &self,
+        name: &str,
+        equalizer: Option<Equalizer>,
+    ) -> Result<EqPreset, Error> {
+        let preset = EqPreset {
+            name: name.to_string(),
+            equalizer: equalizer.unwrap_or_else(|| self.equalizer()),
+        };
+        let mut presets = load_presets()?;
+        match presets.presets.iter_mut().find(|p| p.name == name) {
+            Some(existing) => *existing = preset.clone(),
+            None => presets.presets.push(preset.clone()),
+        }
+        save_presets(&presets)?;
+        Ok(preset)
+    }
+
+    /// Saves an AutoEQ or Equalizer APO parametric profile as a preset.
+    pub fn import_eq_preset(&self, name: &str, profile: &str) -> Result<EqPreset, Error> {
+        let equalizer = parse_parametric_eq(profile)?;
+        self.save_eq_preset(name, Some(equalizer))
+    }
+
+    /// Deletes a preset and unbinds it from the devices, returns false when
+    /// it does not exist.
+    pub fn delete_eq_preset(&self, name: &str) -> Result<bool, Error> {
+        let mut presets = load_presets()?;
+        let len = presets.presets.len();
+        presets.presets.retain(|p| p.name != name);
+        if presets.presets.len() == len {
+            return Ok(false);
+        }
+        presets.devices.retain(|_, preset| preset != name);
+        save_presets(&presets)?;
+        Ok(true)
+    }
+
+    /// Applies a preset, returns `None` when it does not exist.
+    pub fn load_eq_preset(&self, name: &str) -> Result<Option<Equalizer>, Error> {
+        match self.eq_preset(name)? {
+            Some(preset) => self.set_equalizer(preset.equalizer).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// The preset of each device id.
+    pub fn eq_device_presets(&self) -> Result<HashMap<String, String>, Error> {
+        Ok(load_presets()?.devices)
+    }
+
+    /// Loads the preset whenever playback is sent to the device, or stops
+    /// doing so when `preset` is `None`.
+    pub fn bind_eq_preset(&self, device_id: &str, preset: Option<&str>) -> Result<(), Error> {
+        let mut presets = load_presets()?;
+        match preset {
+            Some(preset) => {
+                if !presets.presets.iter().any(|p| p.name == preset) {
+                    return Err(anyhow!("Preset {} does not exist", preset));
+                }
+                presets
+                    .devices
+                    .insert(device_id.to_string(), preset.to_string());
+            }
+            None => {
+                presets.devices.remove(device_id);
+            }
+        }
+        save_presets(&presets)
+    }
+
+    /// Loads the preset bound to the device playback was just sent to.
+    pub(crate) fn apply_device_eq_preset(&self, device_id: &str) -> Result<(), Error> {
+        let presets = load_presets()?;
+        let Some(name) = presets.devices.get(device_id) else {
+            return Ok(());
+        };
+        let Some(preset) = presets.presets.iter().find(|p| &p.name == name) else {
+            return Ok(());
+        };
+        let mut before = BEFORE_DEVICE.lock().unwrap();
+        if before.is_none() {
+            *before = Some(self.equalizer());
+        }
+        self.set_equalizer(preset.equalizer.clone())?;
+        Ok(())
+    }
+
+    /// Restores the equalizer replaced by the preset of a device.
+    pub(crate) fn restore_device_eq_preset(&self) -> Result<(), Error> {
+        let before = BEFORE_DEVICE.lock().unwrap().take();
+        if let Some(equalizer) = before {
+            self.set_equalizer(equalizer)?;
+        }
+        Ok(())
+    }
+
+    /// The two equalizers being compared, B starts as the equalizer
+    /// disabled to compare it with no equalization at all.
+    pub fn eq_comparison(&self) -> EqComparison {
+        let mut comparison = COMPARISON.lock().unwrap();
+        let comparison = comparison.get_or_insert_with(|| {
+            let a = self.equalizer();
+            let b = Equalizer {
+                enabled: false,
+                ..a.clone()
+            };
+            EqComparison {
+                slot: EqSlot::A,
+                a,
+                b,
+            }
+        });
+        // changes made since are part of the side being heard
+        let equalizer = self.equalizer();
+        match comparison.slot {
+            EqSlot::A => comparison.a = equalizer,
+            EqSlot::B => comparison.b = equalizer,
+        }
+        comparison.clone()
+    }
+
+    /// Replaces a side of the comparison, it is applied right away when it
+    /// is the side being heard.
+    pub fn set_eq_slot(&self, slot: EqSlot, equalizer: Equalizer) -> Result<EqComparison, Error> {
+        let mut comparison = self.eq_comparison();
+        match slot {
+            EqSlot::A => comparison.a = equalizer.clone(),
+            EqSlot::B => comparison.b = equalizer.clone(),
+        }
+        if comparison.slot == slot {
+            self.set_equalizer(equalizer)?;
+        }
+        *COMPARISON.lock().unwrap() = Some(comparison.clone());
+        Ok(comparison)
+    }
+
+    /// Switches to the other side of the comparison.
+    pub fn toggle_eq_slot(&self) -> Result<EqComparison, Error> {
+        let mut comparison = self.eq_comparison();
+        let equalizer = match comparison.slot {
+            EqSlot::A => {
+                comparison.slot = EqSlot::B;
+                comparison.b.clone()
+            }
+            EqSlot::B => {
+                comparison.slot = EqSlot::A;
+                comparison.a.clone()
+            }
+        };
+        self.set_equalizer(equalizer)?;
+        *COMPARISON.lock().unwrap() = Some(comparison.clone());
+        Ok(comparison)
+    }
+}
+
+fn read_equalizer() -> Equalizer {
+    let settings = rb::settings::get_global_settings();
+    Equalizer {
+        enabled: settings.eq_enabled,
+        precut: settings.eq_precut as i32,
+        bands: settings
+            .eq_band_settings
+            .into_iter()
+            .map(|band| EqBandSetting {
+                cutoff: band.cutoff,
+                q: band.q,
+                gain: band.gain,
+            })
+            .collect(),
+    }
+}
+
+fn apply_enabled(enabled: bool) {
+    unsafe {
+        rb::global_settings.eq_enabled = enabled;
+    }
+    rb::sound::pcmbuf_set_low_latency(true);
+    rb::sound::dsp::eq_enable(enabled);
+    rb::sound::pcmbuf_set_low_latency(false);
+}
+
+fn apply_precut(precut: i32) {
+    let precut = precut.clamp(0, EQ_PRECUT_MAX);
+    unsafe {
+        rb::global_settings.eq_precut = precut as u32;
+    }
+    rb::sound::dsp::set_eq_precut(precut);
+}
+
+fn apply_band(band: usize, setting: EqBandSetting) {
+    let setting = user_settings::EqBandSetting {
+        cutoff: setting.cutoff.clamp(EQ_CUTOFF_MIN, EQ_CUTOFF_MAX),
+        q: setting.q.clamp(EQ_Q_MIN, EQ_Q_MAX),
+        gain: setting.gain.clamp(EQ_GAIN_MIN, EQ_GAIN_MAX),
+    };
+    unsafe {
+        rb::global_settings.eq_band_settings[band] = setting.clone().into();
+    }
+    rb::sound::dsp::set_eq_coefs(band as i32, setting);
+}
+
+/// Publishes the new equalizer and saves it once the changes stop.
+fn changed(equalizer: &Equalizer) {
+    events::publish(EventKind::EqualizerChanged(equalizer.clone()));
+
+    let generation = WRITE_GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+    thread::spawn(move || {
+        thread::sleep(WRITE_DELAY);
+        if WRITE_GENERATION.load(Ordering::Relaxed) != generation {
+            return;
+        }
+        if let Err(e) = firmware(rockbox_settings::write_settings) {
+            eprintln!("Error saving the equalizer: {}", e);
+        }
+    });
+}
+
+fn presets_path() -> Result<String, Error> {
+    let home = env::var("HOME")?;
+    Ok(format!("{}/.config/rockbox.org/eq-presets.json", home))
+}
+
+fn load_presets() -> Result<EqPresets, Error> {
+    let path = presets_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => Ok(serde_json::from_str(&content)?),
+        Err(_) => Ok(EqPresets::default()),
+    }
+}
+
+fn save_presets(presets: &EqPresets) -> Result<(), Error> {
+    let path = presets_path()?;
+    fs::create_dir_all(Path::new(&path).parent().unwrap())?;
+    fs::write(&path, serde_json::to_string_pretty(presets)?)?;
+    Ok(())
+}
+
+#[derive(PartialEq)]
+enum FilterType {
+    LowShelf,
+    Peaking,
+    HighShelf,
+}
+
+struct Filter {
+    r#type: FilterType,
+    cutoff: f32,
+    gain: f32,
+    q: f32,
+}
+
+/// Reads an Equalizer APO parametric profile, the format of the AutoEQ
+/// `ParametricEQ.txt` files:
+///
+/// ```text
+/// Preamp: -6.2 dB
+/// Filter 1: ON LSC Fc 105 Hz Gain 5.5 dB Q 0.71
+/// Filter 2: ON PK Fc 210 Hz Gain -2.1 dB Q 1.20
+/// Filter 10: ON HSC Fc 10000 Hz Gain -1.5 dB Q 0.71
+/// ```
+///
+/// The first band of the firmware is a low shelf and the last one a high
+/// shelf, only the first shelf of each kind is kept and the 8 peaking
+/// filters with the largest gains when there are more.
+pub fn parse_parametric_eq(profile: &str) -> Result<Equalizer, Error> {
+    let mut preamp = 0.0;
+    let mut filters = vec![];
+
+    for line in profile.lines() {
+        let line = line.trim();
+        if let Some(value) = line.strip_prefix("Preamp:") {
+            preamp = parse_number(value.split_whitespace().next())?;
+            continue;
+        }
+        if !line.starts_with("Filter") {
+            continue;
+        }
+        let Some((_, filter)) = line.split_once(':') else {
+            continue;
+        };
+        let tokens: Vec<&str> = filter.split_whitespace().collect();
+        if tokens.first() != Some(&"ON") {
+            continue;
+        }
+        let r#type = match tokens.get(1).copied() {
+            Some("PK") | Some("PEQ") => FilterType::Peaking,
+            Some("LS") | Some("LSC") | Some("LSQ") => FilterType::LowShelf,
+            Some("HS") | Some("HSC") | Some("HSQ") => FilterType::HighShelf,
+            _ => continue,
+        };
+        let value = |name: &str| {
+            tokens
+                .iter()
+                .position(|token| *token == name)
+                .and_then(|i| tokens.get(i + 1).copied())
+        };
+        filters.push(Filter {
+            r#type,
+            cutoff: parse_number(value("Fc"))?,
+            gain: parse_number(value("Gain"))?,
+            // shelves exported without Q use the usual 0.71
+            q: value("Q")
+                .map(|q| parse_number(Some(q)))
+                .unwrap_or(Ok(0.71))?,
+        });
+    }
+
+    if filters.is_empty() {
+        return Err(anyhow!("No parametric filter found in the profile"));
+    }
+
+    let band = |filter: &Filter| EqBandSetting {
+        cutoff: filter.cutoff.round() as i32,
+        q: (filter.q * 10.0).round() as i32,
+        gain: (filter.gain * 10.0).round() as i32,
+    };
+    let flat = |cutoff: i32, q: i32| EqBandSetting { cutoff, q, gain: 0 };
+
+    let mut peaking: Vec<&Filter> = filters
+        .iter()
+        .filter(|f| f.r#type == FilterType::Peaking)
+        .collect();
+    peaking.sort_by(|a, b| b.gain.abs().total_cmp(&a.gain.abs()));
+    peaking.truncate(EQ_NUM_BANDS - 2);
+    peaking.sort_by(|a, b| a.cutoff.total_cmp(&b.cutoff));
+
+    let mut bands = vec![filters
+        .iter()
+        .find(|f| f.r#type == FilterType::LowShelf)
+        .map(band)
+        .unwrap_or(flat(32, 7))];
+    bands.extend(peaking.into_iter().map(band));
+    // unused peaking bands are flat, spread over the remaining octaves
+    let mut cutoff = bands.last().map(|b| b.cutoff).unwrap_or(32);
+    while bands.len() < EQ_NUM_BANDS - 1 {
+        cutoff = (cutoff * 2).min(EQ_CUTOFF_MAX);
+        bands.push(flat(cutoff, 10));
+    }
+    bands.push(
+        filters
+            .iter()
+            .find(|f| f.r#type == FilterType::HighShelf)
+            .map(band)
+            .unwrap_or(flat(16000, 7)),
+    );
+
+    Ok(Equalizer {
+        enabled: true,
+        // a negative preamp is a positive precut
+        precut: ((-preamp * 10.0).round() as i32).clamp(0, EQ_PRECUT_MAX),
+        bands,
+    })
+}
+
+fn parse_number(value: Option<&str>) -> Result<f32, Error> {
+    value
+        .ok_or_else(|| anyhow!("Missing value in the profile"))?
+        .parse::<f32>()
+        .map_err(|e| anyhow!("Invalid value in the profile: {}", e))
+}
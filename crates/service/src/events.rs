@@ -0,0 +1,221 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use lazy_static::lazy_static;
+use rockbox_sys::types::mp3_entry::Mp3Entry;
+use rockbox_types::{device::Device, dsp::Equalizer, scheduler::SleepTimer};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+/// Events a slow subscriber can lag behind before missing some, and events
+/// kept for clients resuming after a disconnection.
+const CAPACITY: usize = 1024;
+
+lazy_static! {
+    static ref BUS: Mutex<Bus> = Mutex::new(Bus::new());
+}
+
+static QUEUE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+struct Bus {
+    seq: u64,
+    sender: broadcast::Sender<Event>,
+    history: VecDeque<Event>,
+}
+
+impl Bus {
+    fn new() -> Self {
+        let (sender, _) = broadcast::channel(CAPACITY);
+        Self {
+            seq: 0,
+            sender,
+            history: VecDeque::with_capacity(CAPACITY),
+        }
+    }
+}
+
+/// A change of the player state, numbered in the order it happened.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Event {
+    /// Starts at 1 and increases by one with each event.
+    pub seq: u64,
+    /// Milliseconds since the unix epoch.
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub kind: EventKind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum EventKind {
+    /// 0 when stopped, 1 when playing and 3 when paused.
+    StatusChanged {
+        status: i32,
+    },
+    TrackChanged {
+        index: i32,
+        track: Option<Box<Mp3Entry>>,
+    },
+    /// The elapsed time jumped, in milliseconds.
+    Seeked {
+        elapsed: u64,
+    },
+    QueueChanged {
+        amount: i32,
+        diff: QueueDiff,
+    },
+    VolumeChanged {
+        volume: i32,
+    },
+    SettingsChanged(PlaybackSettings),
+    EqualizerChanged(Equalizer),
+    LibraryUpdated {
+        path: String,
+    },
+    DeviceFound(Device),
+    DeviceConnected(Device),
+    DeviceDisconnected,
+    SleepTimerChanged(SleepTimer),
+    /// A schedule started playing.
+    ScheduleTriggered {
+        id: String,
+        name: String,
+    },
+}
+
+impl EventKind {
+    /// The topic clients filter events with: `playback`, `queue`, `volume`,
+    /// `settings`, `library`, `device` or `scheduler`.
+    pub fn topic(&self) -> &'static str {
+        match self {
+            EventKind::StatusChanged { .. }
+            | EventKind::TrackChanged { .. }
+            | EventKind::Seeked { .. } => "playback",
+            EventKind::QueueChanged { .. } => "queue",
+            EventKind::VolumeChanged { .. } => "volume",
+            EventKind::SettingsChanged(_) | EventKind::EqualizerChanged(_) => "settings",
+            EventKind::LibraryUpdated { .. } => "library",
+            EventKind::DeviceFound(_)
+            | EventKind::DeviceConnected(_)
+            | EventKind::DeviceDisconnected => "device",
+            EventKind::SleepTimerChanged(_) | EventKind::ScheduleTriggered { .. } => "scheduler",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EventKind::StatusChanged { .. } => "StatusChanged",
+            EventKind::TrackChanged { .. } => "TrackChanged",
+            EventKind::Seeked { .. } => "Seeked",
+            EventKind::QueueChanged { .. } => "QueueChanged",
+            EventKind::VolumeChanged { .. } => "VolumeChanged",
+            EventKind::SettingsChanged(_) => "SettingsChanged",
+            EventKind::EqualizerChanged(_) => "EqualizerChanged",
+            EventKind::LibraryUpdated { .. } => "LibraryUpdated",
+            EventKind::DeviceFound(_) => "DeviceFound",
+            EventKind::DeviceConnected(_) => "DeviceConnected",
+            EventKind::DeviceDisconnected => "DeviceDisconnected",
+            EventKind::SleepTimerChanged(_) => "SleepTimerChanged",
+            EventKind::ScheduleTriggered { .. } => "ScheduleTriggered",
+        }
+    }
+}
+
+/// Turns the previous queue into the new one like [`Vec::splice`] would,
+/// `removed` paths at `start` are replaced with `inserted`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QueueDiff {
+    pub start: usize,
+    pub removed: usize,
+    pub inserted: Vec<String>,
+}
+
+impl QueueDiff {
+    /// Keeps the paths both queues start and end with, `None` when they
+    /// are the same.
+    pub fn between(old: &[String], new: &[String]) -> Option<Self> {
+        let start = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+        if start == old.len() && start == new.len() {
+            return None;
+        }
+        let end = old[start..]
+            .iter()
+            .rev()
+            .zip(new[start..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        Some(Self {
+            start,
+            removed: old.len() - start - end,
+            inserted: new[start..new.len() - end].to_vec(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlaybackSettings {
+    pub playlist_shuffle: bool,
+    pub repeat_mode: i32,
+    pub pitch: i32,
+    pub timestretch: i32,
+}
+
+/// Numbers the event and sends it to every subscriber.
+pub fn publish(kind: EventKind) -> Event {
+    let mut bus = BUS.lock().unwrap();
+    bus.seq += 1;
+    let event = Event {
+        seq: bus.seq,
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64,
+        kind,
+    };
+    if bus.history.len() == CAPACITY {
+        bus.history.pop_front();
+    }
+    bus.history.push_back(event.clone());
+    // no subscribers is not an error
+    let _ = bus.sender.send(event.clone());
+    event
+}
+
+pub fn subscribe() -> broadcast::Receiver<Event> {
+    BUS.lock().unwrap().sender.subscribe()
+}
+
+/// Subscribes and returns the kept events published after `last_seq`, none
+/// of them are missed or received twice. Returns `None` instead of the
+/// events when some of them are not kept anymore.
+pub fn subscribe_since(last_seq: u64) -> (Option<Vec<Event>>, broadcast::Receiver<Event>) {
+    let bus = BUS.lock().unwrap();
+    let receiver = bus.sender.subscribe();
+    let missed = bus.seq.saturating_sub(last_seq) as usize;
+    if missed > bus.history.len() {
+        return (None, receiver);
+    }
+    let events = bus
+        .history
+        .iter()
+        .skip(bus.history.len() - missed)
+        .cloned()
+        .collect();
+    (Some(events), receiver)
+}
+
+/// Marks the queue as modified so the broker reads it again, changes made
+/// between two of its checks can leave the amount of tracks untouched.
+pub fn queue_modified() {
+    QUEUE_GENERATION.fetch_add(1, Ordering::Relaxed);
+}
+
+pub fn queue_generation() -> u64 {
+    QUEUE_GENERATION.load(Ordering::Relaxed)
+}
@@ -0,0 +1,149 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Error;
+use futures::{future::BoxFuture, stream::FuturesUnordered, StreamExt};
+use kv::{build_tracks_kv, KV};
+use lazy_static::lazy_static;
+use rockbox_library::entity::track::Track;
+use rockbox_search::{create_indexes, Indexes};
+use rockbox_sys::types::{mp3_entry::Mp3Entry, tree::Entry};
+use rockbox_traits::Player;
+use rockbox_types::device::Device;
+use sqlx::{Pool, Sqlite};
+use tokio::{fs, sync::OnceCell};
+
+pub mod bookmarks;
+pub mod cron;
+pub mod devices;
+pub mod dsp;
+pub mod events;
+pub mod kv;
+pub mod library;
+pub mod playback;
+pub mod playlist;
+pub mod podcasts;
+pub mod radio;
+pub mod remote;
+pub mod scheduler;
+pub mod settings;
+pub mod speed;
+pub mod system;
+pub mod transitions;
+
+pub const AUDIO_EXTENSIONS: [&str; 17] = [
+    "mp3", "ogg", "flac", "m4a", "aac", "mp4", "alac", "wav", "wv", "mpc", "aiff", "ac3", "opus",
+    "spx", "sid", "ape", "wma",
+];
+
+lazy_static! {
+    pub static ref GLOBAL_MUTEX: Mutex<i32> = Mutex::new(0);
+    pub static ref PLAYER_MUTEX: Mutex<i32> = Mutex::new(0);
+}
+
+static SERVICE: OnceCell<RockboxService> = OnceCell::const_new();
+
+/// The player, library and devices of this Rockbox instance. The REST, gRPC,
+/// GraphQL and MPD servers run in the same process and all share the same
+/// service, see [`instance`].
+#[derive(Clone)]
+pub struct RockboxService {
+    pub pool: Pool<Sqlite>,
+    pub indexes: Indexes,
+    pub fs_cache: Arc<tokio::sync::Mutex<HashMap<String, Vec<Entry>>>>,
+    pub metadata_cache: Arc<tokio::sync::Mutex<HashMap<String, Mp3Entry>>>,
+    pub devices: Arc<Mutex<Vec<Device>>>,
+    pub current_device: Arc<Mutex<Option<Device>>>,
+    /// The device playback is sent to, `None` when playing locally.
+    pub player: Arc<tokio::sync::Mutex<Option<Box<dyn Player + Send>>>>,
+    pub kv: Arc<Mutex<KV<Track>>>,
+    /// The tracks of remote libraries by path, see [`remote`].
+    pub remote_tracks: Arc<Mutex<KV<Track>>>,
+}
+
+impl RockboxService {
+    pub async fn new() -> Result<Self, Error> {
+        let pool = rockbox_library::create_connection_pool().await?;
+        let kv = build_tracks_kv(pool.clone()).await?;
+        if let Err(e) = rockbox_library::replaygain::load_stored(pool.clone()).await {
+            eprintln!("Error loading the measured ReplayGain values: {}", e);
+        }
+        let service = Self {
+            pool,
+            indexes: create_indexes()?,
+            fs_cache: Arc::new(tokio::sync::Mutex::new(HashMap::new())),
+            metadata_cache: Arc::new(tokio::sync::Mutex::new(HashMap::new())),
+            devices: Arc::new(Mutex::new(Vec::new())),
+            current_device: Arc::new(Mutex::new(None)),
+            player: Arc::new(tokio::sync::Mutex::new(None)),
+            kv: Arc::new(Mutex::new(kv)),
+            remote_tracks: Arc::new(Mutex::new(KV::new())),
+        };
+        service.start_scheduler();
+        service.start_podcast_poller();
+        Ok(service)
+    }
+}
+
+/// The service of this process, created by the first server to start.
+pub async fn instance() -> Result<RockboxService, Error> {
+    SERVICE.get_or_try_init(RockboxService::new).await.cloned()
+}
+
+/// Runs `f` holding `PLAYER_MUTEX`, the firmware playback and playlist
+/// functions must not be called from several threads at once.
+pub(crate) fn firmware<T>(f: impl FnOnce() -> T) -> T {
+    let _player_mutex = PLAYER_MUTEX.lock().unwrap();
+    f()
+}
+
+pub fn read_files(path: String) -> BoxFuture<'static, Result<Vec<String>, Error>> {
+    Box::pin(async move {
+        let mut result = Vec::new();
+        let mut dir = fs::read_dir(path).await?;
+        let mut futures = FuturesUnordered::new();
+        while let Some(entry) = dir.next_entry().await? {
+            let path = entry.path();
+            if path.is_dir() {
+                let dir_path = path.clone();
+                futures.push(tokio::spawn(async move {
+                    read_files(dir_path.to_str().unwrap().to_string()).await
+                }));
+            } else if path.is_file() {
+                if !AUDIO_EXTENSIONS
+                    .into_iter()
+                    .any(|ext| path.to_str().unwrap().ends_with(&format!(".{}", ext)))
+                {
+                    continue;
+                }
+                result.push(path.to_str().unwrap().to_string());
+            }
+        }
+        while let Some(Ok(future)) = futures.next().await {
+            result.extend(future?);
+        }
+        Ok(result)
+    })
+}
+
+/// The audio files of a directory, without its subdirectories, sorted by
+/// path.
+pub fn list_directory(path: &str) -> Result<Vec<String>, Error> {
+    let mut tracks = vec![];
+    for file in std::fs::read_dir(path)? {
+        let file = file?;
+        let path = file.path().to_string_lossy().to_string();
+        if !file.metadata()?.is_file()
+            || !AUDIO_EXTENSIONS
+                .iter()
+                .any(|ext| path.ends_with(&format!(".{}", ext)))
+        {
+            continue;
+        }
+        tracks.push(path);
+    }
+    tracks.sort();
+    Ok(tracks)
+}
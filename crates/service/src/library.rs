@@ -0,0 +1,77 @@
+use std::{env, path::Path, thread};
+
+use anyhow::{anyhow, Error};
+use rockbox_library::{
+    audio_scan::scan_audio_files,
+    entity::replaygain::ReplayGain,
+    replaygain::{self, AnalysisOptions, AnalysisProgress},
+    repo,
+};
+use rockbox_search::{
+    indexer::{index_entities, reindex_library},
+    track::Track,
+};
+
+use crate::{
+    events::{self, EventKind},
+    RockboxService,
+};
+
+impl RockboxService {
+    /// Scans a directory, `~/Music` by default, and rebuilds the search
+    /// indexes. Scanning a subdirectory only indexes its tracks, their albums
+    /// and artists are indexed by the next scan of the whole library.
+    pub async fn scan_library(&self, path: Option<String>) -> Result<(), Error> {
+        let music_library = format!("{}/Music", env::var("HOME")?);
+        let path = path.unwrap_or_else(|| music_library.clone());
+        scan_audio_files(self.pool.clone(), path.clone().into()).await?;
+
+        if path != music_library {
+            let tracks: Vec<Track> = repo::track::all(self.pool.clone())
+                .await?
+                .into_iter()
+                .filter(|track| Path::new(&track.path).starts_with(&path))
+                .map(Into::into)
+                .collect();
+            let index = self.indexes.tracks.clone();
+            thread::spawn(move || {
+                if let Err(e) = index_entities(&index, &tracks, |_| {}) {
+                    eprintln!("Error indexing the scanned tracks: {}", e);
+                }
+            });
+        } else {
+            reindex_library(self.pool.clone(), self.indexes.clone()).await?;
+        }
+
+        events::publish(EventKind::LibraryUpdated { path });
+        Ok(())
+    }
+
+    /// Starts measuring the loudness of the tracks without ReplayGain tags
+    /// in the background, follow it with [`Self::replaygain_progress`].
+    pub fn analyze_replaygain(&self, options: AnalysisOptions) -> Result<(), Error> {
+        if replaygain::progress().running {
+            return Err(anyhow!("ReplayGain analysis already running"));
+        }
+        let pool = self.pool.clone();
+        thread::spawn(move || {
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            if let Err(e) = rt.block_on(replaygain::analyze_library(pool, options)) {
+                eprintln!("ReplayGain analysis failed: {}", e);
+            }
+        });
+        Ok(())
+    }
+
+    pub fn replaygain_progress(&self) -> AnalysisProgress {
+        replaygain::progress()
+    }
+
+    /// The values measured for a track, `None` when it was not analyzed.
+    pub async fn track_replaygain(&self, track_id: &str) -> Result<Option<ReplayGain>, Error> {
+        Ok(repo::replaygain::find_by_track(self.pool.clone(), track_id).await?)
+    }
+}
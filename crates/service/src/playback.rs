@@ -0,0 +1,434 @@
+use std::{env, path::Path};
+
+use anyhow::{anyhow, Error};
+use local_ip_addr::get_local_ip_address;
+use rand::seq::SliceRandom;
+use rockbox_library::repo;
+use rockbox_sys::{
+    self as rb,
+    types::{audio_status::AudioStatus, file_position::FilePosition, mp3_entry::Mp3Entry},
+};
+use rockbox_traits::types::track::Track;
+use rockbox_types::{bookmark::BookmarkSource, device::Device, PlaybackSpeed};
+
+use crate::{
+    bookmarks, devices::connect_player, firmware, list_directory, read_files, speed,
+    RockboxService, GLOBAL_MUTEX,
+};
+
+impl RockboxService {
+    pub async fn play(&self, elapsed: i64, offset: i64) -> Result<(), Error> {
+        if self.player.lock().await.is_none() {
+            firmware(|| rb::playback::play(elapsed, offset));
+        }
+        Ok(())
+    }
+
+    pub async fn pause(&self) -> Result<(), Error> {
+        let mut player = self.player.lock().await;
+        match player.as_mut() {
+            Some(player) => player.pause().await?,
+            None => firmware(rb::playback::pause),
+        }
+        Ok(())
+    }
+
+    pub async fn resume(&self) -> Result<(), Error> {
+        let mut player = self.player.lock().await;
+        match player.as_mut() {
+            Some(player) => player.play().await?,
+            None => firmware(rb::playback::resume),
+        }
+        Ok(())
+    }
+
+    /// Pauses while playing, resumes while paused and otherwise resumes the
+    /// last played track.
+    pub async fn play_or_pause(&self) -> Result<(), Error> {
+        match self.status().await?.status {
+            1 => self.pause().await,
+            3 => self.resume().await,
+            _ => {
+                if firmware(rb::system::get_global_status).resume_index > -1 {
+                    self.resume_track().await?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    pub async fn next(&self) -> Result<(), Error> {
+        let mut player = self.player.lock().await;
+        match player.as_mut() {
+            Some(player) => player.next().await?,
+            None => firmware(rb::playback::next),
+        }
+        Ok(())
+    }
+
+    pub async fn previous(&self) -> Result<(), Error> {
+        let mut player = self.player.lock().await;
+        match player.as_mut() {
+            Some(player) => player.previous().await?,
+            None => firmware(rb::playback::prev),
+        }
+        Ok(())
+    }
+
+    pub async fn ff_rewind(&self, newtime: i32) -> Result<(), Error> {
+        firmware(|| rb::playback::ff_rewind(newtime));
+        Ok(())
+    }
+
+    pub async fn hard_stop(&self) -> Result<(), Error> {
+        firmware(rb::playback::hard_stop);
+        Ok(())
+    }
+
+    pub async fn flush_and_reload_tracks(&self) -> Result<(), Error> {
+        firmware(rb::playback::flush_and_reload_tracks);
+        Ok(())
+    }
+
+    pub async fn status(&self) -> Result<AudioStatus, Error> {
+        let mut player = self.player.lock().await;
+        if let Some(player) = player.as_mut() {
+            let current_playback = player.get_current_playback().await?;
+            return Ok(AudioStatus {
+                status: match current_playback.is_playing {
+                    true => 1,
+                    false => 0,
+                },
+            });
+        }
+        Ok(firmware(rb::playback::status))
+    }
+
+    pub async fn current_track(&self) -> Result<Option<Mp3Entry>, Error> {
+        let mut player = self.player.lock().await;
+        if let Some(player) = player.as_mut() {
+            let current_playback = player.get_current_playback().await?;
+            let track: Option<Mp3Entry> = current_playback.current_track.map(|t| t.into());
+            return Ok(track.map(|mut t| {
+                t.elapsed = current_playback.position_ms as u64;
+                t
+            }));
+        }
+        drop(player);
+
+        let track = firmware(rb::playback::current_track);
+        self.with_library_ids(track).await
+    }
+
+    pub async fn next_track(&self) -> Result<Option<Mp3Entry>, Error> {
+        if self.player.lock().await.is_some() {
+            return Ok(None);
+        }
+        let track = firmware(rb::playback::next_track);
+        self.with_library_ids(track).await
+    }
+
+    /// Adds the ids and the cover of the library to a track of the firmware.
+    async fn with_library_ids(&self, track: Option<Mp3Entry>) -> Result<Option<Mp3Entry>, Error> {
+        let mut track = match track {
+            Some(track) => track,
+            None => return Ok(None),
+        };
+        let hash = format!("{:x}", md5::compute(track.path.as_bytes()));
+        if let Some(metadata) = repo::track::find_by_md5(self.pool.clone(), &hash).await? {
+            track.id = Some(metadata.id);
+            track.album_art = metadata.album_art;
+            track.album_id = Some(metadata.album_id);
+            track.artist_id = Some(metadata.artist_id);
+        }
+        Ok(Some(track))
+    }
+
+    pub async fn file_position(&self) -> Result<FilePosition, Error> {
+        Ok(firmware(rb::playback::get_file_pos))
+    }
+
+    pub async fn adjust_volume(&self, steps: i32) -> Result<(), Error> {
+        rb::sound::adjust_volume(steps);
+        Ok(())
+    }
+
+    pub async fn playback_speed(&self) -> Result<PlaybackSpeed, Error> {
+        Ok(PlaybackSpeed {
+            pitch: rb::sound::get_pitch(),
+            timestretch: rb::sound::dsp::get_timestretch(),
+            timestretch_available: rb::sound::dsp::timestretch_available(),
+        })
+    }
+
+    /// `speed` is in `PITCH_SPEED_100` units, 10000 being the normal speed.
+    pub async fn set_playback_speed(&self, speed: i32) -> Result<(), Error> {
+        // Keep the current pitch and stretch the audio when the dsp supports it,
+        // otherwise fall back to changing the pitch like a turntable would.
+        match rb::sound::dsp::timestretch_available() {
+            true => {
+                rb::sound::dsp::set_timestretch(speed::timestretch(speed, rb::sound::get_pitch()))
+            }
+            false => rb::sound::set_pitch(speed::pitch_for_speed(speed)),
+        }
+        Ok(())
+    }
+
+    pub fn current_player(&self) -> Device {
+        match self.current_device.lock().unwrap().as_ref() {
+            Some(device) => device.clone(),
+            None => Device {
+                name: "Rockbox (Default Player)".to_string(),
+                app: "default".to_string(),
+                service: "rockbox".to_string(),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Loads tracks on the connected device, returns false when playing
+    /// locally.
+    pub async fn load_tracks(&self, tracks: &[String], shuffle: bool) -> Result<bool, Error> {
+        let mut player = self.player.lock().await;
+        if player.is_none() {
+            return Ok(false);
+        }
+
+        let current_device = self.current_device.lock().unwrap().clone();
+        let device = current_device.and_then(|current| {
+            let devices = self.devices.lock().unwrap();
+            devices.iter().find(|d| d.id == current.id).cloned()
+        });
+        if let Some(device) = device {
+            *GLOBAL_MUTEX.lock().unwrap() = 1;
+            *player = connect_player(device.clone()).await?;
+            *self.current_device.lock().unwrap() = Some(device);
+        }
+
+        let mut tracks = self.cast_tracks(tracks);
+        if shuffle {
+            tracks.shuffle(&mut rand::thread_rng());
+        }
+
+        match player.as_mut() {
+            Some(player) => player.load_tracks(tracks, None).await?,
+            None => return Err(anyhow!("Failed to connect to the current device")),
+        }
+        Ok(true)
+    }
+
+    /// The tracks of the library as sent to cast devices, streamed from the
+    /// GraphQL server. Tracks of remote libraries are streamed from their
+    /// instance.
+    pub(crate) fn cast_tracks(&self, paths: &[String]) -> Vec<Track> {
+        let rockbox_addr =
+            env::var("ROCKBOX_ADDR").unwrap_or_else(|_| get_local_ip_address().unwrap());
+        let rockbox_port = env::var("ROCKBOX_GRAPHQL_PORT").unwrap_or_else(|_| "6062".to_string());
+        let tracks: Vec<_> = {
+            let kv = self.kv.lock().unwrap();
+            paths
+                .iter()
+                .filter_map(|path| kv.get(path).cloned().or_else(|| self.remote_track(path)))
+                .collect()
+        };
+        tracks
+            .into_iter()
+            .map(|track| {
+                let remote = track.path.starts_with("http");
+                Track {
+                    id: track.id.clone(),
+                    title: track.title.clone(),
+                    artist: track.artist.clone(),
+                    album: track.album.clone(),
+                    album_artist: Some(track.album_artist.clone()),
+                    artist_id: Some(track.artist_id.clone()),
+                    album_id: Some(track.album_id.clone()),
+                    // cast receivers show the cover full screen
+                    album_cover: match remote {
+                        true => track.album_art.clone(),
+                        false => track.album_art.as_ref().map(|_| {
+                            format!(
+                                "http://{}:{}/covers/{}?size=1024",
+                                rockbox_addr, rockbox_port, track.album_id
+                            )
+                        }),
+                    },
+                    track_number: track.track_number,
+                    path: track.path.clone(),
+                    uri: match remote {
+                        true => track.path.clone(),
+                        false => format!(
+                            "http://{}:{}/tracks/{}",
+                            rockbox_addr, rockbox_port, track.id
+                        ),
+                    },
+                    disc_number: track.disc_number,
+                    duration: Some(track.length as f32 / 1000.0),
+                }
+            })
+            .collect()
+    }
+
+    /// Replaces the playlist with `tracks` and starts playing at `position`,
+    /// on the connected device when there is one.
+    pub async fn play_tracks(
+        &self,
+        tracks: Vec<String>,
+        shuffle: bool,
+        position: Option<i32>,
+    ) -> Result<(), Error> {
+        self.play_tracks_at(tracks, shuffle, position, 0).await
+    }
+
+    /// Like [`Self::play_tracks`], starting `elapsed` milliseconds into the
+    /// track at `position`. Connected devices start at its beginning.
+    pub async fn play_tracks_at(
+        &self,
+        tracks: Vec<String>,
+        shuffle: bool,
+        position: Option<i32>,
+        elapsed: u64,
+    ) -> Result<(), Error> {
+        self.play_queue(tracks, shuffle, position, elapsed, None)
+            .await
+    }
+
+    /// Plays the tracks of `directory` when it is given, their resume
+    /// positions are saved under it.
+    pub(crate) async fn play_queue(
+        &self,
+        tracks: Vec<String>,
+        shuffle: bool,
+        position: Option<i32>,
+        elapsed: u64,
+        directory: Option<&str>,
+    ) -> Result<(), Error> {
+        bookmarks::set_played_directory(directory);
+        if self.load_tracks(&tracks, shuffle).await? {
+            return Ok(());
+        }
+        self.create_playlist(tracks).await?;
+        if shuffle {
+            self.shuffle_playlist(0).await?;
+        }
+        self.start_playlist(position.unwrap_or_default(), elapsed, 0)
+            .await
+    }
+
+    /// Plays an album, from where it was stopped when no position is given
+    /// and it plays in order.
+    pub async fn play_album(
+        &self,
+        album_id: &str,
+        shuffle: bool,
+        position: Option<i32>,
+    ) -> Result<(), Error> {
+        let tracks = repo::album_tracks::find_by_album(self.pool.clone(), album_id).await?;
+        let tracks: Vec<String> = tracks.into_iter().map(|t| t.path).collect();
+        let resume = match (position, shuffle) {
+            (None, false) => {
+                self.resume_position(BookmarkSource::Album, album_id, &tracks)
+                    .await?
+            }
+            _ => None,
+        };
+
+        match resume {
+            Some((position, elapsed)) => {
+                self.play_tracks_at(tracks, false, Some(position), elapsed)
+                    .await
+            }
+            None => self.play_tracks(tracks, shuffle, position).await,
+        }
+    }
+
+    pub async fn play_artist_tracks(
+        &self,
+        artist_id: &str,
+        shuffle: bool,
+        position: Option<i32>,
+    ) -> Result<(), Error> {
+        let tracks = repo::artist_tracks::find_by_artist(self.pool.clone(), artist_id).await?;
+        let tracks = tracks.into_iter().map(|t| t.path).collect();
+        self.play_tracks(tracks, shuffle, position).await
+    }
+
+    pub async fn play_playlist(
+        &self,
+        playlist_id: &str,
+        shuffle: bool,
+        position: Option<i32>,
+    ) -> Result<(), Error> {
+        let tracks =
+            repo::playlist_tracks::find_by_playlist(self.pool.clone(), playlist_id).await?;
+        let tracks = tracks.into_iter().map(|t| t.path).collect();
+        self.play_tracks(tracks, shuffle, position).await
+    }
+
+    pub async fn play_liked_tracks(
+        &self,
+        shuffle: bool,
+        position: Option<i32>,
+    ) -> Result<(), Error> {
+        let tracks = repo::favourites::all_tracks(self.pool.clone()).await?;
+        let tracks = tracks.into_iter().map(|t| t.path).collect();
+        self.play_tracks(tracks, shuffle, position).await
+    }
+
+    pub async fn play_all_tracks(&self, shuffle: bool, position: Option<i32>) -> Result<(), Error> {
+        let tracks = repo::track::all(self.pool.clone()).await?;
+        let tracks = tracks.into_iter().map(|t| t.path).collect();
+        self.play_tracks(tracks, shuffle, position).await
+    }
+
+    /// Plays the files of a directory, and of its subdirectories with
+    /// `recurse`. Starting at a position only makes sense in the directory
+    /// itself, without one it resumes where it was stopped.
+    pub async fn play_directory(
+        &self,
+        path: &str,
+        recurse: bool,
+        shuffle: bool,
+        position: Option<i32>,
+    ) -> Result<(), Error> {
+        if !Path::new(path).is_dir() {
+            return Err(anyhow!("Path is not a directory"));
+        }
+
+        let tracks = match recurse && position.is_none() {
+            true => read_files(path.to_string()).await?,
+            false => list_directory(path)?,
+        };
+        let directory = path.trim_end_matches('/');
+        let resume = match (position, shuffle) {
+            (None, false) => {
+                self.resume_position(BookmarkSource::Directory, directory, &tracks)
+                    .await?
+            }
+            _ => None,
+        };
+
+        match resume {
+            Some((position, elapsed)) => {
+                self.play_queue(tracks, false, Some(position), elapsed, Some(directory))
+                    .await
+            }
+            None => {
+                self.play_queue(tracks, shuffle, position, 0, Some(directory))
+                    .await
+            }
+        }
+    }
+
+    /// Plays a track, from where it was stopped when it is long-form.
+    pub async fn play_track(&self, path: &str) -> Result<(), Error> {
+        let path = path.replace("file://", "");
+        let tracks = vec![path.clone()];
+        let elapsed = self
+            .resume_position(BookmarkSource::Track, &path, &tracks)
+            .await?
+            .map(|(_, elapsed)| elapsed)
+            .unwrap_or_default();
+        self.play_tracks_at(tracks, false, None, elapsed).await
+    }
+}
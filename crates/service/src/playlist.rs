@@ -0,0 +1,241 @@
+use anyhow::{anyhow, Error};
+use rand::seq::SliceRandom;
+use rockbox_library::repo;
+use rockbox_network::download_tracks;
+use rockbox_sys::{
+    self as rb,
+    types::{mp3_entry::Mp3Entry, playlist_info::PlaylistInfo},
+    PLAYLIST_INSERT_LAST, PLAYLIST_INSERT_LAST_SHUFFLED,
+};
+
+use crate::{events, firmware, read_files, transitions, RockboxService};
+
+impl RockboxService {
+    /// Replaces the playlist with `tracks`, urls are downloaded first.
+    /// Returns the start index of the playlist.
+    pub async fn create_playlist(&self, tracks: Vec<String>) -> Result<i32, Error> {
+        if tracks.is_empty() {
+            return Ok(-1);
+        }
+        let tracks = download_tracks(tracks).await?;
+        let start_index = firmware(|| build_playlist(&tracks))?;
+        transitions::set_shuffled(false);
+        events::queue_modified();
+        Ok(start_index)
+    }
+
+    pub async fn start_playlist(
+        &self,
+        start_index: i32,
+        elapsed: u64,
+        offset: u64,
+    ) -> Result<(), Error> {
+        firmware(|| rb::playlist::start(start_index, elapsed, offset));
+        Ok(())
+    }
+
+    pub async fn shuffle_playlist(&self, start_index: i32) -> Result<i32, Error> {
+        let ret = firmware(|| {
+            let seed = rb::system::current_tick();
+            rb::playlist::shuffle(seed as i32, start_index)
+        });
+        transitions::set_shuffled(true);
+        events::queue_modified();
+        Ok(ret)
+    }
+
+    pub async fn playlist_amount(&self) -> Result<i32, Error> {
+        Ok(firmware(rb::playlist::amount))
+    }
+
+    /// Resumes the playlist of the last session, returns -1 when there is
+    /// nothing to resume or when already playing.
+    pub async fn resume_playlist(&self) -> Result<i32, Error> {
+        let ret = firmware(|| {
+            let status = rb::system::get_global_status();
+            let playback_status = rb::playback::status();
+            if status.resume_index == -1 || playback_status.status == 1 {
+                return -1;
+            }
+            rb::playlist::resume()
+        });
+        events::queue_modified();
+        Ok(ret)
+    }
+
+    pub async fn resume_track(&self) -> Result<(), Error> {
+        firmware(|| {
+            let status = rb::system::get_global_status();
+            rb::playlist::resume_track(
+                status.resume_index,
+                status.resume_crc32,
+                status.resume_elapsed.into(),
+                status.resume_offset.into(),
+            )
+        });
+        Ok(())
+    }
+
+    /// The tracks of the playlist with the metadata read from the files.
+    pub async fn playlist_tracks(&self) -> Result<Vec<Mp3Entry>, Error> {
+        Ok(firmware(|| {
+            (0..rb::playlist::amount())
+                .map(|i| {
+                    let info = rb::playlist::get_track_info(i);
+                    rb::metadata::get_metadata(-1, &info.filename)
+                })
+                .collect()
+        }))
+    }
+
+    /// Inserts tracks, or the files of `directory`, at `position`. Creates
+    /// the playlist when it is empty. Returns the position of the tracks.
+    pub async fn insert_tracks(
+        &self,
+        tracks: Vec<String>,
+        position: i32,
+        directory: Option<String>,
+    ) -> Result<i32, Error> {
+        let mut player = self.player.lock().await;
+        if let Some(player) = player.as_mut() {
+            for track in self.cast_tracks(&tracks) {
+                player.play_next(track).await?;
+            }
+            return Ok(0);
+        }
+        drop(player);
+
+        let tracks = match directory {
+            Some(dir) => read_files(dir).await?,
+            None => tracks,
+        };
+        let mut tracks = download_tracks(tracks).await?;
+        if tracks.is_empty() {
+            return Ok(0);
+        }
+
+        if firmware(rb::playlist::amount) == 0 {
+            let start_index = firmware(|| build_playlist(&tracks))?;
+            events::queue_modified();
+            return Ok(start_index);
+        }
+
+        let insert_position = match position {
+            PLAYLIST_INSERT_LAST_SHUFFLED => {
+                tracks.shuffle(&mut rand::thread_rng());
+                PLAYLIST_INSERT_LAST
+            }
+            _ => position,
+        };
+        firmware(|| {
+            rb::playlist::insert_tracks(
+                tracks.iter().map(|t| t.as_str()).collect(),
+                insert_position,
+                tracks.len() as i32,
+            )
+        });
+        events::queue_modified();
+        Ok(position)
+    }
+
+    /// Removes the tracks at `positions`, or all of them when empty.
+    pub async fn remove_tracks(&self, positions: &[i32]) -> Result<i32, Error> {
+        if self.player.lock().await.is_some() {
+            return Ok(0);
+        }
+        let ret = firmware(|| {
+            if positions.is_empty() {
+                return rb::playlist::remove_all_tracks();
+            }
+            let mut ret = 0;
+            for position in positions {
+                ret = rb::playlist::delete_track(*position);
+            }
+            ret
+        });
+        events::queue_modified();
+        Ok(ret)
+    }
+
+    /// The playlist with the ids and covers of the library.
+    pub async fn current_playlist(&self) -> Result<PlaylistInfo, Error> {
+        let mut player = self.player.lock().await;
+        if let Some(player) = player.as_mut() {
+            let current_playback = player.get_current_playback().await?;
+            let tracks = current_playback.items;
+            let index = match tracks.len() >= 2 {
+                true => tracks.len() - 2,
+                false => 0,
+            } as i32;
+
+            return Ok(PlaylistInfo {
+                amount: tracks.len() as i32,
+                index,
+                entries: tracks.into_iter().map(|(t, _)| t.into()).collect(),
+                ..Default::default()
+            });
+        }
+        drop(player);
+
+        let (mut result, filenames) = firmware(|| {
+            let mut result = rb::playlist::get_current();
+            result.amount = rb::playlist::amount();
+            result.max_playlist_size = rb::playlist::max_playlist_size();
+            result.index = rb::playlist::index();
+            result.first_index = rb::playlist::first_index();
+            result.last_insert_pos = rb::playlist::last_insert_pos();
+            result.seed = rb::playlist::seed();
+            result.last_shuffled_start = rb::playlist::last_shuffled_start();
+            let filenames = (0..result.amount)
+                .map(|i| rb::playlist::get_track_info(i).filename)
+                .collect::<Vec<_>>();
+            (result, filenames)
+        });
+
+        let mut metadata_cache = self.metadata_cache.lock().await;
+        let mut entries = vec![];
+
+        for filename in filenames {
+            let hash = format!("{:x}", md5::compute(filename.as_bytes()));
+            if let Some(entry) = metadata_cache.get(&hash) {
+                entries.push(entry.clone());
+                continue;
+            }
+
+            let mut entry = rb::metadata::get_metadata(-1, &filename);
+            let track = repo::track::find_by_md5(self.pool.clone(), &hash).await?;
+
+            if track.is_none() {
+                entries.push(entry);
+                continue;
+            }
+
+            entry.album_art = track.as_ref().and_then(|t| t.album_art.clone());
+            entry.album_id = track.as_ref().map(|t| t.album_id.clone());
+            entry.artist_id = track.as_ref().map(|t| t.artist_id.clone());
+            entry.genre_id = track.as_ref().map(|t| t.genre_id.clone());
+            entry.id = track.as_ref().map(|t| t.id.clone());
+
+            metadata_cache.insert(hash, entry.clone());
+            entries.push(entry);
+        }
+
+        result.entries = entries;
+        Ok(result)
+    }
+}
+
+/// Creates a playlist in the directory of the first track.
+pub(crate) fn build_playlist(tracks: &[String]) -> Result<i32, Error> {
+    let dir = tracks[0].clone();
+    let dir_parts: Vec<_> = dir.split('/').collect();
+    let dir = dir_parts[0..dir_parts.len() - 1].join("/");
+    if rb::playlist::create(&dir, None) == -1 {
+        return Err(anyhow!("Failed to create playlist"));
+    }
+    Ok(rb::playlist::build_playlist(
+        tracks.iter().map(|t| t.as_str()).collect(),
+        0,
+        tracks.len() as i32,
+    ))
+}
@@ -0,0 +1,549 @@
+use std::{
+    collections::HashSet,
+    env, fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    thread,
+    time::Duration,
+};
+
+use anyhow::{anyhow, Error};
+use chrono::Utc;
+use rockbox_library::{entity, repo};
+use rockbox_network::{download_to, feed};
+use rockbox_types::podcast::{Episode, NewPodcast, Podcast, UpdatePodcast};
+
+use crate::{
+    events::{self, EventKind},
+    RockboxService,
+};
+
+/// How often the feeds are polled.
+const POLL_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// How often the poller looks for feeds due, so that a restart does not
+/// poll them all again.
+const CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+const DEFAULT_AUTO_DOWNLOAD: u32 = 3;
+
+/// The episodes being downloaded, by id.
+static DOWNLOADING: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+
+/// Where the episodes are downloaded, in a directory per podcast.
+pub fn podcasts_dir() -> String {
+    format!(
+        "{}/.cache/rockbox/podcasts",
+        env::var("HOME").unwrap_or_default()
+    )
+}
+
+impl RockboxService {
+    pub async fn podcasts(&self) -> Result<Vec<Podcast>, Error> {
+        let podcasts = repo::podcast::all(self.pool.clone()).await?;
+        Ok(podcasts.into_iter().map(into_podcast).collect())
+    }
+
+    pub async fn podcast(&self, id: &str) -> Result<Option<Podcast>, Error> {
+        let podcast = repo::podcast::find(self.pool.clone(), id).await?;
+        Ok(podcast.map(into_podcast))
+    }
+
+    /// Fetches the feed and saves its episodes, the newest are downloaded in
+    /// the background.
+    pub async fn subscribe(&self, podcast: NewPodcast) -> Result<Podcast, Error> {
+        let url = podcast.url.trim().to_string();
+        if !url.starts_with("http") {
+            return Err(anyhow!("Invalid feed URL: {}", url));
+        }
+        if repo::podcast::find_by_url(self.pool.clone(), &url)
+            .await?
+            .is_some()
+        {
+            return Err(anyhow!("Already subscribed to {}", url));
+        }
+        let feed = feed::fetch(&url).await?;
+
+        let now = Utc::now();
+        let mut subscription = entity::podcast::Podcast {
+            id: cuid::cuid1()?,
+            url: url.clone(),
+            title: String::new(),
+            auto_download: podcast.auto_download.unwrap_or(DEFAULT_AUTO_DOWNLOAD) as i32,
+            delete_played: podcast.delete_played.unwrap_or(true),
+            last_polled_at: Some(now),
+            created_at: now,
+            updated_at: now,
+            ..Default::default()
+        };
+        set_feed(&mut subscription, &feed);
+        repo::podcast::save(self.pool.clone(), subscription.clone()).await?;
+        self.save_episodes(&subscription.id, feed.episodes).await?;
+
+        self.sync_downloads_in_background(&subscription.id);
+        Ok(into_podcast(subscription))
+    }
+
+    /// Deletes a podcast with its episodes and their downloads. Returns false
+    /// when there is no such podcast.
+    pub async fn unsubscribe(&self, id: &str) -> Result<bool, Error> {
+        let podcast = match repo::podcast::find(self.pool.clone(), id).await? {
+            Some(podcast) => podcast,
+            None => return Ok(false),
+        };
+        for episode in repo::podcast_episode::find_by_podcast(self.pool.clone(), id).await? {
+            self.delete_download(&episode).await?;
+        }
+        // other files may have been put there, the directory is kept then
+        let _ = fs::remove_dir(podcast_dir(&podcast));
+        Ok(repo::podcast::delete(self.pool.clone(), id).await?)
+    }
+
+    /// Returns `None` when there is no such podcast. The downloads follow
+    /// the new settings in the background.
+    pub async fn update_podcast(
+        &self,
+        id: &str,
+        update: UpdatePodcast,
+    ) -> Result<Option<Podcast>, Error> {
+        let mut podcast = match repo::podcast::find(self.pool.clone(), id).await? {
+            Some(podcast) => podcast,
+            None => return Ok(None),
+        };
+        if let Some(auto_download) = update.auto_download {
+            podcast.auto_download = auto_download as i32;
+        }
+        if let Some(delete_played) = update.delete_played {
+            podcast.delete_played = delete_played;
+        }
+        podcast.updated_at = Utc::now();
+        repo::podcast::update(self.pool.clone(), &podcast).await?;
+
+        self.sync_downloads_in_background(id);
+        Ok(Some(into_podcast(podcast)))
+    }
+
+    /// Polls the feed of a podcast now. Returns `None` when there is no such
+    /// podcast.
+    pub async fn refresh_podcast(&self, id: &str) -> Result<Option<Podcast>, Error> {
+        let podcast = match repo::podcast::find(self.pool.clone(), id).await? {
+            Some(podcast) => podcast,
+            None => return Ok(None),
+        };
+        let podcast = self.poll(podcast).await?;
+        self.sync_downloads_in_background(id);
+        Ok(Some(into_podcast(podcast)))
+    }
+
+    /// The episodes of a podcast, the newest first. Returns `None` when there
+    /// is no such podcast.
+    pub async fn podcast_episodes(&self, podcast_id: &str) -> Result<Option<Vec<Episode>>, Error> {
+        if repo::podcast::find(self.pool.clone(), podcast_id)
+            .await?
+            .is_none()
+        {
+            return Ok(None);
+        }
+        let episodes =
+            repo::podcast_episode::find_by_podcast(self.pool.clone(), podcast_id).await?;
+        Ok(Some(episodes.into_iter().map(into_episode).collect()))
+    }
+
+    pub async fn podcast_episode(&self, id: &str) -> Result<Option<Episode>, Error> {
+        let episode = repo::podcast_episode::find(self.pool.clone(), id).await?;
+        Ok(episode.map(into_episode))
+    }
+
+    /// Downloads an episode now and adds it to the library. Returns `None`
+    /// when there is no such episode.
+    pub async fn download_episode(&self, id: &str) -> Result<Option<Episode>, Error> {
+        let episode = match repo::podcast_episode::find(self.pool.clone(), id).await? {
+            Some(episode) => episode,
+            None => return Ok(None),
+        };
+        let podcast = repo::podcast::find(self.pool.clone(), &episode.podcast_id)
+            .await?
+            .ok_or_else(|| anyhow!("The podcast of the episode is missing"))?;
+        Ok(Some(into_episode(self.download(&podcast, episode).await?)))
+    }
+
+    /// Returns `None` when there is no such episode. The download of a
+    /// played episode is deleted when its podcast says so.
+    pub async fn set_episode_played(
+        &self,
+        id: &str,
+        played: bool,
+    ) -> Result<Option<Episode>, Error> {
+        let mut episode = match repo::podcast_episode::find(self.pool.clone(), id).await? {
+            Some(episode) => episode,
+            None => return Ok(None),
+        };
+        episode.played = played;
+        episode.played_at = played.then(Utc::now);
+        repo::podcast_episode::update_played(self.pool.clone(), id, played, episode.played_at)
+            .await?;
+
+        if played {
+            let podcast = repo::podcast::find(self.pool.clone(), &episode.podcast_id).await?;
+            if podcast
+                .map(|podcast| podcast.delete_played)
+                .unwrap_or(false)
+            {
+                self.delete_download(&episode).await?;
+                episode.path = None;
+            }
+        }
+        Ok(Some(into_episode(episode)))
+    }
+
+    /// Marks the episode downloaded at `path` as played, called when it is
+    /// played to its end.
+    pub(crate) async fn episode_finished(&self, path: &str) -> Result<(), Error> {
+        if !Path::new(path).starts_with(podcasts_dir()) {
+            return Ok(());
+        }
+        if let Some(episode) = repo::podcast_episode::find_by_path(self.pool.clone(), path).await? {
+            self.set_episode_played(&episode.id, true).await?;
+        }
+        Ok(())
+    }
+
+    /// Polls the feeds due in the background, downloading their newest
+    /// episodes and deleting the played ones.
+    pub(crate) fn start_podcast_poller(&self) {
+        let service = self.clone();
+        thread::spawn(move || {
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            rt.block_on(service.run_podcast_poller());
+        });
+    }
+
+    async fn run_podcast_poller(&self) {
+        loop {
+            let podcasts = repo::podcast::all(self.pool.clone())
+                .await
+                .unwrap_or_default();
+            let now = Utc::now();
+            for podcast in podcasts {
+                let due = podcast
+                    .last_polled_at
+                    .and_then(|polled_at| (now - polled_at).to_std().ok())
+                    .map(|elapsed| elapsed >= POLL_INTERVAL)
+                    .unwrap_or(true);
+                if !due {
+                    continue;
+                }
+                let id = podcast.id.clone();
+                if let Err(e) = self.poll(podcast).await {
+                    eprintln!("Error polling podcast {}: {}", id, e);
+                }
+                if let Err(e) = self.sync_downloads(&id).await {
+                    eprintln!("Error downloading podcast {}: {}", id, e);
+                }
+            }
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    }
+
+    /// Fetches the feed of a podcast, updating it and saving the new
+    /// episodes.
+    async fn poll(
+        &self,
+        mut podcast: entity::podcast::Podcast,
+    ) -> Result<entity::podcast::Podcast, Error> {
+        let feed = feed::fetch(&podcast.url).await?;
+        set_feed(&mut podcast, &feed);
+        podcast.last_polled_at = Some(Utc::now());
+        podcast.updated_at = Utc::now();
+        repo::podcast::update(self.pool.clone(), &podcast).await?;
+        self.save_episodes(&podcast.id, feed.episodes).await?;
+        Ok(podcast)
+    }
+
+    async fn save_episodes(
+        &self,
+        podcast_id: &str,
+        episodes: Vec<feed::Episode>,
+    ) -> Result<(), Error> {
+        let now = Utc::now();
+        for episode in episodes {
+            let title = match episode.title.is_empty() {
+                true => file_name(&episode.url),
+                false => episode.title,
+            };
+            let episode = entity::podcast_episode::PodcastEpisode {
+                id: cuid::cuid1()?,
+                podcast_id: podcast_id.to_string(),
+                guid: episode.guid,
+                title,
+                description: episode.description,
+                url: episode.url,
+                mime_type: episode.mime_type,
+                duration: episode.duration.map(|duration| duration as i32),
+                published_at: episode.published_at,
+                path: None,
+                played: false,
+                played_at: None,
+                created_at: now,
+            };
+            repo::podcast_episode::save(self.pool.clone(), episode).await?;
+        }
+        Ok(())
+    }
+
+    fn sync_downloads_in_background(&self, podcast_id: &str) {
+        let service = self.clone();
+        let podcast_id = podcast_id.to_string();
+        thread::spawn(move || {
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            if let Err(e) = rt.block_on(service.sync_downloads(&podcast_id)) {
+                eprintln!("Error downloading podcast {}: {}", podcast_id, e);
+            }
+        });
+    }
+
+    /// Downloads the unplayed episodes among the newest `auto_download`
+    /// ones, and deletes the played downloads when the podcast says so.
+    async fn sync_downloads(&self, podcast_id: &str) -> Result<(), Error> {
+        let podcast = match repo::podcast::find(self.pool.clone(), podcast_id).await? {
+            Some(podcast) => podcast,
+            None => return Ok(()),
+        };
+        let episodes =
+            repo::podcast_episode::find_by_podcast(self.pool.clone(), podcast_id).await?;
+        let (downloads, deletions) = sync_plan(&podcast, episodes);
+        for episode in deletions {
+            self.delete_download(&episode).await?;
+        }
+        for episode in downloads {
+            let title = episode.title.clone();
+            if let Err(e) = self.download(&podcast, episode).await {
+                eprintln!("Error downloading episode {}: {}", title, e);
+            }
+        }
+        Ok(())
+    }
+
+    async fn download(
+        &self,
+        podcast: &entity::podcast::Podcast,
+        mut episode: entity::podcast_episode::PodcastEpisode,
+    ) -> Result<entity::podcast_episode::PodcastEpisode, Error> {
+        if let Some(path) = &episode.path {
+            if Path::new(path).exists() {
+                return Ok(episode);
+            }
+        }
+        {
+            let mut downloading = DOWNLOADING.lock().unwrap();
+            if !downloading
+                .get_or_insert_with(HashSet::new)
+                .insert(episode.id.clone())
+            {
+                return Err(anyhow!("The episode is already downloading"));
+            }
+        }
+
+        let name = match episode.published_at {
+            Some(published_at) => format!(
+                "{} {}",
+                published_at.format("%Y-%m-%d"),
+                sanitize(&episode.title)
+            ),
+            None => sanitize(&episode.title),
+        };
+        let result =
+            download_to(&episode.url, &podcast_dir(podcast).to_string_lossy(), &name).await;
+        if let Some(downloading) = DOWNLOADING.lock().unwrap().as_mut() {
+            downloading.remove(&episode.id);
+        }
+        let path = result?;
+
+        repo::podcast_episode::update_path(self.pool.clone(), &episode.id, Some(&path)).await?;
+        if let Some(track) = repo::track::find_by_path(self.pool.clone(), &path).await? {
+            self.kv.lock().unwrap().set(&path, track);
+        }
+        events::publish(EventKind::LibraryUpdated { path: path.clone() });
+        episode.path = Some(path);
+        Ok(episode)
+    }
+
+    /// Deletes the file of an episode and removes it from the library.
+    async fn delete_download(
+        &self,
+        episode: &entity::podcast_episode::PodcastEpisode,
+    ) -> Result<(), Error> {
+        let Some(path) = &episode.path else {
+            return Ok(());
+        };
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != ErrorKind::NotFound => return Err(e.into()),
+            _ => {}
+        }
+        if let Some(track) = repo::track::find_by_path(self.pool.clone(), path).await? {
+            repo::track::delete(self.pool.clone(), &track.id).await?;
+        }
+        self.kv.lock().unwrap().remove(path);
+        repo::podcast_episode::update_path(self.pool.clone(), &episode.id, None).await?;
+        events::publish(EventKind::LibraryUpdated { path: path.clone() });
+        Ok(())
+    }
+}
+
+fn set_feed(podcast: &mut entity::podcast::Podcast, feed: &feed::Feed) {
+    podcast.title = match feed.title.is_empty() {
+        true => podcast.url.clone(),
+        false => feed.title.clone(),
+    };
+    podcast.description = feed.description.clone();
+    podcast.author = feed.author.clone();
+    podcast.image = feed.image.clone();
+    podcast.link = feed.link.clone();
+}
+
+/// The episodes to download, the unplayed ones among the newest
+/// `auto_download`, and the played downloads to delete. `episodes` are the
+/// newest first.
+fn sync_plan(
+    podcast: &entity::podcast::Podcast,
+    episodes: Vec<entity::podcast_episode::PodcastEpisode>,
+) -> (
+    Vec<entity::podcast_episode::PodcastEpisode>,
+    Vec<entity::podcast_episode::PodcastEpisode>,
+) {
+    let mut downloads = vec![];
+    let mut deletions = vec![];
+    for (index, episode) in episodes.into_iter().enumerate() {
+        if episode.played {
+            if podcast.delete_played && episode.path.is_some() {
+                deletions.push(episode);
+            }
+        } else if index < podcast.auto_download.max(0) as usize {
+            downloads.push(episode);
+        }
+    }
+    (downloads, deletions)
+}
+
+fn podcast_dir(podcast: &entity::podcast::Podcast) -> PathBuf {
+    Path::new(&podcasts_dir()).join(sanitize(&podcast.title))
+}
+
+/// A title usable as a file name.
+fn sanitize(title: &str) -> String {
+    let name: String = title
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .take(100)
+        .collect();
+    match name.trim().trim_start_matches('.') {
+        "" => "Untitled".to_string(),
+        name => name.to_string(),
+    }
+}
+
+fn file_name(url: &str) -> String {
+    url.split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit('/').next())
+        .unwrap_or(url)
+        .to_string()
+}
+
+fn into_podcast(podcast: entity::podcast::Podcast) -> Podcast {
+    Podcast {
+        id: podcast.id,
+        url: podcast.url,
+        title: podcast.title,
+        description: podcast.description,
+        author: podcast.author,
+        image: podcast.image,
+        link: podcast.link,
+        auto_download: podcast.auto_download.max(0) as u32,
+        delete_played: podcast.delete_played,
+        last_polled_at: podcast.last_polled_at.map(|t| t.timestamp()),
+        created_at: podcast.created_at.timestamp(),
+        updated_at: podcast.updated_at.timestamp(),
+    }
+}
+
+fn into_episode(episode: entity::podcast_episode::PodcastEpisode) -> Episode {
+    Episode {
+        id: episode.id,
+        podcast_id: episode.podcast_id,
+        title: episode.title,
+        description: episode.description,
+        url: episode.url,
+        mime_type: episode.mime_type,
+        duration: episode.duration.map(|duration| duration.max(0) as u32),
+        published_at: episode.published_at.map(|t| t.timestamp()),
+        path: episode.path,
+        played: episode.played,
+        played_at: episode.played_at.map(|t| t.timestamp()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn episodes(numbers: &[(u32, bool, bool)]) -> Vec<entity::podcast_episode::PodcastEpisode> {
+        numbers
+            .iter()
+            .map(
+                |(number, played, downloaded)| entity::podcast_episode::PodcastEpisode {
+                    id: format!("episode-{}", number),
+                    played: *played,
+                    path: downloaded.then(|| format!("/podcasts/{}.mp3", number)),
+                    ..Default::default()
+                },
+            )
+            .collect()
+    }
+
+    fn ids(episodes: &[entity::podcast_episode::PodcastEpisode]) -> Vec<&str> {
+        episodes.iter().map(|episode| episode.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sync_plan() {
+        let podcast = entity::podcast::Podcast {
+            auto_download: 3,
+            delete_played: true,
+            ..Default::default()
+        };
+        // the newest first, 4 is played so only 5 and 3 are downloaded
+        let episodes = episodes(&[
+            (5, false, false),
+            (4, true, true),
+            (3, false, true),
+            (2, false, false),
+            (1, true, true),
+            (0, true, false),
+        ]);
+
+        let (downloads, deletions) = sync_plan(&podcast, episodes.clone());
+        assert_eq!(ids(&downloads), vec!["episode-5", "episode-3"]);
+        assert_eq!(ids(&deletions), vec!["episode-4", "episode-1"]);
+
+        let podcast = entity::podcast::Podcast {
+            auto_download: 0,
+            delete_played: false,
+            ..Default::default()
+        };
+        let (downloads, deletions) = sync_plan(&podcast, episodes);
+        assert!(downloads.is_empty());
+        assert!(deletions.is_empty());
+    }
+}
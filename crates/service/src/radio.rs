@@ -0,0 +1,475 @@
+use std::{
+    env, fs,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use anyhow::{anyhow, Error};
+use chrono::Utc;
+use rockbox_library::{entity, repo};
+use rockbox_network::radio::{self, split_stream_title};
+use rockbox_sys::{self as rb, types::mp3_entry::Mp3Entry, PLAYLIST_INSERT_LAST};
+use rockbox_traits::types::track::Track;
+use rockbox_types::radio::{NewStation, NowPlaying, Station, UpdateStation};
+
+use crate::{events, firmware, playlist::build_playlist, transitions, RockboxService};
+
+/// Segments relayed before playback starts, so that it does not starve
+/// while the next one downloads.
+const PREBUFFER_SEGMENTS: usize = 2;
+
+/// Segments waiting after the one playing above which the relay stops,
+/// about 2 minutes of a paused station.
+const MAX_BACKLOG: usize = 16;
+
+/// The station played locally, its stream is relayed in segments queued
+/// one after the other.
+struct Session {
+    station: Station,
+    stop: Arc<AtomicBool>,
+    stream_title: Option<String>,
+    /// The segment playing and its length, and the time played before it,
+    /// in milliseconds.
+    segment: Option<(String, u64)>,
+    elapsed: u64,
+}
+
+static SESSION: Mutex<Option<Session>> = Mutex::new(None);
+
+/// Where the streams played locally are relayed, in a directory per
+/// session.
+pub fn radio_dir() -> String {
+    format!(
+        "{}/.cache/rockbox/radio",
+        env::var("HOME").unwrap_or_default()
+    )
+}
+
+/// Whether `path` is a segment of a relayed stream.
+pub fn is_segment(path: &str) -> bool {
+    path.starts_with(&format!("{}/", radio_dir()))
+}
+
+/// The station playing locally and the title of its stream.
+pub fn now_playing() -> Option<NowPlaying> {
+    let session = SESSION.lock().unwrap();
+    let session = session.as_ref()?;
+    let (artist, title) = stream_title(session);
+    Some(NowPlaying {
+        station: session.station.clone(),
+        title: session
+            .stream_title
+            .as_ref()
+            .filter(|t| !t.is_empty())
+            .map(|_| title),
+        artist,
+    })
+}
+
+/// Shows the segment playing as its station, titled after the stream, with
+/// the time elapsed since the station started. Returns false when `entry`
+/// is not a segment.
+pub fn now_playing_entry(entry: &mut Mp3Entry) -> bool {
+    if !is_segment(&entry.path) {
+        return false;
+    }
+    let mut session = SESSION.lock().unwrap();
+    let Some(session) = session.as_mut() else {
+        return false;
+    };
+
+    match session.segment.as_ref() {
+        Some((path, length)) if *path != entry.path => session.elapsed += length,
+        _ => {}
+    }
+    session.segment = Some((entry.path.clone(), entry.length));
+
+    let (artist, title) = stream_title(session);
+    entry.path = session.station.url.clone();
+    entry.title = title;
+    entry.artist = artist.unwrap_or_default();
+    entry.album = session.station.name.clone();
+    entry.albumartist = String::new();
+    entry.genre_string = session.station.genre.clone().unwrap_or_default();
+    entry.elapsed += session.elapsed;
+    // live, there is no end to seek to
+    entry.length = 0;
+    true
+}
+
+/// Shows a segment of the queue as its station.
+pub fn segment_entry(entry: &mut Mp3Entry) {
+    let session = SESSION.lock().unwrap();
+    if let Some(session) = session.as_ref().filter(|_| is_segment(&entry.path)) {
+        entry.title = session.station.name.clone();
+        entry.album = session.station.name.clone();
+        entry.artist = String::new();
+        entry.length = 0;
+    }
+}
+
+impl RockboxService {
+    /// The stations, the favourites first.
+    pub async fn stations(&self, favourites_only: bool) -> Result<Vec<Station>, Error> {
+        let stations = match favourites_only {
+            true => repo::radio_station::all_favourites(self.pool.clone()).await?,
+            false => repo::radio_station::all(self.pool.clone()).await?,
+        };
+        Ok(stations.into_iter().map(into_station).collect())
+    }
+
+    pub async fn station(&self, id: &str) -> Result<Option<Station>, Error> {
+        let station = repo::radio_station::find(self.pool.clone(), id).await?;
+        Ok(station.map(into_station))
+    }
+
+    /// Adds a station. Without a name its stream is opened to read it.
+    pub async fn create_station(&self, station: NewStation) -> Result<Station, Error> {
+        let url = station.url.trim().to_string();
+        if !url.starts_with("http") {
+            return Err(anyhow!("Invalid station URL: {}", url));
+        }
+        if repo::radio_station::find_by_url(self.pool.clone(), &url)
+            .await?
+            .is_some()
+        {
+            return Err(anyhow!("Station already added: {}", url));
+        }
+
+        let (name, genre) = match station.name.filter(|name| !name.trim().is_empty()) {
+            Some(name) => (name, station.genre),
+            None => {
+                let stream = radio::open(&url).await?;
+                (
+                    stream.name.unwrap_or_else(|| url.clone()),
+                    station.genre.or(stream.genre),
+                )
+            }
+        };
+
+        let now = Utc::now();
+        let station = entity::radio_station::RadioStation {
+            id: cuid::cuid1()?,
+            name,
+            url,
+            genre,
+            homepage: station.homepage,
+            image: station.image,
+            favourite: station.favourite.unwrap_or_default(),
+            last_played_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        repo::radio_station::save(self.pool.clone(), station.clone()).await?;
+        Ok(into_station(station))
+    }
+
+    /// Returns `None` when there is no such station.
+    pub async fn update_station(
+        &self,
+        id: &str,
+        update: UpdateStation,
+    ) -> Result<Option<Station>, Error> {
+        let mut station = match repo::radio_station::find(self.pool.clone(), id).await? {
+            Some(station) => station,
+            None => return Ok(None),
+        };
+        if let Some(name) = update.name {
+            station.name = name;
+        }
+        if let Some(url) = update.url {
+            station.url = url.trim().to_string();
+        }
+        if update.genre.is_some() {
+            station.genre = update.genre;
+        }
+        if update.homepage.is_some() {
+            station.homepage = update.homepage;
+        }
+        if update.image.is_some() {
+            station.image = update.image;
+        }
+        if let Some(favourite) = update.favourite {
+            station.favourite = favourite;
+        }
+        station.updated_at = Utc::now();
+        repo::radio_station::update(self.pool.clone(), &station).await?;
+        Ok(Some(into_station(station)))
+    }
+
+    /// Returns false when there is no such station.
+    pub async fn delete_station(&self, id: &str) -> Result<bool, Error> {
+        Ok(repo::radio_station::delete(self.pool.clone(), id).await?)
+    }
+
+    /// Plays a station, on the connected device when there is one. Returns
+    /// `None` when there is no such station.
+    pub async fn play_station(&self, id: &str) -> Result<Option<Station>, Error> {
+        let station = match repo::radio_station::find(self.pool.clone(), id).await? {
+            Some(station) => station,
+            None => return Ok(None),
+        };
+        self.play_radio(into_station(station.clone())).await?;
+
+        let now = Utc::now();
+        repo::radio_station::update_last_played(self.pool.clone(), id, now).await?;
+        Ok(Some(Station {
+            last_played_at: Some(now.timestamp()),
+            ..into_station(station)
+        }))
+    }
+
+    /// Plays the stream at `url`, as its station when one was added with it.
+    pub async fn play_stream(&self, url: &str) -> Result<Station, Error> {
+        if let Some(station) = repo::radio_station::find_by_url(self.pool.clone(), url).await? {
+            if let Some(station) = self.play_station(&station.id).await? {
+                return Ok(station);
+            }
+        }
+        let station = match self.remote_track(url) {
+            // a track of a remote library, streamed from its instance
+            Some(track) => Station {
+                name: track.title,
+                url: url.to_string(),
+                image: track.album_art,
+                ..Default::default()
+            },
+            None => Station {
+                name: url.to_string(),
+                url: url.to_string(),
+                ..Default::default()
+            },
+        };
+        self.play_radio(station.clone()).await?;
+        Ok(station)
+    }
+
+    async fn play_radio(&self, station: Station) -> Result<(), Error> {
+        let mut player = self.player.lock().await;
+        if let Some(player) = player.as_mut() {
+            // devices play the stream themselves
+            let stream = radio::open(&station.url).await?;
+            let track = Track {
+                id: station.id.clone(),
+                uri: stream.url.clone(),
+                path: station.url.clone(),
+                title: station.name.clone(),
+                album: station.name.clone(),
+                album_cover: station.image.clone(),
+                ..Default::default()
+            };
+            player.load_tracks(vec![track], None).await?;
+            return Ok(());
+        }
+        drop(player);
+        play_locally(station).await
+    }
+}
+
+/// Plays a station on the local output, its stream relayed in segments
+/// replacing the playlist once the first ones are written.
+pub async fn play_locally(mut station: Station) -> Result<(), Error> {
+    let stream = radio::open(&station.url).await?;
+    let Some(extension) = stream.extension() else {
+        return Err(anyhow!(
+            "Unsupported stream format: {}",
+            stream.content_type.as_deref().unwrap_or_default()
+        ));
+    };
+    // a stream played without being added is named after its station
+    if station.id.is_empty() {
+        if let Some(name) = stream.name.clone() {
+            station.name = name;
+        }
+    }
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let previous = SESSION.lock().unwrap().replace(Session {
+        station: station.clone(),
+        stop: stop.clone(),
+        stream_title: None,
+        segment: None,
+        elapsed: 0,
+    });
+    if let Some(previous) = previous {
+        previous.stop.store(true, Ordering::Relaxed);
+    }
+
+    let directory = format!("{}/{}", radio_dir(), Utc::now().timestamp_millis());
+    thread::spawn(move || {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let mut relay = Relay {
+            stop: stop.clone(),
+            directory: directory.clone(),
+            queued: vec![],
+            started: false,
+        };
+        let result = rt.block_on(radio::relay(
+            stream,
+            &directory,
+            &stop,
+            |title| set_stream_title(&stop, title),
+            |segment| relay.queue(segment),
+        ));
+        if let Err(e) = result {
+            eprintln!("Error relaying {} ({}): {}", station.url, extension, e);
+        }
+        relay.finish();
+    });
+    Ok(())
+}
+
+/// Queues the segments of a session as they are relayed.
+struct Relay {
+    stop: Arc<AtomicBool>,
+    directory: String,
+    /// The segments in the playlist, in order.
+    queued: Vec<String>,
+    started: bool,
+}
+
+impl Relay {
+    /// Returns false once the station no longer plays.
+    fn queue(&mut self, segment: String) -> bool {
+        if !is_current(&self.stop) {
+            return false;
+        }
+        self.queued.push(segment.clone());
+
+        if !self.started {
+            if self.queued.len() < PREBUFFER_SEGMENTS {
+                return true;
+            }
+            let started = firmware(|| {
+                let start_index = build_playlist(&self.queued)?;
+                rb::playlist::start(start_index, 0, 0);
+                Ok::<_, Error>(())
+            });
+            if let Err(e) = started {
+                eprintln!("Error starting the station: {}", e);
+                return false;
+            }
+            self.started = true;
+            transitions::set_shuffled(false);
+            events::queue_modified();
+            self.delete_other_sessions();
+            return true;
+        }
+
+        // the segments played are removed, they are before the one playing
+        let played = firmware(|| {
+            let index = rb::playlist::index();
+            let current = rb::playlist::get_track_info(index).filename;
+            let position = self.queued.iter().position(|s| *s == current)?;
+            rb::playlist::insert_tracks(vec![segment.as_str()], PLAYLIST_INSERT_LAST, 1);
+            for i in (0..index).rev() {
+                let filename = rb::playlist::get_track_info(i).filename;
+                if self.queued[..position].contains(&filename) {
+                    rb::playlist::delete_track(i);
+                }
+            }
+            Some(position)
+        });
+        events::queue_modified();
+
+        match played {
+            Some(position) => {
+                for path in self.queued.drain(..position) {
+                    let _ = fs::remove_file(path);
+                }
+                // paused for too long, the station would play late
+                self.queued.len() <= MAX_BACKLOG + 1
+            }
+            // something else plays now
+            None => false,
+        }
+    }
+
+    /// Ends the session when it was not replaced by another one.
+    fn finish(&self) {
+        // not holding the session, the broker locks it under the firmware
+        if self.playing() {
+            return;
+        }
+        let mut session = SESSION.lock().unwrap();
+        if session
+            .as_ref()
+            .map(|s| Arc::ptr_eq(&s.stop, &self.stop))
+            .unwrap_or(false)
+        {
+            session.take();
+            drop(session);
+            let _ = fs::remove_dir_all(&self.directory);
+        }
+    }
+
+    /// Whether a segment of the session still plays, the stream may have
+    /// ended with the last ones queued.
+    fn playing(&self) -> bool {
+        self.started
+            && firmware(|| {
+                let current = rb::playlist::get_track_info(rb::playlist::index()).filename;
+                self.queued.contains(&current)
+            })
+    }
+
+    fn delete_other_sessions(&self) {
+        let Ok(entries) = fs::read_dir(radio_dir()) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            if entry.path().to_str() != Some(self.directory.as_str()) {
+                let _ = fs::remove_dir_all(entry.path());
+            }
+        }
+    }
+}
+
+fn is_current(stop: &Arc<AtomicBool>) -> bool {
+    SESSION
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|session| Arc::ptr_eq(&session.stop, stop))
+        .unwrap_or(false)
+}
+
+fn set_stream_title(stop: &Arc<AtomicBool>, title: String) {
+    let mut session = SESSION.lock().unwrap();
+    if let Some(session) = session
+        .as_mut()
+        .filter(|session| Arc::ptr_eq(&session.stop, stop))
+    {
+        session.stream_title = Some(title);
+    }
+}
+
+/// The artist and title of the stream, the station name when it sends
+/// none.
+fn stream_title(session: &Session) -> (Option<String>, String) {
+    match session.stream_title.as_deref().filter(|t| !t.is_empty()) {
+        Some(stream_title) => split_stream_title(stream_title),
+        None => (None, session.station.name.clone()),
+    }
+}
+
+fn into_station(station: entity::radio_station::RadioStation) -> Station {
+    Station {
+        id: station.id,
+        name: station.name,
+        url: station.url,
+        genre: station.genre,
+        homepage: station.homepage,
+        image: station.image,
+        favourite: station.favourite,
+        last_played_at: station.last_played_at.map(|t| t.timestamp()),
+        created_at: station.created_at.timestamp(),
+        updated_at: station.updated_at.timestamp(),
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::OnceLock;
+
+use anyhow::Error;
+use futures::future::{join_all, BoxFuture};
+use rockbox_library::entity::{album::Album, track::Track};
+use rockbox_types::device::Device;
+
+use crate::RockboxService;
+
+/// What is asked to the library of another Rockbox instance.
+#[derive(Clone)]
+pub enum RemoteQuery {
+    Albums,
+    Tracks,
+    Search(String),
+}
+
+/// The albums and tracks of a remote library, their paths and covers point
+/// to the instance they are streamed from.
+#[derive(Default)]
+pub struct RemoteResults {
+    pub albums: Vec<Album>,
+    pub tracks: Vec<Track>,
+}
+
+/// Queries the library of another Rockbox instance. The gRPC client lives in
+/// a crate depending on this one, the server registers it at startup.
+pub type RemoteLibrary =
+    fn(Device, RemoteQuery) -> BoxFuture<'static, Result<RemoteResults, Error>>;
+
+static REMOTE_LIBRARY: OnceLock<RemoteLibrary> = OnceLock::new();
+
+pub fn register_remote_library(library: RemoteLibrary) {
+    let _ = REMOTE_LIBRARY.set(library);
+}
+
+impl RockboxService {
+    /// Asks all the remote sources at once, the ones failing to answer are
+    /// left out. The tracks are kept so they can be played from their path.
+    pub async fn query_remote_sources(&self, query: RemoteQuery) -> RemoteResults {
+        let Some(library) = REMOTE_LIBRARY.get() else {
+            return RemoteResults::default();
+        };
+        let queries = self.remote_sources().into_iter().map(|device| {
+            let name = device.name.clone();
+            let results = library(device, query.clone());
+            async move { (name, results.await) }
+        });
+
+        let mut results = RemoteResults::default();
+        for (name, remote) in join_all(queries).await {
+            match remote {
+                Ok(remote) => {
+                    results.albums.extend(remote.albums);
+                    results.tracks.extend(remote.tracks);
+                }
+                Err(e) => eprintln!("Failed to query the library of {}: {}", name, e),
+            }
+        }
+
+        let mut remote_tracks = self.remote_tracks.lock().unwrap();
+        for track in &results.tracks {
+            remote_tracks.set(&track.path, track.clone());
+        }
+        results
+    }
+
+    /// A track of a remote library returned by an earlier query.
+    pub fn remote_track(&self, path: &str) -> Option<Track> {
+        self.remote_tracks.lock().unwrap().get(path).cloned()
+    }
+}
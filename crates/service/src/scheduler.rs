@@ -0,0 +1,420 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Error};
+use chrono::{Local, Utc};
+use rand::seq::SliceRandom;
+use rockbox_library::{entity, repo};
+use rockbox_sys as rb;
+use rockbox_types::scheduler::{
+    NewSchedule, PlaybackSource, Schedule, SleepTimer, UpdateSchedule, DEFAULT_FADE_OUT,
+};
+
+use crate::{
+    cron::Cron,
+    events::{self, EventKind},
+    read_files, transitions, RockboxService,
+};
+
+/// Tracks picked by a mix.
+const MIX_SIZE: usize = 100;
+
+/// Schedules run on the minute, checked this often.
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+struct SleepTimerState {
+    duration: u32,
+    fade_out: u32,
+    ends_at: Instant,
+}
+
+static SLEEP_TIMER: Mutex<Option<SleepTimerState>> = Mutex::new(None);
+
+/// Changes each time the sleep timer is set or cancelled, the thread of the
+/// previous timer stops when it sees it.
+static SLEEP_TIMER_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+impl RockboxService {
+    pub fn sleep_timer(&self) -> SleepTimer {
+        match SLEEP_TIMER.lock().unwrap().as_ref() {
+            Some(timer) => SleepTimer {
+                active: true,
+                duration: timer.duration,
+                remaining: timer
+                    .ends_at
+                    .saturating_duration_since(Instant::now())
+                    .as_secs() as u32,
+                fade_out: timer.fade_out,
+            },
+            None => SleepTimer::default(),
+        }
+    }
+
+    /// Pauses playback in `duration` seconds, the volume fading out during
+    /// the last `fade_out` seconds. Replaces the running timer, a duration
+    /// of 0 cancels it.
+    ///
+    /// The firmware sleep timer powers the device off, here playback is
+    /// paused and the volume restored so the next play starts as before.
+    pub fn set_sleep_timer(&self, duration: u32, fade_out: Option<u32>) -> SleepTimer {
+        if duration == 0 {
+            return self.cancel_sleep_timer();
+        }
+        let fade_out = fade_out.unwrap_or(DEFAULT_FADE_OUT).min(duration);
+
+        let generation = {
+            let mut timer = SLEEP_TIMER.lock().unwrap();
+            *timer = Some(SleepTimerState {
+                duration,
+                fade_out,
+                ends_at: Instant::now() + Duration::from_secs(duration as u64),
+            });
+            SLEEP_TIMER_GENERATION.fetch_add(1, Ordering::SeqCst) + 1
+        };
+
+        let service = self.clone();
+        thread::spawn(move || service.run_sleep_timer(generation, duration, fade_out));
+
+        let timer = self.sleep_timer();
+        events::publish(EventKind::SleepTimerChanged(timer.clone()));
+        timer
+    }
+
+    pub fn cancel_sleep_timer(&self) -> SleepTimer {
+        let cancelled = {
+            let mut timer = SLEEP_TIMER.lock().unwrap();
+            SLEEP_TIMER_GENERATION.fetch_add(1, Ordering::SeqCst);
+            timer.take().is_some()
+        };
+        let timer = self.sleep_timer();
+        if cancelled {
+            events::publish(EventKind::SleepTimerChanged(timer.clone()));
+        }
+        timer
+    }
+
+    fn run_sleep_timer(&self, generation: u64, duration: u32, fade_out: u32) {
+        let current = || SLEEP_TIMER_GENERATION.load(Ordering::SeqCst) == generation;
+
+        thread::sleep(Duration::from_secs((duration - fade_out) as u64));
+        if !current() {
+            return;
+        }
+
+        let volume = unsafe { rb::global_settings.volume };
+        let completed = fade(volume, rb::sound::min(rb::SOUND_VOLUME), fade_out, current);
+        if completed {
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            if let Err(e) = rt.block_on(self.pause()) {
+                eprintln!("Error pausing at the end of the sleep timer: {}", e);
+            }
+        }
+        // only the output was faded, the volume setting is unchanged
+        rb::sound::set(rb::SOUND_VOLUME, unsafe { rb::global_settings.volume });
+
+        let ended = {
+            let mut timer = SLEEP_TIMER.lock().unwrap();
+            match current() {
+                true => timer.take().is_some(),
+                false => false,
+            }
+        };
+        if ended {
+            events::publish(EventKind::SleepTimerChanged(SleepTimer::default()));
+        }
+    }
+
+    /// Runs the schedules on the minute in the background, and starts the
+    /// sleep timer when the settings ask for it on startup.
+    pub(crate) fn start_scheduler(&self) {
+        let service = self.clone();
+        thread::spawn(move || {
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            rt.block_on(service.run_schedules());
+        });
+
+        let (on_startup, minutes) = unsafe {
+            (
+                rb::global_settings.sleeptimer_on_startup,
+                rb::global_settings.sleeptimer_duration,
+            )
+        };
+        if on_startup && minutes > 0 {
+            self.set_sleep_timer(minutes as u32 * 60, None);
+        }
+    }
+
+    async fn run_schedules(&self) {
+        let mut last_minute = None;
+        loop {
+            tokio::time::sleep(CHECK_INTERVAL).await;
+            let now = Local::now();
+            let minute = now.timestamp() / 60;
+            if last_minute == Some(minute) {
+                continue;
+            }
+            last_minute = Some(minute);
+
+            let schedules = match repo::schedule::all_enabled(self.pool.clone()).await {
+                Ok(schedules) => schedules,
+                Err(_) => continue,
+            };
+            for schedule in schedules {
+                // already run this minute, before a restart
+                if schedule.last_run_at.map(|t| t.timestamp() / 60) == Some(minute) {
+                    continue;
+                }
+                match schedule.cron.parse::<Cron>() {
+                    Ok(cron) if cron.matches(&now) => {
+                        let service = self.clone();
+                        tokio::spawn(async move {
+                            if let Err(e) = service.start_schedule(&schedule).await {
+                                eprintln!("Error running schedule {}: {}", schedule.name, e);
+                            }
+                        });
+                    }
+                    Ok(_) => {}
+                    Err(e) => eprintln!("Invalid schedule {}: {}", schedule.name, e),
+                }
+            }
+        }
+    }
+
+    /// Starts playing the source of the schedule, from silence when it
+    /// fades in.
+    async fn start_schedule(&self, schedule: &entity::schedule::Schedule) -> Result<(), Error> {
+        let source = schedule.source.parse().map_err(Error::msg)?;
+        let tracks = self
+            .source_tracks(source, schedule.source_id.as_deref())
+            .await?;
+        if tracks.is_empty() {
+            return Err(anyhow!("Nothing to play"));
+        }
+        repo::schedule::update_last_run(self.pool.clone(), &schedule.id, Utc::now()).await?;
+
+        let (min, max) = (
+            rb::sound::min(rb::SOUND_VOLUME),
+            rb::sound::max(rb::SOUND_VOLUME),
+        );
+        if let Some(volume) = schedule.volume {
+            let current = unsafe { rb::global_settings.volume };
+            rb::sound::adjust_volume(volume.clamp(min, max) - current);
+        }
+        let volume = unsafe { rb::global_settings.volume };
+        let fade_in = schedule.fade_in.max(0) as u32;
+        if fade_in > 0 {
+            rb::sound::set(rb::SOUND_VOLUME, min);
+        }
+
+        if let Err(e) = self.play_tracks(tracks, schedule.shuffle, None).await {
+            rb::sound::set(rb::SOUND_VOLUME, volume);
+            return Err(e);
+        }
+        if source == PlaybackSource::Mix {
+            transitions::set_shuffled(true);
+        }
+        events::publish(EventKind::ScheduleTriggered {
+            id: schedule.id.clone(),
+            name: schedule.name.clone(),
+        });
+
+        if fade_in > 0 {
+            thread::spawn(move || {
+                // changing the volume during the fade ends it
+                fade(min, volume, fade_in, || unsafe {
+                    rb::global_settings.volume == volume
+                });
+                rb::sound::set(rb::SOUND_VOLUME, unsafe { rb::global_settings.volume });
+            });
+        }
+        Ok(())
+    }
+
+    async fn source_tracks(
+        &self,
+        source: PlaybackSource,
+        source_id: Option<&str>,
+    ) -> Result<Vec<String>, Error> {
+        let id = || source_id.ok_or_else(|| anyhow!("Missing source id for {}", source));
+        let tracks = match source {
+            PlaybackSource::Playlist => {
+                repo::playlist_tracks::find_by_playlist(self.pool.clone(), id()?).await?
+            }
+            PlaybackSource::Album => {
+                repo::album_tracks::find_by_album(self.pool.clone(), id()?).await?
+            }
+            PlaybackSource::Artist => {
+                repo::artist_tracks::find_by_artist(self.pool.clone(), id()?).await?
+            }
+            PlaybackSource::Directory => return read_files(id()?.to_string()).await,
+            PlaybackSource::LikedTracks => repo::favourites::all_tracks(self.pool.clone()).await?,
+            PlaybackSource::AllTracks => repo::track::all(self.pool.clone()).await?,
+            PlaybackSource::Mix => {
+                let mut tracks = match source_id {
+                    Some(genre_id) => {
+                        repo::genre_tracks::find_by_genre(self.pool.clone(), genre_id).await?
+                    }
+                    None => repo::track::all(self.pool.clone()).await?,
+                };
+                tracks.shuffle(&mut rand::thread_rng());
+                tracks.truncate(MIX_SIZE);
+                tracks
+            }
+        };
+        Ok(tracks.into_iter().map(|t| t.path).collect())
+    }
+
+    pub async fn schedules(&self) -> Result<Vec<Schedule>, Error> {
+        let schedules = repo::schedule::all(self.pool.clone()).await?;
+        Ok(schedules.into_iter().map(into_schedule).collect())
+    }
+
+    pub async fn schedule(&self, id: &str) -> Result<Option<Schedule>, Error> {
+        let schedule = repo::schedule::find(self.pool.clone(), id).await?;
+        Ok(schedule.map(into_schedule))
+    }
+
+    pub async fn create_schedule(&self, schedule: NewSchedule) -> Result<Schedule, Error> {
+        validate(
+            &schedule.cron,
+            schedule.source,
+            schedule.source_id.as_deref(),
+        )?;
+        let now = Utc::now();
+        let schedule = entity::schedule::Schedule {
+            id: cuid::cuid1()?,
+            name: schedule.name,
+            cron: schedule.cron.trim().to_string(),
+            source: schedule.source.to_string(),
+            source_id: schedule.source_id,
+            shuffle: schedule.shuffle,
+            volume: schedule.volume,
+            fade_in: schedule.fade_in as i32,
+            once: schedule.once,
+            enabled: schedule.enabled.unwrap_or(true),
+            last_run_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        repo::schedule::save(self.pool.clone(), schedule.clone()).await?;
+        Ok(into_schedule(schedule))
+    }
+
+    /// Returns `None` when there is no such schedule.
+    pub async fn update_schedule(
+        &self,
+        id: &str,
+        update: UpdateSchedule,
+    ) -> Result<Option<Schedule>, Error> {
+        let mut schedule = match repo::schedule::find(self.pool.clone(), id).await? {
+            Some(schedule) => schedule,
+            None => return Ok(None),
+        };
+        let source = match update.source {
+            Some(source) => source,
+            None => schedule.source.parse().map_err(Error::msg)?,
+        };
+        let source_id = update.source_id.or(schedule.source_id);
+        let cron = update.cron.unwrap_or(schedule.cron);
+        validate(&cron, source, source_id.as_deref())?;
+
+        schedule.name = update.name.unwrap_or(schedule.name);
+        schedule.cron = cron.trim().to_string();
+        schedule.source = source.to_string();
+        schedule.source_id = source_id;
+        schedule.shuffle = update.shuffle.unwrap_or(schedule.shuffle);
+        schedule.volume = update.volume.or(schedule.volume);
+        schedule.fade_in = update
+            .fade_in
+            .map(|fade_in| fade_in as i32)
+            .unwrap_or(schedule.fade_in);
+        schedule.once = update.once.unwrap_or(schedule.once);
+        schedule.enabled = update.enabled.unwrap_or(schedule.enabled);
+        schedule.updated_at = Utc::now();
+        repo::schedule::update(self.pool.clone(), &schedule).await?;
+        Ok(Some(into_schedule(schedule)))
+    }
+
+    pub async fn delete_schedule(&self, id: &str) -> Result<bool, Error> {
+        Ok(repo::schedule::delete(self.pool.clone(), id).await?)
+    }
+
+    /// Runs a schedule now, whether it is enabled or not. Returns false when
+    /// there is no such schedule.
+    pub async fn run_schedule(&self, id: &str) -> Result<bool, Error> {
+        match repo::schedule::find(self.pool.clone(), id).await? {
+            Some(schedule) => {
+                self.start_schedule(&schedule).await?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+fn validate(cron: &str, source: PlaybackSource, source_id: Option<&str>) -> Result<(), Error> {
+    cron.parse::<Cron>()?;
+    if source.requires_id() && source_id.is_none() {
+        return Err(anyhow!("Missing source id for {}", source));
+    }
+    Ok(())
+}
+
+fn into_schedule(schedule: entity::schedule::Schedule) -> Schedule {
+    let next_run_at = match schedule.enabled {
+        true => schedule
+            .cron
+            .parse::<Cron>()
+            .ok()
+            .and_then(|cron| cron.next_after(&Local::now()))
+            .map(|next| next.timestamp()),
+        false => None,
+    };
+    Schedule {
+        id: schedule.id,
+        name: schedule.name,
+        source: schedule.source.parse().unwrap_or_default(),
+        cron: schedule.cron,
+        source_id: schedule.source_id,
+        shuffle: schedule.shuffle,
+        volume: schedule.volume,
+        fade_in: schedule.fade_in.max(0) as u32,
+        once: schedule.once,
+        enabled: schedule.enabled,
+        last_run_at: schedule.last_run_at.map(|t| t.timestamp()),
+        next_run_at,
+        created_at: schedule.created_at.timestamp(),
+    }
+}
+
+/// Ramps the volume of the output from `from` to `to`, in dB, over
+/// `seconds` without changing the volume setting. Returns false when
+/// `running` turned false before the end.
+fn fade(from: i32, to: i32, seconds: u32, running: impl Fn() -> bool) -> bool {
+    let steps = (to - from).abs();
+    if steps == 0 || seconds == 0 {
+        rb::sound::set(rb::SOUND_VOLUME, to);
+        return running();
+    }
+    let interval = Duration::from_secs(seconds as u64) / steps as u32;
+    for step in 1..=steps {
+        thread::sleep(interval);
+        if !running() {
+            return false;
+        }
+        rb::sound::set(rb::SOUND_VOLUME, from + step * (to - from).signum());
+    }
+    true
+}
@@ -0,0 +1,21 @@
+use anyhow::Error;
+use rockbox_sys::{
+    self as rb,
+    types::user_settings::{NewGlobalSettings, UserSettings},
+};
+
+use crate::{firmware, RockboxService};
+
+impl RockboxService {
+    pub async fn global_settings(&self) -> Result<UserSettings, Error> {
+        Ok(firmware(rb::settings::get_global_settings))
+    }
+
+    /// Applies the settings and saves them to `settings.toml`.
+    pub async fn save_settings(&self, settings: NewGlobalSettings) -> Result<(), Error> {
+        firmware(|| {
+            rockbox_settings::load_settings(Some(settings))?;
+            rockbox_settings::write_settings()
+        })
+    }
+}
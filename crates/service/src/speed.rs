@@ -0,0 +1,62 @@
+//! Playback speed arithmetic shared by the REST, gRPC and MPRIS players.
+//!
+//! Speeds, pitches and timestretches are in hundredths of a percent, see
+//! firmware/export/sound.h, a pitch of 0 is reported before the sound
+//! settings are loaded and stands for the normal pitch.
+
+use rockbox_sys as rb;
+
+fn effective_pitch(pitch: i32) -> i32 {
+    match pitch {
+        0 => rb::PITCH_SPEED_100,
+        pitch => pitch,
+    }
+}
+
+/// The timestretch playing at `speed` while keeping `pitch`.
+pub fn timestretch(speed: i32, pitch: i32) -> i32 {
+    let pitch = effective_pitch(pitch);
+    let stretch = (speed * rb::PITCH_SPEED_100 + pitch / 2) / pitch;
+    stretch.clamp(rb::STRETCH_MIN, rb::STRETCH_MAX)
+}
+
+/// The pitch playing at `speed` when timestretch is not available.
+pub fn pitch_for_speed(speed: i32) -> i32 {
+    speed.clamp(rb::PITCH_MIN, rb::PITCH_MAX)
+}
+
+/// The playback rate, 1.0 being the normal speed.
+pub fn rate(pitch: i32, timestretch: i32, timestretch_available: bool) -> f64 {
+    let stretch = match timestretch_available && timestretch > 0 {
+        true => timestretch,
+        false => rb::PITCH_SPEED_100,
+    };
+    effective_pitch(pitch) as f64 * stretch as f64
+        / rb::PITCH_SPEED_100 as f64
+        / rb::PITCH_SPEED_100 as f64
+}
+
+/// The slowest and fastest rates. Timestretch keeps the pitch and scales
+/// the speed around it, without it the speed can only be changed through
+/// the pitch.
+pub fn rate_range(pitch: i32, timestretch_available: bool) -> (f64, f64) {
+    let speed_100 = rb::PITCH_SPEED_100 as f64;
+    match timestretch_available {
+        true => {
+            let pitch = effective_pitch(pitch) as f64;
+            (
+                pitch * rb::STRETCH_MIN as f64 / speed_100 / speed_100,
+                pitch * rb::STRETCH_MAX as f64 / speed_100 / speed_100,
+            )
+        }
+        false => (
+            rb::PITCH_MIN as f64 / speed_100,
+            rb::PITCH_MAX as f64 / speed_100,
+        ),
+    }
+}
+
+/// The speed, in `PITCH_SPEED_100` units, of a rate.
+pub fn speed(rate: f64) -> i32 {
+    (rate * rb::PITCH_SPEED_100 as f64).round() as i32
+}
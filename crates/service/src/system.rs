@@ -0,0 +1,17 @@
+use anyhow::Error;
+use rockbox_sys::{
+    self as rb,
+    types::{system_status::SystemStatus, RockboxVersion},
+};
+
+use crate::RockboxService;
+
+impl RockboxService {
+    pub async fn rockbox_version(&self) -> Result<RockboxVersion, Error> {
+        Ok(rb::system::get_rockbox_version())
+    }
+
+    pub async fn global_status(&self) -> Result<SystemStatus, Error> {
+        Ok(rb::system::get_global_status())
+    }
+}
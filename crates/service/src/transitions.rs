@@ -0,0 +1,498 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    hash::{Hash, Hasher},
+    hint,
+    sync::{
+        atomic::{fence, AtomicBool, AtomicI32, AtomicU64, AtomicU8, Ordering},
+        Mutex, OnceLock, RwLock,
+    },
+    thread::{self, Thread},
+    time::Duration,
+};
+
+use lazy_static::lazy_static;
+use rockbox_library::silence::{self, Silence};
+use rockbox_sys::{self as rb, types::user_settings::TransitionMode};
+
+use crate::{firmware, radio, SERVICE};
+
+/// Tracks after the current one whose silence is measured ahead.
+const LOOKAHEAD: usize = 2;
+
+/// The largest tempo change matching the beats, in percent, like the pitch
+/// fader of a turntable.
+const MAX_TEMPO_CHANGE: f32 = 8.0;
+
+/// The tempo of a beat matched track eases back to its own in that time.
+const TEMPO_EASE: Duration = Duration::from_secs(30);
+const TEMPO_STEPS: u32 = 120;
+
+lazy_static! {
+    static ref SILENCE: Mutex<HashMap<String, Silence>> = Mutex::new(HashMap::new());
+    static ref ANALYZING: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+}
+
+/// The paths of the queue, and the index of the track playing.
+static QUEUE: RwLock<(Vec<String>, usize)> = RwLock::new((Vec::new(), 0));
+
+/// Whether the queue was shuffled since it was created, the shuffle
+/// setting only tells about the shuffle of the firmware.
+static SHUFFLED: AtomicBool = AtomicBool::new(false);
+
+/// The codec and the PCM buffer ask for the transitions from the audio
+/// thread, which must not wait on a lock. What they need about the track
+/// playing, the one before it and the next ones is published to these slots,
+/// guarded by a sequence number odd while they are written.
+const SLOTS: usize = LOOKAHEAD + 2;
+static SNAPSHOT: [Slot; SLOTS] = [Slot::EMPTY; SLOTS];
+static VERSION: AtomicU64 = AtomicU64::new(0);
+/// Reads racing a write are retried this many times, then the track plays
+/// without transition.
+const READ_ATTEMPTS: usize = 4;
+
+/// Serializes the writers of the snapshot.
+static PUBLISHING: Mutex<()> = Mutex::new(());
+
+/// The transition mode of the queue playing, see [`mode`].
+static MODE: AtomicU8 = AtomicU8::new(0);
+
+/// The key of the track the codec decodes, started last.
+static DECODING: AtomicU64 = AtomicU64::new(0);
+
+/// Whether the tempo was changed to match the beats.
+static STRETCHED: AtomicBool = AtomicBool::new(false);
+
+/// The speed the track starting plays at relative to the one set by the
+/// user, in PITCH_SPEED_100 units, 0 for its own tempo. Set from the audio
+/// thread and applied by the tempo thread.
+static TEMPO: AtomicI32 = AtomicI32::new(0);
+
+/// Changes each time a track starts, the tempo thread stops easing the
+/// tempo of the previous track when it sees it.
+static TEMPO_GENERATION: AtomicU64 = AtomicU64::new(0);
+static TEMPO_THREAD: OnceLock<Thread> = OnceLock::new();
+
+/// A radio stream segment.
+const SEGMENT: u8 = 1;
+/// Follows the track before it in the queue on their album.
+const AFTER_PREVIOUS: u8 = 2;
+/// Followed by the next track of the queue on their album.
+const BEFORE_NEXT: u8 = 4;
+/// The silence of the track was measured.
+const MEASURED: u8 = 8;
+
+struct Slot {
+    path: AtomicU64,
+    previous: AtomicU64,
+    flags: AtomicU8,
+    start: AtomicU64,
+    end: AtomicU64,
+    tempo: AtomicI32,
+}
+
+/// A track of the snapshot. `path` and `previous` are the keys of the track
+/// and of the one before it in the queue, `start` and `end` where its sound
+/// starts and ends in ms, 0 keeping the bounds of the track, and `tempo` the
+/// speed matching the beats of the previous track relative to the speed of
+/// the user, 0 when it can't.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct Plan {
+    path: u64,
+    previous: u64,
+    flags: u8,
+    start: u64,
+    end: u64,
+    tempo: i32,
+}
+
+impl Slot {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const EMPTY: Slot = Slot {
+        path: AtomicU64::new(0),
+        previous: AtomicU64::new(0),
+        flags: AtomicU8::new(0),
+        start: AtomicU64::new(0),
+        end: AtomicU64::new(0),
+        tempo: AtomicI32::new(0),
+    };
+
+    fn load(&self) -> Plan {
+        Plan {
+            path: self.path.load(Ordering::Relaxed),
+            previous: self.previous.load(Ordering::Relaxed),
+            flags: self.flags.load(Ordering::Relaxed),
+            start: self.start.load(Ordering::Relaxed),
+            end: self.end.load(Ordering::Relaxed),
+            tempo: self.tempo.load(Ordering::Relaxed),
+        }
+    }
+
+    fn store(&self, plan: Plan) {
+        self.path.store(plan.path, Ordering::Relaxed);
+        self.previous.store(plan.previous, Ordering::Relaxed);
+        self.flags.store(plan.flags, Ordering::Relaxed);
+        self.start.store(plan.start, Ordering::Relaxed);
+        self.end.store(plan.end, Ordering::Relaxed);
+        self.tempo.store(plan.tempo, Ordering::Relaxed);
+    }
+}
+
+/// Identifies a path in the snapshot, never 0 which marks an empty slot.
+fn key(path: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    hasher.finish().max(1)
+}
+
+/// The plan of a track from the snapshot, without locking.
+fn lookup(path: u64) -> Option<Plan> {
+    if path == 0 {
+        return None;
+    }
+    for _ in 0..READ_ATTEMPTS {
+        let version = VERSION.load(Ordering::Acquire);
+        if version % 2 == 1 {
+            hint::spin_loop();
+            continue;
+        }
+        let plan = SNAPSHOT
+            .iter()
+            .map(Slot::load)
+            .find(|plan| plan.path == path);
+        fence(Ordering::Acquire);
+        if VERSION.load(Ordering::Relaxed) == version {
+            return plan;
+        }
+    }
+    None
+}
+
+/// Computes the transitions of the tracks around the one playing and
+/// publishes them for the audio thread.
+fn publish() {
+    let _publishing = PUBLISHING.lock().unwrap();
+    let mode = mode();
+    // the track before the one playing, it and the lookahead, the path after
+    // them only tells whether the last one is followed on its album
+    let (paths, planned) = {
+        let queue = QUEUE.read().unwrap();
+        let first = queue.1.saturating_sub(1);
+        let last = queue.0.len().min(queue.1 + LOOKAHEAD + 2);
+        let paths = queue.0.get(first..last).unwrap_or_default().to_vec();
+        (paths, queue.1 - first + LOOKAHEAD + 1)
+    };
+    let plans: Vec<Plan> = (0..paths.len().min(planned))
+        .map(|i| plan(&paths, i))
+        .collect();
+
+    let version = VERSION.load(Ordering::Relaxed);
+    VERSION.store(version + 1, Ordering::Relaxed);
+    fence(Ordering::Release);
+    for (i, slot) in SNAPSHOT.iter().enumerate() {
+        slot.store(plans.get(i).copied().unwrap_or_default());
+    }
+    MODE.store(mode as u8, Ordering::Relaxed);
+    VERSION.store(version + 2, Ordering::Release);
+
+    TEMPO_THREAD.get_or_init(|| thread::spawn(ease_tempo).thread().clone());
+}
+
+/// The plan of `paths[i]`, the next path being only looked at.
+fn plan(paths: &[String], i: usize) -> Plan {
+    let path = &paths[i];
+    let previous = i.checked_sub(1).map(|previous| paths[previous].as_str());
+    let next = paths.get(i + 1);
+    let silence = SILENCE.lock().unwrap().get(path).copied();
+
+    let mut flags = 0;
+    if radio::is_segment(path) {
+        flags |= SEGMENT;
+    }
+    if previous.is_some_and(|previous| consecutive(previous, path)) {
+        flags |= AFTER_PREVIOUS;
+    }
+    if next.is_some_and(|next| consecutive(path, next)) {
+        flags |= BEFORE_NEXT;
+    }
+    if silence.is_some() {
+        flags |= MEASURED;
+    }
+    let silence = silence.unwrap_or_default();
+
+    Plan {
+        path: key(path),
+        previous: previous.map(key).unwrap_or_default(),
+        flags,
+        start: silence.start,
+        end: match silence.end < silence.length {
+            true => silence.end,
+            false => 0,
+        },
+        tempo: previous
+            .and_then(|previous| tempo_ratio(previous, path))
+            .map(|ratio| ((rb::PITCH_SPEED_100 as f32) * ratio).round() as i32)
+            .unwrap_or_default(),
+    }
+}
+
+pub fn set_shuffled(shuffled: bool) {
+    SHUFFLED.store(shuffled, Ordering::Relaxed);
+    publish();
+}
+
+/// The transition mode of the queue playing, the mix one when it is
+/// shuffled and the album one otherwise.
+pub fn mode() -> TransitionMode {
+    let settings = rb::settings::get_transition_settings();
+    let shuffled =
+        SHUFFLED.load(Ordering::Relaxed) || unsafe { rb::global_settings.playlist_shuffle };
+    match shuffled {
+        true => settings.mix,
+        false => settings.album,
+    }
+}
+
+/// The mode last published, read from the audio thread.
+fn published_mode() -> TransitionMode {
+    match MODE.load(Ordering::Relaxed) {
+        m if m == TransitionMode::Smart as u8 => TransitionMode::Smart,
+        m if m == TransitionMode::BeatMatch as u8 => TransitionMode::BeatMatch,
+        _ => TransitionMode::Crossfade,
+    }
+}
+
+pub fn set_queue(paths: Vec<String>) {
+    QUEUE.write().unwrap().0 = paths;
+}
+
+/// Follows the track playing, measures the silence of the next ones in the
+/// background and publishes their transitions.
+pub fn prepare(index: i32) {
+    let index = index.max(0) as usize;
+    let paths = {
+        let mut queue = QUEUE.write().unwrap();
+        queue.1 = index;
+        queue
+            .0
+            .iter()
+            .skip(index)
+            .take(LOOKAHEAD + 1)
+            .cloned()
+            .collect()
+    };
+    if mode() != TransitionMode::Crossfade {
+        analyze(paths);
+    }
+    publish();
+}
+
+fn analyze(paths: Vec<String>) {
+    let paths: Vec<String> = {
+        let silence = SILENCE.lock().unwrap();
+        let mut analyzing = ANALYZING.lock().unwrap();
+        paths
+            .into_iter()
+            .filter(|path| !radio::is_segment(path))
+            .filter(|path| !silence.contains_key(path) && analyzing.insert(path.clone()))
+            .collect()
+    };
+    if paths.is_empty() {
+        return;
+    }
+
+    thread::spawn(move || {
+        for path in paths {
+            let silence = match silence::detect(&path) {
+                Ok(silence) => silence,
+                Err(e) => {
+                    eprintln!("Failed to detect the silence of {}: {}", path, e);
+                    // not trimmed, and not decoded again
+                    Silence::default()
+                }
+            };
+            SILENCE.lock().unwrap().insert(path.clone(), silence);
+            ANALYZING.lock().unwrap().remove(&path);
+            publish();
+        }
+    });
+}
+
+/// Decides whether the track change is crossfaded, from the decision of the
+/// crossfade setting. Smart transitions never crossfade automatic changes
+/// between consecutive tracks of an album. Called from the audio thread.
+pub fn crossfade(crossfade: bool, auto_skip: bool) -> bool {
+    if !crossfade || !auto_skip {
+        return crossfade;
+    }
+    let Some(plan) = lookup(DECODING.load(Ordering::Relaxed)) else {
+        return crossfade;
+    };
+    // the segments of a radio stream follow each other without a gap
+    if plan.flags & SEGMENT != 0 {
+        return false;
+    }
+    if published_mode() == TransitionMode::Crossfade {
+        return crossfade;
+    }
+    plan.flags & BEFORE_NEXT == 0
+}
+
+/// Where the codec starts and ends a track, in milliseconds, 0 keeping the
+/// bounds of the track. Smart transitions trim the silence, except between
+/// consecutive tracks of an album, the leading one only when the track is
+/// started automatically. Called from the audio thread.
+pub fn bounds(path: &str, auto_skip: bool) -> (u64, u64) {
+    let path = key(path);
+    let previous = DECODING.swap(path, Ordering::Relaxed);
+    // tracks out of the snapshot play whole, it follows the queue
+    let Some(plan) = lookup(path) else {
+        reset_tempo();
+        return (0, 0);
+    };
+    if plan.flags & SEGMENT != 0 {
+        reset_tempo();
+        return (0, 0);
+    }
+    // the track before it in the queue is the one whose tempo it can match
+    let follows = previous != 0 && previous == plan.previous;
+    let trim_start = auto_skip && previous != 0 && !(follows && plan.flags & AFTER_PREVIOUS != 0);
+    let mode = published_mode();
+
+    match mode {
+        TransitionMode::BeatMatch if trim_start && follows && plan.tempo != 0 => {
+            match_tempo(plan.tempo)
+        }
+        _ => reset_tempo(),
+    }
+
+    if mode == TransitionMode::Crossfade || plan.flags & MEASURED == 0 {
+        return (0, 0);
+    }
+    let start = match trim_start {
+        true => plan.start,
+        false => 0,
+    };
+    let end = match plan.flags & BEFORE_NEXT {
+        0 => plan.end,
+        _ => 0,
+    };
+    (start, end)
+}
+
+/// Whether `next` follows `previous` on their album, so they play gapless.
+fn consecutive(previous: &str, next: &str) -> bool {
+    let Some(service) = SERVICE.get() else {
+        return false;
+    };
+    let kv = service.kv.lock().unwrap();
+    let (Some(previous), Some(next)) = (kv.get(previous), kv.get(next)) else {
+        return false;
+    };
+    if previous.album_id != next.album_id {
+        return false;
+    }
+    match (previous.track_number, next.track_number) {
+        (Some(previous_number), Some(next_number)) => match next.disc_number {
+            disc if disc == previous.disc_number => next_number == previous_number + 1,
+            disc if disc == previous.disc_number + 1 => next_number == 1,
+            _ => false,
+        },
+        // without track numbers the album is assumed to play in order
+        _ => true,
+    }
+}
+
+/// Starts the track at `tempo`, the tempo of the previous one, then eases
+/// back to the speed set by the user.
+fn match_tempo(tempo: i32) {
+    TEMPO.store(tempo, Ordering::Relaxed);
+    TEMPO_GENERATION.fetch_add(1, Ordering::Release);
+    wake_tempo_thread();
+}
+
+fn reset_tempo() {
+    TEMPO.store(0, Ordering::Relaxed);
+    TEMPO_GENERATION.fetch_add(1, Ordering::Release);
+    if STRETCHED.load(Ordering::Relaxed) {
+        wake_tempo_thread();
+    }
+}
+
+fn wake_tempo_thread() {
+    if let Some(thread) = TEMPO_THREAD.get() {
+        thread.unpark();
+    }
+}
+
+/// Applies the tempo of the tracks starting, the timestretch is not changed
+/// from the audio thread. Beat matched tracks ease back to the speed set by
+/// the user, read before the tempo is changed.
+fn ease_tempo() {
+    let mut applied = TEMPO_GENERATION.load(Ordering::Acquire);
+    let mut base = rb::PITCH_SPEED_100;
+    loop {
+        let generation = TEMPO_GENERATION.load(Ordering::Acquire);
+        if generation == applied {
+            thread::park();
+            continue;
+        }
+        applied = generation;
+
+        if !STRETCHED.load(Ordering::Relaxed) {
+            base = match firmware(rb::sound::dsp::get_timestretch) {
+                0 => rb::PITCH_SPEED_100,
+                stretch => stretch,
+            };
+        }
+        let tempo = TEMPO.load(Ordering::Relaxed);
+        if tempo == 0 {
+            if STRETCHED.swap(false, Ordering::Relaxed) {
+                firmware(|| rb::sound::dsp::set_timestretch(base));
+            }
+            continue;
+        }
+        let from = (base as i64 * tempo as i64 / rb::PITCH_SPEED_100 as i64) as i32;
+        let from = from.clamp(rb::STRETCH_MIN, rb::STRETCH_MAX);
+        firmware(|| rb::sound::dsp::set_timestretch(from));
+        STRETCHED.store(true, Ordering::Relaxed);
+
+        let mut current = from;
+        for step in 1..=TEMPO_STEPS {
+            thread::park_timeout(TEMPO_EASE / TEMPO_STEPS);
+            if TEMPO_GENERATION.load(Ordering::Acquire) != generation {
+                break;
+            }
+            // the speed was changed by the user meanwhile, it is kept
+            if firmware(rb::sound::dsp::get_timestretch) != current {
+                STRETCHED.store(false, Ordering::Relaxed);
+                break;
+            }
+            current = from + (base - from) * step as i32 / TEMPO_STEPS as i32;
+            firmware(|| rb::sound::dsp::set_timestretch(current));
+            if step == TEMPO_STEPS {
+                STRETCHED.store(false, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+/// The speed playing `path` at the tempo of `previous`, counting double or
+/// half time, `None` when it is too far to match.
+fn tempo_ratio(previous: &str, path: &str) -> Option<f32> {
+    if !rb::sound::dsp::timestretch_available() {
+        return None;
+    }
+    let service = SERVICE.get()?;
+    let (previous_bpm, bpm) = {
+        let kv = service.kv.lock().unwrap();
+        (kv.get(previous)?.bpm?, kv.get(path)?.bpm?)
+    };
+    if previous_bpm <= 0.0 || bpm <= 0.0 {
+        return None;
+    }
+    let ratio = previous_bpm / bpm;
+    [ratio, ratio * 2.0, ratio / 2.0]
+        .into_iter()
+        .min_by(|a, b| (a - 1.0).abs().total_cmp(&(b - 1.0).abs()))
+        .filter(|ratio| (ratio - 1.0).abs() * 100.0 <= MAX_TEMPO_CHANGE)
+}
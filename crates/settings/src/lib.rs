@@ -13,7 +13,8 @@ pub fn load_settings(new_settings: Option<NewGlobalSettings>) -> Result<(), Erro
     };
 
     if new_settings.is_none() {
-        // disable sleep timer
+        // the firmware sleep timer powers off instead of pausing, the service
+        // runs its own and starts it with `sleeptimer_on_startup`
         rb::system::set_sleeptimer_duration(0);
     }
 
@@ -0,0 +1,266 @@
+use anyhow::{anyhow, Error};
+use flacenc::{
+    component::{BitRepr, Stream},
+    error::{Verified, Verify},
+    source::{Fill, FrameBuf},
+};
+use mp3lame_encoder::{Bitrate, Builder, InterleavedPcm, Quality};
+use ogg::{PacketWriteEndInfo, PacketWriter};
+
+pub const CHANNELS: usize = 2;
+
+pub trait Encoder {
+    /// Bytes sent once to every listener before any audio.
+    fn header(&mut self) -> Result<Vec<u8>, Error>;
+    fn encode(&mut self, samples: &[i16]) -> Result<Vec<u8>, Error>;
+}
+
+pub struct WavEncoder {
+    sample_rate: u32,
+}
+
+impl WavEncoder {
+    pub fn new(sample_rate: u32) -> Self {
+        Self { sample_rate }
+    }
+}
+
+impl Encoder for WavEncoder {
+    fn header(&mut self) -> Result<Vec<u8>, Error> {
+        // The stream has no end, so the sizes are set to the maximum value,
+        // which players treat as "read until the connection closes".
+        let byte_rate = self.sample_rate * CHANNELS as u32 * 2;
+        let mut header = Vec::with_capacity(44);
+        header.extend_from_slice(b"RIFF");
+        header.extend_from_slice(&u32::MAX.to_le_bytes());
+        header.extend_from_slice(b"WAVEfmt ");
+        header.extend_from_slice(&16u32.to_le_bytes());
+        header.extend_from_slice(&1u16.to_le_bytes());
+        header.extend_from_slice(&(CHANNELS as u16).to_le_bytes());
+        header.extend_from_slice(&self.sample_rate.to_le_bytes());
+        header.extend_from_slice(&byte_rate.to_le_bytes());
+        header.extend_from_slice(&(CHANNELS as u16 * 2).to_le_bytes());
+        header.extend_from_slice(&16u16.to_le_bytes());
+        header.extend_from_slice(b"data");
+        header.extend_from_slice(&(u32::MAX - 36).to_le_bytes());
+        Ok(header)
+    }
+
+    fn encode(&mut self, samples: &[i16]) -> Result<Vec<u8>, Error> {
+        Ok(samples.iter().flat_map(|s| s.to_le_bytes()).collect())
+    }
+}
+
+pub struct Mp3Encoder {
+    encoder: mp3lame_encoder::Encoder,
+}
+
+impl Mp3Encoder {
+    pub fn new(sample_rate: u32) -> Result<Self, Error> {
+        let mut builder = Builder::new().ok_or_else(|| anyhow!("failed to create lame encoder"))?;
+        builder
+            .set_num_channels(CHANNELS as u8)
+            .map_err(|e| anyhow!("{:?}", e))?;
+        builder
+            .set_sample_rate(sample_rate)
+            .map_err(|e| anyhow!("{:?}", e))?;
+        builder
+            .set_brate(Bitrate::Kbps192)
+            .map_err(|e| anyhow!("{:?}", e))?;
+        builder
+            .set_quality(Quality::Good)
+            .map_err(|e| anyhow!("{:?}", e))?;
+        let encoder = builder.build().map_err(|e| anyhow!("{:?}", e))?;
+        Ok(Self { encoder })
+    }
+}
+
+impl Encoder for Mp3Encoder {
+    fn header(&mut self) -> Result<Vec<u8>, Error> {
+        Ok(vec![])
+    }
+
+    fn encode(&mut self, samples: &[i16]) -> Result<Vec<u8>, Error> {
+        let mut output = Vec::with_capacity(mp3lame_encoder::max_required_buffer_size(
+            samples.len() / CHANNELS,
+        ));
+        let size = self
+            .encoder
+            .encode(InterleavedPcm(samples), output.spare_capacity_mut())
+            .map_err(|e| anyhow!("{:?}", e))?;
+        unsafe { output.set_len(size) };
+        Ok(output)
+    }
+}
+
+pub struct FlacEncoder {
+    config: Verified<flacenc::config::Encoder>,
+    stream: Stream,
+    framebuf: FrameBuf,
+    pending: Vec<i32>,
+    frame_number: usize,
+}
+
+impl FlacEncoder {
+    pub fn new(sample_rate: u32) -> Result<Self, Error> {
+        let config = flacenc::config::Encoder::default()
+            .into_verified()
+            .map_err(|(_, e)| anyhow!("{:?}", e))?;
+        let stream =
+            Stream::new(sample_rate as usize, CHANNELS, 16).map_err(|e| anyhow!("{:?}", e))?;
+        let framebuf =
+            FrameBuf::with_size(CHANNELS, config.block_size).map_err(|e| anyhow!("{:?}", e))?;
+        Ok(Self {
+            config,
+            stream,
+            framebuf,
+            pending: vec![],
+            frame_number: 0,
+        })
+    }
+}
+
+impl Encoder for FlacEncoder {
+    fn header(&mut self) -> Result<Vec<u8>, Error> {
+        // A stream without frames serializes to the "fLaC" marker and the
+        // STREAMINFO block, with an unknown total number of samples.
+        let mut sink = flacenc::bitsink::ByteSink::new();
+        self.stream
+            .write(&mut sink)
+            .map_err(|e| anyhow!("{:?}", e))?;
+        Ok(sink.as_slice().to_vec())
+    }
+
+    fn encode(&mut self, samples: &[i16]) -> Result<Vec<u8>, Error> {
+        self.pending.extend(samples.iter().map(|s| *s as i32));
+
+        let block_len = self.config.block_size * CHANNELS;
+        let mut sink = flacenc::bitsink::ByteSink::new();
+        while self.pending.len() >= block_len {
+            let block: Vec<i32> = self.pending.drain(..block_len).collect();
+            self.framebuf
+                .fill_interleaved(&block)
+                .map_err(|e| anyhow!("{:?}", e))?;
+            let frame = flacenc::encode_fixed_size_frame(
+                &self.config,
+                &self.framebuf,
+                self.frame_number,
+                self.stream.stream_info(),
+            )
+            .map_err(|e| anyhow!("{:?}", e))?;
+            frame.write(&mut sink).map_err(|e| anyhow!("{:?}", e))?;
+            self.frame_number += 1;
+        }
+        Ok(sink.as_slice().to_vec())
+    }
+}
+
+/// Opus only runs at 48kHz, samples are resampled linearly from the mixer
+/// rate and packed in 20ms Ogg pages.
+pub struct OpusEncoder {
+    encoder: opus::Encoder,
+    writer: PacketWriter<'static, Vec<u8>>,
+    serial: u32,
+    sample_rate: u32,
+    pending: Vec<i16>,
+    position: f64,
+    granule: u64,
+}
+
+const OPUS_SAMPLE_RATE: u32 = 48000;
+const OPUS_FRAME_SIZE: usize = 960;
+const OPUS_PRE_SKIP: u16 = 312;
+
+impl OpusEncoder {
+    pub fn new(sample_rate: u32) -> Result<Self, Error> {
+        let mut encoder = opus::Encoder::new(
+            OPUS_SAMPLE_RATE,
+            opus::Channels::Stereo,
+            opus::Application::Audio,
+        )?;
+        encoder.set_bitrate(opus::Bitrate::Bits(160000))?;
+        Ok(Self {
+            encoder,
+            writer: PacketWriter::new(Vec::new()),
+            serial: std::process::id(),
+            sample_rate,
+            pending: vec![],
+            position: 0.0,
+            granule: 0,
+        })
+    }
+
+    fn resample(&mut self, samples: &[i16]) {
+        if self.sample_rate == OPUS_SAMPLE_RATE {
+            self.pending.extend_from_slice(samples);
+            return;
+        }
+
+        let frames = samples.len() / CHANNELS;
+        if frames < 2 {
+            return;
+        }
+        let step = self.sample_rate as f64 / OPUS_SAMPLE_RATE as f64;
+        while (self.position as usize) + 1 < frames {
+            let index = self.position as usize;
+            let fraction = self.position - index as f64;
+            for channel in 0..CHANNELS {
+                let a = samples[index * CHANNELS + channel] as f64;
+                let b = samples[(index + 1) * CHANNELS + channel] as f64;
+                self.pending.push((a + (b - a) * fraction) as i16);
+            }
+            self.position += step;
+        }
+        self.position -= (frames - 1) as f64;
+        self.position = self.position.max(0.0);
+    }
+
+    fn take(&mut self) -> Vec<u8> {
+        std::mem::take(self.writer.inner_mut())
+    }
+}
+
+impl Encoder for OpusEncoder {
+    fn header(&mut self) -> Result<Vec<u8>, Error> {
+        let mut head = Vec::with_capacity(19);
+        head.extend_from_slice(b"OpusHead");
+        head.push(1);
+        head.push(CHANNELS as u8);
+        head.extend_from_slice(&OPUS_PRE_SKIP.to_le_bytes());
+        head.extend_from_slice(&self.sample_rate.to_le_bytes());
+        head.extend_from_slice(&0u16.to_le_bytes());
+        head.push(0);
+        self.writer
+            .write_packet(head, self.serial, PacketWriteEndInfo::EndPage, 0)?;
+
+        let vendor = b"rockbox";
+        let mut tags = Vec::new();
+        tags.extend_from_slice(b"OpusTags");
+        tags.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
+        tags.extend_from_slice(vendor);
+        tags.extend_from_slice(&0u32.to_le_bytes());
+        self.writer
+            .write_packet(tags, self.serial, PacketWriteEndInfo::EndPage, 0)?;
+
+        Ok(self.take())
+    }
+
+    fn encode(&mut self, samples: &[i16]) -> Result<Vec<u8>, Error> {
+        self.resample(samples);
+
+        let frame_len = OPUS_FRAME_SIZE * CHANNELS;
+        let mut packet = vec![0u8; 4000];
+        while self.pending.len() >= frame_len {
+            let frame: Vec<i16> = self.pending.drain(..frame_len).collect();
+            let size = self.encoder.encode(&frame, &mut packet)?;
+            self.granule += OPUS_FRAME_SIZE as u64;
+            self.writer.write_packet(
+                packet[..size].to_vec(),
+                self.serial,
+                PacketWriteEndInfo::EndPage,
+                self.granule,
+            )?;
+        }
+        Ok(self.take())
+    }
+}
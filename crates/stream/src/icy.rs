@@ -0,0 +1,78 @@
+use std::sync::RwLock;
+
+use once_cell::sync::Lazy;
+
+/// Number of audio bytes between two metadata blocks, sent in the `icy-metaint` header.
+pub const METAINT: usize = 16000;
+
+static STREAM_TITLE: Lazy<RwLock<String>> = Lazy::new(|| RwLock::new(String::new()));
+
+pub fn set_stream_title(artist: &str, title: &str) {
+    let stream_title = match artist.is_empty() {
+        true => title.to_string(),
+        false => format!("{} - {}", artist, title),
+    };
+    let mut current = STREAM_TITLE.write().unwrap();
+    if *current != stream_title {
+        *current = stream_title;
+    }
+}
+
+pub fn stream_title() -> String {
+    STREAM_TITLE.read().unwrap().clone()
+}
+
+/// Interleaves SHOUTcast metadata blocks with the encoded audio, the title
+/// is only repeated when it changes, empty blocks are sent otherwise.
+pub struct IcyWriter {
+    remaining: usize,
+    sent_title: Option<String>,
+}
+
+impl Default for IcyWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IcyWriter {
+    pub fn new() -> Self {
+        Self {
+            remaining: METAINT,
+            sent_title: None,
+        }
+    }
+
+    pub fn write(&mut self, mut data: &[u8]) -> Vec<u8> {
+        let mut output = Vec::with_capacity(data.len() + 64);
+        while data.len() >= self.remaining {
+            let (head, tail) = data.split_at(self.remaining);
+            output.extend_from_slice(head);
+            output.extend_from_slice(&self.metadata_block());
+            data = tail;
+            self.remaining = METAINT;
+        }
+        output.extend_from_slice(data);
+        self.remaining -= data.len();
+        output
+    }
+
+    fn metadata_block(&mut self) -> Vec<u8> {
+        let title = stream_title();
+        if self.sent_title.as_ref() == Some(&title) {
+            return vec![0];
+        }
+
+        let metadata = format!("StreamTitle='{}';", title.replace('\'', "’"));
+        let mut bytes = metadata.into_bytes();
+        bytes.truncate(255 * 16);
+        let blocks = bytes.len().div_ceil(16);
+        bytes.resize(blocks * 16, 0);
+
+        let mut block = Vec::with_capacity(bytes.len() + 1);
+        block.push(blocks as u8);
+        block.extend_from_slice(&bytes);
+        self.sent_title = Some(title);
+        block
+    }
+}
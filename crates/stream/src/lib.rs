@@ -0,0 +1,110 @@
+use anyhow::Error;
+use encoder::{Encoder, FlacEncoder, Mp3Encoder, OpusEncoder, WavEncoder};
+use icy::IcyWriter;
+use tokio::sync::broadcast::error::RecvError;
+
+pub mod encoder;
+pub mod icy;
+pub mod tap;
+
+pub use icy::{set_stream_title, METAINT};
+pub use tap::{install, listeners, Chunk};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Flac,
+    Opus,
+    Mp3,
+    Wav,
+}
+
+impl Format {
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_lowercase().as_str() {
+            "flac" => Some(Format::Flac),
+            "opus" | "ogg" => Some(Format::Opus),
+            "mp3" => Some(Format::Mp3),
+            "wav" => Some(Format::Wav),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Format::Flac => "audio/flac",
+            Format::Opus => "audio/ogg",
+            Format::Mp3 => "audio/mpeg",
+            Format::Wav => "audio/wav",
+        }
+    }
+
+    fn encoder(&self, sample_rate: u32) -> Result<Box<dyn Encoder>, Error> {
+        Ok(match self {
+            Format::Flac => Box::new(FlacEncoder::new(sample_rate)?),
+            Format::Opus => Box::new(OpusEncoder::new(sample_rate)?),
+            Format::Mp3 => Box::new(Mp3Encoder::new(sample_rate)?),
+            Format::Wav => Box::new(WavEncoder::new(sample_rate)),
+        })
+    }
+}
+
+/// A single client of the stream, with its own encoder so that every
+/// listener gets a valid header whenever it connects.
+pub struct Listener {
+    format: Format,
+    receiver: tap::Receiver,
+    encoder: Option<(u32, Box<dyn Encoder>)>,
+    icy: Option<IcyWriter>,
+}
+
+impl Listener {
+    pub fn new(format: Format, icy_metadata: bool) -> Self {
+        Self {
+            format,
+            receiver: tap::subscribe(),
+            encoder: None,
+            icy: icy_metadata.then(IcyWriter::new),
+        }
+    }
+
+    /// Waits for the mixer to hand out more samples and returns them encoded,
+    /// `None` once the stream is closed.
+    pub async fn next_chunk(&mut self) -> Option<Result<Vec<u8>, Error>> {
+        loop {
+            let chunk = match self.receiver.recv().await {
+                Ok(chunk) => chunk,
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            };
+
+            let data = match self.encode(&chunk) {
+                Ok(data) => data,
+                Err(e) => return Some(Err(e)),
+            };
+
+            if data.is_empty() {
+                continue;
+            }
+
+            return Some(Ok(match self.icy.as_mut() {
+                Some(icy) => icy.write(&data),
+                None => data,
+            }));
+        }
+    }
+
+    fn encode(&mut self, chunk: &Chunk) -> Result<Vec<u8>, Error> {
+        let mut data = vec![];
+        // WAV and FLAC headers carry the sample rate, a new stream header is
+        // required when it changes, which players handle as a chained stream.
+        if self.encoder.as_ref().map(|(rate, _)| *rate) != Some(chunk.sample_rate) {
+            let mut encoder = self.format.encoder(chunk.sample_rate)?;
+            data.extend(encoder.header()?);
+            self.encoder = Some((chunk.sample_rate, encoder));
+        }
+
+        let (_, encoder) = self.encoder.as_mut().unwrap();
+        data.extend(encoder.encode(&chunk.samples)?);
+        Ok(data)
+    }
+}
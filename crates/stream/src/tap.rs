@@ -0,0 +1,104 @@
+use std::{
+    ffi::c_void,
+    sync::atomic::{fence, AtomicI16, AtomicU64, Ordering},
+};
+
+use once_cell::sync::Lazy;
+use rockbox_sys::{self as rb, PcmMixerChannel};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// Number of mixer buffers a slow listener can lag behind before chunks are dropped.
+const CAPACITY: usize = 256;
+
+/// Interleaved samples kept for the listeners, about 3 seconds of 44.1 kHz
+/// stereo. A listener further behind skips the overwritten chunks.
+const RING_SAMPLES: usize = 1 << 18;
+
+/// Interleaved 16-bit stereo samples, as handed out by the mixer.
+#[derive(Clone)]
+pub struct Chunk {
+    pub samples: Vec<i16>,
+    pub sample_rate: u32,
+}
+
+/// Where a mixer buffer was copied in the ring, in samples written since it
+/// was created.
+#[derive(Clone, Copy)]
+struct Span {
+    start: u64,
+    len: usize,
+    sample_rate: u32,
+}
+
+static SENDER: Lazy<broadcast::Sender<Span>> = Lazy::new(|| broadcast::channel(CAPACITY).0);
+
+/// The samples are copied there from the audio thread, which must not
+/// allocate, and copied out by the listeners.
+static RING: Lazy<Box<[AtomicI16]>> =
+    Lazy::new(|| (0..RING_SAMPLES).map(|_| AtomicI16::new(0)).collect());
+
+/// The end of the samples being written, raised before they are, so a
+/// listener can tell whether the ones it copied were overwritten meanwhile.
+static CLAIMED: AtomicU64 = AtomicU64::new(0);
+
+/// Called by the mixer from the audio thread each time a new playback buffer
+/// starts, so only copy the samples out and return.
+extern "C" fn on_buffer(start: *const c_void, size: usize) {
+    if start.is_null() || SENDER.receiver_count() == 0 {
+        return;
+    }
+
+    let samples = unsafe { std::slice::from_raw_parts(start as *const i16, size / 2) };
+    if samples.len() > RING_SAMPLES {
+        return;
+    }
+    let position = CLAIMED.load(Ordering::Relaxed);
+    CLAIMED.store(position + samples.len() as u64, Ordering::Relaxed);
+    fence(Ordering::Release);
+    for (i, sample) in samples.iter().enumerate() {
+        RING[(position as usize + i) % RING_SAMPLES].store(*sample, Ordering::Relaxed);
+    }
+    let _ = SENDER.send(Span {
+        start: position,
+        len: samples.len(),
+        // the mixer follows the rate of the track playing
+        sample_rate: rb::sound::mixer::get_frequency(),
+    });
+}
+
+pub fn install() {
+    Lazy::force(&RING);
+    rb::sound::mixer::channel_set_buffer_hook(PcmMixerChannel::Playback, on_buffer);
+}
+
+/// The chunks of the mixer from now on.
+pub struct Receiver {
+    receiver: broadcast::Receiver<Span>,
+}
+
+impl Receiver {
+    pub async fn recv(&mut self) -> Result<Chunk, RecvError> {
+        let span = self.receiver.recv().await?;
+        let samples = (0..span.len)
+            .map(|i| RING[(span.start as usize + i) % RING_SAMPLES].load(Ordering::Relaxed))
+            .collect();
+        fence(Ordering::Acquire);
+        if CLAIMED.load(Ordering::Relaxed) > span.start + RING_SAMPLES as u64 {
+            return Err(RecvError::Lagged(1));
+        }
+        Ok(Chunk {
+            samples,
+            sample_rate: span.sample_rate,
+        })
+    }
+}
+
+pub fn subscribe() -> Receiver {
+    Receiver {
+        receiver: SENDER.subscribe(),
+    }
+}
+
+pub fn listeners() -> usize {
+    SENDER.receiver_count()
+}
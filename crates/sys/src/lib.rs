@@ -31,6 +31,18 @@ pub const PLAYLIST_REPLACE: i32 = -6;
 pub const PLAYLIST_INSERT_LAST_SHUFFLED: i32 = -7;
 pub const PLAYLIST_INSERT_LAST_ROTATED: i32 = -8;
 
+pub const PITCH_SPEED_PRECISION: i32 = 100;
+pub const PITCH_SPEED_100: i32 = 100 * PITCH_SPEED_PRECISION;
+pub const PITCH_MIN: i32 = 50 * PITCH_SPEED_PRECISION;
+pub const PITCH_MAX: i32 = 200 * PITCH_SPEED_PRECISION;
+pub const STRETCH_MIN: i32 = 35 * PITCH_SPEED_PRECISION;
+pub const STRETCH_MAX: i32 = 250 * PITCH_SPEED_PRECISION;
+
+// The first of the sound settings, see firmware/export/audiohw.h
+pub const SOUND_VOLUME: i32 = 0;
+
+pub const AUDIO_STATUS_PLAY: i32 = 1;
+
 #[macro_export]
 macro_rules! cast_ptr {
     ($ptr:expr) => {{
@@ -1157,6 +1169,8 @@ extern "C" {
     fn beep_play(frequency: c_uint, duration: c_uint, amplitude: c_uint);
     fn dsp_set_crossfeed_type(r#type: c_int);
     fn dsp_eq_enable(enable: c_uchar);
+    fn dsp_set_eq_precut(precut: c_int);
+    fn dsp_set_eq_coefs(band: c_int, setting: *const EqBandSetting);
     fn dsp_dither_enable(enable: c_uchar);
     fn dsp_get_timestretch() -> c_int;
     fn dsp_set_timestretch(percent: c_int);
@@ -1303,7 +1317,7 @@ extern "C" {
     fn semaphore_release();
     fn reset_poweroff_timer();
     fn set_sleeptimer_duration(minutes: c_int);
-    fn get_sleep_timer();
+    fn get_sleep_timer() -> c_int;
 
     // Menu
     fn root_menu_get_options();
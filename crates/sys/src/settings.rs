@@ -1,11 +1,37 @@
-use std::ffi::{c_char, c_int, c_uchar, c_void, CString};
+use std::{
+    ffi::{c_char, c_int, c_uchar, c_void, CString},
+    sync::RwLock,
+};
 
 use crate::{
     set_str_setting, set_value_setting,
-    types::user_settings::{NewGlobalSettings, UserSettings},
+    types::user_settings::{
+        NewGlobalSettings, ResumeSettings, TransitionMode, TransitionSettings, UserSettings,
+        DEFAULT_RESUME_MIN_LENGTH,
+    },
     EqBandSetting, OptItems, SettingsList, Viewport, EQ_NUM_BANDS, NB_SCREENS,
 };
 
+/// The firmware has no transition settings, they are kept here and saved to
+/// `settings.toml` with the others.
+static TRANSITION_SETTINGS: RwLock<TransitionSettings> = RwLock::new(TransitionSettings {
+    album: TransitionMode::Crossfade,
+    mix: TransitionMode::Crossfade,
+});
+
+pub fn get_transition_settings() -> TransitionSettings {
+    *TRANSITION_SETTINGS.read().unwrap()
+}
+
+static RESUME_SETTINGS: RwLock<ResumeSettings> = RwLock::new(ResumeSettings {
+    min_length: DEFAULT_RESUME_MIN_LENGTH,
+    directories: Vec::new(),
+});
+
+pub fn get_resume_settings() -> ResumeSettings {
+    RESUME_SETTINGS.read().unwrap().clone()
+}
+
 pub fn get_global_settings() -> UserSettings {
     unsafe {
         crate::rb_get_crossfade_mode();
@@ -237,6 +263,14 @@ pub fn save_settings(settings: NewGlobalSettings, from_disk: bool) {
             crate::global_settings.replaygain_settings = replaygain_settings.into();
         }
     }
+
+    if let Some(transitions) = settings.transitions {
+        *TRANSITION_SETTINGS.write().unwrap() = transitions;
+    }
+
+    if let Some(resume) = settings.resume {
+        *RESUME_SETTINGS.write().unwrap() = resume;
+    }
 }
 
 pub fn apply_settings(read_disk: bool) {
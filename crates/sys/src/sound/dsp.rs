@@ -1,4 +1,4 @@
-use crate::{DspBuffer, DspConfig};
+use crate::{types::user_settings::EqBandSetting, DspBuffer, DspConfig};
 
 pub fn set_crossfeed_type(r#type: i32) {
     unsafe { crate::dsp_set_crossfeed_type(r#type) }
@@ -9,6 +9,15 @@ pub fn eq_enable(enable: bool) {
     unsafe { crate::dsp_eq_enable(enable) }
 }
 
+pub fn set_eq_precut(precut: i32) {
+    unsafe { crate::dsp_set_eq_precut(precut) }
+}
+
+pub fn set_eq_coefs(band: i32, setting: EqBandSetting) {
+    let setting: crate::EqBandSetting = setting.into();
+    unsafe { crate::dsp_set_eq_coefs(band, &setting) }
+}
+
 pub fn dither_enable(enable: bool) {
     let enable = if enable { 1 } else { 0 };
     unsafe { crate::dsp_dither_enable(enable) }
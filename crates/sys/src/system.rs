@@ -121,8 +121,7 @@ pub fn set_sleeptimer_duration(minutes: i32) {
     }
 }
 
-pub fn get_sleep_timer() {
-    unsafe {
-        crate::get_sleep_timer();
-    }
+/// Seconds left on the firmware sleep timer, 0 when it is off.
+pub fn get_sleep_timer() -> i32 {
+    unsafe { crate::get_sleep_timer() }
 }
@@ -31,6 +31,61 @@ impl Into<crate::ReplaygainSettings> for ReplaygainSettings {
     }
 }
 
+/// How the server changes tracks, on top of the crossfade settings of the
+/// firmware.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransitionMode {
+    /// Leaves the transitions to the crossfade settings.
+    #[default]
+    Crossfade,
+    /// Never crossfades between consecutive tracks of an album and trims
+    /// the leading and trailing silence of the other tracks.
+    Smart,
+    /// Smart transitions, and the tempo of each track starts at the one of
+    /// the previous track using their BPM, then eases back. Requires
+    /// timestretch to be enabled.
+    BeatMatch,
+}
+
+/// The transition mode of the queues playing in order, like an album, and
+/// of the shuffled ones.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransitionSettings {
+    #[serde(default)]
+    pub album: TransitionMode,
+    #[serde(default)]
+    pub mix: TransitionMode,
+}
+
+/// Tracks at least this long, in seconds, are long-form by default.
+pub const DEFAULT_RESUME_MIN_LENGTH: u32 = 1200;
+
+/// Which tracks remember where they were stopped, besides `.m4b` audiobooks
+/// and the tracks of the audiobook and podcast genres.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResumeSettings {
+    /// Tracks at least this long, in seconds, 0 to disable.
+    #[serde(default = "default_resume_min_length")]
+    pub min_length: u32,
+    /// Audiobook and podcast directories, all their tracks are long-form.
+    #[serde(default)]
+    pub directories: Vec<String>,
+}
+
+impl Default for ResumeSettings {
+    fn default() -> Self {
+        Self {
+            min_length: DEFAULT_RESUME_MIN_LENGTH,
+            directories: vec![],
+        }
+    }
+}
+
+fn default_resume_min_length() -> u32 {
+    DEFAULT_RESUME_MIN_LENGTH
+}
+
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct EqBandSetting {
     pub cutoff: i32,
@@ -362,6 +417,9 @@ pub struct UserSettings {
 
     pub governor: i32,
     pub stereosw_mode: i32,
+
+    pub transitions: TransitionSettings,
+    pub resume: ResumeSettings,
 }
 
 impl From<crate::UserSettings> for UserSettings {
@@ -632,6 +690,8 @@ impl From<crate::UserSettings> for UserSettings {
             afr_enabled: settings.afr_enabled,
             governor: settings.governor,
             stereosw_mode: settings.stereosw_mode,
+            transitions: crate::settings::get_transition_settings(),
+            resume: crate::settings::get_resume_settings(),
         }
     }
 }
@@ -665,6 +725,8 @@ pub struct NewGlobalSettings {
     pub eq_enabled: Option<bool>,
     pub eq_band_settings: Option<Vec<EqBandSetting>>,
     pub replaygain_settings: Option<ReplaygainSettings>,
+    pub transitions: Option<TransitionSettings>,
+    pub resume: Option<ResumeSettings>,
 }
 
 impl From<UserSettings> for NewGlobalSettings {
@@ -697,6 +759,8 @@ impl From<UserSettings> for NewGlobalSettings {
             eq_enabled: Some(settings.eq_enabled),
             eq_band_settings: Some(settings.eq_band_settings),
             replaygain_settings: Some(settings.replaygain_settings),
+            transitions: Some(settings.transitions),
+            resume: Some(settings.resume),
         }
     }
 }
@@ -13,6 +13,8 @@ pub trait Player {
     async fn pause(&self) -> Result<(), Error>;
     async fn resume(&self) -> Result<(), Error>;
     async fn seek(&self, seconds: i32) -> Result<(), Error>;
+    /// Seek with millisecond precision, players that can't do better fall back to `seek`.
+    async fn seek_ms(&self, position_ms: u32) -> Result<(), Error>;
     async fn volume(&self, level: f32) -> Result<(), Error>;
     async fn load_tracks(&self, tracks: Vec<Track>, start_index: Option<i32>) -> Result<(), Error>;
     async fn play_next(&self, track: Track) -> Result<(), Error>;
@@ -0,0 +1,77 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// What a bookmark plays from its position, `source_id` being the path of
+/// the track, the album id or the path of the directory.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BookmarkSource {
+    #[default]
+    Track,
+    Album,
+    Directory,
+}
+
+impl BookmarkSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BookmarkSource::Track => "track",
+            BookmarkSource::Album => "album",
+            BookmarkSource::Directory => "directory",
+        }
+    }
+}
+
+impl fmt::Display for BookmarkSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for BookmarkSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "track" => Ok(BookmarkSource::Track),
+            "album" => Ok(BookmarkSource::Album),
+            "directory" => Ok(BookmarkSource::Directory),
+            _ => Err(format!("Unknown bookmark source: {}", s)),
+        }
+    }
+}
+
+/// A position in a track. Bookmarks without a name are the resume
+/// positions saved while long-form tracks play. `elapsed` is in
+/// milliseconds and times are unix timestamps in seconds.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub id: String,
+    pub name: Option<String>,
+    pub source: BookmarkSource,
+    pub source_id: String,
+    pub path: String,
+    pub track_id: Option<String>,
+    pub elapsed: u64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Bookmarks the track playing at its position, unless `path` and
+/// `elapsed` are given. The source defaults to the album of the track, or
+/// to its directory when it is not in the library.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct NewBookmark {
+    pub name: String,
+    pub path: Option<String>,
+    pub elapsed: Option<u64>,
+    pub source: Option<BookmarkSource>,
+}
+
+/// Changes the given values of a bookmark and keeps the others.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UpdateBookmark {
+    pub name: Option<String>,
+    pub elapsed: Option<u64>,
+}
@@ -14,7 +14,7 @@ pub const MUSIC_PLAYER_DEVICE: &str = "MusicPlayer";
 pub const UPNP_DLNA_DEVICE: &str = "UPnP/DLNA";
 pub const ROCKBOX_DEVICE: &str = "Rockbox";
 
-#[derive(Default, Clone, Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct Device {
     pub id: String,
     pub name: String,
@@ -35,10 +35,10 @@ impl Device {
         self.base_url = base_url;
         self.clone()
     }
-}
 
-impl From<ServiceInfo> for Device {
-    fn from(srv: ServiceInfo) -> Self {
+    /// The device advertised by a service, `current_device_id` is the id of
+    /// this instance.
+    pub fn from_service(srv: ServiceInfo, current_device_id: &str) -> Self {
         if srv.get_fullname().contains("xbmc") {
             return Self {
                 id: srv.get_fullname().to_owned(),
@@ -73,8 +73,7 @@ impl From<ServiceInfo> for Device {
                 .replace(".", "")
                 .to_owned();
 
-            let is_current_device = device_id == "device_id"
-                && srv.get_fullname().split("-").collect::<Vec<&str>>()[0].to_owned() == "http";
+            let is_current_device = device_id == current_device_id;
 
             let mut addresses = srv.get_addresses().iter();
             let mut ip = addresses.next().unwrap().to_string();
@@ -83,6 +82,11 @@ impl From<ServiceInfo> for Device {
                 ip = get_local_ip_address().unwrap();
             }
 
+            let base_url = srv
+                .get_properties()
+                .get("graphql_port")
+                .map(|port| format!("http://{}:{}", ip, port));
+
             return Self {
                 id: device_id.clone(),
                 name: srv
@@ -100,7 +104,7 @@ impl From<ServiceInfo> for Device {
                 service: srv.get_fullname().split("-").collect::<Vec<&str>>()[0].to_owned(),
                 app: "rockbox".to_owned(),
                 is_connected: false,
-                base_url: None,
+                base_url,
                 is_cast_device: true,
                 is_source_device: true,
                 is_current_device,
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::EqBandSetting;
+
+/// Bands of the firmware equalizer, the first one is a low shelf, the last
+/// one a high shelf and the others are peaking filters.
+pub const EQ_NUM_BANDS: usize = 10;
+
+pub const EQ_CUTOFF_MIN: i32 = 20;
+pub const EQ_CUTOFF_MAX: i32 = 22040;
+/// Gains are in tenths of dB.
+pub const EQ_GAIN_MIN: i32 = -240;
+pub const EQ_GAIN_MAX: i32 = 240;
+/// Q factors are in tenths.
+pub const EQ_Q_MIN: i32 = 1;
+pub const EQ_Q_MAX: i32 = 64;
+/// The precut lowers the volume before the equalizer, in tenths of dB.
+pub const EQ_PRECUT_MAX: i32 = 240;
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Equalizer {
+    pub enabled: bool,
+    pub precut: i32,
+    pub bands: Vec<EqBandSetting>,
+}
+
+/// Changes the given values of a band and keeps the others.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UpdateEqBand {
+    pub cutoff: Option<i32>,
+    pub q: Option<i32>,
+    pub gain: Option<i32>,
+}
+
+/// Changes the given values of the equalizer, `bands` replaces the first
+/// bands when given.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UpdateEqualizer {
+    pub enabled: Option<bool>,
+    pub precut: Option<i32>,
+    pub bands: Option<Vec<EqBandSetting>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EqPreset {
+    pub name: String,
+    #[serde(flatten)]
+    pub equalizer: Equalizer,
+}
+
+/// Saves the equalizer currently applied as a preset when `equalizer` is
+/// missing.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewEqPreset {
+    pub name: String,
+    pub equalizer: Option<Equalizer>,
+}
+
+/// An AutoEQ or Equalizer APO parametric profile, e.g. the content of an
+/// AutoEQ `ParametricEQ.txt` file.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportEqPreset {
+    pub name: String,
+    pub profile: String,
+}
+
+/// The presets saved on disk, `devices` maps device ids to the preset
+/// loaded when playback is sent to them.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct EqPresets {
+    pub presets: Vec<EqPreset>,
+    #[serde(default)]
+    pub devices: HashMap<String, String>,
+}
+
+/// Binds a preset to a device, or unbinds it when `preset` is missing.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BindEqPreset {
+    pub preset: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EqSlot {
+    #[default]
+    A,
+    B,
+}
+
+/// The A/B comparison, `slot` is the side being heard.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct EqComparison {
+    pub slot: EqSlot,
+    pub a: Equalizer,
+    pub b: Equalizer,
+}
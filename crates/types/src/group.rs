@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+pub const GROUP_DEVICE: &str = "Group";
+pub const LOCAL_DEVICE_ID: &str = "local";
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct GroupMember {
+    pub device_id: String,
+    /// Added to the group volume (0.0 - 1.0) before it is sent to this member.
+    #[serde(default)]
+    pub volume_offset: f32,
+    /// Output latency of the member, it is started this much earlier than the others.
+    #[serde(default)]
+    pub latency_ms: u32,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Group {
+    pub id: String,
+    pub name: String,
+    pub members: Vec<GroupMember>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewGroup {
+    pub name: String,
+    pub members: Vec<GroupMember>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateGroupMember {
+    pub volume_offset: Option<f32>,
+    pub latency_ms: Option<u32>,
+}
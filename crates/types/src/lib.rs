@@ -1,11 +1,19 @@
 use rockbox_search::artist::Artist;
 use rockbox_search::file::File;
+use rockbox_search::genre::Genre;
 use rockbox_search::liked_album::LikedAlbum;
 use rockbox_search::liked_track::LikedTrack;
-use rockbox_search::{album::Album, track::Track};
+use rockbox_search::query::Facets;
+use rockbox_search::{album::Album, track::Track, LibrarySearchResults};
 use serde::{Deserialize, Serialize};
 
+pub mod bookmark;
 pub mod device;
+pub mod dsp;
+pub mod group;
+pub mod podcast;
+pub mod radio;
+pub mod scheduler;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct NewPlaylist {
@@ -34,6 +42,19 @@ pub struct NewVolume {
     pub steps: i32,
 }
 
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PlaybackSpeed {
+    pub pitch: i32,
+    pub timestretch: i32,
+    pub timestretch_available: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewPlaybackSpeed {
+    /// Playback speed in `PITCH_SPEED_100` units, 10000 being the normal speed.
+    pub speed: i32,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DeleteTracks {
     pub positions: Vec<i32>,
@@ -52,9 +73,87 @@ pub struct SearchResults {
     pub liked_tracks: Vec<LikedTrack>,
     pub liked_albums: Vec<LikedAlbum>,
     pub files: Vec<File>,
+    #[serde(default)]
+    pub genres: Vec<Genre>,
+    #[serde(default)]
+    pub totals: SearchTotals,
+    #[serde(default)]
+    pub facets: Facets,
 }
 
+/// Number of matches for each kind of result, before pagination.
 #[derive(Default, Serialize, Deserialize)]
+pub struct SearchTotals {
+    pub artists: usize,
+    pub albums: usize,
+    pub tracks: usize,
+    pub liked_tracks: usize,
+    pub liked_albums: usize,
+    pub files: usize,
+    #[serde(default)]
+    pub genres: usize,
+}
+
+impl From<LibrarySearchResults> for SearchResults {
+    fn from(results: LibrarySearchResults) -> Self {
+        SearchResults {
+            totals: SearchTotals {
+                artists: results.artists.total,
+                albums: results.albums.total,
+                tracks: results.tracks.total,
+                liked_tracks: results.liked_tracks.total,
+                liked_albums: results.liked_albums.total,
+                files: results.files.total,
+                genres: results.genres.total,
+            },
+            facets: results.tracks.facets,
+            artists: results
+                .artists
+                .documents
+                .into_iter()
+                .map(|(_, x)| x.into())
+                .collect(),
+            albums: results
+                .albums
+                .documents
+                .into_iter()
+                .map(|(_, x)| x.into())
+                .collect(),
+            tracks: results
+                .tracks
+                .documents
+                .into_iter()
+                .map(|(_, x)| x.into())
+                .collect(),
+            liked_tracks: results
+                .liked_tracks
+                .documents
+                .into_iter()
+                .map(|(_, x)| x.into())
+                .collect(),
+            liked_albums: results
+                .liked_albums
+                .documents
+                .into_iter()
+                .map(|(_, x)| x.into())
+                .collect(),
+            files: results
+                .files
+                .documents
+                .into_iter()
+                .map(|(_, x)| x.into())
+                .collect(),
+            genres: results
+                .genres
+                .documents
+                .into_iter()
+                .map(|(_, x)| x.into())
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EqBandSetting {
     pub cutoff: i32,
     pub q: i32,
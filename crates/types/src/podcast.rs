@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+/// A subscribed feed. The newest `auto_download` episodes are downloaded
+/// when the feed is polled, and the played ones are deleted when
+/// `delete_played` is set. Times are unix timestamps in seconds.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Podcast {
+    pub id: String,
+    pub url: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub author: Option<String>,
+    pub image: Option<String>,
+    pub link: Option<String>,
+    pub auto_download: u32,
+    pub delete_played: bool,
+    pub last_polled_at: Option<i64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// An episode of a podcast, `path` is set once it is downloaded and
+/// `duration` is in seconds.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Episode {
+    pub id: String,
+    pub podcast_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub url: String,
+    pub mime_type: Option<String>,
+    pub duration: Option<u32>,
+    pub published_at: Option<i64>,
+    pub path: Option<String>,
+    pub played: bool,
+    pub played_at: Option<i64>,
+}
+
+/// Subscribes to the feed at `url`, downloading its 3 newest episodes and
+/// deleting them once played unless told otherwise.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct NewPodcast {
+    pub url: String,
+    pub auto_download: Option<u32>,
+    pub delete_played: Option<bool>,
+}
+
+/// Changes the given values of a podcast and keeps the others.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UpdatePodcast {
+    pub auto_download: Option<u32>,
+    pub delete_played: Option<bool>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct EpisodePlayed {
+    pub played: bool,
+}
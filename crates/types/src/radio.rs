@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+/// An internet radio station, `url` is its stream or its PLS or M3U
+/// playlist. Times are unix timestamps in seconds.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Station {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    pub genre: Option<String>,
+    pub homepage: Option<String>,
+    pub image: Option<String>,
+    pub favourite: bool,
+    pub last_played_at: Option<i64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Adds a station, named after its `icy-name` header when `name` is not
+/// given.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct NewStation {
+    pub url: String,
+    pub name: Option<String>,
+    pub genre: Option<String>,
+    pub homepage: Option<String>,
+    pub image: Option<String>,
+    pub favourite: Option<bool>,
+}
+
+/// Changes the given values of a station and keeps the others.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UpdateStation {
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub genre: Option<String>,
+    pub homepage: Option<String>,
+    pub image: Option<String>,
+    pub favourite: Option<bool>,
+}
+
+/// The station playing and the title of its stream, split into artist and
+/// title when the station sends `Artist - Title`.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NowPlaying {
+    pub station: Station,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+}
@@ -0,0 +1,145 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// Seconds the volume takes to fade out when the sleep timer ends, unless
+/// given.
+pub const DEFAULT_FADE_OUT: u32 = 30;
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SleepTimer {
+    pub active: bool,
+    /// The duration the timer was set to, in seconds.
+    pub duration: u32,
+    /// Seconds left before playback is paused.
+    pub remaining: u32,
+    /// Seconds the volume fades out before the end.
+    pub fade_out: u32,
+}
+
+/// Starts the sleep timer, replacing the running one. `duration` and
+/// `fade_out` are in seconds.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct NewSleepTimer {
+    pub duration: u32,
+    pub fade_out: Option<u32>,
+}
+
+/// What a schedule plays, `source_id` identifies the playlist, album,
+/// artist, directory or genre.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaybackSource {
+    #[default]
+    Playlist,
+    Album,
+    Artist,
+    Directory,
+    LikedTracks,
+    AllTracks,
+    /// Random tracks of the genre `source_id`, or of the whole library.
+    Mix,
+}
+
+impl PlaybackSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PlaybackSource::Playlist => "playlist",
+            PlaybackSource::Album => "album",
+            PlaybackSource::Artist => "artist",
+            PlaybackSource::Directory => "directory",
+            PlaybackSource::LikedTracks => "liked_tracks",
+            PlaybackSource::AllTracks => "all_tracks",
+            PlaybackSource::Mix => "mix",
+        }
+    }
+
+    /// Whether `source_id` must be given.
+    pub fn requires_id(&self) -> bool {
+        matches!(
+            self,
+            PlaybackSource::Playlist
+                | PlaybackSource::Album
+                | PlaybackSource::Artist
+                | PlaybackSource::Directory
+        )
+    }
+}
+
+impl fmt::Display for PlaybackSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PlaybackSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "playlist" => Ok(PlaybackSource::Playlist),
+            "album" => Ok(PlaybackSource::Album),
+            "artist" => Ok(PlaybackSource::Artist),
+            "directory" => Ok(PlaybackSource::Directory),
+            "liked_tracks" => Ok(PlaybackSource::LikedTracks),
+            "all_tracks" => Ok(PlaybackSource::AllTracks),
+            "mix" => Ok(PlaybackSource::Mix),
+            _ => Err(format!("Unknown playback source: {}", s)),
+        }
+    }
+}
+
+/// Starts playing a source when its cron expression matches, an alarm is a
+/// schedule with a fade-in. Times are unix timestamps in seconds.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Schedule {
+    pub id: String,
+    pub name: String,
+    /// `minute hour day-of-month month day-of-week` in local time, e.g.
+    /// `30 6 * * mon-fri`.
+    pub cron: String,
+    pub source: PlaybackSource,
+    pub source_id: Option<String>,
+    pub shuffle: bool,
+    /// The volume set before playing, in dB.
+    pub volume: Option<i32>,
+    /// Seconds the volume takes to rise from silence.
+    pub fade_in: u32,
+    /// Disables the schedule once it ran.
+    pub once: bool,
+    pub enabled: bool,
+    pub last_run_at: Option<i64>,
+    /// None when disabled or when the expression never matches.
+    pub next_run_at: Option<i64>,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct NewSchedule {
+    pub name: String,
+    pub cron: String,
+    pub source: PlaybackSource,
+    pub source_id: Option<String>,
+    #[serde(default)]
+    pub shuffle: bool,
+    pub volume: Option<i32>,
+    #[serde(default)]
+    pub fade_in: u32,
+    #[serde(default)]
+    pub once: bool,
+    pub enabled: Option<bool>,
+}
+
+/// Changes the given values of a schedule and keeps the others.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UpdateSchedule {
+    pub name: Option<String>,
+    pub cron: Option<String>,
+    pub source: Option<PlaybackSource>,
+    pub source_id: Option<String>,
+    pub shuffle: Option<bool>,
+    pub volume: Option<i32>,
+    pub fade_in: Option<u32>,
+    pub once: Option<bool>,
+    pub enabled: Option<bool>,
+}
@@ -28,6 +28,7 @@
 #include "metadata.h"
 
 #include "metadata_parsers.h"
+#include "replaygain.h"
 
 /* For trailing tag stripping and base audio data types */
 #include "buffering.h"
@@ -414,6 +415,29 @@ unsigned int probe_file_format(const char *filename)
     return AFMT_UNKNOWN;
 }
 
+/* Provided by the library of the Rust server: the ReplayGain values measured
+ * for a file without ReplayGain tags. Gains are in dB multiplied by 512 and
+ * peaks in Q7.24, as parse_replaygain_int() expects them. */
+extern bool rb_stored_replaygain(const char *path, long *track_gain,
+                                 long *track_peak, long *album_gain,
+                                 long *album_peak) __attribute__((weak));
+
+static void apply_stored_replaygain(struct mp3entry *id3, const char *trackname)
+{
+    long track_gain, track_peak, album_gain, album_peak;
+
+    if (!rb_stored_replaygain || id3->track_gain || id3->album_gain)
+        return;
+
+    if (!rb_stored_replaygain(trackname, &track_gain, &track_peak,
+                              &album_gain, &album_peak))
+        return;
+
+    parse_replaygain_int(false, track_gain, track_peak, id3);
+    if (album_gain)
+        parse_replaygain_int(true, album_gain, album_peak, id3);
+}
+
 /* Get metadata for track - return false if parsing showed problems with the
  * file that would prevent playback. supply a filedescriptor <0 and the file will be opened
  * and closed automatically within the get_metadata call
@@ -472,6 +496,10 @@ bool get_metadata_ex(struct mp3entry* id3, int fd, const char* trackname, int fl
         success = false;
         wipe_mp3entry(id3); /* ensure the mp3entry is clear */
     }
+    else
+    {
+        apply_stored_replaygain(id3, trackname);
+    }
 
     if ((flags & METADATA_CLOSE_FD_ON_EXIT))
         close(fd);